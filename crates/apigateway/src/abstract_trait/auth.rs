@@ -3,7 +3,10 @@ use crate::domain::{
         auth::{AuthRequest, RegisterRequest},
         reset_token::CreateResetPasswordRequest,
     },
-    response::{api::ApiResponse, token::TokenResponse, user::UserResponse},
+    response::{
+        api::ApiResponse, lockout::LockoutStatusResponse, login_session::LoginSessionResponse,
+        token::TokenResponse, user::UserResponse,
+    },
 };
 
 use anyhow::Result;
@@ -25,10 +28,28 @@ pub trait AuthGrpcClientTrait {
     ) -> Result<ApiResponse<TokenResponse>, HttpError>;
     async fn get_me(&self, id: i32) -> Result<ApiResponse<UserResponse>, HttpError>;
     async fn forgot(&self, email: &str) -> Result<ApiResponse<bool>, HttpError>;
+    async fn resend_verification(&self, email: &str) -> Result<ApiResponse<bool>, HttpError>;
     async fn reset_password(
         &self,
         request: &CreateResetPasswordRequest,
     ) -> Result<ApiResponse<bool>, HttpError>;
     async fn verify_code(&self, code: &str) -> Result<ApiResponse<bool>, HttpError>;
     async fn refresh_token(&self, token: &str) -> Result<ApiResponse<TokenResponse>, HttpError>;
+    async fn get_lockout_status(
+        &self,
+        user_id: i32,
+    ) -> Result<ApiResponse<LockoutStatusResponse>, HttpError>;
+    async fn unlock_account(
+        &self,
+        user_id: i32,
+    ) -> Result<ApiResponse<LockoutStatusResponse>, HttpError>;
+    async fn list_sessions(
+        &self,
+        user_id: i32,
+    ) -> Result<ApiResponse<Vec<LoginSessionResponse>>, HttpError>;
+    async fn revoke_session(
+        &self,
+        user_id: i32,
+        session_id: i32,
+    ) -> Result<ApiResponse<bool>, HttpError>;
 }
@@ -0,0 +1,10 @@
+use crate::domain::response::health::ServicesHealthResponse;
+use async_trait::async_trait;
+use std::sync::Arc;
+
+pub type DynHealthGrpcClient = Arc<dyn HealthGrpcClientTrait + Send + Sync>;
+
+#[async_trait]
+pub trait HealthGrpcClientTrait {
+    async fn check_all(&self) -> ServicesHealthResponse;
+}
@@ -1,4 +1,5 @@
 pub mod auth;
+pub mod health;
 pub mod order;
 pub mod order_item;
 pub mod product;
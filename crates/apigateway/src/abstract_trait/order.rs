@@ -1,14 +1,15 @@
 use crate::domain::{
-    requests::order::{CreateOrderRequest, FindAllOrder, UpdateOrderRequest},
+    requests::order::{CreateOrderRequest, FindAllOrder, FindUserOrders, UpdateOrderRequest},
     response::{
         api::{ApiResponse, ApiResponsePagination},
-        order::{OrderResponse, OrderResponseDeleteAt},
+        order::{OrderDetailResponse, OrderResponse, OrderResponseDeleteAt},
     },
 };
 use anyhow::Result;
 use async_trait::async_trait;
 use shared::errors::HttpError;
 use std::sync::Arc;
+use uuid::Uuid;
 
 pub type DynOrderGrpcClient = Arc<dyn OrderGrpcClientTrait + Send + Sync>;
 
@@ -26,7 +27,20 @@ pub trait OrderGrpcClientTrait {
         &self,
         req: &FindAllOrder,
     ) -> Result<ApiResponsePagination<Vec<OrderResponseDeleteAt>>, HttpError>;
+    async fn find_by_user_id(
+        &self,
+        user_id: i32,
+        req: &FindUserOrders,
+    ) -> Result<ApiResponsePagination<Vec<OrderResponse>>, HttpError>;
     async fn find_by_id(&self, id: i32) -> Result<ApiResponse<OrderResponse>, HttpError>;
+    async fn find_detail_by_id(
+        &self,
+        id: i32,
+    ) -> Result<ApiResponse<OrderDetailResponse>, HttpError>;
+    async fn find_by_external_id(
+        &self,
+        external_id: Uuid,
+    ) -> Result<ApiResponse<OrderResponse>, HttpError>;
     async fn create_order(
         &self,
         req: &CreateOrderRequest,
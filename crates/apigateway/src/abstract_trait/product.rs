@@ -7,8 +7,9 @@ use crate::domain::{
 };
 use anyhow::Result;
 use async_trait::async_trait;
-use shared::errors::HttpError;
+use shared::{errors::HttpError, utils::BatchResponse};
 use std::sync::Arc;
+use uuid::Uuid;
 
 pub type DynProductGrpcClient = Arc<dyn ProductGrpcClientTrait + Send + Sync>;
 
@@ -26,7 +27,15 @@ pub trait ProductGrpcClientTrait {
         &self,
         req: &FindAllProducts,
     ) -> Result<ApiResponsePagination<Vec<ProductResponseDeleteAt>>, HttpError>;
+    async fn find_low_stock(
+        &self,
+        req: &FindAllProducts,
+    ) -> Result<ApiResponsePagination<Vec<ProductResponse>>, HttpError>;
     async fn find_by_id(&self, id: i32) -> Result<ApiResponse<ProductResponse>, HttpError>;
+    async fn find_by_external_id(
+        &self,
+        external_id: Uuid,
+    ) -> Result<ApiResponse<ProductResponse>, HttpError>;
     async fn create_product(
         &self,
         req: &CreateProductRequest,
@@ -46,4 +55,10 @@ pub trait ProductGrpcClientTrait {
     async fn delete_product(&self, id: i32) -> Result<ApiResponse<()>, HttpError>;
     async fn restore_all_product(&self) -> Result<ApiResponse<()>, HttpError>;
     async fn delete_all_product(&self) -> Result<ApiResponse<()>, HttpError>;
+    /// Streams `csv` to the product service in chunks.
+    async fn import_products(
+        &self,
+        csv: Vec<u8>,
+        strict: bool,
+    ) -> Result<ApiResponse<BatchResponse<i32>>, HttpError>;
 }
@@ -1,3 +1,4 @@
+use crate::domain::response::rate_limit::RateLimitStatus;
 use async_trait::async_trait;
 use std::sync::Arc;
 
@@ -5,12 +6,21 @@ pub type DynRateLimitMiddleware = Arc<dyn RateLimitMiddlewareTrait + Send + Sync
 
 #[async_trait]
 pub trait RateLimitMiddlewareTrait {
+    /// Debits `cost` from the window's quota; a request is rejected when the
+    /// remaining quota can't cover its cost.
     async fn check_rate_limit(
         &self,
         key: &str,
+        cost: u32,
         max_requests: u32,
         window_seconds: u32,
-    ) -> (bool, u32);
+    ) -> (bool, RateLimitStatus);
+    async fn get_status(
+        &self,
+        key: &str,
+        max_requests: u32,
+        window_seconds: u32,
+    ) -> RateLimitStatus;
     async fn get_remaining(&self, key: &str, max_requests: u32) -> u32;
     async fn reset_limit(&self, key: &str) -> bool;
 }
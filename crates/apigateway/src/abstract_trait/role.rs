@@ -9,6 +9,7 @@ use anyhow::Result;
 use async_trait::async_trait;
 use shared::errors::HttpError;
 use std::sync::Arc;
+use uuid::Uuid;
 
 pub type DynRoleGrpcClient = Arc<dyn RoleGrpcClientTrait + Send + Sync>;
 
@@ -27,6 +28,10 @@ pub trait RoleGrpcClientTrait {
         req: &FindAllRole,
     ) -> Result<ApiResponsePagination<Vec<RoleResponseDeleteAt>>, HttpError>;
     async fn find_by_id(&self, id: i32) -> Result<ApiResponse<RoleResponse>, HttpError>;
+    async fn find_by_external_id(
+        &self,
+        external_id: Uuid,
+    ) -> Result<ApiResponse<RoleResponse>, HttpError>;
     async fn find_by_user_id(
         &self,
         user_id: i32,
@@ -9,6 +9,7 @@ use anyhow::Result;
 use async_trait::async_trait;
 use shared::errors::HttpError;
 use std::sync::Arc;
+use uuid::Uuid;
 
 pub type DynUserGrpcClient = Arc<dyn UserGrpcClientTrait + Send + Sync>;
 
@@ -27,10 +28,15 @@ pub trait UserGrpcClientTrait {
         req: &FindAllUsers,
     ) -> Result<ApiResponsePagination<Vec<UserResponseDeleteAt>>, HttpError>;
     async fn find_by_id(&self, id: i32) -> Result<ApiResponse<UserResponse>, HttpError>;
+    async fn find_by_external_id(
+        &self,
+        external_id: Uuid,
+    ) -> Result<ApiResponse<UserResponse>, HttpError>;
     async fn update_user(
         &self,
         req: &UpdateUserRequest,
     ) -> Result<ApiResponse<UserResponse>, HttpError>;
+    async fn anonymize_user(&self, id: i32) -> Result<ApiResponse<UserResponse>, HttpError>;
     async fn trash_user(&self, id: i32) -> Result<ApiResponse<UserResponseDeleteAt>, HttpError>;
     async fn restore_user(&self, id: i32) -> Result<ApiResponse<UserResponseDeleteAt>, HttpError>;
     async fn delete_user(&self, id: i32) -> Result<ApiResponse<()>, HttpError>;
@@ -1,19 +1,83 @@
-use crate::abstract_trait::rate_limit::RateLimitMiddlewareTrait;
+use crate::{
+    abstract_trait::rate_limit::RateLimitMiddlewareTrait,
+    domain::response::rate_limit::RateLimitStatus,
+};
 use async_trait::async_trait;
-use deadpool_redis::{Connection, Pool};
+use deadpool_redis::{Connection, Pool, redis::Script};
 use std::sync::Arc;
-use tracing::{debug, error};
+use tracing::{debug, error, warn};
+
+/// Debits `ARGV[1]` from the window at `KEYS[1]` only if the count stays
+/// within `ARGV[2]`, and returns `{allowed, count, ttl}`. EXPIRE is only set
+/// when the key has none, so the window resets at a fixed time instead of
+/// sliding forward with every request, and a counter that lost its expiry
+/// is repaired instead of throttling its client forever.
+const CHECK_AND_DEBIT: &str = r#"
+local count = tonumber(redis.call('GET', KEYS[1]) or '0')
+local cost = tonumber(ARGV[1])
+local allowed = 0
+if count + cost <= tonumber(ARGV[2]) then
+    count = redis.call('INCRBY', KEYS[1], cost)
+    allowed = 1
+end
+local ttl = redis.call('TTL', KEYS[1])
+if ttl == -1 then
+    redis.call('EXPIRE', KEYS[1], ARGV[3])
+    ttl = tonumber(ARGV[3])
+end
+return {allowed, count, ttl}
+"#;
 
 #[derive(Clone)]
 pub struct RateLimiter {
     redis_pool: Arc<Pool>,
+    debit_script: Script,
 }
 
 impl RateLimiter {
     pub fn new(redis_pool: Pool) -> Self {
         Self {
             redis_pool: Arc::new(redis_pool),
+            debit_script: Script::new(CHECK_AND_DEBIT),
+        }
+    }
+
+    /// Current count and seconds left in the window; a missing key reports the
+    /// full window. A counter without an expiry (left behind when an EXPIRE
+    /// was lost) gets one, so it can't throttle its client forever.
+    async fn read_window(
+        &self,
+        conn: &mut Connection,
+        key: &str,
+        window_seconds: u32,
+    ) -> (u32, u64) {
+        let (current, ttl): (Option<u32>, i64) = deadpool_redis::redis::pipe()
+            .cmd("GET")
+            .arg(key)
+            .cmd("TTL")
+            .arg(key)
+            .query_async(conn)
+            .await
+            .unwrap_or((None, -2));
+
+        if ttl == -1 {
+            warn!("Rate limit counter {key} had no expiry, starting a new window");
+            let _: () = deadpool_redis::redis::cmd("EXPIRE")
+                .arg(key)
+                .arg(window_seconds)
+                .arg("NX")
+                .query_async(conn)
+                .await
+                .unwrap_or(());
         }
+
+        let ttl = if ttl > 0 {
+            ttl as u64
+        } else {
+            window_seconds as u64
+        };
+
+        (current.unwrap_or(0), ttl)
     }
 
     async fn get_conn(&self) -> Option<Connection> {
@@ -32,42 +96,65 @@ impl RateLimitMiddlewareTrait for RateLimiter {
     async fn check_rate_limit(
         &self,
         key: &str,
+        cost: u32,
         max_requests: u32,
         window_seconds: u32,
-    ) -> (bool, u32) {
+    ) -> (bool, RateLimitStatus) {
         let mut conn = match self.get_conn().await {
             Some(conn) => conn,
-            None => return (false, 0),
+            None => {
+                return (
+                    false,
+                    RateLimitStatus::new(max_requests, max_requests, window_seconds as u64),
+                );
+            }
         };
 
-        let current: u32 = match deadpool_redis::redis::cmd("GET")
-            .arg(key)
-            .query_async(&mut conn)
+        // The check and the debit run as one script, so concurrent requests
+        // can't all read the same count and each slip in under the limit.
+        let (allowed, count, ttl): (bool, u32, i64) = match self
+            .debit_script
+            .key(key)
+            .arg(cost)
+            .arg(max_requests)
+            .arg(window_seconds)
+            .invoke_async(&mut conn)
             .await
         {
-            Ok(Some(val)) => val,
-            _ => 0u32,
+            Ok(result) => result,
+            Err(e) => {
+                error!("Failed to check rate limit for key {key}: {:?}", e);
+                (true, cost, -2)
+            }
         };
 
-        if current >= max_requests {
+        let ttl = if ttl > 0 {
+            ttl as u64
+        } else {
+            window_seconds as u64
+        };
+
+        if !allowed {
             debug!("Rate limit exceeded for key: {key}");
-            return (false, current);
         }
 
-        let _: () = deadpool_redis::redis::pipe()
-            .atomic()
-            .cmd("INCR")
-            .arg(key)
-            .ignore()
-            .cmd("EXPIRE")
-            .arg(key)
-            .arg(window_seconds)
-            .ignore()
-            .query_async(&mut conn)
-            .await
-            .unwrap_or(());
+        (allowed, RateLimitStatus::new(max_requests, count, ttl))
+    }
+
+    async fn get_status(
+        &self,
+        key: &str,
+        max_requests: u32,
+        window_seconds: u32,
+    ) -> RateLimitStatus {
+        let mut conn = match self.get_conn().await {
+            Some(conn) => conn,
+            None => return RateLimitStatus::new(max_requests, 0, window_seconds as u64),
+        };
+
+        let (current, ttl) = self.read_window(&mut conn, key, window_seconds).await;
 
-        (true, current + 1)
+        RateLimitStatus::new(max_requests, current, ttl)
     }
 
     async fn get_remaining(&self, key: &str, max_requests: u32) -> u32 {
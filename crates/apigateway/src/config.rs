@@ -1,4 +1,14 @@
 use anyhow::{Context, Result, anyhow};
+use axum::http::Method;
+use chrono::NaiveDate;
+use chrono_tz::Tz;
+use shared::{
+    config::{PreviousJwtKey, client_tls_from_env},
+    errors::HttpError,
+    utils::{Locale, Money, format_money, slow_request_threshold_from_env},
+};
+use std::{net::IpAddr, time::Duration};
+use tonic::transport::ClientTlsConfig;
 
 #[derive(Clone)]
 pub struct GrpcClientConfig {
@@ -7,6 +17,8 @@ pub struct GrpcClientConfig {
     pub role: String,
     pub product: String,
     pub order: String,
+    /// `None` calls the services over plaintext.
+    pub tls: Option<ClientTlsConfig>,
 }
 
 impl GrpcClientConfig {
@@ -32,10 +44,604 @@ impl GrpcClientConfig {
             role,
             product,
             order,
+            tls: client_tls_from_env()?,
         })
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct RedactionConfig {
+    pub fields: Vec<String>,
+    pub exempt_roles: Vec<String>,
+}
+
+impl RedactionConfig {
+    pub fn init() -> Self {
+        let fields = std::env::var("REDACTED_USER_FIELDS").unwrap_or_else(|_| "email".to_string());
+
+        let exempt_roles =
+            std::env::var("REDACTION_EXEMPT_ROLES").unwrap_or_else(|_| "ROLE_ADMIN".to_string());
+
+        Self {
+            fields: split_list(&fields),
+            exempt_roles: split_list(&exempt_roles),
+        }
+    }
+
+    pub fn should_redact(&self, field: &str, roles: &[String]) -> bool {
+        self.fields.iter().any(|f| f == field)
+            && !roles.iter().any(|r| self.exempt_roles.contains(r))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CurrencyConfig {
+    pub default_currency: String,
+}
+
+impl CurrencyConfig {
+    pub fn init() -> Self {
+        let default_currency = std::env::var("DEFAULT_CURRENCY")
+            .unwrap_or_else(|_| "IDR".to_string())
+            .trim()
+            .to_ascii_uppercase();
+
+        Self { default_currency }
+    }
+
+    pub fn format(&self, amount: i64, accept_language: Option<&str>) -> Money {
+        format_money(
+            amount,
+            &self.default_currency,
+            &Locale::from_accept_language(accept_language),
+        )
+    }
+}
+
+/// Timezone response timestamps are rendered in. Storage, gRPC and the
+/// response cache stay in UTC; only the offset shown to clients changes.
+#[derive(Debug, Clone, Copy)]
+pub struct DisplayTimezoneConfig {
+    pub default_timezone: Tz,
+}
+
+impl DisplayTimezoneConfig {
+    pub fn init() -> Result<Self> {
+        let raw = std::env::var("DISPLAY_TIMEZONE").unwrap_or_else(|_| "UTC".to_string());
+
+        let default_timezone = raw
+            .trim()
+            .parse::<Tz>()
+            .map_err(|e| anyhow!("DISPLAY_TIMEZONE must be an IANA timezone name: {e}"))?;
+
+        Ok(Self { default_timezone })
+    }
+
+    /// An `Accept-Timezone` header such as `Asia/Jakarta` wins over the
+    /// configured default; an unknown name is an error, not a silent fallback.
+    pub fn resolve(&self, accept_timezone: Option<&str>) -> Result<Tz, String> {
+        match accept_timezone.map(str::trim).filter(|v| !v.is_empty()) {
+            Some(name) => name
+                .parse::<Tz>()
+                .map_err(|_| format!("Unknown timezone '{name}' in Accept-Timezone")),
+            None => Ok(self.default_timezone),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RequestBudgetConfig {
+    pub budget: Duration,
+}
+
+impl RequestBudgetConfig {
+    pub fn init() -> Self {
+        let budget_ms = std::env::var("REQUEST_TIMEOUT_BUDGET_MS")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+            .filter(|v| *v > 0)
+            .unwrap_or(10_000);
+
+        Self {
+            budget: Duration::from_millis(budget_ms),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SlowRequestConfig {
+    /// Requests taking longer are logged as slow; `None` disables the log.
+    pub threshold: Option<Duration>,
+}
+
+impl SlowRequestConfig {
+    pub fn init() -> Self {
+        Self {
+            threshold: slow_request_threshold_from_env(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MetricsScrapeConfig {
+    /// `(service, url)` pairs; empty disables `/metrics/all`.
+    pub targets: Vec<(String, String)>,
+    pub timeout: Duration,
+}
+
+impl MetricsScrapeConfig {
+    /// Reads `METRICS_SCRAPE_TARGETS` as `name=url` pairs separated by commas,
+    /// e.g. `auth-service=http://auth:8080/metrics`.
+    pub fn init() -> Result<Self> {
+        let raw = std::env::var("METRICS_SCRAPE_TARGETS").unwrap_or_default();
+
+        let targets = split_list(&raw)
+            .into_iter()
+            .map(|entry| {
+                let (name, url) = entry.split_once('=').ok_or_else(|| {
+                    anyhow!("METRICS_SCRAPE_TARGETS entries must be 'name=url', got '{entry}'")
+                })?;
+                Ok((name.trim().to_string(), url.trim().to_string()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let timeout_ms = std::env::var("METRICS_SCRAPE_TIMEOUT_MS")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+            .filter(|v| *v > 0)
+            .unwrap_or(2_000);
+
+        Ok(Self {
+            targets,
+            timeout: Duration::from_millis(timeout_ms),
+        })
+    }
+}
+
+/// Demo deployments set `READ_ONLY_MODE` to reject every mutating request
+/// while reads keep working.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ReadOnlyConfig {
+    pub enabled: bool,
+}
+
+impl ReadOnlyConfig {
+    /// POSTs that store nothing shared: signing in and refreshing only touch
+    /// the caller's own session, and an email preview just renders a
+    /// template. Visitors can still sign in and browse.
+    const ALLOWED_WRITES: &[&str] = &[
+        "/api/auth/login",
+        "/api/auth/refresh",
+        "/api/admin/email/preview",
+    ];
+
+    pub fn init() -> Self {
+        let enabled = std::env::var("READ_ONLY_MODE")
+            .map(|v| v == "true" || v == "1")
+            .unwrap_or(false);
+
+        Self { enabled }
+    }
+
+    pub fn rejects(&self, method: &Method, path: &str) -> bool {
+        self.enabled
+            && !matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+            && !Self::ALLOWED_WRITES.contains(&path)
+    }
+}
+
+/// Deleting or trashing something that is already gone succeeds with
+/// `204 No Content` by default, so a retried DELETE doesn't turn into an
+/// error. `STRICT_DELETE` keeps the `404` instead.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DeleteSemanticsConfig {
+    pub strict: bool,
+}
+
+impl DeleteSemanticsConfig {
+    pub fn init() -> Self {
+        let strict = std::env::var("STRICT_DELETE")
+            .map(|v| v == "true" || v == "1")
+            .unwrap_or(false);
+
+        Self { strict }
+    }
+
+    /// `Ok(None)` when the entity was already gone and that isn't an error.
+    pub fn absorb_missing<T>(&self, result: Result<T, HttpError>) -> Result<Option<T>, HttpError> {
+        match result {
+            Ok(value) => Ok(Some(value)),
+            Err(HttpError::NotFound(_)) if !self.strict => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+/// Request bodies ignore fields their DTO doesn't know by default.
+/// `STRICT_JSON_FIELDS` rejects them with a `422` naming each one instead, so a
+/// typo'd field name isn't silently dropped.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonBodyConfig {
+    pub strict: bool,
+}
+
+impl JsonBodyConfig {
+    pub fn init() -> Self {
+        let strict = std::env::var("STRICT_JSON_FIELDS")
+            .map(|v| v == "true" || v == "1")
+            .unwrap_or(false);
+
+        Self { strict }
+    }
+}
+
+/// Production deployments set `ENFORCE_HTTPS` so plain-http requests are
+/// redirected and cookies are never sent without `Secure`. TLS ends at the
+/// proxy, so only `X-Forwarded-Proto` from `TRUSTED_PROXIES` is believed;
+/// anyone else connecting directly is on plain http.
+#[derive(Debug, Clone, Default)]
+pub struct HttpsConfig {
+    pub enabled: bool,
+    pub trusted_proxies: Vec<IpAddr>,
+}
+
+impl HttpsConfig {
+    pub fn init() -> Result<Self> {
+        let enabled = std::env::var("ENFORCE_HTTPS")
+            .map(|v| v == "true" || v == "1")
+            .unwrap_or(false);
+
+        let trusted_proxies = split_list(&std::env::var("TRUSTED_PROXIES").unwrap_or_default())
+            .into_iter()
+            .map(|ip| {
+                ip.parse::<IpAddr>()
+                    .with_context(|| format!("TRUSTED_PROXIES must list IP addresses, got '{ip}'"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            enabled,
+            trusted_proxies,
+        })
+    }
+
+    /// `peer` is the connecting socket's address, `forwarded_proto` its
+    /// `X-Forwarded-Proto` header.
+    pub fn is_secure(&self, peer: Option<IpAddr>, forwarded_proto: Option<&str>) -> bool {
+        peer.is_some_and(|ip| self.trusted_proxies.contains(&ip))
+            && forwarded_proto
+                .and_then(|proto| proto.split(',').next())
+                .is_some_and(|proto| proto.trim().eq_ignore_ascii_case("https"))
+    }
+}
+
+/// Quota debited per request by the rate limiter. Routes not listed cost 1,
+/// so leaving `RATE_LIMIT_ROUTE_COSTS` empty keeps a plain request count.
+#[derive(Debug, Clone, Default)]
+pub struct RateLimitCostConfig {
+    /// `(method, route, cost)`; a `None` method matches any method.
+    costs: Vec<(Option<String>, String, u32)>,
+}
+
+impl RateLimitCostConfig {
+    pub const DEFAULT_COST: u32 = 1;
+
+    /// Reads `RATE_LIMIT_ROUTE_COSTS` as comma separated `[METHOD ]route=cost`
+    /// entries, where `route` is the path as registered on the router, e.g.
+    /// `POST /api/orders=5,/api/products/{id}=2`. Routes are given without
+    /// `BASE_PATH`, which is prepended to match the nested router.
+    pub fn init() -> Result<Self> {
+        Self::parse(
+            &std::env::var("RATE_LIMIT_ROUTE_COSTS").unwrap_or_default(),
+            &std::env::var("BASE_PATH").unwrap_or_default(),
+        )
+    }
+
+    pub fn parse(raw: &str, base_path: &str) -> Result<Self> {
+        let base_path = normalize_base_path(base_path);
+
+        let costs = split_list(raw)
+            .into_iter()
+            .map(|entry| {
+                let (route, cost) = entry.rsplit_once('=').ok_or_else(|| {
+                    anyhow!("RATE_LIMIT_ROUTE_COSTS entries must be 'route=cost', got '{entry}'")
+                })?;
+
+                let cost = cost.trim().parse::<u32>().with_context(|| {
+                    format!("RATE_LIMIT_ROUTE_COSTS cost must be a valid u32, got '{entry}'")
+                })?;
+
+                let (method, path) = match route.trim().split_once(' ') {
+                    Some((method, path)) => (Some(method.to_ascii_uppercase()), path.trim()),
+                    None => (None, route.trim()),
+                };
+
+                Ok((method, format!("{base_path}{path}"), cost))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self { costs })
+    }
+
+    /// A method-specific entry wins over one that matches any method.
+    pub fn cost_for(&self, method: &str, route: &str) -> u32 {
+        let matching = |want_method: bool| {
+            self.costs.iter().find(|(m, path, _)| {
+                path == route
+                    && match m {
+                        Some(m) => want_method && m.eq_ignore_ascii_case(method),
+                        None => !want_method,
+                    }
+            })
+        };
+
+        matching(true)
+            .or_else(|| matching(false))
+            .map(|(_, _, cost)| *cost)
+            .unwrap_or(Self::DEFAULT_COST)
+    }
+}
+
+/// A second, stricter window for bulk admin operations, so a script stuck in
+/// a loop can't keep running `restore-all`/`delete-all` against the database.
+/// It is counted in its own bucket on top of the general limit.
+#[derive(Debug, Clone, Copy)]
+pub struct BulkRateLimitConfig {
+    pub max_requests: u32,
+    pub window_seconds: u32,
+}
+
+impl BulkRateLimitConfig {
+    const BULK_ROUTE_SUFFIXES: &[&str] = &["/restore-all", "/delete-all"];
+
+    pub fn init() -> Result<Self> {
+        let max_requests = std::env::var("BULK_RATE_LIMIT_MAX_REQUESTS")
+            .unwrap_or_else(|_| "5".to_string())
+            .parse::<u32>()
+            .context("BULK_RATE_LIMIT_MAX_REQUESTS must be a valid u32")?;
+
+        let window_seconds = std::env::var("BULK_RATE_LIMIT_WINDOW_SECONDS")
+            .unwrap_or_else(|_| "300".to_string())
+            .parse::<u32>()
+            .context("BULK_RATE_LIMIT_WINDOW_SECONDS must be a valid u32")?;
+
+        if window_seconds == 0 {
+            return Err(anyhow!(
+                "BULK_RATE_LIMIT_WINDOW_SECONDS must be greater than 0"
+            ));
+        }
+
+        Ok(Self {
+            max_requests,
+            window_seconds,
+        })
+    }
+
+    /// `route` is the matched router path, e.g. `/api/orders/delete-all`.
+    pub fn applies_to(&self, route: &str) -> bool {
+        Self::BULK_ROUTE_SUFFIXES
+            .iter()
+            .any(|suffix| route.ends_with(suffix))
+    }
+}
+
+/// A route being phased out. `sunset` is the day it is planned to go away.
+#[derive(Debug, Clone)]
+pub struct DeprecatedRoute {
+    pub method: Method,
+    pub path: String,
+    pub sunset: Option<NaiveDate>,
+}
+
+/// Routes flagged as deprecated in the OpenAPI document and answered with
+/// `Deprecation`/`Sunset` headers.
+#[derive(Debug, Clone, Default)]
+pub struct DeprecationConfig {
+    base_path: String,
+    routes: Vec<DeprecatedRoute>,
+}
+
+impl DeprecationConfig {
+    /// Reads `DEPRECATED_ROUTES` as comma separated `METHOD route[=sunset]`
+    /// entries, where `route` is the path as registered on the router and
+    /// `sunset` a `YYYY-MM-DD` date, e.g.
+    /// `GET /api/orders/active=2026-06-30,DELETE /api/users/delete-all`.
+    pub fn init() -> Result<Self> {
+        Self::parse(
+            &std::env::var("DEPRECATED_ROUTES").unwrap_or_default(),
+            &std::env::var("BASE_PATH").unwrap_or_default(),
+        )
+    }
+
+    pub fn parse(raw: &str, base_path: &str) -> Result<Self> {
+        let base_path = normalize_base_path(base_path);
+
+        let routes = split_list(raw)
+            .into_iter()
+            .map(|entry| {
+                let (route, sunset) = match entry.split_once('=') {
+                    Some((route, sunset)) => (route, Some(sunset.trim())),
+                    None => (entry.as_str(), None),
+                };
+
+                let (method, path) = route.trim().split_once(' ').ok_or_else(|| {
+                    anyhow!(
+                        "DEPRECATED_ROUTES entries must be 'METHOD route[=sunset]', got '{entry}'"
+                    )
+                })?;
+
+                let method = Method::from_bytes(method.to_ascii_uppercase().as_bytes())
+                    .with_context(|| {
+                        format!("DEPRECATED_ROUTES has an invalid method in '{entry}'")
+                    })?;
+
+                let sunset = sunset
+                    .map(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d"))
+                    .transpose()
+                    .with_context(|| {
+                        format!("DEPRECATED_ROUTES sunset must be a YYYY-MM-DD date, got '{entry}'")
+                    })?;
+
+                Ok(DeprecatedRoute {
+                    method,
+                    path: path.trim().to_string(),
+                    sunset,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self { base_path, routes })
+    }
+
+    pub fn routes(&self) -> &[DeprecatedRoute] {
+        &self.routes
+    }
+
+    /// `route` is the matched router path, which includes `BASE_PATH`.
+    pub fn find(&self, method: &Method, route: &str) -> Option<&DeprecatedRoute> {
+        let route = route.strip_prefix(self.base_path.as_str()).unwrap_or(route);
+
+        self.routes
+            .iter()
+            .find(|deprecated| deprecated.method == method && deprecated.path == route)
+    }
+}
+
+/// `(method, route, roles)`: a caller needs at least one of `roles` in their
+/// access token to reach `route`, given as registered on the router.
+pub type RouteRoles = (&'static str, &'static str, &'static [&'static str]);
+
+/// The per-route role requirements declared in `handler::ROUTE_ROLES`, with
+/// `BASE_PATH` prepended to match the nested router.
+#[derive(Debug, Clone, Default)]
+pub struct RouteRolesConfig {
+    routes: Vec<(&'static str, String, &'static [&'static str])>,
+}
+
+impl RouteRolesConfig {
+    pub fn init(routes: &[RouteRoles]) -> Self {
+        let base_path = normalize_base_path(&std::env::var("BASE_PATH").unwrap_or_default());
+
+        Self {
+            routes: routes
+                .iter()
+                .map(|(method, path, roles)| (*method, format!("{base_path}{path}"), *roles))
+                .collect(),
+        }
+    }
+
+    /// `None` when the route only needs a valid token.
+    pub fn roles_for(&self, method: &str, route: &str) -> Option<&'static [&'static str]> {
+        self.routes
+            .iter()
+            .find(|(m, path, _)| path == route && m.eq_ignore_ascii_case(method))
+            .map(|(_, _, roles)| *roles)
+    }
+}
+
+/// Which limit a request is counted against. Each group has its own bucket
+/// per caller, so a burst of reads can't lock a user out of logging in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitGroup {
+    Auth,
+    Read,
+    Write,
+}
+
+impl RateLimitGroup {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Auth => "auth",
+            Self::Read => "read",
+            Self::Write => "write",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimitWindow {
+    pub max_requests: u32,
+    pub window_seconds: u32,
+}
+
+/// Request limits per route group. Auth endpoints are the usual target for
+/// credential stuffing, so they default to the strictest window; other
+/// routes are split into reads (GET/HEAD) and writes.
+#[derive(Debug, Clone)]
+pub struct RateLimitGroupConfig {
+    pub auth: RateLimitWindow,
+    pub read: RateLimitWindow,
+    pub write: RateLimitWindow,
+    auth_prefix: String,
+}
+
+impl RateLimitGroupConfig {
+    /// Reads `RATE_LIMIT_{AUTH,READ,WRITE}_MAX_REQUESTS` and
+    /// `RATE_LIMIT_{AUTH,READ,WRITE}_WINDOW_SECONDS`.
+    pub fn init() -> Result<Self> {
+        let base_path = normalize_base_path(&std::env::var("BASE_PATH").unwrap_or_default());
+
+        Ok(Self {
+            auth: Self::window("AUTH", 20, 60)?,
+            read: Self::window("READ", 100, 60)?,
+            write: Self::window("WRITE", 30, 60)?,
+            auth_prefix: format!("{base_path}/api/auth/"),
+        })
+    }
+
+    fn window(group: &str, max_requests: u32, window_seconds: u32) -> Result<RateLimitWindow> {
+        let max_key = format!("RATE_LIMIT_{group}_MAX_REQUESTS");
+        let window_key = format!("RATE_LIMIT_{group}_WINDOW_SECONDS");
+
+        let max_requests = std::env::var(&max_key)
+            .unwrap_or_else(|_| max_requests.to_string())
+            .parse::<u32>()
+            .with_context(|| format!("{max_key} must be a valid u32"))?;
+
+        let window_seconds = std::env::var(&window_key)
+            .unwrap_or_else(|_| window_seconds.to_string())
+            .parse::<u32>()
+            .with_context(|| format!("{window_key} must be a valid u32"))?;
+
+        if window_seconds == 0 {
+            return Err(anyhow!("{window_key} must be greater than 0"));
+        }
+
+        Ok(RateLimitWindow {
+            max_requests,
+            window_seconds,
+        })
+    }
+
+    /// `route` is the matched router path, e.g. `/api/auth/login`.
+    pub fn group_for(&self, method: &str, route: &str) -> RateLimitGroup {
+        if route.starts_with(&self.auth_prefix) {
+            RateLimitGroup::Auth
+        } else if method.eq_ignore_ascii_case("GET") || method.eq_ignore_ascii_case("HEAD") {
+            RateLimitGroup::Read
+        } else {
+            RateLimitGroup::Write
+        }
+    }
+
+    pub fn limit_for(&self, group: RateLimitGroup) -> RateLimitWindow {
+        match group {
+            RateLimitGroup::Auth => self.auth,
+            RateLimitGroup::Read => self.read,
+            RateLimitGroup::Write => self.write,
+        }
+    }
+}
+
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
 #[derive(Debug, Clone)]
 pub struct ServiceConfig {
     pub grpc_port: u16,
@@ -46,8 +652,10 @@ pub struct ServiceConfig {
 pub struct Config {
     pub database_url: String,
     pub jwt_secret: String,
+    pub jwt_previous_key: Option<PreviousJwtKey>,
     pub run_migrations: bool,
     pub port: u16,
+    pub base_path: String,
     pub auth: ServiceConfig,
     pub user: ServiceConfig,
     pub role: ServiceConfig,
@@ -61,6 +669,7 @@ impl Config {
             std::env::var("DATABASE_URL").context("Missing environment variable: DATABASE_URL")?;
         let jwt_secret =
             std::env::var("JWT_SECRET").context("Missing environment variable: JWT_SECRET")?;
+        let jwt_previous_key = PreviousJwtKey::from_env()?;
         let run_migrations_str = std::env::var("RUN_MIGRATIONS")
             .context("Missing environment variable: RUN_MIGRATIONS")?;
         let port_str = std::env::var("PORT").context("Missing environment variable: PORT")?;
@@ -82,6 +691,8 @@ impl Config {
             .parse::<u16>()
             .context("PORT must be a valid u16 integer")?;
 
+        let base_path = normalize_base_path(&std::env::var("BASE_PATH").unwrap_or_default());
+
         // user
         let auth_grpc_port = std::env::var("AUTH_GRPC_PORT")
             .context("Missing environment variable: AUTH_GRPC_PORT")?
@@ -140,8 +751,10 @@ impl Config {
         Ok(Self {
             database_url,
             jwt_secret,
+            jwt_previous_key,
             run_migrations,
             port,
+            base_path,
             auth: ServiceConfig {
                 grpc_port: auth_grpc_port,
                 metric_port: auth_metric_port,
@@ -166,3 +779,15 @@ impl Config {
         })
     }
 }
+
+/// Turns `api/v1/`, `/api/v1` or `/api/v1/` into `/api/v1`; empty or `/`
+/// means the routes stay at the root.
+fn normalize_base_path(raw: &str) -> String {
+    let trimmed = raw.trim().trim_matches('/');
+
+    if trimmed.is_empty() {
+        String::new()
+    } else {
+        format!("/{trimmed}")
+    }
+}
@@ -1,11 +1,11 @@
 use crate::{
     abstract_trait::{
-        auth::DynAuthGrpcClient, order::DynOrderGrpcClient, product::DynProductGrpcClient,
-        role::DynRoleGrpcClient, user::DynUserGrpcClient,
+        auth::DynAuthGrpcClient, health::DynHealthGrpcClient, order::DynOrderGrpcClient,
+        product::DynProductGrpcClient, role::DynRoleGrpcClient, user::DynUserGrpcClient,
     },
     service::{
-        AuthGrpcClientService, GrpcClients, OrderGrpcClientService, ProductGrpcClientService,
-        RoleGrpcClientService, UserGrpcClientService,
+        AuthGrpcClientService, GrpcClients, HealthGrpcClientService, OrderGrpcClientService,
+        ProductGrpcClientService, RoleGrpcClientService, UserGrpcClientService,
     },
 };
 use anyhow::{Context, Result};
@@ -20,6 +20,7 @@ pub struct DependenciesInject {
     pub user_clients: DynUserGrpcClient,
     pub product_clients: DynProductGrpcClient,
     pub order_clients: DynOrderGrpcClient,
+    pub health_clients: DynHealthGrpcClient,
 }
 
 impl std::fmt::Debug for DependenciesInject {
@@ -30,6 +31,7 @@ impl std::fmt::Debug for DependenciesInject {
             .field("user_service", &"DynUserService")
             .field("product_service", &"DynProductService")
             .field("order_service", &"DynOrderService")
+            .field("health_service", &"DynHealthService")
             .finish()
     }
 }
@@ -79,12 +81,16 @@ impl DependenciesInject {
             .context("Failed to initialize OrderGrpcClientService")?,
         );
 
+        let health_clients: DynHealthGrpcClient =
+            Arc::new(HealthGrpcClientService::new(clients.health.clone()));
+
         Ok(Self {
             auth_clients,
             role_clients,
             user_clients,
             product_clients,
             order_clients,
+            health_clients,
         })
     }
 }
@@ -11,6 +11,10 @@ pub struct AuthRequest {
     #[validate(length(min = 6))]
     #[serde(rename = "password")]
     pub password: String,
+
+    /// Taken from the `User-Agent` header, never from the body.
+    #[serde(skip)]
+    pub user_agent: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Validate, ToSchema, Clone)]
@@ -33,3 +37,9 @@ pub struct RegisterRequest {
     #[serde(rename = "confirm_password")]
     pub confirm_password: String,
 }
+
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema, Clone)]
+pub struct ResendVerificationRequest {
+    #[validate(email)]
+    pub email: String,
+}
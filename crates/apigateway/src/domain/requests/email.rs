@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+use shared::utils::EmailTemplateData;
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct EmailPreviewRequest {
+    #[schema(example = "email")]
+    pub template: String,
+
+    pub data: EmailPreviewData,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, ToSchema)]
+pub struct EmailPreviewData {
+    #[validate(length(min = 1, message = "Title is required"))]
+    #[schema(example = "Welcome to SanEdge")]
+    pub title: String,
+
+    #[validate(length(min = 1, message = "Message is required"))]
+    #[schema(example = "Please verify your account to get started.")]
+    pub message: String,
+
+    #[validate(length(min = 1, message = "Button label is required"))]
+    #[schema(example = "Verify Account")]
+    pub button: String,
+
+    #[validate(url(message = "Link must be a valid URL"))]
+    #[schema(example = "https://sanedge.example.com/login?verify_code=123456")]
+    pub link: String,
+}
+
+impl From<EmailPreviewData> for EmailTemplateData {
+    fn from(value: EmailPreviewData) -> Self {
+        EmailTemplateData {
+            title: value.title,
+            message: value.message,
+            button: value.button,
+            link: value.link,
+        }
+    }
+}
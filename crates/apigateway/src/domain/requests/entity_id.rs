@@ -0,0 +1,44 @@
+use serde::{Deserialize, Deserializer, de};
+use std::{fmt, str::FromStr};
+use uuid::Uuid;
+
+/// Path identifier accepted by lookup routes: either the internal integer id
+/// or the public UUID exposed as `external_id` in responses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntityId {
+    Id(i32),
+    External(Uuid),
+}
+
+impl FromStr for EntityId {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if let Ok(id) = value.parse::<i32>() {
+            return Ok(Self::Id(id));
+        }
+
+        Uuid::parse_str(value)
+            .map(Self::External)
+            .map_err(|_| format!("'{value}' is neither an integer id nor a UUID"))
+    }
+}
+
+impl<'de> Deserialize<'de> for EntityId {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(de::Error::custom)
+    }
+}
+
+impl fmt::Display for EntityId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Id(id) => write!(f, "{id}"),
+            Self::External(uuid) => write!(f, "{uuid}"),
+        }
+    }
+}
@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema, IntoParams)]
+pub struct FormatParams {
+    /// Adds formatted money fields using the request's `Accept-Language`.
+    #[serde(default)]
+    pub format: bool,
+}
@@ -1,5 +1,8 @@
 pub mod auth;
+pub mod email;
+pub mod entity_id;
 pub mod forgot_password;
+pub mod format;
 pub mod order;
 pub mod order_item;
 pub mod product;
@@ -1,4 +1,8 @@
 use serde::{Deserialize, Serialize};
+use shared::{
+    errors::{FieldViolation, HttpError},
+    utils::parse_datetime,
+};
 use utoipa::{IntoParams, ToSchema};
 use validator::Validate;
 
@@ -12,6 +16,87 @@ pub struct FindAllOrder {
 
     #[serde(default)]
     pub search: String,
+
+    /// Comma separated relations to embed, e.g. `items`.
+    #[serde(default)]
+    pub include: String,
+
+    /// Opaque `next_cursor` from a previous page; switches to keyset
+    /// pagination, where `page` is ignored.
+    #[serde(default)]
+    pub after: String,
+
+    /// Comma separated `field:direction` pairs, e.g. `price:desc,name:asc`.
+    /// Can't be combined with `after`.
+    #[serde(default)]
+    pub sort: String,
+
+    /// Only orders placed by this user.
+    #[serde(default)]
+    pub user_id: Option<i32>,
+
+    /// Only orders placed at or after this RFC3339 timestamp. A `+` offset
+    /// must be sent as `%2B` in the query string.
+    #[serde(default)]
+    pub created_from: Option<String>,
+
+    /// Only orders placed at or before this RFC3339 timestamp.
+    #[serde(default)]
+    pub created_to: Option<String>,
+}
+
+impl FindAllOrder {
+    pub fn include_items(&self) -> bool {
+        self.include.split(',').any(|v| v.trim() == "items")
+    }
+
+    /// `created_from` and `created_to` normalized to UTC, with an empty
+    /// string for a missing bound.
+    pub fn created_range(&self) -> Result<(String, String), HttpError> {
+        let mut violations = Vec::new();
+
+        let mut parse = |field: &str, value: &Option<String>| match value.as_deref() {
+            None | Some("") => String::new(),
+            Some(raw) => parse_datetime(raw.trim()).unwrap_or_else(|| {
+                violations.push(FieldViolation::new(
+                    field,
+                    format!("'{raw}' is not an RFC3339 timestamp"),
+                ));
+                String::new()
+            }),
+        };
+
+        let range = (
+            parse("created_from", &self.created_from),
+            parse("created_to", &self.created_to),
+        );
+
+        if violations.is_empty() {
+            Ok(range)
+        } else {
+            Err(HttpError::ValidationFailed(violations))
+        }
+    }
+}
+
+/// Query parameters for one user's order history.
+#[derive(Debug, Serialize, Deserialize, IntoParams)]
+pub struct FindUserOrders {
+    #[serde(default = "default_page")]
+    pub page: i32,
+
+    #[serde(default = "default_page_size")]
+    pub page_size: i32,
+
+    /// Comma separated relations to embed, e.g. `items`.
+    #[serde(default)]
+    pub include: String,
+}
+
+impl FindUserOrders {
+    pub fn include_items(&self) -> bool {
+        self.include.split(',').any(|v| v.trim() == "items")
+    }
 }
 
 fn default_page() -> i32 {
@@ -30,6 +115,10 @@ pub struct CreateOrderRequest {
 
     #[validate(length(min = 1))]
     pub items: Vec<CreateOrderItemRequest>,
+
+    /// Taken from the `Idempotency-Key` header, never from the body.
+    #[serde(skip)]
+    pub idempotency_key: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
@@ -43,6 +132,14 @@ pub struct UpdateOrderRequest {
 
     #[validate(length(min = 1))]
     pub items: Vec<UpdateOrderItemRequest>,
+
+    /// New lifecycle status; omit to keep the current one.
+    #[serde(default)]
+    pub status: Option<String>,
+
+    /// Whether the caller is ADMIN or MODERATOR, taken from the token.
+    #[serde(skip)]
+    pub caller_is_staff: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Validate, Clone, ToSchema)]
@@ -1,4 +1,6 @@
 use serde::{Deserialize, Serialize};
+use shared::errors::FieldViolation;
+use std::collections::BTreeMap;
 use utoipa::{IntoParams, ToSchema};
 use validator::Validate;
 
@@ -12,6 +14,58 @@ pub struct FindAllProducts {
 
     #[serde(default)]
     pub search: String,
+
+    #[serde(default)]
+    pub min_price: Option<i64>,
+
+    #[serde(default)]
+    pub max_price: Option<i64>,
+
+    /// `key:value` match against the product attributes, e.g. `color:red`.
+    #[serde(default)]
+    pub attribute: String,
+
+    /// Opaque `next_cursor` from a previous page; switches to keyset
+    /// pagination, where `page` is ignored.
+    #[serde(default)]
+    pub after: String,
+
+    /// Comma separated `field:direction` pairs, e.g. `price:desc,name:asc`.
+    /// Can't be combined with `after`.
+    #[serde(default)]
+    pub sort: String,
+}
+
+impl FindAllProducts {
+    /// Either bound may be left open, but a closed range must not be inverted.
+    pub fn price_range_violations(&self) -> Vec<FieldViolation> {
+        let mut violations = Vec::new();
+
+        if self.min_price.is_some_and(|min| min < 0) {
+            violations.push(FieldViolation::new(
+                "min_price",
+                "Minimum price cannot be negative",
+            ));
+        }
+
+        if self.max_price.is_some_and(|max| max < 0) {
+            violations.push(FieldViolation::new(
+                "max_price",
+                "Maximum price cannot be negative",
+            ));
+        }
+
+        if let (Some(min), Some(max)) = (self.min_price, self.max_price)
+            && min > max
+        {
+            violations.push(FieldViolation::new(
+                "min_price",
+                format!("min_price ({min}) must not be greater than max_price ({max})"),
+            ));
+        }
+
+        violations
+    }
 }
 
 fn default_page() -> i32 {
@@ -35,6 +89,25 @@ pub struct CreateProductRequest {
     #[validate(range(min = 0, message = "Stock cannot be negative"))]
     #[schema(example = 100)]
     pub stock: i32,
+
+    #[serde(default)]
+    #[schema(example = json!(["https://cdn.example.com/products/smartphone.jpg"]))]
+    pub images: Vec<String>,
+
+    #[serde(default)]
+    #[validate(range(min = 0, message = "Reorder threshold cannot be negative"))]
+    #[schema(example = 10)]
+    pub reorder_threshold: Option<i32>,
+
+    #[serde(default)]
+    #[validate(length(min = 1, max = 100, message = "Category must be 1 to 100 characters"))]
+    #[schema(example = "apparel")]
+    pub category: Option<String>,
+
+    /// Checked against the category's attribute schema when one is configured.
+    #[serde(default)]
+    #[schema(example = json!({"size": "M", "color": "red"}))]
+    pub attributes: BTreeMap<String, String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Validate, ToSchema)]
@@ -52,4 +125,23 @@ pub struct UpdateProductRequest {
     #[validate(range(min = 0, message = "Stock cannot be negative"))]
     #[schema(example = 100)]
     pub stock: i32,
+
+    #[serde(default)]
+    #[schema(example = json!(["https://cdn.example.com/products/smartphone.jpg"]))]
+    pub images: Vec<String>,
+
+    #[serde(default)]
+    #[validate(range(min = 0, message = "Reorder threshold cannot be negative"))]
+    #[schema(example = 10)]
+    pub reorder_threshold: Option<i32>,
+
+    #[serde(default)]
+    #[validate(length(min = 1, max = 100, message = "Category must be 1 to 100 characters"))]
+    #[schema(example = "apparel")]
+    pub category: Option<String>,
+
+    /// Checked against the category's attribute schema when one is configured.
+    #[serde(default)]
+    #[schema(example = json!({"size": "M", "color": "red"}))]
+    pub attributes: BTreeMap<String, String>,
 }
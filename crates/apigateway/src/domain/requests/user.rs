@@ -12,6 +12,21 @@ pub struct FindAllUsers {
 
     #[serde(default)]
     pub search: String,
+
+    /// Comma separated relations to embed, e.g. `roles`.
+    #[serde(default)]
+    pub include: String,
+
+    /// Opaque `next_cursor` from a previous page; switches to keyset
+    /// pagination, where `page` is ignored.
+    #[serde(default)]
+    pub after: String,
+}
+
+impl FindAllUsers {
+    pub fn include_roles(&self) -> bool {
+        self.include.split(',').any(|v| v.trim() == "roles")
+    }
 }
 
 fn default_page() -> i32 {
@@ -21,6 +21,8 @@ impl<T: std::fmt::Debug> fmt::Display for ApiResponse<T> {
     }
 }
 
+/// A page of a list. No matches is not an error: `data` is an empty array
+/// and the pagination totals are zero.
 #[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
 pub struct ApiResponsePagination<T> {
     pub status: String,
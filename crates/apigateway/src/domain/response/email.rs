@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct EmailPreviewResponse {
+    pub template: String,
+    pub html: String,
+    pub text: String,
+}
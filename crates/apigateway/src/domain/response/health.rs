@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct DownstreamHealthResponse {
+    pub service: String,
+    pub healthy: bool,
+    pub status: String,
+    pub version: Option<String>,
+    /// Round trip time of the health RPC in milliseconds.
+    pub latency_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct ServicesHealthResponse {
+    /// `healthy` when every downstream answered, `degraded` otherwise.
+    pub status: String,
+    pub services: Vec<DownstreamHealthResponse>,
+}
+
+impl ServicesHealthResponse {
+    pub fn from_services(mut services: Vec<DownstreamHealthResponse>) -> Self {
+        services.sort_by(|a, b| a.service.cmp(&b.service));
+
+        let status = if services.iter().all(|s| s.healthy) {
+            "healthy"
+        } else {
+            "degraded"
+        };
+
+        Self {
+            status: status.to_string(),
+            services,
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use genproto::auth::LockoutStatusResponse as LockoutStatusResponseProto;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct LockoutStatusResponse {
+    pub user_id: i32,
+    pub email: String,
+    pub locked: bool,
+    pub failed_attempts: i32,
+    pub max_attempts: i32,
+}
+
+impl From<LockoutStatusResponseProto> for LockoutStatusResponse {
+    fn from(value: LockoutStatusResponseProto) -> Self {
+        Self {
+            user_id: value.user_id,
+            email: value.email,
+            locked: value.locked,
+            failed_attempts: value.failed_attempts,
+            max_attempts: value.max_attempts,
+        }
+    }
+}
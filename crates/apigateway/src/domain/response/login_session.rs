@@ -0,0 +1,24 @@
+use genproto::auth::SessionResponse as SessionResponseProto;
+use serde::{Deserialize, Serialize};
+use shared::utils::parse_datetime;
+use utoipa::ToSchema;
+
+/// A device signed in to the caller's account, as listed by the auth service.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct LoginSessionResponse {
+    pub id: i32,
+    pub user_agent: Option<String>,
+    pub created_at: Option<String>,
+    pub last_used_at: Option<String>,
+}
+
+impl From<SessionResponseProto> for LoginSessionResponse {
+    fn from(value: SessionResponseProto) -> Self {
+        Self {
+            id: value.id,
+            user_agent: Some(value.user_agent).filter(|ua| !ua.is_empty()),
+            created_at: parse_datetime(&value.created_at),
+            last_used_at: parse_datetime(&value.last_used_at),
+        }
+    }
+}
@@ -1,9 +1,15 @@
 pub mod api;
+pub mod email;
+pub mod health;
+pub mod lockout;
+pub mod login_session;
 pub mod order;
 pub mod order_item;
 pub mod pagination;
 pub mod product;
+pub mod rate_limit;
 pub mod role;
 pub mod session;
 pub mod token;
 pub mod user;
+pub mod user_export;
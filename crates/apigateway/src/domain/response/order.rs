@@ -1,29 +1,48 @@
+use crate::{
+    config::CurrencyConfig,
+    domain::response::order_item::{OrderItemDetailResponse, OrderItemResponse},
+};
 use genproto::order::{
-    OrderResponse as OrderResponseProto, OrderResponseDeleteAt as OrderResponseDeleteAtProto,
+    OrderDetailResponse as OrderDetailResponseProto, OrderResponse as OrderResponseProto,
+    OrderResponseDeleteAt as OrderResponseDeleteAtProto,
 };
 use serde::{Deserialize, Serialize};
-use shared::utils::parse_datetime;
+use shared::utils::{Money, parse_datetime};
 use utoipa::ToSchema;
 
 #[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
 pub struct OrderResponse {
     pub id: i32,
+    pub external_id: String,
+    pub order_number: String,
     pub user_id: i32,
     pub total_price: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_price_formatted: Option<Money>,
     #[serde(rename = "created_at")]
     pub created_at: Option<String>,
     #[serde(rename = "updated_at")]
     pub updated_at: Option<String>,
+    /// `pending`, `paid`, `shipped`, `delivered` or `cancelled`.
+    #[serde(default)]
+    pub status: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub items: Vec<OrderItemResponse>,
 }
 
 impl From<OrderResponseProto> for OrderResponse {
     fn from(value: OrderResponseProto) -> Self {
         OrderResponse {
             id: value.id,
+            external_id: value.external_id,
+            order_number: value.order_number,
             user_id: value.user_id,
             total_price: value.total_price,
+            total_price_formatted: None,
             created_at: parse_datetime(&value.created_at),
             updated_at: parse_datetime(&value.updated_at),
+            status: value.status,
+            items: value.items.into_iter().map(Into::into).collect(),
         }
     }
 }
@@ -32,10 +51,14 @@ impl From<OrderResponse> for OrderResponseProto {
     fn from(value: OrderResponse) -> Self {
         OrderResponseProto {
             id: value.id,
+            external_id: value.external_id,
+            order_number: value.order_number,
             user_id: value.user_id,
             total_price: value.total_price,
             created_at: value.created_at.unwrap_or_default(),
             updated_at: value.updated_at.unwrap_or_default(),
+            status: value.status,
+            items: value.items.into_iter().map(Into::into).collect(),
         }
     }
 }
@@ -43,25 +66,35 @@ impl From<OrderResponse> for OrderResponseProto {
 #[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
 pub struct OrderResponseDeleteAt {
     pub id: i32,
+    pub external_id: String,
+    pub order_number: String,
     pub user_id: i32,
     pub total_price: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_price_formatted: Option<Money>,
     #[serde(rename = "created_at")]
     pub created_at: Option<String>,
     #[serde(rename = "updated_at")]
     pub updated_at: Option<String>,
     #[serde(rename = "deleted_at")]
     pub deleted_at: Option<String>,
+    #[serde(default)]
+    pub status: String,
 }
 
 impl From<OrderResponseDeleteAtProto> for OrderResponseDeleteAt {
     fn from(value: OrderResponseDeleteAtProto) -> Self {
         OrderResponseDeleteAt {
             id: value.id,
+            external_id: value.external_id,
+            order_number: value.order_number,
             user_id: value.user_id,
             total_price: value.total_price,
+            total_price_formatted: None,
             created_at: parse_datetime(&value.created_at),
             updated_at: parse_datetime(&value.updated_at),
             deleted_at: value.deleted_at.as_deref().and_then(parse_datetime),
+            status: value.status,
         }
     }
 }
@@ -70,11 +103,97 @@ impl From<OrderResponseDeleteAt> for OrderResponseDeleteAtProto {
     fn from(value: OrderResponseDeleteAt) -> Self {
         OrderResponseDeleteAtProto {
             id: value.id,
+            external_id: value.external_id,
+            order_number: value.order_number,
             user_id: value.user_id,
             total_price: value.total_price,
             created_at: value.created_at.unwrap_or_default(),
             updated_at: value.updated_at.unwrap_or_default(),
             deleted_at: Some(value.deleted_at.unwrap_or_default()),
+            status: value.status,
         }
     }
 }
+
+impl OrderResponse {
+    pub fn with_formatted_price(
+        mut self,
+        currency: &CurrencyConfig,
+        accept_language: Option<&str>,
+    ) -> Self {
+        self.total_price_formatted =
+            Some(currency.format(self.total_price as i64, accept_language));
+        self.items = self
+            .items
+            .into_iter()
+            .map(|item| item.with_formatted_price(currency, accept_language))
+            .collect();
+        self
+    }
+}
+
+impl OrderResponseDeleteAt {
+    pub fn with_formatted_price(
+        mut self,
+        currency: &CurrencyConfig,
+        accept_language: Option<&str>,
+    ) -> Self {
+        self.total_price_formatted =
+            Some(currency.format(self.total_price as i64, accept_language));
+        self
+    }
+}
+
+/// An order with its items, each carrying its product's current name and
+/// price.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct OrderDetailResponse {
+    pub id: i32,
+    pub external_id: String,
+    pub order_number: String,
+    pub user_id: i32,
+    pub total_price: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_price_formatted: Option<Money>,
+    /// `pending`, `paid`, `shipped`, `delivered` or `cancelled`.
+    pub status: String,
+    #[serde(rename = "created_at")]
+    pub created_at: Option<String>,
+    #[serde(rename = "updated_at")]
+    pub updated_at: Option<String>,
+    pub items: Vec<OrderItemDetailResponse>,
+}
+
+impl From<OrderDetailResponseProto> for OrderDetailResponse {
+    fn from(value: OrderDetailResponseProto) -> Self {
+        OrderDetailResponse {
+            id: value.id,
+            external_id: value.external_id,
+            order_number: value.order_number,
+            user_id: value.user_id,
+            total_price: value.total_price,
+            total_price_formatted: None,
+            status: value.status,
+            created_at: parse_datetime(&value.created_at),
+            updated_at: parse_datetime(&value.updated_at),
+            items: value.items.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl OrderDetailResponse {
+    pub fn with_formatted_price(
+        mut self,
+        currency: &CurrencyConfig,
+        accept_language: Option<&str>,
+    ) -> Self {
+        self.total_price_formatted =
+            Some(currency.format(self.total_price as i64, accept_language));
+        self.items = self
+            .items
+            .into_iter()
+            .map(|item| item.with_formatted_price(currency, accept_language))
+            .collect();
+        self
+    }
+}
@@ -1,9 +1,11 @@
+use crate::config::CurrencyConfig;
+use genproto::order::OrderItemDetailResponse as OrderItemDetailResponseProto;
 use genproto::order_item::{
     OrderItemResponse as OrderItemResponseProto,
     OrderItemResponseDeleteAt as OrderItemResposeDeleteAtProto,
 };
 use serde::{Deserialize, Serialize};
-use shared::utils::parse_datetime;
+use shared::utils::{Money, parse_datetime};
 use utoipa::ToSchema;
 
 #[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
@@ -13,6 +15,11 @@ pub struct OrderItemResponse {
     pub product_id: i32,
     pub quantity: i32,
     pub price: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price_formatted: Option<Money>,
+    /// `quantity * price`
+    #[serde(default)]
+    pub subtotal: i32,
     #[serde(rename = "created_at")]
     pub created_at: Option<String>,
     #[serde(rename = "updated_at")]
@@ -27,6 +34,8 @@ impl From<OrderItemResponseProto> for OrderItemResponse {
             product_id: value.product_id,
             quantity: value.quantity,
             price: value.price,
+            price_formatted: None,
+            subtotal: value.subtotal,
             created_at: parse_datetime(&value.created_at),
             updated_at: parse_datetime(&value.updated_at),
         }
@@ -43,6 +52,47 @@ impl From<OrderItemResponse> for OrderItemResponseProto {
             price: value.price,
             created_at: value.created_at.unwrap_or_default(),
             updated_at: value.updated_at.unwrap_or_default(),
+            subtotal: value.subtotal,
+        }
+    }
+}
+
+/// An order item with its product's current name and price.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct OrderItemDetailResponse {
+    pub id: i32,
+    pub order_id: i32,
+    pub product_id: i32,
+    /// A placeholder when the product has been deleted.
+    pub product_name: String,
+    pub quantity: i32,
+    /// Unit price when the order was placed.
+    pub price: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price_formatted: Option<Money>,
+    pub subtotal: i32,
+    /// The product's price now; absent when it has been deleted.
+    pub current_price: Option<i64>,
+    #[serde(rename = "created_at")]
+    pub created_at: Option<String>,
+    #[serde(rename = "updated_at")]
+    pub updated_at: Option<String>,
+}
+
+impl From<OrderItemDetailResponseProto> for OrderItemDetailResponse {
+    fn from(value: OrderItemDetailResponseProto) -> Self {
+        OrderItemDetailResponse {
+            id: value.id,
+            order_id: value.order_id,
+            product_id: value.product_id,
+            product_name: value.product_name,
+            quantity: value.quantity,
+            price: value.price,
+            price_formatted: None,
+            subtotal: value.subtotal,
+            current_price: value.current_price,
+            created_at: parse_datetime(&value.created_at),
+            updated_at: parse_datetime(&value.updated_at),
         }
     }
 }
@@ -91,3 +141,25 @@ impl From<OrderItemResponseDeleteAt> for OrderItemResposeDeleteAtProto {
         }
     }
 }
+
+impl OrderItemResponse {
+    pub fn with_formatted_price(
+        mut self,
+        currency: &CurrencyConfig,
+        accept_language: Option<&str>,
+    ) -> Self {
+        self.price_formatted = Some(currency.format(self.price as i64, accept_language));
+        self
+    }
+}
+
+impl OrderItemDetailResponse {
+    pub fn with_formatted_price(
+        mut self,
+        currency: &CurrencyConfig,
+        accept_language: Option<&str>,
+    ) -> Self {
+        self.price_formatted = Some(currency.format(self.price as i64, accept_language));
+        self
+    }
+}
@@ -2,12 +2,20 @@ use genproto::api::Pagination as ProtoPagination;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
+/// Page metadata of a list response. A list with no matches is still a
+/// 200 with `total_items` and `total_pages` at 0 and `has_next` false.
 #[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
 pub struct Pagination {
     pub page: i32,
     pub page_size: i32,
     pub total_items: i32,
     pub total_pages: i32,
+    /// Whether another page follows this one.
+    #[serde(default)]
+    pub has_next: bool,
+    /// Opaque token for the next page in cursor mode; absent on the last page.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
 }
 
 impl From<ProtoPagination> for Pagination {
@@ -17,6 +25,8 @@ impl From<ProtoPagination> for Pagination {
             page_size: value.page_size,
             total_items: value.total_records,
             total_pages: value.total_pages,
+            has_next: value.current_page < value.total_pages,
+            next_cursor: Some(value.next_cursor).filter(|cursor| !cursor.is_empty()),
         }
     }
 }
@@ -28,6 +38,44 @@ impl From<Pagination> for ProtoPagination {
             page_size: value.page_size,
             total_records: value.total_items,
             total_pages: value.total_pages,
+            next_cursor: value.next_cursor.unwrap_or_default(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Pagination, ProtoPagination};
+    use crate::domain::response::api::ApiResponsePagination;
+    use serde_json::json;
+
+    #[test]
+    fn an_empty_list_is_a_zero_total_last_page() {
+        let pagination = Pagination::from(ProtoPagination {
+            current_page: 1,
+            page_size: 10,
+            total_records: 0,
+            total_pages: 0,
+            next_cursor: String::new(),
+        });
+        let response = ApiResponsePagination {
+            status: "success".to_string(),
+            message: "Orders retrieved successfully".to_string(),
+            data: Vec::<String>::new(),
+            pagination,
+        };
+
+        let body = serde_json::to_value(&response).unwrap();
+        assert_eq!(body["data"], json!([]));
+        assert_eq!(
+            body["pagination"],
+            json!({
+                "page": 1,
+                "page_size": 10,
+                "total_items": 0,
+                "total_pages": 0,
+                "has_next": false,
+            })
+        );
+    }
+}
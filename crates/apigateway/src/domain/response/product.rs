@@ -1,18 +1,31 @@
-use shared::utils::parse_datetime;
+use crate::config::CurrencyConfig;
+use shared::utils::{Money, parse_datetime};
 
 use genproto::product::{
-    ProductResponse as ProductResponseProto,
+    ProductAttribute as ProductAttributeProto, ProductResponse as ProductResponseProto,
     ProductResponseDeleteAt as ProductResponseDeleteAtProto,
 };
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use utoipa::ToSchema;
 
 #[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
 pub struct ProductResponse {
     pub id: i32,
+    pub external_id: String,
     pub name: String,
     pub price: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price_formatted: Option<Money>,
     pub stock: i32,
+    #[serde(default)]
+    pub images: Vec<String>,
+    #[serde(default)]
+    pub reorder_threshold: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    #[serde(default)]
+    pub attributes: BTreeMap<String, String>,
     #[serde(rename = "created_at")]
     pub created_at: Option<String>,
     #[serde(rename = "updated_at")]
@@ -24,9 +37,15 @@ impl From<ProductResponseProto> for ProductResponse {
     fn from(value: ProductResponseProto) -> Self {
         ProductResponse {
             id: value.id,
+            external_id: value.external_id,
             name: value.name,
             price: value.price,
+            price_formatted: None,
             stock: value.stock,
+            images: value.images,
+            reorder_threshold: value.reorder_threshold,
+            category: value.category,
+            attributes: attributes_from_proto(value.attributes),
             created_at: parse_datetime(&value.created_at),
             updated_at: parse_datetime(&value.updated_at),
         }
@@ -38,9 +57,14 @@ impl From<ProductResponse> for ProductResponseProto {
     fn from(value: ProductResponse) -> Self {
         ProductResponseProto {
             id: value.id,
+            external_id: value.external_id,
             name: value.name,
             price: value.price,
             stock: value.stock,
+            images: value.images,
+            reorder_threshold: value.reorder_threshold,
+            category: value.category,
+            attributes: attributes_to_proto(value.attributes),
             created_at: value.created_at.unwrap_or_default(),
             updated_at: value.updated_at.unwrap_or_default(),
         }
@@ -50,9 +74,20 @@ impl From<ProductResponse> for ProductResponseProto {
 #[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
 pub struct ProductResponseDeleteAt {
     pub id: i32,
+    pub external_id: String,
     pub name: String,
     pub price: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price_formatted: Option<Money>,
     pub stock: i32,
+    #[serde(default)]
+    pub images: Vec<String>,
+    #[serde(default)]
+    pub reorder_threshold: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    #[serde(default)]
+    pub attributes: BTreeMap<String, String>,
     #[serde(rename = "created_at")]
     pub created_at: Option<String>,
     #[serde(rename = "updated_at")]
@@ -66,9 +101,15 @@ impl From<ProductResponseDeleteAtProto> for ProductResponseDeleteAt {
     fn from(value: ProductResponseDeleteAtProto) -> Self {
         ProductResponseDeleteAt {
             id: value.id,
+            external_id: value.external_id,
             name: value.name,
             price: value.price,
+            price_formatted: None,
             stock: value.stock,
+            images: value.images,
+            reorder_threshold: value.reorder_threshold,
+            category: value.category,
+            attributes: attributes_from_proto(value.attributes),
             created_at: parse_datetime(&value.created_at),
             updated_at: parse_datetime(&value.updated_at),
             deleted_at: value.deleted_at.as_deref().and_then(parse_datetime),
@@ -81,12 +122,50 @@ impl From<ProductResponseDeleteAt> for ProductResponseDeleteAtProto {
     fn from(value: ProductResponseDeleteAt) -> Self {
         ProductResponseDeleteAtProto {
             id: value.id,
+            external_id: value.external_id,
             name: value.name,
             price: value.price,
             stock: value.stock,
+            images: value.images,
+            reorder_threshold: value.reorder_threshold,
+            category: value.category,
+            attributes: attributes_to_proto(value.attributes),
             created_at: value.created_at.unwrap_or_default(),
             updated_at: value.updated_at.unwrap_or_default(),
             deleted_at: Some(value.deleted_at.unwrap_or_default()),
         }
     }
 }
+
+impl ProductResponse {
+    pub fn with_formatted_price(
+        mut self,
+        currency: &CurrencyConfig,
+        accept_language: Option<&str>,
+    ) -> Self {
+        self.price_formatted = Some(currency.format(self.price, accept_language));
+        self
+    }
+}
+
+impl ProductResponseDeleteAt {
+    pub fn with_formatted_price(
+        mut self,
+        currency: &CurrencyConfig,
+        accept_language: Option<&str>,
+    ) -> Self {
+        self.price_formatted = Some(currency.format(self.price, accept_language));
+        self
+    }
+}
+
+pub fn attributes_from_proto(attributes: Vec<ProductAttributeProto>) -> BTreeMap<String, String> {
+    attributes.into_iter().map(|a| (a.key, a.value)).collect()
+}
+
+pub fn attributes_to_proto(attributes: BTreeMap<String, String>) -> Vec<ProductAttributeProto> {
+    attributes
+        .into_iter()
+        .map(|(key, value)| ProductAttributeProto { key, value })
+        .collect()
+}
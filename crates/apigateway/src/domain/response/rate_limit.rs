@@ -0,0 +1,24 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone, Copy)]
+pub struct RateLimitStatus {
+    pub limit: u32,
+    pub remaining: u32,
+    /// Unix timestamp (seconds) at which the current window resets.
+    pub reset: i64,
+    /// Seconds until the current window resets.
+    pub reset_after: u64,
+}
+
+impl RateLimitStatus {
+    pub fn new(limit: u32, used: u32, ttl_seconds: u64) -> Self {
+        Self {
+            limit,
+            remaining: limit.saturating_sub(used),
+            reset: Utc::now().timestamp() + ttl_seconds as i64,
+            reset_after: ttl_seconds,
+        }
+    }
+}
@@ -8,6 +8,7 @@ use utoipa::ToSchema;
 #[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
 pub struct RoleResponse {
     pub role_id: i32,
+    pub external_id: String,
     pub role_name: String,
     #[serde(rename = "created_at")]
     pub created_at: Option<String>,
@@ -20,6 +21,7 @@ impl From<RoleResponseProto> for RoleResponse {
     fn from(value: RoleResponseProto) -> Self {
         RoleResponse {
             role_id: value.id,
+            external_id: value.external_id,
             role_name: value.name,
             created_at: parse_datetime(&value.created_at),
             updated_at: parse_datetime(&value.updated_at),
@@ -32,6 +34,7 @@ impl From<RoleResponse> for RoleResponseProto {
     fn from(value: RoleResponse) -> Self {
         RoleResponseProto {
             id: value.role_id,
+            external_id: value.external_id,
             name: value.role_name,
             created_at: value.created_at.unwrap_or_default(),
             updated_at: value.updated_at.unwrap_or_default(),
@@ -42,6 +45,7 @@ impl From<RoleResponse> for RoleResponseProto {
 #[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
 pub struct RoleResponseDeleteAt {
     pub role_id: i32,
+    pub external_id: String,
     pub role_name: String,
     #[serde(rename = "created_at")]
     pub created_at: Option<String>,
@@ -56,6 +60,7 @@ impl From<RoleResponseDeleteAtProto> for RoleResponseDeleteAt {
     fn from(value: RoleResponseDeleteAtProto) -> Self {
         RoleResponseDeleteAt {
             role_id: value.id,
+            external_id: value.external_id,
             role_name: value.name,
             created_at: parse_datetime(&value.created_at),
             updated_at: parse_datetime(&value.updated_at),
@@ -69,6 +74,7 @@ impl From<RoleResponseDeleteAt> for RoleResponseDeleteAtProto {
     fn from(value: RoleResponseDeleteAt) -> Self {
         RoleResponseDeleteAtProto {
             id: value.role_id,
+            external_id: value.external_id,
             name: value.role_name,
             created_at: value.created_at.unwrap_or_default(),
             updated_at: value.updated_at.unwrap_or_default(),
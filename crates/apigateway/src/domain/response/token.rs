@@ -6,6 +6,9 @@ use utoipa::ToSchema;
 pub struct TokenResponse {
     pub access_token: String,
     pub refresh_token: String,
+    /// Role names carried in the access token's `roles` claim.
+    #[serde(default)]
+    pub roles: Vec<String>,
 }
 
 impl From<ProtoTokenResponse> for TokenResponse {
@@ -13,6 +16,7 @@ impl From<ProtoTokenResponse> for TokenResponse {
         Self {
             access_token: value.access_token,
             refresh_token: value.refresh_token,
+            roles: value.roles,
         }
     }
 }
@@ -22,6 +26,7 @@ impl From<TokenResponse> for ProtoTokenResponse {
         Self {
             access_token: value.access_token,
             refresh_token: value.refresh_token,
+            roles: value.roles,
         }
     }
 }
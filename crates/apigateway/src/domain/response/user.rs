@@ -1,3 +1,4 @@
+use crate::config::RedactionConfig;
 use genproto::user::{
     UserResponse as UserResponseProto, UserResponseDeleteAt as UserResponseDeleteAtProto,
 };
@@ -8,6 +9,7 @@ use utoipa::ToSchema;
 #[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
 pub struct UserResponse {
     pub id: i32,
+    pub external_id: String,
     pub firstname: String,
     pub lastname: String,
     pub email: String,
@@ -15,17 +17,35 @@ pub struct UserResponse {
     pub created_at: Option<String>,
     #[serde(rename = "updated_at")]
     pub updated_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub roles: Option<Vec<String>>,
 }
 
 impl From<UserResponseProto> for UserResponse {
     fn from(value: UserResponseProto) -> Self {
         UserResponse {
             id: value.id,
+            external_id: value.external_id,
             firstname: value.firstname,
             lastname: value.lastname,
             email: value.email,
             created_at: parse_datetime(&value.created_at),
             updated_at: parse_datetime(&value.updated_at),
+            roles: None,
+        }
+    }
+}
+
+impl UserResponse {
+    /// Keeps the role names sent by the user service; only used when roles
+    /// were requested, since an empty list is otherwise indistinguishable
+    /// from "not loaded".
+    pub fn from_proto_with_roles(value: UserResponseProto) -> Self {
+        let roles = value.roles.clone();
+
+        UserResponse {
+            roles: Some(roles),
+            ..value.into()
         }
     }
 }
@@ -34,11 +54,13 @@ impl From<UserResponse> for UserResponseProto {
     fn from(value: UserResponse) -> Self {
         UserResponseProto {
             id: value.id,
+            external_id: value.external_id,
             firstname: value.firstname,
             lastname: value.lastname,
             email: value.email,
             created_at: value.created_at.unwrap_or_default(),
             updated_at: value.updated_at.unwrap_or_default(),
+            roles: value.roles.unwrap_or_default(),
         }
     }
 }
@@ -46,6 +68,7 @@ impl From<UserResponse> for UserResponseProto {
 #[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
 pub struct UserResponseDeleteAt {
     pub id: i32,
+    pub external_id: String,
     pub firstname: String,
     pub lastname: String,
     pub email: String,
@@ -61,6 +84,7 @@ impl From<UserResponseDeleteAtProto> for UserResponseDeleteAt {
     fn from(value: UserResponseDeleteAtProto) -> Self {
         UserResponseDeleteAt {
             id: value.id,
+            external_id: value.external_id,
             firstname: value.firstname,
             lastname: value.lastname,
             email: value.email,
@@ -75,6 +99,7 @@ impl From<UserResponseDeleteAt> for UserResponseDeleteAtProto {
     fn from(value: UserResponseDeleteAt) -> Self {
         UserResponseDeleteAtProto {
             id: value.id,
+            external_id: value.external_id,
             firstname: value.firstname,
             lastname: value.lastname,
             email: value.email,
@@ -84,3 +109,49 @@ impl From<UserResponseDeleteAt> for UserResponseDeleteAtProto {
         }
     }
 }
+
+impl UserResponse {
+    pub fn redact(mut self, policy: &RedactionConfig, roles: &[String]) -> Self {
+        if policy.should_redact("email", roles) {
+            self.email = mask_email(&self.email);
+        }
+        if policy.should_redact("firstname", roles) {
+            self.firstname = mask_value(&self.firstname);
+        }
+        if policy.should_redact("lastname", roles) {
+            self.lastname = mask_value(&self.lastname);
+        }
+        self
+    }
+}
+
+impl UserResponseDeleteAt {
+    pub fn redact(mut self, policy: &RedactionConfig, roles: &[String]) -> Self {
+        if policy.should_redact("email", roles) {
+            self.email = mask_email(&self.email);
+        }
+        if policy.should_redact("firstname", roles) {
+            self.firstname = mask_value(&self.firstname);
+        }
+        if policy.should_redact("lastname", roles) {
+            self.lastname = mask_value(&self.lastname);
+        }
+        self
+    }
+}
+
+fn mask_value(value: &str) -> String {
+    let mut chars = value.chars();
+
+    match chars.next() {
+        Some(first) => format!("{first}{}", "*".repeat(chars.count())),
+        None => String::new(),
+    }
+}
+
+fn mask_email(email: &str) -> String {
+    match email.split_once('@') {
+        Some((local, domain)) => format!("{}@{domain}", mask_value(local)),
+        None => mask_value(email),
+    }
+}
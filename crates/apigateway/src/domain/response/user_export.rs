@@ -0,0 +1,33 @@
+use crate::domain::response::{order::OrderResponse, user::UserResponse};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Everything held about one user, gathered from the user and order
+/// services for a data-subject export.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct UserExportResponse {
+    pub exported_at: String,
+    pub profile: UserResponse,
+    /// Every order the user placed, with its items, newest first.
+    pub orders: Vec<OrderResponse>,
+    /// Sections a complete export would hold that this one doesn't, so a
+    /// reader can tell "nothing recorded" apart from "not exported".
+    pub omitted: Vec<OmittedSection>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct OmittedSection {
+    pub section: String,
+    pub reason: String,
+}
+
+impl OmittedSection {
+    /// No service records an audit trail of user activity yet, so every
+    /// export lists it as omitted until one does.
+    pub fn audit() -> Self {
+        Self {
+            section: "audit".to_string(),
+            reason: "No audit trail of user activity is recorded yet".to_string(),
+        }
+    }
+}
@@ -1,30 +1,39 @@
 use crate::{
-    abstract_trait::auth::DynAuthGrpcClient,
+    abstract_trait::{auth::DynAuthGrpcClient, rate_limit::DynRateLimitMiddleware},
+    config::{RateLimitGroup, RateLimitGroupConfig},
     domain::{
         requests::{
-            auth::{AuthRequest, RegisterRequest},
+            auth::{AuthRequest, RegisterRequest, ResendVerificationRequest},
             reset_token::CreateResetPasswordRequest,
             verify_code::VerifyCodeQuery,
         },
-        response::{api::ApiResponse, token::TokenResponse, user::UserResponse},
+        response::{
+            api::ApiResponse, lockout::LockoutStatusResponse, login_session::LoginSessionResponse,
+            rate_limit::RateLimitStatus, token::TokenResponse, user::UserResponse,
+        },
     },
 };
 use crate::{
     middleware::{
-        jwt::auth_middleware, rate_limit::rate_limit_middleware, session::session_middleware,
-        validate::SimpleValidatedJson,
+        jwt::auth_middleware,
+        rate_limit::{
+            apply_rate_limit_headers, rate_limit_identity, rate_limit_key, rate_limit_middleware,
+        },
+        roles::require_roles,
+        session::session_middleware,
+        validate::{ApiJson, SimpleValidatedJson},
     },
     state::AppState,
 };
 use axum::{
     Extension, Json,
-    extract::Query,
-    http::StatusCode,
+    extract::{Path, Query},
+    http::{HeaderMap, StatusCode, header::USER_AGENT},
     middleware,
     response::IntoResponse,
-    routing::{get, post},
+    routing::{delete, get, post},
 };
-use shared::errors::HttpError;
+use shared::{abstract_trait::DynJwtService, errors::HttpError};
 use std::sync::Arc;
 use utoipa_axum::router::OpenApiRouter;
 
@@ -46,14 +55,22 @@ pub async fn health_checker_handler() -> Result<impl IntoResponse, HttpError> {
     request_body = AuthRequest,
     responses(
         (status = 200, description = "Login successful", body = ApiResponse<TokenResponse>),
-        (status = 401, description = "Invalid credentials")
+        (status = 401, description = "Invalid credentials"),
+        (status = 403, description = "Email is not verified or the session limit is reached"),
+        (status = 429, description = "Account locked after too many failed attempts")
     ),
     tag = "Auth"
 )]
 pub async fn login_user_handler(
     Extension(service): Extension<DynAuthGrpcClient>,
-    SimpleValidatedJson(body): SimpleValidatedJson<AuthRequest>,
+    headers: HeaderMap,
+    SimpleValidatedJson(mut body): SimpleValidatedJson<AuthRequest>,
 ) -> Result<impl IntoResponse, HttpError> {
+    body.user_agent = headers
+        .get(USER_AGENT)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+
     let response = service.login_user(&body).await?;
     Ok((StatusCode::OK, Json(response)))
 }
@@ -88,12 +105,30 @@ pub async fn register_user_handler(
 )]
 pub async fn forgot_password_handler(
     Extension(service): Extension<DynAuthGrpcClient>,
-    Json(email): Json<String>,
+    ApiJson(email): ApiJson<String>,
 ) -> Result<impl IntoResponse, HttpError> {
     let response = service.forgot(&email).await?;
     Ok((StatusCode::OK, Json(response)))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/auth/resend-verification",
+    request_body = ResendVerificationRequest,
+    responses(
+        (status = 200, description = "Verification email sent if the account is unverified", body = ApiResponse<bool>),
+        (status = 429, description = "Too many verification emails requested")
+    ),
+    tag = "Auth"
+)]
+pub async fn resend_verification_handler(
+    Extension(service): Extension<DynAuthGrpcClient>,
+    SimpleValidatedJson(body): SimpleValidatedJson<ResendVerificationRequest>,
+) -> Result<impl IntoResponse, HttpError> {
+    let response = service.resend_verification(&body.email).await?;
+    Ok((StatusCode::OK, Json(response)))
+}
+
 #[utoipa::path(
     post,
     path = "/api/auth/reset-password",
@@ -138,13 +173,13 @@ pub async fn verify_code_handler(
     request_body(content = String, description = "Refresh token", content_type = "application/json"),
     responses(
         (status = 200, description = "Token refreshed", body = ApiResponse<TokenResponse>),
-        (status = 401, description = "Invalid or expired refresh token")
+        (status = 401, description = "Invalid or expired refresh token, or session revoked after the token was reused")
     ),
     tag = "Auth"
 )]
 pub async fn refresh_token_handler(
     Extension(service): Extension<DynAuthGrpcClient>,
-    Json(token): Json<String>,
+    ApiJson(token): ApiJson<String>,
 ) -> Result<impl IntoResponse, HttpError> {
     let response = service.refresh_token(&token).await?;
     Ok((StatusCode::OK, Json(response)))
@@ -169,26 +204,169 @@ pub async fn get_me_handler(
     Ok((StatusCode::OK, Json(response)))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/auth/sessions",
+    responses(
+        (status = 200, description = "Devices signed in to the caller's account", body = ApiResponse<Vec<LoginSessionResponse>>),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Auth",
+)]
+pub async fn list_sessions_handler(
+    Extension(service): Extension<DynAuthGrpcClient>,
+    Extension(user_id): Extension<i32>,
+) -> Result<impl IntoResponse, HttpError> {
+    let response = service.list_sessions(user_id).await?;
+    Ok((StatusCode::OK, Json(response)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/auth/sessions/{id}",
+    params(("id" = i32, Path, description = "Session ID")),
+    responses(
+        (status = 200, description = "Session signed out", body = ApiResponse<bool>),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Session not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Auth",
+)]
+pub async fn revoke_session_handler(
+    Extension(service): Extension<DynAuthGrpcClient>,
+    Extension(user_id): Extension<i32>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, HttpError> {
+    let response = service.revoke_session(user_id, id).await?;
+    Ok((StatusCode::OK, Json(response)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/auth/rate-limit",
+    tag = "Auth",
+    responses(
+        (status = 200, description = "Current read rate-limit bucket for the caller", body = ApiResponse<RateLimitStatus>)
+    )
+)]
+pub async fn rate_limit_status_handler(
+    Extension(rate_limiter): Extension<DynRateLimitMiddleware>,
+    Extension(groups): Extension<RateLimitGroupConfig>,
+    Extension(jwt): Extension<DynJwtService>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    let key = rate_limit_key(&rate_limit_identity(&headers, &jwt), RateLimitGroup::Read);
+    let limit = groups.limit_for(RateLimitGroup::Read);
+    let status = rate_limiter
+        .get_status(&key, limit.max_requests, limit.window_seconds)
+        .await;
+
+    let mut response = (
+        StatusCode::OK,
+        Json(ApiResponse {
+            status: "success".to_string(),
+            message: "Rate limit status retrieved successfully".to_string(),
+            data: status,
+        }),
+    )
+        .into_response();
+
+    apply_rate_limit_headers(response.headers_mut(), &status);
+    response
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/users/{id}/lockout",
+    params(("id" = i32, Path, description = "User ID")),
+    responses(
+        (status = 200, description = "Current login lockout state of the user", body = ApiResponse<LockoutStatusResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "User not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Auth",
+)]
+pub async fn lockout_status_handler(
+    Extension(service): Extension<DynAuthGrpcClient>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, HttpError> {
+    let response = service.get_lockout_status(id).await?;
+    Ok((StatusCode::OK, Json(response)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/users/{id}/unlock",
+    params(("id" = i32, Path, description = "User ID")),
+    responses(
+        (status = 200, description = "Login lockout cleared", body = ApiResponse<LockoutStatusResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "User not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Auth",
+)]
+pub async fn unlock_account_handler(
+    Extension(service): Extension<DynAuthGrpcClient>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, HttpError> {
+    let response = service.unlock_account(id).await?;
+    Ok((StatusCode::OK, Json(response)))
+}
+
 pub fn auth_routes(app_state: Arc<AppState>) -> OpenApiRouter {
     let public_routes = OpenApiRouter::new()
         .route("/api/auth/register", post(register_user_handler))
         .route("/api/auth/login", post(login_user_handler))
         .route("/api/auth/verify-code", get(verify_code_handler))
+        .route(
+            "/api/auth/resend-verification",
+            post(resend_verification_handler),
+        )
+        .route("/api/auth/rate-limit", get(rate_limit_status_handler))
         .route("/api/healthchecker", get(health_checker_handler))
-        .layer(Extension(app_state.di_container.auth_clients.clone()));
+        .route_layer(middleware::from_fn(rate_limit_middleware))
+        .layer(Extension(app_state.di_container.auth_clients.clone()))
+        .layer(Extension(app_state.rate_limit.clone()))
+        .layer(Extension(app_state.rate_limit_costs.clone()))
+        .layer(Extension(app_state.rate_limit_groups.clone()))
+        .layer(Extension(app_state.bulk_rate_limit))
+        .layer(Extension(app_state.jwt_config.clone()));
 
     let private_routes = OpenApiRouter::new()
         .route("/api/auth/me", get(get_me_handler))
         .route("/api/auth/forgot-password", post(forgot_password_handler))
         .route("/api/auth/reset-password", post(reset_password_handler))
         .route("/api/auth/refresh", post(refresh_token_handler))
+        .route("/api/auth/sessions", get(list_sessions_handler))
+        .route("/api/auth/sessions/{id}", delete(revoke_session_handler))
+        .route("/api/admin/users/{id}/lockout", get(lockout_status_handler))
+        .route("/api/admin/users/{id}/unlock", post(unlock_account_handler))
+        .route_layer(middleware::from_fn(require_roles))
         .route_layer(middleware::from_fn(session_middleware))
         .route_layer(middleware::from_fn(auth_middleware))
         .route_layer(middleware::from_fn(rate_limit_middleware))
         .layer(Extension(app_state.di_container.auth_clients.clone()))
         .layer(Extension(app_state.di_container.role_clients.clone()))
         .layer(Extension(app_state.rate_limit.clone()))
+        .layer(Extension(app_state.rate_limit_costs.clone()))
+        .layer(Extension(app_state.rate_limit_groups.clone()))
+        .layer(Extension(app_state.bulk_rate_limit))
         .layer(Extension(app_state.session.clone()))
+        .layer(Extension(app_state.route_roles.clone()))
         .layer(Extension(app_state.jwt_config.clone()));
 
     public_routes.merge(private_routes).with_state(app_state)
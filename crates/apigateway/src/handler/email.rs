@@ -0,0 +1,135 @@
+use crate::{
+    domain::{
+        requests::email::EmailPreviewRequest,
+        response::{api::ApiResponse, email::EmailPreviewResponse},
+    },
+    middleware::{
+        jwt::auth_middleware, roles::require_roles, session::session_middleware, validate::ApiJson,
+    },
+    state::AppState,
+};
+use axum::{
+    Json, extract::Extension, http::StatusCode, middleware, response::IntoResponse, routing::post,
+};
+use shared::{
+    errors::{AppErrorGrpc, HttpError},
+    utils::{EmailTemplateData, preview_email},
+};
+use std::sync::Arc;
+use utoipa_axum::router::OpenApiRouter;
+use validator::Validate;
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/email/preview",
+    tag = "Email",
+    security(("bearer_auth" = [])),
+    request_body = EmailPreviewRequest,
+    responses(
+        (status = 200, description = "Rendered email template", body = ApiResponse<EmailPreviewResponse>),
+        (status = 400, description = "Unknown template or invalid data"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden")
+    )
+)]
+pub async fn preview_email_handler(
+    ApiJson(body): ApiJson<EmailPreviewRequest>,
+) -> Result<impl IntoResponse, HttpError> {
+    body.data
+        .validate()
+        .map_err(|e| HttpError::BadRequest(format!("Invalid template data: {e}")))?;
+
+    let data = EmailTemplateData::from(body.data);
+
+    let rendered =
+        preview_email(&body.template, &data).map_err(|e| HttpError::from(AppErrorGrpc::from(e)))?;
+
+    let response = ApiResponse {
+        status: "success".to_string(),
+        message: "Email template rendered successfully".to_string(),
+        data: EmailPreviewResponse {
+            template: body.template,
+            html: rendered.html,
+            text: rendered.text,
+        },
+    };
+
+    Ok((StatusCode::OK, Json(response)))
+}
+
+pub fn email_routes(app_state: Arc<AppState>) -> OpenApiRouter {
+    OpenApiRouter::new()
+        .route("/api/admin/email/preview", post(preview_email_handler))
+        .route_layer(middleware::from_fn(require_roles))
+        .route_layer(middleware::from_fn(session_middleware))
+        .route_layer(middleware::from_fn(auth_middleware))
+        .layer(Extension(app_state.session.clone()))
+        .layer(Extension(app_state.route_roles.clone()))
+        .layer(Extension(app_state.jwt_config.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::preview_email_handler;
+    use axum::{
+        Router,
+        body::{Body, to_bytes},
+        http::{Request, StatusCode, header::CONTENT_TYPE},
+        routing::post,
+    };
+    use serde_json::{Value, json};
+    use tower::ServiceExt;
+
+    async fn preview(body: Value) -> (StatusCode, Value) {
+        let router = Router::new().route("/api/admin/email/preview", post(preview_email_handler));
+        let request = Request::post("/api/admin/email/preview")
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+
+        let response = router.oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    fn data(link: &str) -> Value {
+        json!({
+            "title": "Welcome to SanEdge",
+            "message": "Verify <b>now</b> to get started.",
+            "button": "Verify Account",
+            "link": link,
+        })
+    }
+
+    #[tokio::test]
+    async fn renders_html_and_text_from_the_sample_data() {
+        let link = "https://sanedge.example.com/login?verify_code=123456";
+        let (status, body) = preview(json!({ "template": "email", "data": data(link) })).await;
+
+        assert_eq!(status, StatusCode::OK);
+        let html = body["data"]["html"].as_str().unwrap();
+        assert!(html.contains("Welcome to SanEdge"));
+        assert!(html.contains("Verify Account"));
+        assert!(
+            html.contains("Verify &#60;b&#62;now&#60;/b&#62;"),
+            "sample data is escaped like real data: {html}"
+        );
+        assert_eq!(
+            body["data"]["text"],
+            format!(
+                "Welcome to SanEdge\n\nVerify <b>now</b> to get started.\n\nVerify Account: {link}\n"
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn unknown_templates_and_bad_data_are_rejected() {
+        let link = "https://sanedge.example.com/login";
+        let (status, _) = preview(json!({ "template": "invoice", "data": data(link) })).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, _) = preview(json!({ "template": "email", "data": data("not a url") })).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
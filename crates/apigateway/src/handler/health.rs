@@ -0,0 +1,70 @@
+use crate::{
+    abstract_trait::health::DynHealthGrpcClient,
+    domain::response::health::ServicesHealthResponse,
+    middleware::{jwt::auth_middleware, roles::require_roles, session::session_middleware},
+    state::AppState,
+};
+use axum::{
+    Json,
+    extract::{Extension, State},
+    http::StatusCode,
+    middleware,
+    response::IntoResponse,
+    routing::get,
+};
+use shared::errors::HttpError;
+use std::sync::Arc;
+use utoipa_axum::router::OpenApiRouter;
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/health/services",
+    tag = "Health",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Every downstream service answered", body = ServicesHealthResponse),
+        (status = 503, description = "One or more downstream services are unhealthy", body = ServicesHealthResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden")
+    )
+)]
+pub async fn services_health_handler(
+    Extension(health): Extension<DynHealthGrpcClient>,
+) -> Result<impl IntoResponse, HttpError> {
+    let response = health.check_all().await;
+
+    let status = if response.status == "healthy" {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    Ok((status, Json(response)))
+}
+
+/// Unauthenticated readiness probe for Kubernetes: 503 until every
+/// downstream gRPC service answers its health check, so a gateway pod only
+/// takes traffic once it can reach them.
+pub async fn readiness_handler(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let response = state.di_container.health_clients.check_all().await;
+
+    let status = if response.status == "healthy" {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (status, Json(response))
+}
+
+pub fn health_routes(app_state: Arc<AppState>) -> OpenApiRouter {
+    OpenApiRouter::new()
+        .route("/api/admin/health/services", get(services_health_handler))
+        .route_layer(middleware::from_fn(require_roles))
+        .route_layer(middleware::from_fn(session_middleware))
+        .route_layer(middleware::from_fn(auth_middleware))
+        .layer(Extension(app_state.di_container.health_clients.clone()))
+        .layer(Extension(app_state.session.clone()))
+        .layer(Extension(app_state.route_roles.clone()))
+        .layer(Extension(app_state.jwt_config.clone()))
+}
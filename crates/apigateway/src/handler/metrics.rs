@@ -0,0 +1,60 @@
+use crate::state::AppState;
+use axum::{
+    body::Body,
+    extract::State,
+    http::{StatusCode, header::CONTENT_TYPE},
+    response::{IntoResponse, Response},
+};
+use prometheus_client::encoding::text::encode;
+use std::sync::Arc;
+
+/// Combined exposition of the gateway and every `METRICS_SCRAPE_TARGETS`
+/// service; 404 when no targets are configured.
+pub async fn all_metrics_handler(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    if !state.metrics_scraper.is_enabled() {
+        return Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Body::from("Batch metrics scrape is not configured"))
+            .unwrap();
+    }
+
+    let mut gateway = String::new();
+
+    if let Err(e) = encode(&mut gateway, &state.registry) {
+        return Response::builder()
+            .status(StatusCode::INTERNAL_SERVER_ERROR)
+            .body(Body::from(format!("Failed to encode metrics: {e}")))
+            .unwrap();
+    }
+
+    let buffer = state.metrics_scraper.scrape_all(gateway).await;
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(
+            CONTENT_TYPE,
+            "application/openmetrics-text; version=1.0.0; charset=utf-8",
+        )
+        .body(Body::from(buffer))
+        .unwrap()
+}
+
+pub async fn metrics_handler(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let mut buffer = String::new();
+
+    if let Err(e) = encode(&mut buffer, &state.registry) {
+        return Response::builder()
+            .status(StatusCode::INTERNAL_SERVER_ERROR)
+            .body(Body::from(format!("Failed to encode metrics: {e}")))
+            .unwrap();
+    }
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(
+            CONTENT_TYPE,
+            "application/openmetrics-text; version=1.0.0; charset=utf-8",
+        )
+        .body(Body::from(buffer))
+        .unwrap()
+}
@@ -1,29 +1,110 @@
 mod auth;
+mod email;
+mod health;
+mod metrics;
 mod order;
 mod order_item;
 mod product;
 mod role;
 mod user;
 
-use crate::state::AppState;
+use crate::{
+    config::{DeprecationConfig, RouteRoles},
+    middleware::{
+        deadline::deadline_middleware, deprecation::deprecation_middleware,
+        https::enforce_https_middleware, in_flight::in_flight_middleware,
+        read_only::read_only_middleware, request_id::request_id_middleware,
+        slow_request::slow_request_middleware, timezone::display_timezone_middleware,
+    },
+    state::AppState,
+};
 use anyhow::Result;
-use axum::extract::DefaultBodyLimit;
+use axum::{Extension, Router, extract::DefaultBodyLimit, http::Method, middleware, routing::get};
 use shared::utils::shutdown_signal;
-use std::sync::Arc;
+use std::{net::SocketAddr, sync::Arc};
 use tokio::net::TcpListener;
 use tower_http::limit::RequestBodyLimitLayer;
-use tracing::info;
-use utoipa::{Modify, OpenApi, openapi::security::SecurityScheme};
+use tracing::{info, warn};
+use utoipa::{
+    Modify, OpenApi,
+    openapi::{
+        Deprecated,
+        path::{Operation, PathItem},
+        security::SecurityScheme,
+        server::Server,
+    },
+};
 use utoipa_axum::router::OpenApiRouter;
 use utoipa_swagger_ui::SwaggerUi;
 
 pub use self::auth::auth_routes;
+pub use self::email::email_routes;
+pub use self::health::{health_routes, readiness_handler};
+pub use self::metrics::{all_metrics_handler, metrics_handler};
 pub use self::order::order_routes;
 pub use self::order_item::order_item_routes;
 pub use self::product::product_routes;
 pub use self::role::roles_routes;
 pub use self::user::user_routes;
 
+const ADMIN: &[&str] = &["ROLE_ADMIN"];
+const STAFF: &[&str] = &["ROLE_ADMIN", "ROLE_MODERATOR"];
+
+/// Roles the caller's access token must carry, any one of them, to reach a
+/// route; `require_roles` answers 403 otherwise. Routes not listed only need
+/// a valid token, and handlers may still narrow access further.
+pub const ROUTE_ROLES: &[RouteRoles] = &[
+    ("GET", "/api/admin/users/{id}/lockout", ADMIN),
+    ("POST", "/api/admin/users/{id}/unlock", ADMIN),
+    ("POST", "/api/admin/email/preview", ADMIN),
+    ("GET", "/api/admin/health/services", ADMIN),
+    ("GET", "/api/users", STAFF),
+    ("GET", "/api/users/active", STAFF),
+    ("GET", "/api/users/trashed", STAFF),
+    ("DELETE", "/api/users/trash/{id}", ADMIN),
+    ("PUT", "/api/users/restore/{id}", ADMIN),
+    ("DELETE", "/api/users/delete/{id}", ADMIN),
+    ("PUT", "/api/users/restore-all", ADMIN),
+    ("DELETE", "/api/users/delete-all", ADMIN),
+    ("GET", "/api/roles", STAFF),
+    ("GET", "/api/roles/active", STAFF),
+    ("GET", "/api/roles/trashed", STAFF),
+    ("POST", "/api/roles", ADMIN),
+    ("PUT", "/api/roles/{id}", ADMIN),
+    ("DELETE", "/api/roles/trash/{id}", ADMIN),
+    ("PUT", "/api/roles/restore/{id}", ADMIN),
+    ("DELETE", "/api/roles/delete/{id}", ADMIN),
+    ("PUT", "/api/roles/restore-all", ADMIN),
+    ("DELETE", "/api/roles/delete-all", ADMIN),
+    ("GET", "/api/products", STAFF),
+    ("GET", "/api/products/active", STAFF),
+    ("GET", "/api/products/trashed", STAFF),
+    ("GET", "/api/products/low-stock", STAFF),
+    ("GET", "/api/products/{id}", STAFF),
+    ("POST", "/api/products", STAFF),
+    ("POST", "/api/products/import", STAFF),
+    ("PUT", "/api/products/{id}", STAFF),
+    ("DELETE", "/api/products/trash/{id}", ADMIN),
+    ("PUT", "/api/products/restore/{id}", ADMIN),
+    ("DELETE", "/api/products/delete/{id}", ADMIN),
+    ("PUT", "/api/products/restore-all", ADMIN),
+    ("DELETE", "/api/products/delete-all", ADMIN),
+    ("GET", "/api/orders", STAFF),
+    ("GET", "/api/orders/active", STAFF),
+    ("GET", "/api/orders/trashed", STAFF),
+    ("GET", "/api/orders/{id}", STAFF),
+    ("GET", "/api/orders/{id}/detail", STAFF),
+    ("DELETE", "/api/orders/trash/{id}", ADMIN),
+    ("PUT", "/api/orders/restore/{id}", ADMIN),
+    ("DELETE", "/api/orders/delete/{id}", ADMIN),
+    ("PUT", "/api/orders/restore-all", ADMIN),
+    ("DELETE", "/api/orders/delete-all", ADMIN),
+    ("GET", "/api/order-items", STAFF),
+    ("GET", "/api/order-items/active", STAFF),
+    ("GET", "/api/order-items/trashed", STAFF),
+    ("GET", "/api/order-items/{order_item}", STAFF),
+];
+
 #[derive(OpenApi)]
 #[openapi(
     paths(
@@ -31,14 +112,23 @@ pub use self::user::user_routes;
         auth::get_me_handler,
         auth::register_user_handler,
         auth::verify_code_handler,
+        auth::resend_verification_handler,
         auth::forgot_password_handler,
         auth::reset_password_handler,
         auth::refresh_token_handler,
+        auth::list_sessions_handler,
+        auth::revoke_session_handler,
+        auth::rate_limit_status_handler,
+        auth::lockout_status_handler,
+        auth::unlock_account_handler,
 
         user::get_users,
         user::get_active_users,
         user::get_trashed_users,
         user::get_user,
+        user::export_user,
+        user::get_user_orders,
+        user::anonymize_user_handler,
         user::update_user,
         user::trash_user_handler,
         user::restore_user_handler,
@@ -63,8 +153,10 @@ pub use self::user::user_routes;
         product::get_products,
         product::get_active_products,
         product::get_trashed_products,
+        product::get_low_stock_products,
         product::get_product,
         product::create_product,
+        product::import_products,
         product::update_product,
         product::trash_product_handler,
         product::restore_product_handler,
@@ -77,6 +169,7 @@ pub use self::user::user_routes;
         order::get_active_orders,
         order::get_trashed_orders,
         order::get_order,
+        order::get_order_detail,
         order::create_order,
         order::update_order,
         order::trash_order_handler,
@@ -90,6 +183,10 @@ pub use self::user::user_routes;
         order_item::get_trashed_order_items,
         order_item::get_items_by_order_id,
 
+        email::preview_email_handler,
+
+        health::services_health_handler,
+
     ),
     modifiers(&SecurityAddon),
     tags(
@@ -99,6 +196,9 @@ pub use self::user::user_routes;
         (name = "Product", description = "Product endpoints"),
         (name = "Order", description = "Order endpoints"),
         (name = "Order-item", description = "Order Item endpoints"),
+        (name = "Email", description = "Email template endpoints"),
+        (name = "Health", description = "Downstream service health endpoints"),
+        (name = "Deprecated", description = "Endpoints scheduled for removal"),
     )
 )]
 struct ApiDoc;
@@ -118,10 +218,73 @@ impl Modify for SecurityAddon {
     }
 }
 
+fn operation_mut<'a>(item: &'a mut PathItem, method: &Method) -> Option<&'a mut Operation> {
+    match *method {
+        Method::GET => item.get.as_mut(),
+        Method::POST => item.post.as_mut(),
+        Method::PUT => item.put.as_mut(),
+        Method::DELETE => item.delete.as_mut(),
+        Method::PATCH => item.patch.as_mut(),
+        _ => None,
+    }
+}
+
+/// Flags the `DEPRECATED_ROUTES` operations as deprecated, adds them to the
+/// `Deprecated` tag and notes their sunset date.
+fn mark_deprecated(openapi: &mut utoipa::openapi::OpenApi, deprecations: &DeprecationConfig) {
+    for route in deprecations.routes() {
+        let operation = openapi
+            .paths
+            .paths
+            .get_mut(&route.path)
+            .and_then(|item| operation_mut(item, &route.method));
+
+        let Some(operation) = operation else {
+            warn!(
+                "DEPRECATED_ROUTES lists {} {}, which is not in the API docs",
+                route.method, route.path
+            );
+            continue;
+        };
+
+        operation.deprecated = Some(Deprecated::True);
+        operation
+            .tags
+            .get_or_insert_with(Vec::new)
+            .push("Deprecated".to_string());
+
+        if let Some(sunset) = route.sunset {
+            let note = format!("Deprecated, to be removed on {sunset}.");
+            operation.description = Some(match operation.description.take() {
+                Some(description) if !description.is_empty() => format!("{description}\n\n{note}"),
+                _ => note,
+            });
+        }
+    }
+}
+
+/// Nests `routes` under `base_path`, points the OpenAPI servers entry at it
+/// and adds Swagger UI and the OpenAPI document below the same prefix.
+fn mount(routes: Router, mut api: utoipa::openapi::OpenApi, base_path: &str) -> Router {
+    let routes = if base_path.is_empty() {
+        routes
+    } else {
+        api.servers = Some(vec![Server::new(base_path)]);
+        Router::new().nest(base_path, routes)
+    };
+
+    routes.merge(
+        SwaggerUi::new(format!("{base_path}/swagger-ui"))
+            .url(format!("{base_path}/api-docs/openapi.json"), api),
+    )
+}
+
 pub struct AppRouter;
 
 impl AppRouter {
-    pub async fn serve(port: u16, app_state: AppState) -> Result<()> {
+    /// Serves every route, the metrics endpoint and Swagger UI under
+    /// `base_path` (empty for the root).
+    pub async fn serve(port: u16, base_path: &str, app_state: AppState) -> Result<()> {
         let shared_state = Arc::new(app_state);
 
         let api_router = OpenApiRouter::with_openapi(ApiDoc::openapi())
@@ -131,30 +294,257 @@ impl AppRouter {
             .merge(roles_routes(shared_state.clone()))
             .merge(product_routes(shared_state.clone()))
             .merge(order_routes(shared_state.clone()))
-            .merge(order_item_routes(shared_state.clone()));
+            .merge(order_item_routes(shared_state.clone()))
+            .merge(email_routes(shared_state.clone()))
+            .merge(health_routes(shared_state.clone()));
 
         let router_with_layers = api_router
+            .route_layer(middleware::from_fn_with_state(
+                shared_state.deprecations.clone(),
+                deprecation_middleware,
+            ))
+            .layer(Extension(shared_state.json_body))
             .layer(DefaultBodyLimit::disable())
-            .layer(RequestBodyLimitLayer::new(2 * 1024 * 1024));
+            .layer(RequestBodyLimitLayer::new(2 * 1024 * 1024))
+            .layer(middleware::from_fn_with_state(
+                shared_state.display_timezone,
+                display_timezone_middleware,
+            ))
+            .layer(middleware::from_fn_with_state(
+                shared_state.read_only,
+                read_only_middleware,
+            ))
+            .layer(middleware::from_fn_with_state(
+                shared_state.request_budget.clone(),
+                deadline_middleware,
+            ))
+            .layer(middleware::from_fn_with_state(
+                shared_state.clone(),
+                slow_request_middleware,
+            ))
+            .layer(middleware::from_fn_with_state(
+                shared_state.clone(),
+                in_flight_middleware,
+            ))
+            .layer(middleware::from_fn_with_state(
+                shared_state.https.clone(),
+                enforce_https_middleware,
+            ))
+            .layer(middleware::from_fn(request_id_middleware));
+
+        let (app_router, mut api) = router_with_layers.split_for_parts();
+
+        mark_deprecated(&mut api, &shared_state.deprecations);
 
-        let (app_router, api) = router_with_layers.split_for_parts();
+        let metrics_router = Router::new()
+            .route("/metrics", get(metrics_handler))
+            .route("/metrics/all", get(all_metrics_handler))
+            .route("/ready", get(readiness_handler))
+            .with_state(shared_state.clone());
 
-        let app = app_router
-            .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", api.clone()));
+        let app = mount(app_router.merge(metrics_router), api, base_path);
 
         let addr = format!("0.0.0.0:{port}");
         let listener = TcpListener::bind(&addr).await?;
 
         info!("🚀 Server running on http://{}", listener.local_addr()?);
         info!("📚 API Documentation available at:");
-        info!("   📖 Swagger UI: http://localhost:{port}/swagger-ui");
-        info!("   📊 Metrics: http://localhost:{port}/metrics");
+        info!("   📖 Swagger UI: http://localhost:{port}{base_path}/swagger-ui");
+        info!("   📊 Metrics: http://localhost:{port}{base_path}/metrics");
+        info!("   ✅ Readiness: http://localhost:{port}{base_path}/ready");
 
-        axum::serve(listener, app)
-            .with_graceful_shutdown(shutdown_signal())
-            .await
-            .unwrap();
+        axum::serve(
+            listener,
+            app.into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .with_graceful_shutdown(shutdown_signal())
+        .await
+        .unwrap();
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{ApiDoc, ROUTE_ROLES, mark_deprecated, mount, operation_mut};
+    use crate::{
+        config::{DeprecationConfig, RouteRolesConfig},
+        middleware::{
+            deprecation::deprecation_middleware,
+            roles::{TokenRoles, require_roles},
+        },
+    };
+    use axum::{
+        Extension, Router,
+        body::{Body, to_bytes},
+        http::{Method, Request, StatusCode},
+        middleware,
+        routing::{delete, get},
+    };
+    use serde_json::Value;
+    use tower::ServiceExt;
+    use utoipa::{OpenApi, openapi::Deprecated};
+
+    fn router(roles: &[&str]) -> Router {
+        Router::new()
+            .route("/api/orders", get(|| async { "ok" }))
+            .route("/api/orders/trash/{id}", delete(|| async { "ok" }))
+            .route("/api/auth/me", get(|| async { "ok" }))
+            .route_layer(middleware::from_fn(require_roles))
+            .layer(Extension(TokenRoles(
+                roles.iter().map(|role| role.to_string()).collect(),
+            )))
+            .layer(Extension(RouteRolesConfig::init(ROUTE_ROLES)))
+    }
+
+    async fn status(roles: &[&str], method: Method, uri: &str) -> StatusCode {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .body(Body::empty())
+            .unwrap();
+
+        router(roles).oneshot(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn routes_answer_403_without_a_listed_role() {
+        let user = &["ROLE_USER"];
+        let moderator = &["ROLE_MODERATOR"];
+        let admin = &["ROLE_ADMIN"];
+
+        assert_eq!(
+            status(user, Method::GET, "/api/orders").await,
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            status(moderator, Method::GET, "/api/orders").await,
+            StatusCode::OK
+        );
+
+        let trash = "/api/orders/trash/1";
+        assert_eq!(
+            status(moderator, Method::DELETE, trash).await,
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(status(admin, Method::DELETE, trash).await, StatusCode::OK);
+
+        assert_eq!(
+            status(user, Method::GET, "/api/auth/me").await,
+            StatusCode::OK
+        );
+    }
+
+    #[test]
+    fn every_route_role_names_a_documented_route() {
+        let api = ApiDoc::openapi();
+
+        for (method, path, _) in ROUTE_ROLES {
+            let method: Method = method.parse().unwrap();
+            let documented = api
+                .paths
+                .paths
+                .get(*path)
+                .cloned()
+                .is_some_and(|mut item| operation_mut(&mut item, &method).is_some());
+
+            assert!(documented, "{method} {path} is not a documented route");
+        }
+    }
+
+    async fn get_from(app: &Router, uri: &str) -> (StatusCode, Vec<u8>) {
+        let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, body.to_vec())
+    }
+
+    #[tokio::test]
+    async fn routes_and_docs_are_served_under_the_base_path() {
+        let routes = Router::new()
+            .route("/api/products", get(|| async { "products" }))
+            .route("/metrics", get(|| async { "metrics" }));
+        let app = mount(routes, ApiDoc::openapi(), "/api/v1");
+
+        for (uri, body) in [
+            ("/api/v1/api/products", "products"),
+            ("/api/v1/metrics", "metrics"),
+        ] {
+            assert_eq!(get_from(&app, uri).await, (StatusCode::OK, body.into()));
+        }
+        for uri in ["/api/products", "/metrics", "/api-docs/openapi.json"] {
+            assert_eq!(get_from(&app, uri).await.0, StatusCode::NOT_FOUND, "{uri}");
+        }
+
+        let (status, body) = get_from(&app, "/api/v1/api-docs/openapi.json").await;
+        assert_eq!(status, StatusCode::OK);
+        let doc: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(doc["servers"], serde_json::json!([{ "url": "/api/v1" }]));
+    }
+
+    #[tokio::test]
+    async fn empty_base_path_serves_from_the_root() {
+        let routes = Router::new().route("/api/products", get(|| async { "products" }));
+        let app = mount(routes, ApiDoc::openapi(), "");
+
+        assert_eq!(get_from(&app, "/api/products").await.0, StatusCode::OK);
+
+        let (status, body) = get_from(&app, "/api-docs/openapi.json").await;
+        assert_eq!(status, StatusCode::OK);
+        let doc: Value = serde_json::from_slice(&body).unwrap();
+        assert!(doc.get("servers").is_none());
+    }
+
+    #[tokio::test]
+    async fn deprecated_routes_are_flagged_in_the_docs_and_the_response() {
+        let deprecations =
+            DeprecationConfig::parse("GET /api/orders/active=2026-06-30", "/api/v1").unwrap();
+
+        let mut api = ApiDoc::openapi();
+        mark_deprecated(&mut api, &deprecations);
+        let operation = |api: &mut utoipa::openapi::OpenApi, path: &str| {
+            let item = api.paths.paths.get_mut(path).unwrap();
+            operation_mut(item, &Method::GET).unwrap().clone()
+        };
+
+        let active = operation(&mut api, "/api/orders/active");
+        assert!(matches!(active.deprecated, Some(Deprecated::True)));
+        assert!(active.tags.unwrap().contains(&"Deprecated".to_string()));
+        assert!(
+            active
+                .description
+                .unwrap()
+                .ends_with("Deprecated, to be removed on 2026-06-30.")
+        );
+        let listing = operation(&mut api, "/api/orders");
+        assert!(!matches!(listing.deprecated, Some(Deprecated::True)));
+
+        let routes = Router::new()
+            .route("/api/orders/active", get(|| async { "active" }))
+            .route("/api/orders", get(|| async { "orders" }))
+            .route_layer(middleware::from_fn_with_state(
+                deprecations,
+                deprecation_middleware,
+            ));
+        let app = mount(routes, api, "/api/v1");
+        let headers = async |uri: &str| {
+            let request = Request::get(uri).body(Body::empty()).unwrap();
+            app.clone()
+                .oneshot(request)
+                .await
+                .unwrap()
+                .headers()
+                .clone()
+        };
+
+        let active = headers("/api/v1/api/orders/active").await;
+        assert_eq!(active["deprecation"], "true");
+        assert_eq!(active["sunset"], "Tue, 30 Jun 2026 00:00:00 GMT");
+
+        let listing = headers("/api/v1/api/orders").await;
+        assert!(!listing.contains_key("deprecation"));
+        assert!(!listing.contains_key("sunset"));
+    }
+}
@@ -1,16 +1,25 @@
 use crate::{
-    abstract_trait::{order::DynOrderGrpcClient, session::DynSessionMiddleware},
+    abstract_trait::order::DynOrderGrpcClient,
+    config::{CurrencyConfig, DeleteSemanticsConfig},
     domain::{
-        requests::order::{CreateOrderRequest, FindAllOrder, UpdateOrderRequest},
+        requests::{
+            entity_id::EntityId,
+            format::FormatParams,
+            order::{CreateOrderRequest, FindAllOrder, UpdateOrderRequest},
+        },
         response::{
             api::{ApiResponse, ApiResponsePagination},
-            order::{OrderResponse, OrderResponseDeleteAt},
+            order::{OrderDetailResponse, OrderResponse, OrderResponseDeleteAt},
         },
     },
 };
 use crate::{
+    handler::STAFF,
     middleware::{
-        jwt::auth_middleware, rate_limit::rate_limit_middleware, session::session_middleware,
+        jwt::auth_middleware,
+        rate_limit::rate_limit_middleware,
+        roles::{TokenRoles, require_roles},
+        session::session_middleware,
         validate::SimpleValidatedJson,
     },
     state::AppState,
@@ -18,52 +27,50 @@ use crate::{
 use axum::{
     Json,
     extract::{Extension, Path, Query},
-    http::StatusCode,
+    http::{HeaderMap, StatusCode, header::ACCEPT_LANGUAGE},
     middleware,
     response::IntoResponse,
     routing::{delete, get, post, put},
 };
 use serde_json::json;
-use shared::errors::HttpError;
+use shared::errors::{HttpError, InsufficientStockResponse, ValidationErrorResponse};
 use std::sync::Arc;
 use utoipa_axum::router::OpenApiRouter;
 
+const IDEMPOTENCY_KEY: &str = "idempotency-key";
+const MAX_IDEMPOTENCY_KEY_LEN: usize = 255;
+
 #[utoipa::path(
     get,
     path = "/api/orders",
     tag = "Order",
     security(("bearer_auth" = [])),
-    params(FindAllOrder),
+    params(FindAllOrder, FormatParams),
     responses(
         (status = 200, description = "List of orders", body = ApiResponsePagination<Vec<OrderResponse>>),
+        (status = 400, description = "Malformed cursor, invalid sort or invalid created_from/created_to", body = ValidationErrorResponse),
         (status = 401, description = "Unauthorized"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn get_orders(
     Extension(service): Extension<DynOrderGrpcClient>,
-    Extension(user_id): Extension<i32>,
-    Extension(session): Extension<DynSessionMiddleware>,
     Query(params): Query<FindAllOrder>,
+    Extension(currency): Extension<CurrencyConfig>,
+    Query(format): Query<FormatParams>,
+    headers: HeaderMap,
 ) -> Result<impl IntoResponse, HttpError> {
-    let key = format!("session:{user_id}");
-
-    let current_session = session
-        .get_session(&key)
-        .await
-        .ok_or_else(|| HttpError::Unauthorized("Session expired or not found".to_string()))?;
+    let mut response = service.find_all(&params).await?;
 
-    if !current_session
-        .roles
-        .iter()
-        .any(|r| r == "ROLE_ADMIN" || r == "ROLE_MODERATOR")
-    {
-        return Err(HttpError::Forbidden(
-            "Access denied. Required role: ADMIN or MODERATOR".to_string(),
-        ));
+    if format.format {
+        let accept_language = headers.get(ACCEPT_LANGUAGE).and_then(|v| v.to_str().ok());
+        response.data = response
+            .data
+            .into_iter()
+            .map(|item| item.with_formatted_price(&currency, accept_language))
+            .collect();
     }
 
-    let response = service.find_all(&params).await?;
     Ok((StatusCode::OK, Json(response)))
 }
 
@@ -72,7 +79,7 @@ pub async fn get_orders(
     path = "/api/orders/active",
     tag = "Order",
     security(("bearer_auth" = [])),
-    params(FindAllOrder),
+    params(FindAllOrder, FormatParams),
     responses(
         (status = 200, description = "List of active orders", body = ApiResponsePagination<Vec<OrderResponse>>),
         (status = 401, description = "Unauthorized"),
@@ -81,28 +88,22 @@ pub async fn get_orders(
 )]
 pub async fn get_active_orders(
     Extension(service): Extension<DynOrderGrpcClient>,
-    Extension(user_id): Extension<i32>,
-    Extension(session): Extension<DynSessionMiddleware>,
     Query(params): Query<FindAllOrder>,
+    Extension(currency): Extension<CurrencyConfig>,
+    Query(format): Query<FormatParams>,
+    headers: HeaderMap,
 ) -> Result<impl IntoResponse, HttpError> {
-    let key = format!("session:{user_id}");
+    let mut response = service.find_active(&params).await?;
 
-    let current_session = session
-        .get_session(&key)
-        .await
-        .ok_or_else(|| HttpError::Unauthorized("Session expired or not found".to_string()))?;
-
-    if !current_session
-        .roles
-        .iter()
-        .any(|r| r == "ROLE_ADMIN" || r == "ROLE_MODERATOR")
-    {
-        return Err(HttpError::Forbidden(
-            "Access denied. Required role: ADMIN or MODERATOR".to_string(),
-        ));
+    if format.format {
+        let accept_language = headers.get(ACCEPT_LANGUAGE).and_then(|v| v.to_str().ok());
+        response.data = response
+            .data
+            .into_iter()
+            .map(|item| item.with_formatted_price(&currency, accept_language))
+            .collect();
     }
 
-    let response = service.find_active(&params).await?;
     Ok((StatusCode::OK, Json(response)))
 }
 
@@ -111,7 +112,7 @@ pub async fn get_active_orders(
     path = "/api/orders/trashed",
     tag = "Order",
     security(("bearer_auth" = [])),
-    params(FindAllOrder),
+    params(FindAllOrder, FormatParams),
     responses(
         (status = 200, description = "List of soft-deleted orders", body = ApiResponsePagination<Vec<OrderResponseDeleteAt>>),
         (status = 401, description = "Unauthorized"),
@@ -120,28 +121,22 @@ pub async fn get_active_orders(
 )]
 pub async fn get_trashed_orders(
     Extension(service): Extension<DynOrderGrpcClient>,
-    Extension(user_id): Extension<i32>,
-    Extension(session): Extension<DynSessionMiddleware>,
     Query(params): Query<FindAllOrder>,
+    Extension(currency): Extension<CurrencyConfig>,
+    Query(format): Query<FormatParams>,
+    headers: HeaderMap,
 ) -> Result<impl IntoResponse, HttpError> {
-    let key = format!("session:{user_id}");
-
-    let current_session = session
-        .get_session(&key)
-        .await
-        .ok_or_else(|| HttpError::Unauthorized("Session expired or not found".to_string()))?;
+    let mut response = service.find_trashed(&params).await?;
 
-    if !current_session
-        .roles
-        .iter()
-        .any(|r| r == "ROLE_ADMIN" || r == "ROLE_MODERATOR")
-    {
-        return Err(HttpError::Forbidden(
-            "Access denied. Required role: ADMIN or MODERATOR".to_string(),
-        ));
+    if format.format {
+        let accept_language = headers.get(ACCEPT_LANGUAGE).and_then(|v| v.to_str().ok());
+        response.data = response
+            .data
+            .into_iter()
+            .map(|item| item.with_formatted_price(&currency, accept_language))
+            .collect();
     }
 
-    let response = service.find_trashed(&params).await?;
     Ok((StatusCode::OK, Json(response)))
 }
 
@@ -150,7 +145,7 @@ pub async fn get_trashed_orders(
     path = "/api/orders/{id}",
     tag = "Order",
     security(("bearer_auth" = [])),
-    params(("id" = i32, Path, description = "Order ID")),
+    params(("id" = String, Path, description = "Order ID or external UUID"), FormatParams),
     responses(
         (status = 200, description = "Order details", body = ApiResponse<OrderResponse>),
         (status = 404, description = "Order not found"),
@@ -159,29 +154,56 @@ pub async fn get_trashed_orders(
 )]
 pub async fn get_order(
     Extension(service): Extension<DynOrderGrpcClient>,
-    Extension(user_id): Extension<i32>,
-    Extension(session): Extension<DynSessionMiddleware>,
-    Path(id): Path<i32>,
-    Extension(_user_id): Extension<i64>,
+    Path(id): Path<EntityId>,
+    Extension(currency): Extension<CurrencyConfig>,
+    Query(format): Query<FormatParams>,
+    headers: HeaderMap,
 ) -> Result<impl IntoResponse, HttpError> {
-    let key = format!("session:{user_id}");
+    let mut response = match id {
+        EntityId::Id(id) => service.find_by_id(id).await?,
+        EntityId::External(external_id) => service.find_by_external_id(external_id).await?,
+    };
 
-    let current_session = session
-        .get_session(&key)
-        .await
-        .ok_or_else(|| HttpError::Unauthorized("Session expired or not found".to_string()))?;
+    if format.format {
+        let accept_language = headers.get(ACCEPT_LANGUAGE).and_then(|v| v.to_str().ok());
+        response.data = response
+            .data
+            .with_formatted_price(&currency, accept_language);
+    }
 
-    if !current_session
-        .roles
-        .iter()
-        .any(|r| r == "ROLE_ADMIN" || r == "ROLE_MODERATOR")
-    {
-        return Err(HttpError::Forbidden(
-            "Access denied. Required role: ADMIN or MODERATOR".to_string(),
-        ));
+    Ok(Json(response))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/orders/{id}/detail",
+    tag = "Order",
+    security(("bearer_auth" = [])),
+    params(("id" = String, Path, description = "Order ID or external UUID"), FormatParams),
+    responses(
+        (status = 200, description = "Order with each item's current product name and price", body = ApiResponse<OrderDetailResponse>),
+        (status = 404, description = "Order not found"),
+        (status = 401, description = "Unauthorized"),
+        (status = 503, description = "Product service unavailable")
+    )
+)]
+pub async fn get_order_detail(
+    Extension(service): Extension<DynOrderGrpcClient>,
+    Path(id): Path<EntityId>,
+    Extension(currency): Extension<CurrencyConfig>,
+    Query(format): Query<FormatParams>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, HttpError> {
+    let id = resolve_order_id(&service, id).await?;
+    let mut response = service.find_detail_by_id(id).await?;
+
+    if format.format {
+        let accept_language = headers.get(ACCEPT_LANGUAGE).and_then(|v| v.to_str().ok());
+        response.data = response
+            .data
+            .with_formatted_price(&currency, accept_language);
     }
 
-    let response = service.find_by_id(id).await?;
     Ok(Json(response))
 }
 
@@ -190,18 +212,41 @@ pub async fn get_order(
     path = "/api/orders",
     tag = "Order",
     security(("bearer_auth" = [])),
+    params(
+        ("Idempotency-Key" = Option<String>, Header, description = "Replays the original order when a create is retried within 24 hours")
+    ),
     request_body = CreateOrderRequest,
     responses(
-        (status = 201, description = "Order created", body = ApiResponse<OrderResponse>),
+        (status = 201, description = "Order created, or replayed for a repeated Idempotency-Key", body = ApiResponse<OrderResponse>),
         (status = 400, description = "Validation error"),
+        (status = 409, description = "Insufficient stock, or Idempotency-Key reused with a different body", body = InsufficientStockResponse),
         (status = 401, description = "Unauthorized"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn create_order(
     Extension(service): Extension<DynOrderGrpcClient>,
-    SimpleValidatedJson(body): SimpleValidatedJson<CreateOrderRequest>,
+    headers: HeaderMap,
+    SimpleValidatedJson(mut body): SimpleValidatedJson<CreateOrderRequest>,
 ) -> Result<impl IntoResponse, HttpError> {
+    body.idempotency_key = match headers.get(IDEMPOTENCY_KEY) {
+        Some(value) => {
+            let key = value
+                .to_str()
+                .map(str::trim)
+                .map_err(|_| HttpError::BadRequest("Idempotency-Key must be ASCII".into()))?;
+
+            if key.is_empty() || key.len() > MAX_IDEMPOTENCY_KEY_LEN {
+                return Err(HttpError::BadRequest(format!(
+                    "Idempotency-Key must be 1 to {MAX_IDEMPOTENCY_KEY_LEN} characters"
+                )));
+            }
+
+            Some(key.to_string())
+        }
+        None => None,
+    };
+
     let response = service.create_order(&body).await?;
     Ok((StatusCode::CREATED, Json(response)))
 }
@@ -211,21 +256,38 @@ pub async fn create_order(
     path = "/api/orders/{id}",
     tag = "Order",
     security(("bearer_auth" = [])),
-    params(("id" = i32, Path, description = "Order ID")),
+    params(("id" = String, Path, description = "Order ID or external UUID")),
     request_body = UpdateOrderRequest,
     responses(
         (status = 200, description = "Order updated", body = ApiResponse<OrderResponse>),
         (status = 404, description = "Order not found"),
+        (status = 409, description = "Insufficient stock", body = InsufficientStockResponse),
         (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not the order's owner and not ADMIN or MODERATOR"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn update_order(
     Extension(service): Extension<DynOrderGrpcClient>,
-    Path(id): Path<i32>,
+    Path(id): Path<EntityId>,
+    Extension(user_id): Extension<i32>,
+    Extension(roles): Extension<TokenRoles>,
     SimpleValidatedJson(mut body): SimpleValidatedJson<UpdateOrderRequest>,
 ) -> Result<impl IntoResponse, HttpError> {
+    let id = resolve_order_id(&service, id).await?;
+    if !roles.has_any(STAFF) {
+        let owner_id = service.find_by_id(id).await?.data.user_id;
+        roles.require_owner_or(user_id, owner_id, STAFF, "update this order")?;
+        roles.require_owner_or(
+            user_id,
+            body.user_id,
+            STAFF,
+            "give an order to another user",
+        )?;
+    }
+
     body.order_id = Some(id);
+    body.caller_is_staff = roles.has_any(STAFF);
     let response = service.update_order(&body).await?;
     Ok((StatusCode::OK, Json(response)))
 }
@@ -235,19 +297,26 @@ pub async fn update_order(
     path = "/api/orders/trash/{id}",
     tag = "Order",
     security(("bearer_auth" = [])),
-    params(("id" = i32, Path, description = "Order ID")),
+    params(("id" = String, Path, description = "Order ID or external UUID")),
     responses(
         (status = 200, description = "Order soft-deleted", body = ApiResponse<OrderResponseDeleteAt>),
+        (status = 204, description = "Order already trashed or missing; nothing to do"),
+        (status = 404, description = "Order already trashed or missing, with STRICT_DELETE set"),
         (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn trash_order_handler(
     Extension(service): Extension<DynOrderGrpcClient>,
-    Path(id): Path<i32>,
+    Extension(deletes): Extension<DeleteSemanticsConfig>,
+    Path(id): Path<EntityId>,
 ) -> Result<impl IntoResponse, HttpError> {
-    let response = service.trash_order(id).await?;
-    Ok((StatusCode::OK, Json(response)))
+    let id = resolve_order_id(&service, id).await?;
+    let Some(response) = deletes.absorb_missing(service.trash_order(id).await)? else {
+        return Ok(StatusCode::NO_CONTENT.into_response());
+    };
+    Ok((StatusCode::OK, Json(response)).into_response())
 }
 
 #[utoipa::path(
@@ -255,17 +324,21 @@ pub async fn trash_order_handler(
     path = "/api/orders/restore/{id}",
     tag = "Order",
     security(("bearer_auth" = [])),
-    params(("id" = i32, Path, description = "Order ID")),
+    params(("id" = String, Path, description = "Order ID or external UUID")),
     responses(
         (status = 200, description = "Order restored", body = ApiResponse<OrderResponse>),
         (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Order not found"),
+        (status = 409, description = "Order is not trashed"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn restore_order_handler(
     Extension(service): Extension<DynOrderGrpcClient>,
-    Path(id): Path<i32>,
+    Path(id): Path<EntityId>,
 ) -> Result<impl IntoResponse, HttpError> {
+    let id = resolve_order_id(&service, id).await?;
     let response = service.restore_order(id).await?;
     Ok((StatusCode::OK, Json(response)))
 }
@@ -275,25 +348,37 @@ pub async fn restore_order_handler(
     path = "/api/orders/delete/{id}",
     tag = "Order",
     security(("bearer_auth" = [])),
-    params(("id" = i32, Path, description = "Order ID")),
+    params(("id" = String, Path, description = "Order ID or external UUID")),
     responses(
         (status = 200, description = "Order permanently deleted", body = serde_json::Value),
+        (status = 204, description = "Order not trashed or missing; nothing to do"),
+        (status = 404, description = "Order not trashed or missing, with STRICT_DELETE set"),
         (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn delete_order(
     Extension(service): Extension<DynOrderGrpcClient>,
-    Path(id): Path<i32>,
+    Extension(deletes): Extension<DeleteSemanticsConfig>,
+    Path(id): Path<EntityId>,
 ) -> Result<impl IntoResponse, HttpError> {
-    service.delete_order(id).await?;
+    let id = resolve_order_id(&service, id).await?;
+    if deletes
+        .absorb_missing(service.delete_order(id).await)?
+        .is_none()
+    {
+        return Ok(StatusCode::NO_CONTENT.into_response());
+    }
+
     Ok((
         StatusCode::OK,
         Json(json!({
             "status": "success",
             "message": "Order deleted permanently"
         })),
-    ))
+    )
+        .into_response())
 }
 
 #[utoipa::path(
@@ -303,6 +388,7 @@ pub async fn delete_order(
     security(("bearer_auth" = [])),
     responses(
         (status = 200, description = "All trashed orders restored", body = serde_json::Value),
+        (status = 403, description = "Forbidden"),
         (status = 500, description = "Internal server error")
     )
 )]
@@ -327,6 +413,7 @@ pub async fn restore_all_order_handler(
     security(("bearer_auth" = [])),
     responses(
         (status = 200, description = "All trashed orders permanently deleted", body = serde_json::Value),
+        (status = 403, description = "Forbidden"),
         (status = 500, description = "Internal server error")
     )
 )]
@@ -344,12 +431,24 @@ pub async fn delete_all_order_handler(
     ))
 }
 
+/// Maps a path id to the internal integer id the write RPCs take, looking
+/// a UUID up first so every `{id}` route accepts either form.
+async fn resolve_order_id(service: &DynOrderGrpcClient, id: EntityId) -> Result<i32, HttpError> {
+    match id {
+        EntityId::Id(id) => Ok(id),
+        EntityId::External(external_id) => {
+            Ok(service.find_by_external_id(external_id).await?.data.id)
+        }
+    }
+}
+
 pub fn order_routes(app_state: Arc<AppState>) -> OpenApiRouter {
     OpenApiRouter::new()
         .route("/api/orders", get(get_orders))
         .route("/api/orders/active", get(get_active_orders))
         .route("/api/orders/trashed", get(get_trashed_orders))
         .route("/api/orders/{id}", get(get_order))
+        .route("/api/orders/{id}/detail", get(get_order_detail))
         .route("/api/orders", post(create_order))
         .route("/api/orders/{id}", put(update_order))
         .route("/api/orders/trash/{id}", delete(trash_order_handler))
@@ -357,12 +456,216 @@ pub fn order_routes(app_state: Arc<AppState>) -> OpenApiRouter {
         .route("/api/orders/restore-all", put(restore_all_order_handler))
         .route("/api/orders/delete/{id}", delete(delete_order))
         .route("/api/orders/delete-all", delete(delete_all_order_handler))
+        .route_layer(middleware::from_fn(require_roles))
         .route_layer(middleware::from_fn(session_middleware))
         .route_layer(middleware::from_fn(auth_middleware))
         .route_layer(middleware::from_fn(rate_limit_middleware))
         .layer(Extension(app_state.di_container.order_clients.clone()))
         .layer(Extension(app_state.di_container.role_clients.clone()))
         .layer(Extension(app_state.rate_limit.clone()))
+        .layer(Extension(app_state.rate_limit_costs.clone()))
+        .layer(Extension(app_state.rate_limit_groups.clone()))
+        .layer(Extension(app_state.bulk_rate_limit))
+        .layer(Extension(app_state.delete_semantics))
         .layer(Extension(app_state.session.clone()))
+        .layer(Extension(app_state.route_roles.clone()))
+        .layer(Extension(app_state.currency.clone()))
         .layer(Extension(app_state.jwt_config.clone()))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{delete_order, trash_order_handler};
+    use crate::{
+        abstract_trait::order::{DynOrderGrpcClient, OrderGrpcClientTrait},
+        config::DeleteSemanticsConfig,
+        domain::{
+            requests::order::{
+                CreateOrderRequest, FindAllOrder, FindUserOrders, UpdateOrderRequest,
+            },
+            response::{
+                api::{ApiResponse, ApiResponsePagination},
+                order::{OrderDetailResponse, OrderResponse, OrderResponseDeleteAt},
+            },
+        },
+    };
+    use async_trait::async_trait;
+    use axum::{
+        Extension, Router,
+        body::Body,
+        http::{Request, StatusCode},
+        routing::delete,
+    };
+    use shared::errors::HttpError;
+    use std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+    };
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    /// Orders by id, `true` once trashed. Trashing needs a live order and
+    /// deleting a trashed one, as in the order service.
+    struct Orders(Mutex<HashMap<i32, bool>>);
+
+    #[async_trait]
+    impl OrderGrpcClientTrait for Orders {
+        async fn find_all(
+            &self,
+            _req: &FindAllOrder,
+        ) -> Result<ApiResponsePagination<Vec<OrderResponse>>, HttpError> {
+            unimplemented!()
+        }
+        async fn find_active(
+            &self,
+            _req: &FindAllOrder,
+        ) -> Result<ApiResponsePagination<Vec<OrderResponseDeleteAt>>, HttpError> {
+            unimplemented!()
+        }
+        async fn find_trashed(
+            &self,
+            _req: &FindAllOrder,
+        ) -> Result<ApiResponsePagination<Vec<OrderResponseDeleteAt>>, HttpError> {
+            unimplemented!()
+        }
+        async fn find_by_user_id(
+            &self,
+            _user_id: i32,
+            _req: &FindUserOrders,
+        ) -> Result<ApiResponsePagination<Vec<OrderResponse>>, HttpError> {
+            unimplemented!()
+        }
+        async fn find_by_id(&self, _id: i32) -> Result<ApiResponse<OrderResponse>, HttpError> {
+            unimplemented!()
+        }
+        async fn find_detail_by_id(
+            &self,
+            _id: i32,
+        ) -> Result<ApiResponse<OrderDetailResponse>, HttpError> {
+            unimplemented!()
+        }
+        async fn find_by_external_id(
+            &self,
+            _external_id: Uuid,
+        ) -> Result<ApiResponse<OrderResponse>, HttpError> {
+            unimplemented!()
+        }
+        async fn create_order(
+            &self,
+            _req: &CreateOrderRequest,
+        ) -> Result<ApiResponse<OrderResponse>, HttpError> {
+            unimplemented!()
+        }
+        async fn update_order(
+            &self,
+            _req: &UpdateOrderRequest,
+        ) -> Result<ApiResponse<OrderResponse>, HttpError> {
+            unimplemented!()
+        }
+        async fn trash_order(
+            &self,
+            id: i32,
+        ) -> Result<ApiResponse<OrderResponseDeleteAt>, HttpError> {
+            match self.0.lock().unwrap().get_mut(&id) {
+                Some(trashed @ false) => *trashed = true,
+                _ => return Err(HttpError::NotFound("Order not found".into())),
+            }
+
+            Ok(ApiResponse {
+                status: "success".to_string(),
+                message: "Order trashed successfully".to_string(),
+                data: OrderResponseDeleteAt {
+                    id,
+                    external_id: Uuid::nil().to_string(),
+                    order_number: format!("ORD-{id}"),
+                    user_id: 7,
+                    total_price: 1_000,
+                    total_price_formatted: None,
+                    created_at: None,
+                    updated_at: None,
+                    deleted_at: None,
+                    status: "pending".to_string(),
+                },
+            })
+        }
+        async fn restore_order(
+            &self,
+            _id: i32,
+        ) -> Result<ApiResponse<OrderResponseDeleteAt>, HttpError> {
+            unimplemented!()
+        }
+        async fn delete_order(&self, id: i32) -> Result<ApiResponse<()>, HttpError> {
+            let mut orders = self.0.lock().unwrap();
+            if orders.get(&id) != Some(&true) {
+                return Err(HttpError::NotFound("Order not found".into()));
+            }
+            orders.remove(&id);
+
+            Ok(ApiResponse {
+                status: "success".to_string(),
+                message: "Order deleted permanently".to_string(),
+                data: (),
+            })
+        }
+        async fn restore_all_order(&self) -> Result<ApiResponse<()>, HttpError> {
+            unimplemented!()
+        }
+        async fn delete_all_order(&self) -> Result<ApiResponse<()>, HttpError> {
+            unimplemented!()
+        }
+    }
+
+    /// Sends each DELETE in turn against one live order, id 1, and returns
+    /// the statuses.
+    async fn statuses(strict: bool, paths: &[&str]) -> Vec<StatusCode> {
+        let orders: DynOrderGrpcClient = Arc::new(Orders(Mutex::new(HashMap::from([(1, false)]))));
+        let router = Router::new()
+            .route("/api/orders/trash/{id}", delete(trash_order_handler))
+            .route("/api/orders/delete/{id}", delete(delete_order))
+            .layer(Extension(orders))
+            .layer(Extension(DeleteSemanticsConfig { strict }));
+
+        let mut statuses = Vec::new();
+        for path in paths {
+            let request = Request::delete(*path).body(Body::empty()).unwrap();
+            statuses.push(router.clone().oneshot(request).await.unwrap().status());
+        }
+        statuses
+    }
+
+    const REPEATED: [&str; 5] = [
+        "/api/orders/trash/1",
+        "/api/orders/trash/1",
+        "/api/orders/delete/1",
+        "/api/orders/delete/1",
+        "/api/orders/delete/99",
+    ];
+
+    #[tokio::test]
+    async fn repeated_deletes_are_no_content_by_default() {
+        assert_eq!(
+            statuses(false, &REPEATED).await,
+            [
+                StatusCode::OK,
+                StatusCode::NO_CONTENT,
+                StatusCode::OK,
+                StatusCode::NO_CONTENT,
+                StatusCode::NO_CONTENT,
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn repeated_deletes_are_not_found_in_strict_mode() {
+        assert_eq!(
+            statuses(true, &REPEATED).await,
+            [
+                StatusCode::OK,
+                StatusCode::NOT_FOUND,
+                StatusCode::OK,
+                StatusCode::NOT_FOUND,
+                StatusCode::NOT_FOUND,
+            ]
+        );
+    }
+}
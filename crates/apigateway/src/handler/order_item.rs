@@ -1,5 +1,5 @@
 use crate::{
-    abstract_trait::{order_item::DynOrderItemGrpcClient, session::DynSessionMiddleware},
+    abstract_trait::order_item::DynOrderItemGrpcClient,
     domain::{
         requests::order_item::FindAllOrderItems,
         response::{
@@ -10,7 +10,8 @@ use crate::{
 };
 use crate::{
     middleware::{
-        jwt::auth_middleware, rate_limit::rate_limit_middleware, session::session_middleware,
+        jwt::auth_middleware, rate_limit::rate_limit_middleware, roles::require_roles,
+        session::session_middleware,
     },
     state::AppState,
 };
@@ -40,27 +41,8 @@ use utoipa_axum::router::OpenApiRouter;
 )]
 pub async fn get_order_items(
     Extension(service): Extension<DynOrderItemGrpcClient>,
-    Extension(user_id): Extension<i32>,
-    Extension(session): Extension<DynSessionMiddleware>,
     Query(params): Query<FindAllOrderItems>,
 ) -> Result<impl IntoResponse, HttpError> {
-    let key = format!("session:{user_id}");
-
-    let current_session = session
-        .get_session(&key)
-        .await
-        .ok_or_else(|| HttpError::Unauthorized("Session expired or not found".to_string()))?;
-
-    if !current_session
-        .roles
-        .iter()
-        .any(|r| r == "ROLE_ADMIN" || r == "ROLE_MODERATOR")
-    {
-        return Err(HttpError::Forbidden(
-            "Access denied. Required role: ADMIN or MODERATOR".to_string(),
-        ));
-    }
-
     let response = service.find_all(&params).await?;
     Ok((StatusCode::OK, Json(response)))
 }
@@ -79,26 +61,8 @@ pub async fn get_order_items(
 )]
 pub async fn get_active_order_items(
     Extension(service): Extension<DynOrderItemGrpcClient>,
-    Extension(user_id): Extension<i32>,
-    Extension(session): Extension<DynSessionMiddleware>,
     Query(params): Query<FindAllOrderItems>,
 ) -> Result<impl IntoResponse, HttpError> {
-    let key = format!("session:{user_id}");
-
-    let current_session = session
-        .get_session(&key)
-        .await
-        .ok_or_else(|| HttpError::Unauthorized("Session expired or not found".to_string()))?;
-
-    if !current_session
-        .roles
-        .iter()
-        .any(|r| r == "ROLE_ADMIN" || r == "ROLE_MODERATOR")
-    {
-        return Err(HttpError::Forbidden(
-            "Access denied. Required role: ADMIN or MODERATOR".to_string(),
-        ));
-    }
     let response = service.find_by_active(&params).await?;
     Ok((StatusCode::OK, Json(response)))
 }
@@ -117,27 +81,8 @@ pub async fn get_active_order_items(
 )]
 pub async fn get_trashed_order_items(
     Extension(service): Extension<DynOrderItemGrpcClient>,
-    Extension(user_id): Extension<i32>,
-    Extension(session): Extension<DynSessionMiddleware>,
     Query(params): Query<FindAllOrderItems>,
 ) -> Result<impl IntoResponse, HttpError> {
-    let key = format!("session:{user_id}");
-
-    let current_session = session
-        .get_session(&key)
-        .await
-        .ok_or_else(|| HttpError::Unauthorized("Session expired or not found".to_string()))?;
-
-    if !current_session
-        .roles
-        .iter()
-        .any(|r| r == "ROLE_ADMIN" || r == "ROLE_MODERATOR")
-    {
-        return Err(HttpError::Forbidden(
-            "Access denied. Required role: ADMIN or MODERATOR".to_string(),
-        ));
-    }
-
     let response = service.find_by_trashed(&params).await?;
     Ok((StatusCode::OK, Json(response)))
 }
@@ -159,27 +104,8 @@ pub async fn get_trashed_order_items(
 )]
 pub async fn get_items_by_order_id(
     Extension(service): Extension<DynOrderItemGrpcClient>,
-    Extension(user_id): Extension<i32>,
-    Extension(session): Extension<DynSessionMiddleware>,
     Path(order_id): Path<i32>,
 ) -> Result<impl IntoResponse, HttpError> {
-    let key = format!("session:{user_id}");
-
-    let current_session = session
-        .get_session(&key)
-        .await
-        .ok_or_else(|| HttpError::Unauthorized("Session expired or not found".to_string()))?;
-
-    if !current_session
-        .roles
-        .iter()
-        .any(|r| r == "ROLE_ADMIN" || r == "ROLE_MODERATOR")
-    {
-        return Err(HttpError::Forbidden(
-            "Access denied. Required role: ADMIN or MODERATOR".to_string(),
-        ));
-    }
-
     let response = service.find_order_item_by_order(order_id).await?;
     Ok((StatusCode::OK, Json(response)))
 }
@@ -190,12 +116,17 @@ pub fn order_item_routes(app_state: Arc<AppState>) -> OpenApiRouter {
         .route("/api/order-items/active", get(get_active_order_items))
         .route("/api/order-items/trashed", get(get_trashed_order_items))
         .route("/api/order-items/{order_item}", get(get_items_by_order_id))
+        .route_layer(middleware::from_fn(require_roles))
         .route_layer(middleware::from_fn(session_middleware))
         .route_layer(middleware::from_fn(auth_middleware))
         .route_layer(middleware::from_fn(rate_limit_middleware))
         .layer(Extension(app_state.di_container.order_clients.clone()))
         .layer(Extension(app_state.di_container.role_clients.clone()))
         .layer(Extension(app_state.rate_limit.clone()))
+        .layer(Extension(app_state.rate_limit_costs.clone()))
+        .layer(Extension(app_state.rate_limit_groups.clone()))
+        .layer(Extension(app_state.bulk_rate_limit))
         .layer(Extension(app_state.session.clone()))
+        .layer(Extension(app_state.route_roles.clone()))
         .layer(Extension(app_state.jwt_config.clone()))
 }
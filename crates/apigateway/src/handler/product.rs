@@ -1,8 +1,12 @@
-use crate::abstract_trait::session::DynSessionMiddleware;
 use crate::{
     abstract_trait::product::DynProductGrpcClient,
+    config::{CurrencyConfig, DeleteSemanticsConfig},
     domain::{
-        requests::product::{CreateProductRequest, FindAllProducts, UpdateProductRequest},
+        requests::{
+            entity_id::EntityId,
+            format::FormatParams,
+            product::{CreateProductRequest, FindAllProducts, UpdateProductRequest},
+        },
         response::{
             api::{ApiResponse, ApiResponsePagination},
             product::{ProductResponse, ProductResponseDeleteAt},
@@ -11,20 +15,24 @@ use crate::{
 };
 use crate::{
     middleware::{
-        jwt::auth_middleware, session::session_middleware, validate::SimpleValidatedJson,
+        jwt::auth_middleware, rate_limit::rate_limit_middleware, roles::require_roles,
+        session::session_middleware, validate::SimpleValidatedJson,
     },
     state::AppState,
 };
 use axum::{
     Json,
-    extract::{Extension, Path, Query},
-    http::StatusCode,
+    extract::{Extension, Multipart, Path, Query},
+    http::{HeaderMap, StatusCode, header::ACCEPT_LANGUAGE},
     middleware,
     response::IntoResponse,
     routing::{delete, get, post, put},
 };
 use serde_json::json;
-use shared::errors::HttpError;
+use shared::{
+    errors::{HttpError, ValidationErrorResponse},
+    utils::BatchResponse,
+};
 use std::sync::Arc;
 use utoipa_axum::router::OpenApiRouter;
 
@@ -33,37 +41,70 @@ use utoipa_axum::router::OpenApiRouter;
     path = "/api/products",
     tag = "Product",
     security(("bearer_auth" = [])),
-    params(FindAllProducts),
+    params(FindAllProducts, FormatParams),
     responses(
         (status = 200, description = "List of products", body = ApiResponsePagination<Vec<ProductResponse>>),
+        (status = 400, description = "Invalid price range, sort or malformed cursor", body = ValidationErrorResponse),
         (status = 401, description = "Unauthorized"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn get_products(
     Extension(service): Extension<DynProductGrpcClient>,
-    Extension(user_id): Extension<i32>,
-    Extension(session): Extension<DynSessionMiddleware>,
     Query(params): Query<FindAllProducts>,
+    Extension(currency): Extension<CurrencyConfig>,
+    Query(format): Query<FormatParams>,
+    headers: HeaderMap,
 ) -> Result<impl IntoResponse, HttpError> {
-    let key = format!("session:{user_id}");
+    let violations = params.price_range_violations();
+    if !violations.is_empty() {
+        return Err(HttpError::ValidationFailed(violations));
+    }
 
-    let current_session = session
-        .get_session(&key)
-        .await
-        .ok_or_else(|| HttpError::Unauthorized("Session expired or not found".to_string()))?;
+    let mut response = service.find_all(&params).await?;
 
-    if !current_session
-        .roles
-        .iter()
-        .any(|r| r == "ROLE_ADMIN" || r == "ROLE_MODERATOR")
-    {
-        return Err(HttpError::Forbidden(
-            "Access denied. Required role: ADMIN or MODERATOR".to_string(),
-        ));
+    if format.format {
+        let accept_language = headers.get(ACCEPT_LANGUAGE).and_then(|v| v.to_str().ok());
+        response.data = response
+            .data
+            .into_iter()
+            .map(|item| item.with_formatted_price(&currency, accept_language))
+            .collect();
+    }
+
+    Ok((StatusCode::OK, Json(response)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/products/low-stock",
+    tag = "Product",
+    security(("bearer_auth" = [])),
+    params(FindAllProducts, FormatParams),
+    responses(
+        (status = 200, description = "Active products at or below their reorder threshold", body = ApiResponsePagination<Vec<ProductResponse>>),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn get_low_stock_products(
+    Extension(service): Extension<DynProductGrpcClient>,
+    Query(params): Query<FindAllProducts>,
+    Extension(currency): Extension<CurrencyConfig>,
+    Query(format): Query<FormatParams>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, HttpError> {
+    let mut response = service.find_low_stock(&params).await?;
+
+    if format.format {
+        let accept_language = headers.get(ACCEPT_LANGUAGE).and_then(|v| v.to_str().ok());
+        response.data = response
+            .data
+            .into_iter()
+            .map(|item| item.with_formatted_price(&currency, accept_language))
+            .collect();
     }
 
-    let response = service.find_all(&params).await?;
     Ok((StatusCode::OK, Json(response)))
 }
 
@@ -72,7 +113,7 @@ pub async fn get_products(
     path = "/api/products/active",
     tag = "Product",
     security(("bearer_auth" = [])),
-    params(FindAllProducts),
+    params(FindAllProducts, FormatParams),
     responses(
         (status = 200, description = "List of active products", body = ApiResponsePagination<Vec<ProductResponse>>),
         (status = 401, description = "Unauthorized"),
@@ -81,28 +122,22 @@ pub async fn get_products(
 )]
 pub async fn get_active_products(
     Extension(service): Extension<DynProductGrpcClient>,
-    Extension(user_id): Extension<i32>,
-    Extension(session): Extension<DynSessionMiddleware>,
     Query(params): Query<FindAllProducts>,
+    Extension(currency): Extension<CurrencyConfig>,
+    Query(format): Query<FormatParams>,
+    headers: HeaderMap,
 ) -> Result<impl IntoResponse, HttpError> {
-    let key = format!("session:{user_id}");
+    let mut response = service.find_active(&params).await?;
 
-    let current_session = session
-        .get_session(&key)
-        .await
-        .ok_or_else(|| HttpError::Unauthorized("Session expired or not found".to_string()))?;
-
-    if !current_session
-        .roles
-        .iter()
-        .any(|r| r == "ROLE_ADMIN" || r == "ROLE_MODERATOR")
-    {
-        return Err(HttpError::Forbidden(
-            "Access denied. Required role: ADMIN or MODERATOR".to_string(),
-        ));
+    if format.format {
+        let accept_language = headers.get(ACCEPT_LANGUAGE).and_then(|v| v.to_str().ok());
+        response.data = response
+            .data
+            .into_iter()
+            .map(|item| item.with_formatted_price(&currency, accept_language))
+            .collect();
     }
 
-    let response = service.find_active(&params).await?;
     Ok((StatusCode::OK, Json(response)))
 }
 
@@ -111,7 +146,7 @@ pub async fn get_active_products(
     path = "/api/products/trashed",
     tag = "Product",
     security(("bearer_auth" = [])),
-    params(FindAllProducts),
+    params(FindAllProducts, FormatParams),
     responses(
         (status = 200, description = "List of soft-deleted products", body = ApiResponsePagination<Vec<ProductResponseDeleteAt>>),
         (status = 401, description = "Unauthorized"),
@@ -120,28 +155,22 @@ pub async fn get_active_products(
 )]
 pub async fn get_trashed_products(
     Extension(service): Extension<DynProductGrpcClient>,
-    Extension(user_id): Extension<i32>,
-    Extension(session): Extension<DynSessionMiddleware>,
     Query(params): Query<FindAllProducts>,
+    Extension(currency): Extension<CurrencyConfig>,
+    Query(format): Query<FormatParams>,
+    headers: HeaderMap,
 ) -> Result<impl IntoResponse, HttpError> {
-    let key = format!("session:{user_id}");
+    let mut response = service.find_trashed(&params).await?;
 
-    let current_session = session
-        .get_session(&key)
-        .await
-        .ok_or_else(|| HttpError::Unauthorized("Session expired or not found".to_string()))?;
-
-    if !current_session
-        .roles
-        .iter()
-        .any(|r| r == "ROLE_ADMIN" || r == "ROLE_MODERATOR")
-    {
-        return Err(HttpError::Forbidden(
-            "Access denied. Required role: ADMIN or MODERATOR".to_string(),
-        ));
+    if format.format {
+        let accept_language = headers.get(ACCEPT_LANGUAGE).and_then(|v| v.to_str().ok());
+        response.data = response
+            .data
+            .into_iter()
+            .map(|item| item.with_formatted_price(&currency, accept_language))
+            .collect();
     }
 
-    let response = service.find_trashed(&params).await?;
     Ok((StatusCode::OK, Json(response)))
 }
 
@@ -150,7 +179,7 @@ pub async fn get_trashed_products(
     path = "/api/products/{id}",
     tag = "Product",
     security(("bearer_auth" = [])),
-    params(("id" = i32, Path, description = "Product ID")),
+    params(("id" = String, Path, description = "Product ID or external UUID"), FormatParams),
     responses(
         (status = 200, description = "Product details", body = ApiResponse<ProductResponse>),
         (status = 404, description = "Product not found"),
@@ -159,28 +188,23 @@ pub async fn get_trashed_products(
 )]
 pub async fn get_product(
     Extension(service): Extension<DynProductGrpcClient>,
-    Path(id): Path<i32>,
-    Extension(user_id): Extension<i32>,
-    Extension(session): Extension<DynSessionMiddleware>,
+    Path(id): Path<EntityId>,
+    Extension(currency): Extension<CurrencyConfig>,
+    Query(format): Query<FormatParams>,
+    headers: HeaderMap,
 ) -> Result<impl IntoResponse, HttpError> {
-    let key = format!("session:{user_id}");
-
-    let current_session = session
-        .get_session(&key)
-        .await
-        .ok_or_else(|| HttpError::Unauthorized("Session expired or not found".to_string()))?;
+    let mut response = match id {
+        EntityId::Id(id) => service.find_by_id(id).await?,
+        EntityId::External(external_id) => service.find_by_external_id(external_id).await?,
+    };
 
-    if !current_session
-        .roles
-        .iter()
-        .any(|r| r == "ROLE_ADMIN" || r == "ROLE_MODERATOR")
-    {
-        return Err(HttpError::Forbidden(
-            "Access denied. Required role: ADMIN or MODERATOR".to_string(),
-        ));
+    if format.format {
+        let accept_language = headers.get(ACCEPT_LANGUAGE).and_then(|v| v.to_str().ok());
+        response.data = response
+            .data
+            .with_formatted_price(&currency, accept_language);
     }
 
-    let response = service.find_by_id(id).await?;
     Ok((StatusCode::OK, Json(response)))
 }
 
@@ -194,6 +218,7 @@ pub async fn get_product(
         (status = 201, description = "Product created", body = ApiResponse<ProductResponse>),
         (status = 400, description = "Validation error"),
         (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
         (status = 500, description = "Internal server error")
     )
 )]
@@ -205,25 +230,83 @@ pub async fn create_product(
     Ok((StatusCode::CREATED, Json(response)))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/products/import",
+    tag = "Product",
+    security(("bearer_auth" = [])),
+    request_body(
+        content_type = "multipart/form-data",
+        description = "`file`: a CSV with `name,price,stock[,description]` columns. \
+            `strict` (optional, `true`/`false`): reject the whole file if any row is invalid."
+    ),
+    responses(
+        (status = 200, description = "Every row imported; `succeeded` lists their line numbers", body = ApiResponse<BatchResponse<i32>>),
+        (status = 207, description = "Some rows were skipped; `failed` lists each line number with the reason", body = ApiResponse<BatchResponse<i32>>),
+        (status = 400, description = "Missing file, unusable CSV, or invalid rows in a strict import"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn import_products(
+    Extension(service): Extension<DynProductGrpcClient>,
+    mut multipart: Multipart,
+) -> Result<impl IntoResponse, HttpError> {
+    let mut csv = None;
+    let mut strict = false;
+
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| HttpError::BadRequest(format!("Invalid multipart body: {e}")))?
+    {
+        match field.name() {
+            Some("file") => {
+                let bytes = field
+                    .bytes()
+                    .await
+                    .map_err(|e| HttpError::BadRequest(format!("Failed to read file: {e}")))?;
+                csv = Some(bytes.to_vec());
+            }
+            Some("strict") => {
+                let value = field
+                    .text()
+                    .await
+                    .map_err(|e| HttpError::BadRequest(format!("Invalid strict field: {e}")))?;
+                strict = matches!(value.trim(), "true" | "1");
+            }
+            _ => {}
+        }
+    }
+
+    let csv = csv.ok_or_else(|| HttpError::BadRequest("Missing 'file' field".into()))?;
+
+    let response = service.import_products(csv, strict).await?;
+    Ok((response.data.status_code(), Json(response)))
+}
+
 #[utoipa::path(
     put,
     path = "/api/products/{id}",
     tag = "Product",
     security(("bearer_auth" = [])),
-    params(("id" = i32, Path, description = "Product ID")),
+    params(("id" = String, Path, description = "Product ID or external UUID")),
     request_body = UpdateProductRequest,
     responses(
         (status = 200, description = "Product updated", body = ApiResponse<ProductResponse>),
         (status = 404, description = "Product not found"),
         (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn update_product(
     Extension(service): Extension<DynProductGrpcClient>,
-    Path(id): Path<i32>,
+    Path(id): Path<EntityId>,
     SimpleValidatedJson(mut body): SimpleValidatedJson<UpdateProductRequest>,
 ) -> Result<impl IntoResponse, HttpError> {
+    let id = resolve_product_id(&service, id).await?;
     body.id = Some(id);
     let response = service.update_product(&body).await?;
     Ok((StatusCode::OK, Json(response)))
@@ -234,19 +317,26 @@ pub async fn update_product(
     path = "/api/products/trash/{id}",
     tag = "Product",
     security(("bearer_auth" = [])),
-    params(("id" = i32, Path, description = "Product ID")),
+    params(("id" = String, Path, description = "Product ID or external UUID")),
     responses(
         (status = 200, description = "Product soft-deleted", body = ApiResponse<ProductResponseDeleteAt>),
+        (status = 204, description = "Product already trashed or missing; nothing to do"),
+        (status = 404, description = "Product already trashed or missing, with STRICT_DELETE set"),
         (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn trash_product_handler(
     Extension(service): Extension<DynProductGrpcClient>,
-    Path(id): Path<i32>,
+    Extension(deletes): Extension<DeleteSemanticsConfig>,
+    Path(id): Path<EntityId>,
 ) -> Result<impl IntoResponse, HttpError> {
-    let response = service.trash_product(id).await?;
-    Ok((StatusCode::OK, Json(response)))
+    let id = resolve_product_id(&service, id).await?;
+    let Some(response) = deletes.absorb_missing(service.trash_product(id).await)? else {
+        return Ok(StatusCode::NO_CONTENT.into_response());
+    };
+    Ok((StatusCode::OK, Json(response)).into_response())
 }
 
 #[utoipa::path(
@@ -254,17 +344,21 @@ pub async fn trash_product_handler(
     path = "/api/products/restore/{id}",
     tag = "Product",
     security(("bearer_auth" = [])),
-    params(("id" = i32, Path, description = "Product ID")),
+    params(("id" = String, Path, description = "Product ID or external UUID")),
     responses(
         (status = 200, description = "Product restored", body = ApiResponse<ProductResponse>),
         (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Product not found"),
+        (status = 409, description = "Product is not trashed"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn restore_product_handler(
     Extension(service): Extension<DynProductGrpcClient>,
-    Path(id): Path<i32>,
+    Path(id): Path<EntityId>,
 ) -> Result<impl IntoResponse, HttpError> {
+    let id = resolve_product_id(&service, id).await?;
     let response = service.restore_product(id).await?;
     Ok((StatusCode::OK, Json(response)))
 }
@@ -274,18 +368,28 @@ pub async fn restore_product_handler(
     path = "/api/products/delete/{id}",
     tag = "Product",
     security(("bearer_auth" = [])),
-    params(("id" = i32, Path, description = "Product ID")),
+    params(("id" = String, Path, description = "Product ID or external UUID")),
     responses(
         (status = 200, description = "Product permanently deleted", body = serde_json::Value),
+        (status = 204, description = "Product not trashed or missing; nothing to do"),
+        (status = 404, description = "Product not trashed or missing, with STRICT_DELETE set"),
         (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn delete_product(
     Extension(service): Extension<DynProductGrpcClient>,
-    Path(id): Path<i32>,
+    Extension(deletes): Extension<DeleteSemanticsConfig>,
+    Path(id): Path<EntityId>,
 ) -> Result<impl IntoResponse, HttpError> {
-    service.delete_product(id).await?;
+    let id = resolve_product_id(&service, id).await?;
+    if deletes
+        .absorb_missing(service.delete_product(id).await)?
+        .is_none()
+    {
+        return Ok(StatusCode::NO_CONTENT.into_response());
+    }
 
     Ok((
         StatusCode::OK,
@@ -293,7 +397,8 @@ pub async fn delete_product(
             "status": "success",
             "message": "Product deleted permanently"
         })),
-    ))
+    )
+        .into_response())
 }
 
 #[utoipa::path(
@@ -303,6 +408,7 @@ pub async fn delete_product(
     security(("bearer_auth" = [])),
     responses(
         (status = 200, description = "All trashed products restored", body = serde_json::Value),
+        (status = 403, description = "Forbidden"),
         (status = 500, description = "Internal server error")
     )
 )]
@@ -326,6 +432,7 @@ pub async fn restore_all_product_handler(
     security(("bearer_auth" = [])),
     responses(
         (status = 200, description = "All trashed products permanently deleted", body = serde_json::Value),
+        (status = 403, description = "Forbidden"),
         (status = 500, description = "Internal server error")
     )
 )]
@@ -343,13 +450,29 @@ pub async fn delete_all_product_handler(
     ))
 }
 
+/// Maps a path id to the internal integer id the write RPCs take, looking
+/// a UUID up first so every `{id}` route accepts either form.
+async fn resolve_product_id(
+    service: &DynProductGrpcClient,
+    id: EntityId,
+) -> Result<i32, HttpError> {
+    match id {
+        EntityId::Id(id) => Ok(id),
+        EntityId::External(external_id) => {
+            Ok(service.find_by_external_id(external_id).await?.data.id)
+        }
+    }
+}
+
 pub fn product_routes(app_state: Arc<AppState>) -> OpenApiRouter {
     OpenApiRouter::new()
         .route("/api/products", get(get_products))
         .route("/api/products/active", get(get_active_products))
         .route("/api/products/trashed", get(get_trashed_products))
+        .route("/api/products/low-stock", get(get_low_stock_products))
         .route("/api/products/{id}", get(get_product))
         .route("/api/products", post(create_product))
+        .route("/api/products/import", post(import_products))
         .route("/api/products/{id}", put(update_product))
         .route("/api/products/trash/{id}", delete(trash_product_handler))
         .route("/api/products/restore/{id}", put(restore_product_handler))
@@ -362,10 +485,19 @@ pub fn product_routes(app_state: Arc<AppState>) -> OpenApiRouter {
             "/api/products/delete-all",
             delete(delete_all_product_handler),
         )
+        .route_layer(middleware::from_fn(require_roles))
         .route_layer(middleware::from_fn(session_middleware))
         .route_layer(middleware::from_fn(auth_middleware))
+        .route_layer(middleware::from_fn(rate_limit_middleware))
         .layer(Extension(app_state.di_container.product_clients.clone()))
         .layer(Extension(app_state.di_container.role_clients.clone()))
+        .layer(Extension(app_state.rate_limit.clone()))
+        .layer(Extension(app_state.rate_limit_costs.clone()))
+        .layer(Extension(app_state.rate_limit_groups.clone()))
+        .layer(Extension(app_state.bulk_rate_limit))
+        .layer(Extension(app_state.delete_semantics))
         .layer(Extension(app_state.session.clone()))
+        .layer(Extension(app_state.route_roles.clone()))
+        .layer(Extension(app_state.currency.clone()))
         .layer(Extension(app_state.jwt_config.clone()))
 }
@@ -1,7 +1,11 @@
 use crate::{
-    abstract_trait::{role::DynRoleGrpcClient, session::DynSessionMiddleware},
+    abstract_trait::role::DynRoleGrpcClient,
+    config::DeleteSemanticsConfig,
     domain::{
-        requests::role::{CreateRoleRequest, FindAllRole, UpdateRoleRequest},
+        requests::{
+            entity_id::EntityId,
+            role::{CreateRoleRequest, FindAllRole, UpdateRoleRequest},
+        },
         response::{
             api::{ApiResponse, ApiResponsePagination},
             role::{RoleResponse, RoleResponseDeleteAt},
@@ -10,8 +14,8 @@ use crate::{
 };
 use crate::{
     middleware::{
-        jwt::auth_middleware, rate_limit::rate_limit_middleware, session::session_middleware,
-        validate::SimpleValidatedJson,
+        jwt::auth_middleware, rate_limit::rate_limit_middleware, roles::require_roles,
+        session::session_middleware, validate::SimpleValidatedJson,
     },
     state::AppState,
 };
@@ -42,27 +46,8 @@ use utoipa_axum::router::OpenApiRouter;
 )]
 pub async fn get_roles(
     Extension(service): Extension<DynRoleGrpcClient>,
-    Extension(user_id): Extension<i32>,
-    Extension(session): Extension<DynSessionMiddleware>,
     Query(params): Query<FindAllRole>,
 ) -> Result<impl IntoResponse, HttpError> {
-    let key = format!("session:{user_id}");
-
-    let current_session = session
-        .get_session(&key)
-        .await
-        .ok_or_else(|| HttpError::Unauthorized("Session expired or not found".to_string()))?;
-
-    if !current_session
-        .roles
-        .iter()
-        .any(|r| r == "ROLE_ADMIN" || r == "ROLE_MODERATOR")
-    {
-        return Err(HttpError::Forbidden(
-            "Access denied. Required role: ADMIN or MODERATOR".to_string(),
-        ));
-    }
-
     let response = service.find_all(&params).await?;
     Ok((StatusCode::OK, Json(response)))
 }
@@ -81,27 +66,8 @@ pub async fn get_roles(
 )]
 pub async fn get_active_roles(
     Extension(service): Extension<DynRoleGrpcClient>,
-    Extension(user_id): Extension<i32>,
-    Extension(session): Extension<DynSessionMiddleware>,
     Query(params): Query<FindAllRole>,
 ) -> Result<impl IntoResponse, HttpError> {
-    let key = format!("session:{user_id}");
-
-    let current_session = session
-        .get_session(&key)
-        .await
-        .ok_or_else(|| HttpError::Unauthorized("Session expired or not found".to_string()))?;
-
-    if !current_session
-        .roles
-        .iter()
-        .any(|r| r == "ROLE_ADMIN" || r == "ROLE_MODERATOR")
-    {
-        return Err(HttpError::Forbidden(
-            "Access denied. Required role: ADMIN or MODERATOR".to_string(),
-        ));
-    }
-
     let response = service.find_active(&params).await?;
     Ok((StatusCode::OK, Json(response)))
 }
@@ -120,27 +86,8 @@ pub async fn get_active_roles(
 )]
 pub async fn get_trashed_roles(
     Extension(service): Extension<DynRoleGrpcClient>,
-    Extension(user_id): Extension<i32>,
-    Extension(session): Extension<DynSessionMiddleware>,
     Query(params): Query<FindAllRole>,
 ) -> Result<impl IntoResponse, HttpError> {
-    let key = format!("session:{user_id}");
-
-    let current_session = session
-        .get_session(&key)
-        .await
-        .ok_or_else(|| HttpError::Unauthorized("Session expired or not found".to_string()))?;
-
-    if !current_session
-        .roles
-        .iter()
-        .any(|r| r == "ROLE_ADMIN" || r == "ROLE_MODERATOR")
-    {
-        return Err(HttpError::Forbidden(
-            "Access denied. Required role: ADMIN or MODERATOR".to_string(),
-        ));
-    }
-
     let response = service.find_trashed(&params).await?;
     Ok((StatusCode::OK, Json(response)))
 }
@@ -150,7 +97,7 @@ pub async fn get_trashed_roles(
     path = "/api/roles/{id}",
     tag = "Role",
     security(("bearer_auth" = [])),
-    params(("id" = i32, Path, description = "Role ID")),
+    params(("id" = String, Path, description = "Role ID or external UUID")),
     responses(
         (status = 200, description = "Role details", body = ApiResponse<RoleResponse>),
         (status = 404, description = "Role not found"),
@@ -159,10 +106,12 @@ pub async fn get_trashed_roles(
 )]
 pub async fn get_role(
     Extension(service): Extension<DynRoleGrpcClient>,
-    Path(id): Path<i32>,
-    Extension(_user_id): Extension<i64>,
+    Path(id): Path<EntityId>,
 ) -> Result<impl IntoResponse, HttpError> {
-    let response = service.find_by_id(id).await?;
+    let response = match id {
+        EntityId::Id(id) => service.find_by_id(id).await?,
+        EntityId::External(external_id) => service.find_by_external_id(external_id).await?,
+    };
     Ok((StatusCode::OK, Json(response)))
 }
 
@@ -176,6 +125,7 @@ pub async fn get_role(
         (status = 201, description = "Role created", body = ApiResponse<RoleResponse>),
         (status = 400, description = "Validation error"),
         (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
         (status = 500, description = "Internal server error")
     )
 )]
@@ -192,20 +142,22 @@ pub async fn create_role(
     path = "/api/roles/{id}",
     tag = "Role",
     security(("bearer_auth" = [])),
-    params(("id" = i32, Path, description = "Role ID")),
+    params(("id" = String, Path, description = "Role ID or external UUID")),
     request_body = UpdateRoleRequest,
     responses(
         (status = 200, description = "Role updated", body = ApiResponse<RoleResponse>),
         (status = 404, description = "Role not found"),
         (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn update_role(
     Extension(service): Extension<DynRoleGrpcClient>,
-    Path(id): Path<i32>,
+    Path(id): Path<EntityId>,
     SimpleValidatedJson(mut body): SimpleValidatedJson<UpdateRoleRequest>,
 ) -> Result<impl IntoResponse, HttpError> {
+    let id = resolve_role_id(&service, id).await?;
     body.id = Some(id);
     let response = service.update_role(&body).await?;
     Ok((StatusCode::OK, Json(response)))
@@ -216,19 +168,26 @@ pub async fn update_role(
     path = "/api/roles/trash/{id}",
     tag = "Role",
     security(("bearer_auth" = [])),
-    params(("id" = i32, Path, description = "Role ID")),
+    params(("id" = String, Path, description = "Role ID or external UUID")),
     responses(
         (status = 200, description = "Role soft-deleted", body = ApiResponse<RoleResponseDeleteAt>),
+        (status = 204, description = "Role already trashed or missing; nothing to do"),
+        (status = 404, description = "Role already trashed or missing, with STRICT_DELETE set"),
         (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn trash_role_handler(
     Extension(service): Extension<DynRoleGrpcClient>,
-    Path(id): Path<i32>,
+    Extension(deletes): Extension<DeleteSemanticsConfig>,
+    Path(id): Path<EntityId>,
 ) -> Result<impl IntoResponse, HttpError> {
-    let response = service.trash_role(id).await?;
-    Ok((StatusCode::OK, Json(response)))
+    let id = resolve_role_id(&service, id).await?;
+    let Some(response) = deletes.absorb_missing(service.trash_role(id).await)? else {
+        return Ok(StatusCode::NO_CONTENT.into_response());
+    };
+    Ok((StatusCode::OK, Json(response)).into_response())
 }
 
 #[utoipa::path(
@@ -236,17 +195,21 @@ pub async fn trash_role_handler(
     path = "/api/roles/restore/{id}",
     tag = "Role",
     security(("bearer_auth" = [])),
-    params(("id" = i32, Path, description = "Role ID")),
+    params(("id" = String, Path, description = "Role ID or external UUID")),
     responses(
         (status = 200, description = "Role restored", body = ApiResponse<RoleResponse>),
         (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Role not found"),
+        (status = 409, description = "Role is not trashed"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn restore_role_handler(
     Extension(service): Extension<DynRoleGrpcClient>,
-    Path(id): Path<i32>,
+    Path(id): Path<EntityId>,
 ) -> Result<impl IntoResponse, HttpError> {
+    let id = resolve_role_id(&service, id).await?;
     let response = service.restore_role(id).await?;
     Ok((StatusCode::OK, Json(response)))
 }
@@ -256,19 +219,28 @@ pub async fn restore_role_handler(
     path = "/api/roles/delete/{id}",
     tag = "Role",
     security(("bearer_auth" = [])),
-    params(("id" = i32, Path, description = "Role ID")),
+    params(("id" = String, Path, description = "Role ID or external UUID")),
     responses(
         (status = 200, description = "Role permanently deleted", body = serde_json::Value),
+        (status = 204, description = "Role not trashed or missing; nothing to do"),
+        (status = 404, description = "Role not trashed or missing, with STRICT_DELETE set"),
         (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn delete_role(
     Extension(service): Extension<DynRoleGrpcClient>,
-    Path(id): Path<i32>,
-    Extension(_user_id): Extension<i64>,
+    Extension(deletes): Extension<DeleteSemanticsConfig>,
+    Path(id): Path<EntityId>,
 ) -> Result<impl IntoResponse, HttpError> {
-    service.delete_ole(id).await?;
+    let id = resolve_role_id(&service, id).await?;
+    if deletes
+        .absorb_missing(service.delete_ole(id).await)?
+        .is_none()
+    {
+        return Ok(StatusCode::NO_CONTENT.into_response());
+    }
 
     Ok((
         StatusCode::OK,
@@ -276,7 +248,8 @@ pub async fn delete_role(
             "status": "success",
             "message": "Role deleted permanently"
         })),
-    ))
+    )
+        .into_response())
 }
 
 #[utoipa::path(
@@ -286,6 +259,7 @@ pub async fn delete_role(
     security(("bearer_auth" = [])),
     responses(
         (status = 200, description = "All trashed roles restored", body = serde_json::Value),
+        (status = 403, description = "Forbidden"),
         (status = 500, description = "Internal server error")
     )
 )]
@@ -310,6 +284,7 @@ pub async fn restore_all_role_handler(
     security(("bearer_auth" = [])),
     responses(
         (status = 200, description = "All trashed roles permanently deleted", body = serde_json::Value),
+        (status = 403, description = "Forbidden"),
         (status = 500, description = "Internal server error")
     )
 )]
@@ -327,6 +302,17 @@ pub async fn delete_all_role_handler(
     ))
 }
 
+/// Maps a path id to the internal integer id the write RPCs take, looking
+/// a UUID up first so every `{id}` route accepts either form.
+async fn resolve_role_id(service: &DynRoleGrpcClient, id: EntityId) -> Result<i32, HttpError> {
+    match id {
+        EntityId::Id(id) => Ok(id),
+        EntityId::External(external_id) => {
+            Ok(service.find_by_external_id(external_id).await?.data.role_id)
+        }
+    }
+}
+
 pub fn roles_routes(app_state: Arc<AppState>) -> OpenApiRouter {
     OpenApiRouter::new()
         .route("/api/roles", get(get_roles))
@@ -340,11 +326,203 @@ pub fn roles_routes(app_state: Arc<AppState>) -> OpenApiRouter {
         .route("/api/roles/restore-all", put(restore_all_role_handler))
         .route("/api/roles/delete/{id}", delete(delete_role))
         .route("/api/roles/delete-all", delete(delete_all_role_handler))
+        .route_layer(middleware::from_fn(require_roles))
         .route_layer(middleware::from_fn(session_middleware))
         .route_layer(middleware::from_fn(auth_middleware))
         .route_layer(middleware::from_fn(rate_limit_middleware))
         .layer(Extension(app_state.di_container.role_clients.clone()))
         .layer(Extension(app_state.rate_limit.clone()))
+        .layer(Extension(app_state.rate_limit_costs.clone()))
+        .layer(Extension(app_state.rate_limit_groups.clone()))
+        .layer(Extension(app_state.bulk_rate_limit))
+        .layer(Extension(app_state.delete_semantics))
         .layer(Extension(app_state.session.clone()))
+        .layer(Extension(app_state.route_roles.clone()))
         .layer(Extension(app_state.jwt_config.clone()))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{get_role, update_role};
+    use crate::{
+        abstract_trait::role::{DynRoleGrpcClient, RoleGrpcClientTrait},
+        domain::{
+            requests::role::{CreateRoleRequest, FindAllRole, UpdateRoleRequest},
+            response::{
+                api::{ApiResponse, ApiResponsePagination},
+                role::{RoleResponse, RoleResponseDeleteAt},
+            },
+        },
+    };
+    use async_trait::async_trait;
+    use axum::{
+        Extension, Router,
+        body::{Body, to_bytes},
+        http::{Method, Request, StatusCode, header::CONTENT_TYPE},
+        routing::get,
+    };
+    use serde_json::Value;
+    use shared::errors::HttpError;
+    use std::sync::{Arc, Mutex};
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    const ROLE_ID: i32 = 7;
+    const EXTERNAL_ID: &str = "5f0c2a9e-8d4b-4c1e-9a57-3b6f1d2e4c80";
+
+    /// Holds a single role, reachable by either of its ids.
+    struct OneRole(Mutex<RoleResponse>);
+
+    impl OneRole {
+        fn found(&self) -> Result<ApiResponse<RoleResponse>, HttpError> {
+            Ok(ApiResponse {
+                status: "success".to_string(),
+                message: "Role retrieved successfully".to_string(),
+                data: self.0.lock().unwrap().clone(),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl RoleGrpcClientTrait for OneRole {
+        async fn find_all(
+            &self,
+            _req: &FindAllRole,
+        ) -> Result<ApiResponsePagination<Vec<RoleResponse>>, HttpError> {
+            unimplemented!()
+        }
+        async fn find_active(
+            &self,
+            _req: &FindAllRole,
+        ) -> Result<ApiResponsePagination<Vec<RoleResponseDeleteAt>>, HttpError> {
+            unimplemented!()
+        }
+        async fn find_trashed(
+            &self,
+            _req: &FindAllRole,
+        ) -> Result<ApiResponsePagination<Vec<RoleResponseDeleteAt>>, HttpError> {
+            unimplemented!()
+        }
+        async fn find_by_id(&self, id: i32) -> Result<ApiResponse<RoleResponse>, HttpError> {
+            if id != ROLE_ID {
+                return Err(HttpError::NotFound("Role not found".into()));
+            }
+            self.found()
+        }
+        async fn find_by_external_id(
+            &self,
+            external_id: Uuid,
+        ) -> Result<ApiResponse<RoleResponse>, HttpError> {
+            if external_id.to_string() != self.0.lock().unwrap().external_id {
+                return Err(HttpError::NotFound("Role not found".into()));
+            }
+            self.found()
+        }
+        async fn find_by_user_id(
+            &self,
+            _user_id: i32,
+        ) -> Result<ApiResponse<Vec<RoleResponse>>, HttpError> {
+            unimplemented!()
+        }
+        async fn create_role(
+            &self,
+            _role: &CreateRoleRequest,
+        ) -> Result<ApiResponse<RoleResponse>, HttpError> {
+            unimplemented!()
+        }
+        async fn update_role(
+            &self,
+            role: &UpdateRoleRequest,
+        ) -> Result<ApiResponse<RoleResponse>, HttpError> {
+            assert_eq!(
+                role.id,
+                Some(ROLE_ID),
+                "updates are keyed by the integer id"
+            );
+            self.0.lock().unwrap().role_name = role.name.clone();
+            self.found()
+        }
+        async fn trash_role(
+            &self,
+            _role_id: i32,
+        ) -> Result<ApiResponse<RoleResponseDeleteAt>, HttpError> {
+            unimplemented!()
+        }
+        async fn restore_role(
+            &self,
+            _role_id: i32,
+        ) -> Result<ApiResponse<RoleResponseDeleteAt>, HttpError> {
+            unimplemented!()
+        }
+        async fn delete_ole(&self, _role_id: i32) -> Result<ApiResponse<()>, HttpError> {
+            unimplemented!()
+        }
+        async fn restore_all_role(&self) -> Result<ApiResponse<()>, HttpError> {
+            unimplemented!()
+        }
+        async fn delete_all_role(&self) -> Result<ApiResponse<()>, HttpError> {
+            unimplemented!()
+        }
+    }
+
+    fn router() -> Router {
+        let service: DynRoleGrpcClient = Arc::new(OneRole(Mutex::new(RoleResponse {
+            role_id: ROLE_ID,
+            external_id: EXTERNAL_ID.to_string(),
+            role_name: "ROLE_SUPPORT".to_string(),
+            created_at: None,
+            updated_at: None,
+        })));
+
+        Router::new()
+            .route("/api/roles/{id}", get(get_role).put(update_role))
+            .layer(Extension(service))
+    }
+
+    async fn send(router: &Router, method: Method, uri: &str, body: Body) -> (StatusCode, Value) {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header(CONTENT_TYPE, "application/json")
+            .body(body)
+            .unwrap();
+
+        let response = router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    #[tokio::test]
+    async fn uuid_and_integer_ids_reach_the_same_role() {
+        let router = router();
+        let by_uuid = format!("/api/roles/{EXTERNAL_ID}");
+
+        let (status, by_id) = send(&router, Method::GET, "/api/roles/7", Body::empty()).await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, looked_up) = send(&router, Method::GET, &by_uuid, Body::empty()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(by_id, looked_up);
+        assert_eq!(looked_up["data"]["role_id"], ROLE_ID);
+
+        let rename = Body::from(r#"{"name":"ROLE_OPS"}"#);
+        let (status, _) = send(&router, Method::PUT, &by_uuid, rename).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, updated) = send(&router, Method::GET, &by_uuid, Body::empty()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(updated["data"]["role_name"], "ROLE_OPS");
+        assert_eq!(updated["data"]["external_id"], EXTERNAL_ID);
+        assert_eq!(updated["data"]["role_id"], ROLE_ID);
+
+        let unknown = format!("/api/roles/{}", Uuid::nil());
+        let (status, _) = send(
+            &router,
+            Method::PUT,
+            &unknown,
+            Body::from(r#"{"name":"X"}"#),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+}
@@ -1,16 +1,26 @@
 use crate::{
-    abstract_trait::{session::DynSessionMiddleware, user::DynUserGrpcClient},
+    abstract_trait::{order::DynOrderGrpcClient, user::DynUserGrpcClient},
     domain::{
-        requests::user::{FindAllUsers, UpdateUserRequest},
+        requests::{
+            entity_id::EntityId,
+            order::{FindAllOrder, FindUserOrders},
+            user::{FindAllUsers, UpdateUserRequest},
+        },
         response::{
             api::{ApiResponse, ApiResponsePagination},
+            order::OrderResponse,
             user::{UserResponse, UserResponseDeleteAt},
+            user_export::{OmittedSection, UserExportResponse},
         },
     },
 };
 use crate::{
+    config::{DeleteSemanticsConfig, RedactionConfig},
     middleware::{
-        jwt::auth_middleware, rate_limit::rate_limit_middleware, session::session_middleware,
+        jwt::auth_middleware,
+        rate_limit::rate_limit_middleware,
+        roles::{TokenRoles, require_roles},
+        session::session_middleware,
         validate::SimpleValidatedJson,
     },
     state::AppState,
@@ -21,10 +31,11 @@ use axum::{
     http::StatusCode,
     middleware,
     response::IntoResponse,
-    routing::{delete, get, put},
+    routing::{delete, get, post, put},
 };
+use chrono::Utc;
 use serde_json::json;
-use shared::errors::HttpError;
+use shared::errors::{HttpError, ValidationErrorResponse};
 use std::sync::Arc;
 use utoipa_axum::router::OpenApiRouter;
 
@@ -36,34 +47,24 @@ use utoipa_axum::router::OpenApiRouter;
     params(FindAllUsers),
     responses(
         (status = 200, description = "List of users", body = ApiResponsePagination<Vec<UserResponse>>),
+        (status = 400, description = "Malformed cursor", body = ValidationErrorResponse),
         (status = 401, description = "Unauthorized"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn get_users(
     Extension(service): Extension<DynUserGrpcClient>,
-    Extension(user_id): Extension<i32>,
-    Extension(session): Extension<DynSessionMiddleware>,
+    Extension(roles): Extension<TokenRoles>,
+    Extension(redaction): Extension<RedactionConfig>,
     Query(params): Query<FindAllUsers>,
 ) -> Result<impl IntoResponse, HttpError> {
-    let key = format!("session:{user_id}");
-
-    let current_session = session
-        .get_session(&key)
-        .await
-        .ok_or_else(|| HttpError::Unauthorized("Session expired or not found".to_string()))?;
-
-    if !current_session
-        .roles
-        .iter()
-        .any(|r| r == "ROLE_ADMIN" || r == "ROLE_MODERATOR")
-    {
-        return Err(HttpError::Forbidden(
-            "Access denied. Required role: ADMIN or MODERATOR".to_string(),
-        ));
-    }
+    let mut response = service.find_all(&params).await?;
+    response.data = response
+        .data
+        .into_iter()
+        .map(|user| user.redact(&redaction, &roles.0))
+        .collect();
 
-    let response = service.find_all(&params).await?;
     Ok((StatusCode::OK, Json(response)))
 }
 
@@ -81,28 +82,17 @@ pub async fn get_users(
 )]
 pub async fn get_active_users(
     Extension(service): Extension<DynUserGrpcClient>,
-    Extension(user_id): Extension<i32>,
-    Extension(session): Extension<DynSessionMiddleware>,
+    Extension(roles): Extension<TokenRoles>,
+    Extension(redaction): Extension<RedactionConfig>,
     Query(params): Query<FindAllUsers>,
 ) -> Result<impl IntoResponse, HttpError> {
-    let key = format!("session:{user_id}");
+    let mut response = service.find_active(&params).await?;
+    response.data = response
+        .data
+        .into_iter()
+        .map(|user| user.redact(&redaction, &roles.0))
+        .collect();
 
-    let current_session = session
-        .get_session(&key)
-        .await
-        .ok_or_else(|| HttpError::Unauthorized("Session expired or not found".to_string()))?;
-
-    if !current_session
-        .roles
-        .iter()
-        .any(|r| r == "ROLE_ADMIN" || r == "ROLE_MODERATOR")
-    {
-        return Err(HttpError::Forbidden(
-            "Access denied. Required role: ADMIN or MODERATOR".to_string(),
-        ));
-    }
-
-    let response = service.find_active(&params).await?;
     Ok((StatusCode::OK, Json(response)))
 }
 
@@ -120,28 +110,17 @@ pub async fn get_active_users(
 )]
 pub async fn get_trashed_users(
     Extension(service): Extension<DynUserGrpcClient>,
-    Extension(user_id): Extension<i32>,
-    Extension(session): Extension<DynSessionMiddleware>,
+    Extension(roles): Extension<TokenRoles>,
+    Extension(redaction): Extension<RedactionConfig>,
     Query(params): Query<FindAllUsers>,
 ) -> Result<impl IntoResponse, HttpError> {
-    let key = format!("session:{user_id}");
-
-    let current_session = session
-        .get_session(&key)
-        .await
-        .ok_or_else(|| HttpError::Unauthorized("Session expired or not found".to_string()))?;
-
-    if !current_session
-        .roles
-        .iter()
-        .any(|r| r == "ROLE_ADMIN" || r == "ROLE_MODERATOR")
-    {
-        return Err(HttpError::Forbidden(
-            "Access denied. Required role: ADMIN or MODERATOR".to_string(),
-        ));
-    }
+    let mut response = service.find_trashed(&params).await?;
+    response.data = response
+        .data
+        .into_iter()
+        .map(|user| user.redact(&redaction, &roles.0))
+        .collect();
 
-    let response = service.find_trashed(&params).await?;
     Ok((StatusCode::OK, Json(response)))
 }
 
@@ -150,7 +129,7 @@ pub async fn get_trashed_users(
     path = "/api/users/{id}",
     tag = "User",
     security(("bearer_auth" = [])),
-    params(("id" = i32, Path, description = "User ID")),
+    params(("id" = String, Path, description = "User ID or external UUID")),
     responses(
         (status = 200, description = "User details", body = ApiResponse<UserResponse>),
         (status = 404, description = "User not found"),
@@ -159,55 +138,216 @@ pub async fn get_trashed_users(
 )]
 pub async fn get_user(
     Extension(service): Extension<DynUserGrpcClient>,
-    Path(id): Path<i32>,
-    Extension(_user_id): Extension<i32>,
+    Path(id): Path<EntityId>,
+    Extension(user_id): Extension<i32>,
+    Extension(roles): Extension<TokenRoles>,
+    Extension(redaction): Extension<RedactionConfig>,
+) -> Result<impl IntoResponse, HttpError> {
+    let mut response = match id {
+        EntityId::Id(id) => service.find_by_id(id).await?,
+        EntityId::External(external_id) => service.find_by_external_id(external_id).await?,
+    };
+
+    if response.data.id != user_id {
+        response.data = response.data.redact(&redaction, &roles.0);
+    }
+
+    Ok((StatusCode::OK, Json(response)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/users/{id}/orders",
+    tag = "User",
+    security(("bearer_auth" = [])),
+    params(("id" = String, Path, description = "User ID or external UUID"), FindUserOrders),
+    responses(
+        (status = 200, description = "The user's orders, newest first", body = ApiResponsePagination<Vec<OrderResponse>>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not the user and not an admin"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn get_user_orders(
+    Extension(service): Extension<DynUserGrpcClient>,
+    Extension(orders): Extension<DynOrderGrpcClient>,
+    Path(id): Path<EntityId>,
+    Query(params): Query<FindUserOrders>,
+    Extension(user_id): Extension<i32>,
+    Extension(roles): Extension<TokenRoles>,
 ) -> Result<impl IntoResponse, HttpError> {
-    let response = service.find_by_id(id).await?;
+    let id = resolve_user_id(&service, id).await?;
+    roles.require_self_or_admin(user_id, id, "view these orders")?;
+
+    let response = orders.find_by_user_id(id, &params).await?;
     Ok((StatusCode::OK, Json(response)))
 }
 
+/// Orders are paged through at this size until the last page is reached.
+const EXPORT_ORDER_PAGE_SIZE: i32 = 100;
+
+#[utoipa::path(
+    get,
+    path = "/api/users/{id}/export",
+    tag = "User",
+    security(("bearer_auth" = [])),
+    params(("id" = String, Path, description = "User ID or external UUID")),
+    responses(
+        (status = 200, description = "The user's profile and orders as one document, listing the sections it can't include under `omitted`", body = ApiResponse<UserExportResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not the user and not an admin"),
+        (status = 404, description = "User not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn export_user(
+    Extension(service): Extension<DynUserGrpcClient>,
+    Extension(orders): Extension<DynOrderGrpcClient>,
+    Path(id): Path<EntityId>,
+    Extension(user_id): Extension<i32>,
+    Extension(roles): Extension<TokenRoles>,
+) -> Result<impl IntoResponse, HttpError> {
+    let id = resolve_user_id(&service, id).await?;
+    roles.require_self_or_admin(user_id, id, "export this data")?;
+
+    let export = assemble_user_export(&service, &orders, id).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(ApiResponse {
+            status: "success".to_string(),
+            message: "User data exported successfully".to_string(),
+            data: export,
+        }),
+    ))
+}
+
+/// Fetches the profile first so an unknown user is a 404 before any order
+/// lookups, then walks every page of the user's orders.
+async fn assemble_user_export(
+    users: &DynUserGrpcClient,
+    orders: &DynOrderGrpcClient,
+    id: i32,
+) -> Result<UserExportResponse, HttpError> {
+    let profile = users.find_by_id(id).await?.data;
+
+    let mut user_orders = Vec::new();
+    let mut page = 1;
+
+    loop {
+        let response = orders
+            .find_all(&FindAllOrder {
+                page,
+                page_size: EXPORT_ORDER_PAGE_SIZE,
+                search: String::new(),
+                include: "items".to_string(),
+                after: String::new(),
+                sort: String::new(),
+                user_id: Some(profile.id),
+                created_from: None,
+                created_to: None,
+            })
+            .await?;
+
+        let last_page = response.data.is_empty() || page >= response.pagination.total_pages;
+        user_orders.extend(response.data);
+
+        if last_page {
+            break;
+        }
+        page += 1;
+    }
+
+    Ok(UserExportResponse {
+        exported_at: Utc::now().to_rfc3339(),
+        profile,
+        orders: user_orders,
+        omitted: vec![OmittedSection::audit()],
+    })
+}
+
 #[utoipa::path(
     put,
     path = "/api/users/{id}",
     tag = "User",
     security(("bearer_auth" = [])),
-    params(("id" = i32, Path, description = "User ID")),
+    params(("id" = String, Path, description = "User ID or external UUID")),
     request_body = UpdateUserRequest,
     responses(
         (status = 200, description = "User updated", body = ApiResponse<UserResponse>),
         (status = 404, description = "User not found"),
         (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not the user and not an admin"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn update_user(
     Extension(service): Extension<DynUserGrpcClient>,
-    Path(id): Path<i32>,
+    Path(id): Path<EntityId>,
+    Extension(user_id): Extension<i32>,
+    Extension(roles): Extension<TokenRoles>,
     SimpleValidatedJson(mut body): SimpleValidatedJson<UpdateUserRequest>,
 ) -> Result<impl IntoResponse, HttpError> {
+    let id = resolve_user_id(&service, id).await?;
+    roles.require_self_or_admin(user_id, id, "update this account")?;
+
     body.user_id = Some(id);
     let response = service.update_user(&body).await?;
     Ok((StatusCode::OK, Json(response)))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/users/{id}/anonymize",
+    tag = "User",
+    security(("bearer_auth" = [])),
+    params(("id" = String, Path, description = "User ID or external UUID")),
+    responses(
+        (status = 200, description = "Personal data scrubbed; the account and its orders are kept", body = ApiResponse<UserResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not the user and not an admin"),
+        (status = 404, description = "User not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn anonymize_user_handler(
+    Extension(service): Extension<DynUserGrpcClient>,
+    Path(id): Path<EntityId>,
+    Extension(user_id): Extension<i32>,
+    Extension(roles): Extension<TokenRoles>,
+) -> Result<impl IntoResponse, HttpError> {
+    let id = resolve_user_id(&service, id).await?;
+    roles.require_self_or_admin(user_id, id, "erase this data")?;
+
+    let response = service.anonymize_user(id).await?;
+    Ok((StatusCode::OK, Json(response)))
+}
+
 #[utoipa::path(
     delete,
     path = "/api/users/trash/{id}",
     tag = "User",
     security(("bearer_auth" = [])),
-    params(("id" = i32, Path, description = "User ID")),
+    params(("id" = String, Path, description = "User ID or external UUID")),
     responses(
         (status = 200, description = "User soft-deleted", body = ApiResponse<UserResponseDeleteAt>),
+        (status = 204, description = "User already trashed or missing; nothing to do"),
+        (status = 404, description = "User already trashed or missing, with STRICT_DELETE set"),
         (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn trash_user_handler(
     Extension(service): Extension<DynUserGrpcClient>,
-    Path(id): Path<i32>,
+    Extension(deletes): Extension<DeleteSemanticsConfig>,
+    Path(id): Path<EntityId>,
 ) -> Result<impl IntoResponse, HttpError> {
-    let response = service.trash_user(id).await?;
-    Ok((StatusCode::OK, Json(response)))
+    let id = resolve_user_id(&service, id).await?;
+    let Some(response) = deletes.absorb_missing(service.trash_user(id).await)? else {
+        return Ok(StatusCode::NO_CONTENT.into_response());
+    };
+    Ok((StatusCode::OK, Json(response)).into_response())
 }
 
 #[utoipa::path(
@@ -215,17 +355,21 @@ pub async fn trash_user_handler(
     path = "/api/users/restore/{id}",
     tag = "User",
     security(("bearer_auth" = [])),
-    params(("id" = i32, Path, description = "User ID")),
+    params(("id" = String, Path, description = "User ID or external UUID")),
     responses(
         (status = 200, description = "User restored", body = ApiResponse<UserResponse>),
         (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "User not found"),
+        (status = 409, description = "User is not trashed"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn restore_user_handler(
     Extension(service): Extension<DynUserGrpcClient>,
-    Path(id): Path<i32>,
+    Path(id): Path<EntityId>,
 ) -> Result<impl IntoResponse, HttpError> {
+    let id = resolve_user_id(&service, id).await?;
     let response = service.restore_user(id).await?;
     Ok((StatusCode::OK, Json(response)))
 }
@@ -235,18 +379,28 @@ pub async fn restore_user_handler(
     path = "/api/users/delete/{id}",
     tag = "User",
     security(("bearer_auth" = [])),
-    params(("id" = i32, Path, description = "User ID")),
+    params(("id" = String, Path, description = "User ID or external UUID")),
     responses(
         (status = 200, description = "User permanently deleted", body = serde_json::Value),
+        (status = 204, description = "User not trashed or missing; nothing to do"),
+        (status = 404, description = "User not trashed or missing, with STRICT_DELETE set"),
         (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn delete_user(
     Extension(service): Extension<DynUserGrpcClient>,
-    Path(id): Path<i32>,
+    Extension(deletes): Extension<DeleteSemanticsConfig>,
+    Path(id): Path<EntityId>,
 ) -> Result<impl IntoResponse, HttpError> {
-    service.delete_user(id).await?;
+    let id = resolve_user_id(&service, id).await?;
+    if deletes
+        .absorb_missing(service.delete_user(id).await)?
+        .is_none()
+    {
+        return Ok(StatusCode::NO_CONTENT.into_response());
+    }
 
     Ok((
         StatusCode::OK,
@@ -254,7 +408,8 @@ pub async fn delete_user(
             "status": "success",
             "message": "User deleted permanently"
         })),
-    ))
+    )
+        .into_response())
 }
 
 #[utoipa::path(
@@ -264,6 +419,7 @@ pub async fn delete_user(
     security(("bearer_auth" = [])),
     responses(
         (status = 200, description = "All trashed users restored", body = serde_json::Value),
+        (status = 403, description = "Forbidden"),
         (status = 500, description = "Internal server error")
     )
 )]
@@ -288,6 +444,7 @@ pub async fn restore_all_user_handler(
     security(("bearer_auth" = [])),
     responses(
         (status = 200, description = "All trashed users permanently deleted", body = serde_json::Value),
+        (status = 403, description = "Forbidden"),
         (status = 500, description = "Internal server error")
     )
 )]
@@ -305,6 +462,17 @@ pub async fn delete_all_user_handler(
     ))
 }
 
+/// Maps a path id to the internal integer id the write RPCs take, looking
+/// a UUID up first so every `{id}` route accepts either form.
+async fn resolve_user_id(service: &DynUserGrpcClient, id: EntityId) -> Result<i32, HttpError> {
+    match id {
+        EntityId::Id(id) => Ok(id),
+        EntityId::External(external_id) => {
+            Ok(service.find_by_external_id(external_id).await?.data.id)
+        }
+    }
+}
+
 pub fn user_routes(app_state: Arc<AppState>) -> OpenApiRouter {
     OpenApiRouter::new()
         .route("/api/users", get(get_users))
@@ -312,17 +480,352 @@ pub fn user_routes(app_state: Arc<AppState>) -> OpenApiRouter {
         .route("/api/users/trashed", get(get_trashed_users))
         .route("/api/users/{id}", get(get_user))
         .route("/api/users/{id}", put(update_user))
+        .route("/api/users/{id}/export", get(export_user))
+        .route("/api/users/{id}/orders", get(get_user_orders))
+        .route("/api/users/{id}/anonymize", post(anonymize_user_handler))
         .route("/api/users/trash/{id}", delete(trash_user_handler))
         .route("/api/users/restore/{id}", put(restore_user_handler))
         .route("/api/users/restore-all", put(restore_all_user_handler))
         .route("/api/users/delete/{id}", delete(delete_user))
         .route("/api/users/delete-all", delete(delete_all_user_handler))
+        .route_layer(middleware::from_fn(require_roles))
         .route_layer(middleware::from_fn(session_middleware))
         .route_layer(middleware::from_fn(auth_middleware))
         .route_layer(middleware::from_fn(rate_limit_middleware))
         .layer(Extension(app_state.di_container.user_clients.clone()))
         .layer(Extension(app_state.di_container.role_clients.clone()))
+        .layer(Extension(app_state.di_container.order_clients.clone()))
         .layer(Extension(app_state.rate_limit.clone()))
+        .layer(Extension(app_state.rate_limit_costs.clone()))
+        .layer(Extension(app_state.rate_limit_groups.clone()))
+        .layer(Extension(app_state.bulk_rate_limit))
+        .layer(Extension(app_state.delete_semantics))
         .layer(Extension(app_state.session.clone()))
+        .layer(Extension(app_state.route_roles.clone()))
         .layer(Extension(app_state.jwt_config.clone()))
+        .layer(Extension(app_state.redaction.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{export_user, get_user};
+    use crate::{
+        abstract_trait::{
+            order::{DynOrderGrpcClient, OrderGrpcClientTrait},
+            user::{DynUserGrpcClient, UserGrpcClientTrait},
+        },
+        config::RedactionConfig,
+        domain::{
+            requests::{
+                order::{CreateOrderRequest, FindAllOrder, FindUserOrders, UpdateOrderRequest},
+                user::{FindAllUsers, UpdateUserRequest},
+            },
+            response::{
+                api::{ApiResponse, ApiResponsePagination},
+                order::{OrderDetailResponse, OrderResponse, OrderResponseDeleteAt},
+                pagination::Pagination,
+                user::{UserResponse, UserResponseDeleteAt},
+            },
+        },
+        middleware::roles::TokenRoles,
+    };
+    use async_trait::async_trait;
+    use axum::{
+        Extension, Router,
+        body::{Body, to_bytes},
+        http::{Request, StatusCode},
+        routing::get,
+    };
+    use serde_json::Value;
+    use shared::errors::HttpError;
+    use std::sync::{Arc, Mutex};
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    const USER_ID: i32 = 7;
+
+    /// Holds a single user, reachable by its integer id.
+    struct OneUser;
+
+    fn user() -> UserResponse {
+        UserResponse {
+            id: USER_ID,
+            external_id: Uuid::nil().to_string(),
+            firstname: "Ayu".to_string(),
+            lastname: "Lestari".to_string(),
+            email: "ayu@example.com".to_string(),
+            created_at: None,
+            updated_at: None,
+            roles: None,
+        }
+    }
+
+    #[async_trait]
+    impl UserGrpcClientTrait for OneUser {
+        async fn find_all(
+            &self,
+            _req: &FindAllUsers,
+        ) -> Result<ApiResponsePagination<Vec<UserResponse>>, HttpError> {
+            unimplemented!()
+        }
+        async fn find_active(
+            &self,
+            _req: &FindAllUsers,
+        ) -> Result<ApiResponsePagination<Vec<UserResponseDeleteAt>>, HttpError> {
+            unimplemented!()
+        }
+        async fn find_trashed(
+            &self,
+            _req: &FindAllUsers,
+        ) -> Result<ApiResponsePagination<Vec<UserResponseDeleteAt>>, HttpError> {
+            unimplemented!()
+        }
+        async fn find_by_id(&self, id: i32) -> Result<ApiResponse<UserResponse>, HttpError> {
+            if id != USER_ID {
+                return Err(HttpError::NotFound("User not found".into()));
+            }
+            Ok(ApiResponse {
+                status: "success".to_string(),
+                message: "User retrieved successfully".to_string(),
+                data: user(),
+            })
+        }
+        async fn find_by_external_id(
+            &self,
+            _external_id: Uuid,
+        ) -> Result<ApiResponse<UserResponse>, HttpError> {
+            unimplemented!()
+        }
+        async fn update_user(
+            &self,
+            _req: &UpdateUserRequest,
+        ) -> Result<ApiResponse<UserResponse>, HttpError> {
+            unimplemented!()
+        }
+        async fn anonymize_user(&self, _id: i32) -> Result<ApiResponse<UserResponse>, HttpError> {
+            unimplemented!()
+        }
+        async fn trash_user(
+            &self,
+            _id: i32,
+        ) -> Result<ApiResponse<UserResponseDeleteAt>, HttpError> {
+            unimplemented!()
+        }
+        async fn restore_user(
+            &self,
+            _id: i32,
+        ) -> Result<ApiResponse<UserResponseDeleteAt>, HttpError> {
+            unimplemented!()
+        }
+        async fn delete_user(&self, _id: i32) -> Result<ApiResponse<()>, HttpError> {
+            unimplemented!()
+        }
+        async fn restore_all_user(&self) -> Result<ApiResponse<()>, HttpError> {
+            unimplemented!()
+        }
+        async fn delete_all_user(&self) -> Result<ApiResponse<()>, HttpError> {
+            unimplemented!()
+        }
+    }
+
+    /// Fetches user 7 as `caller_id` holding `roles`.
+    async fn get_as(caller_id: i32, roles: &[&str]) -> Value {
+        let service: DynUserGrpcClient = Arc::new(OneUser);
+        let redaction = RedactionConfig {
+            fields: vec!["email".to_string(), "lastname".to_string()],
+            exempt_roles: vec!["ROLE_ADMIN".to_string()],
+        };
+        let router = Router::new()
+            .route("/api/users/{id}", get(get_user))
+            .layer(Extension(service))
+            .layer(Extension(redaction));
+
+        let mut request = Request::get("/api/users/7").body(Body::empty()).unwrap();
+        request.extensions_mut().insert(caller_id);
+        request.extensions_mut().insert(TokenRoles(
+            roles.iter().map(|role| role.to_string()).collect(),
+        ));
+
+        let response = router.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice::<Value>(&bytes).unwrap()["data"].clone()
+    }
+
+    #[tokio::test]
+    async fn other_users_see_redacted_fields() {
+        let seen = get_as(8, &["ROLE_USER"]).await;
+
+        assert_eq!(seen["email"], "a**@example.com");
+        assert_eq!(seen["lastname"], "L******");
+        assert_eq!(seen["firstname"], "Ayu", "fields not listed stay visible");
+    }
+
+    #[tokio::test]
+    async fn owner_and_admins_see_everything() {
+        for seen in [get_as(USER_ID, &[]).await, get_as(8, &["ROLE_ADMIN"]).await] {
+            assert_eq!(seen["email"], "ayu@example.com");
+            assert_eq!(seen["lastname"], "Lestari");
+        }
+    }
+
+    /// Serves five orders of user 7, two to a page whatever page size is
+    /// asked for, and records the pages requested.
+    #[derive(Default)]
+    struct PagedOrders(Mutex<Vec<(i32, Option<i32>)>>);
+
+    const ORDERS: i32 = 5;
+    const ORDERS_PER_PAGE: i32 = 2;
+
+    #[async_trait]
+    impl OrderGrpcClientTrait for PagedOrders {
+        async fn find_all(
+            &self,
+            req: &FindAllOrder,
+        ) -> Result<ApiResponsePagination<Vec<OrderResponse>>, HttpError> {
+            self.0.lock().unwrap().push((req.page, req.user_id));
+
+            let first = (req.page - 1) * ORDERS_PER_PAGE + 1;
+            let data = (first..=ORDERS)
+                .take(ORDERS_PER_PAGE as usize)
+                .map(|id| OrderResponse {
+                    id,
+                    external_id: Uuid::nil().to_string(),
+                    order_number: format!("ORD-{id}"),
+                    user_id: USER_ID,
+                    total_price: 1_000 * id,
+                    total_price_formatted: None,
+                    created_at: None,
+                    updated_at: None,
+                    status: "paid".to_string(),
+                    items: Vec::new(),
+                })
+                .collect();
+            let total_pages = (ORDERS + ORDERS_PER_PAGE - 1) / ORDERS_PER_PAGE;
+
+            Ok(ApiResponsePagination {
+                status: "success".to_string(),
+                message: "Orders retrieved successfully".to_string(),
+                data,
+                pagination: Pagination {
+                    page: req.page,
+                    page_size: ORDERS_PER_PAGE,
+                    total_items: ORDERS,
+                    total_pages,
+                    has_next: req.page < total_pages,
+                    next_cursor: None,
+                },
+            })
+        }
+        async fn find_active(
+            &self,
+            _req: &FindAllOrder,
+        ) -> Result<ApiResponsePagination<Vec<OrderResponseDeleteAt>>, HttpError> {
+            unimplemented!()
+        }
+        async fn find_trashed(
+            &self,
+            _req: &FindAllOrder,
+        ) -> Result<ApiResponsePagination<Vec<OrderResponseDeleteAt>>, HttpError> {
+            unimplemented!()
+        }
+        async fn find_by_user_id(
+            &self,
+            _user_id: i32,
+            _req: &FindUserOrders,
+        ) -> Result<ApiResponsePagination<Vec<OrderResponse>>, HttpError> {
+            unimplemented!()
+        }
+        async fn find_by_id(&self, _id: i32) -> Result<ApiResponse<OrderResponse>, HttpError> {
+            unimplemented!()
+        }
+        async fn find_detail_by_id(
+            &self,
+            _id: i32,
+        ) -> Result<ApiResponse<OrderDetailResponse>, HttpError> {
+            unimplemented!()
+        }
+        async fn find_by_external_id(
+            &self,
+            _external_id: Uuid,
+        ) -> Result<ApiResponse<OrderResponse>, HttpError> {
+            unimplemented!()
+        }
+        async fn create_order(
+            &self,
+            _req: &CreateOrderRequest,
+        ) -> Result<ApiResponse<OrderResponse>, HttpError> {
+            unimplemented!()
+        }
+        async fn update_order(
+            &self,
+            _req: &UpdateOrderRequest,
+        ) -> Result<ApiResponse<OrderResponse>, HttpError> {
+            unimplemented!()
+        }
+        async fn trash_order(
+            &self,
+            _id: i32,
+        ) -> Result<ApiResponse<OrderResponseDeleteAt>, HttpError> {
+            unimplemented!()
+        }
+        async fn restore_order(
+            &self,
+            _id: i32,
+        ) -> Result<ApiResponse<OrderResponseDeleteAt>, HttpError> {
+            unimplemented!()
+        }
+        async fn delete_order(&self, _id: i32) -> Result<ApiResponse<()>, HttpError> {
+            unimplemented!()
+        }
+        async fn restore_all_order(&self) -> Result<ApiResponse<()>, HttpError> {
+            unimplemented!()
+        }
+        async fn delete_all_order(&self) -> Result<ApiResponse<()>, HttpError> {
+            unimplemented!()
+        }
+    }
+
+    #[tokio::test]
+    async fn the_export_holds_the_profile_and_every_page_of_orders() {
+        let users: DynUserGrpcClient = Arc::new(OneUser);
+        let paged = Arc::new(PagedOrders::default());
+        let orders: DynOrderGrpcClient = paged.clone();
+        let router = Router::new()
+            .route("/api/users/{id}/export", get(export_user))
+            .layer(Extension(users))
+            .layer(Extension(orders));
+
+        let export_as = async |caller_id: i32| {
+            let mut request = Request::get("/api/users/7/export")
+                .body(Body::empty())
+                .unwrap();
+            request.extensions_mut().insert(caller_id);
+            request.extensions_mut().insert(TokenRoles(Vec::new()));
+            router.clone().oneshot(request).await.unwrap()
+        };
+
+        let response = export_as(USER_ID).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let export = serde_json::from_slice::<Value>(&bytes).unwrap()["data"].clone();
+
+        assert_eq!(export["profile"]["id"], USER_ID);
+        assert_eq!(export["profile"]["email"], "ayu@example.com");
+        let numbers: Vec<_> = export["orders"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|order| order["order_number"].as_str().unwrap())
+            .collect();
+        assert_eq!(numbers, ["ORD-1", "ORD-2", "ORD-3", "ORD-4", "ORD-5"]);
+        assert_eq!(
+            *paged.0.lock().unwrap(),
+            [(1, Some(USER_ID)), (2, Some(USER_ID)), (3, Some(USER_ID))]
+        );
+        assert_eq!(export["omitted"][0]["section"], "audit");
+
+        let response = export_as(8).await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
 }
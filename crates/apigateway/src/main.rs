@@ -33,13 +33,13 @@ async fn main() -> Result<()> {
 
     let port = config.port;
 
-    let state = AppState::new(&config.jwt_secret)
+    let state = AppState::new(&config.jwt_secret, config.jwt_previous_key.clone())
         .await
         .context("Failed to create AppState")?;
 
     println!("🚀 Server started successfully");
 
-    AppRouter::serve(port, state)
+    AppRouter::serve(port, &config.base_path, state)
         .await
         .context("Failed to start server")?;
 
@@ -0,0 +1,145 @@
+use crate::config::RequestBudgetConfig;
+use axum::{
+    body::Body,
+    extract::State,
+    http::Request,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use shared::errors::HttpError;
+use std::time::Duration;
+use tokio::time::{Instant, timeout_at};
+use tracing::warn;
+
+tokio::task_local! {
+    static REQUEST_DEADLINE: Instant;
+}
+
+/// Starts the request's time budget. Downstream gRPC calls pick up whatever
+/// is left through `apply_deadline`, and the request is aborted with a 504
+/// once the budget runs out.
+pub async fn deadline_middleware(
+    State(request_budget): State<RequestBudgetConfig>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let deadline = Instant::now() + request_budget.budget;
+    let path = req.uri().path().to_string();
+
+    match timeout_at(deadline, REQUEST_DEADLINE.scope(deadline, next.run(req))).await {
+        Ok(response) => response,
+        Err(_) => {
+            warn!(
+                "Request to {path} exceeded its {:?} budget",
+                request_budget.budget
+            );
+            HttpError::GatewayTimeout("Request timed out".to_string()).into_response()
+        }
+    }
+}
+
+/// Remaining budget of the current request, `None` outside a request scope.
+pub fn remaining_budget() -> Option<Duration> {
+    REQUEST_DEADLINE
+        .try_with(|deadline| deadline.saturating_duration_since(Instant::now()))
+        .ok()
+}
+
+/// Sets the gRPC deadline of an outgoing call to the remaining budget.
+pub fn apply_deadline<T>(request: &mut tonic::Request<T>) {
+    if let Some(remaining) = remaining_budget() {
+        request.set_timeout(remaining);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_deadline, deadline_middleware};
+    use crate::config::RequestBudgetConfig;
+    use axum::{
+        Json, Router,
+        body::{Body, to_bytes},
+        http::{Request, StatusCode},
+        middleware,
+        routing::get,
+    };
+    use std::time::Duration;
+    use tower::ServiceExt;
+
+    const BUDGET: Duration = Duration::from_millis(300);
+    const CALL: Duration = Duration::from_millis(100);
+
+    /// Deadline a downstream call would be sent with, read back from the
+    /// `grpc-timeout` header tonic derives from it.
+    fn outgoing_timeout() -> Duration {
+        let mut request = tonic::Request::new(());
+        apply_deadline(&mut request);
+
+        let header = request.metadata().get("grpc-timeout").unwrap();
+        let header = header.to_str().unwrap();
+        let (value, unit) = header.split_at(header.len() - 1);
+        let value: u64 = value.parse().unwrap();
+        match unit {
+            "S" => Duration::from_secs(value),
+            "m" => Duration::from_millis(value),
+            "u" => Duration::from_micros(value),
+            "n" => Duration::from_nanos(value),
+            other => panic!("unexpected grpc-timeout unit {other}"),
+        }
+    }
+
+    fn router() -> Router {
+        Router::new()
+            .route(
+                "/fan-out",
+                get(|| async {
+                    let mut timeouts = Vec::new();
+                    for _ in 0..2 {
+                        timeouts.push(outgoing_timeout().as_millis() as u64);
+                        tokio::time::sleep(CALL).await;
+                    }
+                    Json(timeouts)
+                }),
+            )
+            .route(
+                "/stuck",
+                get(|| async {
+                    tokio::time::sleep(BUDGET * 10).await;
+                    "too late"
+                }),
+            )
+            .layer(middleware::from_fn_with_state(
+                RequestBudgetConfig { budget: BUDGET },
+                deadline_middleware,
+            ))
+    }
+
+    async fn get_path(path: &str) -> (StatusCode, Vec<u8>) {
+        let request = Request::builder().uri(path).body(Body::empty()).unwrap();
+        let response = router().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, body.to_vec())
+    }
+
+    #[tokio::test]
+    async fn sequential_calls_get_what_is_left_of_the_budget() {
+        let (status, body) = get_path("/fan-out").await;
+        assert_eq!(status, StatusCode::OK);
+
+        let timeouts: Vec<u64> = serde_json::from_slice(&body).unwrap();
+        let budget = BUDGET.as_millis() as u64;
+        let call = CALL.as_millis() as u64;
+        assert!(timeouts[0] <= budget && timeouts[0] > budget - call);
+        assert!(timeouts[1] <= timeouts[0] - call);
+    }
+
+    #[tokio::test]
+    async fn exhausted_budget_aborts_with_504() {
+        let started = std::time::Instant::now();
+        let (status, _) = get_path("/stuck").await;
+
+        assert_eq!(status, StatusCode::GATEWAY_TIMEOUT);
+        assert!(started.elapsed() < BUDGET * 3);
+    }
+}
@@ -0,0 +1,46 @@
+use crate::config::DeprecationConfig;
+use axum::{
+    body::Body,
+    extract::{MatchedPath, State},
+    http::{HeaderName, HeaderValue, Request},
+    middleware::Next,
+    response::Response,
+};
+
+const DEPRECATION: HeaderName = HeaderName::from_static("deprecation");
+const SUNSET: HeaderName = HeaderName::from_static("sunset");
+
+/// Marks responses of `DEPRECATED_ROUTES` with `Deprecation: true` and, when
+/// a sunset date is configured, a `Sunset` header (RFC 8594) with the
+/// midnight UTC it falls on.
+pub async fn deprecation_middleware(
+    State(deprecations): State<DeprecationConfig>,
+    matched_path: Option<MatchedPath>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let deprecated = matched_path
+        .and_then(|route| deprecations.find(req.method(), route.as_str()))
+        .cloned();
+
+    let mut response = next.run(req).await;
+
+    if let Some(route) = deprecated {
+        let headers = response.headers_mut();
+        headers.insert(DEPRECATION, HeaderValue::from_static("true"));
+
+        if let Some(sunset) = route.sunset {
+            let http_date = sunset
+                .and_hms_opt(0, 0, 0)
+                .unwrap_or_default()
+                .format("%a, %d %b %Y %H:%M:%S GMT")
+                .to_string();
+
+            if let Ok(value) = HeaderValue::from_str(&http_date) {
+                headers.insert(SUNSET, value);
+            }
+        }
+    }
+
+    response
+}
@@ -0,0 +1,218 @@
+use crate::config::HttpsConfig;
+use axum::{
+    body::Body,
+    extract::{ConnectInfo, OriginalUri, State},
+    http::{
+        HeaderValue, Request,
+        header::{HOST, SET_COOKIE},
+    },
+    middleware::Next,
+    response::{IntoResponse, Redirect, Response},
+};
+use axum_extra::extract::cookie::{Cookie, SameSite};
+use shared::errors::HttpError;
+use std::net::SocketAddr;
+use tracing::{info, warn};
+
+pub const X_FORWARDED_PROTO: &str = "x-forwarded-proto";
+
+/// With `ENFORCE_HTTPS` on, redirects plain-http requests to https and makes
+/// every cookie the gateway sets `Secure`, `HttpOnly` and `SameSite`.
+pub async fn enforce_https_middleware(
+    State(https): State<HttpsConfig>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    if !https.enabled {
+        return next.run(req).await;
+    }
+
+    let peer = req
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+
+    let forwarded_proto = req
+        .headers()
+        .get(X_FORWARDED_PROTO)
+        .and_then(|v| v.to_str().ok());
+
+    if !https.is_secure(peer, forwarded_proto) {
+        return redirect_to_https(&req);
+    }
+
+    let mut response = next.run(req).await;
+    harden_cookies(&mut response);
+    response
+}
+
+fn redirect_to_https(req: &Request<Body>) -> Response {
+    let Some(host) = req.headers().get(HOST).and_then(|v| v.to_str().ok()) else {
+        return HttpError::BadRequest("HTTPS is required".to_string()).into_response();
+    };
+
+    // Inside a nested router `uri()` has `BASE_PATH` stripped.
+    let uri = req
+        .extensions()
+        .get::<OriginalUri>()
+        .map(|OriginalUri(uri)| uri)
+        .unwrap_or(req.uri());
+
+    let path = uri.path_and_query().map(|pq| pq.as_str()).unwrap_or("/");
+
+    info!("Redirecting plain-http {} {} to https", req.method(), path);
+
+    Redirect::permanent(&format!("https://{host}{path}")).into_response()
+}
+
+fn harden_cookies(response: &mut Response) {
+    let cookies: Vec<HeaderValue> = response
+        .headers()
+        .get_all(SET_COOKIE)
+        .iter()
+        .cloned()
+        .collect();
+
+    response.headers_mut().remove(SET_COOKIE);
+
+    for value in cookies {
+        let Some(mut cookie) = value
+            .to_str()
+            .ok()
+            .and_then(|raw| Cookie::parse(raw.to_string()).ok())
+        else {
+            warn!("Dropping unparsable Set-Cookie header");
+            continue;
+        };
+
+        cookie.set_secure(true);
+        cookie.set_http_only(true);
+        if cookie.same_site().is_none() {
+            cookie.set_same_site(SameSite::Strict);
+        }
+
+        match HeaderValue::from_str(&cookie.to_string()) {
+            Ok(value) => {
+                response.headers_mut().append(SET_COOKIE, value);
+            }
+            Err(e) => warn!("Dropping Set-Cookie header that no longer encodes: {e}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{X_FORWARDED_PROTO, enforce_https_middleware};
+    use crate::config::HttpsConfig;
+    use axum::{
+        Router,
+        body::Body,
+        extract::ConnectInfo,
+        http::{
+            Request, StatusCode,
+            header::{HOST, LOCATION, SET_COOKIE},
+        },
+        middleware::from_fn_with_state,
+        response::{AppendHeaders, IntoResponse, Response},
+        routing::post,
+    };
+    use std::net::SocketAddr;
+    use tower::ServiceExt;
+
+    const PROXY: &str = "10.0.0.2:41000";
+
+    fn app(enabled: bool) -> Router {
+        Router::new()
+            .route(
+                "/api/auth/login",
+                post(|| async {
+                    (
+                        AppendHeaders([
+                            (SET_COOKIE, "refresh_token=abc; Path=/api/auth"),
+                            (SET_COOKIE, "theme=dark; SameSite=Lax"),
+                        ]),
+                        "ok",
+                    )
+                        .into_response()
+                }),
+            )
+            .layer(from_fn_with_state(
+                HttpsConfig {
+                    enabled,
+                    trusted_proxies: vec!["10.0.0.2".parse().unwrap()],
+                },
+                enforce_https_middleware,
+            ))
+    }
+
+    async fn login(app: Router, peer: &str, forwarded_proto: &str) -> Response {
+        let mut request = Request::post("/api/auth/login?next=%2Fcart")
+            .header(HOST, "shop.example.com")
+            .header(X_FORWARDED_PROTO, forwarded_proto)
+            .body(Body::empty())
+            .unwrap();
+        request
+            .extensions_mut()
+            .insert(ConnectInfo(peer.parse::<SocketAddr>().unwrap()));
+
+        app.oneshot(request).await.unwrap()
+    }
+
+    fn cookies(response: &Response) -> Vec<&str> {
+        response
+            .headers()
+            .get_all(SET_COOKIE)
+            .iter()
+            .map(|v| v.to_str().unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn plain_http_is_redirected_to_https() {
+        let response = login(app(true), PROXY, "http").await;
+
+        assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(
+            response.headers()[LOCATION],
+            "https://shop.example.com/api/auth/login?next=%2Fcart"
+        );
+        assert!(cookies(&response).is_empty());
+    }
+
+    #[tokio::test]
+    async fn forwarded_proto_is_only_believed_from_trusted_proxies() {
+        let response = login(app(true), "198.51.100.20:52000", "https").await;
+
+        assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+    }
+
+    #[tokio::test]
+    async fn cookies_set_over_https_are_hardened() {
+        let response = login(app(true), PROXY, "https").await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let cookies = cookies(&response);
+        assert_eq!(cookies.len(), 2);
+        for (cookie, same_site) in cookies.iter().zip(["SameSite=Strict", "SameSite=Lax"]) {
+            assert!(cookie.contains("Secure"), "{cookie}");
+            assert!(cookie.contains("HttpOnly"), "{cookie}");
+            assert!(cookie.contains(same_site), "{cookie}");
+        }
+        assert!(cookies[0].starts_with("refresh_token=abc"));
+        assert!(cookies[0].contains("Path=/api/auth"));
+    }
+
+    #[tokio::test]
+    async fn disabled_enforcement_leaves_requests_and_cookies_alone() {
+        let response = login(app(false), "198.51.100.20:52000", "http").await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            cookies(&response),
+            [
+                "refresh_token=abc; Path=/api/auth",
+                "theme=dark; SameSite=Lax"
+            ]
+        );
+    }
+}
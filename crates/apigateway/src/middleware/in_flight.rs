@@ -0,0 +1,13 @@
+use crate::state::AppState;
+use axum::{body::Body, extract::State, http::Request, middleware::Next, response::IntoResponse};
+use std::sync::Arc;
+
+pub async fn in_flight_middleware(
+    State(state): State<Arc<AppState>>,
+    req: Request<Body>,
+    next: Next,
+) -> impl IntoResponse {
+    let _guard = state.in_flight.start();
+
+    next.run(req).await
+}
@@ -1,3 +1,4 @@
+use crate::middleware::{roles::TokenRoles, slow_request::AuthenticatedUser};
 use axum::{
     Extension, Json,
     body::Body,
@@ -37,8 +38,8 @@ pub async fn auth_middleware(
         }
     };
 
-    let user_id = match jwt.verify_token(&token, "access") {
-        Ok(id) => id as i32,
+    let claims = match jwt.verify_token_claims(&token, "access") {
+        Ok(claims) => claims,
         Err(_) => {
             return Err((
                 StatusCode::UNAUTHORIZED,
@@ -50,7 +51,13 @@ pub async fn auth_middleware(
         }
     };
 
+    let user_id = claims.user_id as i32;
+
     req.extensions_mut().insert(user_id);
+    req.extensions_mut().insert(TokenRoles(claims.roles));
+
+    let mut response = next.run(req).await;
+    response.extensions_mut().insert(AuthenticatedUser(user_id));
 
-    Ok(next.run(req).await)
+    Ok(response)
 }
@@ -1,4 +1,13 @@
+pub mod deadline;
+pub mod deprecation;
+pub mod https;
+pub mod in_flight;
 pub mod jwt;
 pub mod rate_limit;
+pub mod read_only;
+pub mod request_id;
+pub mod roles;
 pub mod session;
+pub mod slow_request;
+pub mod timezone;
 pub mod validate;
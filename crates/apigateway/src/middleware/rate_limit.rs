@@ -1,47 +1,389 @@
-use crate::abstract_trait::rate_limit::DynRateLimitMiddleware;
+use crate::{
+    abstract_trait::rate_limit::DynRateLimitMiddleware,
+    config::{BulkRateLimitConfig, RateLimitCostConfig, RateLimitGroup, RateLimitGroupConfig},
+    domain::response::rate_limit::RateLimitStatus,
+};
 use axum::{
     Extension, Json,
     body::Body,
-    http::{Request, StatusCode},
+    extract::MatchedPath,
+    http::{HeaderMap, HeaderValue, Request, StatusCode, header, header::RETRY_AFTER},
     middleware::Next,
-    response::IntoResponse,
+    response::{IntoResponse, Response},
 };
-use shared::errors::ErrorResponse;
+use axum_extra::extract::cookie::CookieJar;
+use shared::{abstract_trait::DynJwtService, errors::ErrorResponse};
 use tracing::warn;
 
+/// Who a request is counted against: the user id from a valid access token,
+/// or the client IP when there is none. The token is read the same way as
+/// `auth_middleware` does, since this layer runs before it.
+pub fn rate_limit_identity(headers: &HeaderMap, jwt: &DynJwtService) -> String {
+    let token = CookieJar::from_headers(headers)
+        .get("token")
+        .map(|cookie| cookie.value().to_string())
+        .or_else(|| {
+            headers
+                .get(header::AUTHORIZATION)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.strip_prefix("Bearer ").map(str::to_owned))
+        });
+
+    if let Some(user_id) = token.and_then(|token| jwt.verify_token(&token, "access").ok()) {
+        return format!("user:{user_id}");
+    }
+
+    let client_ip = headers
+        .get("x-forwarded-for")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.split(',').next())
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .unwrap_or("unknown");
+
+    format!("ip:{client_ip}")
+}
+
+pub fn rate_limit_key(identity: &str, group: RateLimitGroup) -> String {
+    format!("rate_limit:{identity}:{}", group.as_str())
+}
+
+pub fn apply_rate_limit_headers(headers: &mut HeaderMap, status: &RateLimitStatus) {
+    headers.insert("x-ratelimit-limit", HeaderValue::from(status.limit));
+    headers.insert("x-ratelimit-remaining", HeaderValue::from(status.remaining));
+    headers.insert("x-ratelimit-reset", HeaderValue::from(status.reset));
+}
+
+fn too_many_requests(status: &RateLimitStatus) -> Response {
+    let mut response = (
+        StatusCode::TOO_MANY_REQUESTS,
+        Json(ErrorResponse {
+            status: "fail".to_string(),
+            message: "Too many requests, please try again later".to_string(),
+        }),
+    )
+        .into_response();
+
+    apply_rate_limit_headers(response.headers_mut(), status);
+    response
+        .headers_mut()
+        .insert(RETRY_AFTER, HeaderValue::from(status.reset_after));
+
+    response
+}
+
 pub async fn rate_limit_middleware(
     Extension(rate_limiter): Extension<DynRateLimitMiddleware>,
+    Extension(costs): Extension<RateLimitCostConfig>,
+    Extension(bulk): Extension<BulkRateLimitConfig>,
+    Extension(groups): Extension<RateLimitGroupConfig>,
+    Extension(jwt): Extension<DynJwtService>,
     req: Request<Body>,
     next: Next,
-) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
-    let client_ip = req
-        .headers()
-        .get("x-forwarded-for")
-        .and_then(|v| v.to_str().ok())
-        .map(|s| s.to_string())
-        .unwrap_or_else(|| "unknown".to_string());
+) -> Response {
+    let identity = rate_limit_identity(req.headers(), &jwt);
+
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|p| p.as_str())
+        .unwrap_or_else(|| req.uri().path());
+    let cost = costs.cost_for(req.method().as_str(), route);
+    let group = groups.group_for(req.method().as_str(), route);
+    let limit = groups.limit_for(group);
+    let key = rate_limit_key(&identity, group);
 
-    let key = format!("rate_limit:{client_ip}");
-    let max_requests = 100;
-    let window_seconds = 60;
+    if bulk.applies_to(route) {
+        let bulk_key = format!("rate_limit:{identity}:bulk");
+        let (allowed, status) = rate_limiter
+            .check_rate_limit(&bulk_key, 1, bulk.max_requests, bulk.window_seconds)
+            .await;
 
-    let (allowed, current) = rate_limiter
-        .check_rate_limit(&key, max_requests, window_seconds)
+        if !allowed {
+            warn!(
+                "Bulk rate limit exceeded for {} on {} (limit: {}, resets in {}s)",
+                identity, route, status.limit, status.reset_after
+            );
+            return too_many_requests(&status);
+        }
+    }
+
+    let (allowed, status) = rate_limiter
+        .check_rate_limit(&key, cost, limit.max_requests, limit.window_seconds)
         .await;
 
     if !allowed {
         warn!(
-            "Rate limit exceeded for IP: {} (requests: {})",
-            client_ip, current
+            "Rate limit exceeded for {} on {} ({} group, cost: {}, limit: {}, resets in {}s)",
+            identity,
+            route,
+            group.as_str(),
+            cost,
+            status.limit,
+            status.reset_after
         );
-        return Err((
-            StatusCode::TOO_MANY_REQUESTS,
-            Json(ErrorResponse {
-                status: "fail".to_string(),
-                message: "Too many requests, please try again later".to_string(),
-            }),
-        ));
+        return too_many_requests(&status);
+    }
+
+    let mut response = next.run(req).await;
+    apply_rate_limit_headers(response.headers_mut(), &status);
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::rate_limit_middleware;
+    use crate::{
+        abstract_trait::rate_limit::{DynRateLimitMiddleware, RateLimitMiddlewareTrait},
+        config::{BulkRateLimitConfig, RateLimitCostConfig, RateLimitGroupConfig},
+        domain::response::rate_limit::RateLimitStatus,
+    };
+    use async_trait::async_trait;
+    use axum::{
+        Extension, Router,
+        body::Body,
+        http::{Request, StatusCode},
+        middleware::from_fn,
+        routing::{delete, get, post},
+    };
+    use chrono::{Duration, Utc};
+    use shared::{
+        abstract_trait::{DynJwtService, JwtServiceTrait},
+        config::Claims,
+        errors::ServiceError,
+    };
+    use std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+    };
+    use tower::ServiceExt;
+
+    /// Counts debits per key in memory, the same way the Redis script does.
+    #[derive(Default)]
+    struct InMemoryLimiter(Mutex<HashMap<String, u32>>);
+
+    #[async_trait]
+    impl RateLimitMiddlewareTrait for InMemoryLimiter {
+        async fn check_rate_limit(
+            &self,
+            key: &str,
+            cost: u32,
+            max_requests: u32,
+            window_seconds: u32,
+        ) -> (bool, RateLimitStatus) {
+            let mut counts = self.0.lock().unwrap();
+            let count = counts.entry(key.to_string()).or_default();
+            let allowed = *count + cost <= max_requests;
+            if allowed {
+                *count += cost;
+            }
+            (
+                allowed,
+                RateLimitStatus::new(max_requests, *count, window_seconds as u64),
+            )
+        }
+
+        async fn get_status(&self, _: &str, _: u32, _: u32) -> RateLimitStatus {
+            unimplemented!()
+        }
+
+        async fn get_remaining(&self, _: &str, _: u32) -> u32 {
+            unimplemented!()
+        }
+
+        async fn reset_limit(&self, _: &str) -> bool {
+            unimplemented!()
+        }
     }
 
-    Ok(next.run(req).await)
+    /// Rejects every token, so requests are counted by client IP.
+    #[derive(Debug)]
+    struct NoTokens;
+
+    impl JwtServiceTrait for NoTokens {
+        fn generate_token(&self, _: i64, _: &str) -> Result<String, ServiceError> {
+            unimplemented!()
+        }
+
+        fn generate_token_with_roles(
+            &self,
+            _: i64,
+            _: &str,
+            _: &[String],
+        ) -> Result<String, ServiceError> {
+            unimplemented!()
+        }
+
+        fn refresh_token_ttl(&self) -> Duration {
+            unimplemented!()
+        }
+
+        fn verify_token(&self, _: &str, _: &str) -> Result<i64, ServiceError> {
+            Err(ServiceError::InvalidCredentials)
+        }
+
+        fn verify_token_claims(&self, _: &str, _: &str) -> Result<Claims, ServiceError> {
+            Err(ServiceError::InvalidCredentials)
+        }
+    }
+
+    fn header(response: &axum::response::Response, name: &str) -> i64 {
+        response.headers()[name].to_str().unwrap().parse().unwrap()
+    }
+
+    #[tokio::test]
+    async fn headers_count_down_and_reset_in_the_future() {
+        let limiter: DynRateLimitMiddleware = Arc::new(InMemoryLimiter::default());
+        let jwt: DynJwtService = Arc::new(NoTokens);
+        let groups = RateLimitGroupConfig::init().unwrap();
+        let bulk = BulkRateLimitConfig {
+            max_requests: 5,
+            window_seconds: 300,
+        };
+
+        let app = Router::new()
+            .route("/api/products", get(|| async { "ok" }))
+            .layer(from_fn(rate_limit_middleware))
+            .layer(Extension(limiter))
+            .layer(Extension(RateLimitCostConfig::default()))
+            .layer(Extension(bulk))
+            .layer(Extension(groups.clone()))
+            .layer(Extension(jwt));
+
+        let limit = groups.read.max_requests as i64;
+        for used in 1..=3 {
+            let response = app
+                .clone()
+                .oneshot(
+                    Request::get("/api/products")
+                        .header("x-forwarded-for", "203.0.113.9")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(header(&response, "x-ratelimit-limit"), limit);
+            assert_eq!(header(&response, "x-ratelimit-remaining"), limit - used);
+            assert!(header(&response, "x-ratelimit-reset") > Utc::now().timestamp());
+        }
+    }
+
+    #[tokio::test]
+    async fn expensive_routes_use_up_the_bucket_faster() {
+        let limiter: DynRateLimitMiddleware = Arc::new(InMemoryLimiter::default());
+        let jwt: DynJwtService = Arc::new(NoTokens);
+        let groups = RateLimitGroupConfig::init().unwrap();
+        let costs = RateLimitCostConfig::parse("POST /api/orders=10", "").unwrap();
+        let bulk = BulkRateLimitConfig {
+            max_requests: 5,
+            window_seconds: 300,
+        };
+
+        let app = Router::new()
+            .route("/api/orders", post(|| async { "created" }))
+            .route("/api/carts", post(|| async { "created" }))
+            .layer(from_fn(rate_limit_middleware))
+            .layer(Extension(limiter))
+            .layer(Extension(costs))
+            .layer(Extension(bulk))
+            .layer(Extension(groups.clone()))
+            .layer(Extension(jwt));
+
+        // Each route gets its own client, so each has a full write bucket.
+        let post_as = async |client: &str, uri: &str| {
+            app.clone()
+                .oneshot(
+                    Request::post(uri)
+                        .header("x-forwarded-for", client)
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap()
+        };
+
+        let limit = groups.write.max_requests;
+        let affordable = limit / 10;
+        assert!(affordable >= 1, "write limit {limit} is below one order");
+
+        for sent in 1..=affordable {
+            let expensive = post_as("203.0.113.1", "/api/orders").await;
+            assert_eq!(expensive.status(), StatusCode::OK);
+            assert_eq!(
+                header(&expensive, "x-ratelimit-remaining"),
+                i64::from(limit - 10 * sent)
+            );
+
+            let cheap = post_as("203.0.113.2", "/api/carts").await;
+            assert_eq!(cheap.status(), StatusCode::OK);
+            assert_eq!(
+                header(&cheap, "x-ratelimit-remaining"),
+                i64::from(limit - sent)
+            );
+        }
+
+        let expensive = post_as("203.0.113.1", "/api/orders").await;
+        assert_eq!(expensive.status(), StatusCode::TOO_MANY_REQUESTS);
+
+        let cheap = post_as("203.0.113.2", "/api/carts").await;
+        assert_eq!(cheap.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn bulk_admin_routes_hit_their_own_lower_limit() {
+        let limiter: DynRateLimitMiddleware = Arc::new(InMemoryLimiter::default());
+        let jwt: DynJwtService = Arc::new(NoTokens);
+        let groups = RateLimitGroupConfig::init().unwrap();
+        let bulk = BulkRateLimitConfig {
+            max_requests: 2,
+            window_seconds: 300,
+        };
+
+        let app = Router::new()
+            .route("/api/orders/delete-all", delete(|| async { "purged" }))
+            .route("/api/orders", post(|| async { "created" }))
+            .layer(from_fn(rate_limit_middleware))
+            .layer(Extension(limiter))
+            .layer(Extension(RateLimitCostConfig::default()))
+            .layer(Extension(bulk))
+            .layer(Extension(groups.clone()))
+            .layer(Extension(jwt));
+
+        let send = async |request: axum::http::request::Builder| {
+            app.clone()
+                .oneshot(
+                    request
+                        .header("x-forwarded-for", "203.0.113.7")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap()
+        };
+
+        for _ in 0..2 {
+            let response = send(Request::delete("/api/orders/delete-all")).await;
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+
+        let refused = send(Request::delete("/api/orders/delete-all")).await;
+        assert_eq!(refused.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(header(&refused, "x-ratelimit-limit"), 2);
+        assert!(groups.write.max_requests > 3);
+
+        // Ordinary writes from the same caller still run on the general
+        // limit, which the bulk calls only dented.
+        let response = send(Request::post("/api/orders")).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            header(&response, "x-ratelimit-limit"),
+            i64::from(groups.write.max_requests)
+        );
+        assert_eq!(
+            header(&response, "x-ratelimit-remaining"),
+            i64::from(groups.write.max_requests - 3)
+        );
+    }
 }
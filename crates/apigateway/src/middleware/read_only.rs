@@ -0,0 +1,98 @@
+use crate::config::ReadOnlyConfig;
+use axum::{
+    body::Body,
+    extract::State,
+    http::Request,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use shared::errors::HttpError;
+use tracing::info;
+
+/// Rejects mutating requests with a 403 while `READ_ONLY_MODE` is on.
+pub async fn read_only_middleware(
+    State(read_only): State<ReadOnlyConfig>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    if read_only.rejects(req.method(), req.uri().path()) {
+        info!(
+            "Rejected {} {} in read-only mode",
+            req.method(),
+            req.uri().path()
+        );
+        return HttpError::Forbidden("The service is in read-only mode".to_string())
+            .into_response();
+    }
+
+    next.run(req).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::read_only_middleware;
+    use crate::config::ReadOnlyConfig;
+    use axum::{
+        Router,
+        body::Body,
+        http::{Method, Request, StatusCode},
+        middleware,
+        routing::{get, post},
+    };
+    use tower::ServiceExt;
+
+    fn router(enabled: bool) -> Router {
+        let ok = || async { "ok" };
+
+        Router::new()
+            .route(
+                "/api/products/{id}",
+                get(ok).post(ok).put(ok).patch(ok).delete(ok),
+            )
+            .route("/api/auth/login", post(ok))
+            .route("/api/admin/email/preview", post(ok))
+            .layer(middleware::from_fn_with_state(
+                ReadOnlyConfig { enabled },
+                read_only_middleware,
+            ))
+    }
+
+    async fn status(enabled: bool, method: Method, uri: &str) -> StatusCode {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .body(Body::empty())
+            .unwrap();
+
+        router(enabled).oneshot(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn read_only_mode_forbids_writes_but_serves_reads() {
+        for method in [Method::POST, Method::PUT, Method::PATCH, Method::DELETE] {
+            assert_eq!(
+                status(true, method.clone(), "/api/products/1").await,
+                StatusCode::FORBIDDEN,
+                "{method}"
+            );
+            assert_eq!(
+                status(false, method.clone(), "/api/products/1").await,
+                StatusCode::OK,
+                "{method}"
+            );
+        }
+
+        assert_eq!(
+            status(true, Method::GET, "/api/products/1").await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            status(true, Method::POST, "/api/auth/login").await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            status(true, Method::POST, "/api/admin/email/preview").await,
+            StatusCode::OK
+        );
+    }
+}
@@ -0,0 +1,43 @@
+use axum::{
+    body::Body,
+    http::{HeaderValue, Request},
+    middleware::Next,
+    response::Response,
+};
+use shared::utils::{REQUEST_ID_HEADER, request_id_or_new, with_request_id};
+use tracing::{Instrument, info_span};
+
+/// The request's `X-Request-Id`, as kept or generated by `request_id_middleware`.
+#[derive(Debug, Clone)]
+pub struct RequestId(pub String);
+
+/// Reuses the client's `X-Request-Id` or generates one, and handles the
+/// request inside a span and request scope carrying it. gRPC calls made
+/// while handling it forward the ID in their metadata, and the response
+/// echoes it back.
+pub async fn request_id_middleware(mut req: Request<Body>, next: Next) -> Response {
+    let request_id = request_id_or_new(
+        req.headers()
+            .get(REQUEST_ID_HEADER)
+            .and_then(|v| v.to_str().ok()),
+    );
+
+    req.extensions_mut().insert(RequestId(request_id.clone()));
+
+    let span = info_span!(
+        "http_request",
+        request_id = %request_id,
+        method = %req.method(),
+        path = req.uri().path(),
+    );
+
+    let mut response = with_request_id(request_id.clone(), next.run(req))
+        .instrument(span)
+        .await;
+
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+
+    response
+}
@@ -0,0 +1,154 @@
+use crate::config::RouteRolesConfig;
+use axum::{
+    Extension, Json,
+    body::Body,
+    extract::MatchedPath,
+    http::{Request, StatusCode},
+    middleware::Next,
+    response::IntoResponse,
+};
+use shared::errors::{ErrorResponse, HttpError};
+
+/// Role names from the caller's access token, set by `auth_middleware`.
+#[derive(Debug, Clone, Default)]
+pub struct TokenRoles(pub Vec<String>);
+
+impl TokenRoles {
+    pub fn has_any(&self, roles: &[&str]) -> bool {
+        self.0.iter().any(|role| roles.contains(&role.as_str()))
+    }
+
+    /// For routes any signed-in user may call on their own account: passes
+    /// when `caller_id` is `target_id` or the token carries `ROLE_ADMIN`.
+    /// `action` completes the 403 message, e.g. "export this data".
+    pub fn require_self_or_admin(
+        &self,
+        caller_id: i32,
+        target_id: i32,
+        action: &str,
+    ) -> Result<(), HttpError> {
+        if caller_id == target_id || self.has_any(&["ROLE_ADMIN"]) {
+            return Ok(());
+        }
+
+        Err(HttpError::Forbidden(format!(
+            "Access denied. Only the user or an ADMIN can {action}"
+        )))
+    }
+
+    /// For records that belong to a user, such as orders: passes when
+    /// `caller_id` owns the record or the token carries one of `roles`.
+    pub fn require_owner_or(
+        &self,
+        caller_id: i32,
+        owner_id: i32,
+        roles: &[&str],
+        action: &str,
+    ) -> Result<(), HttpError> {
+        if caller_id == owner_id || self.has_any(roles) {
+            return Ok(());
+        }
+
+        Err(HttpError::Forbidden(format!(
+            "Access denied. Only the owner or {} can {action}",
+            role_names(roles)
+        )))
+    }
+}
+
+/// `["ROLE_ADMIN", "ROLE_MODERATOR"]` as "ADMIN or MODERATOR".
+fn role_names(roles: &[&str]) -> String {
+    roles
+        .iter()
+        .map(|role| role.trim_start_matches("ROLE_"))
+        .collect::<Vec<_>>()
+        .join(" or ")
+}
+
+/// Answers 403 unless the token carries one of the roles `ROUTE_ROLES`
+/// requires for the matched route. Must run after `auth_middleware`, so it
+/// is the innermost `route_layer`.
+pub async fn require_roles(
+    Extension(route_roles): Extension<RouteRolesConfig>,
+    req: Request<Body>,
+    next: Next,
+) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    let required = req
+        .extensions()
+        .get::<MatchedPath>()
+        .and_then(|route| route_roles.roles_for(req.method().as_str(), route.as_str()));
+
+    if let Some(required) = required {
+        let allowed = req
+            .extensions()
+            .get::<TokenRoles>()
+            .is_some_and(|roles| roles.has_any(required));
+
+        if !allowed {
+            return Err((
+                StatusCode::FORBIDDEN,
+                Json(ErrorResponse {
+                    status: "fail".to_string(),
+                    message: format!("Access denied. Required role: {}", role_names(required)),
+                }),
+            ));
+        }
+    }
+
+    Ok(next.run(req).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TokenRoles;
+    use shared::errors::HttpError;
+
+    #[test]
+    fn self_or_admin_allows_the_owner_and_admins_only() {
+        let member = TokenRoles(vec!["ROLE_USER".to_string()]);
+        let admin = TokenRoles(vec!["ROLE_USER".to_string(), "ROLE_ADMIN".to_string()]);
+
+        assert!(
+            member
+                .require_self_or_admin(7, 7, "view these orders")
+                .is_ok()
+        );
+        assert!(
+            admin
+                .require_self_or_admin(1, 7, "view these orders")
+                .is_ok()
+        );
+        assert!(matches!(
+            member.require_self_or_admin(1, 7, "view these orders"),
+            Err(HttpError::Forbidden(message)) if message.ends_with("can view these orders")
+        ));
+        assert!(
+            TokenRoles::default()
+                .require_self_or_admin(1, 7, "view these orders")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn owner_or_roles_rejects_other_users_orders() {
+        let staff = &["ROLE_ADMIN", "ROLE_MODERATOR"];
+        let member = TokenRoles(vec!["ROLE_USER".to_string()]);
+        let moderator = TokenRoles(vec!["ROLE_MODERATOR".to_string()]);
+
+        assert!(
+            member
+                .require_owner_or(7, 7, staff, "update this order")
+                .is_ok()
+        );
+        assert!(
+            moderator
+                .require_owner_or(1, 7, staff, "update this order")
+                .is_ok()
+        );
+        assert!(matches!(
+            member.require_owner_or(1, 7, staff, "update this order"),
+            Err(HttpError::Forbidden(message))
+                if message == "Access denied. Only the owner or ADMIN or MODERATOR can update this order"
+        ));
+    }
+}
@@ -0,0 +1,59 @@
+use crate::state::AppState;
+use axum::{
+    body::Body,
+    extract::{MatchedPath, State},
+    http::Request,
+    middleware::Next,
+    response::Response,
+};
+use std::sync::Arc;
+use tokio::time::Instant;
+use tracing::warn;
+
+/// Set on the response by `auth_middleware`, so outer layers can tell who a
+/// request was made by.
+#[derive(Debug, Clone, Copy)]
+pub struct AuthenticatedUser(pub i32);
+
+/// Logs a warning for any request slower than `SLOW_REQUEST_THRESHOLD_MS`,
+/// with its route, user and how much of the request budget it used.
+pub async fn slow_request_middleware(
+    State(state): State<Arc<AppState>>,
+    matched_path: Option<MatchedPath>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let Some(threshold) = state.slow_request.threshold else {
+        return next.run(req).await;
+    };
+
+    let method = req.method().clone();
+    let route = matched_path
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| req.uri().path().to_string());
+    let started = Instant::now();
+
+    let response = next.run(req).await;
+    let elapsed = started.elapsed();
+
+    if elapsed > threshold {
+        let user = response
+            .extensions()
+            .get::<AuthenticatedUser>()
+            .map(|user| user.0.to_string());
+        let budget = state.request_budget.budget;
+
+        warn!(
+            method = %method,
+            route,
+            user = user.as_deref().unwrap_or("anonymous"),
+            status = response.status().as_u16(),
+            elapsed_ms = elapsed.as_millis() as u64,
+            threshold_ms = threshold.as_millis() as u64,
+            budget_used_pct = (elapsed.as_secs_f64() / budget.as_secs_f64() * 100.0).round(),
+            "🐢 Slow request"
+        );
+    }
+
+    response
+}
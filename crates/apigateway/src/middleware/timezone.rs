@@ -0,0 +1,175 @@
+use crate::config::DisplayTimezoneConfig;
+use axum::{
+    body::{Body, to_bytes},
+    extract::State,
+    http::{
+        Request,
+        header::{CONTENT_LENGTH, CONTENT_TYPE},
+    },
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use chrono_tz::Tz;
+use serde_json::Value;
+use shared::{errors::HttpError, utils::format_in_timezone};
+use tracing::warn;
+
+pub const ACCEPT_TIMEZONE: &str = "accept-timezone";
+
+/// Renders the `*_at` timestamps of JSON responses in the caller's display
+/// timezone. Handlers and the response cache keep working in UTC; the
+/// rewrite happens once, on the way out.
+pub async fn display_timezone_middleware(
+    State(display_timezone): State<DisplayTimezoneConfig>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let accept_timezone = req
+        .headers()
+        .get(ACCEPT_TIMEZONE)
+        .and_then(|v| v.to_str().ok());
+
+    let tz = match display_timezone.resolve(accept_timezone) {
+        Ok(tz) => tz,
+        Err(e) => return HttpError::BadRequest(e).into_response(),
+    };
+
+    let response = next.run(req).await;
+
+    let is_json = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("application/json"));
+
+    if tz == Tz::UTC || !is_json {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+
+    let bytes = match to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            warn!("Failed to buffer response for timezone rendering: {e}");
+            return HttpError::Internal("Failed to render response".to_string()).into_response();
+        }
+    };
+
+    let body = match serde_json::from_slice::<Value>(&bytes) {
+        Ok(mut value) => {
+            localize_timestamps(&mut value, tz);
+            match serde_json::to_vec(&value) {
+                Ok(rendered) => Body::from(rendered),
+                Err(_) => Body::from(bytes),
+            }
+        }
+        Err(_) => Body::from(bytes),
+    };
+
+    parts.headers.remove(CONTENT_LENGTH);
+    Response::from_parts(parts, body)
+}
+
+fn localize_timestamps(value: &mut Value, tz: Tz) {
+    match value {
+        Value::Object(fields) => {
+            for (key, field) in fields.iter_mut() {
+                if key.ends_with("_at")
+                    && let Value::String(timestamp) = field
+                {
+                    if let Some(local) = format_in_timezone(timestamp, tz) {
+                        *timestamp = local;
+                    }
+                } else {
+                    localize_timestamps(field, tz);
+                }
+            }
+        }
+        Value::Array(items) => items
+            .iter_mut()
+            .for_each(|item| localize_timestamps(item, tz)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ACCEPT_TIMEZONE, display_timezone_middleware};
+    use crate::config::DisplayTimezoneConfig;
+    use axum::{
+        Json, Router,
+        body::{Body, to_bytes},
+        http::{Request, StatusCode},
+        middleware::from_fn_with_state,
+        routing::get,
+    };
+    use chrono_tz::Tz;
+    use serde_json::{Value, json};
+    use tower::ServiceExt;
+
+    const CREATED_AT: &str = "2025-01-15T10:30:00+00:00";
+
+    fn app(default_timezone: Tz) -> Router {
+        Router::new()
+            .route(
+                "/api/orders/{id}",
+                get(|| async {
+                    Json(json!({
+                        "data": {
+                            "id": 1,
+                            "created_at": CREATED_AT,
+                            "items": [{ "updated_at": CREATED_AT, "note": CREATED_AT }],
+                        }
+                    }))
+                }),
+            )
+            .layer(from_fn_with_state(
+                DisplayTimezoneConfig { default_timezone },
+                display_timezone_middleware,
+            ))
+    }
+
+    async fn get_order(app: Router, accept_timezone: Option<&str>) -> (StatusCode, Value) {
+        let mut request = Request::get("/api/orders/1");
+        if let Some(tz) = accept_timezone {
+            request = request.header(ACCEPT_TIMEZONE, tz);
+        }
+
+        let response = app
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn the_same_instant_renders_in_each_configured_timezone() {
+        for (tz, expected) in [
+            (Tz::UTC, CREATED_AT),
+            (Tz::Asia__Jakarta, "2025-01-15T17:30:00+07:00"),
+            (Tz::America__New_York, "2025-01-15T05:30:00-05:00"),
+        ] {
+            let (status, body) = get_order(app(tz), None).await;
+            assert_eq!(status, StatusCode::OK);
+
+            let order = &body["data"];
+            assert_eq!(order["created_at"], expected, "{tz}");
+            assert_eq!(order["items"][0]["updated_at"], expected, "{tz}");
+            // Only `*_at` fields are timestamps.
+            assert_eq!(order["items"][0]["note"], CREATED_AT);
+        }
+    }
+
+    #[tokio::test]
+    async fn accept_timezone_overrides_the_default() {
+        let (_, body) = get_order(app(Tz::Asia__Jakarta), Some("Europe/London")).await;
+        assert_eq!(body["data"]["created_at"], "2025-01-15T10:30:00+00:00");
+
+        let (status, body) = get_order(app(Tz::UTC), Some("Mars/Olympus_Mons")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(body.to_string().contains("Mars/Olympus_Mons"), "{body}");
+    }
+}
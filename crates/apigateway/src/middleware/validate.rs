@@ -1,11 +1,61 @@
+use crate::config::JsonBodyConfig;
 use axum::{
-    extract::{FromRequest, Request},
+    Json,
+    extract::{FromRequest, Request, rejection::JsonRejection},
     http::StatusCode,
+    response::{IntoResponse, Response},
 };
 use serde::de::DeserializeOwned;
+use serde_ignored::Path;
 use serde_json::{Value, json};
+use shared::errors::{FieldViolation, ValidationErrorResponse};
+use std::error::Error;
 use validator::{Validate, ValidationErrors};
 
+/// `axum::Json` whose rejections use the API's JSON error envelope instead of
+/// axum's plain-text body. With `STRICT_JSON_FIELDS` on, fields `T` doesn't
+/// know are rejected rather than ignored.
+pub struct ApiJson<T>(pub T);
+
+impl<S, T> FromRequest<S> for ApiJson<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let strict = req
+            .extensions()
+            .get::<JsonBodyConfig>()
+            .is_some_and(|config| config.strict);
+
+        if !strict {
+            return Json::<T>::from_request(req, state)
+                .await
+                .map(|Json(value)| Self(value))
+                .map_err(json_rejection_response);
+        }
+
+        let Json(body) = Json::<Value>::from_request(req, state)
+            .await
+            .map_err(json_rejection_response)?;
+
+        let mut unknown = Vec::new();
+        let value = serde_path_to_error::deserialize(serde_ignored::Deserializer::new(
+            body,
+            &mut |path: Path| unknown.push(field_path(&path)),
+        ))
+        .map_err(|err| data_error_response(&err))?;
+
+        if !unknown.is_empty() {
+            return Err(unknown_fields_response(unknown));
+        }
+
+        Ok(Self(value))
+    }
+}
+
 pub struct SimpleValidatedJson<T>(pub T);
 
 impl<S, T> FromRequest<S> for SimpleValidatedJson<T>
@@ -13,19 +63,10 @@ where
     T: DeserializeOwned + Validate + Send,
     S: Send + Sync,
 {
-    type Rejection = (StatusCode, axum::Json<Value>);
+    type Rejection = Response;
 
     async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
-        let axum::Json(json_value) =
-            axum::Json::<T>::from_request(req, state)
-                .await
-                .map_err(|rejection| {
-                    let payload = json!({
-                        "error": "Invalid JSON",
-                        "message": rejection.body_text(),
-                    });
-                    (rejection.status(), axum::Json(payload))
-                })?;
+        let ApiJson(json_value) = ApiJson::<T>::from_request(req, state).await?;
 
         json_value.validate().map_err(|validation_errors| {
             let payload = json!({
@@ -33,13 +74,95 @@ where
                 "message": format_validation_errors(&validation_errors),
                 "details": format_validation_errors_detailed(&validation_errors)
             });
-            (StatusCode::BAD_REQUEST, axum::Json(payload))
+            (StatusCode::BAD_REQUEST, Json(payload)).into_response()
         })?;
 
         Ok(Self(json_value))
     }
 }
 
+fn json_rejection_response(rejection: JsonRejection) -> Response {
+    let errors = match &rejection {
+        JsonRejection::JsonDataError(err) => data_error_violation(err).into_iter().collect(),
+        _ => Vec::new(),
+    };
+
+    let body = ValidationErrorResponse {
+        status: "error".into(),
+        code: "invalid_body".into(),
+        message: rejection.body_text(),
+        errors,
+    };
+
+    (rejection.status(), Json(body)).into_response()
+}
+
+/// Points at the offending field, e.g. `items[0].quantity`, when serde could
+/// track where deserialization failed.
+fn data_error_violation(err: &(dyn Error + 'static)) -> Option<FieldViolation> {
+    let mut source = err.source();
+
+    while let Some(inner) = source {
+        if let Some(path_err) =
+            inner.downcast_ref::<serde_path_to_error::Error<serde_json::Error>>()
+        {
+            return path_violation(path_err);
+        }
+        source = inner.source();
+    }
+
+    None
+}
+
+fn path_violation(err: &serde_path_to_error::Error<serde_json::Error>) -> Option<FieldViolation> {
+    let field = err.path().to_string();
+
+    (field != ".").then(|| FieldViolation::new(field, err.inner().to_string()))
+}
+
+/// Same body axum's `JsonDataError` rejection gets, for strict-mode bodies
+/// that are deserialized outside of `Json`.
+fn data_error_response(err: &serde_path_to_error::Error<serde_json::Error>) -> Response {
+    let body = ValidationErrorResponse {
+        status: "error".into(),
+        code: "invalid_body".into(),
+        message: format!("Failed to deserialize the JSON body into the target type: {err}"),
+        errors: path_violation(err).into_iter().collect(),
+    };
+
+    (StatusCode::UNPROCESSABLE_ENTITY, Json(body)).into_response()
+}
+
+fn unknown_fields_response(fields: Vec<String>) -> Response {
+    let body = ValidationErrorResponse {
+        status: "error".into(),
+        code: "unknown_field".into(),
+        message: format!("Unknown field(s) in request body: {}", fields.join(", ")),
+        errors: fields
+            .into_iter()
+            .map(|field| FieldViolation::new(field, "Unknown field"))
+            .collect(),
+    };
+
+    (StatusCode::UNPROCESSABLE_ENTITY, Json(body)).into_response()
+}
+
+/// Names an ignored field the way serde_path_to_error does, e.g.
+/// `items[0].note`.
+fn field_path(path: &Path) -> String {
+    match path {
+        Path::Root => String::new(),
+        Path::Seq { parent, index } => format!("{}[{index}]", field_path(parent)),
+        Path::Map { parent, key } => match field_path(parent) {
+            parent if parent.is_empty() => key.to_string(),
+            parent => format!("{parent}.{key}"),
+        },
+        Path::Some { parent }
+        | Path::NewtypeStruct { parent }
+        | Path::NewtypeVariant { parent } => field_path(parent),
+    }
+}
+
 fn format_validation_errors(errors: &ValidationErrors) -> String {
     let mut error_messages = Vec::new();
 
@@ -93,3 +216,158 @@ fn format_validation_errors_detailed(errors: &ValidationErrors) -> Value {
 
     json!(error_map)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::{Body, to_bytes};
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize)]
+    struct Item {
+        product_id: i32,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Order {
+        items: Vec<Item>,
+    }
+
+    fn request(body: &str, strict: bool) -> Request {
+        let mut req = Request::builder()
+            .method("POST")
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        req.extensions_mut().insert(JsonBodyConfig { strict });
+        req
+    }
+
+    async fn body_json(response: Response) -> Value {
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    const TYPO: &str = r#"{"items":[{"product_id":1,"quantiy":2}]}"#;
+
+    #[tokio::test]
+    async fn strict_mode_rejects_and_names_unknown_fields() {
+        let Err(response) = ApiJson::<Order>::from_request(request(TYPO, true), &()).await else {
+            panic!("unknown field was accepted");
+        };
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let body = body_json(response).await;
+        assert_eq!(body["code"], "unknown_field");
+        assert_eq!(body["errors"][0]["field"], "items[0].quantiy");
+    }
+
+    #[tokio::test]
+    async fn lenient_mode_ignores_unknown_fields() {
+        let Ok(ApiJson(order)) = ApiJson::<Order>::from_request(request(TYPO, false), &()).await
+        else {
+            panic!("unknown field was rejected");
+        };
+
+        assert_eq!(order.items[0].product_id, 1);
+    }
+
+    #[tokio::test]
+    async fn strict_mode_accepts_known_fields() {
+        let body = r#"{"items":[{"product_id":3}]}"#;
+
+        assert!(
+            ApiJson::<Order>::from_request(request(body, true), &())
+                .await
+                .is_ok()
+        );
+    }
+
+    #[tokio::test]
+    async fn strict_mode_points_at_a_mistyped_field() {
+        let body = r#"{"items":[{"product_id":"three"}]}"#;
+        let Err(response) = ApiJson::<Order>::from_request(request(body, true), &()).await else {
+            panic!("wrong type was accepted");
+        };
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(
+            body_json(response).await["errors"][0]["field"],
+            "items[0].product_id"
+        );
+    }
+
+    #[tokio::test]
+    async fn malformed_json_gets_the_error_envelope_in_both_modes() {
+        for strict in [false, true] {
+            let body = r#"{"items":[{"product_id":1}"#;
+            let Err(response) = ApiJson::<Order>::from_request(request(body, strict), &()).await
+            else {
+                panic!("malformed body was accepted");
+            };
+
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+            assert_eq!(
+                response.headers()["content-type"],
+                "application/json",
+                "strict: {strict}"
+            );
+
+            let body = body_json(response).await;
+            assert_eq!(body["status"], "error");
+            assert_eq!(body["code"], "invalid_body");
+            assert!(
+                body["message"]
+                    .as_str()
+                    .unwrap()
+                    .starts_with("Failed to parse the request body as JSON"),
+                "{body}"
+            );
+            assert_eq!(body["errors"], json!([]));
+        }
+    }
+
+    #[tokio::test]
+    async fn lenient_mode_points_at_a_mistyped_field() {
+        let body = r#"{"items":[{"product_id":"three"}]}"#;
+        let Err(response) = ApiJson::<Order>::from_request(request(body, false), &()).await else {
+            panic!("wrong type was accepted");
+        };
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let body = body_json(response).await;
+        assert_eq!(body["code"], "invalid_body");
+        assert_eq!(body["errors"][0]["field"], "items[0].product_id");
+        assert!(
+            body["errors"][0]["description"]
+                .as_str()
+                .unwrap()
+                .contains("invalid type"),
+            "{body}"
+        );
+    }
+
+    #[test]
+    fn field_path_matches_serde_path_to_error() {
+        let root = Path::Root;
+        let items = Path::Map {
+            parent: &root,
+            key: "items".to_string(),
+        };
+        let first = Path::Seq {
+            parent: &items,
+            index: 0,
+        };
+        let some = Path::Some { parent: &first };
+        let note = Path::Map {
+            parent: &some,
+            key: "note".to_string(),
+        };
+
+        assert_eq!(field_path(&root), "");
+        assert_eq!(field_path(&items), "items");
+        assert_eq!(field_path(&note), "items[0].note");
+    }
+}
@@ -5,15 +5,20 @@ use crate::{
             auth::{AuthRequest as DomainLoginRequest, RegisterRequest as DomainRegisterRequest},
             reset_token::CreateResetPasswordRequest as DomainResetPasswordRequest,
         },
-        response::{api::ApiResponse, token::TokenResponse, user::UserResponse},
+        response::{
+            api::ApiResponse, lockout::LockoutStatusResponse, login_session::LoginSessionResponse,
+            token::TokenResponse, user::UserResponse,
+        },
     },
+    middleware::deadline::apply_deadline,
 };
 use anyhow::Result;
 use async_trait::async_trait;
 use genproto::{
     auth::{
-        ApiResponseLogin, ForgotPasswordRequest, GetMeRequest, LoginRequest, RefreshTokenRequest,
-        ResetPasswordRequest, VerifyCodeRequest, auth_service_client::AuthServiceClient,
+        ApiResponseLogin, ForgotPasswordRequest, GetMeRequest, ListSessionsRequest, LoginRequest,
+        RefreshTokenRequest, ResendVerificationRequest, ResetPasswordRequest, RevokeSessionRequest,
+        UserLockoutRequest, VerifyCodeRequest, auth_service_client::AuthServiceClient,
     },
     common::RegisterRequest,
 };
@@ -51,6 +56,7 @@ impl AuthGrpcClientService {
         global::get_text_map_propagator(|propagator| {
             propagator.inject_context(cx, &mut MetadataInjector(request.metadata_mut()))
         });
+        apply_deadline(request);
     }
 
     fn start_tracing(&self, operation_name: &str, attributes: Vec<KeyValue>) -> TracingContext {
@@ -253,6 +259,7 @@ impl AuthGrpcClientTrait for AuthGrpcClientService {
         let mut request = Request::new(LoginRequest {
             email: input.email.clone(),
             password: input.password.clone(),
+            user_agent: input.user_agent.clone().unwrap_or_default(),
         });
 
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
@@ -379,7 +386,7 @@ impl AuthGrpcClientTrait for AuthGrpcClientService {
             }
         };
 
-        let domain_user: UserResponse = user_data.into();
+        let domain_user = UserResponse::from_proto_with_roles(user_data);
 
         let api_response = ApiResponse {
             status: inner.status,
@@ -450,6 +457,66 @@ impl AuthGrpcClientTrait for AuthGrpcClientService {
         info!("Forgot password initiated for: {email}");
         Ok(api_response)
     }
+    async fn resend_verification(&self, email: &str) -> Result<ApiResponse<bool>, HttpError> {
+        info!("Requesting verification resend for: {}", email);
+
+        let method = Method::Post;
+        let tracing_ctx = self.start_tracing(
+            "ResendVerification",
+            vec![
+                KeyValue::new("component", "auth"),
+                KeyValue::new("user.email", email.to_string()),
+            ],
+        );
+
+        let mut request = Request::new(ResendVerificationRequest {
+            email: email.to_string(),
+        });
+
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let response = match self.client.clone().resend_verification(request).await {
+            Ok(response) => {
+                info!("gRPC resend_verification succeeded");
+                self.complete_tracing_success(
+                    &tracing_ctx,
+                    method.clone(),
+                    "gRPC resend_verification succeeded",
+                )
+                .await;
+                response
+            }
+            Err(status) => {
+                error!(
+                    "gRPC resend_verification failed: {}: {}",
+                    status.code(),
+                    status.message()
+                );
+                self.complete_tracing_error(
+                    &tracing_ctx,
+                    method,
+                    &format!(
+                        "gRPC resend_verification failed: {}: {}",
+                        status.code(),
+                        status.message()
+                    ),
+                )
+                .await;
+                return Err(AppErrorGrpc::from(status).into());
+            }
+        };
+
+        let inner = response.into_inner();
+
+        let api_response = ApiResponse {
+            status: inner.status,
+            message: inner.message,
+            data: true,
+        };
+
+        info!("Verification resend requested for: {email}");
+        Ok(api_response)
+    }
     async fn reset_password(
         &self,
         request: &DomainResetPasswordRequest,
@@ -649,4 +716,219 @@ impl AuthGrpcClientTrait for AuthGrpcClientService {
         info!("Token refreshed successfully");
         Ok(api_response)
     }
+
+    async fn get_lockout_status(
+        &self,
+        user_id: i32,
+    ) -> Result<ApiResponse<LockoutStatusResponse>, HttpError> {
+        info!("Fetching lockout status for user ID: {}", user_id);
+
+        let method = Method::Get;
+        let tracing_ctx = self.start_tracing(
+            "GetLockoutStatus",
+            vec![
+                KeyValue::new("component", "auth"),
+                KeyValue::new("user.id", user_id.to_string()),
+            ],
+        );
+
+        let mut request = Request::new(UserLockoutRequest { user_id });
+
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let response = match self.client.clone().get_lockout_status(request).await {
+            Ok(response) => {
+                self.complete_tracing_success(
+                    &tracing_ctx,
+                    method,
+                    "gRPC get_lockout_status succeeded",
+                )
+                .await;
+                response
+            }
+            Err(status) => {
+                let message = format!(
+                    "gRPC get_lockout_status failed: {}: {}",
+                    status.code(),
+                    status.message()
+                );
+                error!("{message}");
+                self.complete_tracing_error(&tracing_ctx, method, &message)
+                    .await;
+                return Err(AppErrorGrpc::from(status).into());
+            }
+        };
+
+        let inner = response.into_inner();
+
+        let data = inner.data.ok_or_else(|| {
+            error!("Lockout status is missing in gRPC response");
+            HttpError::Internal("Lockout status is missing in gRPC response".into())
+        })?;
+
+        Ok(ApiResponse {
+            status: inner.status,
+            message: inner.message,
+            data: data.into(),
+        })
+    }
+
+    async fn unlock_account(
+        &self,
+        user_id: i32,
+    ) -> Result<ApiResponse<LockoutStatusResponse>, HttpError> {
+        info!("Unlocking account for user ID: {}", user_id);
+
+        let method = Method::Post;
+        let tracing_ctx = self.start_tracing(
+            "UnlockAccount",
+            vec![
+                KeyValue::new("component", "auth"),
+                KeyValue::new("user.id", user_id.to_string()),
+            ],
+        );
+
+        let mut request = Request::new(UserLockoutRequest { user_id });
+
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let response = match self.client.clone().unlock_account(request).await {
+            Ok(response) => {
+                self.complete_tracing_success(
+                    &tracing_ctx,
+                    method,
+                    "gRPC unlock_account succeeded",
+                )
+                .await;
+                response
+            }
+            Err(status) => {
+                let message = format!(
+                    "gRPC unlock_account failed: {}: {}",
+                    status.code(),
+                    status.message()
+                );
+                error!("{message}");
+                self.complete_tracing_error(&tracing_ctx, method, &message)
+                    .await;
+                return Err(AppErrorGrpc::from(status).into());
+            }
+        };
+
+        let inner = response.into_inner();
+
+        let data = inner.data.ok_or_else(|| {
+            error!("Lockout status is missing in gRPC response");
+            HttpError::Internal("Lockout status is missing in gRPC response".into())
+        })?;
+
+        Ok(ApiResponse {
+            status: inner.status,
+            message: inner.message,
+            data: data.into(),
+        })
+    }
+
+    async fn list_sessions(
+        &self,
+        user_id: i32,
+    ) -> Result<ApiResponse<Vec<LoginSessionResponse>>, HttpError> {
+        info!("Listing sessions for user ID: {}", user_id);
+
+        let method = Method::Get;
+        let tracing_ctx = self.start_tracing(
+            "ListSessions",
+            vec![
+                KeyValue::new("component", "auth"),
+                KeyValue::new("user.id", user_id.to_string()),
+            ],
+        );
+
+        let mut request = Request::new(ListSessionsRequest { user_id });
+
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let response = match self.client.clone().list_sessions(request).await {
+            Ok(response) => {
+                self.complete_tracing_success(&tracing_ctx, method, "gRPC list_sessions succeeded")
+                    .await;
+                response
+            }
+            Err(status) => {
+                let message = format!(
+                    "gRPC list_sessions failed: {}: {}",
+                    status.code(),
+                    status.message()
+                );
+                error!("{message}");
+                self.complete_tracing_error(&tracing_ctx, method, &message)
+                    .await;
+                return Err(AppErrorGrpc::from(status).into());
+            }
+        };
+
+        let inner = response.into_inner();
+
+        Ok(ApiResponse {
+            status: inner.status,
+            message: inner.message,
+            data: inner.data.into_iter().map(Into::into).collect(),
+        })
+    }
+
+    async fn revoke_session(
+        &self,
+        user_id: i32,
+        session_id: i32,
+    ) -> Result<ApiResponse<bool>, HttpError> {
+        info!("Revoking session {} for user ID: {}", session_id, user_id);
+
+        let method = Method::Delete;
+        let tracing_ctx = self.start_tracing(
+            "RevokeSession",
+            vec![
+                KeyValue::new("component", "auth"),
+                KeyValue::new("user.id", user_id.to_string()),
+                KeyValue::new("session.id", session_id.to_string()),
+            ],
+        );
+
+        let mut request = Request::new(RevokeSessionRequest {
+            user_id,
+            session_id,
+        });
+
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let response = match self.client.clone().revoke_session(request).await {
+            Ok(response) => {
+                self.complete_tracing_success(
+                    &tracing_ctx,
+                    method,
+                    "gRPC revoke_session succeeded",
+                )
+                .await;
+                response
+            }
+            Err(status) => {
+                let message = format!(
+                    "gRPC revoke_session failed: {}: {}",
+                    status.code(),
+                    status.message()
+                );
+                error!("{message}");
+                self.complete_tracing_error(&tracing_ctx, method, &message)
+                    .await;
+                return Err(AppErrorGrpc::from(status).into());
+            }
+        };
+
+        let inner = response.into_inner();
+
+        Ok(ApiResponse {
+            status: inner.status,
+            message: inner.message,
+            data: true,
+        })
+    }
 }
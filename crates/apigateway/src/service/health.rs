@@ -0,0 +1,164 @@
+use crate::{
+    abstract_trait::health::HealthGrpcClientTrait,
+    domain::response::health::{DownstreamHealthResponse, ServicesHealthResponse},
+    middleware::deadline::apply_deadline,
+};
+use async_trait::async_trait;
+use genproto::health::{HealthCheckRequest, health_service_client::HealthServiceClient};
+use shared::utils::HEALTH_SERVING;
+use std::time::Duration;
+use tokio::{task::JoinSet, time::Instant};
+use tonic::{Request, transport::Channel};
+use tracing::warn;
+
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(3);
+
+#[derive(Clone)]
+pub struct HealthGrpcClientService {
+    clients: Vec<(String, HealthServiceClient<Channel>)>,
+}
+
+impl HealthGrpcClientService {
+    pub fn new(clients: Vec<(String, HealthServiceClient<Channel>)>) -> Self {
+        Self { clients }
+    }
+
+    async fn check_one(
+        service: String,
+        mut client: HealthServiceClient<Channel>,
+    ) -> DownstreamHealthResponse {
+        let start = Instant::now();
+
+        let mut request = Request::new(HealthCheckRequest {});
+        apply_deadline(&mut request);
+
+        let result = tokio::time::timeout(HEALTH_CHECK_TIMEOUT, client.check(request)).await;
+
+        let latency_ms = start.elapsed().as_millis() as u64;
+
+        match result {
+            Ok(Ok(response)) => {
+                let inner = response.into_inner();
+
+                DownstreamHealthResponse {
+                    service,
+                    healthy: inner.status == HEALTH_SERVING,
+                    status: inner.status,
+                    version: Some(inner.version),
+                    latency_ms,
+                    error: None,
+                }
+            }
+            Ok(Err(status)) => {
+                warn!("Health check for {service} failed: {}", status.message());
+
+                DownstreamHealthResponse {
+                    service,
+                    healthy: false,
+                    status: "UNAVAILABLE".to_string(),
+                    version: None,
+                    latency_ms,
+                    error: Some(status.message().to_string()),
+                }
+            }
+            Err(_) => {
+                warn!("Health check for {service} timed out after {HEALTH_CHECK_TIMEOUT:?}");
+
+                DownstreamHealthResponse {
+                    service,
+                    healthy: false,
+                    status: "TIMEOUT".to_string(),
+                    version: None,
+                    latency_ms,
+                    error: Some("Health check timed out".to_string()),
+                }
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl HealthGrpcClientTrait for HealthGrpcClientService {
+    async fn check_all(&self) -> ServicesHealthResponse {
+        let mut checks = JoinSet::new();
+
+        for (service, client) in self.clients.iter().cloned() {
+            checks.spawn(Self::check_one(service, client));
+        }
+
+        let mut services = Vec::with_capacity(self.clients.len());
+
+        while let Some(result) = checks.join_next().await {
+            match result {
+                Ok(health) => services.push(health),
+                Err(e) => warn!("Health check task failed: {e}"),
+            }
+        }
+
+        ServicesHealthResponse::from_services(services)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HealthGrpcClientService;
+    use crate::abstract_trait::health::HealthGrpcClientTrait;
+    use genproto::health::{
+        health_service_client::HealthServiceClient, health_service_server::HealthServiceServer,
+    };
+    use shared::utils::{HEALTH_SERVING, HealthGrpcService};
+    use tokio::net::TcpListener;
+    use tonic::transport::{Channel, Server, server::TcpIncoming};
+
+    fn client(addr: std::net::SocketAddr) -> HealthServiceClient<Channel> {
+        HealthServiceClient::new(
+            Channel::from_shared(format!("http://{addr}"))
+                .unwrap()
+                .connect_lazy(),
+        )
+    }
+
+    #[tokio::test]
+    async fn one_downstream_down_degrades_without_hiding_the_others() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let up = listener.local_addr().unwrap();
+        tokio::spawn(
+            Server::builder()
+                .add_service(HealthServiceServer::new(HealthGrpcService::new(
+                    "user-service",
+                    "1.2.3",
+                )))
+                .serve_with_incoming(TcpIncoming::from(listener)),
+        );
+
+        // Bound and released, so nothing is listening there any more.
+        let down = TcpListener::bind("127.0.0.1:0")
+            .await
+            .unwrap()
+            .local_addr()
+            .unwrap();
+
+        let health = HealthGrpcClientService::new(vec![
+            ("user".to_string(), client(up)),
+            ("order".to_string(), client(down)),
+        ])
+        .check_all()
+        .await;
+
+        assert_eq!(health.status, "degraded");
+        assert_eq!(health.services.len(), 2);
+
+        let order = &health.services[0];
+        assert_eq!(order.service, "order");
+        assert!(!order.healthy);
+        assert_eq!(order.status, "UNAVAILABLE");
+        assert!(order.error.is_some());
+
+        let user = &health.services[1];
+        assert_eq!(user.service, "user");
+        assert!(user.healthy);
+        assert_eq!(user.status, HEALTH_SERVING);
+        assert_eq!(user.version.as_deref(), Some("1.2.3"));
+        assert!(user.error.is_none());
+    }
+}
@@ -0,0 +1,277 @@
+use crate::config::MetricsScrapeConfig;
+use std::collections::HashMap;
+use tokio::task::JoinSet;
+use tracing::warn;
+
+pub const GATEWAY_SERVICE: &str = "apigateway";
+
+/// Fetches every configured downstream `/metrics` endpoint and merges them
+/// into one OpenMetrics exposition, each sample labeled with `service`.
+#[derive(Clone)]
+pub struct MetricsScraper {
+    client: reqwest::Client,
+    targets: Vec<(String, String)>,
+}
+
+impl MetricsScraper {
+    pub fn new(config: MetricsScrapeConfig) -> anyhow::Result<Self> {
+        let client = reqwest::Client::builder().timeout(config.timeout).build()?;
+
+        Ok(Self {
+            client,
+            targets: config.targets,
+        })
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.targets.is_empty()
+    }
+
+    /// `gateway` is the gateway's own exposition, included so one scrape
+    /// covers the whole deployment.
+    pub async fn scrape_all(&self, gateway: String) -> String {
+        let mut scrapes = JoinSet::new();
+
+        for (index, (service, url)) in self.targets.iter().cloned().enumerate() {
+            let client = self.client.clone();
+            scrapes.spawn(async move {
+                let body = fetch(&client, &url).await;
+                if let Err(e) = &body {
+                    warn!("Metrics scrape of {service} at {url} failed: {e}");
+                }
+                (index, service, body.ok())
+            });
+        }
+
+        let mut results = Vec::with_capacity(self.targets.len());
+
+        while let Some(result) = scrapes.join_next().await {
+            match result {
+                Ok(scrape) => results.push(scrape),
+                Err(e) => warn!("Metrics scrape task failed: {e}"),
+            }
+        }
+
+        // Keep the configured order so the output is stable between scrapes.
+        results.sort_by_key(|(index, _, _)| *index);
+
+        let mut expositions = vec![(GATEWAY_SERVICE.to_string(), Some(gateway))];
+        expositions.extend(
+            results
+                .into_iter()
+                .map(|(_, service, body)| (service, body)),
+        );
+
+        combine_expositions(&expositions)
+    }
+}
+
+async fn fetch(client: &reqwest::Client, url: &str) -> Result<String, reqwest::Error> {
+    client
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await
+}
+
+#[derive(Default)]
+struct Family {
+    help: Option<String>,
+    kind: Option<String>,
+    samples: Vec<String>,
+}
+
+/// Families in first-seen order.
+#[derive(Default)]
+struct Families {
+    order: Vec<String>,
+    by_name: HashMap<String, Family>,
+}
+
+impl Families {
+    fn get(&mut self, name: &str) -> &mut Family {
+        if !self.by_name.contains_key(name) {
+            self.order.push(name.to_string());
+        }
+        self.by_name.entry(name.to_string()).or_default()
+    }
+}
+
+/// Merges `(service, exposition)` pairs so each metric family is emitted once
+/// with the samples of every service. A `None` exposition marks a failed
+/// scrape and only shows up in `metrics_scrape_up`.
+pub fn combine_expositions(expositions: &[(String, Option<String>)]) -> String {
+    let mut families = Families::default();
+
+    let up = families.get("metrics_scrape_up");
+    up.help = Some("Whether the last scrape of the service succeeded".to_string());
+    up.kind = Some("gauge".to_string());
+
+    for (service, body) in expositions {
+        families.get("metrics_scrape_up").samples.push(format!(
+            "metrics_scrape_up{{service=\"{}\"}} {}",
+            escape_label(service),
+            u8::from(body.is_some())
+        ));
+
+        let Some(body) = body else {
+            continue;
+        };
+
+        let mut current: Option<String> = None;
+
+        for line in body.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            if let Some(rest) = line.strip_prefix("# HELP ") {
+                let (name, help) = rest.split_once(' ').unwrap_or((rest, ""));
+                families
+                    .get(name)
+                    .help
+                    .get_or_insert_with(|| help.to_string());
+                current = Some(name.to_string());
+            } else if let Some(rest) = line.strip_prefix("# TYPE ") {
+                let (name, kind) = rest.split_once(' ').unwrap_or((rest, "untyped"));
+                families
+                    .get(name)
+                    .kind
+                    .get_or_insert_with(|| kind.to_string());
+                current = Some(name.to_string());
+            } else if line.starts_with('#') {
+                // `# EOF`, `# UNIT` and plain comments are not carried over.
+                continue;
+            } else {
+                let metric = sample_name(line);
+                let name = current
+                    .as_deref()
+                    .filter(|c| metric.starts_with(c))
+                    .unwrap_or(metric)
+                    .to_string();
+
+                families
+                    .get(&name)
+                    .samples
+                    .push(with_service_label(line, service));
+            }
+        }
+    }
+
+    let mut output = String::new();
+
+    for name in &families.order {
+        let family = &families.by_name[name];
+
+        if let Some(help) = &family.help {
+            output.push_str(&format!("# HELP {name} {help}\n"));
+        }
+        if let Some(kind) = &family.kind {
+            output.push_str(&format!("# TYPE {name} {kind}\n"));
+        }
+        for sample in &family.samples {
+            output.push_str(sample);
+            output.push('\n');
+        }
+    }
+
+    output.push_str("# EOF\n");
+    output
+}
+
+fn sample_name(line: &str) -> &str {
+    let end = line
+        .find(|c: char| c == '{' || c.is_whitespace())
+        .unwrap_or(line.len());
+    &line[..end]
+}
+
+fn with_service_label(line: &str, service: &str) -> String {
+    let name = sample_name(line);
+    let rest = &line[name.len()..];
+    let label = format!("service=\"{}\"", escape_label(service));
+
+    match rest.strip_prefix('{') {
+        Some(labels) if labels.starts_with('}') => format!("{name}{{{label}{labels}"),
+        Some(labels) => format!("{name}{{{label},{labels}"),
+        None => format!("{name}{{{label}}}{rest}"),
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MetricsScraper;
+    use crate::config::MetricsScrapeConfig;
+    use axum::{Router, routing::get};
+    use std::{net::SocketAddr, time::Duration};
+    use tokio::net::TcpListener;
+
+    /// Serves `body` at `/metrics` on an ephemeral port.
+    async fn downstream(body: &'static str) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let app = Router::new().route("/metrics", get(move || async move { body }));
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        addr
+    }
+
+    #[tokio::test]
+    async fn downstream_metrics_are_merged_under_their_service_label() {
+        let user = downstream(
+            "# HELP grpc_requests_total Handled requests\n\
+             # TYPE grpc_requests_total counter\n\
+             grpc_requests_total{method=\"find_by_id\"} 3\n\
+             # EOF\n",
+        )
+        .await;
+        let order = downstream(
+            "# HELP grpc_requests_total Handled requests\n\
+             # TYPE grpc_requests_total counter\n\
+             grpc_requests_total{method=\"create_order\"} 5\n\
+             # TYPE orders_created counter\n\
+             orders_created 2\n",
+        )
+        .await;
+        // Bound and released, so nothing is listening there any more.
+        let down = TcpListener::bind("127.0.0.1:0")
+            .await
+            .unwrap()
+            .local_addr()
+            .unwrap();
+
+        let scraper = MetricsScraper::new(MetricsScrapeConfig {
+            targets: vec![
+                ("user".into(), format!("http://{user}/metrics")),
+                ("order".into(), format!("http://{order}/metrics")),
+                ("role".into(), format!("http://{down}/metrics")),
+            ],
+            timeout: Duration::from_secs(2),
+        })
+        .unwrap();
+
+        let combined = scraper
+            .scrape_all("# TYPE http_requests counter\nhttp_requests 7\n".into())
+            .await;
+
+        assert_eq!(
+            combined,
+            "# HELP metrics_scrape_up Whether the last scrape of the service succeeded\n\
+             # TYPE metrics_scrape_up gauge\n\
+             metrics_scrape_up{service=\"apigateway\"} 1\n\
+             metrics_scrape_up{service=\"user\"} 1\n\
+             metrics_scrape_up{service=\"order\"} 1\n\
+             metrics_scrape_up{service=\"role\"} 0\n\
+             # TYPE http_requests counter\n\
+             http_requests{service=\"apigateway\"} 7\n\
+             # HELP grpc_requests_total Handled requests\n\
+             # TYPE grpc_requests_total counter\n\
+             grpc_requests_total{service=\"user\",method=\"find_by_id\"} 3\n\
+             grpc_requests_total{service=\"order\",method=\"create_order\"} 5\n\
+             # TYPE orders_created counter\n\
+             orders_created{service=\"order\"} 2\n\
+             # EOF\n"
+        );
+    }
+}
@@ -1,4 +1,6 @@
 mod auth;
+mod health;
+mod metrics_scrape;
 mod order;
 mod order_item;
 mod product;
@@ -6,6 +8,8 @@ mod role;
 mod user;
 
 pub use self::auth::AuthGrpcClientService;
+pub use self::health::HealthGrpcClientService;
+pub use self::metrics_scrape::{MetricsScraper, combine_expositions};
 pub use self::order::OrderGrpcClientService;
 pub use self::order_item::OrderItemGrpcClientService;
 pub use self::product::ProductGrpcClientService;
@@ -13,9 +17,10 @@ pub use self::role::RoleGrpcClientService;
 pub use self::user::UserGrpcClientService;
 
 use crate::config::GrpcClientConfig;
-use anyhow::{Context, Result};
+use anyhow::Result;
 use genproto::{
     auth::auth_service_client::AuthServiceClient,
+    health::health_service_client::HealthServiceClient,
     order::{
         order_command_service_client::OrderCommandServiceClient,
         order_query_service_client::OrderQueryServiceClient,
@@ -34,8 +39,9 @@ use genproto::{
         user_query_service_client::UserQueryServiceClient,
     },
 };
+use shared::{config::grpc_endpoint, with_grpc_compression};
 use std::time::Duration;
-use tonic::transport::{Channel, Endpoint};
+use tonic::transport::{Channel, ClientTlsConfig};
 use tracing::info;
 
 #[derive(Clone)]
@@ -59,35 +65,75 @@ pub struct GrpcClients {
     pub order_query: OrderQueryServiceClient<Channel>,
 
     pub order_item: OrderItemServiceClient<Channel>,
+
+    // Health, keyed by downstream service name
+    pub health: Vec<(String, HealthServiceClient<Channel>)>,
 }
 
 impl GrpcClients {
     pub async fn init(config: GrpcClientConfig) -> Result<Self> {
-        let auth_channel = Self::connect(&config.auth, "auth-service").await?;
-        let user_channel = Self::connect(&config.user, "user-service").await?;
-        let role_channel = Self::connect(&config.role, "role-service").await?;
-        let product_channel = Self::connect(&config.product, "product-service").await?;
-        let order_channel = Self::connect(&config.order, "order-service").await?;
+        let auth_channel = Self::connect(&config.auth, "auth-service", config.tls.as_ref()).await?;
+        let user_channel = Self::connect(&config.user, "user-service", config.tls.as_ref()).await?;
+        let role_channel = Self::connect(&config.role, "role-service", config.tls.as_ref()).await?;
+        let product_channel =
+            Self::connect(&config.product, "product-service", config.tls.as_ref()).await?;
+        let order_channel =
+            Self::connect(&config.order, "order-service", config.tls.as_ref()).await?;
+
+        let health = vec![
+            (
+                "auth-service".to_string(),
+                with_grpc_compression!(HealthServiceClient::new(auth_channel.clone())),
+            ),
+            (
+                "user-service".to_string(),
+                with_grpc_compression!(HealthServiceClient::new(user_channel.clone())),
+            ),
+            (
+                "role-service".to_string(),
+                with_grpc_compression!(HealthServiceClient::new(role_channel.clone())),
+            ),
+            (
+                "product-service".to_string(),
+                with_grpc_compression!(HealthServiceClient::new(product_channel.clone())),
+            ),
+            (
+                "order-service".to_string(),
+                with_grpc_compression!(HealthServiceClient::new(order_channel.clone())),
+            ),
+        ];
 
         Ok(Self {
-            auth: AuthServiceClient::new(auth_channel),
-
-            user_command: UserCommandServiceClient::new(user_channel.clone()),
-            user_query: UserQueryServiceClient::new(user_channel),
-
-            role_command: RoleCommandServiceClient::new(role_channel.clone()),
-            role_query: RoleQueryServiceClient::new(role_channel),
-
-            product_command: ProductCommandServiceClient::new(product_channel.clone()),
-            product_query: ProductQueryServiceClient::new(product_channel),
-
-            order_command: OrderCommandServiceClient::new(order_channel.clone()),
-            order_query: OrderQueryServiceClient::new(order_channel.clone()),
-            order_item: OrderItemServiceClient::new(order_channel),
+            auth: with_grpc_compression!(AuthServiceClient::new(auth_channel)),
+
+            user_command: with_grpc_compression!(UserCommandServiceClient::new(
+                user_channel.clone()
+            )),
+            user_query: with_grpc_compression!(UserQueryServiceClient::new(user_channel)),
+
+            role_command: with_grpc_compression!(RoleCommandServiceClient::new(
+                role_channel.clone()
+            )),
+            role_query: with_grpc_compression!(RoleQueryServiceClient::new(role_channel)),
+
+            product_command: with_grpc_compression!(ProductCommandServiceClient::new(
+                product_channel.clone()
+            )),
+            product_query: with_grpc_compression!(ProductQueryServiceClient::new(product_channel)),
+
+            order_command: with_grpc_compression!(OrderCommandServiceClient::new(
+                order_channel.clone()
+            )),
+            order_query: with_grpc_compression!(OrderQueryServiceClient::new(
+                order_channel.clone()
+            )),
+            order_item: with_grpc_compression!(OrderItemServiceClient::new(order_channel)),
+
+            health,
         })
     }
 
-    async fn connect(addr: &str, service: &str) -> Result<Channel> {
+    async fn connect(addr: &str, service: &str, tls: Option<&ClientTlsConfig>) -> Result<Channel> {
         info!("Connecting (balanced) to {} at {}", service, addr);
 
         const POOL_SIZE: usize = 10;
@@ -95,8 +141,7 @@ impl GrpcClients {
         let mut endpoints = Vec::with_capacity(POOL_SIZE);
 
         for _ in 0..POOL_SIZE {
-            let ep = Endpoint::from_shared(addr.to_string())
-                .with_context(|| format!("Invalid gRPC address for {service}: {addr}"))?
+            let ep = grpc_endpoint(addr, service, tls)?
                 .connect_timeout(Duration::from_secs(3))
                 .timeout(Duration::from_secs(15))
                 .tcp_keepalive(Some(Duration::from_secs(120)))
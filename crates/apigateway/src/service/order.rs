@@ -3,20 +3,21 @@ use crate::{
     domain::{
         requests::order::{
             CreateOrderRequest as DomainCreateOrderRequest, FindAllOrder as DomainFindAllOrders,
-            UpdateOrderRequest as DomainUpdateOrderRequest,
+            FindUserOrders as DomainFindUserOrders, UpdateOrderRequest as DomainUpdateOrderRequest,
         },
         response::{
             api::{ApiResponse, ApiResponsePagination},
-            order::{OrderResponse, OrderResponseDeleteAt},
+            order::{OrderDetailResponse, OrderResponse, OrderResponseDeleteAt},
         },
     },
+    middleware::deadline::apply_deadline,
 };
 use anyhow::Result;
 use async_trait::async_trait;
 use chrono::Duration;
 use genproto::order::{
-    CreateOrderItemRequest, CreateOrderRequest, FindAllOrderRequest, FindByIdOrderRequest,
-    UpdateOrderItemRequest, UpdateOrderRequest,
+    CreateOrderItemRequest, CreateOrderRequest, FindAllOrderRequest, FindByExternalIdOrderRequest,
+    FindByIdOrderRequest, FindByUserIdOrderRequest, UpdateOrderItemRequest, UpdateOrderRequest,
     order_command_service_client::OrderCommandServiceClient,
     order_query_service_client::OrderQueryServiceClient,
 };
@@ -34,6 +35,7 @@ use std::sync::Arc;
 use tokio::time::Instant;
 use tonic::{Request, transport::Channel};
 use tracing::{error, info};
+use uuid::Uuid;
 
 #[derive(Clone)]
 pub struct OrderGrpcClientService {
@@ -67,6 +69,7 @@ impl OrderGrpcClientService {
         global::get_text_map_propagator(|propagator| {
             propagator.inject_context(cx, &mut MetadataInjector(request.metadata_mut()))
         });
+        apply_deadline(request);
     }
 
     fn start_tracing(&self, operation_name: &str, attributes: Vec<KeyValue>) -> TracingContext {
@@ -174,17 +177,29 @@ impl OrderGrpcClientTrait for OrderGrpcClientService {
             ],
         );
 
+        let (created_from, created_to) = req.created_range()?;
+
         let mut request = Request::new(FindAllOrderRequest {
             page,
             page_size,
             search: req.search.clone(),
+            include_items: req.include_items(),
+            after: req.after.clone(),
+            sort: req.sort.clone(),
+            user_id: req.user_id.unwrap_or_default(),
+            created_from: created_from.clone(),
+            created_to: created_to.clone(),
         });
 
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
         let cache_key = format!(
-            "order:find_all:page:{page}:size:{page_size}:search:{}",
+            "order:find_all:page:{page}:size:{page_size}:search:{}:items:{}:sort:{}:after:{}:user:{}:from:{created_from}:to:{created_to}",
             req.search.clone(),
+            req.include_items(),
+            req.sort,
+            req.after,
+            req.user_id.map(|id| id.to_string()).unwrap_or_default(),
         );
 
         if let Some(cache) = self
@@ -266,6 +281,12 @@ impl OrderGrpcClientTrait for OrderGrpcClientService {
             page,
             page_size,
             search: req.search.clone(),
+            include_items: false,
+            after: String::new(),
+            sort: String::new(),
+            user_id: 0,
+            created_from: String::new(),
+            created_to: String::new(),
         });
 
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
@@ -358,6 +379,12 @@ impl OrderGrpcClientTrait for OrderGrpcClientService {
             page,
             page_size,
             search: req.search.clone(),
+            include_items: false,
+            after: String::new(),
+            sort: String::new(),
+            user_id: 0,
+            created_from: String::new(),
+            created_to: String::new(),
         });
 
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
@@ -422,6 +449,100 @@ impl OrderGrpcClientTrait for OrderGrpcClientService {
         Ok(api_response)
     }
 
+    async fn find_by_user_id(
+        &self,
+        user_id: i32,
+        req: &DomainFindUserOrders,
+    ) -> Result<ApiResponsePagination<Vec<OrderResponse>>, HttpError> {
+        let page = req.page;
+        let page_size = req.page_size;
+
+        info!("Retrieving orders of user {user_id} (page: {page}, size: {page_size})");
+
+        let method = Method::Get;
+        let tracing_ctx = self.start_tracing(
+            "FindOrdersByUserId",
+            vec![
+                KeyValue::new("component", "order"),
+                KeyValue::new("operation", "find_by_user_id"),
+                KeyValue::new("user.id", user_id.to_string()),
+                KeyValue::new("page", page.to_string()),
+                KeyValue::new("page_size", page_size.to_string()),
+            ],
+        );
+
+        let mut request = Request::new(FindByUserIdOrderRequest {
+            user_id,
+            page,
+            page_size,
+            include_items: req.include_items(),
+        });
+
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let cache_key = format!(
+            "order:find_by_user_id:user:{user_id}:page:{page}:size:{page_size}:items:{}",
+            req.include_items(),
+        );
+
+        if let Some(cache) = self
+            .cache_store
+            .get_from_cache::<ApiResponsePagination<Vec<OrderResponse>>>(&cache_key)
+            .await
+        {
+            let log_msg = format!(
+                "✅ Found {} orders of user {user_id} in cache",
+                cache.data.len()
+            );
+            info!("{log_msg}");
+            self.complete_tracing_success(&tracing_ctx, method, &log_msg)
+                .await;
+            return Ok(cache);
+        }
+
+        let response = match self.query_client.clone().find_by_user_id(request).await {
+            Ok(resp) => {
+                self.complete_tracing_success(
+                    &tracing_ctx,
+                    method,
+                    "Successfully fetched orders of user",
+                )
+                .await;
+                resp
+            }
+            Err(status) => {
+                let error_message = format!(
+                    "gRPC find_by_user_id failed: {}: {}",
+                    status.code(),
+                    status.message()
+                );
+                self.complete_tracing_error(&tracing_ctx, method, &error_message)
+                    .await;
+                return Err(AppErrorGrpc::from(status).into());
+            }
+        };
+
+        let inner = response.into_inner();
+
+        let orders: Vec<OrderResponse> = inner.data.into_iter().map(Into::into).collect();
+
+        let order_len = orders.len();
+
+        let api_response = ApiResponsePagination {
+            status: inner.status,
+            message: inner.message,
+            data: orders,
+            pagination: inner.pagination.unwrap_or_default().into(),
+        };
+
+        self.cache_store
+            .set_to_cache(&cache_key, &api_response, Duration::minutes(30))
+            .await;
+
+        info!("Successfully fetched {order_len} orders of user {user_id}");
+        Ok(api_response)
+    }
+
     async fn find_by_id(&self, id: i32) -> Result<ApiResponse<OrderResponse>, HttpError> {
         info!("Fetching Order by ID: {}", id);
 
@@ -500,6 +621,145 @@ impl OrderGrpcClientTrait for OrderGrpcClientService {
         Ok(api_response)
     }
 
+    async fn find_detail_by_id(
+        &self,
+        id: i32,
+    ) -> Result<ApiResponse<OrderDetailResponse>, HttpError> {
+        info!("Fetching Order detail by ID: {}", id);
+
+        let method = Method::Get;
+        let tracing_ctx = self.start_tracing(
+            "FindDetailByIdOrder",
+            vec![
+                KeyValue::new("component", "order"),
+                KeyValue::new("operation", "find_detail_by_id"),
+                KeyValue::new("order.id", id as i64),
+            ],
+        );
+
+        let mut request = Request::new(FindByIdOrderRequest { id });
+
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        // Not cached: the product names and prices in it change
+        // independently of the order.
+        let response = match self.query_client.clone().find_detail_by_id(request).await {
+            Ok(resp) => {
+                self.complete_tracing_success(
+                    &tracing_ctx,
+                    method,
+                    "Successfully fetched Order detail by ID",
+                )
+                .await;
+                resp
+            }
+            Err(status) => {
+                let error_message = format!(
+                    "gRPC find_detail_by_id failed: {}: {}",
+                    status.code(),
+                    status.message()
+                );
+                self.complete_tracing_error(&tracing_ctx, method, &error_message)
+                    .await;
+                return Err(AppErrorGrpc::from(status).into());
+            }
+        };
+
+        let inner = response.into_inner();
+
+        let order_data = inner.data.ok_or_else(|| {
+            let err: HttpError =
+                AppErrorGrpc::Unhandled("Order detail is missing in gRPC response".into()).into();
+            err
+        })?;
+
+        let api_response = ApiResponse {
+            status: inner.status,
+            message: inner.message,
+            data: OrderDetailResponse::from(order_data),
+        };
+
+        info!("Successfully fetched Order detail: {id}");
+        Ok(api_response)
+    }
+
+    async fn find_by_external_id(
+        &self,
+        external_id: Uuid,
+    ) -> Result<ApiResponse<OrderResponse>, HttpError> {
+        info!("Fetching Order by external ID: {external_id}");
+
+        let method = Method::Get;
+        let tracing_ctx = self.start_tracing(
+            "FindByExternalIdOrder",
+            vec![
+                KeyValue::new("component", "order"),
+                KeyValue::new("operation", "find_by_external_id"),
+                KeyValue::new("order.external_id", external_id.to_string()),
+            ],
+        );
+
+        let mut request = Request::new(FindByExternalIdOrderRequest {
+            external_id: external_id.to_string(),
+        });
+
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let cache_key = format!("order:find_by_external_id:{external_id}");
+
+        if let Some(cache) = self
+            .cache_store
+            .get_from_cache::<ApiResponse<OrderResponse>>(&cache_key)
+            .await
+        {
+            info!("✅ Found order in cache");
+            self.complete_tracing_success(&tracing_ctx, method, "Order retrieved from cache")
+                .await;
+            return Ok(cache);
+        }
+
+        let response = match self.query_client.clone().find_by_external_id(request).await {
+            Ok(response) => {
+                self.complete_tracing_success(
+                    &tracing_ctx,
+                    method,
+                    "Successfully fetched Order by external ID",
+                )
+                .await;
+                response
+            }
+            Err(status) => {
+                error!(
+                    "gRPC find_by_external_id failed: {}: {}",
+                    status.code(),
+                    status.message()
+                );
+                self.complete_tracing_error(&tracing_ctx, method, status.message())
+                    .await;
+                return Err(AppErrorGrpc::from(status).into());
+            }
+        };
+
+        let inner = response.into_inner();
+
+        let data = inner.data.ok_or_else(|| {
+            let err: HttpError =
+                AppErrorGrpc::Unhandled("Order data is missing in gRPC response".into()).into();
+            err
+        })?;
+
+        let api_response = ApiResponse {
+            status: inner.status,
+            message: inner.message,
+            data: OrderResponse::from(data),
+        };
+
+        self.cache_store
+            .set_to_cache(&cache_key, &api_response, Duration::minutes(30))
+            .await;
+
+        Ok(api_response)
+    }
     async fn create_order(
         &self,
         req: &DomainCreateOrderRequest,
@@ -531,6 +791,7 @@ impl OrderGrpcClientTrait for OrderGrpcClientService {
                     quantity: i.quantity,
                 })
                 .collect(),
+            idempotency_key: req.idempotency_key.clone().unwrap_or_default(),
         });
 
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
@@ -600,7 +861,7 @@ impl OrderGrpcClientTrait for OrderGrpcClientService {
             return Err(HttpError::BadRequest("Items cannot be empty".into()));
         }
 
-        let request = Request::new(UpdateOrderRequest {
+        let mut request = Request::new(UpdateOrderRequest {
             order_id,
             user_id: req.user_id,
             items: req
@@ -613,8 +874,12 @@ impl OrderGrpcClientTrait for OrderGrpcClientService {
                     quantity: i.quantity,
                 })
                 .collect(),
+            status: req.status.clone().unwrap_or_default(),
+            caller_is_staff: req.caller_is_staff,
         });
 
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
         let response = match self.command_client.clone().update(request).await {
             Ok(resp) => {
                 self.complete_tracing_success(&tracing_ctx, method, "Order updated")
@@ -7,6 +7,7 @@ use crate::{
             order_item::{OrderItemResponse, OrderItemResponseDeleteAt},
         },
     },
+    middleware::deadline::apply_deadline,
 };
 use anyhow::Result;
 use async_trait::async_trait;
@@ -58,6 +59,7 @@ impl OrderItemGrpcClientService {
         global::get_text_map_propagator(|propagator| {
             propagator.inject_context(cx, &mut MetadataInjector(request.metadata_mut()))
         });
+        apply_deadline(request);
     }
 
     fn start_tracing(&self, operation_name: &str, attributes: Vec<KeyValue>) -> TracingContext {
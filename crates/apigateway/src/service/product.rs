@@ -8,15 +8,17 @@ use crate::{
         },
         response::{
             api::{ApiResponse, ApiResponsePagination},
-            product::{ProductResponse, ProductResponseDeleteAt},
+            product::{ProductResponse, ProductResponseDeleteAt, attributes_to_proto},
         },
     },
+    middleware::deadline::apply_deadline,
 };
 use anyhow::Result;
 use async_trait::async_trait;
 use chrono::Duration;
 use genproto::product::{
-    CreateProductRequest, FindAllProductRequest, FindByIdProductRequest, UpdateProductRequest,
+    CreateProductRequest, FindAllProductRequest, FindByExternalIdProductRequest,
+    FindByIdProductRequest, ImportProductsChunk, UpdateProductRequest,
     product_command_service_client::ProductCommandServiceClient,
     product_query_service_client::ProductQueryServiceClient,
 };
@@ -28,12 +30,18 @@ use opentelemetry::{
 use shared::cache::CacheStore;
 use shared::{
     errors::{AppErrorGrpc, HttpError},
-    utils::{MetadataInjector, Method, Metrics, Status as StatusUtils, TracingContext},
+    utils::{
+        BatchResponse, MetadataInjector, Method, Metrics, Status as StatusUtils, TracingContext,
+    },
 };
 use std::sync::Arc;
 use tokio::time::Instant;
-use tonic::{Request, transport::Channel};
+use tonic::{Request, codegen::tokio_stream, transport::Channel};
 use tracing::{error, info};
+use uuid::Uuid;
+
+/// Size of the chunks a CSV upload is streamed to the product service in.
+const IMPORT_CHUNK_BYTES: usize = 64 * 1024;
 
 #[derive(Clone)]
 pub struct ProductGrpcClientService {
@@ -67,6 +75,7 @@ impl ProductGrpcClientService {
         global::get_text_map_propagator(|propagator| {
             propagator.inject_context(cx, &mut MetadataInjector(request.metadata_mut()))
         });
+        apply_deadline(request);
     }
 
     fn start_tracing(&self, operation_name: &str, attributes: Vec<KeyValue>) -> TracingContext {
@@ -178,13 +187,23 @@ impl ProductGrpcClientTrait for ProductGrpcClientService {
             page,
             page_size,
             search: req.search.clone(),
+            min_price: req.min_price,
+            max_price: req.max_price,
+            after: req.after.clone(),
+            sort: req.sort.clone(),
+            attribute: req.attribute.clone(),
         });
 
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
         let cache_key = format!(
-            "product:find_all:page:{page}:size:{page_size}:search:{}",
+            "product:find_all:page:{page}:size:{page_size}:search:{}:price:{}-{}:attribute:{}:sort:{}:after:{}",
             req.search.clone(),
+            req.min_price.map(|v| v.to_string()).unwrap_or_default(),
+            req.max_price.map(|v| v.to_string()).unwrap_or_default(),
+            req.attribute,
+            req.sort,
+            req.after,
         );
 
         if let Some(cache) = self
@@ -270,6 +289,11 @@ impl ProductGrpcClientTrait for ProductGrpcClientService {
             page,
             page_size,
             search: req.search.clone(),
+            min_price: None,
+            max_price: None,
+            after: String::new(),
+            sort: String::new(),
+            attribute: String::new(),
         });
 
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
@@ -363,6 +387,11 @@ impl ProductGrpcClientTrait for ProductGrpcClientService {
             page,
             page_size,
             search: req.search.clone(),
+            min_price: None,
+            max_price: None,
+            after: String::new(),
+            sort: String::new(),
+            attribute: String::new(),
         });
 
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
@@ -424,6 +453,82 @@ impl ProductGrpcClientTrait for ProductGrpcClientService {
         Ok(api_response)
     }
 
+    async fn find_low_stock(
+        &self,
+        req: &DomainFindAllProducts,
+    ) -> Result<ApiResponsePagination<Vec<ProductResponse>>, HttpError> {
+        let page = req.page;
+        let page_size = req.page_size;
+
+        info!(
+            "Retrieving low-stock products (page: {page}, size: {page_size} search: {})",
+            req.search
+        );
+
+        let method = Method::Get;
+        let tracing_ctx = self.start_tracing(
+            "FindLowStockProduct",
+            vec![
+                KeyValue::new("component", "product"),
+                KeyValue::new("operation", "find_low_stock"),
+                KeyValue::new("page", page.to_string()),
+                KeyValue::new("page_size", page_size.to_string()),
+                KeyValue::new("search", req.search.clone()),
+            ],
+        );
+
+        let mut request = Request::new(FindAllProductRequest {
+            page,
+            page_size,
+            search: req.search.clone(),
+            min_price: None,
+            max_price: None,
+            after: String::new(),
+            sort: String::new(),
+            attribute: String::new(),
+        });
+
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let response = match self.query_client.clone().find_low_stock(request).await {
+            Ok(response) => {
+                self.complete_tracing_success(
+                    &tracing_ctx,
+                    method,
+                    "Successfully fetched low-stock products",
+                )
+                .await;
+                response
+            }
+            Err(status) => {
+                error!(
+                    "gRPC find_low_stock failed: {}: {}",
+                    status.code(),
+                    status.message()
+                );
+                self.complete_tracing_error(&tracing_ctx, method, status.message())
+                    .await;
+                return Err(AppErrorGrpc::from(status).into());
+            }
+        };
+
+        let inner = response.into_inner();
+
+        let products: Vec<ProductResponse> = inner.data.into_iter().map(Into::into).collect();
+
+        let product_len = products.len();
+
+        let api_response = ApiResponsePagination {
+            status: inner.status,
+            message: inner.message,
+            data: products,
+            pagination: inner.pagination.unwrap_or_default().into(),
+        };
+
+        info!("Successfully fetched {product_len} low-stock Products");
+        Ok(api_response)
+    }
+
     async fn find_by_id(&self, id: i32) -> Result<ApiResponse<ProductResponse>, HttpError> {
         info!("Fetching Product by ID: {id}");
 
@@ -502,6 +607,84 @@ impl ProductGrpcClientTrait for ProductGrpcClientService {
         Ok(api_response)
     }
 
+    async fn find_by_external_id(
+        &self,
+        external_id: Uuid,
+    ) -> Result<ApiResponse<ProductResponse>, HttpError> {
+        info!("Fetching Product by external ID: {external_id}");
+
+        let method = Method::Get;
+        let tracing_ctx = self.start_tracing(
+            "FindByExternalIdProduct",
+            vec![
+                KeyValue::new("component", "product"),
+                KeyValue::new("operation", "find_by_external_id"),
+                KeyValue::new("product.external_id", external_id.to_string()),
+            ],
+        );
+
+        let mut request = Request::new(FindByExternalIdProductRequest {
+            external_id: external_id.to_string(),
+        });
+
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let cache_key = format!("product:find_by_external_id:{external_id}");
+
+        if let Some(cache) = self
+            .cache_store
+            .get_from_cache::<ApiResponse<ProductResponse>>(&cache_key)
+            .await
+        {
+            info!("✅ Found product in cache");
+            self.complete_tracing_success(&tracing_ctx, method, "Product retrieved from cache")
+                .await;
+            return Ok(cache);
+        }
+
+        let response = match self.query_client.clone().find_by_external_id(request).await {
+            Ok(response) => {
+                self.complete_tracing_success(
+                    &tracing_ctx,
+                    method,
+                    "Successfully fetched Product by external ID",
+                )
+                .await;
+                response
+            }
+            Err(status) => {
+                error!(
+                    "gRPC find_by_external_id failed: {}: {}",
+                    status.code(),
+                    status.message()
+                );
+                self.complete_tracing_error(&tracing_ctx, method, status.message())
+                    .await;
+                return Err(AppErrorGrpc::from(status).into());
+            }
+        };
+
+        let inner = response.into_inner();
+
+        let product_data = inner.data.ok_or_else(|| {
+            let err: HttpError =
+                AppErrorGrpc::Unhandled("Product data is missing in gRPC response".into()).into();
+            err
+        })?;
+
+        let api_response = ApiResponse {
+            status: inner.status,
+            message: inner.message,
+            data: ProductResponse::from(product_data),
+        };
+
+        self.cache_store
+            .set_to_cache(&cache_key, &api_response, Duration::minutes(30))
+            .await;
+
+        Ok(api_response)
+    }
+
     async fn create_product(
         &self,
         req: &DomainCreateProductRequest,
@@ -524,6 +707,10 @@ impl ProductGrpcClientTrait for ProductGrpcClientService {
             name: req.name.clone(),
             price: req.price,
             stock: req.stock,
+            images: req.images.clone(),
+            reorder_threshold: req.reorder_threshold,
+            category: req.category.clone(),
+            attributes: attributes_to_proto(req.attributes.clone()),
         });
 
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
@@ -594,6 +781,10 @@ impl ProductGrpcClientTrait for ProductGrpcClientService {
             name: req.name.clone(),
             price: req.price,
             stock: req.stock,
+            images: req.images.clone(),
+            reorder_threshold: req.reorder_threshold,
+            category: req.category.clone(),
+            attributes: attributes_to_proto(req.attributes.clone()),
         });
 
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
@@ -911,4 +1102,76 @@ impl ProductGrpcClientTrait for ProductGrpcClientService {
         info!("All trashed Products permanently deleted");
         Ok(api_response)
     }
+
+    async fn import_products(
+        &self,
+        csv: Vec<u8>,
+        strict: bool,
+    ) -> Result<ApiResponse<BatchResponse<i32>>, HttpError> {
+        info!("Importing products from a {} byte CSV", csv.len());
+
+        let method = Method::Post;
+        let tracing_ctx = self.start_tracing(
+            "ImportProducts",
+            vec![
+                KeyValue::new("component", "product"),
+                KeyValue::new("operation", "import"),
+                KeyValue::new("import.bytes", csv.len().to_string()),
+                KeyValue::new("import.strict", strict.to_string()),
+            ],
+        );
+
+        let chunks: Vec<ImportProductsChunk> = csv
+            .chunks(IMPORT_CHUNK_BYTES)
+            .enumerate()
+            .map(|(index, data)| ImportProductsChunk {
+                data: data.to_vec(),
+                strict: index == 0 && strict,
+            })
+            .collect();
+
+        let mut request = Request::new(tokio_stream::iter(chunks));
+
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let response = match self.command_client.clone().import_products(request).await {
+            Ok(response) => {
+                self.complete_tracing_success(
+                    &tracing_ctx,
+                    method,
+                    "Successfully imported Products",
+                )
+                .await;
+                response
+            }
+            Err(status) => {
+                error!(
+                    "gRPC import_products failed: {}: {}",
+                    status.code(),
+                    status.message()
+                );
+                self.complete_tracing_error(&tracing_ctx, method, status.message())
+                    .await;
+                return Err(AppErrorGrpc::from(status).into());
+            }
+        };
+
+        let inner = response.into_inner();
+
+        let api_response = ApiResponse {
+            status: inner.status.clone(),
+            message: inner.message.clone(),
+            data: BatchResponse {
+                succeeded: inner.succeeded,
+                failed: inner.failed.into_iter().map(Into::into).collect(),
+            },
+        };
+
+        info!(
+            "Product import finished: {} inserted, {} failed",
+            api_response.data.succeeded.len(),
+            api_response.data.failed.len()
+        );
+        Ok(api_response)
+    }
 }
@@ -10,13 +10,15 @@ use crate::{
             role::{RoleResponse, RoleResponseDeleteAt},
         },
     },
+    middleware::deadline::apply_deadline,
 };
 use anyhow::Result;
 use async_trait::async_trait;
 use chrono::Duration;
 use genproto::role::{
-    CreateRoleRequest, FindAllRoleRequest, FindByIdRoleRequest, FindByIdUserRoleRequest,
-    UpdateRoleRequest, role_command_service_client::RoleCommandServiceClient,
+    CreateRoleRequest, FindAllRoleRequest, FindByExternalIdRoleRequest, FindByIdRoleRequest,
+    FindByIdUserRoleRequest, UpdateRoleRequest,
+    role_command_service_client::RoleCommandServiceClient,
     role_query_service_client::RoleQueryServiceClient,
 };
 use opentelemetry::{
@@ -33,6 +35,7 @@ use std::sync::Arc;
 use tokio::time::Instant;
 use tonic::{Request, transport::Channel};
 use tracing::{error, info};
+use uuid::Uuid;
 
 #[derive(Clone)]
 pub struct RoleGrpcClientService {
@@ -65,6 +68,7 @@ impl RoleGrpcClientService {
         global::get_text_map_propagator(|propagator| {
             propagator.inject_context(cx, &mut MetadataInjector(request.metadata_mut()))
         });
+        apply_deadline(request);
     }
 
     fn start_tracing(&self, operation_name: &str, attributes: Vec<KeyValue>) -> TracingContext {
@@ -466,6 +470,83 @@ impl RoleGrpcClientTrait for RoleGrpcClientService {
         Ok(api_response)
     }
 
+    async fn find_by_external_id(
+        &self,
+        external_id: Uuid,
+    ) -> Result<ApiResponse<RoleResponse>, HttpError> {
+        info!("Fetching Role by external ID: {external_id}");
+
+        let method = Method::Get;
+        let tracing_ctx = self.start_tracing(
+            "FindByExternalIdRole",
+            vec![
+                KeyValue::new("component", "role"),
+                KeyValue::new("operation", "find_by_external_id"),
+                KeyValue::new("role.external_id", external_id.to_string()),
+            ],
+        );
+
+        let mut request = Request::new(FindByExternalIdRoleRequest {
+            external_id: external_id.to_string(),
+        });
+
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let cache_key = format!("role:find_by_external_id:{external_id}");
+
+        if let Some(cache) = self
+            .cache_store
+            .get_from_cache::<ApiResponse<RoleResponse>>(&cache_key)
+            .await
+        {
+            info!("✅ Found role in cache");
+            self.complete_tracing_success(&tracing_ctx, method, "Role retrieved from cache")
+                .await;
+            return Ok(cache);
+        }
+
+        let response = match self.query_client.clone().find_by_external_id(request).await {
+            Ok(response) => {
+                self.complete_tracing_success(
+                    &tracing_ctx,
+                    method,
+                    "Successfully fetched Role by external ID",
+                )
+                .await;
+                response
+            }
+            Err(status) => {
+                error!(
+                    "gRPC find_by_external_id failed: {}: {}",
+                    status.code(),
+                    status.message()
+                );
+                self.complete_tracing_error(&tracing_ctx, method, status.message())
+                    .await;
+                return Err(AppErrorGrpc::from(status).into());
+            }
+        };
+
+        let inner = response.into_inner();
+
+        let data = inner.data.ok_or_else(|| {
+            let err: HttpError =
+                AppErrorGrpc::Unhandled("Role data is missing in gRPC response".into()).into();
+            err
+        })?;
+
+        let api_response = ApiResponse {
+            status: inner.status,
+            message: inner.message,
+            data: RoleResponse::from(data),
+        };
+
+        self.cache_store
+            .set_to_cache(&cache_key, &api_response, Duration::minutes(30))
+            .await;
+
+        Ok(api_response)
+    }
     async fn find_by_user_id(
         &self,
         user_id: i32,
@@ -9,12 +9,13 @@ use crate::{
             user::{UserResponse, UserResponseDeleteAt},
         },
     },
+    middleware::deadline::apply_deadline,
 };
 use anyhow::Result;
 use async_trait::async_trait;
 use chrono::Duration;
 use genproto::user::{
-    FindAllUserRequest, FindByIdUserRequest, UpdateUserRequest,
+    FindAllUserRequest, FindByExternalIdUserRequest, FindByIdUserRequest, UpdateUserRequest,
     user_command_service_client::UserCommandServiceClient,
     user_query_service_client::UserQueryServiceClient,
 };
@@ -32,6 +33,7 @@ use std::sync::Arc;
 use tokio::time::Instant;
 use tonic::{Request, transport::Channel};
 use tracing::{error, info};
+use uuid::Uuid;
 
 #[derive(Clone)]
 pub struct UserGrpcClientService {
@@ -64,6 +66,7 @@ impl UserGrpcClientService {
         global::get_text_map_propagator(|propagator| {
             propagator.inject_context(cx, &mut MetadataInjector(request.metadata_mut()))
         });
+        apply_deadline(request);
     }
 
     fn start_tracing(&self, operation_name: &str, attributes: Vec<KeyValue>) -> TracingContext {
@@ -171,17 +174,22 @@ impl UserGrpcClientTrait for UserGrpcClientService {
             ],
         );
 
+        let include_roles = req.include_roles();
+
         let mut request = Request::new(FindAllUserRequest {
             page,
             page_size,
             search: req.search.clone(),
+            include_roles,
+            after: req.after.clone(),
         });
 
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
         let cache_key = format!(
-            "user:find_all:page:{page}:size:{page_size}:search:{}",
-            req.search.clone()
+            "user:find_all:page:{page}:size:{page_size}:search:{}:roles:{include_roles}:after:{}",
+            req.search.clone(),
+            req.after
         );
 
         if let Some(cache) = self
@@ -211,7 +219,15 @@ impl UserGrpcClientTrait for UserGrpcClientService {
 
         let inner = response.into_inner();
 
-        let users: Vec<UserResponse> = inner.data.into_iter().map(Into::into).collect();
+        let users: Vec<UserResponse> = if include_roles {
+            inner
+                .data
+                .into_iter()
+                .map(UserResponse::from_proto_with_roles)
+                .collect()
+        } else {
+            inner.data.into_iter().map(Into::into).collect()
+        };
 
         let user_len = users.len();
 
@@ -258,6 +274,8 @@ impl UserGrpcClientTrait for UserGrpcClientService {
             page,
             page_size,
             search: req.search.clone(),
+            include_roles: false,
+            after: String::new(),
         });
 
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
@@ -345,6 +363,8 @@ impl UserGrpcClientTrait for UserGrpcClientService {
             page,
             page_size,
             search: req.search.clone(),
+            include_roles: false,
+            after: String::new(),
         });
 
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
@@ -473,6 +493,83 @@ impl UserGrpcClientTrait for UserGrpcClientService {
         Ok(api_response)
     }
 
+    async fn find_by_external_id(
+        &self,
+        external_id: Uuid,
+    ) -> Result<ApiResponse<UserResponse>, HttpError> {
+        info!("Fetching User by external ID: {external_id}");
+
+        let method = Method::Get;
+        let tracing_ctx = self.start_tracing(
+            "FindByExternalIdUser",
+            vec![
+                KeyValue::new("component", "user"),
+                KeyValue::new("operation", "find_by_external_id"),
+                KeyValue::new("user.external_id", external_id.to_string()),
+            ],
+        );
+
+        let mut request = Request::new(FindByExternalIdUserRequest {
+            external_id: external_id.to_string(),
+        });
+
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let cache_key = format!("user:find_by_external_id:{external_id}");
+
+        if let Some(cache) = self
+            .cache_store
+            .get_from_cache::<ApiResponse<UserResponse>>(&cache_key)
+            .await
+        {
+            info!("✅ Found user in cache");
+            self.complete_tracing_success(&tracing_ctx, method, "User retrieved from cache")
+                .await;
+            return Ok(cache);
+        }
+
+        let response = match self.query_client.clone().find_by_external_id(request).await {
+            Ok(response) => {
+                self.complete_tracing_success(
+                    &tracing_ctx,
+                    method,
+                    "Successfully fetched User by external ID",
+                )
+                .await;
+                response
+            }
+            Err(status) => {
+                error!(
+                    "gRPC find_by_external_id failed: {}: {}",
+                    status.code(),
+                    status.message()
+                );
+                self.complete_tracing_error(&tracing_ctx, method, status.message())
+                    .await;
+                return Err(AppErrorGrpc::from(status).into());
+            }
+        };
+
+        let inner = response.into_inner();
+
+        let data = inner.data.ok_or_else(|| {
+            let err: HttpError =
+                AppErrorGrpc::Unhandled("User data is missing in gRPC response".into()).into();
+            err
+        })?;
+
+        let api_response = ApiResponse {
+            status: inner.status,
+            message: inner.message,
+            data: UserResponse::from(data),
+        };
+
+        self.cache_store
+            .set_to_cache(&cache_key, &api_response, Duration::minutes(30))
+            .await;
+
+        Ok(api_response)
+    }
     async fn update_user(
         &self,
         req: &DomainUpdateUserRequest,
@@ -541,6 +638,66 @@ impl UserGrpcClientTrait for UserGrpcClientService {
         Ok(api_response)
     }
 
+    async fn anonymize_user(&self, id: i32) -> Result<ApiResponse<UserResponse>, HttpError> {
+        info!("Anonymizing user: {id}");
+
+        let method = Method::Post;
+        let tracing_ctx = self.start_tracing(
+            "AnonymizeUser",
+            vec![
+                KeyValue::new("component", "user"),
+                KeyValue::new("operation", "anonymize"),
+                KeyValue::new("user_id", id.to_string()),
+            ],
+        );
+
+        let mut request = Request::new(FindByIdUserRequest { id });
+
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let response = match self.command_client.clone().anonymize_user(request).await {
+            Ok(response) => {
+                self.complete_tracing_success(&tracing_ctx, method, "User anonymized successfully")
+                    .await;
+                response
+            }
+            Err(status) => {
+                self.complete_tracing_error(&tracing_ctx, method, status.message())
+                    .await;
+                return Err(AppErrorGrpc::from(status).into());
+            }
+        };
+
+        let inner = response.into_inner();
+
+        let user_data = inner.data.ok_or_else(|| {
+            let err: HttpError =
+                AppErrorGrpc::Unhandled("User data is missing in gRPC response".into()).into();
+            err
+        })?;
+
+        let domain_user: UserResponse = user_data.into();
+
+        // The cached profile would keep serving the scrubbed personal data.
+        self.cache_store
+            .delete_from_cache(&format!("user:find_by_id:{id}"))
+            .await;
+        self.cache_store
+            .delete_from_cache(&format!(
+                "user:find_by_external_id:{}",
+                domain_user.external_id
+            ))
+            .await;
+
+        info!("User {} anonymized successfully", id);
+
+        Ok(ApiResponse {
+            status: inner.status,
+            message: inner.message,
+            data: domain_user,
+        })
+    }
+
     async fn trash_user(&self, id: i32) -> Result<ApiResponse<UserResponseDeleteAt>, HttpError> {
         info!("Soft deleting user: {id}");
 
@@ -1,15 +1,22 @@
 use crate::{
     abstract_trait::{rate_limit::DynRateLimitMiddleware, session::DynSessionMiddleware},
     cache::{rate_limit::RateLimiter, session::SessionStore},
-    config::GrpcClientConfig,
+    config::{
+        BulkRateLimitConfig, CurrencyConfig, DeleteSemanticsConfig, DeprecationConfig,
+        DisplayTimezoneConfig, GrpcClientConfig, HttpsConfig, JsonBodyConfig, MetricsScrapeConfig,
+        RateLimitCostConfig, RateLimitGroupConfig, ReadOnlyConfig, RedactionConfig,
+        RequestBudgetConfig, RouteRolesConfig, SlowRequestConfig,
+    },
     di::DependenciesInject,
-    service::GrpcClients,
+    handler::ROUTE_ROLES,
+    service::{GrpcClients, MetricsScraper},
 };
 use anyhow::{Context, Result};
+use prometheus_client::registry::Registry;
 use shared::{
     abstract_trait::DynJwtService,
-    config::{JwtConfig, RedisConfig, RedisPool},
-    utils::{SystemMetrics, run_metrics_collector},
+    config::{JwtConfig, PreviousJwtKey, RedisConfig, RedisPool},
+    utils::{InFlightRequests, SystemMetrics, run_metrics_collector},
 };
 use std::sync::Arc;
 use tracing::info;
@@ -18,17 +25,62 @@ use tracing::info;
 pub struct AppState {
     pub jwt_config: DynJwtService,
     pub rate_limit: DynRateLimitMiddleware,
+    pub rate_limit_costs: RateLimitCostConfig,
+    pub rate_limit_groups: RateLimitGroupConfig,
+    pub bulk_rate_limit: BulkRateLimitConfig,
+    pub route_roles: RouteRolesConfig,
+    pub deprecations: DeprecationConfig,
     pub session: DynSessionMiddleware,
     pub di_container: DependenciesInject,
     pub system_metrics: Arc<SystemMetrics>,
+    pub in_flight: InFlightRequests,
+    pub registry: Arc<Registry>,
+    pub redaction: RedactionConfig,
+    pub currency: CurrencyConfig,
+    pub display_timezone: DisplayTimezoneConfig,
+    pub request_budget: RequestBudgetConfig,
+    pub slow_request: SlowRequestConfig,
+    pub read_only: ReadOnlyConfig,
+    pub delete_semantics: DeleteSemanticsConfig,
+    pub https: HttpsConfig,
+    pub json_body: JsonBodyConfig,
     pub redis: Arc<RedisPool>,
+    pub metrics_scraper: MetricsScraper,
 }
 
 impl AppState {
-    pub async fn new(jwt_secret: &str) -> Result<Self> {
-        let jwt_config = Arc::new(JwtConfig::new(jwt_secret)) as DynJwtService;
+    pub async fn new(jwt_secret: &str, jwt_previous_key: Option<PreviousJwtKey>) -> Result<Self> {
+        let jwt_config = Arc::new(JwtConfig::new(jwt_secret).with_previous_key(jwt_previous_key))
+            as DynJwtService;
         let system_metrics = Arc::new(SystemMetrics::new());
+        let in_flight = InFlightRequests::default();
+
+        let mut registry = Registry::default();
+        in_flight.register(&mut registry);
+
         let grpc_config = GrpcClientConfig::init().context("failed config grpc")?;
+        let redaction = RedactionConfig::init();
+        let currency = CurrencyConfig::init();
+        let display_timezone =
+            DisplayTimezoneConfig::init().context("failed config display timezone")?;
+        let request_budget = RequestBudgetConfig::init();
+        let slow_request = SlowRequestConfig::init();
+        let read_only = ReadOnlyConfig::init();
+        let delete_semantics = DeleteSemanticsConfig::init();
+        let https = HttpsConfig::init().context("failed config https enforcement")?;
+        let json_body = JsonBodyConfig::init();
+        let rate_limit_costs =
+            RateLimitCostConfig::init().context("failed config rate limit costs")?;
+        let rate_limit_groups =
+            RateLimitGroupConfig::init().context("failed config rate limit groups")?;
+        let bulk_rate_limit =
+            BulkRateLimitConfig::init().context("failed config bulk rate limit")?;
+        let route_roles = RouteRolesConfig::init(ROUTE_ROLES);
+        let deprecations = DeprecationConfig::init().context("failed config deprecated routes")?;
+        let metrics_scraper = MetricsScraper::new(
+            MetricsScrapeConfig::init().context("failed config metrics scrape")?,
+        )
+        .context("failed metrics scraper")?;
 
         info!("Initializing Redis connection for API Gateway");
 
@@ -57,9 +109,26 @@ impl AppState {
             jwt_config,
             di_container,
             system_metrics,
+            in_flight,
+            registry: Arc::new(registry),
+            redaction,
+            currency,
+            display_timezone,
+            request_budget,
+            slow_request,
+            read_only,
+            delete_semantics,
+            https,
+            json_body,
             rate_limit: rate_limiter_middleware,
+            rate_limit_costs,
+            rate_limit_groups,
+            bulk_rate_limit,
+            route_roles,
+            deprecations,
             session: session_middleware,
             redis: Arc::new(redis),
+            metrics_scraper,
         })
     }
 }
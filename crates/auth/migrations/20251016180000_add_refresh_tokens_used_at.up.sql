@@ -0,0 +1,3 @@
+-- Add up migration script here
+ALTER TABLE refresh_tokens
+ADD COLUMN IF NOT EXISTS used_at TIMESTAMP DEFAULT NULL;
@@ -0,0 +1,4 @@
+-- Add down migration script here
+ALTER TABLE refresh_tokens
+DROP COLUMN IF EXISTS session_started_at,
+DROP COLUMN IF EXISTS user_agent;
@@ -0,0 +1,4 @@
+-- Add up migration script here
+ALTER TABLE refresh_tokens
+ADD COLUMN IF NOT EXISTS user_agent VARCHAR(512) DEFAULT NULL,
+ADD COLUMN IF NOT EXISTS session_started_at TIMESTAMP DEFAULT current_timestamp;
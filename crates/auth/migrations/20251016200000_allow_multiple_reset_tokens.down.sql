@@ -0,0 +1,9 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS idx_reset_tokens_user_id;
+
+DELETE FROM reset_tokens r
+USING reset_tokens newer
+WHERE newer.user_id = r.user_id
+  AND newer.id > r.id;
+
+ALTER TABLE reset_tokens ADD CONSTRAINT reset_tokens_user_id_key UNIQUE (user_id);
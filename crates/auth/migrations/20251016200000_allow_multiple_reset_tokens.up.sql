@@ -0,0 +1,4 @@
+-- Add up migration script here
+ALTER TABLE reset_tokens DROP CONSTRAINT IF EXISTS reset_tokens_user_id_key;
+
+CREATE INDEX IF NOT EXISTS idx_reset_tokens_user_id ON reset_tokens (user_id, id DESC);
@@ -0,0 +1,19 @@
+use crate::domain::response::{api::ApiResponse, lockout::LockoutStatusResponse};
+use anyhow::Result;
+use async_trait::async_trait;
+use shared::errors::ServiceError;
+use std::sync::Arc;
+
+pub type DynLockoutService = Arc<dyn LockoutServiceTrait + Send + Sync>;
+
+#[async_trait]
+pub trait LockoutServiceTrait {
+    async fn get_status(
+        &self,
+        user_id: i32,
+    ) -> Result<ApiResponse<LockoutStatusResponse>, ServiceError>;
+    async fn unlock(
+        &self,
+        user_id: i32,
+    ) -> Result<ApiResponse<LockoutStatusResponse>, ServiceError>;
+}
@@ -1,11 +1,15 @@
 pub mod identity;
+pub mod lockout;
 pub mod login;
 pub mod password_reset;
 pub mod register;
+pub mod session;
 pub mod token;
 
 pub use self::identity::{DynIdentityService, IdentityServiceTrait};
+pub use self::lockout::{DynLockoutService, LockoutServiceTrait};
 pub use self::login::{DynLoginService, LoginServiceTrait};
 pub use self::password_reset::{DynPasswordResetService, PasswordServiceTrait};
 pub use self::register::{DynRegisterService, RegisterServiceTrait};
+pub use self::session::{DynSessionService, SessionServiceTrait};
 pub use self::token::{DynTokenService, TokenServiceTrait};
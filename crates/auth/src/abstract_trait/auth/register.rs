@@ -15,4 +15,6 @@ pub trait RegisterServiceTrait {
         &self,
         register_request: &RegisterRequest,
     ) -> Result<ApiResponse<UserResponse>, ServiceError>;
+
+    async fn resend_verification(&self, email: &str) -> Result<ApiResponse<bool>, ServiceError>;
 }
@@ -0,0 +1,20 @@
+use crate::domain::response::{api::ApiResponse, session::SessionResponse};
+use anyhow::Result;
+use async_trait::async_trait;
+use shared::errors::ServiceError;
+use std::sync::Arc;
+
+pub type DynSessionService = Arc<dyn SessionServiceTrait + Send + Sync>;
+
+#[async_trait]
+pub trait SessionServiceTrait {
+    async fn list_sessions(
+        &self,
+        user_id: i32,
+    ) -> Result<ApiResponse<Vec<SessionResponse>>, ServiceError>;
+    async fn revoke_session(
+        &self,
+        user_id: i32,
+        session_id: i32,
+    ) -> Result<ApiResponse<bool>, ServiceError>;
+}
@@ -3,12 +3,29 @@ use std::sync::Arc;
 use anyhow::Result;
 use async_trait::async_trait;
 
+use crate::domain::response::token::AccessToken;
 use shared::errors::ServiceError;
 
 pub type DynTokenService = Arc<dyn TokenServiceTrait + Send + Sync>;
 
 #[async_trait]
 pub trait TokenServiceTrait {
-    async fn create_access_token(&self, id: i32) -> Result<String, ServiceError>;
-    async fn create_refresh_token(&self, id: i32) -> Result<String, ServiceError>;
+    /// Signs an access token carrying the user's current roles, looked up
+    /// from the role service.
+    async fn create_access_token(&self, id: i32) -> Result<AccessToken, ServiceError>;
+    /// The role names `create_access_token` would put in the token for `id`.
+    async fn role_names(&self, id: i32) -> Result<Vec<String>, ServiceError>;
+    /// Starts a new session; the user's other sessions stay signed in unless
+    /// the per-user session limit evicts the oldest of them.
+    async fn create_refresh_token(
+        &self,
+        id: i32,
+        user_agent: Option<String>,
+    ) -> Result<String, ServiceError>;
+    /// Exchanges the refresh token `token` of user `id` for the next token of
+    /// its session, keeping its user agent and start time. Fails with
+    /// `TokenExpired` once the session has outlived the configured maximum
+    /// age, and with `SessionRevoked` after revoking every session of the user
+    /// when `token` was already rotated out.
+    async fn rotate_refresh_token(&self, token: &str, id: i32) -> Result<String, ServiceError>;
 }
@@ -9,4 +9,10 @@ pub type DynRoleGrpcClient = Arc<dyn RoleGrpcClientTrait + Send + Sync>;
 #[async_trait]
 pub trait RoleGrpcClientTrait {
     async fn find_by_name(&self, name: &str) -> Result<ApiResponse<RoleResponse>, AppErrorGrpc>;
+    /// The user's current roles, never cached, so a new token reflects
+    /// role changes straight away.
+    async fn find_by_user_id(
+        &self,
+        user_id: i32,
+    ) -> Result<ApiResponse<Vec<RoleResponse>>, AppErrorGrpc>;
 }
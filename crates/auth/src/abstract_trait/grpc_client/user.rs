@@ -1,5 +1,8 @@
 use crate::domain::{
-    requests::user::{CreateUserRequest, UpdateUserPasswordRequest, UpdateUserVerifiedRequest},
+    requests::user::{
+        CreateUserRequest, UpdateUserPasswordRequest, UpdateUserVerificationCodeRequest,
+        UpdateUserVerifiedRequest,
+    },
     response::{
         api::ApiResponse,
         user::{UserResponse, UserResponseWithPassword},
@@ -24,6 +27,11 @@ pub trait UserGrpcClientTrait {
         req: UpdateUserVerifiedRequest,
     ) -> Result<ApiResponse<UserResponse>, AppErrorGrpc>;
 
+    async fn update_user_verification_code(
+        &self,
+        req: UpdateUserVerificationCodeRequest,
+    ) -> Result<ApiResponse<UserResponse>, AppErrorGrpc>;
+
     async fn update_user_password(
         &self,
         req: UpdateUserPasswordRequest,
@@ -1,7 +1,8 @@
 use crate::{
     domain::requests::refresh_token::{CreateRefreshToken, UpdateRefreshToken},
-    models::refresh_token::RefreshToken as RefreshTokenModel,
+    models::refresh_token::{RefreshToken as RefreshTokenModel, RefreshTokenRotation},
 };
+use chrono::NaiveDateTime;
 use shared::errors::RepositoryError;
 use std::sync::Arc;
 
@@ -19,6 +20,12 @@ pub trait RefreshTokenQueryRepositoryTrait {
         &self,
         user_id: i32,
     ) -> Result<Option<RefreshTokenModel>, RepositoryError>;
+    /// Unexpired tokens that have not been rotated out, newest first; each
+    /// one is the head of a signed-in session.
+    async fn find_active_by_user_id(
+        &self,
+        user_id: i32,
+    ) -> Result<Vec<RefreshTokenModel>, RepositoryError>;
     async fn find_by_token(
         &self,
         token: String,
@@ -35,6 +42,28 @@ pub trait RefreshTokenCommandRepositoryTrait {
         &self,
         request: &UpdateRefreshToken,
     ) -> Result<RefreshTokenModel, RepositoryError>;
+    /// Exchanges `token` for `next.token` in one transaction, so a failed
+    /// insert leaves `token` usable. The new token continues the same
+    /// session; sessions started before `session_started_after` are ended
+    /// instead. Reads the presented token from the primary, so reuse is never
+    /// judged on a stale replica.
+    async fn rotate(
+        &self,
+        token: String,
+        next: &UpdateRefreshToken,
+        session_started_after: NaiveDateTime,
+    ) -> Result<RefreshTokenRotation, RepositoryError>;
     async fn delete_token(&self, token: String) -> Result<(), RepositoryError>;
+    /// Deletes the active token `refresh_token_id` of `user_id`, ending that
+    /// session. `NotFound` when it belongs to someone else or was rotated out.
+    async fn delete_session(
+        &self,
+        user_id: i32,
+        refresh_token_id: i32,
+    ) -> Result<RefreshTokenModel, RepositoryError>;
     async fn delete_by_user_id(&self, user_id: i32) -> Result<(), RepositoryError>;
+    /// Deletes up to `limit` tokens that have expired or were revoked,
+    /// returning how many went. Rotated-out tokens stay until they expire so
+    /// reuse can still be detected.
+    async fn purge_stale(&self, limit: i64) -> Result<u64, RepositoryError>;
 }
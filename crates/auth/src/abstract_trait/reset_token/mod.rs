@@ -24,4 +24,7 @@ pub trait ResetTokenCommandRepositoryTrait {
         request: &CreateResetTokenRequest,
     ) -> Result<ResetTokenModel, RepositoryError>;
     async fn delete_reset_token(&self, user_id: i32) -> Result<(), RepositoryError>;
+    async fn prune_reset_tokens(&self, user_id: i32, keep: i64) -> Result<u64, RepositoryError>;
+    /// Deletes up to `limit` expired reset tokens, returning how many went.
+    async fn delete_expired(&self, limit: i64) -> Result<u64, RepositoryError>;
 }
@@ -1,9 +1,13 @@
 use anyhow::{Context, Result};
+use shared::config::client_tls_from_env;
+use tonic::transport::ClientTlsConfig;
 
 #[derive(Clone)]
 pub struct GrpcClientConfig {
     pub user: String,
     pub role: String,
+    /// `None` calls the services over plaintext.
+    pub tls: Option<ClientTlsConfig>,
 }
 
 impl GrpcClientConfig {
@@ -14,6 +18,10 @@ impl GrpcClientConfig {
         let role = std::env::var("GRPC_ROLE_ADDR")
             .context("Missing environment variable: GRPC_ROLE_ADDR")?;
 
-        Ok(Self { user, role })
+        Ok(Self {
+            user,
+            role,
+            tls: client_tls_from_env()?,
+        })
     }
 }
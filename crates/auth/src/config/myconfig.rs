@@ -1,4 +1,6 @@
 use anyhow::{Context, Result, anyhow};
+use shared::config::{PreviousJwtKey, TokenTtl};
+use std::collections::HashSet;
 
 #[derive(Debug, Clone)]
 pub struct ServiceConfig {
@@ -6,10 +8,105 @@ pub struct ServiceConfig {
     pub metric_port: u16,
 }
 
+/// Failed logins allowed per email before it is locked out, and how long the
+/// lockout lasts.
+#[derive(Debug, Clone, Copy)]
+pub struct LoginLockoutPolicy {
+    pub max_attempts: i32,
+    pub lockout_minutes: i64,
+}
+
+/// Verification emails an address may request within the window before
+/// further resends are refused.
+#[derive(Debug, Clone, Copy)]
+pub struct VerificationResendPolicy {
+    pub max_attempts: i32,
+    pub window_minutes: i64,
+}
+
+/// What to do when a login would exceed the per-user session cap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionLimitAction {
+    /// Sign out the user's oldest sessions to make room.
+    EvictOldest,
+    /// Refuse the login until the user signs out elsewhere.
+    Reject,
+}
+
+/// Active sessions a user may hold at once; `None` leaves it unlimited.
+#[derive(Debug, Clone, Copy)]
+pub struct SessionLimitPolicy {
+    pub max_sessions: Option<usize>,
+    pub action: SessionLimitAction,
+}
+
+/// Whether the configured email domains are the only ones allowed to
+/// register or the ones refused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmailDomainMode {
+    Deny,
+    Allow,
+}
+
+/// Email domains checked at registration, e.g. to keep out disposable
+/// providers. A listed domain also covers its subdomains. An empty list in
+/// deny mode lets everything through.
+#[derive(Debug, Clone)]
+pub struct EmailDomainPolicy {
+    pub mode: EmailDomainMode,
+    pub domains: HashSet<String>,
+}
+
+impl EmailDomainPolicy {
+    /// Returns why `email` may not register, or `None` when it may.
+    pub fn check(&self, email: &str) -> Option<String> {
+        let domain = email
+            .rsplit_once('@')
+            .map(|(_, domain)| domain.trim().trim_end_matches('.').to_ascii_lowercase())
+            .unwrap_or_default();
+
+        let listed = domain
+            .match_indices('.')
+            .map(|(i, _)| &domain[i + 1..])
+            .chain(std::iter::once(domain.as_str()))
+            .any(|candidate| self.domains.contains(candidate));
+
+        match self.mode {
+            EmailDomainMode::Deny if listed => {
+                Some(format!("Email domain '{domain}' is not allowed"))
+            }
+            EmailDomainMode::Allow if !listed => Some(format!(
+                "Email domain '{domain}' is not on the list of allowed domains"
+            )),
+            _ => None,
+        }
+    }
+
+    /// Entries are comma or newline separated; blank lines and `#` comments
+    /// are skipped so a file can be used as-is.
+    fn parse_domains(raw: &str) -> impl Iterator<Item = String> + '_ {
+        raw.lines()
+            .map(|line| line.split('#').next().unwrap_or_default())
+            .flat_map(|line| line.split(','))
+            .map(|domain| domain.trim().trim_start_matches('@').to_ascii_lowercase())
+            .filter(|domain| !domain.is_empty())
+    }
+}
+
+/// Batch size and interval of the expired reset and refresh token purge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PurgeJobConfig {
+    pub batch_size: i64,
+    pub interval_seconds: u64,
+}
+
 #[derive(Debug, Clone)]
 pub struct Config {
     pub database_url: String,
+    pub database_read_url: Option<String>,
     pub jwt_secret: String,
+    pub jwt_previous_key: Option<PreviousJwtKey>,
+    pub token_ttl: TokenTtl,
     pub run_migrations: bool,
     pub port: u16,
     pub auth: ServiceConfig,
@@ -18,15 +115,39 @@ pub struct Config {
     pub kafka_broker: String,
     pub db_max_conn: u32,
     pub db_min_conn: u32,
+    pub require_email_verification: bool,
+    pub login_lockout: LoginLockoutPolicy,
+    pub verification_resend: VerificationResendPolicy,
+    pub session_max_age_days: i64,
+    pub session_limit: SessionLimitPolicy,
+    pub email_domains: EmailDomainPolicy,
+    pub max_reset_tokens_per_user: i64,
+    pub token_purge: PurgeJobConfig,
 }
 impl Config {
     pub fn init() -> Result<Self> {
         let database_url =
             std::env::var("DATABASE_URL").context("Missing environment variable: DATABASE_URL")?;
+        let database_read_url = std::env::var("DATABASE_READ_URL")
+            .ok()
+            .filter(|url| !url.trim().is_empty());
         let jwt_secret =
             std::env::var("JWT_SECRET").context("Missing environment variable: JWT_SECRET")?;
-        let run_migrations_str = std::env::var("RUN_MIGRATIONS")
-            .context("Missing environment variable: RUN_MIGRATIONS")?;
+        let jwt_previous_key = PreviousJwtKey::from_env()?;
+
+        let access_token_ttl = TokenTtl::parse(
+            &std::env::var("ACCESS_TOKEN_TTL").unwrap_or_else(|_| "60m".to_string()),
+        )
+        .context("ACCESS_TOKEN_TTL must be a duration like 15m")?;
+        let refresh_token_ttl = TokenTtl::parse(
+            &std::env::var("REFRESH_TOKEN_TTL").unwrap_or_else(|_| "24h".to_string()),
+        )
+        .context("REFRESH_TOKEN_TTL must be a duration like 7d")?;
+        let token_ttl = TokenTtl::new(access_token_ttl, refresh_token_ttl)?;
+
+        let run_migrations_str = std::env::var("AUTO_MIGRATE")
+            .or_else(|_| std::env::var("RUN_MIGRATIONS"))
+            .context("Missing environment variable: AUTO_MIGRATE")?;
         let port_str = std::env::var("PORT").context("Missing environment variable: PORT")?;
 
         let kafka_broker = std::env::var("KAFKA").context("Missing environment variable: KAFKA")?;
@@ -36,7 +157,7 @@ impl Config {
             "false" => false,
             other => {
                 return Err(anyhow!(
-                    "RUN_MIGRATIONS must be 'true' or 'false', got '{}'",
+                    "AUTO_MIGRATE must be 'true' or 'false', got '{}'",
                     other
                 ));
             }
@@ -52,6 +173,115 @@ impl Config {
             .parse::<u32>()
             .context("Unable to parse DB_MIN_CONNECTION as u32")?;
 
+        let require_email_verification = match std::env::var("REQUIRE_EMAIL_VERIFICATION")
+            .unwrap_or_else(|_| "true".to_string())
+            .as_str()
+        {
+            "true" => true,
+            "false" => false,
+            other => {
+                return Err(anyhow!(
+                    "REQUIRE_EMAIL_VERIFICATION must be 'true' or 'false', got '{}'",
+                    other
+                ));
+            }
+        };
+
+        let login_max_attempts = std::env::var("LOGIN_MAX_ATTEMPTS")
+            .unwrap_or_else(|_| "5".to_string())
+            .parse::<i32>()
+            .context("LOGIN_MAX_ATTEMPTS must be a valid i32 integer")?;
+
+        let login_lockout_minutes = std::env::var("LOGIN_LOCKOUT_MINUTES")
+            .unwrap_or_else(|_| "15".to_string())
+            .parse::<i64>()
+            .context("LOGIN_LOCKOUT_MINUTES must be a valid i64 integer")?;
+
+        let resend_max_attempts = std::env::var("VERIFICATION_RESEND_MAX_ATTEMPTS")
+            .unwrap_or_else(|_| "3".to_string())
+            .parse::<i32>()
+            .context("VERIFICATION_RESEND_MAX_ATTEMPTS must be a valid i32 integer")?;
+
+        let resend_window_minutes = std::env::var("VERIFICATION_RESEND_WINDOW_MINUTES")
+            .unwrap_or_else(|_| "60".to_string())
+            .parse::<i64>()
+            .context("VERIFICATION_RESEND_WINDOW_MINUTES must be a valid i64 integer")?;
+
+        let session_max_age_days = std::env::var("SESSION_MAX_AGE_DAYS")
+            .unwrap_or_else(|_| "30".to_string())
+            .parse::<i64>()
+            .context("SESSION_MAX_AGE_DAYS must be a valid i64 integer")?;
+
+        let max_sessions = std::env::var("MAX_SESSIONS_PER_USER")
+            .unwrap_or_else(|_| "0".to_string())
+            .parse::<usize>()
+            .context("MAX_SESSIONS_PER_USER must be a valid usize integer")?;
+
+        let session_limit_action = match std::env::var("SESSION_LIMIT_ACTION")
+            .unwrap_or_else(|_| "evict".to_string())
+            .as_str()
+        {
+            "evict" => SessionLimitAction::EvictOldest,
+            "reject" => SessionLimitAction::Reject,
+            other => {
+                return Err(anyhow!(
+                    "SESSION_LIMIT_ACTION must be 'evict' or 'reject', got '{}'",
+                    other
+                ));
+            }
+        };
+
+        let max_reset_tokens_per_user = std::env::var("MAX_RESET_TOKENS_PER_USER")
+            .unwrap_or_else(|_| "1".to_string())
+            .parse::<i64>()
+            .context("MAX_RESET_TOKENS_PER_USER must be a valid i64 integer")?;
+
+        if max_reset_tokens_per_user < 1 {
+            return Err(anyhow!(
+                "MAX_RESET_TOKENS_PER_USER must be at least 1, got {}",
+                max_reset_tokens_per_user
+            ));
+        }
+
+        let token_purge_batch_size = std::env::var("TOKEN_PURGE_BATCH_SIZE")
+            .or_else(|_| std::env::var("REFRESH_TOKEN_PURGE_BATCH_SIZE"))
+            .unwrap_or_else(|_| "500".to_string())
+            .parse::<i64>()
+            .context("TOKEN_PURGE_BATCH_SIZE must be a valid i64 integer")?;
+
+        let token_purge_interval_seconds = std::env::var("TOKEN_PURGE_INTERVAL_SECONDS")
+            .or_else(|_| std::env::var("REFRESH_TOKEN_PURGE_INTERVAL_SECONDS"))
+            .unwrap_or_else(|_| "3600".to_string())
+            .parse::<u64>()
+            .context("TOKEN_PURGE_INTERVAL_SECONDS must be a valid u64 integer")?;
+
+        let email_domain_mode = match std::env::var("EMAIL_DOMAIN_MODE")
+            .unwrap_or_else(|_| "deny".to_string())
+            .as_str()
+        {
+            "deny" => EmailDomainMode::Deny,
+            "allow" => EmailDomainMode::Allow,
+            other => {
+                return Err(anyhow!(
+                    "EMAIL_DOMAIN_MODE must be 'deny' or 'allow', got '{}'",
+                    other
+                ));
+            }
+        };
+
+        let mut email_domains: HashSet<String> =
+            EmailDomainPolicy::parse_domains(&std::env::var("EMAIL_DOMAINS").unwrap_or_default())
+                .collect();
+
+        if let Some(path) = std::env::var("EMAIL_DOMAINS_FILE")
+            .ok()
+            .filter(|path| !path.trim().is_empty())
+        {
+            let contents = std::fs::read_to_string(&path)
+                .with_context(|| format!("Unable to read EMAIL_DOMAINS_FILE '{path}'"))?;
+            email_domains.extend(EmailDomainPolicy::parse_domains(&contents));
+        }
+
         let port = port_str
             .parse::<u16>()
             .context("PORT must be a valid u16 integer")?;
@@ -91,7 +321,10 @@ impl Config {
 
         Ok(Self {
             database_url,
+            database_read_url,
             jwt_secret,
+            jwt_previous_key,
+            token_ttl,
             run_migrations,
             port,
             auth: ServiceConfig {
@@ -109,6 +342,29 @@ impl Config {
             kafka_broker,
             db_max_conn,
             db_min_conn,
+            require_email_verification,
+            login_lockout: LoginLockoutPolicy {
+                max_attempts: login_max_attempts.max(1),
+                lockout_minutes: login_lockout_minutes.max(1),
+            },
+            verification_resend: VerificationResendPolicy {
+                max_attempts: resend_max_attempts.max(1),
+                window_minutes: resend_window_minutes.max(1),
+            },
+            session_max_age_days: session_max_age_days.max(1),
+            session_limit: SessionLimitPolicy {
+                max_sessions: (max_sessions > 0).then_some(max_sessions),
+                action: session_limit_action,
+            },
+            email_domains: EmailDomainPolicy {
+                mode: email_domain_mode,
+                domains: email_domains,
+            },
+            max_reset_tokens_per_user,
+            token_purge: PurgeJobConfig {
+                batch_size: token_purge_batch_size.max(1),
+                interval_seconds: token_purge_interval_seconds.max(1),
+            },
         })
     }
 }
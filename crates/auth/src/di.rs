@@ -1,23 +1,36 @@
 use crate::abstract_trait::auth::{
-    DynIdentityService, DynLoginService, DynPasswordResetService, DynRegisterService,
+    DynIdentityService, DynLockoutService, DynLoginService, DynPasswordResetService,
+    DynRegisterService, DynSessionService,
 };
 use crate::{
-    abstract_trait::{auth::DynTokenService, grpc_client::user::DynUserGrpcClient},
-    grpc_client::{GrpcClients, user::UserGrpcClientService},
+    abstract_trait::{
+        auth::DynTokenService,
+        grpc_client::{role::DynRoleGrpcClient, user::DynUserGrpcClient},
+    },
+    config::myconfig::{
+        EmailDomainPolicy, LoginLockoutPolicy, PurgeJobConfig, SessionLimitPolicy,
+        VerificationResendPolicy,
+    },
+    grpc_client::{GrpcClients, role::RoleGrpcClientService, user::UserGrpcClientService},
     repository::{refresh_token::RefreshTokenRepository, reset_token::ResetTokenRepository},
     service::{
         forgot::{PasswordResetService, PasswordResetServiceDeps},
         identity::{IdentityService, IdentityServiceDeps},
+        lockout::{LockoutService, LockoutServiceDeps},
         login::{LoginService, LoginServiceDeps},
         register::{RegisterService, RegisterServiceDeps},
-        token::TokenService,
+        session::{SessionService, SessionServiceDeps},
+        token::{TokenService, TokenServiceDeps},
+        token_sweeper::ExpiredTokenSweeper,
     },
 };
 use anyhow::{Context, Result};
+use chrono::Duration;
 use shared::{
-    abstract_trait::{DynHashing, DynJwtService, DynKafka},
+    abstract_trait::{DynHashing, DynJwtService, DynKafka, DynPasswordBreachChecker},
     cache::CacheStore,
-    config::{ConnectionPool, RedisPool},
+    config::{DatabasePools, RedisPool},
+    utils::CircuitBreaker,
 };
 use std::{fmt, sync::Arc};
 
@@ -27,6 +40,9 @@ pub struct DependenciesInject {
     pub register_service: DynRegisterService,
     pub identity_service: DynIdentityService,
     pub password_reset_service: DynPasswordResetService,
+    pub lockout_service: DynLockoutService,
+    pub session_service: DynSessionService,
+    pub token_sweeper: Arc<ExpiredTokenSweeper>,
 }
 
 impl fmt::Debug for DependenciesInject {
@@ -36,59 +52,103 @@ impl fmt::Debug for DependenciesInject {
             .field("register_service", &"DynRegisterService")
             .field("identity_service", &"DynIdentityService")
             .field("password_reset_service", &"DynPasswordResetService")
+            .field("lockout_service", &"DynLockoutService")
+            .field("session_service", &"DynSessionService")
+            .field("token_sweeper", &"ExpiredTokenSweeper")
             .finish()
     }
 }
 
 #[derive(Clone)]
 pub struct DependenciesInjectDeps {
-    pub pool: ConnectionPool,
+    pub pools: DatabasePools,
     pub hash: DynHashing,
     pub jwt_config: DynJwtService,
     pub kafka: DynKafka,
     pub redis: RedisPool,
+    pub password_breach: DynPasswordBreachChecker,
+    pub require_email_verification: bool,
+    pub login_lockout: LoginLockoutPolicy,
+    pub verification_resend: VerificationResendPolicy,
+    pub session_max_age_days: i64,
+    pub session_limit: SessionLimitPolicy,
+    pub email_domains: EmailDomainPolicy,
+    pub max_reset_tokens_per_user: i64,
+    pub token_purge: PurgeJobConfig,
 }
 
 impl DependenciesInject {
     pub async fn new(deps: DependenciesInjectDeps, clients: GrpcClients) -> Result<Self> {
         let DependenciesInjectDeps {
             hash,
-            pool,
+            pools,
             jwt_config,
             kafka,
             redis,
+            password_breach,
+            require_email_verification,
+            login_lockout,
+            verification_resend,
+            session_max_age_days,
+            session_limit,
+            email_domains,
+            max_reset_tokens_per_user,
+            token_purge,
         } = deps;
 
         let cache = Arc::new(CacheStore::new(redis.pool.clone()));
 
-        let refresh_token = RefreshTokenRepository::new(pool.clone());
-        let reset_token = ResetTokenRepository::new(pool.clone());
+        let refresh_token = RefreshTokenRepository::new(pools.write.clone());
+        let reset_token = ResetTokenRepository::new(pools.write.clone());
 
         let user_client: DynUserGrpcClient = Arc::new(UserGrpcClientService::new(
             clients.user_query_client.clone(),
             clients.user_command_client.clone(),
+            Arc::new(CircuitBreaker::from_env("user-service")),
         ));
 
+        let role_client: DynRoleGrpcClient = Arc::new(
+            RoleGrpcClientService::new(
+                clients.role_client.clone(),
+                Arc::new(CircuitBreaker::from_env("role-service")),
+                cache.clone(),
+                Duration::minutes(5),
+            )
+            .await,
+        );
+
         let register_deps = RegisterServiceDeps {
             user_client: user_client.clone(),
             kafka: kafka.clone(),
             cache_store: cache.clone(),
+            password_breach: password_breach.clone(),
+            resend_policy: verification_resend,
+            email_domains,
         };
 
         let register_service =
             Arc::new(RegisterService::new(register_deps).context("failed initialize register")?)
                 as DynRegisterService;
 
-        let token_service = Arc::new(TokenService::new(
-            jwt_config.clone(),
-            refresh_token.command.clone(),
-        )) as DynTokenService;
+        let token_deps = TokenServiceDeps {
+            jwt: jwt_config.clone(),
+            refresh_token_query: refresh_token.query.clone(),
+            refresh_token_command: refresh_token.command.clone(),
+            role_client,
+            cache_store: cache.clone(),
+            max_session_age: Duration::days(session_max_age_days),
+            session_limit,
+        };
+
+        let token_service = Arc::new(TokenService::new(token_deps)) as DynTokenService;
 
         let login_deps = LoginServiceDeps {
             hash,
             token_service: token_service.clone(),
             user_client: user_client.clone(),
             cache_store: cache.clone(),
+            require_email_verification,
+            lockout_policy: login_lockout,
         };
 
         let login_service =
@@ -109,21 +169,48 @@ impl DependenciesInject {
 
         let password_deps = PasswordResetServiceDeps {
             reset_token_query: reset_token.query,
-            reset_token_command: reset_token.command,
+            reset_token_command: reset_token.command.clone(),
             user_client: user_client.clone(),
             kafka: kafka.clone(),
             cache_store: cache.clone(),
+            password_breach,
+            max_reset_tokens: max_reset_tokens_per_user,
         };
 
         let password_reset_service = Arc::new(
             PasswordResetService::new(password_deps).context("failed iniliazlie password reset")?,
         ) as DynPasswordResetService;
 
+        let lockout_deps = LockoutServiceDeps {
+            user_client: user_client.clone(),
+            cache_store: cache.clone(),
+            policy: login_lockout,
+        };
+
+        let lockout_service = Arc::new(LockoutService::new(lockout_deps)) as DynLockoutService;
+
+        let session_deps = SessionServiceDeps {
+            refresh_token_query: refresh_token.query.clone(),
+            refresh_token_command: refresh_token.command.clone(),
+            cache_store: cache.clone(),
+        };
+
+        let session_service = Arc::new(SessionService::new(session_deps)) as DynSessionService;
+
+        let token_sweeper = Arc::new(ExpiredTokenSweeper::new(
+            refresh_token.command.clone(),
+            reset_token.command.clone(),
+            token_purge,
+        ));
+
         Ok(Self {
             login_service,
+            lockout_service,
+            session_service,
             register_service,
             identity_service,
             password_reset_service,
+            token_sweeper,
         })
     }
 }
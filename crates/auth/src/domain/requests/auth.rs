@@ -10,6 +10,9 @@ pub struct AuthRequest {
     #[validate(length(min = 6))]
     #[serde(rename = "password")]
     pub password: String,
+
+    #[serde(default)]
+    pub user_agent: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Validate, Clone)]
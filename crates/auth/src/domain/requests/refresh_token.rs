@@ -1,3 +1,4 @@
+use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use validator::Validate;
@@ -14,6 +15,12 @@ pub struct CreateRefreshToken {
     #[serde(rename = "expires_at")]
     #[validate(length(min = 1))]
     pub expired_date: String,
+
+    #[validate(length(max = 512))]
+    pub user_agent: Option<String>,
+
+    /// Start of the session a rotated token continues; `None` starts a new one.
+    pub session_started_at: Option<NaiveDateTime>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Validate, ToSchema)]
@@ -10,6 +10,15 @@ pub struct UpdateUserVerifiedRequest {
     pub is_verified: bool,
 }
 
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct UpdateUserVerificationCodeRequest {
+    #[serde(rename = "user_id")]
+    pub user_id: i32,
+
+    #[serde(rename = "verification_code")]
+    pub verification_code: String,
+}
+
 #[derive(Debug, Serialize, Deserialize, Validate)]
 pub struct UpdateUserPasswordRequest {
     #[serde(rename = "user_id")]
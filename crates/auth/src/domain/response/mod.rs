@@ -1,6 +1,8 @@
 pub mod api;
+pub mod lockout;
 pub mod pagination;
 pub mod role;
+pub mod session;
 pub mod token;
 pub mod user;
 pub mod user_role;
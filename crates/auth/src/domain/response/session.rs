@@ -0,0 +1,35 @@
+use crate::models::refresh_token::RefreshToken as RefreshTokenModel;
+use genproto::auth::SessionResponse as ProtoSessionResponse;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SessionResponse {
+    pub id: i32,
+    pub user_agent: Option<String>,
+    pub created_at: Option<String>,
+    pub last_used_at: Option<String>,
+}
+
+/// A session's current token was issued at its last login or refresh.
+impl From<RefreshTokenModel> for SessionResponse {
+    fn from(value: RefreshTokenModel) -> Self {
+        Self {
+            id: value.refresh_token_id,
+            user_agent: value.user_agent,
+            created_at: value.session_started_at.map(|dt| dt.and_utc().to_rfc3339()),
+            last_used_at: value.created_at.map(|dt| dt.and_utc().to_rfc3339()),
+        }
+    }
+}
+
+impl From<SessionResponse> for ProtoSessionResponse {
+    fn from(value: SessionResponse) -> Self {
+        Self {
+            id: value.id,
+            user_agent: value.user_agent.unwrap_or_default(),
+            created_at: value.created_at.unwrap_or_default(),
+            last_used_at: value.last_used_at.unwrap_or_default(),
+        }
+    }
+}
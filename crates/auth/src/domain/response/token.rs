@@ -2,10 +2,20 @@ use genproto::auth::TokenResponse as ProtoTokenResponse;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
+/// A signed access token and the role names put in its `roles` claim.
+#[derive(Debug, Clone)]
+pub struct AccessToken {
+    pub token: String,
+    pub roles: Vec<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct TokenResponse {
     pub access_token: String,
     pub refresh_token: String,
+    /// Same role names as the access token's `roles` claim.
+    #[serde(default)]
+    pub roles: Vec<String>,
 }
 
 impl From<ProtoTokenResponse> for TokenResponse {
@@ -13,6 +23,7 @@ impl From<ProtoTokenResponse> for TokenResponse {
         Self {
             access_token: value.access_token,
             refresh_token: value.refresh_token,
+            roles: value.roles,
         }
     }
 }
@@ -22,6 +33,7 @@ impl From<TokenResponse> for ProtoTokenResponse {
         Self {
             access_token: value.access_token,
             refresh_token: value.refresh_token,
+            roles: value.roles,
         }
     }
 }
@@ -8,6 +8,7 @@ use utoipa::ToSchema;
 #[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
 pub struct UserResponse {
     pub id: i32,
+    pub external_id: String,
     pub firstname: String,
     pub lastname: String,
     pub email: String,
@@ -15,17 +16,23 @@ pub struct UserResponse {
     pub created_at: Option<String>,
     #[serde(rename = "updated_at")]
     pub updated_at: Option<String>,
+    /// Role names, filled in by `get_me` from the same lookup used for the
+    /// access token's `roles` claim.
+    #[serde(default)]
+    pub roles: Vec<String>,
 }
 
 impl From<UserResponseProto> for UserResponse {
     fn from(value: UserResponseProto) -> Self {
         UserResponse {
             id: value.id,
+            external_id: value.external_id,
             firstname: value.firstname,
             lastname: value.lastname,
             email: value.email,
             created_at: parse_datetime(&value.created_at),
             updated_at: parse_datetime(&value.updated_at),
+            roles: value.roles,
         }
     }
 }
@@ -34,11 +41,13 @@ impl From<UserResponse> for UserResponseProto {
     fn from(value: UserResponse) -> Self {
         UserResponseProto {
             id: value.id,
+            external_id: value.external_id,
             firstname: value.firstname,
             lastname: value.lastname,
             email: value.email,
             created_at: value.created_at.unwrap_or_default(),
             updated_at: value.updated_at.unwrap_or_default(),
+            roles: value.roles,
         }
     }
 }
@@ -46,6 +55,7 @@ impl From<UserResponse> for UserResponseProto {
 #[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
 pub struct UserResponseWithPassword {
     pub id: i32,
+    pub external_id: String,
     pub firstname: String,
     pub lastname: String,
     pub email: String,
@@ -54,18 +64,21 @@ pub struct UserResponseWithPassword {
     pub created_at: Option<String>,
     #[serde(rename = "updated_at")]
     pub updated_at: Option<String>,
+    pub is_verified: bool,
 }
 
 impl From<UserResponseWithPasswordProto> for UserResponseWithPassword {
     fn from(value: UserResponseWithPasswordProto) -> Self {
         UserResponseWithPassword {
             id: value.id,
+            external_id: value.external_id,
             firstname: value.firstname,
             lastname: value.lastname,
             email: value.email,
             password: value.password,
             created_at: parse_datetime(&value.created_at),
             updated_at: parse_datetime(&value.updated_at),
+            is_verified: value.is_verified,
         }
     }
 }
@@ -74,12 +87,14 @@ impl From<UserResponseWithPassword> for UserResponseWithPasswordProto {
     fn from(value: UserResponseWithPassword) -> Self {
         UserResponseWithPasswordProto {
             id: value.id,
+            external_id: value.external_id,
             firstname: value.firstname,
             lastname: value.lastname,
             email: value.email,
             password: value.password,
             created_at: value.created_at.unwrap_or_default(),
             updated_at: value.updated_at.unwrap_or_default(),
+            is_verified: value.is_verified,
         }
     }
 }
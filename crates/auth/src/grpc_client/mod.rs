@@ -8,8 +8,9 @@ use genproto::{
     },
     user_role::user_role_service_client::UserRoleServiceClient,
 };
+use shared::{config::grpc_endpoint, with_grpc_compression};
 use std::time::Duration;
-use tonic::transport::{Channel, Endpoint};
+use tonic::transport::{Channel, ClientTlsConfig};
 
 pub mod role;
 pub mod user;
@@ -25,20 +26,27 @@ pub struct GrpcClients {
 
 impl GrpcClients {
     pub async fn init(config: GrpcClientConfig) -> Result<Self> {
-        let user_channel = Self::connect(config.user, "user-service").await?;
-        let role_channel = Self::connect(config.role, "role-service").await?;
+        let user_channel = Self::connect(config.user, "user-service", config.tls.as_ref()).await?;
+        let role_channel = Self::connect(config.role, "role-service", config.tls.as_ref()).await?;
 
         Ok(Self {
-            user_command_client: UserCommandServiceClient::new(user_channel.clone()),
-            user_query_client: UserQueryServiceClient::new(user_channel),
-            role_client: RoleQueryServiceClient::new(role_channel.clone()),
-            user_role_client: UserRoleServiceClient::new(role_channel.clone()),
+            user_command_client: with_grpc_compression!(UserCommandServiceClient::new(
+                user_channel.clone()
+            )),
+            user_query_client: with_grpc_compression!(UserQueryServiceClient::new(user_channel)),
+            role_client: with_grpc_compression!(RoleQueryServiceClient::new(role_channel.clone())),
+            user_role_client: with_grpc_compression!(UserRoleServiceClient::new(
+                role_channel.clone()
+            )),
         })
     }
 
-    async fn connect(addr: String, service: &str) -> Result<Channel> {
-        let endpoint = Endpoint::from_shared(addr.clone())
-            .with_context(|| format!("Invalid gRPC address for {service}: {addr}"))?;
+    async fn connect(
+        addr: String,
+        service: &str,
+        tls: Option<&ClientTlsConfig>,
+    ) -> Result<Channel> {
+        let endpoint = grpc_endpoint(&addr, service, tls)?;
 
         let configured_endpoint = endpoint
             .connect_timeout(Duration::from_secs(3))
@@ -4,33 +4,64 @@ use crate::{
 };
 use anyhow::Result;
 use async_trait::async_trait;
+use chrono::Duration;
 use genproto::role::{
-    FindByNameRequest,
+    FindByIdUserRoleRequest, FindByNameRequest,
     role_query_service_client::RoleQueryServiceClient as RoleQueryServiceGrpcClient,
 };
-use shared::errors::AppErrorGrpc;
+use shared::{cache::CacheStore, errors::AppErrorGrpc, utils::CircuitBreaker};
+use std::sync::Arc;
 use tonic::{Request, transport::Channel};
 
+/// Roles change rarely, so lookups are cached briefly to keep auth working
+/// through short role-service blips.
 pub struct RoleGrpcClientService {
     client: RoleQueryServiceGrpcClient<Channel>,
+    breaker: Arc<CircuitBreaker>,
+    cache_store: Arc<CacheStore>,
+    cache_ttl: Duration,
 }
 
 impl RoleGrpcClientService {
-    pub async fn new(client: RoleQueryServiceGrpcClient<Channel>) -> Self {
-        Self { client }
+    pub async fn new(
+        client: RoleQueryServiceGrpcClient<Channel>,
+        breaker: Arc<CircuitBreaker>,
+        cache_store: Arc<CacheStore>,
+        cache_ttl: Duration,
+    ) -> Self {
+        Self {
+            client,
+            breaker,
+            cache_store,
+            cache_ttl,
+        }
     }
 }
 
 #[async_trait]
 impl RoleGrpcClientTrait for RoleGrpcClientService {
     async fn find_by_name(&self, name: &str) -> Result<ApiResponse<RoleResponse>, AppErrorGrpc> {
+        let cache_key = format!("auth:role:find_by_name:{name}");
+
+        if let Some(role) = self
+            .cache_store
+            .get_from_cache::<RoleResponse>(&cache_key)
+            .await
+        {
+            return Ok(ApiResponse {
+                status: "success".to_string(),
+                message: "OK".to_string(),
+                data: role,
+            });
+        }
+
         let req = Request::new(FindByNameRequest {
             name: name.to_string(),
         });
 
         let mut client = self.client.clone();
 
-        let response = client.find_by_name(req).await.map_err(AppErrorGrpc::from)?;
+        let response = self.breaker.call(|| client.find_by_name(req)).await?;
 
         let inner = response.into_inner();
 
@@ -40,10 +71,35 @@ impl RoleGrpcClientTrait for RoleGrpcClientService {
 
         let domain_user: RoleResponse = user_data.into();
 
+        if self.cache_ttl > Duration::zero() {
+            self.cache_store
+                .set_to_cache(&cache_key, &domain_user, self.cache_ttl)
+                .await;
+        }
+
         Ok(ApiResponse {
             status: "success".to_string(),
             message: "OK".to_string(),
             data: domain_user,
         })
     }
+
+    async fn find_by_user_id(
+        &self,
+        user_id: i32,
+    ) -> Result<ApiResponse<Vec<RoleResponse>>, AppErrorGrpc> {
+        let req = Request::new(FindByIdUserRoleRequest { user_id });
+
+        let mut client = self.client.clone();
+
+        let response = self.breaker.call(|| client.find_by_user_id(req)).await?;
+
+        let inner = response.into_inner();
+
+        Ok(ApiResponse {
+            status: inner.status,
+            message: inner.message,
+            data: inner.data.into_iter().map(Into::into).collect(),
+        })
+    }
 }
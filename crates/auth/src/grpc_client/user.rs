@@ -1,7 +1,10 @@
 use crate::{
     abstract_trait::grpc_client::user::UserGrpcClientTrait,
     domain::{
-        requests::user::{CreateUserRequest, UpdateUserPasswordRequest, UpdateUserVerifiedRequest},
+        requests::user::{
+            CreateUserRequest, UpdateUserPasswordRequest, UpdateUserVerificationCodeRequest,
+            UpdateUserVerifiedRequest,
+        },
         response::{
             api::ApiResponse,
             user::{UserResponse, UserResponseWithPassword},
@@ -15,27 +18,32 @@ use genproto::{
     user::{
         FindByEmailUserRequest, FindByIdUserRequest,
         UpdateUserPasswordRequest as UpdateUserPasswordProtoRequest,
+        UpdateUserVerificationCodeRequest as UpdateUserVerificationCodeProtoRequest,
         UpdateUserVerifiedRequest as UpdateUserVerifiedProtoRequest, VerifyCodeRequest,
         user_command_service_client::UserCommandServiceClient,
         user_query_service_client::UserQueryServiceClient,
     },
 };
-use shared::errors::AppErrorGrpc;
+use shared::{errors::AppErrorGrpc, utils::CircuitBreaker};
+use std::sync::Arc;
 use tonic::{Request, transport::Channel};
 
 pub struct UserGrpcClientService {
     query_client: UserQueryServiceClient<Channel>,
     command_client: UserCommandServiceClient<Channel>,
+    breaker: Arc<CircuitBreaker>,
 }
 
 impl UserGrpcClientService {
     pub fn new(
         query_client: UserQueryServiceClient<Channel>,
         command_client: UserCommandServiceClient<Channel>,
+        breaker: Arc<CircuitBreaker>,
     ) -> Self {
         Self {
             query_client,
             command_client,
+            breaker,
         }
     }
 }
@@ -58,7 +66,7 @@ impl UserGrpcClientTrait for UserGrpcClientService {
 
         let mut client = self.command_client.clone();
 
-        let response = client.create_user(req).await.map_err(AppErrorGrpc::from)?;
+        let response = self.breaker.call(|| client.create_user(req)).await?;
 
         let inner = response.into_inner();
 
@@ -86,10 +94,41 @@ impl UserGrpcClientTrait for UserGrpcClientService {
 
         let mut client = self.command_client.clone();
 
-        let response = client
-            .update_user_is_verified(req)
-            .await
-            .map_err(AppErrorGrpc::from)?;
+        let response = self
+            .breaker
+            .call(|| client.update_user_is_verified(req))
+            .await?;
+
+        let inner = response.into_inner();
+
+        let user_data = inner.data.ok_or_else(|| {
+            AppErrorGrpc::Unhandled("User data is missing in gRPC response".into())
+        })?;
+
+        let domain_user: UserResponse = user_data.into();
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "OK".to_string(),
+            data: domain_user,
+        })
+    }
+
+    async fn update_user_verification_code(
+        &self,
+        req: UpdateUserVerificationCodeRequest,
+    ) -> Result<ApiResponse<UserResponse>, AppErrorGrpc> {
+        let req = Request::new(UpdateUserVerificationCodeProtoRequest {
+            user_id: req.user_id,
+            verification_code: req.verification_code,
+        });
+
+        let mut client = self.command_client.clone();
+
+        let response = self
+            .breaker
+            .call(|| client.update_user_verification_code(req))
+            .await?;
 
         let inner = response.into_inner();
 
@@ -117,10 +156,10 @@ impl UserGrpcClientTrait for UserGrpcClientService {
 
         let mut client = self.command_client.clone();
 
-        let response = client
-            .update_user_password(req)
-            .await
-            .map_err(AppErrorGrpc::from)?;
+        let response = self
+            .breaker
+            .call(|| client.update_user_password(req))
+            .await?;
 
         let inner = response.into_inner();
 
@@ -145,10 +184,10 @@ impl UserGrpcClientTrait for UserGrpcClientService {
 
         let mut client = self.query_client.clone();
 
-        let response = client
-            .find_verification_code(request)
-            .await
-            .map_err(AppErrorGrpc::from)?;
+        let response = self
+            .breaker
+            .call(|| client.find_verification_code(request))
+            .await?;
 
         let inner = response.into_inner();
 
@@ -173,10 +212,7 @@ impl UserGrpcClientTrait for UserGrpcClientService {
 
         let mut client = self.query_client.clone();
 
-        let response = client
-            .find_by_email(request)
-            .await
-            .map_err(AppErrorGrpc::from)?;
+        let response = self.breaker.call(|| client.find_by_email(request)).await?;
 
         let inner = response.into_inner();
 
@@ -201,10 +237,10 @@ impl UserGrpcClientTrait for UserGrpcClientService {
 
         let mut client = self.query_client.clone();
 
-        let response = client
-            .find_by_email_and_verify(request)
-            .await
-            .map_err(AppErrorGrpc::from)?;
+        let response = self
+            .breaker
+            .call(|| client.find_by_email_and_verify(request))
+            .await?;
 
         let inner = response.into_inner();
 
@@ -226,10 +262,7 @@ impl UserGrpcClientTrait for UserGrpcClientService {
 
         let mut client = self.query_client.clone();
 
-        let response = client
-            .find_by_id(request)
-            .await
-            .map_err(AppErrorGrpc::from)?;
+        let response = self.breaker.call(|| client.find_by_id(request)).await?;
 
         let inner = response.into_inner();
 
@@ -1,6 +1,7 @@
 use crate::{
     abstract_trait::auth::{
-        DynIdentityService, DynLoginService, DynPasswordResetService, DynRegisterService,
+        DynIdentityService, DynLockoutService, DynLoginService, DynPasswordResetService,
+        DynRegisterService, DynSessionService,
     },
     domain::requests::{
         auth::{AuthRequest as DomainLoginRequest, RegisterRequest as DomainRegisterRequest},
@@ -9,14 +10,17 @@ use crate::{
 };
 use genproto::{
     auth::{
-        ApiResponseForgotPassword, ApiResponseGetMe, ApiResponseLogin, ApiResponseRefreshToken,
-        ApiResponseRegister, ApiResponseResetPassword, ApiResponseVerifyCode,
-        ForgotPasswordRequest, GetMeRequest, LoginRequest, RefreshTokenRequest,
-        ResetPasswordRequest, VerifyCodeRequest, auth_service_server::AuthService,
+        ApiResponseForgotPassword, ApiResponseGetMe, ApiResponseLockoutStatus, ApiResponseLogin,
+        ApiResponseRefreshToken, ApiResponseRegister, ApiResponseResendVerification,
+        ApiResponseResetPassword, ApiResponseRevokeSession, ApiResponseSessions,
+        ApiResponseVerifyCode, ForgotPasswordRequest, GetMeRequest, ListSessionsRequest,
+        LoginRequest, RefreshTokenRequest, ResendVerificationRequest, ResetPasswordRequest,
+        RevokeSessionRequest, UserLockoutRequest, VerifyCodeRequest,
+        auth_service_server::AuthService,
     },
     common::RegisterRequest,
 };
-use shared::errors::AppErrorGrpc;
+use shared::{errors::AppErrorGrpc, utils::EmailNormalizer};
 use std::fmt;
 use tonic::{Request, Response, Status};
 use tracing::info;
@@ -27,6 +31,9 @@ pub struct AuthGrpcServiceImpl {
     pub login_service: DynLoginService,
     pub register_service: DynRegisterService,
     pub password_reset_service: DynPasswordResetService,
+    pub lockout_service: DynLockoutService,
+    pub session_service: DynSessionService,
+    pub email_normalizer: EmailNormalizer,
 }
 
 impl fmt::Debug for AuthGrpcServiceImpl {
@@ -36,6 +43,9 @@ impl fmt::Debug for AuthGrpcServiceImpl {
             .field("login_service", &"DynLoginService")
             .field("register_service", &"DynRegisterService")
             .field("password_reset_service", &"DynPasswordResetService")
+            .field("lockout_service", &"DynLockoutService")
+            .field("session_service", &"DynSessionService")
+            .field("email_normalizer", &self.email_normalizer)
             .finish()
     }
 }
@@ -46,6 +56,9 @@ pub struct AuthGrpcServiceDeps {
     pub login_service: DynLoginService,
     pub register_service: DynRegisterService,
     pub password_reset_service: DynPasswordResetService,
+    pub lockout_service: DynLockoutService,
+    pub session_service: DynSessionService,
+    pub email_normalizer: EmailNormalizer,
 }
 
 impl AuthGrpcServiceImpl {
@@ -55,6 +68,9 @@ impl AuthGrpcServiceImpl {
             login_service,
             register_service,
             password_reset_service,
+            lockout_service,
+            session_service,
+            email_normalizer,
         } = deps;
 
         Self {
@@ -62,6 +78,9 @@ impl AuthGrpcServiceImpl {
             login_service,
             register_service,
             password_reset_service,
+            lockout_service,
+            session_service,
+            email_normalizer,
         }
     }
 }
@@ -101,10 +120,11 @@ impl AuthService for AuthGrpcServiceImpl {
         );
 
         let req = request.into_inner();
+        let email = self.email_normalizer.normalize(&req.email);
 
         let api_response = self
             .password_reset_service
-            .forgot(&req.email)
+            .forgot(&email)
             .await
             .map_err(AppErrorGrpc::from)?;
 
@@ -117,6 +137,33 @@ impl AuthService for AuthGrpcServiceImpl {
         Ok(Response::new(reply))
     }
 
+    async fn resend_verification(
+        &self,
+        request: Request<ResendVerificationRequest>,
+    ) -> Result<Response<ApiResponseResendVerification>, Status> {
+        info!(
+            "Handling verification resend for email: {}",
+            request.get_ref().email
+        );
+
+        let req = request.into_inner();
+        let email = self.email_normalizer.normalize(&req.email);
+
+        let api_response = self
+            .register_service
+            .resend_verification(&email)
+            .await
+            .map_err(AppErrorGrpc::from)?;
+
+        let reply = ApiResponseResendVerification {
+            status: "success".into(),
+            message: api_response.message,
+        };
+
+        info!("Verification resend handled");
+        Ok(Response::new(reply))
+    }
+
     async fn reset_password(
         &self,
         request: Request<ResetPasswordRequest>,
@@ -157,7 +204,7 @@ impl AuthService for AuthGrpcServiceImpl {
         let domain_req = DomainRegisterRequest {
             first_name: req.firstname,
             last_name: req.lastname,
-            email: req.email,
+            email: self.email_normalizer.normalize(&req.email),
             password: req.password,
             confirm_password: req.confirm_password,
         };
@@ -187,8 +234,9 @@ impl AuthService for AuthGrpcServiceImpl {
         let req = request.into_inner();
 
         let domain_req = DomainLoginRequest {
-            email: req.email,
+            email: self.email_normalizer.normalize(&req.email),
             password: req.password,
+            user_agent: Some(req.user_agent).filter(|ua| !ua.is_empty()),
         };
 
         let api_response = self
@@ -254,4 +302,292 @@ impl AuthService for AuthGrpcServiceImpl {
         info!("User fetched successfully");
         Ok(Response::new(reply))
     }
+
+    async fn get_lockout_status(
+        &self,
+        request: Request<UserLockoutRequest>,
+    ) -> Result<Response<ApiResponseLockoutStatus>, Status> {
+        let req = request.into_inner();
+
+        info!("Getting lockout status for user {}", req.user_id);
+
+        let api_response = self
+            .lockout_service
+            .get_status(req.user_id)
+            .await
+            .map_err(AppErrorGrpc::from)?;
+
+        let reply = ApiResponseLockoutStatus {
+            status: api_response.status,
+            message: api_response.message,
+            data: Some(api_response.data.into()),
+        };
+
+        Ok(Response::new(reply))
+    }
+
+    async fn unlock_account(
+        &self,
+        request: Request<UserLockoutRequest>,
+    ) -> Result<Response<ApiResponseLockoutStatus>, Status> {
+        let req = request.into_inner();
+
+        info!("Unlocking account for user {}", req.user_id);
+
+        let api_response = self
+            .lockout_service
+            .unlock(req.user_id)
+            .await
+            .map_err(AppErrorGrpc::from)?;
+
+        let reply = ApiResponseLockoutStatus {
+            status: api_response.status,
+            message: api_response.message,
+            data: Some(api_response.data.into()),
+        };
+
+        info!("Account unlocked for user {}", req.user_id);
+        Ok(Response::new(reply))
+    }
+
+    async fn list_sessions(
+        &self,
+        request: Request<ListSessionsRequest>,
+    ) -> Result<Response<ApiResponseSessions>, Status> {
+        let req = request.into_inner();
+
+        info!("Listing sessions for user {}", req.user_id);
+
+        let api_response = self
+            .session_service
+            .list_sessions(req.user_id)
+            .await
+            .map_err(AppErrorGrpc::from)?;
+
+        let reply = ApiResponseSessions {
+            status: api_response.status,
+            message: api_response.message,
+            data: api_response.data.into_iter().map(Into::into).collect(),
+        };
+
+        Ok(Response::new(reply))
+    }
+
+    async fn revoke_session(
+        &self,
+        request: Request<RevokeSessionRequest>,
+    ) -> Result<Response<ApiResponseRevokeSession>, Status> {
+        let req = request.into_inner();
+
+        info!(
+            "Revoking session {} for user {}",
+            req.session_id, req.user_id
+        );
+
+        let api_response = self
+            .session_service
+            .revoke_session(req.user_id, req.session_id)
+            .await
+            .map_err(AppErrorGrpc::from)?;
+
+        let reply = ApiResponseRevokeSession {
+            status: api_response.status,
+            message: api_response.message,
+        };
+
+        info!(
+            "Session {} revoked for user {}",
+            req.session_id, req.user_id
+        );
+        Ok(Response::new(reply))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        abstract_trait::auth::{
+            IdentityServiceTrait, LockoutServiceTrait, LoginServiceTrait, PasswordServiceTrait,
+            RegisterServiceTrait, SessionServiceTrait,
+        },
+        domain::response::{
+            api::ApiResponse, lockout::LockoutStatusResponse, session::SessionResponse,
+            token::TokenResponse, user::UserResponse,
+        },
+    };
+    use async_trait::async_trait;
+    use shared::errors::ServiceError;
+    use std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+    };
+
+    /// Stores accounts by the exact email it is given, like the unique
+    /// `users.email` column does.
+    #[derive(Default)]
+    struct Accounts {
+        by_email: Mutex<HashMap<String, i32>>,
+    }
+
+    #[async_trait]
+    impl RegisterServiceTrait for Accounts {
+        async fn register(
+            &self,
+            request: &DomainRegisterRequest,
+        ) -> Result<ApiResponse<UserResponse>, ServiceError> {
+            let mut accounts = self.by_email.lock().unwrap();
+            let id = accounts.len() as i32 + 1;
+            accounts.insert(request.email.clone(), id);
+
+            Ok(ApiResponse {
+                status: "success".into(),
+                message: "registered".into(),
+                data: UserResponse {
+                    id,
+                    external_id: String::new(),
+                    firstname: request.first_name.clone(),
+                    lastname: request.last_name.clone(),
+                    email: request.email.clone(),
+                    created_at: None,
+                    updated_at: None,
+                    roles: Vec::new(),
+                },
+            })
+        }
+
+        async fn resend_verification(&self, _: &str) -> Result<ApiResponse<bool>, ServiceError> {
+            unimplemented!()
+        }
+    }
+
+    #[async_trait]
+    impl LoginServiceTrait for Accounts {
+        async fn login(
+            &self,
+            request: &DomainLoginRequest,
+        ) -> Result<ApiResponse<TokenResponse>, ServiceError> {
+            let id = self
+                .by_email
+                .lock()
+                .unwrap()
+                .get(&request.email)
+                .copied()
+                .ok_or(ServiceError::InvalidCredentials)?;
+
+            Ok(ApiResponse {
+                status: "success".into(),
+                message: "logged in".into(),
+                data: TokenResponse {
+                    access_token: id.to_string(),
+                    refresh_token: String::new(),
+                    roles: Vec::new(),
+                },
+            })
+        }
+    }
+
+    #[async_trait]
+    impl IdentityServiceTrait for Accounts {
+        async fn refresh_token(&self, _: &str) -> Result<ApiResponse<TokenResponse>, ServiceError> {
+            unimplemented!()
+        }
+
+        async fn get_me(&self, _: i32) -> Result<ApiResponse<Option<UserResponse>>, ServiceError> {
+            unimplemented!()
+        }
+    }
+
+    #[async_trait]
+    impl PasswordServiceTrait for Accounts {
+        async fn forgot(&self, _: &str) -> Result<ApiResponse<bool>, ServiceError> {
+            unimplemented!()
+        }
+
+        async fn reset_password(
+            &self,
+            _: &CreateResetPasswordRequest,
+        ) -> Result<ApiResponse<bool>, ServiceError> {
+            unimplemented!()
+        }
+
+        async fn verify_code(&self, _: &str) -> Result<ApiResponse<bool>, ServiceError> {
+            unimplemented!()
+        }
+    }
+
+    #[async_trait]
+    impl LockoutServiceTrait for Accounts {
+        async fn get_status(
+            &self,
+            _: i32,
+        ) -> Result<ApiResponse<LockoutStatusResponse>, ServiceError> {
+            unimplemented!()
+        }
+
+        async fn unlock(&self, _: i32) -> Result<ApiResponse<LockoutStatusResponse>, ServiceError> {
+            unimplemented!()
+        }
+    }
+
+    #[async_trait]
+    impl SessionServiceTrait for Accounts {
+        async fn list_sessions(
+            &self,
+            _: i32,
+        ) -> Result<ApiResponse<Vec<SessionResponse>>, ServiceError> {
+            unimplemented!()
+        }
+
+        async fn revoke_session(&self, _: i32, _: i32) -> Result<ApiResponse<bool>, ServiceError> {
+            unimplemented!()
+        }
+    }
+
+    fn service(accounts: Arc<Accounts>) -> AuthGrpcServiceImpl {
+        AuthGrpcServiceImpl::new(AuthGrpcServiceDeps {
+            identity_service: accounts.clone(),
+            login_service: accounts.clone(),
+            register_service: accounts.clone(),
+            password_reset_service: accounts.clone(),
+            lockout_service: accounts.clone(),
+            session_service: accounts,
+            email_normalizer: EmailNormalizer::default(),
+        })
+    }
+
+    #[tokio::test]
+    async fn login_finds_account_registered_with_different_casing() {
+        let service = service(Arc::new(Accounts::default()));
+
+        let registered = service
+            .register_user(Request::new(RegisterRequest {
+                firstname: "Jane".into(),
+                lastname: "Doe".into(),
+                email: " User@Example.COM ".into(),
+                password: "correct horse battery".into(),
+                confirm_password: "correct horse battery".into(),
+            }))
+            .await
+            .expect("register")
+            .into_inner()
+            .data
+            .expect("registered user");
+
+        assert_eq!(registered.email, "user@example.com");
+
+        let token = service
+            .login_user(Request::new(LoginRequest {
+                email: "user@example.com".into(),
+                password: "correct horse battery".into(),
+                user_agent: String::new(),
+            }))
+            .await
+            .expect("login")
+            .into_inner()
+            .data
+            .expect("token");
+
+        assert_eq!(token.access_token, registered.id.to_string());
+    }
 }
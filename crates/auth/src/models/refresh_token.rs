@@ -11,4 +11,22 @@ pub struct RefreshToken {
     pub created_at: Option<NaiveDateTime>,
     pub updated_at: Option<NaiveDateTime>,
     pub deleted_at: Option<NaiveDateTime>,
+    /// Set once the token has been exchanged; presenting it again is reuse.
+    pub used_at: Option<NaiveDateTime>,
+    /// Captured at login and carried along each rotation.
+    pub user_agent: Option<String>,
+    pub session_started_at: Option<NaiveDateTime>,
+}
+
+/// What became of a refresh token presented for rotation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RefreshTokenRotation {
+    /// It was active; it is now rotated out and its successor is stored.
+    Rotated,
+    /// Its session outlived the maximum age, so it was deleted instead.
+    SessionExpired,
+    /// It had already been rotated out, so it has leaked.
+    Reused,
+    /// No live token of the user matches.
+    Unknown,
 }
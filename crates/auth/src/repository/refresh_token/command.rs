@@ -1,9 +1,10 @@
 use crate::{
     abstract_trait::refresh_token::RefreshTokenCommandRepositoryTrait,
     domain::requests::refresh_token::{CreateRefreshToken, UpdateRefreshToken},
-    models::refresh_token::RefreshToken as RefreshTokenModel,
+    models::refresh_token::{RefreshToken as RefreshTokenModel, RefreshTokenRotation},
 };
 use async_trait::async_trait;
+use chrono::NaiveDateTime;
 use shared::{config::ConnectionPool, errors::RepositoryError, utils::parse_expiration_datetime};
 use tracing::{error, info};
 
@@ -36,13 +37,21 @@ impl RefreshTokenCommandRepositoryTrait for RefreshTokenCommandRepository {
         let result = sqlx::query_as!(
             RefreshTokenModel,
             r#"
-            INSERT INTO refresh_tokens (user_id, token, expiration, created_at, updated_at)
-            VALUES ($1, $2, $3, current_timestamp, current_timestamp)
-            RETURNING refresh_token_id, user_id, token, expiration, created_at, updated_at, deleted_at
+            INSERT INTO refresh_tokens (
+                user_id, token, expiration, user_agent, session_started_at, created_at, updated_at
+            )
+            VALUES (
+                $1, $2, $3, $4, COALESCE($5::TIMESTAMP, current_timestamp), current_timestamp,
+                current_timestamp
+            )
+            RETURNING refresh_token_id, user_id, token, expiration, created_at, updated_at, deleted_at,
+                used_at, user_agent, session_started_at
             "#,
             request.user_id,
             request.token,
             expired_at,
+            request.user_agent,
+            request.session_started_at,
         )
         .fetch_one(&mut *conn)
         .await
@@ -74,8 +83,9 @@ impl RefreshTokenCommandRepositoryTrait for RefreshTokenCommandRepository {
             r#"
             UPDATE refresh_tokens
             SET token = $2, expiration = $3, updated_at = current_timestamp
-            WHERE user_id = $1 AND deleted_at IS NULL
-            RETURNING refresh_token_id, user_id, token, expiration, created_at, updated_at, deleted_at
+            WHERE user_id = $1 AND deleted_at IS NULL AND used_at IS NULL
+            RETURNING refresh_token_id, user_id, token, expiration, created_at, updated_at, deleted_at,
+                used_at, user_agent, session_started_at
             "#,
             request.user_id,
             request.token,
@@ -95,6 +105,127 @@ impl RefreshTokenCommandRepositoryTrait for RefreshTokenCommandRepository {
         Ok(updated)
     }
 
+    async fn rotate(
+        &self,
+        token: String,
+        next: &UpdateRefreshToken,
+        session_started_after: NaiveDateTime,
+    ) -> Result<RefreshTokenRotation, RepositoryError> {
+        let expired_at = match parse_expiration_datetime(&next.expired_date) {
+            Ok(datetime) => datetime,
+            Err(e) => {
+                error!("Failed to parse datetime: {e}");
+                return Err(RepositoryError::Custom("Invalid datetime format".into()));
+            }
+        };
+
+        let mut tx = self.db.begin().await.map_err(RepositoryError::from)?;
+
+        let presented = sqlx::query_as!(
+            RefreshTokenModel,
+            r#"
+            SELECT refresh_token_id, user_id, token, expiration, created_at, updated_at, deleted_at,
+                used_at, user_agent, session_started_at
+            FROM refresh_tokens
+            WHERE token = $1 AND user_id = $2 AND deleted_at IS NULL
+            FOR UPDATE
+            "#,
+            token,
+            next.user_id
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| {
+            error!("Failed to lock refresh token for rotation: {}", e);
+            RepositoryError::from(e)
+        })?;
+
+        let Some(presented) = presented else {
+            return Ok(RefreshTokenRotation::Unknown);
+        };
+
+        if presented.used_at.is_some() {
+            return Ok(RefreshTokenRotation::Reused);
+        }
+
+        if presented
+            .session_started_at
+            .is_some_and(|started_at| started_at < session_started_after)
+        {
+            sqlx::query_as!(
+                RefreshTokenModel,
+                r#"
+            DELETE FROM refresh_tokens
+            WHERE refresh_token_id = $1 AND user_id = $2 AND used_at IS NULL
+            RETURNING refresh_token_id, user_id, token, expiration, created_at, updated_at, deleted_at,
+                used_at, user_agent, session_started_at
+            "#,
+                presented.refresh_token_id,
+                presented.user_id
+            )
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| {
+                error!("Failed to end expired session: {}", e);
+                RepositoryError::from(e)
+            })?;
+
+            tx.commit().await.map_err(RepositoryError::from)?;
+
+            info!("Ended expired session for user_id={}", next.user_id);
+            return Ok(RefreshTokenRotation::SessionExpired);
+        }
+
+        sqlx::query_as!(
+            RefreshTokenModel,
+            r#"
+            UPDATE refresh_tokens
+            SET used_at = current_timestamp, updated_at = current_timestamp
+            WHERE token = $1 AND deleted_at IS NULL AND used_at IS NULL
+            RETURNING refresh_token_id, user_id, token, expiration, created_at, updated_at, deleted_at,
+                used_at, user_agent, session_started_at
+            "#,
+            token
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| {
+            error!("Failed to mark refresh token as used: {}", e);
+            RepositoryError::from(e)
+        })?;
+
+        sqlx::query_as!(
+            RefreshTokenModel,
+            r#"
+            INSERT INTO refresh_tokens (
+                user_id, token, expiration, user_agent, session_started_at, created_at, updated_at
+            )
+            VALUES (
+                $1, $2, $3, $4, COALESCE($5::TIMESTAMP, current_timestamp), current_timestamp,
+                current_timestamp
+            )
+            RETURNING refresh_token_id, user_id, token, expiration, created_at, updated_at, deleted_at,
+                used_at, user_agent, session_started_at
+            "#,
+            next.user_id,
+            next.token,
+            expired_at,
+            presented.user_agent,
+            presented.session_started_at,
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| {
+            error!("Failed to store rotated refresh token: {}", e);
+            RepositoryError::from(e)
+        })?;
+
+        tx.commit().await.map_err(RepositoryError::from)?;
+
+        info!("Rotated refresh token for user_id={}", next.user_id);
+        Ok(RefreshTokenRotation::Rotated)
+    }
+
     async fn delete_token(&self, token: String) -> Result<(), RepositoryError> {
         let mut conn = self.db.acquire().await.map_err(RepositoryError::from)?;
 
@@ -120,6 +251,42 @@ impl RefreshTokenCommandRepositoryTrait for RefreshTokenCommandRepository {
         Ok(())
     }
 
+    async fn delete_session(
+        &self,
+        user_id: i32,
+        refresh_token_id: i32,
+    ) -> Result<RefreshTokenModel, RepositoryError> {
+        let mut conn = self.db.acquire().await.map_err(RepositoryError::from)?;
+
+        let deleted = sqlx::query_as!(
+            RefreshTokenModel,
+            r#"
+            DELETE FROM refresh_tokens
+            WHERE refresh_token_id = $1 AND user_id = $2 AND used_at IS NULL
+            RETURNING refresh_token_id, user_id, token, expiration, created_at, updated_at, deleted_at,
+                used_at, user_agent, session_started_at
+            "#,
+            refresh_token_id,
+            user_id
+        )
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| {
+            error!(
+                "Failed to revoke session {} for user_id={}: {}",
+                refresh_token_id, user_id, e
+            );
+            RepositoryError::from(e)
+        })?
+        .ok_or(RepositoryError::NotFound)?;
+
+        info!(
+            "Revoked session {} for user_id={}",
+            refresh_token_id, user_id
+        );
+        Ok(deleted)
+    }
+
     async fn delete_by_user_id(&self, user_id: i32) -> Result<(), RepositoryError> {
         let mut conn = self.db.acquire().await.map_err(RepositoryError::from)?;
 
@@ -144,4 +311,75 @@ impl RefreshTokenCommandRepositoryTrait for RefreshTokenCommandRepository {
         info!("Deleted all refresh tokens for user_id={}", user_id);
         Ok(())
     }
+
+    async fn purge_stale(&self, limit: i64) -> Result<u64, RepositoryError> {
+        let mut conn = self.db.acquire().await.map_err(RepositoryError::from)?;
+
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM refresh_tokens
+            WHERE refresh_token_id IN (
+                SELECT refresh_token_id FROM refresh_tokens
+                WHERE expiration < current_timestamp OR deleted_at IS NOT NULL
+                LIMIT $1
+            )
+            "#,
+            limit,
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| {
+            error!("Failed to purge stale refresh tokens: {}", e);
+            RepositoryError::from(e)
+        })?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RefreshTokenCommandRepository;
+    use crate::abstract_trait::refresh_token::RefreshTokenCommandRepositoryTrait;
+    use sqlx::PgPool;
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "needs a Postgres server at DATABASE_URL"]
+    async fn purging_removes_expired_and_revoked_tokens_in_batches(pool: PgPool) {
+        sqlx::raw_sql(
+            r#"
+            INSERT INTO refresh_tokens (user_id, token, expiration, deleted_at, used_at)
+            VALUES (1, 'live', current_timestamp + INTERVAL '1 day', NULL, NULL),
+                   (1, 'rotated', current_timestamp + INTERVAL '1 day', NULL, current_timestamp),
+                   (1, 'expired-1', current_timestamp - INTERVAL '1 minute', NULL, NULL),
+                   (1, 'expired-2', current_timestamp - INTERVAL '1 day', NULL, NULL),
+                   (2, 'expired-3', current_timestamp - INTERVAL '1 day', NULL, current_timestamp),
+                   (2, 'revoked-1', current_timestamp + INTERVAL '1 day', current_timestamp, NULL),
+                   (2, 'revoked-2', current_timestamp - INTERVAL '1 day', current_timestamp, NULL);
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let repo = RefreshTokenCommandRepository::new(pool.clone());
+        let mut batches = Vec::new();
+        loop {
+            let purged = repo.purge_stale(2).await.unwrap();
+            batches.push(purged);
+            if purged < 2 {
+                break;
+            }
+        }
+        assert_eq!(batches, [2, 2, 1]);
+
+        // Rotated-out tokens stay until they expire, so a replay is still
+        // recognised as reuse.
+        let remaining: Vec<String> =
+            sqlx::query_scalar("SELECT token FROM refresh_tokens ORDER BY token")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        assert_eq!(remaining, ["live", "rotated"]);
+    }
 }
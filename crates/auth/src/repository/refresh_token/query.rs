@@ -26,9 +26,10 @@ impl RefreshTokenQueryRepositoryTrait for RefreshTokenQueryRepository {
         let result = sqlx::query_as!(
             RefreshTokenModel,
             r#"
-            SELECT refresh_token_id, user_id, token, expiration, created_at, updated_at, deleted_at
+            SELECT refresh_token_id, user_id, token, expiration, created_at, updated_at, deleted_at,
+                used_at, user_agent, session_started_at
             FROM refresh_tokens
-            WHERE user_id = $1 AND deleted_at IS NULL
+            WHERE user_id = $1 AND deleted_at IS NULL AND used_at IS NULL
             ORDER BY created_at DESC
             LIMIT 1
             "#,
@@ -41,6 +42,31 @@ impl RefreshTokenQueryRepositoryTrait for RefreshTokenQueryRepository {
         Ok(result)
     }
 
+    async fn find_active_by_user_id(
+        &self,
+        user_id: i32,
+    ) -> Result<Vec<RefreshTokenModel>, RepositoryError> {
+        let mut conn = self.db.acquire().await.map_err(RepositoryError::from)?;
+
+        let result = sqlx::query_as!(
+            RefreshTokenModel,
+            r#"
+            SELECT refresh_token_id, user_id, token, expiration, created_at, updated_at, deleted_at,
+                used_at, user_agent, session_started_at
+            FROM refresh_tokens
+            WHERE user_id = $1 AND deleted_at IS NULL AND used_at IS NULL
+                AND expiration > current_timestamp
+            ORDER BY created_at DESC
+            "#,
+            user_id
+        )
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(result)
+    }
+
     async fn find_by_token(
         &self,
         token: String,
@@ -50,7 +76,8 @@ impl RefreshTokenQueryRepositoryTrait for RefreshTokenQueryRepository {
         let result = sqlx::query_as!(
             RefreshTokenModel,
             r#"
-            SELECT refresh_token_id, user_id, token, expiration, created_at, updated_at, deleted_at
+            SELECT refresh_token_id, user_id, token, expiration, created_at, updated_at, deleted_at,
+                used_at, user_agent, session_started_at
             FROM refresh_tokens
             WHERE token = $1 AND deleted_at IS NULL
             "#,
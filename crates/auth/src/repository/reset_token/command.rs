@@ -97,4 +97,125 @@ impl ResetTokenCommandRepositoryTrait for ResetTokenCommandRepository {
 
         Ok(())
     }
+
+    /// Deletes the user's reset tokens except the `keep` newest unexpired
+    /// ones, so older links stop working once a new one is issued.
+    async fn prune_reset_tokens(&self, user_id: i32, keep: i64) -> Result<u64, RepositoryError> {
+        let mut conn = self.db.acquire().await.map_err(|e| {
+            error!("❌ Failed to acquire DB connection: {:?}", e);
+            RepositoryError::from(e)
+        })?;
+
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM reset_tokens
+            WHERE user_id = $1
+              AND id NOT IN (
+                  SELECT id
+                  FROM reset_tokens
+                  WHERE user_id = $1
+                    AND expiry_date > current_timestamp
+                  ORDER BY id DESC
+                  LIMIT $2
+              )
+            "#,
+            user_id,
+            keep
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(|err| {
+            error!(
+                "❌ Error pruning reset tokens for user_id {}: {:?}",
+                user_id, err
+            );
+            RepositoryError::from(err)
+        })?;
+
+        if result.rows_affected() > 0 {
+            info!(
+                "🧹 Invalidated {} older reset token(s) for user_id {}",
+                result.rows_affected(),
+                user_id
+            );
+        }
+
+        Ok(result.rows_affected())
+    }
+
+    async fn delete_expired(&self, limit: i64) -> Result<u64, RepositoryError> {
+        let mut conn = self.db.acquire().await.map_err(RepositoryError::from)?;
+
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM reset_tokens
+            WHERE id IN (
+                SELECT id FROM reset_tokens
+                WHERE expiry_date < current_timestamp
+                LIMIT $1
+            )
+            "#,
+            limit,
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| {
+            error!("❌ Failed to delete expired reset tokens: {:?}", e);
+            RepositoryError::from(e)
+        })?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ResetTokenCommandRepository;
+    use crate::{
+        abstract_trait::reset_token::{
+            ResetTokenCommandRepositoryTrait, ResetTokenQueryRepositoryTrait,
+        },
+        domain::requests::reset_token::CreateResetTokenRequest,
+        repository::reset_token::query::ResetTokenQueryRepository,
+    };
+    use chrono::{Duration, Utc};
+    use sqlx::PgPool;
+
+    /// Issues `token` the way a forgot-password request does: store it,
+    /// then prune down to `keep`.
+    async fn issue(repo: &ResetTokenCommandRepository, user_id: i32, token: &str, keep: i64) {
+        repo.create_reset_token(&CreateResetTokenRequest {
+            user_id,
+            reset_token: token.to_string(),
+            expired_at: (Utc::now() + Duration::hours(24))
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string(),
+        })
+        .await
+        .unwrap();
+        repo.prune_reset_tokens(user_id, keep).await.unwrap();
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "needs a Postgres server at DATABASE_URL"]
+    async fn a_new_reset_token_invalidates_the_previous_one(pool: PgPool) {
+        let command = ResetTokenCommandRepository::new(pool.clone());
+        let query = ResetTokenQueryRepository::new(pool);
+        let works = async |token: &str| query.find_by_token(token).await.unwrap().is_some();
+
+        issue(&command, 1, "first", 1).await;
+        issue(&command, 2, "other-user", 1).await;
+        issue(&command, 1, "second", 1).await;
+
+        assert!(!works("first").await);
+        assert!(works("second").await);
+        assert!(works("other-user").await, "other users keep theirs");
+
+        // A larger cap keeps that many of the newest.
+        issue(&command, 1, "third", 2).await;
+        issue(&command, 1, "fourth", 2).await;
+        assert!(!works("second").await);
+        assert!(works("third").await);
+        assert!(works("fourth").await);
+    }
 }
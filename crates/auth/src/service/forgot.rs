@@ -23,9 +23,9 @@ use opentelemetry::{
     trace::{Span, SpanKind, TraceContextExt, Tracer},
 };
 use shared::{
-    abstract_trait::DynKafka,
+    abstract_trait::{DynKafka, DynPasswordBreachChecker},
     cache::CacheStore,
-    errors::{AppErrorGrpc, ServiceError},
+    errors::{AppErrorGrpc, RepositoryError, ServiceError},
     utils::{
         EmailTemplateData, MetadataInjector, Method, Metrics, Status as StatusUtils,
         TracingContext, generate_random_string,
@@ -42,6 +42,10 @@ pub struct PasswordResetServiceDeps {
     pub user_client: DynUserGrpcClient,
     pub kafka: DynKafka,
     pub cache_store: Arc<CacheStore>,
+    pub password_breach: DynPasswordBreachChecker,
+    /// Unexpired reset tokens a user may hold; older ones are invalidated
+    /// when a new one is issued.
+    pub max_reset_tokens: i64,
 }
 
 #[derive(Clone)]
@@ -52,6 +56,8 @@ pub struct PasswordResetService {
     kafka: DynKafka,
     metrics: Metrics,
     cache_store: Arc<CacheStore>,
+    password_breach: DynPasswordBreachChecker,
+    max_reset_tokens: i64,
 }
 
 impl PasswordResetService {
@@ -64,6 +70,8 @@ impl PasswordResetService {
             user_client,
             kafka,
             cache_store,
+            password_breach,
+            max_reset_tokens,
         } = deps;
 
         Ok(Self {
@@ -73,6 +81,8 @@ impl PasswordResetService {
             kafka,
             metrics,
             cache_store,
+            password_breach,
+            max_reset_tokens,
         })
     }
 
@@ -232,7 +242,7 @@ impl PasswordServiceTrait for PasswordResetService {
             }
         };
 
-        let expires_at = (Utc::now() + Duration::hours(24)).naive_utc();
+        let expires_at = (Utc::now() + Duration::hours(24)).format("%Y-%m-%d %H:%M:%S");
 
         let request_token = CreateResetTokenRequest {
             user_id: user_response.data.id,
@@ -251,6 +261,17 @@ impl PasswordServiceTrait for PasswordResetService {
             return Err(ServiceError::Repo(e));
         }
 
+        if let Err(e) = self
+            .reset_token_command
+            .prune_reset_tokens(request_token.user_id, self.max_reset_tokens)
+            .await
+        {
+            error!("❌ Failed to invalidate older reset tokens: {:?}", e);
+            self.complete_tracing_error(&tracing_ctx, method, "Failed to save reset token")
+                .await;
+            return Err(ServiceError::Repo(e));
+        }
+
         self.cache_store
             .set_to_cache(&cache_key, &true, Duration::minutes(1))
             .await;
@@ -360,6 +381,13 @@ impl PasswordServiceTrait for PasswordResetService {
             return Err(ServiceError::Custom(msg.to_string()));
         }
 
+        if self.password_breach.is_breached(&data.password).await {
+            let msg = "Password has appeared in a known data breach, please choose a different one";
+            warn!("❌ {msg}");
+            self.complete_tracing_error(&tracing_ctx, method, msg).await;
+            return Err(ServiceError::Custom(msg.to_string()));
+        }
+
         self.user_client
             .update_user_password(UpdateUserPasswordRequest {
                 user_id: reset_token_model.user_id as i32,
@@ -371,6 +399,17 @@ impl PasswordServiceTrait for PasswordResetService {
                 Self::grpc_status_to_service_error(err.into())
             })?;
 
+        // The password changed, so none of the user's reset links should
+        // work anymore, not just the one that was used.
+        match self
+            .reset_token_command
+            .delete_reset_token(reset_token_model.user_id)
+            .await
+        {
+            Ok(()) | Err(RepositoryError::NotFound) => {}
+            Err(e) => warn!("⚠️ Failed to delete used reset tokens: {:?}", e),
+        }
+
         self.cache_store
             .set_to_cache(&cache_key, &true, Duration::hours(24))
             .await;
@@ -4,10 +4,7 @@ use crate::{
         grpc_client::user::DynUserGrpcClient,
         refresh_token::DynRefreshTokenCommandRepository,
     },
-    domain::{
-        requests::refresh_token::UpdateRefreshToken,
-        response::{api::ApiResponse, token::TokenResponse, user::UserResponse},
-    },
+    domain::response::{api::ApiResponse, token::TokenResponse, user::UserResponse},
 };
 use anyhow::Result;
 use async_trait::async_trait;
@@ -196,21 +193,6 @@ impl IdentityServiceTrait for IdentityService {
             }
         };
 
-        if let Err(e) = self
-            .refresh_token_command
-            .delete_token(token.to_string())
-            .await
-        {
-            error!("❌ Failed to delete old refresh token: {:?}", e);
-            self.complete_tracing_error(
-                &tracing_ctx,
-                method.clone(),
-                "Failed to delete old refresh token",
-            )
-            .await;
-            return Err(ServiceError::from(e));
-        }
-
         let access_token = match self.token_service.create_access_token(user_id as i32).await {
             Ok(token) => token,
             Err(e) => {
@@ -227,44 +209,27 @@ impl IdentityServiceTrait for IdentityService {
 
         let refresh_token = match self
             .token_service
-            .create_refresh_token(user_id as i32)
+            .rotate_refresh_token(token, user_id as i32)
             .await
         {
             Ok(token) => token,
             Err(e) => {
-                error!("❌ Failed to generate refresh token: {:?}", e);
-                self.complete_tracing_error(
-                    &tracing_ctx,
-                    method.clone(),
-                    "Failed to generate refresh token",
-                )
-                .await;
+                self.complete_tracing_error(&tracing_ctx, method.clone(), &e.to_string())
+                    .await;
                 return Err(e);
             }
         };
 
-        let expiry = chrono::Utc::now() + chrono::Duration::hours(24);
-
-        let update_req = &UpdateRefreshToken {
-            user_id: user_id as i32,
-            token: refresh_token.clone(),
-            expired_date: expiry.naive_utc().to_string(),
-        };
-
-        if let Err(e) = self.refresh_token_command.update(update_req).await {
-            error!("❌ Failed to update refresh token: {:?}", e);
-            self.complete_tracing_error(&tracing_ctx, method, "Failed to update refresh token")
-                .await;
-            return Err(ServiceError::Internal(
-                "Failed to update refresh token".into(),
-            ));
-        }
+        let _ = self
+            .cache_store
+            .delete_from_cache(&format!("auth:refresh:{token}"))
+            .await;
 
         self.cache_store
             .set_to_cache(
                 &format!("auth:refresh:{refresh_token}"),
                 &user_id,
-                chrono::Duration::hours(24),
+                self.jwt.refresh_token_ttl(),
             )
             .await;
 
@@ -275,8 +240,9 @@ impl IdentityServiceTrait for IdentityService {
             status: "success".into(),
             message: "token refreshed".into(),
             data: TokenResponse {
-                access_token,
+                access_token: access_token.token,
                 refresh_token,
+                roles: access_token.roles,
             },
         })
     }
@@ -310,7 +276,7 @@ impl IdentityServiceTrait for IdentityService {
             });
         }
 
-        let user_response = match self.user_client.find_by_id(id).await {
+        let mut user_response = match self.user_client.find_by_id(id).await {
             Ok(resp) => resp,
             Err(err) => {
                 error!("❌ Failed to find verification code: {}", err);
@@ -318,6 +284,16 @@ impl IdentityServiceTrait for IdentityService {
             }
         };
 
+        user_response.data.roles = match self.token_service.role_names(id).await {
+            Ok(roles) => roles,
+            Err(e) => {
+                error!("❌ Failed to fetch roles for user {id}: {:?}", e);
+                self.complete_tracing_error(&tracing_ctx, method, "Failed to fetch roles")
+                    .await;
+                return Err(e);
+            }
+        };
+
         self.cache_store
             .set_to_cache(&cache_key, &user_response, Duration::minutes(30))
             .await;
@@ -0,0 +1,349 @@
+use crate::{
+    abstract_trait::{auth::LockoutServiceTrait, grpc_client::user::DynUserGrpcClient},
+    config::myconfig::LoginLockoutPolicy,
+    domain::response::{api::ApiResponse, lockout::LockoutStatusResponse},
+    service::login::login_attempts_key,
+};
+use async_trait::async_trait;
+use shared::{
+    cache::CacheStore,
+    errors::{AppErrorGrpc, ServiceError},
+};
+use std::sync::Arc;
+use tracing::{error, info};
+
+pub struct LockoutServiceDeps {
+    pub user_client: DynUserGrpcClient,
+    pub cache_store: Arc<CacheStore>,
+    pub policy: LoginLockoutPolicy,
+}
+
+/// Lets support staff inspect and clear the failed-login lockout that
+/// `LoginService` keeps per email.
+#[derive(Clone)]
+pub struct LockoutService {
+    user_client: DynUserGrpcClient,
+    cache_store: Arc<CacheStore>,
+    policy: LoginLockoutPolicy,
+}
+
+impl LockoutService {
+    pub fn new(deps: LockoutServiceDeps) -> Self {
+        let LockoutServiceDeps {
+            user_client,
+            cache_store,
+            policy,
+        } = deps;
+
+        Self {
+            user_client,
+            cache_store,
+            policy,
+        }
+    }
+
+    async fn find_email(&self, user_id: i32) -> Result<String, ServiceError> {
+        let user = self.user_client.find_by_id(user_id).await.map_err(|err| {
+            error!("❌ Failed to fetch user {user_id} for lockout: {err}");
+            match err {
+                AppErrorGrpc::Service(service_err) => service_err,
+                AppErrorGrpc::Unhandled(msg) => ServiceError::Internal(msg),
+            }
+        })?;
+
+        Ok(user.data.email)
+    }
+
+    async fn status_for(&self, user_id: i32, email: String) -> LockoutStatusResponse {
+        let failed_attempts = self
+            .cache_store
+            .peek::<i32>(&login_attempts_key(&email))
+            .await
+            .unwrap_or(0);
+
+        LockoutStatusResponse {
+            user_id,
+            email,
+            locked: failed_attempts >= self.policy.max_attempts,
+            failed_attempts,
+            max_attempts: self.policy.max_attempts,
+        }
+    }
+}
+
+#[async_trait]
+impl LockoutServiceTrait for LockoutService {
+    async fn get_status(
+        &self,
+        user_id: i32,
+    ) -> Result<ApiResponse<LockoutStatusResponse>, ServiceError> {
+        let email = self.find_email(user_id).await?;
+        let status = self.status_for(user_id, email).await;
+
+        Ok(ApiResponse {
+            status: "success".into(),
+            message: "Lockout status retrieved successfully".into(),
+            data: status,
+        })
+    }
+
+    async fn unlock(
+        &self,
+        user_id: i32,
+    ) -> Result<ApiResponse<LockoutStatusResponse>, ServiceError> {
+        let email = self.find_email(user_id).await?;
+
+        self.cache_store
+            .delete_from_cache(&login_attempts_key(&email))
+            .await;
+
+        info!("🔓 Cleared login lockout for user {user_id}");
+
+        let status = self.status_for(user_id, email).await;
+
+        Ok(ApiResponse {
+            status: "success".into(),
+            message: "Account unlocked successfully".into(),
+            data: status,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LockoutService, LockoutServiceDeps};
+    use crate::{
+        abstract_trait::{
+            auth::{LockoutServiceTrait, LoginServiceTrait, token::TokenServiceTrait},
+            grpc_client::user::UserGrpcClientTrait,
+        },
+        config::myconfig::LoginLockoutPolicy,
+        domain::{
+            requests::{
+                auth::AuthRequest,
+                user::{
+                    CreateUserRequest, UpdateUserPasswordRequest,
+                    UpdateUserVerificationCodeRequest, UpdateUserVerifiedRequest,
+                },
+            },
+            response::{
+                api::ApiResponse,
+                token::AccessToken,
+                user::{UserResponse, UserResponseWithPassword},
+            },
+        },
+        service::login::{LoginService, LoginServiceDeps},
+    };
+    use async_trait::async_trait;
+    use shared::{
+        abstract_trait::HashingTrait,
+        cache::CacheStore,
+        config::{Hashing, RedisConfig, RedisPool},
+        errors::{AppErrorGrpc, ServiceError},
+    };
+    use std::sync::Arc;
+
+    const USER_ID: i32 = 7;
+    const PASSWORD: &str = "correct-horse";
+
+    /// The single, verified account the user service knows.
+    struct OneUser {
+        email: String,
+        password_hash: String,
+    }
+
+    impl OneUser {
+        fn user(&self) -> UserResponse {
+            UserResponse {
+                id: USER_ID,
+                external_id: String::new(),
+                firstname: "Ada".into(),
+                lastname: "Lovelace".into(),
+                email: self.email.clone(),
+                created_at: None,
+                updated_at: None,
+                roles: Vec::new(),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl UserGrpcClientTrait for OneUser {
+        async fn create_user(
+            &self,
+            _req: CreateUserRequest,
+        ) -> Result<ApiResponse<UserResponse>, AppErrorGrpc> {
+            unimplemented!()
+        }
+
+        async fn update_user_is_verified(
+            &self,
+            _req: UpdateUserVerifiedRequest,
+        ) -> Result<ApiResponse<UserResponse>, AppErrorGrpc> {
+            unimplemented!()
+        }
+
+        async fn update_user_verification_code(
+            &self,
+            _req: UpdateUserVerificationCodeRequest,
+        ) -> Result<ApiResponse<UserResponse>, AppErrorGrpc> {
+            unimplemented!()
+        }
+
+        async fn update_user_password(
+            &self,
+            _req: UpdateUserPasswordRequest,
+        ) -> Result<ApiResponse<UserResponse>, AppErrorGrpc> {
+            unimplemented!()
+        }
+
+        async fn find_verification_code(
+            &self,
+            _code: String,
+        ) -> Result<ApiResponse<UserResponse>, AppErrorGrpc> {
+            unimplemented!()
+        }
+
+        async fn find_by_email(
+            &self,
+            _email: String,
+        ) -> Result<ApiResponse<UserResponse>, AppErrorGrpc> {
+            unimplemented!()
+        }
+
+        async fn find_by_email_and_verify(
+            &self,
+            _email: String,
+        ) -> Result<ApiResponse<UserResponseWithPassword>, AppErrorGrpc> {
+            let user = self.user();
+
+            Ok(ApiResponse {
+                status: "success".into(),
+                message: "User found".into(),
+                data: UserResponseWithPassword {
+                    id: user.id,
+                    external_id: user.external_id,
+                    firstname: user.firstname,
+                    lastname: user.lastname,
+                    email: user.email,
+                    password: self.password_hash.clone(),
+                    created_at: None,
+                    updated_at: None,
+                    is_verified: true,
+                },
+            })
+        }
+
+        async fn find_by_id(&self, id: i32) -> Result<ApiResponse<UserResponse>, AppErrorGrpc> {
+            assert_eq!(id, USER_ID);
+
+            Ok(ApiResponse {
+                status: "success".into(),
+                message: "User found".into(),
+                data: self.user(),
+            })
+        }
+    }
+
+    struct Tokens;
+
+    #[async_trait]
+    impl TokenServiceTrait for Tokens {
+        async fn create_access_token(&self, id: i32) -> Result<AccessToken, ServiceError> {
+            Ok(AccessToken {
+                token: format!("access-{id}"),
+                roles: vec!["ROLE_USER".into()],
+            })
+        }
+
+        async fn role_names(&self, _id: i32) -> Result<Vec<String>, ServiceError> {
+            unimplemented!()
+        }
+
+        async fn create_refresh_token(
+            &self,
+            id: i32,
+            _user_agent: Option<String>,
+        ) -> Result<String, ServiceError> {
+            Ok(format!("refresh-{id}"))
+        }
+
+        async fn rotate_refresh_token(
+            &self,
+            _token: &str,
+            _id: i32,
+        ) -> Result<String, ServiceError> {
+            unimplemented!()
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a Redis server at REDIS_HOST"]
+    async fn unlocking_a_locked_account_allows_an_immediate_login() {
+        // Unique per run, so counters left over in Redis don't leak in.
+        let email = format!(
+            "locked-{}@example.com",
+            chrono::Utc::now().timestamp_nanos_opt().unwrap()
+        );
+        let hash = Arc::new(Hashing::new());
+        let user_client = Arc::new(OneUser {
+            email: email.clone(),
+            password_hash: hash.hash_password(PASSWORD).await.unwrap(),
+        });
+        let cache_store = Arc::new(CacheStore::new(
+            RedisPool::new(&RedisConfig::new()).unwrap().pool,
+        ));
+        let policy = LoginLockoutPolicy {
+            max_attempts: 3,
+            lockout_minutes: 15,
+        };
+
+        let login = LoginService::new(LoginServiceDeps {
+            hash,
+            token_service: Arc::new(Tokens),
+            user_client: user_client.clone(),
+            cache_store: cache_store.clone(),
+            require_email_verification: false,
+            lockout_policy: policy,
+        })
+        .unwrap();
+        let lockout = LockoutService::new(LockoutServiceDeps {
+            user_client,
+            cache_store,
+            policy,
+        });
+        let attempt = |password: &str| AuthRequest {
+            email: email.clone(),
+            password: password.into(),
+            user_agent: None,
+        };
+
+        let status = lockout.get_status(USER_ID).await.unwrap().data;
+        assert!(!status.locked);
+        assert_eq!((status.failed_attempts, status.max_attempts), (0, 3));
+
+        for _ in 0..3 {
+            assert!(matches!(
+                login.login(&attempt("wrong")).await,
+                Err(ServiceError::InvalidCredentials)
+            ));
+        }
+
+        let status = lockout.get_status(USER_ID).await.unwrap().data;
+        assert!(status.locked);
+        assert_eq!(status.failed_attempts, 3);
+        assert_eq!(status.email, email);
+        assert!(matches!(
+            login.login(&attempt(PASSWORD)).await,
+            Err(ServiceError::TooManyRequests(_))
+        ));
+
+        let unlocked = lockout.unlock(USER_ID).await.unwrap().data;
+        assert!(!unlocked.locked);
+        assert_eq!(unlocked.failed_attempts, 0);
+
+        let tokens = login.login(&attempt(PASSWORD)).await.unwrap().data;
+        assert_eq!(tokens.access_token, "access-7");
+        assert!(!lockout.get_status(USER_ID).await.unwrap().data.locked);
+    }
+}
@@ -3,6 +3,7 @@ use crate::{
         auth::{LoginServiceTrait, token::DynTokenService},
         grpc_client::user::DynUserGrpcClient,
     },
+    config::myconfig::LoginLockoutPolicy,
     domain::{
         requests::auth::AuthRequest,
         response::{api::ApiResponse, token::TokenResponse},
@@ -32,6 +33,14 @@ pub struct LoginServiceDeps {
     pub token_service: DynTokenService,
     pub user_client: DynUserGrpcClient,
     pub cache_store: Arc<CacheStore>,
+    pub require_email_verification: bool,
+    pub lockout_policy: LoginLockoutPolicy,
+}
+
+/// Redis key counting failed logins for `email`; its presence past the
+/// policy's limit is what locks the account.
+pub fn login_attempts_key(email: &str) -> String {
+    format!("auth:login_attempts:{email}")
 }
 
 #[derive(Clone)]
@@ -41,6 +50,8 @@ pub struct LoginService {
     user_client: DynUserGrpcClient,
     metrics: Metrics,
     cache_store: Arc<CacheStore>,
+    require_email_verification: bool,
+    lockout_policy: LoginLockoutPolicy,
 }
 
 impl LoginService {
@@ -52,6 +63,8 @@ impl LoginService {
             token_service,
             user_client,
             cache_store,
+            require_email_verification,
+            lockout_policy,
         } = deps;
 
         Ok(Self {
@@ -60,6 +73,8 @@ impl LoginService {
             user_client,
             metrics,
             cache_store,
+            require_email_verification,
+            lockout_policy,
         })
     }
     fn get_tracer(&self) -> BoxedTracer {
@@ -182,20 +197,21 @@ impl LoginServiceTrait for LoginService {
         let mut request = Request::new(request.clone());
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        let attempts_key = format!("auth:login_attempts:{email}");
+        let attempts_key = login_attempts_key(email);
         let attempts = self
             .cache_store
             .get_from_cache::<i32>(&attempts_key)
             .await
             .unwrap_or(0);
 
-        if attempts >= 5 {
+        if attempts >= self.lockout_policy.max_attempts {
             let msg = "Too many failed login attempts (rate limited)";
             self.complete_tracing_error(&tracing_ctx, method, msg).await;
 
-            return Err(ServiceError::Custom(
-                "Too many failed attempts. Try again later.".into(),
-            ));
+            return Err(ServiceError::TooManyRequests(format!(
+                "Too many failed attempts. Try again in {} minutes.",
+                self.lockout_policy.lockout_minutes
+            )));
         }
 
         let user_response = self
@@ -215,7 +231,11 @@ impl LoginServiceTrait for LoginService {
             let new_attempts = attempts + 1;
 
             self.cache_store
-                .set_to_cache(&attempts_key, &new_attempts, Duration::minutes(15))
+                .set_to_cache(
+                    &attempts_key,
+                    &new_attempts,
+                    Duration::minutes(self.lockout_policy.lockout_minutes),
+                )
                 .await;
 
             self.complete_tracing_error(&tracing_ctx, method.clone(), "Invalid password")
@@ -226,6 +246,13 @@ impl LoginServiceTrait for LoginService {
 
         self.cache_store.delete_from_cache(&attempts_key).await;
 
+        if self.require_email_verification && !user.is_verified {
+            self.complete_tracing_error(&tracing_ctx, method.clone(), "Email not verified")
+                .await;
+
+            return Err(ServiceError::EmailNotVerified);
+        }
+
         let uid = user.id;
 
         let access_token = match self.token_service.create_access_token(uid).await {
@@ -241,7 +268,11 @@ impl LoginServiceTrait for LoginService {
             }
         };
 
-        let refresh_token = match self.token_service.create_refresh_token(uid).await {
+        let refresh_token = match self
+            .token_service
+            .create_refresh_token(uid, request.get_ref().user_agent.clone())
+            .await
+        {
             Ok(t) => t,
             Err(e) => {
                 self.complete_tracing_error(
@@ -255,8 +286,9 @@ impl LoginServiceTrait for LoginService {
         };
 
         let tokens = TokenResponse {
-            access_token,
+            access_token: access_token.token,
             refresh_token,
+            roles: access_token.roles,
         };
 
         self.complete_tracing_success(&tracing_ctx, method, "Login successful")
@@ -269,3 +301,203 @@ impl LoginServiceTrait for LoginService {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{LoginService, LoginServiceDeps};
+    use crate::{
+        abstract_trait::{
+            auth::{LoginServiceTrait, token::TokenServiceTrait},
+            grpc_client::user::UserGrpcClientTrait,
+        },
+        config::myconfig::LoginLockoutPolicy,
+        domain::{
+            requests::{
+                auth::AuthRequest,
+                user::{
+                    CreateUserRequest, UpdateUserPasswordRequest,
+                    UpdateUserVerificationCodeRequest, UpdateUserVerifiedRequest,
+                },
+            },
+            response::{
+                api::ApiResponse,
+                token::{AccessToken, TokenResponse},
+                user::{UserResponse, UserResponseWithPassword},
+            },
+        },
+    };
+    use async_trait::async_trait;
+    use shared::{
+        abstract_trait::HashingTrait,
+        cache::CacheStore,
+        config::{Hashing, RedisConfig, RedisPool},
+        errors::{AppErrorGrpc, ServiceError},
+    };
+    use std::sync::Arc;
+
+    const PASSWORD: &str = "correct-horse";
+
+    /// The single account the user service knows, with its verification
+    /// state.
+    struct OneUser {
+        password_hash: String,
+        is_verified: bool,
+    }
+
+    #[async_trait]
+    impl UserGrpcClientTrait for OneUser {
+        async fn create_user(
+            &self,
+            _req: CreateUserRequest,
+        ) -> Result<ApiResponse<UserResponse>, AppErrorGrpc> {
+            unimplemented!()
+        }
+
+        async fn update_user_is_verified(
+            &self,
+            _req: UpdateUserVerifiedRequest,
+        ) -> Result<ApiResponse<UserResponse>, AppErrorGrpc> {
+            unimplemented!()
+        }
+
+        async fn update_user_verification_code(
+            &self,
+            _req: UpdateUserVerificationCodeRequest,
+        ) -> Result<ApiResponse<UserResponse>, AppErrorGrpc> {
+            unimplemented!()
+        }
+
+        async fn update_user_password(
+            &self,
+            _req: UpdateUserPasswordRequest,
+        ) -> Result<ApiResponse<UserResponse>, AppErrorGrpc> {
+            unimplemented!()
+        }
+
+        async fn find_verification_code(
+            &self,
+            _code: String,
+        ) -> Result<ApiResponse<UserResponse>, AppErrorGrpc> {
+            unimplemented!()
+        }
+
+        async fn find_by_email(
+            &self,
+            _email: String,
+        ) -> Result<ApiResponse<UserResponse>, AppErrorGrpc> {
+            unimplemented!()
+        }
+
+        async fn find_by_email_and_verify(
+            &self,
+            email: String,
+        ) -> Result<ApiResponse<UserResponseWithPassword>, AppErrorGrpc> {
+            Ok(ApiResponse {
+                status: "success".into(),
+                message: "User found".into(),
+                data: UserResponseWithPassword {
+                    id: 7,
+                    external_id: String::new(),
+                    firstname: "Ada".into(),
+                    lastname: "Lovelace".into(),
+                    email,
+                    password: self.password_hash.clone(),
+                    created_at: None,
+                    updated_at: None,
+                    is_verified: self.is_verified,
+                },
+            })
+        }
+
+        async fn find_by_id(&self, _id: i32) -> Result<ApiResponse<UserResponse>, AppErrorGrpc> {
+            unimplemented!()
+        }
+    }
+
+    struct Tokens;
+
+    #[async_trait]
+    impl TokenServiceTrait for Tokens {
+        async fn create_access_token(&self, id: i32) -> Result<AccessToken, ServiceError> {
+            Ok(AccessToken {
+                token: format!("access-{id}"),
+                roles: vec!["ROLE_USER".into()],
+            })
+        }
+
+        async fn role_names(&self, _id: i32) -> Result<Vec<String>, ServiceError> {
+            unimplemented!()
+        }
+
+        async fn create_refresh_token(
+            &self,
+            id: i32,
+            _user_agent: Option<String>,
+        ) -> Result<String, ServiceError> {
+            Ok(format!("refresh-{id}"))
+        }
+
+        async fn rotate_refresh_token(
+            &self,
+            _token: &str,
+            _id: i32,
+        ) -> Result<String, ServiceError> {
+            unimplemented!()
+        }
+    }
+
+    async fn login(
+        is_verified: bool,
+        require_email_verification: bool,
+    ) -> Result<ApiResponse<TokenResponse>, ServiceError> {
+        let hash = Arc::new(Hashing::new());
+        let password_hash = hash.hash_password(PASSWORD).await.unwrap();
+
+        // Never connected: the lockout counter always reads as zero.
+        let cache_store = Arc::new(CacheStore::new(
+            RedisPool::new(&RedisConfig::new()).unwrap().pool,
+        ));
+
+        let service = LoginService::new(LoginServiceDeps {
+            hash,
+            token_service: Arc::new(Tokens),
+            user_client: Arc::new(OneUser {
+                password_hash,
+                is_verified,
+            }),
+            cache_store,
+            require_email_verification,
+            lockout_policy: LoginLockoutPolicy {
+                max_attempts: 5,
+                lockout_minutes: 15,
+            },
+        })
+        .unwrap();
+
+        service
+            .login(&AuthRequest {
+                email: "ada@example.com".into(),
+                password: PASSWORD.into(),
+                user_agent: None,
+            })
+            .await
+    }
+
+    #[tokio::test]
+    async fn unverified_users_are_refused_while_verification_is_required() {
+        assert!(matches!(
+            login(false, true).await,
+            Err(ServiceError::EmailNotVerified)
+        ));
+
+        let tokens = login(true, true).await.unwrap().data;
+        assert_eq!(tokens.access_token, "access-7");
+    }
+
+    #[tokio::test]
+    async fn unverified_users_sign_in_when_verification_is_optional() {
+        let tokens = login(false, false).await.unwrap().data;
+        assert_eq!(tokens.access_token, "access-7");
+        assert_eq!(tokens.refresh_token, "refresh-7");
+    }
+}
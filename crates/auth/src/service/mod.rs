@@ -1,5 +1,8 @@
 pub mod forgot;
 pub mod identity;
+pub mod lockout;
 pub mod login;
 pub mod register;
+pub mod session;
 pub mod token;
+pub mod token_sweeper;
@@ -1,8 +1,12 @@
 use crate::domain::requests::auth::RegisterRequest;
 use crate::{
     abstract_trait::{auth::RegisterServiceTrait, grpc_client::user::DynUserGrpcClient},
+    config::myconfig::{EmailDomainPolicy, VerificationResendPolicy},
     domain::{
-        requests::{email::EmailRequest, user::CreateUserRequest},
+        requests::{
+            email::EmailRequest,
+            user::{CreateUserRequest, UpdateUserVerificationCodeRequest},
+        },
         response::{api::ApiResponse, user::UserResponse},
     },
 };
@@ -16,9 +20,9 @@ use opentelemetry::{
 };
 use shared::errors::grpc_status_to_service_error;
 use shared::{
-    abstract_trait::DynKafka,
+    abstract_trait::{DynKafka, DynPasswordBreachChecker},
     cache::CacheStore,
-    errors::ServiceError,
+    errors::{RepositoryError, ServiceError},
     utils::{
         EmailTemplateData, MetadataInjector, Method, Metrics, Status as StatusUtils,
         TracingContext, generate_random_string,
@@ -33,6 +37,15 @@ pub struct RegisterServiceDeps {
     pub user_client: DynUserGrpcClient,
     pub kafka: DynKafka,
     pub cache_store: Arc<CacheStore>,
+    pub password_breach: DynPasswordBreachChecker,
+    pub resend_policy: VerificationResendPolicy,
+    pub email_domains: EmailDomainPolicy,
+}
+
+/// Redis key counting verification emails resent to `email` in the current
+/// window.
+fn resend_attempts_key(email: &str) -> String {
+    format!("auth:resend_verification:{email}")
 }
 
 #[derive(Clone)]
@@ -41,6 +54,9 @@ pub struct RegisterService {
     kafka: DynKafka,
     metrics: Metrics,
     cache_store: Arc<CacheStore>,
+    password_breach: DynPasswordBreachChecker,
+    resend_policy: VerificationResendPolicy,
+    email_domains: EmailDomainPolicy,
 }
 
 impl RegisterService {
@@ -51,6 +67,9 @@ impl RegisterService {
             user_client,
             kafka,
             cache_store,
+            password_breach,
+            resend_policy,
+            email_domains,
         } = deps;
 
         Ok(Self {
@@ -58,9 +77,27 @@ impl RegisterService {
             kafka,
             metrics,
             cache_store,
+            password_breach,
+            resend_policy,
+            email_domains,
         })
     }
 
+    fn verification_email(to: String, verification_code: &str) -> EmailRequest {
+        let template = EmailTemplateData {
+            title: "Welcome to SanEdge".to_string(),
+            message: "Your account has been created. Verify using the link below.".to_string(),
+            button: "Verify Email".to_string(),
+            link: format!("https://sanedge.example.com/login?verify_code={verification_code}"),
+        };
+
+        EmailRequest {
+            to,
+            subject: "Welcome to SanEdge".into(),
+            data: template,
+        }
+    }
+
     fn get_tracer(&self) -> BoxedTracer {
         global::tracer("register-service")
     }
@@ -173,6 +210,13 @@ impl RegisterServiceTrait for RegisterService {
         let mut request = Request::new(req.clone());
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
+        if let Some(reason) = self.email_domains.check(&req.email) {
+            error!("❌ [REGISTER] {reason}");
+            self.complete_tracing_error(&tracing_ctx, method, &reason)
+                .await;
+            return Err(ServiceError::Validation(vec![reason]));
+        }
+
         let cache_key = format!("auth:register:{}", req.email);
 
         if let Some(cached) = self
@@ -213,6 +257,15 @@ impl RegisterServiceTrait for RegisterService {
             }
         }
 
+        if self.password_breach.is_breached(&req.password).await {
+            self.complete_tracing_error(&tracing_ctx, method, "Password found in breach data")
+                .await;
+            return Err(ServiceError::Custom(
+                "Password has appeared in a known data breach, please choose a different one"
+                    .into(),
+            ));
+        }
+
         let verification_code = generate_random_string(10)
             .map_err(|_| ServiceError::Internal("Failed to generate verification code".into()))?;
 
@@ -233,18 +286,7 @@ impl RegisterServiceTrait for RegisterService {
             Err(e) => return Err(grpc_status_to_service_error(e.into())),
         };
 
-        let template = EmailTemplateData {
-            title: "Welcome to SanEdge".to_string(),
-            message: "Your account has been created. Verify using the link below.".to_string(),
-            button: "Verify Email".to_string(),
-            link: format!("https://sanedge.example.com/login?verify_code={verification_code}"),
-        };
-
-        let email_request = EmailRequest {
-            to: new_user.email.clone(),
-            subject: "Welcome to SanEdge".into(),
-            data: template,
-        };
+        let email_request = Self::verification_email(new_user.email.clone(), &verification_code);
 
         let payload = serde_json::to_vec(&email_request)
             .map_err(|_| ServiceError::Custom("Failed to serialize email".into()))?;
@@ -271,4 +313,330 @@ impl RegisterServiceTrait for RegisterService {
             data: new_user,
         })
     }
+
+    async fn resend_verification(&self, email: &str) -> Result<ApiResponse<bool>, ServiceError> {
+        info!("📧 [RESEND VERIFICATION] Requested | Email: {email}");
+
+        let method = Method::Post;
+        let tracing_ctx = self.start_tracing(
+            "ResendVerification",
+            vec![
+                KeyValue::new("component", "auth"),
+                KeyValue::new("user.email", email.to_string()),
+            ],
+        );
+
+        let mut request = Request::new(email);
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        // Unknown and already verified addresses get the same answer as a
+        // real resend, so the endpoint cannot be used to probe accounts.
+        let accepted = ApiResponse {
+            status: "success".to_string(),
+            message: "If the account exists and is unverified, a verification email was sent"
+                .to_string(),
+            data: true,
+        };
+
+        let attempts_key = resend_attempts_key(email);
+        let attempts = self
+            .cache_store
+            .get_from_cache::<i32>(&attempts_key)
+            .await
+            .unwrap_or(0);
+
+        if attempts >= self.resend_policy.max_attempts {
+            let msg = "Too many verification resend requests (rate limited)";
+            self.complete_tracing_error(&tracing_ctx, method, msg).await;
+
+            return Err(ServiceError::TooManyRequests(format!(
+                "Too many verification emails requested. Try again in {} minutes.",
+                self.resend_policy.window_minutes
+            )));
+        }
+
+        self.cache_store
+            .set_to_cache(
+                &attempts_key,
+                &(attempts + 1),
+                Duration::minutes(self.resend_policy.window_minutes),
+            )
+            .await;
+
+        let user = match self
+            .user_client
+            .find_by_email_and_verify(email.to_string())
+            .await
+        {
+            Ok(resp) => resp.data,
+            Err(e) => match grpc_status_to_service_error(e.into()) {
+                ServiceError::Repo(RepositoryError::NotFound) => {
+                    self.complete_tracing_success(&tracing_ctx, method, "No account for email")
+                        .await;
+                    return Ok(accepted);
+                }
+                err => {
+                    error!("❌ Failed to find user for verification resend: {err}");
+                    self.complete_tracing_error(&tracing_ctx, method, "User query failed")
+                        .await;
+                    return Err(err);
+                }
+            },
+        };
+
+        if user.is_verified {
+            self.complete_tracing_success(&tracing_ctx, method, "Email already verified")
+                .await;
+            return Ok(accepted);
+        }
+
+        let verification_code = generate_random_string(10)
+            .map_err(|_| ServiceError::Internal("Failed to generate verification code".into()))?;
+
+        if let Err(e) = self
+            .user_client
+            .update_user_verification_code(UpdateUserVerificationCodeRequest {
+                user_id: user.id,
+                verification_code: verification_code.clone(),
+            })
+            .await
+        {
+            self.complete_tracing_error(&tracing_ctx, method, "Failed to store verification code")
+                .await;
+            return Err(grpc_status_to_service_error(e.into()));
+        }
+
+        let email_request = Self::verification_email(user.email.clone(), &verification_code);
+
+        let payload = serde_json::to_vec(&email_request)
+            .map_err(|_| ServiceError::Custom("Failed to serialize email".into()))?;
+
+        if let Err(e) = self
+            .kafka
+            .publish(
+                "email-service-topic-auth-register",
+                &user.id.to_string(),
+                &payload,
+            )
+            .await
+        {
+            error!("❌ Failed to publish verification resend event: {:?}", e);
+            self.complete_tracing_error(&tracing_ctx, method, "Failed to publish email event")
+                .await;
+            return Err(ServiceError::Custom(
+                "Failed to send verification email".into(),
+            ));
+        }
+
+        self.complete_tracing_success(&tracing_ctx, method, "Verification email resent")
+            .await;
+
+        Ok(accepted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RegisterService, RegisterServiceDeps};
+    use crate::{
+        abstract_trait::{auth::RegisterServiceTrait, grpc_client::user::UserGrpcClientTrait},
+        config::myconfig::{EmailDomainMode, EmailDomainPolicy, VerificationResendPolicy},
+        domain::{
+            requests::{
+                auth::RegisterRequest,
+                user::{
+                    CreateUserRequest, UpdateUserPasswordRequest,
+                    UpdateUserVerificationCodeRequest, UpdateUserVerifiedRequest,
+                },
+            },
+            response::{
+                api::ApiResponse,
+                user::{UserResponse, UserResponseWithPassword},
+            },
+        },
+    };
+    use async_trait::async_trait;
+    use shared::{
+        abstract_trait::KafkaTrait,
+        cache::CacheStore,
+        config::{RedisConfig, RedisPool},
+        errors::{AppErrorGrpc, ServiceError},
+        utils::PasswordBreachChecker,
+    };
+    use std::{
+        net::TcpListener,
+        sync::{Arc, Mutex},
+    };
+
+    /// A user service with no accounts yet, recording who got created.
+    #[derive(Default)]
+    struct NewUsers(Mutex<Vec<String>>);
+
+    #[async_trait]
+    impl UserGrpcClientTrait for NewUsers {
+        async fn create_user(
+            &self,
+            req: CreateUserRequest,
+        ) -> Result<ApiResponse<UserResponse>, AppErrorGrpc> {
+            self.0.lock().unwrap().push(req.email.clone());
+
+            Ok(ApiResponse {
+                status: "success".into(),
+                message: "User created".into(),
+                data: UserResponse {
+                    id: 1,
+                    external_id: String::new(),
+                    firstname: req.first_name,
+                    lastname: req.last_name,
+                    email: req.email,
+                    created_at: None,
+                    updated_at: None,
+                    roles: Vec::new(),
+                },
+            })
+        }
+
+        async fn update_user_is_verified(
+            &self,
+            _req: UpdateUserVerifiedRequest,
+        ) -> Result<ApiResponse<UserResponse>, AppErrorGrpc> {
+            unimplemented!()
+        }
+
+        async fn update_user_verification_code(
+            &self,
+            _req: UpdateUserVerificationCodeRequest,
+        ) -> Result<ApiResponse<UserResponse>, AppErrorGrpc> {
+            unimplemented!()
+        }
+
+        async fn update_user_password(
+            &self,
+            _req: UpdateUserPasswordRequest,
+        ) -> Result<ApiResponse<UserResponse>, AppErrorGrpc> {
+            unimplemented!()
+        }
+
+        async fn find_verification_code(
+            &self,
+            _code: String,
+        ) -> Result<ApiResponse<UserResponse>, AppErrorGrpc> {
+            unimplemented!()
+        }
+
+        async fn find_by_email(
+            &self,
+            _email: String,
+        ) -> Result<ApiResponse<UserResponse>, AppErrorGrpc> {
+            Err(AppErrorGrpc::Unhandled("User not found".into()))
+        }
+
+        async fn find_by_email_and_verify(
+            &self,
+            _email: String,
+        ) -> Result<ApiResponse<UserResponseWithPassword>, AppErrorGrpc> {
+            unimplemented!()
+        }
+
+        async fn find_by_id(&self, _id: i32) -> Result<ApiResponse<UserResponse>, AppErrorGrpc> {
+            unimplemented!()
+        }
+    }
+
+    struct Accepting;
+
+    #[async_trait]
+    impl KafkaTrait for Accepting {
+        async fn publish(
+            &self,
+            _topic: &str,
+            _key: &str,
+            _value: &[u8],
+        ) -> Result<(), ServiceError> {
+            Ok(())
+        }
+
+        async fn subscribe(&self, _topics: Vec<&str>, _group_id: &str) -> Result<(), ServiceError> {
+            unimplemented!()
+        }
+    }
+
+    fn service(users: &Arc<NewUsers>, mode: EmailDomainMode, domains: &[&str]) -> RegisterService {
+        // Bound and released, so every cache lookup is a miss and earlier
+        // runs can't answer from a cached registration.
+        let port = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let redis = RedisPool::new(&RedisConfig {
+            host: "127.0.0.1".into(),
+            port,
+            ..RedisConfig::new()
+        })
+        .unwrap();
+
+        RegisterService::new(RegisterServiceDeps {
+            user_client: users.clone(),
+            kafka: Arc::new(Accepting),
+            cache_store: Arc::new(CacheStore::new(redis.pool)),
+            password_breach: Arc::new(PasswordBreachChecker::new(false, "")),
+            resend_policy: VerificationResendPolicy {
+                max_attempts: 3,
+                window_minutes: 15,
+            },
+            email_domains: EmailDomainPolicy {
+                mode,
+                domains: domains.iter().map(|d| d.to_string()).collect(),
+            },
+        })
+        .unwrap()
+    }
+
+    fn registration(email: &str) -> RegisterRequest {
+        RegisterRequest {
+            first_name: "Ada".into(),
+            last_name: "Lovelace".into(),
+            email: email.into(),
+            password: "correct-horse".into(),
+            confirm_password: "correct-horse".into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn denylisted_domains_are_refused_and_others_register() {
+        let users = Arc::new(NewUsers::default());
+        let service = service(&users, EmailDomainMode::Deny, &["mailinator.com"]);
+
+        for email in ["ada@mailinator.com", "ada@eu.Mailinator.com"] {
+            let refused = service.register(&registration(email)).await;
+            assert!(
+                matches!(&refused, Err(ServiceError::Validation(reasons)) if reasons[0].contains("is not allowed")),
+                "{email}: {refused:?}"
+            );
+        }
+        assert!(users.0.lock().unwrap().is_empty());
+
+        service
+            .register(&registration("ada@example.com"))
+            .await
+            .unwrap();
+        assert_eq!(*users.0.lock().unwrap(), ["ada@example.com"]);
+    }
+
+    #[tokio::test]
+    async fn allowlist_mode_refuses_every_unlisted_domain() {
+        let users = Arc::new(NewUsers::default());
+        let service = service(&users, EmailDomainMode::Allow, &["example.com"]);
+
+        let refused = service.register(&registration("ada@gmail.com")).await;
+        assert!(matches!(refused, Err(ServiceError::Validation(_))));
+        assert!(users.0.lock().unwrap().is_empty());
+
+        service
+            .register(&registration("ada@example.com"))
+            .await
+            .unwrap();
+        assert_eq!(*users.0.lock().unwrap(), ["ada@example.com"]);
+    }
 }
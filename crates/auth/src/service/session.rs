@@ -0,0 +1,89 @@
+use crate::{
+    abstract_trait::{
+        auth::SessionServiceTrait,
+        refresh_token::{DynRefreshTokenCommandRepository, DynRefreshTokenQueryRepository},
+    },
+    domain::response::{api::ApiResponse, session::SessionResponse},
+};
+use async_trait::async_trait;
+use shared::{cache::CacheStore, errors::ServiceError};
+use std::sync::Arc;
+use tracing::{error, info};
+
+pub struct SessionServiceDeps {
+    pub refresh_token_query: DynRefreshTokenQueryRepository,
+    pub refresh_token_command: DynRefreshTokenCommandRepository,
+    pub cache_store: Arc<CacheStore>,
+}
+
+/// Lets a user see where they are signed in and sign out one device. Each
+/// session is the active refresh token of its rotation chain.
+#[derive(Clone)]
+pub struct SessionService {
+    refresh_token_query: DynRefreshTokenQueryRepository,
+    refresh_token_command: DynRefreshTokenCommandRepository,
+    cache_store: Arc<CacheStore>,
+}
+
+impl SessionService {
+    pub fn new(deps: SessionServiceDeps) -> Self {
+        let SessionServiceDeps {
+            refresh_token_query,
+            refresh_token_command,
+            cache_store,
+        } = deps;
+
+        Self {
+            refresh_token_query,
+            refresh_token_command,
+            cache_store,
+        }
+    }
+}
+
+#[async_trait]
+impl SessionServiceTrait for SessionService {
+    async fn list_sessions(
+        &self,
+        user_id: i32,
+    ) -> Result<ApiResponse<Vec<SessionResponse>>, ServiceError> {
+        let tokens = self
+            .refresh_token_query
+            .find_active_by_user_id(user_id)
+            .await
+            .map_err(|e| {
+                error!("❌ Failed to list sessions for user_id={user_id}: {:?}", e);
+                ServiceError::from(e)
+            })?;
+
+        Ok(ApiResponse {
+            status: "success".into(),
+            message: "Sessions retrieved successfully".into(),
+            data: tokens.into_iter().map(SessionResponse::from).collect(),
+        })
+    }
+
+    async fn revoke_session(
+        &self,
+        user_id: i32,
+        session_id: i32,
+    ) -> Result<ApiResponse<bool>, ServiceError> {
+        let revoked = self
+            .refresh_token_command
+            .delete_session(user_id, session_id)
+            .await
+            .map_err(ServiceError::from)?;
+
+        self.cache_store
+            .delete_from_cache(&format!("auth:refresh:{}", revoked.token))
+            .await;
+
+        info!("🔒 Revoked session {session_id} for user_id={user_id}");
+
+        Ok(ApiResponse {
+            status: "success".into(),
+            message: "Session revoked successfully".into(),
+            data: true,
+        })
+    }
+}
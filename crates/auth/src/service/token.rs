@@ -1,31 +1,186 @@
 use crate::{
-    abstract_trait::{auth::TokenServiceTrait, refresh_token::DynRefreshTokenCommandRepository},
-    domain::requests::refresh_token::CreateRefreshToken,
+    abstract_trait::{
+        auth::TokenServiceTrait,
+        grpc_client::role::DynRoleGrpcClient,
+        refresh_token::{DynRefreshTokenCommandRepository, DynRefreshTokenQueryRepository},
+    },
+    config::myconfig::{SessionLimitAction, SessionLimitPolicy},
+    domain::{
+        requests::refresh_token::{CreateRefreshToken, UpdateRefreshToken},
+        response::token::AccessToken,
+    },
+    models::refresh_token::RefreshTokenRotation,
 };
 use async_trait::async_trait;
-use chrono::{Duration, Utc};
+use chrono::{Duration, NaiveDateTime, Utc};
 use shared::abstract_trait::DynJwtService;
-use shared::errors::ServiceError;
-use tracing::{error, info};
+use shared::cache::CacheStore;
+use shared::errors::{AppErrorGrpc, RepositoryError, ServiceError};
+use std::sync::Arc;
+use tracing::{error, info, warn};
+
+/// Width of `refresh_tokens.user_agent`; longer headers are truncated.
+const MAX_USER_AGENT_LEN: usize = 512;
+
+pub struct TokenServiceDeps {
+    pub jwt: DynJwtService,
+    pub refresh_token_query: DynRefreshTokenQueryRepository,
+    pub refresh_token_command: DynRefreshTokenCommandRepository,
+    pub role_client: DynRoleGrpcClient,
+    pub cache_store: Arc<CacheStore>,
+    /// Sessions older than this, counted from the original login, are not
+    /// rotated any further.
+    pub max_session_age: Duration,
+    pub session_limit: SessionLimitPolicy,
+}
 
 pub struct TokenService {
     token: DynJwtService,
+    refresh_query: DynRefreshTokenQueryRepository,
     refresh: DynRefreshTokenCommandRepository,
+    role_client: DynRoleGrpcClient,
+    cache_store: Arc<CacheStore>,
+    max_session_age: Duration,
+    session_limit: SessionLimitPolicy,
 }
 
 impl TokenService {
-    pub fn new(token: DynJwtService, refresh: DynRefreshTokenCommandRepository) -> Self {
-        Self { token, refresh }
+    pub fn new(deps: TokenServiceDeps) -> Self {
+        let TokenServiceDeps {
+            jwt,
+            refresh_token_query,
+            refresh_token_command,
+            role_client,
+            cache_store,
+            max_session_age,
+            session_limit,
+        } = deps;
+
+        Self {
+            token: jwt,
+            refresh_query: refresh_token_query,
+            refresh: refresh_token_command,
+            role_client,
+            cache_store,
+            max_session_age,
+            session_limit,
+        }
+    }
+
+    /// Makes room for one more session of `user_id` under the configured cap,
+    /// either by ending the oldest sessions or by refusing the login.
+    async fn enforce_session_limit(&self, user_id: i32) -> Result<(), ServiceError> {
+        let Some(max_sessions) = self.session_limit.max_sessions else {
+            return Ok(());
+        };
+
+        let active = self
+            .refresh_query
+            .find_active_by_user_id(user_id)
+            .await
+            .map_err(ServiceError::from)?;
+
+        if active.len() < max_sessions {
+            return Ok(());
+        }
+
+        match self.session_limit.action {
+            SessionLimitAction::Reject => {
+                info!(
+                    "🚫 user_id {} already has {} active sessions",
+                    user_id,
+                    active.len()
+                );
+                Err(ServiceError::Forbidden(format!(
+                    "Maximum of {max_sessions} active sessions reached, sign out of another session first"
+                )))
+            }
+            SessionLimitAction::EvictOldest => {
+                // Newest first, so everything past the first `max_sessions - 1`
+                // has to go for the new session to fit.
+                for session in active.iter().skip(max_sessions - 1) {
+                    match self
+                        .refresh
+                        .delete_session(user_id, session.refresh_token_id)
+                        .await
+                    {
+                        Ok(evicted) => {
+                            self.cache_store
+                                .delete_from_cache(&format!("auth:refresh:{}", evicted.token))
+                                .await;
+
+                            info!(
+                                "🧹 Evicted session {} of user_id {} to stay within the session limit",
+                                evicted.refresh_token_id, user_id
+                            );
+                        }
+                        Err(RepositoryError::NotFound) => {}
+                        Err(e) => {
+                            error!("❌ Failed to evict session: {:?}", e);
+                            return Err(ServiceError::from(e));
+                        }
+                    }
+                }
+
+                Ok(())
+            }
+        }
+    }
+
+    /// Expiry of a refresh token signed now, as the repositories expect it.
+    fn refresh_token_expiry(&self) -> String {
+        (Utc::now() + self.token.refresh_token_ttl())
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string()
+    }
+
+    async fn issue_refresh_token(
+        &self,
+        id: i32,
+        user_agent: Option<String>,
+        session_started_at: Option<NaiveDateTime>,
+    ) -> Result<String, ServiceError> {
+        let token = self.token.generate_token(id as i64, "refresh")?;
+
+        let req = CreateRefreshToken {
+            user_id: id,
+            token: token.clone(),
+            expired_date: self.refresh_token_expiry(),
+            user_agent: user_agent.map(|ua| ua.chars().take(MAX_USER_AGENT_LEN).collect()),
+            session_started_at,
+        };
+
+        match self.refresh.create(&req).await {
+            Ok(_) => {
+                info!("✅ Created refresh token for user_id {}", id);
+                Ok(token)
+            }
+            Err(e) => {
+                error!("❌ Failed to create refresh token: {:?}", e);
+                Err(ServiceError::from(e))
+            }
+        }
     }
 }
 
 #[async_trait]
 impl TokenServiceTrait for TokenService {
-    async fn create_access_token(&self, id: i32) -> Result<String, ServiceError> {
-        match self.token.generate_token(id as i64, "access") {
+    async fn create_access_token(&self, id: i32) -> Result<AccessToken, ServiceError> {
+        let roles = match self.role_names(id).await {
+            Ok(roles) => roles,
+            Err(err) => {
+                error!("❌ Failed to fetch roles for user_id {}: {:?}", id, err);
+                return Err(err);
+            }
+        };
+
+        match self
+            .token
+            .generate_token_with_roles(id as i64, "access", &roles)
+        {
             Ok(token) => {
                 info!("✅ Successfully generated access token for user_id: {}", id);
-                Ok(token)
+                Ok(AccessToken { token, roles })
             }
             Err(err) => {
                 error!(
@@ -37,32 +192,390 @@ impl TokenServiceTrait for TokenService {
         }
     }
 
-    async fn create_refresh_token(&self, id: i32) -> Result<String, ServiceError> {
-        let token = self.token.generate_token(id as i64, "refresh")?;
-
-        if let Err(e) = self.refresh.delete_by_user_id(id).await {
-            error!("❌ Failed to delete existing refresh token: {:?}", e);
+    async fn role_names(&self, id: i32) -> Result<Vec<String>, ServiceError> {
+        match self.role_client.find_by_user_id(id).await {
+            Ok(response) => Ok(response.data.into_iter().map(|role| role.name).collect()),
+            Err(AppErrorGrpc::Service(e)) => Err(e),
+            Err(AppErrorGrpc::Unhandled(msg)) => Err(ServiceError::Internal(msg)),
         }
+    }
 
-        let expires_at = (Utc::now() + Duration::hours(24))
-            .format("%Y-%m-%d %H:%M:%S")
-            .to_string();
+    async fn create_refresh_token(
+        &self,
+        id: i32,
+        user_agent: Option<String>,
+    ) -> Result<String, ServiceError> {
+        self.enforce_session_limit(id).await?;
 
-        let req = CreateRefreshToken {
+        self.issue_refresh_token(id, user_agent, None).await
+    }
+
+    async fn rotate_refresh_token(&self, token: &str, id: i32) -> Result<String, ServiceError> {
+        let next = UpdateRefreshToken {
             user_id: id,
-            token: token.clone(),
-            expired_date: expires_at,
+            token: self.token.generate_token(id as i64, "refresh")?,
+            expired_date: self.refresh_token_expiry(),
         };
+        let session_started_after = Utc::now().naive_utc() - self.max_session_age;
 
-        match self.refresh.create(&req).await {
-            Ok(_) => {
-                info!("✅ Created refresh token for user_id {}", id);
-                Ok(token)
+        match self
+            .refresh
+            .rotate(token.to_string(), &next, session_started_after)
+            .await
+        {
+            Ok(RefreshTokenRotation::Rotated) => {
+                info!("✅ Rotated refresh token for user_id {}", id);
+                Ok(next.token)
             }
+            Ok(RefreshTokenRotation::SessionExpired) => {
+                info!("⌛ Session of user_id {} is past its maximum age", id);
+                Err(ServiceError::TokenExpired)
+            }
+            Ok(RefreshTokenRotation::Reused) => {
+                warn!("🚨 Refresh token reuse detected for user_id={id}, revoking all sessions");
+
+                if let Err(e) = self.refresh.delete_by_user_id(id).await {
+                    error!(
+                        "❌ Failed to revoke refresh tokens for user_id={id}: {:?}",
+                        e
+                    );
+                }
+
+                Err(ServiceError::SessionRevoked)
+            }
+            Ok(RefreshTokenRotation::Unknown) => Err(ServiceError::InvalidCredentials),
             Err(e) => {
-                error!("❌ Failed to create refresh token: {:?}", e);
+                error!("❌ Failed to rotate refresh token: {:?}", e);
                 Err(ServiceError::from(e))
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        abstract_trait::{
+            grpc_client::role::RoleGrpcClientTrait,
+            refresh_token::RefreshTokenCommandRepositoryTrait,
+            refresh_token::RefreshTokenQueryRepositoryTrait,
+        },
+        domain::response::{api::ApiResponse, role::RoleResponse},
+        models::refresh_token::RefreshToken,
+    };
+    use shared::{
+        abstract_trait::JwtServiceTrait,
+        config::{Claims, RedisConfig, RedisPool},
+    };
+    use std::sync::{
+        Mutex,
+        atomic::{AtomicUsize, Ordering},
+    };
+
+    /// Keeps refresh tokens in memory and rotates them the way the
+    /// `refresh_tokens` table does: a rotated-out token stays, marked used.
+    #[derive(Default)]
+    struct InMemoryTokens(Mutex<Vec<RefreshToken>>);
+
+    impl InMemoryTokens {
+        fn live_tokens(&self, user_id: i32) -> Vec<String> {
+            self.0
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|t| t.user_id == user_id && t.used_at.is_none())
+                .map(|t| t.token.clone())
+                .collect()
+        }
+    }
+
+    fn stored(user_id: i32, token: &str, session_started_at: NaiveDateTime) -> RefreshToken {
+        let now = Utc::now().naive_utc();
+        RefreshToken {
+            refresh_token_id: 0,
+            user_id,
+            token: token.to_string(),
+            expiration: now + Duration::days(1),
+            created_at: Some(now),
+            updated_at: Some(now),
+            deleted_at: None,
+            used_at: None,
+            user_agent: None,
+            session_started_at: Some(session_started_at),
+        }
+    }
+
+    #[async_trait]
+    impl RefreshTokenQueryRepositoryTrait for InMemoryTokens {
+        async fn find_by_user_id(&self, _: i32) -> Result<Option<RefreshToken>, RepositoryError> {
+            unimplemented!()
+        }
+
+        async fn find_active_by_user_id(
+            &self,
+            user_id: i32,
+        ) -> Result<Vec<RefreshToken>, RepositoryError> {
+            // Newest first, like the query behind it.
+            Ok(self
+                .0
+                .lock()
+                .unwrap()
+                .iter()
+                .rev()
+                .filter(|t| t.user_id == user_id && t.used_at.is_none())
+                .map(|t| {
+                    let mut active = stored(t.user_id, &t.token, t.session_started_at.unwrap());
+                    active.refresh_token_id = t.refresh_token_id;
+                    active
+                })
+                .collect())
+        }
+
+        async fn find_by_token(&self, _: String) -> Result<Option<RefreshToken>, RepositoryError> {
+            unimplemented!()
+        }
+    }
+
+    #[async_trait]
+    impl RefreshTokenCommandRepositoryTrait for InMemoryTokens {
+        async fn create(
+            &self,
+            request: &CreateRefreshToken,
+        ) -> Result<RefreshToken, RepositoryError> {
+            let started_at = request
+                .session_started_at
+                .unwrap_or_else(|| Utc::now().naive_utc());
+            let mut tokens = self.0.lock().unwrap();
+            let id = tokens.iter().map(|t| t.refresh_token_id).max().unwrap_or(0) + 1;
+
+            let mut token = stored(request.user_id, &request.token, started_at);
+            token.refresh_token_id = id;
+            tokens.push(token);
+
+            let mut created = stored(request.user_id, &request.token, started_at);
+            created.refresh_token_id = id;
+            Ok(created)
+        }
+
+        async fn update(&self, _: &UpdateRefreshToken) -> Result<RefreshToken, RepositoryError> {
+            unimplemented!()
+        }
+
+        async fn rotate(
+            &self,
+            token: String,
+            next: &UpdateRefreshToken,
+            session_started_after: NaiveDateTime,
+        ) -> Result<RefreshTokenRotation, RepositoryError> {
+            let mut tokens = self.0.lock().unwrap();
+            let Some(index) = tokens
+                .iter()
+                .position(|t| t.token == token && t.user_id == next.user_id)
+            else {
+                return Ok(RefreshTokenRotation::Unknown);
+            };
+
+            let presented = &mut tokens[index];
+            if presented.used_at.is_some() {
+                return Ok(RefreshTokenRotation::Reused);
+            }
+
+            let started_at = presented.session_started_at.unwrap();
+            if started_at < session_started_after {
+                tokens.remove(index);
+                return Ok(RefreshTokenRotation::SessionExpired);
+            }
+
+            presented.used_at = Some(Utc::now().naive_utc());
+            tokens.push(stored(next.user_id, &next.token, started_at));
+            Ok(RefreshTokenRotation::Rotated)
+        }
+
+        async fn delete_token(&self, _: String) -> Result<(), RepositoryError> {
+            unimplemented!()
+        }
+
+        async fn delete_session(
+            &self,
+            user_id: i32,
+            refresh_token_id: i32,
+        ) -> Result<RefreshToken, RepositoryError> {
+            let mut tokens = self.0.lock().unwrap();
+            let index = tokens
+                .iter()
+                .position(|t| t.user_id == user_id && t.refresh_token_id == refresh_token_id)
+                .ok_or(RepositoryError::NotFound)?;
+            Ok(tokens.remove(index))
+        }
+
+        async fn delete_by_user_id(&self, user_id: i32) -> Result<(), RepositoryError> {
+            self.0.lock().unwrap().retain(|t| t.user_id != user_id);
+            Ok(())
+        }
+
+        async fn purge_stale(&self, _: i64) -> Result<u64, RepositoryError> {
+            unimplemented!()
+        }
+    }
+
+    /// Signs each token with a new serial number, so every token differs.
+    #[derive(Debug, Default)]
+    struct SerialTokens(AtomicUsize);
+
+    impl JwtServiceTrait for SerialTokens {
+        fn generate_token(&self, user_id: i64, token_type: &str) -> Result<String, ServiceError> {
+            let serial = self.0.fetch_add(1, Ordering::SeqCst);
+            Ok(format!("{token_type}-{user_id}-{serial}"))
+        }
+
+        fn generate_token_with_roles(
+            &self,
+            _: i64,
+            _: &str,
+            _: &[String],
+        ) -> Result<String, ServiceError> {
+            unimplemented!()
+        }
+
+        fn refresh_token_ttl(&self) -> Duration {
+            Duration::days(1)
+        }
+
+        fn verify_token(&self, _: &str, _: &str) -> Result<i64, ServiceError> {
+            unimplemented!()
+        }
+
+        fn verify_token_claims(&self, _: &str, _: &str) -> Result<Claims, ServiceError> {
+            unimplemented!()
+        }
+    }
+
+    struct NoRoles;
+
+    #[async_trait]
+    impl RoleGrpcClientTrait for NoRoles {
+        async fn find_by_name(&self, _: &str) -> Result<ApiResponse<RoleResponse>, AppErrorGrpc> {
+            unimplemented!()
+        }
+
+        async fn find_by_user_id(
+            &self,
+            _: i32,
+        ) -> Result<ApiResponse<Vec<RoleResponse>>, AppErrorGrpc> {
+            unimplemented!()
+        }
+    }
+
+    fn service(
+        tokens: &Arc<InMemoryTokens>,
+        max_session_age: Duration,
+        session_limit: SessionLimitPolicy,
+    ) -> TokenService {
+        // The pool connects lazily, and a cache that can't be reached only
+        // gets logged when a session is evicted.
+        let redis = RedisPool::new(&RedisConfig::new()).unwrap();
+
+        TokenService::new(TokenServiceDeps {
+            jwt: Arc::new(SerialTokens::default()),
+            refresh_token_query: tokens.clone(),
+            refresh_token_command: tokens.clone(),
+            role_client: Arc::new(NoRoles),
+            cache_store: Arc::new(CacheStore::new(redis.pool)),
+            max_session_age,
+            session_limit,
+        })
+    }
+
+    const UNLIMITED: SessionLimitPolicy = SessionLimitPolicy {
+        max_sessions: None,
+        action: SessionLimitAction::Reject,
+    };
+
+    #[tokio::test]
+    async fn replaying_a_rotated_token_revokes_every_session() {
+        let tokens = Arc::new(InMemoryTokens::default());
+        let service = service(&tokens, Duration::days(30), UNLIMITED);
+
+        let first = service.create_refresh_token(1, None).await.unwrap();
+        let second = service.rotate_refresh_token(&first, 1).await.unwrap();
+        assert_eq!(tokens.live_tokens(1), std::slice::from_ref(&second));
+
+        let replayed = service.rotate_refresh_token(&first, 1).await;
+        assert!(matches!(replayed, Err(ServiceError::SessionRevoked)));
+        assert!(tokens.live_tokens(1).is_empty());
+
+        let after_revoke = service.rotate_refresh_token(&second, 1).await;
+        assert!(matches!(
+            after_revoke,
+            Err(ServiceError::InvalidCredentials)
+        ));
+    }
+
+    #[tokio::test]
+    async fn sessions_past_the_maximum_age_are_not_rotated() {
+        let tokens = Arc::new(InMemoryTokens::default());
+        let service = service(&tokens, Duration::days(30), UNLIMITED);
+
+        let long_ago = Utc::now().naive_utc() - Duration::days(31);
+        tokens.0.lock().unwrap().push(stored(1, "stale", long_ago));
+        let recent = Utc::now().naive_utc() - Duration::days(29);
+        tokens.0.lock().unwrap().push(stored(2, "fresh", recent));
+
+        let stale = service.rotate_refresh_token("stale", 1).await;
+        assert!(matches!(stale, Err(ServiceError::TokenExpired)));
+        assert!(tokens.live_tokens(1).is_empty());
+
+        let fresh = service.rotate_refresh_token("fresh", 2).await.unwrap();
+        assert_eq!(tokens.live_tokens(2), std::slice::from_ref(&fresh));
+
+        // The rotated token keeps the original login time, so the chain
+        // still ends 30 days after it started.
+        let started_at = tokens
+            .0
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|t| t.token == fresh)
+            .and_then(|t| t.session_started_at);
+        assert_eq!(started_at, Some(recent));
+    }
+
+    #[tokio::test]
+    async fn a_login_past_the_session_limit_evicts_the_oldest_session() {
+        let tokens = Arc::new(InMemoryTokens::default());
+        let limit = SessionLimitPolicy {
+            max_sessions: Some(2),
+            action: SessionLimitAction::EvictOldest,
+        };
+        let service = service(&tokens, Duration::days(30), limit);
+
+        let first = service.create_refresh_token(1, None).await.unwrap();
+        let second = service.create_refresh_token(1, None).await.unwrap();
+        let third = service.create_refresh_token(1, None).await.unwrap();
+
+        assert_eq!(tokens.live_tokens(1), [second, third]);
+        let evicted = service.rotate_refresh_token(&first, 1).await;
+        assert!(matches!(evicted, Err(ServiceError::InvalidCredentials)));
+    }
+
+    #[tokio::test]
+    async fn a_login_past_the_session_limit_is_refused_when_rejecting() {
+        let tokens = Arc::new(InMemoryTokens::default());
+        let limit = SessionLimitPolicy {
+            max_sessions: Some(2),
+            action: SessionLimitAction::Reject,
+        };
+        let service = service(&tokens, Duration::days(30), limit);
+
+        let first = service.create_refresh_token(1, None).await.unwrap();
+        let second = service.create_refresh_token(1, None).await.unwrap();
+        let third = service.create_refresh_token(1, None).await;
+
+        assert!(matches!(third, Err(ServiceError::Forbidden(_))));
+        assert_eq!(tokens.live_tokens(1), [first, second]);
+
+        // Other users have sessions of their own to spend.
+        assert!(service.create_refresh_token(2, None).await.is_ok());
+    }
+}
@@ -0,0 +1,92 @@
+use crate::{
+    abstract_trait::{
+        refresh_token::DynRefreshTokenCommandRepository,
+        reset_token::DynResetTokenCommandRepository,
+    },
+    config::myconfig::PurgeJobConfig,
+};
+use shared::errors::RepositoryError;
+use std::{future::Future, time::Duration};
+use tokio::sync::broadcast;
+use tracing::{error, info};
+
+/// Deletes expired reset tokens and expired or revoked refresh tokens, one
+/// bounded batch per statement so the purge never holds long row locks.
+/// Tokens are otherwise only removed when they are used.
+pub struct ExpiredTokenSweeper {
+    refresh_tokens: DynRefreshTokenCommandRepository,
+    reset_tokens: DynResetTokenCommandRepository,
+    config: PurgeJobConfig,
+}
+
+impl ExpiredTokenSweeper {
+    pub fn new(
+        refresh_tokens: DynRefreshTokenCommandRepository,
+        reset_tokens: DynResetTokenCommandRepository,
+        config: PurgeJobConfig,
+    ) -> Self {
+        Self {
+            refresh_tokens,
+            reset_tokens,
+            config,
+        }
+    }
+
+    /// Sweeps every interval until `shutdown` fires; a sweep in progress is
+    /// finished first.
+    pub async fn run(&self, mut shutdown: broadcast::Receiver<()>) {
+        info!("🧹 Starting expired token sweeper...");
+
+        let mut interval = tokio::time::interval(Duration::from_secs(self.config.interval_seconds));
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = shutdown.recv() => {
+                    info!("🧹 Expired token sweeper stopped");
+                    return;
+                }
+            }
+
+            let refresh = self
+                .drain(|limit| self.refresh_tokens.purge_stale(limit))
+                .await;
+            let reset = self
+                .drain(|limit| self.reset_tokens.delete_expired(limit))
+                .await;
+
+            match (refresh, reset) {
+                (Ok(refresh), Ok(reset)) => info!(
+                    "🧹 Token sweep removed {refresh} refresh token(s) and {reset} reset token(s)"
+                ),
+                (refresh, reset) => {
+                    if let Err(e) = refresh {
+                        error!("Refresh token sweep failed: {e:?}");
+                    }
+                    if let Err(e) = reset {
+                        error!("Reset token sweep failed: {e:?}");
+                    }
+                }
+            }
+        }
+    }
+
+    /// Deletes batch after batch until one comes back short, clearing the
+    /// whole backlog within a tick.
+    async fn drain<F, Fut>(&self, delete_batch: F) -> Result<u64, RepositoryError>
+    where
+        F: Fn(i64) -> Fut,
+        Fut: Future<Output = Result<u64, RepositoryError>>,
+    {
+        let mut total = 0;
+
+        loop {
+            let deleted = delete_batch(self.config.batch_size).await?;
+            total += deleted;
+
+            if (deleted as i64) < self.config.batch_size {
+                return Ok(total);
+            }
+        }
+    }
+}
@@ -5,9 +5,9 @@ use crate::{
 };
 use anyhow::{Context, Result};
 use shared::{
-    abstract_trait::{DynHashing, DynJwtService, DynKafka},
-    config::{ConnectionPool, Hashing, JwtConfig, Kafka, RedisConfig, RedisPool},
-    utils::{SystemMetrics, run_metrics_collector},
+    abstract_trait::{DynHashing, DynJwtService, DynKafka, DynPasswordBreachChecker},
+    config::{DatabasePools, Hashing, JwtConfig, Kafka, RedisConfig, RedisPool},
+    utils::{PasswordBreachChecker, SystemMetrics, run_metrics_collector},
 };
 use std::{fmt, sync::Arc};
 
@@ -16,6 +16,7 @@ pub struct AppState {
     pub di_container: DependenciesInject,
     pub kafka_config: DynKafka,
     pub system_metrics: Arc<SystemMetrics>,
+    pub db_pools: DatabasePools,
 }
 
 impl fmt::Debug for AppState {
@@ -28,11 +29,25 @@ impl fmt::Debug for AppState {
 }
 
 impl AppState {
-    pub async fn new(pool: ConnectionPool, config: Config) -> Result<Self> {
-        let jwt_config = Arc::new(JwtConfig::new(&config.jwt_secret)) as DynJwtService;
+    pub async fn new(pools: DatabasePools, config: Config) -> Result<Self> {
+        let jwt_config = Arc::new(
+            JwtConfig::new(&config.jwt_secret)
+                .with_previous_key(config.jwt_previous_key.clone())
+                .with_ttl(config.token_ttl),
+        ) as DynJwtService;
         let kafka_config = Arc::new(Kafka::new(&config.kafka_broker)) as DynKafka;
         let hashing = Arc::new(Hashing::new()) as DynHashing;
+        let password_breach =
+            Arc::new(PasswordBreachChecker::from_env()) as DynPasswordBreachChecker;
         let system_metrics = Arc::new(SystemMetrics::new());
+        let require_email_verification = config.require_email_verification;
+        let login_lockout = config.login_lockout;
+        let verification_resend = config.verification_resend;
+        let session_max_age_days = config.session_max_age_days;
+        let session_limit = config.session_limit;
+        let email_domains = config.email_domains.clone();
+        let max_reset_tokens_per_user = config.max_reset_tokens_per_user;
+        let token_purge = config.token_purge;
 
         let config = RedisConfig::new();
 
@@ -42,10 +57,19 @@ impl AppState {
 
         let deps = DependenciesInjectDeps {
             hash: hashing.clone(),
-            pool: pool.clone(),
+            pools: pools.clone(),
             jwt_config: jwt_config.clone(),
             kafka: kafka_config.clone(),
             redis: redis.clone(),
+            password_breach,
+            require_email_verification,
+            login_lockout,
+            verification_resend,
+            session_max_age_days,
+            session_limit,
+            email_domains,
+            max_reset_tokens_per_user,
+            token_purge,
         };
 
         let grpc_config = GrpcClientConfig::init().context("failed config grpc")?;
@@ -66,6 +90,7 @@ impl AppState {
             di_container,
             kafka_config,
             system_metrics,
+            db_pools: pools,
         })
     }
 }
@@ -0,0 +1,16 @@
+-- Add up migration script here
+CREATE TABLE "outbox_emails" (
+    "id" SERIAL PRIMARY KEY,
+    "recipient" TEXT NOT NULL,
+    "subject" TEXT NOT NULL,
+    "payload" JSONB NOT NULL,
+    "status" TEXT NOT NULL DEFAULT 'pending',
+    "attempts" INT NOT NULL DEFAULT 0,
+    "last_error" TEXT,
+    "next_attempt_at" TIMESTAMP NOT NULL DEFAULT current_timestamp,
+    "created_at" TIMESTAMP DEFAULT current_timestamp,
+    "updated_at" TIMESTAMP DEFAULT current_timestamp
+);
+
+CREATE INDEX IF NOT EXISTS idx_outbox_emails_due ON outbox_emails (next_attempt_at)
+WHERE status = 'pending';
@@ -1,11 +1,36 @@
 use async_trait::async_trait;
+use chrono::NaiveDateTime;
 use std::sync::Arc;
 
-use crate::domain::EmailRequest;
-use shared::errors::ServiceError;
+use crate::{
+    domain::EmailRequest,
+    model::{NewOutboxEmail, OutboxEmail},
+};
+use shared::errors::{RepositoryError, ServiceError};
 pub type DynEmailService = Arc<dyn EmailServiceTrait>;
+pub type DynOutboxRepository = Arc<dyn OutboxRepositoryTrait>;
 
 #[async_trait]
 pub trait EmailServiceTrait: Send + Sync {
     async fn send(&self, req: &EmailRequest) -> Result<(), ServiceError>;
 }
+
+#[async_trait]
+pub trait OutboxRepositoryTrait: Send + Sync {
+    async fn enqueue(&self, email: &NewOutboxEmail) -> Result<(), RepositoryError>;
+    /// Claims up to `limit` due rows by pushing their next attempt to
+    /// `lease_until`, so a crashed relay's rows become due again later.
+    async fn claim_due(
+        &self,
+        limit: i64,
+        lease_until: NaiveDateTime,
+    ) -> Result<Vec<OutboxEmail>, RepositoryError>;
+    async fn mark_sent(&self, id: i32) -> Result<(), RepositoryError>;
+    async fn reschedule(
+        &self,
+        id: i32,
+        last_error: &str,
+        next_attempt_at: NaiveDateTime,
+    ) -> Result<(), RepositoryError>;
+    async fn mark_dead(&self, id: i32, last_error: &str) -> Result<(), RepositoryError>;
+}
@@ -1,10 +1,13 @@
 use crate::{
-    abstract_trait::DynEmailService,
+    abstract_trait::{DynEmailService, DynOutboxRepository},
     config::MyConfigConfig,
     handler::EmailHandler,
-    service::{EmailService, KafkaEmailService},
+    repository::OutboxRepository,
+    service::{EmailService, KafkaEmailService, OutboxRelay},
 };
-use shared::errors::ServiceError;
+use anyhow::Context;
+use shared::{config::ConnectionManager, errors::ServiceError};
+use sqlx::{Pool, Postgres};
 use std::sync::Arc;
 use tracing::info;
 
@@ -28,7 +31,25 @@ impl EmailServiceApp {
             .await,
         ) as DynEmailService;
 
-        let handler = EmailHandler::new(email_service);
+        let mut handler = EmailHandler::new(email_service.clone());
+
+        if let Some(outbox_config) = self.config.outbox.clone() {
+            let pool = ConnectionManager::new_pool(&outbox_config.database_url, 1, 5)
+                .await
+                .map_err(|e| ServiceError::Internal(e.to_string()))?;
+
+            run_migrations(&pool, outbox_config.run_migrations)
+                .await
+                .map_err(|e| ServiceError::Internal(format!("{e:#}")))?;
+
+            let repository = Arc::new(OutboxRepository::new(pool)) as DynOutboxRepository;
+            handler = handler.with_outbox(repository.clone(), outbox_config.clone());
+
+            let relay = OutboxRelay::new(repository, email_service, outbox_config);
+            tokio::spawn(async move { relay.run().await });
+        } else {
+            info!("⏭️ EMAIL_OUTBOX_ENABLED is false, failed sends are not retried");
+        }
 
         let topics = vec![
             "email-service-topic-auth-register",
@@ -47,3 +68,23 @@ impl EmailServiceApp {
         kafka_service.start_consuming().await
     }
 }
+
+/// Does nothing when AUTO_MIGRATE is false, for deployments that migrate
+/// out of band. Replicas booting together are serialized by the migrator's
+/// Postgres advisory lock, so only one of them applies pending migrations.
+pub async fn run_migrations(pool: &Pool<Postgres>, enabled: bool) -> anyhow::Result<()> {
+    if !enabled {
+        info!("⏭️ AUTO_MIGRATE is disabled, skipping database migrations");
+        return Ok(());
+    }
+
+    let mut migrator = sqlx::migrate!("./migrations");
+    migrator.set_locking(true);
+
+    migrator
+        .run(pool)
+        .await
+        .context("failed to migration database")?;
+
+    Ok(())
+}
@@ -1,4 +1,104 @@
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, anyhow};
+use chrono::Duration;
+
+/// Persistent retry of emails whose SMTP send failed transiently. Rows are
+/// retried with exponential backoff and marked dead after `max_attempts`.
+#[derive(Debug, Clone)]
+pub struct OutboxConfig {
+    pub database_url: String,
+    pub run_migrations: bool,
+    pub max_attempts: i32,
+    pub base_backoff_seconds: i64,
+    pub max_backoff_seconds: i64,
+    pub poll_interval_seconds: u64,
+    pub batch_size: i64,
+}
+
+impl OutboxConfig {
+    /// Delay before the next attempt once `attempts` sends have failed.
+    pub fn backoff(&self, attempts: i32) -> Duration {
+        let exponent = (attempts - 1).clamp(0, 20) as u32;
+        let seconds = self
+            .base_backoff_seconds
+            .saturating_mul(2_i64.saturating_pow(exponent))
+            .min(self.max_backoff_seconds);
+
+        Duration::seconds(seconds)
+    }
+
+    fn from_env() -> Result<Option<Self>> {
+        let enabled = match std::env::var("EMAIL_OUTBOX_ENABLED")
+            .unwrap_or_else(|_| "false".to_string())
+            .as_str()
+        {
+            "true" => true,
+            "false" => false,
+            other => {
+                return Err(anyhow!(
+                    "EMAIL_OUTBOX_ENABLED must be 'true' or 'false', got '{}'",
+                    other
+                ));
+            }
+        };
+
+        if !enabled {
+            return Ok(None);
+        }
+
+        let database_url =
+            std::env::var("DATABASE_URL").context("Missing environment variable: DATABASE_URL")?;
+
+        let run_migrations = match std::env::var("AUTO_MIGRATE")
+            .or_else(|_| std::env::var("RUN_MIGRATIONS"))
+            .unwrap_or_else(|_| "true".to_string())
+            .as_str()
+        {
+            "true" => true,
+            "false" => false,
+            other => {
+                return Err(anyhow!(
+                    "AUTO_MIGRATE must be 'true' or 'false', got '{}'",
+                    other
+                ));
+            }
+        };
+
+        let max_attempts = std::env::var("EMAIL_OUTBOX_MAX_ATTEMPTS")
+            .unwrap_or_else(|_| "5".to_string())
+            .parse::<i32>()
+            .context("EMAIL_OUTBOX_MAX_ATTEMPTS must be a valid i32 integer")?;
+
+        let base_backoff_seconds = std::env::var("EMAIL_OUTBOX_BASE_BACKOFF_SECONDS")
+            .unwrap_or_else(|_| "30".to_string())
+            .parse::<i64>()
+            .context("EMAIL_OUTBOX_BASE_BACKOFF_SECONDS must be a valid i64 integer")?;
+
+        let max_backoff_seconds = std::env::var("EMAIL_OUTBOX_MAX_BACKOFF_SECONDS")
+            .unwrap_or_else(|_| "3600".to_string())
+            .parse::<i64>()
+            .context("EMAIL_OUTBOX_MAX_BACKOFF_SECONDS must be a valid i64 integer")?;
+
+        let poll_interval_seconds = std::env::var("EMAIL_OUTBOX_POLL_SECONDS")
+            .unwrap_or_else(|_| "15".to_string())
+            .parse::<u64>()
+            .context("EMAIL_OUTBOX_POLL_SECONDS must be a valid u64 integer")?;
+
+        let batch_size = std::env::var("EMAIL_OUTBOX_BATCH_SIZE")
+            .unwrap_or_else(|_| "20".to_string())
+            .parse::<i64>()
+            .context("EMAIL_OUTBOX_BATCH_SIZE must be a valid i64 integer")?;
+
+        Ok(Some(Self {
+            database_url,
+            run_migrations,
+            max_attempts: max_attempts.max(1),
+            base_backoff_seconds: base_backoff_seconds.max(1),
+            max_backoff_seconds: max_backoff_seconds.max(1),
+            poll_interval_seconds: poll_interval_seconds.max(1),
+            batch_size: batch_size.max(1),
+        }))
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct MyConfigConfig {
@@ -7,6 +107,7 @@ pub struct MyConfigConfig {
     pub smtp_user: String,
     pub smtp_pass: String,
     pub kafka_broker: String,
+    pub outbox: Option<OutboxConfig>,
 }
 
 impl MyConfigConfig {
@@ -19,6 +120,7 @@ impl MyConfigConfig {
             .parse()
             .expect("Invalid SMTP_PORT");
         let kafka_broker = std::env::var("KAFKA").context("Missing environment variable: KAFKA")?;
+        let outbox = OutboxConfig::from_env()?;
 
         Ok(Self {
             smtp_server: smtp_host,
@@ -26,6 +128,7 @@ impl MyConfigConfig {
             smtp_user: smtp_username,
             smtp_pass: smtp_password,
             kafka_broker,
+            outbox,
         })
     }
 }
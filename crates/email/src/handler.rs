@@ -1,20 +1,63 @@
-use crate::{abstract_trait::EmailServiceTrait, domain::EmailRequest};
-use rdkafka::{Message, message::BorrowedMessage};
-use shared::errors::ServiceError;
+use crate::{
+    abstract_trait::{DynOutboxRepository, EmailServiceTrait},
+    config::OutboxConfig,
+    domain::EmailRequest,
+    model::NewOutboxEmail,
+};
+use chrono::Utc;
+use rdkafka::{
+    Message,
+    message::{BorrowedMessage, Headers},
+};
+use shared::{errors::ServiceError, utils::REQUEST_ID_HEADER};
 use std::sync::Arc;
-use tracing::error;
+use tracing::{Instrument, error, info_span, warn};
 
 pub struct EmailHandler {
     mailer: Arc<dyn EmailServiceTrait>,
+    outbox: Option<(DynOutboxRepository, OutboxConfig)>,
 }
 
 impl EmailHandler {
     pub fn new(mailer: Arc<dyn EmailServiceTrait>) -> Self {
-        Self { mailer }
+        Self {
+            mailer,
+            outbox: None,
+        }
     }
 
+    /// Parks transiently failed sends in the outbox for the relay to retry.
+    pub fn with_outbox(mut self, repository: DynOutboxRepository, config: OutboxConfig) -> Self {
+        self.outbox = Some((repository, config));
+        self
+    }
+
+    /// Runs inside a span carrying the `x-request-id` header set by the
+    /// producer, so the send can be traced back to the originating request.
     pub async fn handle_message(&self, message: &BorrowedMessage<'_>) -> Result<(), ServiceError> {
-        self.process_message(message).await
+        let request_id = message
+            .headers()
+            .and_then(|headers| {
+                headers
+                    .iter()
+                    .find(|header| header.key == REQUEST_ID_HEADER)
+                    .and_then(|header| header.value)
+            })
+            .map(String::from_utf8_lossy)
+            .unwrap_or_default()
+            .into_owned();
+
+        let span = info_span!(
+            "email_message",
+            topic = message.topic(),
+            request_id = if request_id.is_empty() {
+                "-"
+            } else {
+                request_id.as_str()
+            }
+        );
+
+        self.process_message(message).instrument(span).await
     }
 
     async fn process_message(&self, message: &BorrowedMessage<'_>) -> Result<(), ServiceError> {
@@ -35,9 +78,39 @@ impl EmailHandler {
             data: email_payload.data,
         };
 
-        self.mailer.send(&email_request).await.map_err(|e| {
-            error!("Failed to send email: {e:?}");
-            ServiceError::Custom(format!("Email sending failed: {e:?}"))
-        })
+        match self.mailer.send(&email_request).await {
+            Ok(()) => Ok(()),
+            Err(ServiceError::Unavailable(reason)) if self.outbox.is_some() => {
+                self.enqueue_retry(&email_request, &reason).await
+            }
+            Err(e) => {
+                error!("Failed to send email: {e:?}");
+                Err(ServiceError::Custom(format!("Email sending failed: {e:?}")))
+            }
+        }
+    }
+
+    async fn enqueue_retry(&self, req: &EmailRequest, reason: &str) -> Result<(), ServiceError> {
+        let Some((repository, config)) = &self.outbox else {
+            return Ok(());
+        };
+
+        let payload = serde_json::to_value(req)
+            .map_err(|e| ServiceError::Internal(format!("Failed to encode outbox payload: {e}")))?;
+
+        let email = NewOutboxEmail {
+            recipient: req.to.clone(),
+            subject: req.subject.clone(),
+            payload,
+            last_error: reason.to_string(),
+            next_attempt_at: (Utc::now() + config.backoff(1)).naive_utc(),
+        };
+
+        warn!(
+            "Send to {} failed transiently, queuing retry: {reason}",
+            req.to
+        );
+
+        repository.enqueue(&email).await.map_err(ServiceError::from)
     }
 }
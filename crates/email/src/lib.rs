@@ -3,4 +3,6 @@ pub mod app;
 pub mod config;
 pub mod domain;
 pub mod handler;
+pub mod model;
+pub mod repository;
 pub mod service;
@@ -0,0 +1,21 @@
+use chrono::NaiveDateTime;
+
+/// A due `outbox_emails` row claimed by the relay for another send attempt.
+#[derive(Debug, Clone)]
+pub struct OutboxEmail {
+    pub id: i32,
+    pub recipient: String,
+    pub subject: String,
+    pub payload: serde_json::Value,
+    pub attempts: i32,
+}
+
+/// Values written when a failed send is parked in the outbox.
+#[derive(Debug, Clone)]
+pub struct NewOutboxEmail {
+    pub recipient: String,
+    pub subject: String,
+    pub payload: serde_json::Value,
+    pub last_error: String,
+    pub next_attempt_at: NaiveDateTime,
+}
@@ -0,0 +1,165 @@
+use crate::{
+    abstract_trait::OutboxRepositoryTrait,
+    model::{NewOutboxEmail, OutboxEmail},
+};
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use shared::{config::ConnectionPool, errors::RepositoryError};
+use tracing::{error, info};
+
+pub struct OutboxRepository {
+    db: ConnectionPool,
+}
+
+impl OutboxRepository {
+    pub fn new(db: ConnectionPool) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl OutboxRepositoryTrait for OutboxRepository {
+    async fn enqueue(&self, email: &NewOutboxEmail) -> Result<(), RepositoryError> {
+        let mut conn = self.db.acquire().await.map_err(RepositoryError::from)?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO outbox_emails (recipient, subject, payload, attempts, last_error, next_attempt_at)
+            VALUES ($1, $2, $3, 1, $4, $5)
+            "#,
+            email.recipient,
+            email.subject,
+            email.payload,
+            email.last_error,
+            email.next_attempt_at,
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| {
+            error!("Failed to enqueue outbox email: {}", e);
+            RepositoryError::from(e)
+        })?;
+
+        info!("Queued email to {} for retry", email.recipient);
+        Ok(())
+    }
+
+    async fn claim_due(
+        &self,
+        limit: i64,
+        lease_until: NaiveDateTime,
+    ) -> Result<Vec<OutboxEmail>, RepositoryError> {
+        let mut conn = self.db.acquire().await.map_err(RepositoryError::from)?;
+
+        let rows = sqlx::query_as!(
+            OutboxEmail,
+            r#"
+            UPDATE outbox_emails
+            SET next_attempt_at = $2, updated_at = current_timestamp
+            WHERE id IN (
+                SELECT id FROM outbox_emails
+                WHERE status = 'pending' AND next_attempt_at <= current_timestamp
+                ORDER BY next_attempt_at
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING id, recipient, subject, payload, attempts
+            "#,
+            limit,
+            lease_until,
+        )
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| {
+            error!("Failed to claim due outbox emails: {}", e);
+            RepositoryError::from(e)
+        })?;
+
+        Ok(rows)
+    }
+
+    async fn mark_sent(&self, id: i32) -> Result<(), RepositoryError> {
+        let mut conn = self.db.acquire().await.map_err(RepositoryError::from)?;
+
+        let result = sqlx::query!(
+            r#"
+            UPDATE outbox_emails
+            SET status = 'sent', updated_at = current_timestamp
+            WHERE id = $1
+            "#,
+            id,
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| {
+            error!("Failed to mark outbox email {} as sent: {}", id, e);
+            RepositoryError::from(e)
+        })?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        Ok(())
+    }
+
+    async fn reschedule(
+        &self,
+        id: i32,
+        last_error: &str,
+        next_attempt_at: NaiveDateTime,
+    ) -> Result<(), RepositoryError> {
+        let mut conn = self.db.acquire().await.map_err(RepositoryError::from)?;
+
+        let result = sqlx::query!(
+            r#"
+            UPDATE outbox_emails
+            SET attempts = attempts + 1, last_error = $2, next_attempt_at = $3,
+                updated_at = current_timestamp
+            WHERE id = $1
+            "#,
+            id,
+            last_error,
+            next_attempt_at,
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| {
+            error!("Failed to reschedule outbox email {}: {}", id, e);
+            RepositoryError::from(e)
+        })?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        Ok(())
+    }
+
+    async fn mark_dead(&self, id: i32, last_error: &str) -> Result<(), RepositoryError> {
+        let mut conn = self.db.acquire().await.map_err(RepositoryError::from)?;
+
+        let result = sqlx::query!(
+            r#"
+            UPDATE outbox_emails
+            SET status = 'dead', attempts = attempts + 1, last_error = $2,
+                updated_at = current_timestamp
+            WHERE id = $1
+            "#,
+            id,
+            last_error,
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| {
+            error!("Failed to dead-letter outbox email {}: {}", id, e);
+            RepositoryError::from(e)
+        })?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        Ok(())
+    }
+}
@@ -65,10 +65,16 @@ impl EmailServiceTrait for EmailService {
                 info!("✅ Email sent to {}", req.to);
                 Ok(())
             }
-            Err(e) => {
+            Err(e) if e.is_permanent() => {
                 error!("❌ Failed to send email to {}: {}", req.to, e);
                 Err(ServiceError::Custom(format!("Failed to send email: {e}")))
             }
+            Err(e) => {
+                error!("❌ Transient failure sending email to {}: {}", req.to, e);
+                Err(ServiceError::Unavailable(format!(
+                    "Failed to send email: {e}"
+                )))
+            }
         }
     }
 }
@@ -1,5 +1,7 @@
 mod email;
 mod kafka;
+mod outbox;
 
 pub use self::email::EmailService;
 pub use self::kafka::KafkaEmailService;
+pub use self::outbox::OutboxRelay;
@@ -0,0 +1,360 @@
+use crate::{
+    abstract_trait::{DynEmailService, DynOutboxRepository},
+    config::OutboxConfig,
+    domain::EmailRequest,
+    model::OutboxEmail,
+};
+use chrono::{Duration, Utc};
+use shared::errors::ServiceError;
+use tracing::{error, info, warn};
+
+/// How long a claimed row stays hidden from other relays while it is sent.
+const CLAIM_LEASE_SECONDS: i64 = 300;
+
+/// Retries due `outbox_emails` rows, rescheduling transient failures with
+/// backoff and dead-lettering rows that run out of attempts.
+pub struct OutboxRelay {
+    repository: DynOutboxRepository,
+    mailer: DynEmailService,
+    config: OutboxConfig,
+}
+
+impl OutboxRelay {
+    pub fn new(
+        repository: DynOutboxRepository,
+        mailer: DynEmailService,
+        config: OutboxConfig,
+    ) -> Self {
+        Self {
+            repository,
+            mailer,
+            config,
+        }
+    }
+
+    pub async fn run(&self) {
+        info!("📬 Starting email outbox relay...");
+
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+            self.config.poll_interval_seconds,
+        ));
+
+        loop {
+            interval.tick().await;
+
+            if let Err(e) = self.drain().await {
+                error!("Email outbox relay pass failed: {e:?}");
+            }
+        }
+    }
+
+    /// Processes due rows one batch at a time until a batch comes back short,
+    /// so a backlog is cleared within a single tick without one claim
+    /// touching more than `batch_size` rows.
+    pub async fn drain(&self) -> Result<usize, ServiceError> {
+        let mut total = 0;
+
+        loop {
+            let processed = self.process_due().await?;
+            total += processed;
+
+            if (processed as i64) < self.config.batch_size {
+                break;
+            }
+        }
+
+        if total > 0 {
+            info!("📬 Email outbox relay processed {total} rows");
+        }
+
+        Ok(total)
+    }
+
+    /// Runs one relay pass and returns how many rows were processed.
+    pub async fn process_due(&self) -> Result<usize, ServiceError> {
+        let lease_until = (Utc::now() + Duration::seconds(CLAIM_LEASE_SECONDS)).naive_utc();
+        let due = self
+            .repository
+            .claim_due(self.config.batch_size, lease_until)
+            .await?;
+
+        let count = due.len();
+        for email in due {
+            let id = email.id;
+            if let Err(e) = self.retry(email).await {
+                error!("Failed to record outcome for outbox email {id}: {e:?}");
+            }
+        }
+
+        Ok(count)
+    }
+
+    async fn retry(&self, email: OutboxEmail) -> Result<(), ServiceError> {
+        let request: EmailRequest = match serde_json::from_value(email.payload) {
+            Ok(request) => request,
+            Err(e) => {
+                let reason = format!("Invalid outbox payload: {e}");
+                error!("Dead-lettering outbox email {}: {reason}", email.id);
+                return Ok(self.repository.mark_dead(email.id, &reason).await?);
+            }
+        };
+
+        let reason = match self.mailer.send(&request).await {
+            Ok(()) => {
+                info!(
+                    "✅ Outbox email {} delivered to {}",
+                    email.id, email.recipient
+                );
+                return Ok(self.repository.mark_sent(email.id).await?);
+            }
+            Err(ServiceError::Unavailable(reason)) => reason,
+            Err(e) => {
+                let reason = e.to_string();
+                error!("Dead-lettering outbox email {}: {reason}", email.id);
+                return Ok(self.repository.mark_dead(email.id, &reason).await?);
+            }
+        };
+
+        let attempts = email.attempts + 1;
+        if attempts >= self.config.max_attempts {
+            error!(
+                "Outbox email {} to {} exhausted {attempts} attempts, dead-lettering: {reason}",
+                email.id, email.recipient
+            );
+            return Ok(self.repository.mark_dead(email.id, &reason).await?);
+        }
+
+        let next_attempt_at = (Utc::now() + self.config.backoff(attempts)).naive_utc();
+        warn!(
+            "Outbox email {} attempt {attempts} failed, retrying at {next_attempt_at}: {reason}",
+            email.id
+        );
+
+        Ok(self
+            .repository
+            .reschedule(email.id, &reason, next_attempt_at)
+            .await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OutboxRelay;
+    use crate::{
+        abstract_trait::{EmailServiceTrait, OutboxRepositoryTrait},
+        config::OutboxConfig,
+        domain::EmailRequest,
+        model::{NewOutboxEmail, OutboxEmail},
+        repository::OutboxRepository,
+    };
+    use async_trait::async_trait;
+    use chrono::{Duration, NaiveDateTime, Utc};
+    use shared::errors::RepositoryError;
+    use shared::{errors::ServiceError, utils::EmailTemplateData};
+    use sqlx::PgPool;
+    use std::sync::{Arc, Mutex};
+
+    /// Fails with `Unavailable` for the first `failures` sends, then delivers.
+    struct FlakySmtp {
+        failures: Mutex<usize>,
+        delivered: Mutex<Vec<String>>,
+    }
+
+    impl FlakySmtp {
+        fn failing(failures: usize) -> Arc<Self> {
+            Arc::new(Self {
+                failures: Mutex::new(failures),
+                delivered: Mutex::default(),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl EmailServiceTrait for FlakySmtp {
+        async fn send(&self, req: &EmailRequest) -> Result<(), ServiceError> {
+            let mut failures = self.failures.lock().unwrap();
+            if *failures > 0 {
+                *failures -= 1;
+                return Err(ServiceError::Unavailable("SMTP timed out".into()));
+            }
+
+            self.delivered.lock().unwrap().push(req.to.clone());
+            Ok(())
+        }
+    }
+
+    /// Passes through to Postgres and records the size of every claim.
+    struct RecordingClaims {
+        inner: OutboxRepository,
+        claims: Mutex<Vec<usize>>,
+    }
+
+    #[async_trait]
+    impl OutboxRepositoryTrait for RecordingClaims {
+        async fn enqueue(&self, email: &NewOutboxEmail) -> Result<(), RepositoryError> {
+            self.inner.enqueue(email).await
+        }
+
+        async fn claim_due(
+            &self,
+            limit: i64,
+            lease_until: NaiveDateTime,
+        ) -> Result<Vec<OutboxEmail>, RepositoryError> {
+            let claimed = self.inner.claim_due(limit, lease_until).await?;
+            self.claims.lock().unwrap().push(claimed.len());
+            Ok(claimed)
+        }
+
+        async fn mark_sent(&self, id: i32) -> Result<(), RepositoryError> {
+            self.inner.mark_sent(id).await
+        }
+
+        async fn reschedule(
+            &self,
+            id: i32,
+            last_error: &str,
+            next_attempt_at: NaiveDateTime,
+        ) -> Result<(), RepositoryError> {
+            self.inner.reschedule(id, last_error, next_attempt_at).await
+        }
+
+        async fn mark_dead(&self, id: i32, last_error: &str) -> Result<(), RepositoryError> {
+            self.inner.mark_dead(id, last_error).await
+        }
+    }
+
+    fn config(max_attempts: i32) -> OutboxConfig {
+        OutboxConfig {
+            database_url: String::new(),
+            run_migrations: false,
+            max_attempts,
+            base_backoff_seconds: 30,
+            max_backoff_seconds: 600,
+            poll_interval_seconds: 5,
+            batch_size: 10,
+        }
+    }
+
+    /// The row `EmailHandler` parks after the first send failed transiently,
+    /// already due.
+    async fn park_failed_send(repository: &OutboxRepository) {
+        let request = EmailRequest {
+            to: "ada@example.com".into(),
+            subject: "Verify your email".into(),
+            data: EmailTemplateData {
+                title: "Welcome".into(),
+                message: "Confirm your address".into(),
+                button: "Verify".into(),
+                link: "https://shop.example.com/verify".into(),
+            },
+        };
+
+        repository
+            .enqueue(&NewOutboxEmail {
+                recipient: request.to.clone(),
+                subject: request.subject.clone(),
+                payload: serde_json::to_value(&request).unwrap(),
+                last_error: "SMTP timed out".into(),
+                next_attempt_at: (Utc::now() - Duration::seconds(1)).naive_utc(),
+            })
+            .await
+            .unwrap();
+    }
+
+    async fn row(pool: &PgPool) -> (String, i32, Option<String>, NaiveDateTime) {
+        sqlx::query_as("SELECT status, attempts, last_error, next_attempt_at FROM outbox_emails")
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    async fn make_due(pool: &PgPool) {
+        sqlx::query(
+            "UPDATE outbox_emails SET next_attempt_at = current_timestamp - interval '1 second'",
+        )
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "needs a Postgres server at DATABASE_URL"]
+    async fn transient_failures_are_retried_with_backoff_until_sent(pool: PgPool) {
+        let repository = Arc::new(OutboxRepository::new(pool.clone()));
+        park_failed_send(&repository).await;
+
+        let smtp = FlakySmtp::failing(1);
+        let relay = OutboxRelay::new(repository, smtp.clone(), config(5));
+
+        let before = Utc::now().naive_utc();
+        assert_eq!(relay.drain().await.unwrap(), 1);
+
+        let (status, attempts, last_error, next_attempt_at) = row(&pool).await;
+        assert_eq!((status.as_str(), attempts), ("pending", 2));
+        assert_eq!(last_error.as_deref(), Some("SMTP timed out"));
+        // Second failure backs off twice the base delay.
+        assert!(next_attempt_at >= before + Duration::seconds(60));
+        assert!(next_attempt_at <= Utc::now().naive_utc() + Duration::seconds(60));
+
+        // Not due yet, so another pass leaves it alone.
+        assert_eq!(relay.drain().await.unwrap(), 0);
+
+        make_due(&pool).await;
+        assert_eq!(relay.drain().await.unwrap(), 1);
+
+        let (status, attempts, _, _) = row(&pool).await;
+        assert_eq!((status.as_str(), attempts), ("sent", 2));
+        assert_eq!(*smtp.delivered.lock().unwrap(), ["ada@example.com"]);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "needs a Postgres server at DATABASE_URL"]
+    async fn running_out_of_attempts_dead_letters_the_row(pool: PgPool) {
+        let repository = Arc::new(OutboxRepository::new(pool.clone()));
+        park_failed_send(&repository).await;
+
+        let smtp = FlakySmtp::failing(usize::MAX);
+        let relay = OutboxRelay::new(repository, smtp.clone(), config(3));
+
+        relay.drain().await.unwrap();
+        let (status, attempts, _, _) = row(&pool).await;
+        assert_eq!((status.as_str(), attempts), ("pending", 2));
+
+        make_due(&pool).await;
+        relay.drain().await.unwrap();
+        let (status, attempts, last_error, _) = row(&pool).await;
+        assert_eq!((status.as_str(), attempts), ("dead", 3));
+        assert_eq!(last_error.as_deref(), Some("SMTP timed out"));
+
+        // Dead rows are never claimed again.
+        make_due(&pool).await;
+        assert_eq!(relay.drain().await.unwrap(), 0);
+        assert!(smtp.delivered.lock().unwrap().is_empty());
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "needs a Postgres server at DATABASE_URL"]
+    async fn a_backlog_larger_than_a_batch_is_drained_in_one_tick(pool: PgPool) {
+        let repository = Arc::new(RecordingClaims {
+            inner: OutboxRepository::new(pool.clone()),
+            claims: Mutex::default(),
+        });
+        for _ in 0..25 {
+            park_failed_send(&repository.inner).await;
+        }
+
+        let smtp = FlakySmtp::failing(0);
+        let relay = OutboxRelay::new(repository.clone(), smtp.clone(), config(5));
+
+        assert_eq!(relay.drain().await.unwrap(), 25);
+        assert_eq!(*repository.claims.lock().unwrap(), [10, 10, 5]);
+        assert_eq!(smtp.delivered.lock().unwrap().len(), 25);
+
+        let pending: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM outbox_emails WHERE status <> 'sent'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(pending, 0);
+    }
+}
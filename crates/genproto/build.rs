@@ -33,6 +33,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 "../../proto/user_role/user_role.proto",
                 // order item proto
                 "../../proto/order_item/orderitem.proto",
+                // health proto
+                "../../proto/health/health.proto",
             ],
             &["../../proto"],
         )?;
@@ -1,5 +1,5 @@
 // This file is @generated by prost-build.
-#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct Pagination {
     #[prost(int32, tag = "1")]
     pub current_page: i32,
@@ -9,6 +9,8 @@ pub struct Pagination {
     pub total_pages: i32,
     #[prost(int32, tag = "4")]
     pub total_records: i32,
+    #[prost(string, tag = "5")]
+    pub next_cursor: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct ErrorResponse {
@@ -19,3 +21,10 @@ pub struct ErrorResponse {
     #[prost(int32, tag = "3")]
     pub code: i32,
 }
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct BatchFailure {
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+    #[prost(string, tag = "2")]
+    pub error: ::prost::alloc::string::String,
+}
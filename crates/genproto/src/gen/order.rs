@@ -5,6 +5,11 @@ pub struct FindByIdOrderRequest {
     pub id: i32,
 }
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct FindByExternalIdOrderRequest {
+    #[prost(string, tag = "1")]
+    pub external_id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct OrderResponse {
     #[prost(int32, tag = "1")]
     pub id: i32,
@@ -16,6 +21,14 @@ pub struct OrderResponse {
     pub created_at: ::prost::alloc::string::String,
     #[prost(string, tag = "5")]
     pub updated_at: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "6")]
+    pub items: ::prost::alloc::vec::Vec<super::order_item::OrderItemResponse>,
+    #[prost(string, tag = "7")]
+    pub external_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "8")]
+    pub status: ::prost::alloc::string::String,
+    #[prost(string, tag = "9")]
+    pub order_number: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct OrderResponseDeleteAt {
@@ -31,8 +44,14 @@ pub struct OrderResponseDeleteAt {
     pub updated_at: ::prost::alloc::string::String,
     #[prost(message, optional, tag = "6")]
     pub deleted_at: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, tag = "7")]
+    pub external_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "8")]
+    pub status: ::prost::alloc::string::String,
+    #[prost(string, tag = "9")]
+    pub order_number: ::prost::alloc::string::String,
 }
-#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ApiResponseOrder {
     #[prost(string, tag = "1")]
     pub status: ::prost::alloc::string::String,
@@ -58,6 +77,32 @@ pub struct FindAllOrderRequest {
     pub page_size: i32,
     #[prost(string, tag = "3")]
     pub search: ::prost::alloc::string::String,
+    #[prost(bool, tag = "4")]
+    pub include_items: bool,
+    #[prost(string, tag = "5")]
+    pub after: ::prost::alloc::string::String,
+    #[prost(string, tag = "6")]
+    pub sort: ::prost::alloc::string::String,
+    /// Only orders placed by this user; 0 matches every user. FindAll only.
+    #[prost(int32, tag = "7")]
+    pub user_id: i32,
+    /// RFC3339 bounds on created_at, inclusive; empty leaves that side open.
+    /// FindAll only.
+    #[prost(string, tag = "8")]
+    pub created_from: ::prost::alloc::string::String,
+    #[prost(string, tag = "9")]
+    pub created_to: ::prost::alloc::string::String,
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct FindByUserIdOrderRequest {
+    #[prost(int32, tag = "1")]
+    pub user_id: i32,
+    #[prost(int32, tag = "2")]
+    pub page: i32,
+    #[prost(int32, tag = "3")]
+    pub page_size: i32,
+    #[prost(bool, tag = "4")]
+    pub include_items: bool,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ApiResponsePaginationOrderDeleteAt {
@@ -81,6 +126,62 @@ pub struct ApiResponsePaginationOrder {
     #[prost(message, optional, tag = "4")]
     pub pagination: ::core::option::Option<super::api::Pagination>,
 }
+/// An order item with the product's current name and price. A product that
+/// no longer exists gets a placeholder name and no current price.
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct OrderItemDetailResponse {
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+    #[prost(int32, tag = "2")]
+    pub order_id: i32,
+    #[prost(int32, tag = "3")]
+    pub product_id: i32,
+    #[prost(string, tag = "4")]
+    pub product_name: ::prost::alloc::string::String,
+    #[prost(int32, tag = "5")]
+    pub quantity: i32,
+    /// Unit price when the order was placed.
+    #[prost(int32, tag = "6")]
+    pub price: i32,
+    #[prost(int32, tag = "7")]
+    pub subtotal: i32,
+    #[prost(message, optional, tag = "8")]
+    pub current_price: ::core::option::Option<i64>,
+    #[prost(string, tag = "9")]
+    pub created_at: ::prost::alloc::string::String,
+    #[prost(string, tag = "10")]
+    pub updated_at: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct OrderDetailResponse {
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+    #[prost(string, tag = "2")]
+    pub external_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub order_number: ::prost::alloc::string::String,
+    #[prost(int32, tag = "4")]
+    pub user_id: i32,
+    #[prost(int32, tag = "5")]
+    pub total_price: i32,
+    #[prost(string, tag = "6")]
+    pub status: ::prost::alloc::string::String,
+    #[prost(string, tag = "7")]
+    pub created_at: ::prost::alloc::string::String,
+    #[prost(string, tag = "8")]
+    pub updated_at: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "9")]
+    pub items: ::prost::alloc::vec::Vec<OrderItemDetailResponse>,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct ApiResponseOrderDetail {
+    #[prost(string, tag = "1")]
+    pub status: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub message: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "3")]
+    pub data: ::core::option::Option<OrderDetailResponse>,
+}
 /// Generated client implementations.
 pub mod order_query_service_client {
     #![allow(
@@ -88,10 +189,10 @@ pub mod order_query_service_client {
         dead_code,
         missing_docs,
         clippy::wildcard_imports,
-        clippy::let_unit_value,
+        clippy::let_unit_value
     )]
-    use tonic::codegen::*;
     use tonic::codegen::http::Uri;
+    use tonic::codegen::*;
     #[derive(Debug, Clone)]
     pub struct OrderQueryServiceClient<T> {
         inner: tonic::client::Grpc<T>,
@@ -130,14 +231,13 @@ pub mod order_query_service_client {
             F: tonic::service::Interceptor,
             T::ResponseBody: Default,
             T: tonic::codegen::Service<
-                http::Request<tonic::body::Body>,
-                Response = http::Response<
-                    <T as tonic::client::GrpcService<tonic::body::Body>>::ResponseBody,
+                    http::Request<tonic::body::Body>,
+                    Response = http::Response<
+                        <T as tonic::client::GrpcService<tonic::body::Body>>::ResponseBody,
+                    >,
                 >,
-            >,
-            <T as tonic::codegen::Service<
-                http::Request<tonic::body::Body>,
-            >>::Error: Into<StdError> + std::marker::Send + std::marker::Sync,
+            <T as tonic::codegen::Service<http::Request<tonic::body::Body>>>::Error:
+                Into<StdError> + std::marker::Send + std::marker::Sync,
         {
             OrderQueryServiceClient::new(InterceptedService::new(inner, interceptor))
         }
@@ -175,22 +275,13 @@ pub mod order_query_service_client {
         pub async fn find_all(
             &mut self,
             request: impl tonic::IntoRequest<super::FindAllOrderRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::ApiResponsePaginationOrder>,
-            tonic::Status,
-        > {
-            self.inner
-                .ready()
-                .await
-                .map_err(|e| {
-                    tonic::Status::unknown(
-                        format!("Service was not ready: {}", e.into()),
-                    )
-                })?;
+        ) -> std::result::Result<tonic::Response<super::ApiResponsePaginationOrder>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
             let codec = tonic_prost::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static(
-                "/order.OrderQueryService/FindAll",
-            );
+            let path = http::uri::PathAndQuery::from_static("/order.OrderQueryService/FindAll");
             let mut req = request.into_request();
             req.extensions_mut()
                 .insert(GrpcMethod::new("order.OrderQueryService", "FindAll"));
@@ -199,27 +290,34 @@ pub mod order_query_service_client {
         pub async fn find_by_id(
             &mut self,
             request: impl tonic::IntoRequest<super::FindByIdOrderRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::ApiResponseOrder>,
-            tonic::Status,
-        > {
-            self.inner
-                .ready()
-                .await
-                .map_err(|e| {
-                    tonic::Status::unknown(
-                        format!("Service was not ready: {}", e.into()),
-                    )
-                })?;
+        ) -> std::result::Result<tonic::Response<super::ApiResponseOrder>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
             let codec = tonic_prost::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static(
-                "/order.OrderQueryService/FindById",
-            );
+            let path = http::uri::PathAndQuery::from_static("/order.OrderQueryService/FindById");
             let mut req = request.into_request();
             req.extensions_mut()
                 .insert(GrpcMethod::new("order.OrderQueryService", "FindById"));
             self.inner.unary(req, path, codec).await
         }
+        pub async fn find_by_external_id(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FindByExternalIdOrderRequest>,
+        ) -> std::result::Result<tonic::Response<super::ApiResponseOrder>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/order.OrderQueryService/FindByExternalId");
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new(
+                "order.OrderQueryService",
+                "FindByExternalId",
+            ));
+            self.inner.unary(req, path, codec).await
+        }
         pub async fn find_by_active(
             &mut self,
             request: impl tonic::IntoRequest<super::FindAllOrderRequest>,
@@ -227,18 +325,12 @@ pub mod order_query_service_client {
             tonic::Response<super::ApiResponsePaginationOrderDeleteAt>,
             tonic::Status,
         > {
-            self.inner
-                .ready()
-                .await
-                .map_err(|e| {
-                    tonic::Status::unknown(
-                        format!("Service was not ready: {}", e.into()),
-                    )
-                })?;
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
             let codec = tonic_prost::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static(
-                "/order.OrderQueryService/FindByActive",
-            );
+            let path =
+                http::uri::PathAndQuery::from_static("/order.OrderQueryService/FindByActive");
             let mut req = request.into_request();
             req.extensions_mut()
                 .insert(GrpcMethod::new("order.OrderQueryService", "FindByActive"));
@@ -251,23 +343,49 @@ pub mod order_query_service_client {
             tonic::Response<super::ApiResponsePaginationOrderDeleteAt>,
             tonic::Status,
         > {
-            self.inner
-                .ready()
-                .await
-                .map_err(|e| {
-                    tonic::Status::unknown(
-                        format!("Service was not ready: {}", e.into()),
-                    )
-                })?;
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
             let codec = tonic_prost::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static(
-                "/order.OrderQueryService/FindByTrashed",
-            );
+            let path =
+                http::uri::PathAndQuery::from_static("/order.OrderQueryService/FindByTrashed");
             let mut req = request.into_request();
             req.extensions_mut()
                 .insert(GrpcMethod::new("order.OrderQueryService", "FindByTrashed"));
             self.inner.unary(req, path, codec).await
         }
+        pub async fn find_by_user_id(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FindByUserIdOrderRequest>,
+        ) -> std::result::Result<tonic::Response<super::ApiResponsePaginationOrder>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/order.OrderQueryService/FindByUserId");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("order.OrderQueryService", "FindByUserId"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn find_detail_by_id(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FindByIdOrderRequest>,
+        ) -> std::result::Result<tonic::Response<super::ApiResponseOrderDetail>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/order.OrderQueryService/FindDetailById");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("order.OrderQueryService", "FindDetailById"));
+            self.inner.unary(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -277,7 +395,7 @@ pub mod order_query_service_server {
         dead_code,
         missing_docs,
         clippy::wildcard_imports,
-        clippy::let_unit_value,
+        clippy::let_unit_value
     )]
     use tonic::codegen::*;
     /// Generated trait containing gRPC methods that should be implemented for use with OrderQueryServiceServer.
@@ -286,17 +404,15 @@ pub mod order_query_service_server {
         async fn find_all(
             &self,
             request: tonic::Request<super::FindAllOrderRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::ApiResponsePaginationOrder>,
-            tonic::Status,
-        >;
+        ) -> std::result::Result<tonic::Response<super::ApiResponsePaginationOrder>, tonic::Status>;
         async fn find_by_id(
             &self,
             request: tonic::Request<super::FindByIdOrderRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::ApiResponseOrder>,
-            tonic::Status,
-        >;
+        ) -> std::result::Result<tonic::Response<super::ApiResponseOrder>, tonic::Status>;
+        async fn find_by_external_id(
+            &self,
+            request: tonic::Request<super::FindByExternalIdOrderRequest>,
+        ) -> std::result::Result<tonic::Response<super::ApiResponseOrder>, tonic::Status>;
         async fn find_by_active(
             &self,
             request: tonic::Request<super::FindAllOrderRequest>,
@@ -311,6 +427,14 @@ pub mod order_query_service_server {
             tonic::Response<super::ApiResponsePaginationOrderDeleteAt>,
             tonic::Status,
         >;
+        async fn find_by_user_id(
+            &self,
+            request: tonic::Request<super::FindByUserIdOrderRequest>,
+        ) -> std::result::Result<tonic::Response<super::ApiResponsePaginationOrder>, tonic::Status>;
+        async fn find_detail_by_id(
+            &self,
+            request: tonic::Request<super::FindByIdOrderRequest>,
+        ) -> std::result::Result<tonic::Response<super::ApiResponseOrderDetail>, tonic::Status>;
     }
     #[derive(Debug)]
     pub struct OrderQueryServiceServer<T> {
@@ -333,10 +457,7 @@ pub mod order_query_service_server {
                 max_encoding_message_size: None,
             }
         }
-        pub fn with_interceptor<F>(
-            inner: T,
-            interceptor: F,
-        ) -> InterceptedService<Self, F>
+        pub fn with_interceptor<F>(inner: T, interceptor: F) -> InterceptedService<Self, F>
         where
             F: tonic::service::Interceptor,
         {
@@ -391,15 +512,11 @@ pub mod order_query_service_server {
                 "/order.OrderQueryService/FindAll" => {
                     #[allow(non_camel_case_types)]
                     struct FindAllSvc<T: OrderQueryService>(pub Arc<T>);
-                    impl<
-                        T: OrderQueryService,
-                    > tonic::server::UnaryService<super::FindAllOrderRequest>
-                    for FindAllSvc<T> {
+                    impl<T: OrderQueryService>
+                        tonic::server::UnaryService<super::FindAllOrderRequest> for FindAllSvc<T>
+                    {
                         type Response = super::ApiResponsePaginationOrder;
-                        type Future = BoxFuture<
-                            tonic::Response<Self::Response>,
-                            tonic::Status,
-                        >;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
                             request: tonic::Request<super::FindAllOrderRequest>,
@@ -436,15 +553,12 @@ pub mod order_query_service_server {
                 "/order.OrderQueryService/FindById" => {
                     #[allow(non_camel_case_types)]
                     struct FindByIdSvc<T: OrderQueryService>(pub Arc<T>);
-                    impl<
-                        T: OrderQueryService,
-                    > tonic::server::UnaryService<super::FindByIdOrderRequest>
-                    for FindByIdSvc<T> {
+                    impl<T: OrderQueryService>
+                        tonic::server::UnaryService<super::FindByIdOrderRequest>
+                        for FindByIdSvc<T>
+                    {
                         type Response = super::ApiResponseOrder;
-                        type Future = BoxFuture<
-                            tonic::Response<Self::Response>,
-                            tonic::Status,
-                        >;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
                             request: tonic::Request<super::FindByIdOrderRequest>,
@@ -478,26 +592,64 @@ pub mod order_query_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/order.OrderQueryService/FindByExternalId" => {
+                    #[allow(non_camel_case_types)]
+                    struct FindByExternalIdSvc<T: OrderQueryService>(pub Arc<T>);
+                    impl<T: OrderQueryService>
+                        tonic::server::UnaryService<super::FindByExternalIdOrderRequest>
+                        for FindByExternalIdSvc<T>
+                    {
+                        type Response = super::ApiResponseOrder;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FindByExternalIdOrderRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as OrderQueryService>::find_by_external_id(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = FindByExternalIdSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/order.OrderQueryService/FindByActive" => {
                     #[allow(non_camel_case_types)]
                     struct FindByActiveSvc<T: OrderQueryService>(pub Arc<T>);
-                    impl<
-                        T: OrderQueryService,
-                    > tonic::server::UnaryService<super::FindAllOrderRequest>
-                    for FindByActiveSvc<T> {
+                    impl<T: OrderQueryService>
+                        tonic::server::UnaryService<super::FindAllOrderRequest>
+                        for FindByActiveSvc<T>
+                    {
                         type Response = super::ApiResponsePaginationOrderDeleteAt;
-                        type Future = BoxFuture<
-                            tonic::Response<Self::Response>,
-                            tonic::Status,
-                        >;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
                             request: tonic::Request<super::FindAllOrderRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as OrderQueryService>::find_by_active(&inner, request)
-                                    .await
+                                <T as OrderQueryService>::find_by_active(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -527,23 +679,19 @@ pub mod order_query_service_server {
                 "/order.OrderQueryService/FindByTrashed" => {
                     #[allow(non_camel_case_types)]
                     struct FindByTrashedSvc<T: OrderQueryService>(pub Arc<T>);
-                    impl<
-                        T: OrderQueryService,
-                    > tonic::server::UnaryService<super::FindAllOrderRequest>
-                    for FindByTrashedSvc<T> {
+                    impl<T: OrderQueryService>
+                        tonic::server::UnaryService<super::FindAllOrderRequest>
+                        for FindByTrashedSvc<T>
+                    {
                         type Response = super::ApiResponsePaginationOrderDeleteAt;
-                        type Future = BoxFuture<
-                            tonic::Response<Self::Response>,
-                            tonic::Status,
-                        >;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
                             request: tonic::Request<super::FindAllOrderRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as OrderQueryService>::find_by_trashed(&inner, request)
-                                    .await
+                                <T as OrderQueryService>::find_by_trashed(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -570,25 +718,103 @@ pub mod order_query_service_server {
                     };
                     Box::pin(fut)
                 }
-                _ => {
-                    Box::pin(async move {
-                        let mut response = http::Response::new(
-                            tonic::body::Body::default(),
-                        );
-                        let headers = response.headers_mut();
-                        headers
-                            .insert(
-                                tonic::Status::GRPC_STATUS,
-                                (tonic::Code::Unimplemented as i32).into(),
+                "/order.OrderQueryService/FindByUserId" => {
+                    #[allow(non_camel_case_types)]
+                    struct FindByUserIdSvc<T: OrderQueryService>(pub Arc<T>);
+                    impl<T: OrderQueryService>
+                        tonic::server::UnaryService<super::FindByUserIdOrderRequest>
+                        for FindByUserIdSvc<T>
+                    {
+                        type Response = super::ApiResponsePaginationOrder;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FindByUserIdOrderRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as OrderQueryService>::find_by_user_id(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = FindByUserIdSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
                             );
-                        headers
-                            .insert(
-                                http::header::CONTENT_TYPE,
-                                tonic::metadata::GRPC_CONTENT_TYPE,
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/order.OrderQueryService/FindDetailById" => {
+                    #[allow(non_camel_case_types)]
+                    struct FindDetailByIdSvc<T: OrderQueryService>(pub Arc<T>);
+                    impl<T: OrderQueryService>
+                        tonic::server::UnaryService<super::FindByIdOrderRequest>
+                        for FindDetailByIdSvc<T>
+                    {
+                        type Response = super::ApiResponseOrderDetail;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FindByIdOrderRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as OrderQueryService>::find_detail_by_id(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = FindDetailByIdSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
                             );
-                        Ok(response)
-                    })
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
                 }
+                _ => Box::pin(async move {
+                    let mut response = http::Response::new(tonic::body::Body::default());
+                    let headers = response.headers_mut();
+                    headers.insert(
+                        tonic::Status::GRPC_STATUS,
+                        (tonic::Code::Unimplemented as i32).into(),
+                    );
+                    headers.insert(
+                        http::header::CONTENT_TYPE,
+                        tonic::metadata::GRPC_CONTENT_TYPE,
+                    );
+                    Ok(response)
+                }),
             }
         }
     }
@@ -636,6 +862,8 @@ pub struct CreateOrderRequest {
     pub user_id: i32,
     #[prost(message, repeated, tag = "2")]
     pub items: ::prost::alloc::vec::Vec<CreateOrderItemRequest>,
+    #[prost(string, tag = "3")]
+    pub idempotency_key: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct UpdateOrderRequest {
@@ -645,6 +873,12 @@ pub struct UpdateOrderRequest {
     pub user_id: i32,
     #[prost(message, repeated, tag = "3")]
     pub items: ::prost::alloc::vec::Vec<UpdateOrderItemRequest>,
+    #[prost(string, tag = "4")]
+    pub status: ::prost::alloc::string::String,
+    /// Set by the gateway for ADMIN and MODERATOR callers; anyone else may
+    /// only cancel.
+    #[prost(bool, tag = "5")]
+    pub caller_is_staff: bool,
 }
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct ApiResponseOrderDelete {
@@ -667,10 +901,10 @@ pub mod order_command_service_client {
         dead_code,
         missing_docs,
         clippy::wildcard_imports,
-        clippy::let_unit_value,
+        clippy::let_unit_value
     )]
-    use tonic::codegen::*;
     use tonic::codegen::http::Uri;
+    use tonic::codegen::*;
     #[derive(Debug, Clone)]
     pub struct OrderCommandServiceClient<T> {
         inner: tonic::client::Grpc<T>,
@@ -709,14 +943,13 @@ pub mod order_command_service_client {
             F: tonic::service::Interceptor,
             T::ResponseBody: Default,
             T: tonic::codegen::Service<
-                http::Request<tonic::body::Body>,
-                Response = http::Response<
-                    <T as tonic::client::GrpcService<tonic::body::Body>>::ResponseBody,
+                    http::Request<tonic::body::Body>,
+                    Response = http::Response<
+                        <T as tonic::client::GrpcService<tonic::body::Body>>::ResponseBody,
+                    >,
                 >,
-            >,
-            <T as tonic::codegen::Service<
-                http::Request<tonic::body::Body>,
-            >>::Error: Into<StdError> + std::marker::Send + std::marker::Sync,
+            <T as tonic::codegen::Service<http::Request<tonic::body::Body>>>::Error:
+                Into<StdError> + std::marker::Send + std::marker::Sync,
         {
             OrderCommandServiceClient::new(InterceptedService::new(inner, interceptor))
         }
@@ -754,22 +987,12 @@ pub mod order_command_service_client {
         pub async fn create(
             &mut self,
             request: impl tonic::IntoRequest<super::CreateOrderRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::ApiResponseOrder>,
-            tonic::Status,
-        > {
-            self.inner
-                .ready()
-                .await
-                .map_err(|e| {
-                    tonic::Status::unknown(
-                        format!("Service was not ready: {}", e.into()),
-                    )
-                })?;
+        ) -> std::result::Result<tonic::Response<super::ApiResponseOrder>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
             let codec = tonic_prost::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static(
-                "/order.OrderCommandService/Create",
-            );
+            let path = http::uri::PathAndQuery::from_static("/order.OrderCommandService/Create");
             let mut req = request.into_request();
             req.extensions_mut()
                 .insert(GrpcMethod::new("order.OrderCommandService", "Create"));
@@ -778,22 +1001,12 @@ pub mod order_command_service_client {
         pub async fn update(
             &mut self,
             request: impl tonic::IntoRequest<super::UpdateOrderRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::ApiResponseOrder>,
-            tonic::Status,
-        > {
-            self.inner
-                .ready()
-                .await
-                .map_err(|e| {
-                    tonic::Status::unknown(
-                        format!("Service was not ready: {}", e.into()),
-                    )
-                })?;
+        ) -> std::result::Result<tonic::Response<super::ApiResponseOrder>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
             let codec = tonic_prost::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static(
-                "/order.OrderCommandService/Update",
-            );
+            let path = http::uri::PathAndQuery::from_static("/order.OrderCommandService/Update");
             let mut req = request.into_request();
             req.extensions_mut()
                 .insert(GrpcMethod::new("order.OrderCommandService", "Update"));
@@ -802,22 +1015,13 @@ pub mod order_command_service_client {
         pub async fn trashed(
             &mut self,
             request: impl tonic::IntoRequest<super::FindByIdOrderRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::ApiResponseOrderDeleteAt>,
-            tonic::Status,
-        > {
-            self.inner
-                .ready()
-                .await
-                .map_err(|e| {
-                    tonic::Status::unknown(
-                        format!("Service was not ready: {}", e.into()),
-                    )
-                })?;
+        ) -> std::result::Result<tonic::Response<super::ApiResponseOrderDeleteAt>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
             let codec = tonic_prost::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static(
-                "/order.OrderCommandService/Trashed",
-            );
+            let path = http::uri::PathAndQuery::from_static("/order.OrderCommandService/Trashed");
             let mut req = request.into_request();
             req.extensions_mut()
                 .insert(GrpcMethod::new("order.OrderCommandService", "Trashed"));
@@ -826,22 +1030,13 @@ pub mod order_command_service_client {
         pub async fn restore(
             &mut self,
             request: impl tonic::IntoRequest<super::FindByIdOrderRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::ApiResponseOrderDeleteAt>,
-            tonic::Status,
-        > {
-            self.inner
-                .ready()
-                .await
-                .map_err(|e| {
-                    tonic::Status::unknown(
-                        format!("Service was not ready: {}", e.into()),
-                    )
-                })?;
+        ) -> std::result::Result<tonic::Response<super::ApiResponseOrderDeleteAt>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
             let codec = tonic_prost::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static(
-                "/order.OrderCommandService/Restore",
-            );
+            let path = http::uri::PathAndQuery::from_static("/order.OrderCommandService/Restore");
             let mut req = request.into_request();
             req.extensions_mut()
                 .insert(GrpcMethod::new("order.OrderCommandService", "Restore"));
@@ -850,75 +1045,56 @@ pub mod order_command_service_client {
         pub async fn delete_order_permanent(
             &mut self,
             request: impl tonic::IntoRequest<super::FindByIdOrderRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::ApiResponseOrderDelete>,
-            tonic::Status,
-        > {
-            self.inner
-                .ready()
-                .await
-                .map_err(|e| {
-                    tonic::Status::unknown(
-                        format!("Service was not ready: {}", e.into()),
-                    )
-                })?;
+        ) -> std::result::Result<tonic::Response<super::ApiResponseOrderDelete>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
             let codec = tonic_prost::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
                 "/order.OrderCommandService/DeleteOrderPermanent",
             );
             let mut req = request.into_request();
-            req.extensions_mut()
-                .insert(
-                    GrpcMethod::new("order.OrderCommandService", "DeleteOrderPermanent"),
-                );
+            req.extensions_mut().insert(GrpcMethod::new(
+                "order.OrderCommandService",
+                "DeleteOrderPermanent",
+            ));
             self.inner.unary(req, path, codec).await
         }
         pub async fn restore_all_order(
             &mut self,
             request: impl tonic::IntoRequest<()>,
-        ) -> std::result::Result<
-            tonic::Response<super::ApiResponseOrderAll>,
-            tonic::Status,
-        > {
-            self.inner
-                .ready()
-                .await
-                .map_err(|e| {
-                    tonic::Status::unknown(
-                        format!("Service was not ready: {}", e.into()),
-                    )
-                })?;
+        ) -> std::result::Result<tonic::Response<super::ApiResponseOrderAll>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
             let codec = tonic_prost::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static(
-                "/order.OrderCommandService/RestoreAllOrder",
-            );
+            let path =
+                http::uri::PathAndQuery::from_static("/order.OrderCommandService/RestoreAllOrder");
             let mut req = request.into_request();
-            req.extensions_mut()
-                .insert(GrpcMethod::new("order.OrderCommandService", "RestoreAllOrder"));
+            req.extensions_mut().insert(GrpcMethod::new(
+                "order.OrderCommandService",
+                "RestoreAllOrder",
+            ));
             self.inner.unary(req, path, codec).await
         }
         pub async fn delete_all_order(
             &mut self,
             request: impl tonic::IntoRequest<()>,
-        ) -> std::result::Result<
-            tonic::Response<super::ApiResponseOrderAll>,
-            tonic::Status,
-        > {
-            self.inner
-                .ready()
-                .await
-                .map_err(|e| {
-                    tonic::Status::unknown(
-                        format!("Service was not ready: {}", e.into()),
-                    )
-                })?;
+        ) -> std::result::Result<tonic::Response<super::ApiResponseOrderAll>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
             let codec = tonic_prost::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static(
-                "/order.OrderCommandService/DeleteAllOrder",
-            );
+            let path =
+                http::uri::PathAndQuery::from_static("/order.OrderCommandService/DeleteAllOrder");
             let mut req = request.into_request();
-            req.extensions_mut()
-                .insert(GrpcMethod::new("order.OrderCommandService", "DeleteAllOrder"));
+            req.extensions_mut().insert(GrpcMethod::new(
+                "order.OrderCommandService",
+                "DeleteAllOrder",
+            ));
             self.inner.unary(req, path, codec).await
         }
     }
@@ -930,7 +1106,7 @@ pub mod order_command_service_server {
         dead_code,
         missing_docs,
         clippy::wildcard_imports,
-        clippy::let_unit_value,
+        clippy::let_unit_value
     )]
     use tonic::codegen::*;
     /// Generated trait containing gRPC methods that should be implemented for use with OrderCommandServiceServer.
@@ -939,52 +1115,31 @@ pub mod order_command_service_server {
         async fn create(
             &self,
             request: tonic::Request<super::CreateOrderRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::ApiResponseOrder>,
-            tonic::Status,
-        >;
+        ) -> std::result::Result<tonic::Response<super::ApiResponseOrder>, tonic::Status>;
         async fn update(
             &self,
             request: tonic::Request<super::UpdateOrderRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::ApiResponseOrder>,
-            tonic::Status,
-        >;
+        ) -> std::result::Result<tonic::Response<super::ApiResponseOrder>, tonic::Status>;
         async fn trashed(
             &self,
             request: tonic::Request<super::FindByIdOrderRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::ApiResponseOrderDeleteAt>,
-            tonic::Status,
-        >;
+        ) -> std::result::Result<tonic::Response<super::ApiResponseOrderDeleteAt>, tonic::Status>;
         async fn restore(
             &self,
             request: tonic::Request<super::FindByIdOrderRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::ApiResponseOrderDeleteAt>,
-            tonic::Status,
-        >;
+        ) -> std::result::Result<tonic::Response<super::ApiResponseOrderDeleteAt>, tonic::Status>;
         async fn delete_order_permanent(
             &self,
             request: tonic::Request<super::FindByIdOrderRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::ApiResponseOrderDelete>,
-            tonic::Status,
-        >;
+        ) -> std::result::Result<tonic::Response<super::ApiResponseOrderDelete>, tonic::Status>;
         async fn restore_all_order(
             &self,
             request: tonic::Request<()>,
-        ) -> std::result::Result<
-            tonic::Response<super::ApiResponseOrderAll>,
-            tonic::Status,
-        >;
+        ) -> std::result::Result<tonic::Response<super::ApiResponseOrderAll>, tonic::Status>;
         async fn delete_all_order(
             &self,
             request: tonic::Request<()>,
-        ) -> std::result::Result<
-            tonic::Response<super::ApiResponseOrderAll>,
-            tonic::Status,
-        >;
+        ) -> std::result::Result<tonic::Response<super::ApiResponseOrderAll>, tonic::Status>;
     }
     #[derive(Debug)]
     pub struct OrderCommandServiceServer<T> {
@@ -1007,10 +1162,7 @@ pub mod order_command_service_server {
                 max_encoding_message_size: None,
             }
         }
-        pub fn with_interceptor<F>(
-            inner: T,
-            interceptor: F,
-        ) -> InterceptedService<Self, F>
+        pub fn with_interceptor<F>(inner: T, interceptor: F) -> InterceptedService<Self, F>
         where
             F: tonic::service::Interceptor,
         {
@@ -1065,15 +1217,11 @@ pub mod order_command_service_server {
                 "/order.OrderCommandService/Create" => {
                     #[allow(non_camel_case_types)]
                     struct CreateSvc<T: OrderCommandService>(pub Arc<T>);
-                    impl<
-                        T: OrderCommandService,
-                    > tonic::server::UnaryService<super::CreateOrderRequest>
-                    for CreateSvc<T> {
+                    impl<T: OrderCommandService>
+                        tonic::server::UnaryService<super::CreateOrderRequest> for CreateSvc<T>
+                    {
                         type Response = super::ApiResponseOrder;
-                        type Future = BoxFuture<
-                            tonic::Response<Self::Response>,
-                            tonic::Status,
-                        >;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
                             request: tonic::Request<super::CreateOrderRequest>,
@@ -1110,15 +1258,11 @@ pub mod order_command_service_server {
                 "/order.OrderCommandService/Update" => {
                     #[allow(non_camel_case_types)]
                     struct UpdateSvc<T: OrderCommandService>(pub Arc<T>);
-                    impl<
-                        T: OrderCommandService,
-                    > tonic::server::UnaryService<super::UpdateOrderRequest>
-                    for UpdateSvc<T> {
+                    impl<T: OrderCommandService>
+                        tonic::server::UnaryService<super::UpdateOrderRequest> for UpdateSvc<T>
+                    {
                         type Response = super::ApiResponseOrder;
-                        type Future = BoxFuture<
-                            tonic::Response<Self::Response>,
-                            tonic::Status,
-                        >;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
                             request: tonic::Request<super::UpdateOrderRequest>,
@@ -1155,15 +1299,11 @@ pub mod order_command_service_server {
                 "/order.OrderCommandService/Trashed" => {
                     #[allow(non_camel_case_types)]
                     struct TrashedSvc<T: OrderCommandService>(pub Arc<T>);
-                    impl<
-                        T: OrderCommandService,
-                    > tonic::server::UnaryService<super::FindByIdOrderRequest>
-                    for TrashedSvc<T> {
+                    impl<T: OrderCommandService>
+                        tonic::server::UnaryService<super::FindByIdOrderRequest> for TrashedSvc<T>
+                    {
                         type Response = super::ApiResponseOrderDeleteAt;
-                        type Future = BoxFuture<
-                            tonic::Response<Self::Response>,
-                            tonic::Status,
-                        >;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
                             request: tonic::Request<super::FindByIdOrderRequest>,
@@ -1200,15 +1340,11 @@ pub mod order_command_service_server {
                 "/order.OrderCommandService/Restore" => {
                     #[allow(non_camel_case_types)]
                     struct RestoreSvc<T: OrderCommandService>(pub Arc<T>);
-                    impl<
-                        T: OrderCommandService,
-                    > tonic::server::UnaryService<super::FindByIdOrderRequest>
-                    for RestoreSvc<T> {
+                    impl<T: OrderCommandService>
+                        tonic::server::UnaryService<super::FindByIdOrderRequest> for RestoreSvc<T>
+                    {
                         type Response = super::ApiResponseOrderDeleteAt;
-                        type Future = BoxFuture<
-                            tonic::Response<Self::Response>,
-                            tonic::Status,
-                        >;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
                             request: tonic::Request<super::FindByIdOrderRequest>,
@@ -1245,25 +1381,19 @@ pub mod order_command_service_server {
                 "/order.OrderCommandService/DeleteOrderPermanent" => {
                     #[allow(non_camel_case_types)]
                     struct DeleteOrderPermanentSvc<T: OrderCommandService>(pub Arc<T>);
-                    impl<
-                        T: OrderCommandService,
-                    > tonic::server::UnaryService<super::FindByIdOrderRequest>
-                    for DeleteOrderPermanentSvc<T> {
+                    impl<T: OrderCommandService>
+                        tonic::server::UnaryService<super::FindByIdOrderRequest>
+                        for DeleteOrderPermanentSvc<T>
+                    {
                         type Response = super::ApiResponseOrderDelete;
-                        type Future = BoxFuture<
-                            tonic::Response<Self::Response>,
-                            tonic::Status,
-                        >;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
                             request: tonic::Request<super::FindByIdOrderRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as OrderCommandService>::delete_order_permanent(
-                                        &inner,
-                                        request,
-                                    )
+                                <T as OrderCommandService>::delete_order_permanent(&inner, request)
                                     .await
                             };
                             Box::pin(fut)
@@ -1294,21 +1424,13 @@ pub mod order_command_service_server {
                 "/order.OrderCommandService/RestoreAllOrder" => {
                     #[allow(non_camel_case_types)]
                     struct RestoreAllOrderSvc<T: OrderCommandService>(pub Arc<T>);
-                    impl<T: OrderCommandService> tonic::server::UnaryService<()>
-                    for RestoreAllOrderSvc<T> {
+                    impl<T: OrderCommandService> tonic::server::UnaryService<()> for RestoreAllOrderSvc<T> {
                         type Response = super::ApiResponseOrderAll;
-                        type Future = BoxFuture<
-                            tonic::Response<Self::Response>,
-                            tonic::Status,
-                        >;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(&mut self, request: tonic::Request<()>) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as OrderCommandService>::restore_all_order(
-                                        &inner,
-                                        request,
-                                    )
-                                    .await
+                                <T as OrderCommandService>::restore_all_order(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -1338,21 +1460,13 @@ pub mod order_command_service_server {
                 "/order.OrderCommandService/DeleteAllOrder" => {
                     #[allow(non_camel_case_types)]
                     struct DeleteAllOrderSvc<T: OrderCommandService>(pub Arc<T>);
-                    impl<T: OrderCommandService> tonic::server::UnaryService<()>
-                    for DeleteAllOrderSvc<T> {
+                    impl<T: OrderCommandService> tonic::server::UnaryService<()> for DeleteAllOrderSvc<T> {
                         type Response = super::ApiResponseOrderAll;
-                        type Future = BoxFuture<
-                            tonic::Response<Self::Response>,
-                            tonic::Status,
-                        >;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(&mut self, request: tonic::Request<()>) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as OrderCommandService>::delete_all_order(
-                                        &inner,
-                                        request,
-                                    )
-                                    .await
+                                <T as OrderCommandService>::delete_all_order(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -1379,25 +1493,19 @@ pub mod order_command_service_server {
                     };
                     Box::pin(fut)
                 }
-                _ => {
-                    Box::pin(async move {
-                        let mut response = http::Response::new(
-                            tonic::body::Body::default(),
-                        );
-                        let headers = response.headers_mut();
-                        headers
-                            .insert(
-                                tonic::Status::GRPC_STATUS,
-                                (tonic::Code::Unimplemented as i32).into(),
-                            );
-                        headers
-                            .insert(
-                                http::header::CONTENT_TYPE,
-                                tonic::metadata::GRPC_CONTENT_TYPE,
-                            );
-                        Ok(response)
-                    })
-                }
+                _ => Box::pin(async move {
+                    let mut response = http::Response::new(tonic::body::Body::default());
+                    let headers = response.headers_mut();
+                    headers.insert(
+                        tonic::Status::GRPC_STATUS,
+                        (tonic::Code::Unimplemented as i32).into(),
+                    );
+                    headers.insert(
+                        http::header::CONTENT_TYPE,
+                        tonic::metadata::GRPC_CONTENT_TYPE,
+                    );
+                    Ok(response)
+                }),
             }
         }
     }
@@ -29,6 +29,8 @@ pub struct OrderItemResponse {
     pub created_at: ::prost::alloc::string::String,
     #[prost(string, tag = "7")]
     pub updated_at: ::prost::alloc::string::String,
+    #[prost(int32, tag = "8")]
+    pub subtotal: i32,
 }
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct OrderItemResponseDeleteAt {
@@ -5,6 +5,18 @@ pub struct FindByIdProductRequest {
     pub id: i32,
 }
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct FindByExternalIdProductRequest {
+    #[prost(string, tag = "1")]
+    pub external_id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct ProductAttribute {
+    #[prost(string, tag = "1")]
+    pub key: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub value: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct ProductResponse {
     #[prost(int32, tag = "1")]
     pub id: i32,
@@ -18,6 +30,16 @@ pub struct ProductResponse {
     pub created_at: ::prost::alloc::string::String,
     #[prost(string, tag = "6")]
     pub updated_at: ::prost::alloc::string::String,
+    #[prost(string, tag = "7")]
+    pub external_id: ::prost::alloc::string::String,
+    #[prost(string, repeated, tag = "8")]
+    pub images: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(message, optional, tag = "9")]
+    pub reorder_threshold: ::core::option::Option<i32>,
+    #[prost(message, optional, tag = "10")]
+    pub category: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(message, repeated, tag = "11")]
+    pub attributes: ::prost::alloc::vec::Vec<ProductAttribute>,
 }
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct ProductResponseDeleteAt {
@@ -35,6 +57,16 @@ pub struct ProductResponseDeleteAt {
     pub updated_at: ::prost::alloc::string::String,
     #[prost(message, optional, tag = "7")]
     pub deleted_at: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, tag = "8")]
+    pub external_id: ::prost::alloc::string::String,
+    #[prost(string, repeated, tag = "9")]
+    pub images: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(message, optional, tag = "10")]
+    pub reorder_threshold: ::core::option::Option<i32>,
+    #[prost(message, optional, tag = "11")]
+    pub category: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(message, repeated, tag = "12")]
+    pub attributes: ::prost::alloc::vec::Vec<ProductAttribute>,
 }
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct ApiResponseProduct {
@@ -62,6 +94,33 @@ pub struct FindAllProductRequest {
     pub page_size: i32,
     #[prost(string, tag = "3")]
     pub search: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "4")]
+    pub min_price: ::core::option::Option<i64>,
+    #[prost(message, optional, tag = "5")]
+    pub max_price: ::core::option::Option<i64>,
+    #[prost(string, tag = "6")]
+    pub after: ::prost::alloc::string::String,
+    #[prost(string, tag = "7")]
+    pub sort: ::prost::alloc::string::String,
+    #[prost(string, tag = "8")]
+    pub attribute: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct FindByIdsProductRequest {
+    #[prost(int32, repeated, tag = "1")]
+    pub ids: ::prost::alloc::vec::Vec<i32>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ApiResponsesProduct {
+    #[prost(string, tag = "1")]
+    pub status: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub message: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "3")]
+    pub succeeded: ::prost::alloc::vec::Vec<ProductResponse>,
+    /// Requested ids with no product, each with the reason.
+    #[prost(message, repeated, tag = "4")]
+    pub failed: ::prost::alloc::vec::Vec<super::api::BatchFailure>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ApiResponsePaginationProductDeleteAt {
@@ -224,6 +283,32 @@ pub mod product_query_service_client {
                 .insert(GrpcMethod::new("product.ProductQueryService", "FindById"));
             self.inner.unary(req, path, codec).await
         }
+        pub async fn find_by_external_id(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FindByExternalIdProductRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponseProduct>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/product.ProductQueryService/FindByExternalId",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("product.ProductQueryService", "FindByExternalId"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
         pub async fn find_by_active(
             &mut self,
             request: impl tonic::IntoRequest<super::FindAllProductRequest>,
@@ -272,6 +357,54 @@ pub mod product_query_service_client {
                 .insert(GrpcMethod::new("product.ProductQueryService", "FindByTrashed"));
             self.inner.unary(req, path, codec).await
         }
+        pub async fn find_low_stock(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FindAllProductRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponsePaginationProduct>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/product.ProductQueryService/FindLowStock",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("product.ProductQueryService", "FindLowStock"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn find_by_ids(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FindByIdsProductRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponsesProduct>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/product.ProductQueryService/FindByIds",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("product.ProductQueryService", "FindByIds"));
+            self.inner.unary(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -301,6 +434,13 @@ pub mod product_query_service_server {
             tonic::Response<super::ApiResponseProduct>,
             tonic::Status,
         >;
+        async fn find_by_external_id(
+            &self,
+            request: tonic::Request<super::FindByExternalIdProductRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponseProduct>,
+            tonic::Status,
+        >;
         async fn find_by_active(
             &self,
             request: tonic::Request<super::FindAllProductRequest>,
@@ -315,6 +455,20 @@ pub mod product_query_service_server {
             tonic::Response<super::ApiResponsePaginationProductDeleteAt>,
             tonic::Status,
         >;
+        async fn find_low_stock(
+            &self,
+            request: tonic::Request<super::FindAllProductRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponsePaginationProduct>,
+            tonic::Status,
+        >;
+        async fn find_by_ids(
+            &self,
+            request: tonic::Request<super::FindByIdsProductRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponsesProduct>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct ProductQueryServiceServer<T> {
@@ -483,6 +637,57 @@ pub mod product_query_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/product.ProductQueryService/FindByExternalId" => {
+                    #[allow(non_camel_case_types)]
+                    struct FindByExternalIdSvc<T: ProductQueryService>(pub Arc<T>);
+                    impl<
+                        T: ProductQueryService,
+                    > tonic::server::UnaryService<super::FindByExternalIdProductRequest>
+                    for FindByExternalIdSvc<T> {
+                        type Response = super::ApiResponseProduct;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::FindByExternalIdProductRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as ProductQueryService>::find_by_external_id(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = FindByExternalIdSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/product.ProductQueryService/FindByActive" => {
                     #[allow(non_camel_case_types)]
                     struct FindByActiveSvc<T: ProductQueryService>(pub Arc<T>);
@@ -575,6 +780,98 @@ pub mod product_query_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/product.ProductQueryService/FindLowStock" => {
+                    #[allow(non_camel_case_types)]
+                    struct FindLowStockSvc<T: ProductQueryService>(pub Arc<T>);
+                    impl<
+                        T: ProductQueryService,
+                    > tonic::server::UnaryService<super::FindAllProductRequest>
+                    for FindLowStockSvc<T> {
+                        type Response = super::ApiResponsePaginationProduct;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FindAllProductRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as ProductQueryService>::find_low_stock(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = FindLowStockSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/product.ProductQueryService/FindByIds" => {
+                    #[allow(non_camel_case_types)]
+                    struct FindByIdsSvc<T: ProductQueryService>(pub Arc<T>);
+                    impl<
+                        T: ProductQueryService,
+                    > tonic::server::UnaryService<super::FindByIdsProductRequest>
+                    for FindByIdsSvc<T> {
+                        type Response = super::ApiResponsesProduct;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FindByIdsProductRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as ProductQueryService>::find_by_ids(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = FindByIdsSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         let mut response = http::Response::new(
@@ -623,6 +920,14 @@ pub struct CreateProductRequest {
     pub price: i64,
     #[prost(int32, tag = "3")]
     pub stock: i32,
+    #[prost(string, repeated, tag = "4")]
+    pub images: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(message, optional, tag = "5")]
+    pub reorder_threshold: ::core::option::Option<i32>,
+    #[prost(message, optional, tag = "6")]
+    pub category: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(message, repeated, tag = "7")]
+    pub attributes: ::prost::alloc::vec::Vec<ProductAttribute>,
 }
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct UpdateProductRequest {
@@ -634,6 +939,14 @@ pub struct UpdateProductRequest {
     pub price: i64,
     #[prost(int32, tag = "4")]
     pub stock: i32,
+    #[prost(string, repeated, tag = "5")]
+    pub images: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(message, optional, tag = "6")]
+    pub reorder_threshold: ::core::option::Option<i32>,
+    #[prost(message, optional, tag = "7")]
+    pub category: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(message, repeated, tag = "8")]
+    pub attributes: ::prost::alloc::vec::Vec<ProductAttribute>,
 }
 #[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct UpdateProductStockRequest {
@@ -656,6 +969,28 @@ pub struct ApiResponseProductAll {
     #[prost(string, tag = "2")]
     pub message: ::prost::alloc::string::String,
 }
+/// One piece of a CSV upload with a `name,price,stock,description` header.
+/// `strict` is read from the first chunk.
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct ImportProductsChunk {
+    #[prost(bytes = "vec", tag = "1")]
+    pub data: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bool, tag = "2")]
+    pub strict: bool,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct ApiResponseImportProducts {
+    #[prost(string, tag = "1")]
+    pub status: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub message: ::prost::alloc::string::String,
+    /// CSV line numbers that were inserted; the header is line 1.
+    #[prost(int32, repeated, tag = "6")]
+    pub succeeded: ::prost::alloc::vec::Vec<i32>,
+    /// Skipped lines, keyed by line number, each with the reason.
+    #[prost(message, repeated, tag = "7")]
+    pub failed: ::prost::alloc::vec::Vec<super::api::BatchFailure>,
+}
 /// Generated client implementations.
 pub mod product_command_service_client {
     #![allow(
@@ -924,6 +1259,34 @@ pub mod product_command_service_client {
                 );
             self.inner.unary(req, path, codec).await
         }
+        pub async fn import_products(
+            &mut self,
+            request: impl tonic::IntoStreamingRequest<
+                Message = super::ImportProductsChunk,
+            >,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponseImportProducts>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/product.ProductCommandService/ImportProducts",
+            );
+            let mut req = request.into_streaming_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("product.ProductCommandService", "ImportProducts"),
+                );
+            self.inner.client_streaming(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -988,6 +1351,13 @@ pub mod product_command_service_server {
             tonic::Response<super::ApiResponseProductAll>,
             tonic::Status,
         >;
+        async fn import_products(
+            &self,
+            request: tonic::Request<tonic::Streaming<super::ImportProductsChunk>>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponseImportProducts>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct ProductCommandServiceServer<T> {
@@ -1385,6 +1755,57 @@ pub mod product_command_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/product.ProductCommandService/ImportProducts" => {
+                    #[allow(non_camel_case_types)]
+                    struct ImportProductsSvc<T: ProductCommandService>(pub Arc<T>);
+                    impl<
+                        T: ProductCommandService,
+                    > tonic::server::ClientStreamingService<super::ImportProductsChunk>
+                    for ImportProductsSvc<T> {
+                        type Response = super::ApiResponseImportProducts;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                tonic::Streaming<super::ImportProductsChunk>,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as ProductCommandService>::import_products(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ImportProductsSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.client_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         let mut response = http::Response::new(
@@ -13,12 +13,38 @@ pub struct FindByIdRoleRequest {
     #[prost(int32, tag = "1")]
     pub role_id: i32,
 }
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct FindByExternalIdRoleRequest {
+    #[prost(string, tag = "1")]
+    pub external_id: ::prost::alloc::string::String,
+}
 #[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct FindByIdUserRoleRequest {
     #[prost(int32, tag = "1")]
     pub user_id: i32,
 }
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct FindByUserIdsRequest {
+    #[prost(int32, repeated, tag = "1")]
+    pub user_ids: ::prost::alloc::vec::Vec<i32>,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct UserRoleNames {
+    #[prost(int32, tag = "1")]
+    pub user_id: i32,
+    #[prost(string, repeated, tag = "2")]
+    pub roles: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ApiResponseUserRoleNames {
+    #[prost(string, tag = "1")]
+    pub status: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub message: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "3")]
+    pub data: ::prost::alloc::vec::Vec<UserRoleNames>,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct RoleResponse {
     #[prost(int32, tag = "1")]
     pub id: i32,
@@ -28,6 +54,8 @@ pub struct RoleResponse {
     pub created_at: ::prost::alloc::string::String,
     #[prost(string, tag = "4")]
     pub updated_at: ::prost::alloc::string::String,
+    #[prost(string, tag = "5")]
+    pub external_id: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct RoleResponseDeleteAt {
@@ -41,6 +69,8 @@ pub struct RoleResponseDeleteAt {
     pub updated_at: ::prost::alloc::string::String,
     #[prost(message, optional, tag = "5")]
     pub deleted_at: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, tag = "6")]
+    pub external_id: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct ApiResponseRole {
@@ -259,6 +289,30 @@ pub mod role_query_service_client {
                 .insert(GrpcMethod::new("role.RoleQueryService", "FindByIdRole"));
             self.inner.unary(req, path, codec).await
         }
+        pub async fn find_by_external_id(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FindByExternalIdRoleRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponseRole>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/role.RoleQueryService/FindByExternalId",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("role.RoleQueryService", "FindByExternalId"));
+            self.inner.unary(req, path, codec).await
+        }
         pub async fn find_by_active(
             &mut self,
             request: impl tonic::IntoRequest<super::FindAllRoleRequest>,
@@ -331,6 +385,32 @@ pub mod role_query_service_client {
                 .insert(GrpcMethod::new("role.RoleQueryService", "FindByUserId"));
             self.inner.unary(req, path, codec).await
         }
+        pub async fn find_role_names_by_user_ids(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FindByUserIdsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponseUserRoleNames>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/role.RoleQueryService/FindRoleNamesByUserIds",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("role.RoleQueryService", "FindRoleNamesByUserIds"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -361,6 +441,10 @@ pub mod role_query_service_server {
             &self,
             request: tonic::Request<super::FindByIdRoleRequest>,
         ) -> std::result::Result<tonic::Response<super::ApiResponseRole>, tonic::Status>;
+        async fn find_by_external_id(
+            &self,
+            request: tonic::Request<super::FindByExternalIdRoleRequest>,
+        ) -> std::result::Result<tonic::Response<super::ApiResponseRole>, tonic::Status>;
         async fn find_by_active(
             &self,
             request: tonic::Request<super::FindAllRoleRequest>,
@@ -382,6 +466,13 @@ pub mod role_query_service_server {
             tonic::Response<super::ApiResponsesRole>,
             tonic::Status,
         >;
+        async fn find_role_names_by_user_ids(
+            &self,
+            request: tonic::Request<super::FindByUserIdsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponseUserRoleNames>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct RoleQueryServiceServer<T> {
@@ -596,6 +687,55 @@ pub mod role_query_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/role.RoleQueryService/FindByExternalId" => {
+                    #[allow(non_camel_case_types)]
+                    struct FindByExternalIdSvc<T: RoleQueryService>(pub Arc<T>);
+                    impl<
+                        T: RoleQueryService,
+                    > tonic::server::UnaryService<super::FindByExternalIdRoleRequest>
+                    for FindByExternalIdSvc<T> {
+                        type Response = super::ApiResponseRole;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FindByExternalIdRoleRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RoleQueryService>::find_by_external_id(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = FindByExternalIdSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/role.RoleQueryService/FindByActive" => {
                     #[allow(non_camel_case_types)]
                     struct FindByActiveSvc<T: RoleQueryService>(pub Arc<T>);
@@ -734,6 +874,55 @@ pub mod role_query_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/role.RoleQueryService/FindRoleNamesByUserIds" => {
+                    #[allow(non_camel_case_types)]
+                    struct FindRoleNamesByUserIdsSvc<T: RoleQueryService>(pub Arc<T>);
+                    impl<
+                        T: RoleQueryService,
+                    > tonic::server::UnaryService<super::FindByUserIdsRequest>
+                    for FindRoleNamesByUserIdsSvc<T> {
+                        type Response = super::ApiResponseUserRoleNames;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FindByUserIdsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RoleQueryService>::find_role_names_by_user_ids(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = FindRoleNamesByUserIdsSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         let mut response = http::Response::new(
@@ -5,6 +5,11 @@ pub struct FindByIdUserRequest {
     pub id: i32,
 }
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct FindByExternalIdUserRequest {
+    #[prost(string, tag = "1")]
+    pub external_id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct UserResponse {
     #[prost(int32, tag = "1")]
     pub id: i32,
@@ -18,6 +23,10 @@ pub struct UserResponse {
     pub created_at: ::prost::alloc::string::String,
     #[prost(string, tag = "6")]
     pub updated_at: ::prost::alloc::string::String,
+    #[prost(string, repeated, tag = "7")]
+    pub roles: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(string, tag = "8")]
+    pub external_id: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct UserResponseWithPassword {
@@ -35,6 +44,10 @@ pub struct UserResponseWithPassword {
     pub created_at: ::prost::alloc::string::String,
     #[prost(string, tag = "7")]
     pub updated_at: ::prost::alloc::string::String,
+    #[prost(bool, tag = "8")]
+    pub is_verified: bool,
+    #[prost(string, tag = "9")]
+    pub external_id: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct UserResponseDeleteAt {
@@ -52,6 +65,8 @@ pub struct UserResponseDeleteAt {
     pub updated_at: ::prost::alloc::string::String,
     #[prost(message, optional, tag = "7")]
     pub deleted_at: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, tag = "8")]
+    pub external_id: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct ApiResponseUser {
@@ -88,6 +103,13 @@ pub struct UpdateUserVerifiedRequest {
     pub is_verified: bool,
 }
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct UpdateUserVerificationCodeRequest {
+    #[prost(int32, tag = "1")]
+    pub user_id: i32,
+    #[prost(string, tag = "2")]
+    pub verification_code: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct UpdateUserPasswordRequest {
     #[prost(int32, tag = "1")]
     pub user_id: i32,
@@ -362,6 +384,58 @@ pub mod user_command_service_client {
                 );
             self.inner.unary(req, path, codec).await
         }
+        pub async fn update_user_verification_code(
+            &mut self,
+            request: impl tonic::IntoRequest<super::UpdateUserVerificationCodeRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponseUser>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/user.UserCommandService/UpdateUserVerificationCode",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("user.UserCommandService", "UpdateUserVerificationCode"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn anonymize_user(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FindByIdUserRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponseUser>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/user.UserCommandService/AnonymizeUser",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("user.UserCommandService", "AnonymizeUser"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
         pub async fn update_user_password(
             &mut self,
             request: impl tonic::IntoRequest<super::UpdateUserPasswordRequest>,
@@ -486,6 +560,14 @@ pub mod user_command_service_server {
             &self,
             request: tonic::Request<super::UpdateUserVerifiedRequest>,
         ) -> std::result::Result<tonic::Response<super::ApiResponseUser>, tonic::Status>;
+        async fn update_user_verification_code(
+            &self,
+            request: tonic::Request<super::UpdateUserVerificationCodeRequest>,
+        ) -> std::result::Result<tonic::Response<super::ApiResponseUser>, tonic::Status>;
+        async fn anonymize_user(
+            &self,
+            request: tonic::Request<super::FindByIdUserRequest>,
+        ) -> std::result::Result<tonic::Response<super::ApiResponseUser>, tonic::Status>;
         async fn update_user_password(
             &self,
             request: tonic::Request<super::UpdateUserPasswordRequest>,
@@ -866,6 +948,104 @@ pub mod user_command_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/user.UserCommandService/UpdateUserVerificationCode" => {
+                    #[allow(non_camel_case_types)]
+                    struct UpdateUserVerificationCodeSvc<T: UserCommandService>(pub Arc<T>);
+                    impl<
+                        T: UserCommandService,
+                    > tonic::server::UnaryService<super::UpdateUserVerificationCodeRequest>
+                    for UpdateUserVerificationCodeSvc<T> {
+                        type Response = super::ApiResponseUser;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::UpdateUserVerificationCodeRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as UserCommandService>::update_user_verification_code(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = UpdateUserVerificationCodeSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/user.UserCommandService/AnonymizeUser" => {
+                    #[allow(non_camel_case_types)]
+                    struct AnonymizeUserSvc<T: UserCommandService>(pub Arc<T>);
+                    impl<
+                        T: UserCommandService,
+                    > tonic::server::UnaryService<super::FindByIdUserRequest>
+                    for AnonymizeUserSvc<T> {
+                        type Response = super::ApiResponseUser;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FindByIdUserRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as UserCommandService>::anonymize_user(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = AnonymizeUserSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/user.UserCommandService/UpdateUserPassword" => {
                     #[allow(non_camel_case_types)]
                     struct UpdateUserPasswordSvc<T: UserCommandService>(pub Arc<T>);
@@ -1048,6 +1228,10 @@ pub struct FindAllUserRequest {
     pub page_size: i32,
     #[prost(string, tag = "3")]
     pub search: ::prost::alloc::string::String,
+    #[prost(bool, tag = "4")]
+    pub include_roles: bool,
+    #[prost(string, tag = "5")]
+    pub after: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct FindByEmailUserRequest {
@@ -1220,6 +1404,30 @@ pub mod user_query_service_client {
                 .insert(GrpcMethod::new("user.UserQueryService", "FindById"));
             self.inner.unary(req, path, codec).await
         }
+        pub async fn find_by_external_id(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FindByExternalIdUserRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponseUser>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/user.UserQueryService/FindByExternalId",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("user.UserQueryService", "FindByExternalId"));
+            self.inner.unary(req, path, codec).await
+        }
         pub async fn find_by_active(
             &mut self,
             request: impl tonic::IntoRequest<super::FindAllUserRequest>,
@@ -1370,6 +1578,10 @@ pub mod user_query_service_server {
             &self,
             request: tonic::Request<super::FindByIdUserRequest>,
         ) -> std::result::Result<tonic::Response<super::ApiResponseUser>, tonic::Status>;
+        async fn find_by_external_id(
+            &self,
+            request: tonic::Request<super::FindByExternalIdUserRequest>,
+        ) -> std::result::Result<tonic::Response<super::ApiResponseUser>, tonic::Status>;
         async fn find_by_active(
             &self,
             request: tonic::Request<super::FindAllUserRequest>,
@@ -1566,6 +1778,55 @@ pub mod user_query_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/user.UserQueryService/FindByExternalId" => {
+                    #[allow(non_camel_case_types)]
+                    struct FindByExternalIdSvc<T: UserQueryService>(pub Arc<T>);
+                    impl<
+                        T: UserQueryService,
+                    > tonic::server::UnaryService<super::FindByExternalIdUserRequest>
+                    for FindByExternalIdSvc<T> {
+                        type Response = super::ApiResponseUser;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FindByExternalIdUserRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as UserQueryService>::find_by_external_id(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = FindByExternalIdSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/user.UserQueryService/FindByActive" => {
                     #[allow(non_camel_case_types)]
                     struct FindByActiveSvc<T: UserQueryService>(pub Arc<T>);
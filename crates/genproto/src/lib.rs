@@ -33,3 +33,7 @@ pub mod order_item {
 pub mod user_role {
     include!("gen/user_role.rs");
 }
+
+pub mod health {
+    include!("gen/health.rs");
+}
@@ -0,0 +1,4 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS idx_orders_external_id;
+
+ALTER TABLE orders DROP COLUMN IF EXISTS external_id;
@@ -0,0 +1,16 @@
+-- Add up migration script here
+CREATE SEQUENCE IF NOT EXISTS order_number_seq;
+
+ALTER TABLE orders ADD COLUMN IF NOT EXISTS order_number TEXT;
+
+UPDATE orders
+SET order_number = 'ORD-' || LPAD(numbered.n::TEXT, 6, '0')
+FROM (
+    SELECT order_id, nextval('order_number_seq') AS n
+    FROM (SELECT order_id FROM orders WHERE order_number IS NULL ORDER BY order_id) AS pending
+) AS numbered
+WHERE orders.order_id = numbered.order_id;
+
+ALTER TABLE orders ALTER COLUMN order_number SET NOT NULL;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_orders_order_number ON orders (order_number);
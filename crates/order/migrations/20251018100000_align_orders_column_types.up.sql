@@ -0,0 +1,9 @@
+-- Add up migration script here
+-- The service reads orders with INT totals and UTC TIMESTAMPs, which is what
+-- its compile-time checked queries were prepared against. Align the table so
+-- a freshly migrated database decodes the same way.
+ALTER TABLE orders
+    ALTER COLUMN total_price TYPE INT,
+    ALTER COLUMN created_at TYPE TIMESTAMP USING created_at AT TIME ZONE 'UTC',
+    ALTER COLUMN updated_at TYPE TIMESTAMP USING updated_at AT TIME ZONE 'UTC',
+    ALTER COLUMN deleted_at TYPE TIMESTAMP USING deleted_at AT TIME ZONE 'UTC';
@@ -1,7 +1,7 @@
 use crate::domain::response::{api::ApiResponse, product::ProductResponse};
 use anyhow::Result;
 use async_trait::async_trait;
-use shared::errors::AppErrorGrpc;
+use shared::{errors::AppErrorGrpc, utils::BatchResponse};
 use std::sync::Arc;
 
 pub type DynProductGrpcClient = Arc<dyn ProductGrpcClientTrait + Send + Sync>;
@@ -9,4 +9,10 @@ pub type DynProductGrpcClient = Arc<dyn ProductGrpcClientTrait + Send + Sync>;
 #[async_trait]
 pub trait ProductGrpcClientTrait {
     async fn find_by_id(&self, id: i32) -> Result<ApiResponse<ProductResponse>, AppErrorGrpc>;
+    /// Active products among `ids` in one call; missing or trashed ones come
+    /// back in `failed`.
+    async fn find_by_ids(
+        &self,
+        ids: Vec<i32>,
+    ) -> Result<ApiResponse<BatchResponse<ProductResponse>>, AppErrorGrpc>;
 }
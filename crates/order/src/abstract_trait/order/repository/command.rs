@@ -1,6 +1,6 @@
 use crate::{
     domain::requests::order::{CreateOrderRecordRequest, UpdateOrderRecordRequest},
-    model::order::Order as OrderModel,
+    model::order::{Order as OrderModel, OrderWithItems},
 };
 use anyhow::Result;
 use async_trait::async_trait;
@@ -11,10 +11,13 @@ pub type DynOrderCommandRepository = Arc<dyn OrderCommandRepositoryTrait + Send
 
 #[async_trait]
 pub trait OrderCommandRepositoryTrait {
+    /// Inserts the order, its items and the idempotency claim, if any, in
+    /// one transaction. Returns `None` when the key is already held by a
+    /// live claim.
     async fn create_order(
         &self,
         req: &CreateOrderRecordRequest,
-    ) -> Result<OrderModel, RepositoryError>;
+    ) -> Result<Option<OrderWithItems>, RepositoryError>;
     async fn update_order(
         &self,
         req: &UpdateOrderRecordRequest,
@@ -0,0 +1,19 @@
+use crate::model::idempotency::IdempotencyKey;
+use anyhow::Result;
+use async_trait::async_trait;
+use shared::errors::RepositoryError;
+use std::sync::Arc;
+
+pub type DynOrderIdempotencyRepository = Arc<dyn OrderIdempotencyRepositoryTrait + Send + Sync>;
+
+#[async_trait]
+pub trait OrderIdempotencyRepositoryTrait {
+    /// Looks up a key claimed within the last 24 hours.
+    async fn find(
+        &self,
+        user_id: i32,
+        key: &str,
+    ) -> Result<Option<IdempotencyKey>, RepositoryError>;
+    /// Deletes up to `limit` keys older than the 24 hour replay window.
+    async fn purge_expired(&self, limit: i64) -> Result<u64, RepositoryError>;
+}
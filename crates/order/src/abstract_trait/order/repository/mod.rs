@@ -1,5 +1,7 @@
 mod command;
+mod idempotency;
 mod query;
 
 pub use self::command::{DynOrderCommandRepository, OrderCommandRepositoryTrait};
+pub use self::idempotency::{DynOrderIdempotencyRepository, OrderIdempotencyRepositoryTrait};
 pub use self::query::{DynOrderQueryRepository, OrderQueryRepositoryTrait};
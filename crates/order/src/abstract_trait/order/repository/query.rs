@@ -1,15 +1,23 @@
-use crate::{domain::requests::order::FindAllOrder, model::order::Order as OrderModel};
+use crate::{
+    domain::requests::order::FindAllOrder,
+    model::order::{Order as OrderModel, OrderWithItems},
+};
 use anyhow::Result;
 use async_trait::async_trait;
-use shared::errors::RepositoryError;
+use shared::{errors::RepositoryError, utils::Cursor};
 use std::sync::Arc;
+use uuid::Uuid;
 
 pub type DynOrderQueryRepository = Arc<dyn OrderQueryRepositoryTrait + Send + Sync>;
 
 #[async_trait]
 pub trait OrderQueryRepositoryTrait {
-    async fn find_all(&self, req: &FindAllOrder)
-    -> Result<(Vec<OrderModel>, i64), RepositoryError>;
+    /// Offset pagination, or keyset pagination after `after` when given.
+    async fn find_all(
+        &self,
+        req: &FindAllOrder,
+        after: Option<Cursor>,
+    ) -> Result<(Vec<OrderWithItems>, i64), RepositoryError>;
     async fn find_active(
         &self,
         req: &FindAllOrder,
@@ -18,5 +26,15 @@ pub trait OrderQueryRepositoryTrait {
         &self,
         req: &FindAllOrder,
     ) -> Result<(Vec<OrderModel>, i64), RepositoryError>;
+    /// One user's orders, newest first, with offset pagination.
+    async fn find_by_user_id(
+        &self,
+        user_id: i32,
+        req: &FindAllOrder,
+    ) -> Result<(Vec<OrderWithItems>, i64), RepositoryError>;
     async fn find_by_id(&self, id: i32) -> Result<Option<OrderModel>, RepositoryError>;
+    async fn find_by_external_id(
+        &self,
+        external_id: Uuid,
+    ) -> Result<Option<OrderModel>, RepositoryError>;
 }
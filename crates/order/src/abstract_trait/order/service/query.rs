@@ -2,13 +2,14 @@ use crate::domain::{
     requests::order::FindAllOrder,
     response::{
         api::{ApiResponse, ApiResponsePagination},
-        order::{OrderResponse, OrderResponseDeleteAt},
+        order::{OrderDetailResponse, OrderResponse, OrderResponseDeleteAt},
     },
 };
 use anyhow::Result;
 use async_trait::async_trait;
 use shared::errors::ServiceError;
 use std::sync::Arc;
+use uuid::Uuid;
 
 pub type DynOrderQueryService = Arc<dyn OrderQueryServiceTrait + Send + Sync>;
 
@@ -26,5 +27,20 @@ pub trait OrderQueryServiceTrait {
         &self,
         req: &FindAllOrder,
     ) -> Result<ApiResponsePagination<Vec<OrderResponseDeleteAt>>, ServiceError>;
+    async fn find_by_user_id(
+        &self,
+        user_id: i32,
+        req: &FindAllOrder,
+    ) -> Result<ApiResponsePagination<Vec<OrderResponse>>, ServiceError>;
     async fn find_by_id(&self, id: i32) -> Result<ApiResponse<OrderResponse>, ServiceError>;
+    /// The order with its items enriched with their products' current names
+    /// and prices, fetched from the product service in one batch.
+    async fn find_detail_by_id(
+        &self,
+        id: i32,
+    ) -> Result<ApiResponse<OrderDetailResponse>, ServiceError>;
+    async fn find_by_external_id(
+        &self,
+        external_id: Uuid,
+    ) -> Result<ApiResponse<OrderResponse>, ServiceError>;
 }
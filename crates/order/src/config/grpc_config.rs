@@ -1,8 +1,12 @@
 use anyhow::{Context, Result};
+use shared::config::client_tls_from_env;
+use tonic::transport::ClientTlsConfig;
 
 #[derive(Clone)]
 pub struct GrpcClientConfig {
     pub product: String,
+    /// `None` calls the services over plaintext.
+    pub tls: Option<ClientTlsConfig>,
 }
 
 impl GrpcClientConfig {
@@ -10,6 +14,9 @@ impl GrpcClientConfig {
         let product = std::env::var("GRPC_PRODUCT_ADDR")
             .context("Missing environment variable: GRPC_PRODUCT_ADDR")?;
 
-        Ok(Self { product })
+        Ok(Self {
+            product,
+            tls: client_tls_from_env()?,
+        })
     }
 }
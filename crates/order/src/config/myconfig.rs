@@ -6,9 +6,59 @@ pub struct ServiceConfig {
     pub metric_port: u16,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicateItemPolicy {
+    Reject,
+    Aggregate,
+}
+
+/// Bounds on an order's computed total, in the same unit as product prices.
+/// They are global because orders have no currency of their own.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OrderTotalLimits {
+    pub min: Option<i32>,
+    pub max: Option<i32>,
+}
+
+impl OrderTotalLimits {
+    /// Returns a description of the violated bound, if any.
+    pub fn check(&self, total: i32) -> Option<String> {
+        if let Some(min) = self.min
+            && total < min
+        {
+            return Some(format!("Order total {total} is below the minimum of {min}"));
+        }
+
+        if let Some(max) = self.max
+            && total > max
+        {
+            return Some(format!("Order total {total} exceeds the maximum of {max}"));
+        }
+
+        None
+    }
+}
+
+/// Batch size and interval of the expired idempotency key purge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PurgeJobConfig {
+    pub batch_size: i64,
+    pub interval_seconds: u64,
+}
+
+/// How order numbers are rendered: `prefix` followed by the sequence value
+/// left-padded with zeros to at least `padding` digits. The number is stored
+/// when the order is created, so changing this leaves issued numbers as-is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderNumberFormat {
+    pub prefix: String,
+    pub padding: i32,
+}
+
 #[derive(Debug, Clone)]
 pub struct Config {
     pub database_url: String,
+    pub database_read_url: Option<String>,
     pub jwt_secret: String,
     pub run_migrations: bool,
     pub port: u16,
@@ -17,15 +67,24 @@ pub struct Config {
     pub kafka_broker: String,
     pub db_max_conn: u32,
     pub db_min_conn: u32,
+    pub duplicate_item_policy: DuplicateItemPolicy,
+    pub order_creation_paused: bool,
+    pub order_total_limits: OrderTotalLimits,
+    pub idempotency_purge: PurgeJobConfig,
+    pub order_number_format: OrderNumberFormat,
 }
 impl Config {
     pub fn init() -> Result<Self> {
         let database_url =
             std::env::var("DATABASE_URL").context("Missing environment variable: DATABASE_URL")?;
+        let database_read_url = std::env::var("DATABASE_READ_URL")
+            .ok()
+            .filter(|url| !url.trim().is_empty());
         let jwt_secret =
             std::env::var("JWT_SECRET").context("Missing environment variable: JWT_SECRET")?;
-        let run_migrations_str = std::env::var("RUN_MIGRATIONS")
-            .context("Missing environment variable: RUN_MIGRATIONS")?;
+        let run_migrations_str = std::env::var("AUTO_MIGRATE")
+            .or_else(|_| std::env::var("RUN_MIGRATIONS"))
+            .context("Missing environment variable: AUTO_MIGRATE")?;
         let port_str = std::env::var("PORT").context("Missing environment variable: PORT")?;
 
         let kafka_broker = std::env::var("KAFKA").context("Missing environment variable: KAFKA")?;
@@ -35,7 +94,7 @@ impl Config {
             "false" => false,
             other => {
                 return Err(anyhow!(
-                    "RUN_MIGRATIONS must be 'true' or 'false', got '{}'",
+                    "AUTO_MIGRATE must be 'true' or 'false', got '{}'",
                     other
                 ));
             }
@@ -51,6 +110,84 @@ impl Config {
             .parse::<u32>()
             .context("Unable to parse DB_MIN_CONNECTION as u32")?;
 
+        let duplicate_item_policy = match std::env::var("ORDER_DUPLICATE_ITEM_POLICY")
+            .unwrap_or_else(|_| "reject".to_string())
+            .as_str()
+        {
+            "reject" => DuplicateItemPolicy::Reject,
+            "aggregate" => DuplicateItemPolicy::Aggregate,
+            other => {
+                return Err(anyhow!(
+                    "ORDER_DUPLICATE_ITEM_POLICY must be 'reject' or 'aggregate', got '{}'",
+                    other
+                ));
+            }
+        };
+
+        let order_creation_paused = match std::env::var("ORDER_CREATION_PAUSED")
+            .unwrap_or_else(|_| "false".to_string())
+            .as_str()
+        {
+            "true" => true,
+            "false" => false,
+            other => {
+                return Err(anyhow!(
+                    "ORDER_CREATION_PAUSED must be 'true' or 'false', got '{}'",
+                    other
+                ));
+            }
+        };
+
+        let order_total_min = std::env::var("ORDER_MIN_TOTAL")
+            .ok()
+            .filter(|v| !v.is_empty())
+            .map(|v| v.parse::<i32>())
+            .transpose()
+            .context("ORDER_MIN_TOTAL must be a valid i32 integer")?;
+
+        let order_total_max = std::env::var("ORDER_MAX_TOTAL")
+            .ok()
+            .filter(|v| !v.is_empty())
+            .map(|v| v.parse::<i32>())
+            .transpose()
+            .context("ORDER_MAX_TOTAL must be a valid i32 integer")?;
+
+        if let (Some(min), Some(max)) = (order_total_min, order_total_max)
+            && min > max
+        {
+            return Err(anyhow!(
+                "ORDER_MIN_TOTAL ({}) must not be greater than ORDER_MAX_TOTAL ({})",
+                min,
+                max
+            ));
+        }
+
+        let idempotency_purge_batch_size = std::env::var("IDEMPOTENCY_PURGE_BATCH_SIZE")
+            .unwrap_or_else(|_| "500".to_string())
+            .parse::<i64>()
+            .context("IDEMPOTENCY_PURGE_BATCH_SIZE must be a valid i64 integer")?;
+
+        let idempotency_purge_interval_seconds =
+            std::env::var("IDEMPOTENCY_PURGE_INTERVAL_SECONDS")
+                .unwrap_or_else(|_| "3600".to_string())
+                .parse::<u64>()
+                .context("IDEMPOTENCY_PURGE_INTERVAL_SECONDS must be a valid u64 integer")?;
+
+        let order_number_prefix =
+            std::env::var("ORDER_NUMBER_PREFIX").unwrap_or_else(|_| "ORD-".to_string());
+
+        let order_number_padding = std::env::var("ORDER_NUMBER_PADDING")
+            .unwrap_or_else(|_| "6".to_string())
+            .parse::<i32>()
+            .context("ORDER_NUMBER_PADDING must be a valid i32 integer")?;
+
+        if !(0..=20).contains(&order_number_padding) {
+            return Err(anyhow!(
+                "ORDER_NUMBER_PADDING must be between 0 and 20, got {}",
+                order_number_padding
+            ));
+        }
+
         let port = port_str
             .parse::<u16>()
             .context("PORT must be a valid u16 integer")?;
@@ -79,6 +216,7 @@ impl Config {
 
         Ok(Self {
             database_url,
+            database_read_url,
             jwt_secret,
             run_migrations,
             port,
@@ -93,6 +231,20 @@ impl Config {
             kafka_broker,
             db_max_conn,
             db_min_conn,
+            duplicate_item_policy,
+            order_creation_paused,
+            order_total_limits: OrderTotalLimits {
+                min: order_total_min,
+                max: order_total_max,
+            },
+            idempotency_purge: PurgeJobConfig {
+                batch_size: idempotency_purge_batch_size.max(1),
+                interval_seconds: idempotency_purge_interval_seconds.max(1),
+            },
+            order_number_format: OrderNumberFormat {
+                prefix: order_number_prefix,
+                padding: order_number_padding,
+            },
         })
     }
 }
@@ -1,12 +1,18 @@
 use crate::{
-    abstract_trait::grpc_client::DynProductGrpcClient,
+    abstract_trait::{
+        grpc_client::DynProductGrpcClient, order::repository::DynOrderIdempotencyRepository,
+    },
+    config::myconfig::{DuplicateItemPolicy, OrderNumberFormat, OrderTotalLimits, PurgeJobConfig},
     grpc_client::{GrpcClients, product::ProductGrpcClientService},
     repository::{
-        order::{OrderCommandRepository, OrderQueryRepository},
+        order::{OrderCommandRepository, OrderIdempotencyRepository, OrderQueryRepository},
         order_item::{command::OrderItemCommandRepository, query::OrderItemQueryRepository},
     },
     service::{
-        order::{OrderCommandService, OrderCommandServiceDeps, OrderQueryService},
+        order::{
+            IdempotencyKeySweeper, OrderCommandService, OrderCommandServiceDeps, OrderCreationGate,
+            OrderQueryService,
+        },
         order_item::OrderItemQueryService,
     },
 };
@@ -14,7 +20,7 @@ use anyhow::{Context, Result};
 use shared::{
     abstract_trait::DynKafka,
     cache::CacheStore,
-    config::{ConnectionPool, RedisPool},
+    config::{DatabasePools, RedisPool},
 };
 use std::{fmt, sync::Arc};
 
@@ -37,40 +43,77 @@ impl fmt::Debug for DependenciesInject {
 
 #[derive(Clone)]
 pub struct DependenciesInjectDeps {
-    pub pool: ConnectionPool,
+    pub pools: DatabasePools,
     pub kafka: DynKafka,
     pub redis: RedisPool,
+    pub duplicate_item_policy: DuplicateItemPolicy,
+    pub order_creation_paused: bool,
+    pub order_total_limits: OrderTotalLimits,
+    pub idempotency_purge: PurgeJobConfig,
+    pub order_number_format: OrderNumberFormat,
 }
 
 impl DependenciesInject {
     pub async fn new(deps: DependenciesInjectDeps, clients: GrpcClients) -> Result<Self> {
-        let DependenciesInjectDeps { kafka, pool, redis } = deps;
+        let DependenciesInjectDeps {
+            kafka,
+            pools,
+            redis,
+            duplicate_item_policy,
+            order_creation_paused,
+            order_total_limits,
+            idempotency_purge,
+            order_number_format,
+        } = deps;
 
-        let order_query_repo = Arc::new(OrderQueryRepository::new(pool.clone()));
-        let order_command_repo = Arc::new(OrderCommandRepository::new(pool.clone()));
-        let order_item_query_repo = Arc::new(OrderItemQueryRepository::new(pool.clone()));
-        let order_item_command_repo = Arc::new(OrderItemCommandRepository::new(pool.clone()));
+        let order_query_repo = Arc::new(OrderQueryRepository::new(pools.read.clone()));
+        let order_command_repo = Arc::new(OrderCommandRepository::new(
+            pools.write.clone(),
+            order_number_format,
+        ));
+        let order_item_query_repo = Arc::new(OrderItemQueryRepository::new(pools.read.clone()));
+        let order_item_command_repo =
+            Arc::new(OrderItemCommandRepository::new(pools.write.clone()));
+        // The command service's reads decide whether a write goes ahead, so
+        // they must not see a lagging replica.
+        let order_guard_repo = Arc::new(OrderQueryRepository::new(pools.write.clone()));
+        let order_item_guard_repo = Arc::new(OrderItemQueryRepository::new(pools.write.clone()));
+        let idempotency_repo: DynOrderIdempotencyRepository =
+            Arc::new(OrderIdempotencyRepository::new(pools.write.clone()));
 
         let product_client: DynProductGrpcClient =
             Arc::new(ProductGrpcClientService::new(clients.product_query_client).await);
 
         let cache = Arc::new(CacheStore::new(redis.pool.clone()));
 
-        let order_query = OrderQueryService::new(order_query_repo.clone(), cache.clone())
-            .context("failed initialize order query")?;
+        let order_query = OrderQueryService::new(
+            order_query_repo.clone(),
+            order_item_query_repo.clone(),
+            product_client.clone(),
+            cache.clone(),
+        )
+        .context("failed initialize order query")?;
 
         let order_command_deps = OrderCommandServiceDeps {
             product_client,
-            order_item_query: order_item_query_repo.clone(),
+            order_item_query: order_item_guard_repo,
             order_item_command: order_item_command_repo,
-            query: order_query_repo.clone(),
+            query: order_guard_repo,
             command: order_command_repo,
+            idempotency: idempotency_repo.clone(),
             kafka,
+            duplicate_item_policy,
+            creation_gate: OrderCreationGate::new(order_creation_paused, cache.clone()),
+            order_total_limits,
+            cache_store: cache.clone(),
         };
 
         let order_command = OrderCommandService::new(order_command_deps)
             .context("failed initialize order command")?;
 
+        let sweeper = IdempotencyKeySweeper::new(idempotency_repo, idempotency_purge);
+        tokio::spawn(async move { sweeper.run().await });
+
         let order_item_query =
             OrderItemQueryService::new(order_item_query_repo.clone(), cache.clone())
                 .context("failed initialize order item query")?;
@@ -1,4 +1,7 @@
-use crate::domain::requests::order::{CreateOrderItemRequest, UpdateOrderItemRequest};
+use crate::domain::{
+    requests::order::{CreateOrderItemRequest, UpdateOrderItemRequest},
+    status::OrderStatus,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -48,4 +51,9 @@ pub enum OrderEvent {
         order_id: i32,
         deleted_items: Vec<OrderItemEvent>,
     },
+    StatusChanged {
+        order_id: i32,
+        from: OrderStatus,
+        to: OrderStatus,
+    },
 }
@@ -1,3 +1,4 @@
 pub mod event;
 pub mod requests;
 pub mod response;
+pub mod status;
@@ -1,3 +1,5 @@
+use crate::domain::status::OrderStatus;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
@@ -11,6 +13,31 @@ pub struct FindAllOrder {
 
     #[serde(default)]
     pub search: String,
+
+    #[serde(default)]
+    pub include_items: bool,
+
+    /// Opaque `next_cursor` from a previous page; switches `find_all` to
+    /// keyset pagination when set.
+    #[serde(default)]
+    pub after: String,
+
+    /// Comma separated `field:direction` pairs, e.g. `price:desc,name:asc`.
+    /// Empty keeps the newest-first default.
+    #[serde(default)]
+    pub sort: String,
+
+    /// Restricts `find_all` to one user's orders.
+    #[serde(default)]
+    pub user_id: Option<i32>,
+
+    /// Inclusive bounds on `created_at` for `find_all`; `None` leaves that
+    /// side open.
+    #[serde(default)]
+    pub created_from: Option<DateTime<Utc>>,
+
+    #[serde(default)]
+    pub created_to: Option<DateTime<Utc>>,
 }
 
 fn default_page() -> i32 {
@@ -29,6 +56,31 @@ pub struct CreateOrderRecordRequest {
 
     #[serde(rename = "total_price")]
     pub total_price: i32,
+
+    pub items: Vec<CreateOrderLineRecord>,
+
+    /// Key to claim in the same transaction as the order, if the client
+    /// sent one.
+    pub idempotency: Option<IdempotencyClaim>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct CreateOrderLineRecord {
+    #[validate(range(min = 1))]
+    #[serde(rename = "product_id")]
+    pub product_id: i32,
+
+    #[validate(range(min = 1))]
+    pub quantity: i32,
+
+    #[validate(range(min = 1))]
+    pub price: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdempotencyClaim {
+    pub key: String,
+    pub request_hash: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, Validate)]
@@ -43,6 +95,8 @@ pub struct UpdateOrderRecordRequest {
     #[validate(range(min = 1))]
     #[serde(rename = "total_price")]
     pub total_price: i32,
+
+    pub status: OrderStatus,
 }
 
 #[derive(Debug, Serialize, Deserialize, Validate, Clone)]
@@ -53,6 +107,11 @@ pub struct CreateOrderRequest {
 
     #[validate(length(min = 1))]
     pub items: Vec<CreateOrderItemRequest>,
+
+    /// Client supplied `Idempotency-Key`; a retry with the same key replays
+    /// the order created by the first request.
+    #[serde(default)]
+    pub idempotency_key: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Validate, Clone)]
@@ -66,6 +125,15 @@ pub struct UpdateOrderRequest {
 
     #[validate(length(min = 1))]
     pub items: Vec<UpdateOrderItemRequest>,
+
+    /// Requested lifecycle status, checked against the current one; `None`
+    /// keeps it unchanged.
+    #[serde(default)]
+    pub status: Option<String>,
+
+    /// ADMIN or MODERATOR caller. Anyone else may only cancel.
+    #[serde(default)]
+    pub caller_is_staff: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Validate, Clone)]
@@ -1,6 +1,10 @@
-use crate::model::order::Order as OrderModel;
+use crate::{
+    domain::response::order_item::{OrderItemDetailResponse, OrderItemResponse},
+    model::order::{Order as OrderModel, OrderWithItems},
+};
 use genproto::order::{
-    OrderResponse as OrderResponseProto, OrderResponseDeleteAt as OrderResponseDeleteAtProto,
+    OrderDetailResponse as OrderDetailResponseProto, OrderResponse as OrderResponseProto,
+    OrderResponseDeleteAt as OrderResponseDeleteAtProto,
 };
 use serde::{Deserialize, Serialize};
 use shared::utils::parse_datetime;
@@ -9,12 +13,17 @@ use utoipa::ToSchema;
 #[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
 pub struct OrderResponse {
     pub id: i32,
+    pub external_id: String,
+    pub order_number: String,
     pub user_id: i32,
     pub total_price: i32,
     #[serde(rename = "created_at")]
     pub created_at: Option<String>,
     #[serde(rename = "updated_at")]
     pub updated_at: Option<String>,
+    pub status: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub items: Vec<OrderItemResponse>,
 }
 
 // model to response
@@ -22,23 +31,44 @@ impl From<OrderModel> for OrderResponse {
     fn from(value: OrderModel) -> Self {
         OrderResponse {
             id: value.order_id,
+            external_id: value.external_id.to_string(),
+            order_number: value.order_number,
             user_id: value.user_id,
             total_price: value.total_price,
             created_at: value.created_at.map(|dt| dt.to_string()),
             updated_at: value.updated_at.map(|dt| dt.to_string()),
+            status: value.status.to_string(),
+            items: vec![],
         }
     }
 }
 
+// model with items to response
+impl From<OrderWithItems> for OrderResponse {
+    fn from(value: OrderWithItems) -> Self {
+        let mut response = OrderResponse::from(value.order);
+        response.items = value
+            .items
+            .into_iter()
+            .map(OrderItemResponse::from)
+            .collect();
+        response
+    }
+}
+
 // proto to response
 impl From<OrderResponseProto> for OrderResponse {
     fn from(value: OrderResponseProto) -> Self {
         OrderResponse {
             id: value.id,
+            external_id: value.external_id,
+            order_number: value.order_number,
             user_id: value.user_id,
             total_price: value.total_price,
             created_at: parse_datetime(&value.created_at),
             updated_at: parse_datetime(&value.updated_at),
+            status: value.status,
+            items: value.items.into_iter().map(Into::into).collect(),
         }
     }
 }
@@ -48,10 +78,14 @@ impl From<OrderResponse> for OrderResponseProto {
     fn from(value: OrderResponse) -> Self {
         OrderResponseProto {
             id: value.id,
+            external_id: value.external_id,
+            order_number: value.order_number,
             user_id: value.user_id,
             total_price: value.total_price,
             created_at: value.created_at.unwrap_or_default(),
             updated_at: value.updated_at.unwrap_or_default(),
+            status: value.status,
+            items: value.items.into_iter().map(Into::into).collect(),
         }
     }
 }
@@ -59,6 +93,8 @@ impl From<OrderResponse> for OrderResponseProto {
 #[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
 pub struct OrderResponseDeleteAt {
     pub id: i32,
+    pub external_id: String,
+    pub order_number: String,
     pub user_id: i32,
     pub total_price: i32,
     #[serde(rename = "created_at")]
@@ -67,6 +103,7 @@ pub struct OrderResponseDeleteAt {
     pub updated_at: Option<String>,
     #[serde(rename = "deleted_at")]
     pub deleted_at: Option<String>,
+    pub status: String,
 }
 
 // model to response
@@ -74,11 +111,14 @@ impl From<OrderModel> for OrderResponseDeleteAt {
     fn from(value: OrderModel) -> Self {
         OrderResponseDeleteAt {
             id: value.order_id,
+            external_id: value.external_id.to_string(),
+            order_number: value.order_number,
             user_id: value.user_id,
             total_price: value.total_price,
             created_at: value.created_at.map(|dt| dt.to_string()),
             updated_at: value.updated_at.map(|dt| dt.to_string()),
             deleted_at: value.deleted_at.map(|dt| dt.to_string()),
+            status: value.status.to_string(),
         }
     }
 }
@@ -88,11 +128,14 @@ impl From<OrderResponseDeleteAtProto> for OrderResponseDeleteAt {
     fn from(value: OrderResponseDeleteAtProto) -> Self {
         OrderResponseDeleteAt {
             id: value.id,
+            external_id: value.external_id,
+            order_number: value.order_number,
             user_id: value.user_id,
             total_price: value.total_price,
             created_at: parse_datetime(&value.created_at),
             updated_at: parse_datetime(&value.updated_at),
             deleted_at: value.deleted_at.as_deref().and_then(parse_datetime),
+            status: value.status,
         }
     }
 }
@@ -102,11 +145,66 @@ impl From<OrderResponseDeleteAt> for OrderResponseDeleteAtProto {
     fn from(value: OrderResponseDeleteAt) -> Self {
         OrderResponseDeleteAtProto {
             id: value.id,
+            external_id: value.external_id,
+            order_number: value.order_number,
             user_id: value.user_id,
             total_price: value.total_price,
             created_at: value.created_at.unwrap_or_default(),
             updated_at: value.updated_at.unwrap_or_default(),
             deleted_at: Some(value.deleted_at.unwrap_or_default()),
+            status: value.status,
+        }
+    }
+}
+
+/// An order with its items, each carrying its product's current name and
+/// price.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct OrderDetailResponse {
+    pub id: i32,
+    pub external_id: String,
+    pub order_number: String,
+    pub user_id: i32,
+    pub total_price: i32,
+    pub status: String,
+    #[serde(rename = "created_at")]
+    pub created_at: Option<String>,
+    #[serde(rename = "updated_at")]
+    pub updated_at: Option<String>,
+    pub items: Vec<OrderItemDetailResponse>,
+}
+
+impl OrderDetailResponse {
+    pub fn new(order: OrderModel, items: Vec<OrderItemDetailResponse>) -> Self {
+        let order = OrderResponse::from(order);
+
+        OrderDetailResponse {
+            id: order.id,
+            external_id: order.external_id,
+            order_number: order.order_number,
+            user_id: order.user_id,
+            total_price: order.total_price,
+            status: order.status,
+            created_at: order.created_at,
+            updated_at: order.updated_at,
+            items,
+        }
+    }
+}
+
+// response to proto
+impl From<OrderDetailResponse> for OrderDetailResponseProto {
+    fn from(value: OrderDetailResponse) -> Self {
+        OrderDetailResponseProto {
+            id: value.id,
+            external_id: value.external_id,
+            order_number: value.order_number,
+            user_id: value.user_id,
+            total_price: value.total_price,
+            status: value.status,
+            created_at: value.created_at.unwrap_or_default(),
+            updated_at: value.updated_at.unwrap_or_default(),
+            items: value.items.into_iter().map(Into::into).collect(),
         }
     }
 }
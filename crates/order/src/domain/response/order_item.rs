@@ -1,4 +1,7 @@
-use crate::model::order_item::OrderItem as OrderItemModel;
+use crate::{
+    domain::response::product::ProductResponse, model::order_item::OrderItem as OrderItemModel,
+};
+use genproto::order::OrderItemDetailResponse as OrderItemDetailResponseProto;
 use genproto::order_item::{
     OrderItemResponse as OrderItemResponseProto,
     OrderItemResponseDeleteAt as OrderItemResposeDeleteAtProto,
@@ -14,6 +17,8 @@ pub struct OrderItemResponse {
     pub product_id: i32,
     pub quantity: i32,
     pub price: i32,
+    /// `quantity * price`
+    pub subtotal: i32,
     #[serde(rename = "created_at")]
     pub created_at: Option<String>,
     #[serde(rename = "updated_at")]
@@ -28,6 +33,7 @@ impl From<OrderItemModel> for OrderItemResponse {
             product_id: value.product_id,
             quantity: value.quantity,
             price: value.price,
+            subtotal: value.quantity * value.price,
             created_at: value.created_at.map(|dt| dt.to_string()),
             updated_at: value.updated_at.map(|dt| dt.to_string()),
         }
@@ -42,6 +48,7 @@ impl From<OrderItemResponseProto> for OrderItemResponse {
             product_id: value.product_id,
             quantity: value.quantity,
             price: value.price,
+            subtotal: value.subtotal,
             created_at: parse_datetime(&value.created_at),
             updated_at: parse_datetime(&value.updated_at),
         }
@@ -58,6 +65,68 @@ impl From<OrderItemResponse> for OrderItemResponseProto {
             price: value.price,
             created_at: value.created_at.unwrap_or_default(),
             updated_at: value.updated_at.unwrap_or_default(),
+            subtotal: value.subtotal,
+        }
+    }
+}
+
+/// Shown in place of the name of a product that no longer exists.
+pub const DELETED_PRODUCT_NAME: &str = "Deleted product";
+
+/// An order item with its product's current name and price.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct OrderItemDetailResponse {
+    pub id: i32,
+    pub order_id: i32,
+    pub product_id: i32,
+    pub product_name: String,
+    pub quantity: i32,
+    /// Unit price when the order was placed.
+    pub price: i32,
+    pub subtotal: i32,
+    /// `None` when the product no longer exists.
+    pub current_price: Option<i64>,
+    #[serde(rename = "created_at")]
+    pub created_at: Option<String>,
+    #[serde(rename = "updated_at")]
+    pub updated_at: Option<String>,
+}
+
+impl OrderItemDetailResponse {
+    /// `product` is `None` when it was deleted since the order was placed.
+    pub fn new(item: OrderItemModel, product: Option<&ProductResponse>) -> Self {
+        let item = OrderItemResponse::from(item);
+
+        OrderItemDetailResponse {
+            id: item.id,
+            order_id: item.order_id,
+            product_id: item.product_id,
+            product_name: product
+                .map(|p| p.name.clone())
+                .unwrap_or_else(|| DELETED_PRODUCT_NAME.to_string()),
+            quantity: item.quantity,
+            price: item.price,
+            subtotal: item.subtotal,
+            current_price: product.map(|p| p.price),
+            created_at: item.created_at,
+            updated_at: item.updated_at,
+        }
+    }
+}
+
+impl From<OrderItemDetailResponse> for OrderItemDetailResponseProto {
+    fn from(value: OrderItemDetailResponse) -> Self {
+        OrderItemDetailResponseProto {
+            id: value.id,
+            order_id: value.order_id,
+            product_id: value.product_id,
+            product_name: value.product_name,
+            quantity: value.quantity,
+            price: value.price,
+            subtotal: value.subtotal,
+            current_price: value.current_price,
+            created_at: value.created_at.unwrap_or_default(),
+            updated_at: value.updated_at.unwrap_or_default(),
         }
     }
 }
@@ -7,9 +7,14 @@ use utoipa::ToSchema;
 #[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
 pub struct ProductResponse {
     pub id: i32,
+    pub external_id: String,
     pub name: String,
     pub price: i64,
     pub stock: i32,
+    #[serde(default)]
+    pub images: Vec<String>,
+    #[serde(default)]
+    pub reorder_threshold: Option<i32>,
     #[serde(rename = "created_at")]
     pub created_at: Option<String>,
     #[serde(rename = "updated_at")]
@@ -20,9 +25,12 @@ impl From<ProductResponseProto> for ProductResponse {
     fn from(value: ProductResponseProto) -> Self {
         ProductResponse {
             id: value.id,
+            external_id: value.external_id,
             name: value.name,
             price: value.price,
             stock: value.stock,
+            images: value.images,
+            reorder_threshold: value.reorder_threshold,
             created_at: parse_datetime(&value.created_at),
             updated_at: parse_datetime(&value.updated_at),
         }
@@ -33,11 +41,16 @@ impl From<ProductResponse> for ProductResponseProto {
     fn from(value: ProductResponse) -> Self {
         ProductResponseProto {
             id: value.id,
+            external_id: value.external_id,
             name: value.name,
             price: value.price,
             stock: value.stock,
+            images: value.images,
+            reorder_threshold: value.reorder_threshold,
             created_at: value.created_at.unwrap_or_default(),
             updated_at: value.updated_at.unwrap_or_default(),
+            category: None,
+            attributes: Vec::new(),
         }
     }
 }
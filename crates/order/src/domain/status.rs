@@ -0,0 +1,144 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{
+    Decode, Encode, Postgres, Type,
+    encode::IsNull,
+    error::BoxDynError,
+    postgres::{PgArgumentBuffer, PgTypeInfo, PgValueRef},
+};
+use std::{fmt, str::FromStr};
+
+/// Lifecycle of an order. Stored as a lowercase string so existing rows keep
+/// parsing; a value outside this set fails to decode instead of being passed
+/// through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OrderStatus {
+    Pending,
+    Paid,
+    Shipped,
+    Delivered,
+    Cancelled,
+}
+
+impl OrderStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Paid => "paid",
+            Self::Shipped => "shipped",
+            Self::Delivered => "delivered",
+            Self::Cancelled => "cancelled",
+        }
+    }
+
+    /// Orders move forward one step at a time and can only be cancelled
+    /// before they ship; delivered and cancelled are final.
+    pub fn can_transition_to(&self, next: OrderStatus) -> bool {
+        matches!(
+            (self, next),
+            (Self::Pending, Self::Paid)
+                | (Self::Pending, Self::Cancelled)
+                | (Self::Paid, Self::Shipped)
+                | (Self::Paid, Self::Cancelled)
+                | (Self::Shipped, Self::Delivered)
+        )
+    }
+
+    /// Customers may cancel their own orders; paying, shipping and
+    /// delivering are left to ADMIN and MODERATOR.
+    pub fn is_staff_only(&self) -> bool {
+        !matches!(self, Self::Cancelled)
+    }
+}
+
+impl fmt::Display for OrderStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for OrderStatus {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "pending" => Ok(Self::Pending),
+            "paid" => Ok(Self::Paid),
+            "shipped" => Ok(Self::Shipped),
+            "delivered" => Ok(Self::Delivered),
+            "cancelled" => Ok(Self::Cancelled),
+            other => Err(format!("Unknown order status '{other}'")),
+        }
+    }
+}
+
+impl Type<Postgres> for OrderStatus {
+    fn type_info() -> PgTypeInfo {
+        <String as Type<Postgres>>::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <String as Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for OrderStatus {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        let raw = <&str as Decode<Postgres>>::decode(value)?;
+        Ok(raw.parse()?)
+    }
+}
+
+impl Encode<'_, Postgres> for OrderStatus {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        <&str as Encode<Postgres>>::encode_by_ref(&self.as_str(), buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OrderStatus::{self, *};
+
+    const ALL: [OrderStatus; 5] = [Pending, Paid, Shipped, Delivered, Cancelled];
+
+    #[test]
+    fn allows_only_the_forward_steps_and_early_cancellation() {
+        let allowed = [
+            (Pending, Paid),
+            (Pending, Cancelled),
+            (Paid, Shipped),
+            (Paid, Cancelled),
+            (Shipped, Delivered),
+        ];
+
+        for from in ALL {
+            for to in ALL {
+                assert_eq!(
+                    from.can_transition_to(to),
+                    allowed.contains(&(from, to)),
+                    "{from} -> {to}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn cancelled_and_delivered_are_final() {
+        assert!(!Cancelled.can_transition_to(Pending));
+        assert!(ALL.iter().all(|to| !Cancelled.can_transition_to(*to)));
+        assert!(ALL.iter().all(|to| !Delivered.can_transition_to(*to)));
+    }
+
+    #[test]
+    fn parses_what_it_stores() {
+        for status in ALL {
+            assert_eq!(status.as_str().parse::<OrderStatus>(), Ok(status));
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_values() {
+        assert!("refunded".parse::<OrderStatus>().is_err());
+        assert!("Pending".parse::<OrderStatus>().is_err());
+    }
+}
@@ -3,8 +3,9 @@ pub mod product;
 use crate::config::grpc_config::GrpcClientConfig;
 use anyhow::{Context, Result};
 use genproto::product::product_query_service_client::ProductQueryServiceClient;
+use shared::{config::grpc_endpoint, with_grpc_compression};
 use std::time::Duration;
-use tonic::transport::{Channel, Endpoint};
+use tonic::transport::{Channel, ClientTlsConfig};
 
 #[derive(Clone)]
 pub struct GrpcClients {
@@ -13,16 +14,22 @@ pub struct GrpcClients {
 
 impl GrpcClients {
     pub async fn init(config: GrpcClientConfig) -> Result<Self> {
-        let product_channel = Self::connect(config.product, "product-service").await?;
+        let product_channel =
+            Self::connect(config.product, "product-service", config.tls.as_ref()).await?;
 
         Ok(Self {
-            product_query_client: ProductQueryServiceClient::new(product_channel.clone()),
+            product_query_client: with_grpc_compression!(ProductQueryServiceClient::new(
+                product_channel.clone()
+            )),
         })
     }
 
-    async fn connect(addr: String, service: &str) -> Result<Channel> {
-        let endpoint = Endpoint::from_shared(addr.clone())
-            .with_context(|| format!("Invalid gRPC address for {service}: {addr}"))?;
+    async fn connect(
+        addr: String,
+        service: &str,
+        tls: Option<&ClientTlsConfig>,
+    ) -> Result<Channel> {
+        let endpoint = grpc_endpoint(&addr, service, tls)?;
 
         let configured_endpoint = endpoint
             .connect_timeout(Duration::from_secs(3))
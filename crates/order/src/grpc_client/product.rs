@@ -5,10 +5,10 @@ use crate::{
 use anyhow::Result;
 use async_trait::async_trait;
 use genproto::product::{
-    FindByIdProductRequest,
+    FindByIdProductRequest, FindByIdsProductRequest,
     product_query_service_client::ProductQueryServiceClient as ProductQueryServiceGrpcClient,
 };
-use shared::errors::AppErrorGrpc;
+use shared::{errors::AppErrorGrpc, utils::BatchResponse};
 use tonic::{Request, transport::Channel};
 
 pub struct ProductGrpcClientService {
@@ -44,4 +44,26 @@ impl ProductGrpcClientTrait for ProductGrpcClientService {
             data: domain_user,
         })
     }
+
+    async fn find_by_ids(
+        &self,
+        ids: Vec<i32>,
+    ) -> Result<ApiResponse<BatchResponse<ProductResponse>>, AppErrorGrpc> {
+        let req = Request::new(FindByIdsProductRequest { ids });
+
+        let mut client = self.client.clone();
+
+        let response = client.find_by_ids(req).await.map_err(AppErrorGrpc::from)?;
+
+        let inner = response.into_inner();
+
+        Ok(ApiResponse {
+            status: inner.status,
+            message: inner.message,
+            data: BatchResponse {
+                succeeded: inner.succeeded.into_iter().map(Into::into).collect(),
+                failed: inner.failed.into_iter().map(Into::into).collect(),
+            },
+        })
+    }
 }
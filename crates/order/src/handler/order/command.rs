@@ -62,6 +62,7 @@ impl OrderCommandService for OrderCommandGrpcServiceImpl {
         let domain_req = DomainCreateOrderRequest {
             user_id: req.user_id,
             items,
+            idempotency_key: Some(req.idempotency_key).filter(|k| !k.is_empty()),
         };
 
         let api_response = self
@@ -106,6 +107,8 @@ impl OrderCommandService for OrderCommandGrpcServiceImpl {
             order_id: req.order_id,
             user_id: req.user_id,
             items,
+            status: Some(req.status).filter(|s| !s.is_empty()),
+            caller_is_staff: req.caller_is_staff,
         };
 
         let api_response = self
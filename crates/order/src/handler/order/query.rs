@@ -2,14 +2,28 @@ use crate::{
     abstract_trait::order::service::DynOrderQueryService,
     domain::requests::order::FindAllOrder as DomainFindAllOrder,
 };
+use chrono::{DateTime, Utc};
 use genproto::order::{
-    ApiResponseOrder, ApiResponsePaginationOrder, ApiResponsePaginationOrderDeleteAt,
-    FindAllOrderRequest, FindByIdOrderRequest, order_query_service_server::OrderQueryService,
+    ApiResponseOrder, ApiResponseOrderDetail, ApiResponsePaginationOrder,
+    ApiResponsePaginationOrderDeleteAt, FindAllOrderRequest, FindByExternalIdOrderRequest,
+    FindByIdOrderRequest, FindByUserIdOrderRequest, order_query_service_server::OrderQueryService,
 };
 use shared::errors::AppErrorGrpc;
 use std::fmt;
 use tonic::{Request, Response, Status};
 use tracing::info;
+use uuid::Uuid;
+
+/// Empty means the range is open on that side.
+fn parse_created_bound(value: &str, field: &str) -> Result<Option<DateTime<Utc>>, Status> {
+    if value.is_empty() {
+        return Ok(None);
+    }
+
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| Some(dt.with_timezone(&Utc)))
+        .map_err(|_| Status::invalid_argument(format!("{field} must be an RFC3339 timestamp")))
+}
 
 #[derive(Clone)]
 pub struct OrderQueryGrpcServiceImpl {
@@ -46,6 +60,12 @@ impl OrderQueryService for OrderQueryGrpcServiceImpl {
             page: req.page,
             page_size: req.page_size,
             search: req.search,
+            include_items: req.include_items,
+            after: req.after,
+            sort: req.sort,
+            user_id: (req.user_id > 0).then_some(req.user_id),
+            created_from: parse_created_bound(&req.created_from, "created_from")?,
+            created_to: parse_created_bound(&req.created_to, "created_to")?,
         };
 
         let api_response = self
@@ -98,6 +118,56 @@ impl OrderQueryService for OrderQueryGrpcServiceImpl {
         Ok(Response::new(reply))
     }
 
+    async fn find_detail_by_id(
+        &self,
+        request: Request<FindByIdOrderRequest>,
+    ) -> Result<Response<ApiResponseOrderDetail>, Status> {
+        info!("Handling gRPC request: Find order detail by ID");
+
+        let req = request.into_inner();
+
+        let api_response = self
+            .order_query_service
+            .find_detail_by_id(req.id)
+            .await
+            .map_err(AppErrorGrpc::from)?;
+
+        let reply = ApiResponseOrderDetail {
+            status: "success".into(),
+            message: api_response.message,
+            data: Some(api_response.data.into()),
+        };
+
+        info!("Successfully fetched order detail with ID: {}", req.id);
+        Ok(Response::new(reply))
+    }
+
+    async fn find_by_external_id(
+        &self,
+        request: Request<FindByExternalIdOrderRequest>,
+    ) -> Result<Response<ApiResponseOrder>, Status> {
+        info!("Handling gRPC request: Find Order by external ID");
+
+        let req = request.into_inner();
+
+        let external_id = Uuid::parse_str(&req.external_id)
+            .map_err(|_| Status::invalid_argument("external_id must be a valid UUID"))?;
+
+        let api_response = self
+            .order_query_service
+            .find_by_external_id(external_id)
+            .await
+            .map_err(AppErrorGrpc::from)?;
+
+        let reply = ApiResponseOrder {
+            status: "success".into(),
+            message: api_response.message,
+            data: Some(api_response.data.into()),
+        };
+
+        Ok(Response::new(reply))
+    }
+
     async fn find_by_active(
         &self,
         request: Request<FindAllOrderRequest>,
@@ -110,6 +180,12 @@ impl OrderQueryService for OrderQueryGrpcServiceImpl {
             page: req.page,
             page_size: req.page_size,
             search: req.search,
+            include_items: req.include_items,
+            after: String::new(),
+            sort: String::new(),
+            user_id: None,
+            created_from: None,
+            created_to: None,
         };
 
         let api_response = self
@@ -149,6 +225,12 @@ impl OrderQueryService for OrderQueryGrpcServiceImpl {
             page: req.page,
             page_size: req.page_size,
             search: req.search,
+            include_items: req.include_items,
+            after: String::new(),
+            sort: String::new(),
+            user_id: None,
+            created_from: None,
+            created_to: None,
         };
 
         let api_response = self
@@ -175,4 +257,52 @@ impl OrderQueryService for OrderQueryGrpcServiceImpl {
         info!("Successfully fetched {} trashed orders", len);
         Ok(Response::new(reply))
     }
+
+    async fn find_by_user_id(
+        &self,
+        request: Request<FindByUserIdOrderRequest>,
+    ) -> Result<Response<ApiResponsePaginationOrder>, Status> {
+        info!("Handling gRPC request: Find orders by user");
+
+        let req = request.into_inner();
+
+        let domain_req = DomainFindAllOrder {
+            page: req.page,
+            page_size: req.page_size,
+            search: String::new(),
+            include_items: req.include_items,
+            after: String::new(),
+            sort: String::new(),
+            user_id: None,
+            created_from: None,
+            created_to: None,
+        };
+
+        let api_response = self
+            .order_query_service
+            .find_by_user_id(req.user_id, &domain_req)
+            .await
+            .map_err(AppErrorGrpc::from)?;
+
+        let data: Vec<genproto::order::OrderResponse> = api_response
+            .data
+            .into_iter()
+            .map(|item| item.into())
+            .collect();
+
+        let len = data.len();
+
+        let reply = ApiResponsePaginationOrder {
+            status: "success".into(),
+            message: api_response.message,
+            data,
+            pagination: Some(api_response.pagination.into()),
+        };
+
+        info!(
+            "Successfully fetched {} orders of user {}",
+            len, req.user_id
+        );
+        Ok(Response::new(reply))
+    }
 }
@@ -1,5 +1,6 @@
 use anyhow::{Context, Result};
 use genproto::{
+    health::health_service_server::HealthServiceServer,
     order::{
         order_command_service_server::OrderCommandServiceServer,
         order_query_service_server::OrderQueryServiceServer,
@@ -14,13 +15,23 @@ use order::{
     },
     state::AppState,
 };
+use prometheus_client::registry::Registry;
 use shared::{
     config::ConnectionManager,
-    utils::{Telemetry, init_logger},
+    utils::{
+        ConnectionLimit, HealthGrpcService, InFlightLayer, InFlightRequests, ReadOnlyLayer,
+        RequestIdLayer, RequiredMetadata, RestartBackoff, SlowRequestLayer, Telemetry, init_logger,
+        spawn_metrics_server,
+    },
+    with_grpc_compression,
 };
 use sqlx::{Pool, Postgres};
 use std::sync::Arc;
 use tokio::sync::broadcast;
+use tonic::{
+    service::InterceptorLayer,
+    transport::{Server, ServerTlsConfig},
+};
 use tracing::{error, info, warn};
 
 #[tokio::main]
@@ -72,20 +83,21 @@ async fn setup() -> Result<(ServerConfig, Arc<AppState>, Telemetry)> {
         is_enable_file,
     );
 
-    let db_pool = ConnectionManager::new_pool(
+    let db_pools = ConnectionManager::new_pools(
         &server_config.database_url,
+        server_config.database_read_url.as_deref(),
         config.db_min_conn,
         config.db_max_conn,
     )
     .await
     .context("Failed to initialize database pool")?;
 
-    run_migrations(&db_pool)
+    run_migrations(&db_pools.write, server_config.run_migrations)
         .await
         .context("failed to migration database")?;
 
     let state = Arc::new(
-        AppState::new(db_pool, config)
+        AppState::new(db_pools.clone(), config)
             .await
             .context("Failed to create AppState")?,
     );
@@ -100,13 +112,22 @@ async fn run_servers(
     shutdown_tx: broadcast::Sender<()>,
 ) -> Result<tokio::task::JoinHandle<()>> {
     let grpc_addr = server_config.grpc_addr;
+    let grpc_tls = server_config.tls.clone();
+
+    let in_flight = InFlightRequests::default();
+    let mut registry = Registry::default();
+    in_flight.register(&mut registry);
+    spawn_metrics_server(server_config.metrics_addr, registry, state.db_pools.clone());
 
     let shutdown_tx_for_server = shutdown_tx.clone();
     let shutdown_tx_for_ctrlc = shutdown_tx.clone();
 
     let server_handle = tokio::spawn(async move {
+        let mut backoff = RestartBackoff::default();
+
         loop {
             info!("Attempting to start gRPC server on {grpc_addr}");
+            backoff.mark_started();
 
             let shutdown_rx = shutdown_tx_for_server.subscribe();
 
@@ -126,6 +147,8 @@ async fn run_servers(
                 order_query,
                 order_item_query,
                 grpc_addr,
+                grpc_tls.clone(),
+                in_flight.clone(),
                 shutdown_rx,
             )
             .await
@@ -135,8 +158,9 @@ async fn run_servers(
                     break;
                 }
                 Err(e) => {
-                    error!("gRPC server failed: {e}. Restarting in 5s...");
-                    tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+                    let delay = backoff.next_delay();
+                    error!("gRPC server failed: {e}. Restarting in {delay:?}...");
+                    tokio::time::sleep(delay).await;
                 }
             }
         }
@@ -186,6 +210,8 @@ async fn start_grpc_server(
     order_query_handler: OrderQueryGrpcServiceImpl,
     order_item_handler: OrderItemGrpcServiceImpl,
     addr: std::net::SocketAddr,
+    tls: Option<ServerTlsConfig>,
+    in_flight: InFlightRequests,
     mut shutdown_rx: broadcast::Receiver<()>,
 ) -> Result<()> {
     info!("Starting gRPC server on {addr}");
@@ -195,17 +221,111 @@ async fn start_grpc_server(
         info!("gRPC server received shutdown signal");
     };
 
-    tonic::transport::Server::builder()
-        .add_service(OrderCommandServiceServer::new(order_command_handler))
-        .add_service(OrderQueryServiceServer::new(order_query_handler))
-        .add_service(OrderItemServiceServer::new(order_item_handler))
-        .serve_with_shutdown(addr, shutdown_future)
+    let mut server = Server::builder();
+
+    if let Some(tls) = tls {
+        server = server
+            .tls_config(tls)
+            .context("Invalid gRPC server TLS configuration")?;
+    }
+
+    let incoming = ConnectionLimit::from_env()
+        .bind(addr)
+        .await
+        .with_context(|| format!("Failed to bind gRPC server on {addr}"))?;
+
+    server
+        .layer(InFlightLayer::new(in_flight))
+        .layer(RequestIdLayer)
+        .layer(SlowRequestLayer::from_env())
+        .layer(InterceptorLayer::new(RequiredMetadata::from_env()))
+        .layer(ReadOnlyLayer::from_env())
+        .add_service(with_grpc_compression!(OrderCommandServiceServer::new(
+            order_command_handler
+        )))
+        .add_service(with_grpc_compression!(OrderQueryServiceServer::new(
+            order_query_handler
+        )))
+        .add_service(with_grpc_compression!(OrderItemServiceServer::new(
+            order_item_handler
+        )))
+        .add_service(with_grpc_compression!(HealthServiceServer::new(
+            HealthGrpcService::new("order-service", env!("CARGO_PKG_VERSION"))
+        )))
+        .serve_with_incoming_shutdown(incoming, shutdown_future)
         .await
         .context("gRPC server failed to start or serve")
 }
 
-pub async fn run_migrations(pool: &Pool<Postgres>) -> anyhow::Result<()> {
-    sqlx::migrate!("./migrations").run(pool).await?;
+/// Does nothing when AUTO_MIGRATE is false, for deployments that migrate
+/// out of band. Replicas booting together are serialized by the migrator's
+/// Postgres advisory lock, so only one of them applies pending migrations.
+pub async fn run_migrations(pool: &Pool<Postgres>, enabled: bool) -> anyhow::Result<()> {
+    if !enabled {
+        info!("⏭️ AUTO_MIGRATE is disabled, skipping database migrations");
+        return Ok(());
+    }
+
+    let mut migrator = sqlx::migrate!("./migrations");
+    migrator.set_locking(true);
+
+    migrator.run(pool).await?;
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::run_migrations;
+    use sqlx::PgPool;
+
+    /// `None` until the migrator has created its bookkeeping table.
+    async fn applied_migrations(pool: &PgPool) -> Option<i64> {
+        let table: Option<String> =
+            sqlx::query_scalar("SELECT to_regclass('_sqlx_migrations')::text")
+                .fetch_one(pool)
+                .await
+                .unwrap();
+        table?;
+
+        sqlx::query_scalar("SELECT count(*) FROM _sqlx_migrations")
+            .fetch_one(pool)
+            .await
+            .ok()
+    }
+
+    #[sqlx::test(migrations = false)]
+    #[ignore = "needs a Postgres server at DATABASE_URL"]
+    async fn disabled_auto_migrate_leaves_the_schema_alone(pool: PgPool) {
+        run_migrations(&pool, false).await.unwrap();
+
+        let orders: Option<String> = sqlx::query_scalar("SELECT to_regclass('orders')::text")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(orders, None);
+        assert_eq!(applied_migrations(&pool).await, None);
+    }
+
+    #[sqlx::test(migrations = false)]
+    #[ignore = "needs a Postgres server at DATABASE_URL"]
+    async fn replicas_migrating_at_once_apply_each_migration_once(pool: PgPool) {
+        // Without the advisory lock the losers fail on the migrations table
+        // or on objects the winner just created.
+        let replicas: Vec<_> = (0..4)
+            .map(|_| {
+                let pool = pool.clone();
+                tokio::spawn(async move { run_migrations(&pool, true).await })
+            })
+            .collect();
+        for replica in replicas {
+            replica.await.unwrap().unwrap();
+        }
+
+        let expected = sqlx::migrate!("./migrations")
+            .iter()
+            .filter(|m| m.migration_type.is_up_migration())
+            .count() as i64;
+        assert_eq!(applied_migrations(&pool).await, Some(expected));
+    }
+}
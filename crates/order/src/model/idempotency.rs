@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// A reserved `Idempotency-Key`. `order_id` stays empty until the order it
+/// guards has been created.
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct IdempotencyKey {
+    pub request_hash: String,
+    pub order_id: Option<i32>,
+}
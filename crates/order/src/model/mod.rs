@@ -1,2 +1,3 @@
+pub mod idempotency;
 pub mod order;
 pub mod order_item;
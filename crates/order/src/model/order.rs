@@ -1,13 +1,24 @@
+use crate::{domain::status::OrderStatus, model::order_item::OrderItem};
 use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use uuid::Uuid;
 
 #[derive(Debug, Serialize, Deserialize, FromRow)]
 pub struct Order {
     pub order_id: i32,
+    pub external_id: Uuid,
+    pub order_number: String,
     pub user_id: i32,
     pub total_price: i32,
     pub created_at: Option<NaiveDateTime>,
     pub updated_at: Option<NaiveDateTime>,
     pub deleted_at: Option<NaiveDateTime>,
+    pub status: OrderStatus,
+}
+
+#[derive(Debug)]
+pub struct OrderWithItems {
+    pub order: Order,
+    pub items: Vec<OrderItem>,
 }
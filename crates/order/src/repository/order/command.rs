@@ -1,7 +1,14 @@
 use crate::{
     abstract_trait::order::repository::OrderCommandRepositoryTrait,
-    domain::requests::order::{CreateOrderRecordRequest, UpdateOrderRecordRequest},
-    model::order::Order as OrderModel,
+    config::myconfig::OrderNumberFormat,
+    domain::{
+        requests::order::{CreateOrderRecordRequest, UpdateOrderRecordRequest},
+        status::OrderStatus,
+    },
+    model::{
+        order::{Order as OrderModel, OrderWithItems},
+        order_item::OrderItem as OrderItemModel,
+    },
 };
 use shared::{config::ConnectionPool, errors::RepositoryError};
 
@@ -10,11 +17,15 @@ use tracing::{error, info};
 
 pub struct OrderCommandRepository {
     db: ConnectionPool,
+    order_number_format: OrderNumberFormat,
 }
 
 impl OrderCommandRepository {
-    pub fn new(db: ConnectionPool) -> Self {
-        Self { db }
+    pub fn new(db: ConnectionPool, order_number_format: OrderNumberFormat) -> Self {
+        Self {
+            db,
+            order_number_format,
+        }
     }
 }
 
@@ -23,20 +34,59 @@ impl OrderCommandRepositoryTrait for OrderCommandRepository {
     async fn create_order(
         &self,
         req: &CreateOrderRecordRequest,
-    ) -> Result<OrderModel, RepositoryError> {
-        let mut conn = self.db.acquire().await.map_err(RepositoryError::from)?;
+    ) -> Result<Option<OrderWithItems>, RepositoryError> {
+        let mut tx = self.db.begin().await.map_err(RepositoryError::from)?;
 
-        let result = sqlx::query_as!(
+        // A claim held by a transaction that is still open blocks here until
+        // that transaction commits or rolls back.
+        if let Some(claim) = &req.idempotency {
+            let reserved = sqlx::query!(
+                r#"
+            INSERT INTO order_idempotency_keys (user_id, idempotency_key, request_hash)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (user_id, idempotency_key) DO UPDATE
+            SET request_hash = EXCLUDED.request_hash,
+                order_id     = NULL,
+                created_at   = current_timestamp
+            WHERE order_idempotency_keys.created_at < current_timestamp - INTERVAL '24 hours'
+            RETURNING user_id
+            "#,
+                req.user_id,
+                claim.key,
+                claim.request_hash,
+            )
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| {
+                error!(
+                    "❌ Failed to reserve idempotency key for user {}: {:?}",
+                    req.user_id, e
+                );
+                RepositoryError::from(e)
+            })?;
+
+            if reserved.is_none() {
+                return Ok(None);
+            }
+        }
+
+        let order = sqlx::query_as!(
             OrderModel,
             r#"
-        INSERT INTO orders (user_id, total_price, created_at, updated_at)
-        VALUES ($1, $2, current_timestamp, current_timestamp)
-        RETURNING order_id, user_id, total_price, created_at, updated_at, deleted_at
+        WITH seq AS (SELECT nextval('order_number_seq') AS n)
+        INSERT INTO orders (user_id, total_price, order_number, created_at, updated_at)
+        SELECT $1, $2, $3::TEXT || LPAD(seq.n::TEXT, GREATEST($4::INT, LENGTH(seq.n::TEXT)), '0'),
+            current_timestamp, current_timestamp
+        FROM seq
+        RETURNING order_id, external_id, order_number, user_id, total_price, created_at, updated_at, deleted_at,
+            status AS "status: OrderStatus"
         "#,
             req.user_id,
             req.total_price,
+            self.order_number_format.prefix,
+            self.order_number_format.padding,
         )
-        .fetch_one(&mut *conn)
+        .fetch_one(&mut *tx)
         .await
         .map_err(|err| {
             error!(
@@ -46,11 +96,66 @@ impl OrderCommandRepositoryTrait for OrderCommandRepository {
             RepositoryError::from(err)
         })?;
 
+        let mut items = Vec::with_capacity(req.items.len());
+
+        for line in &req.items {
+            let item = sqlx::query_as!(
+                OrderItemModel,
+                r#"
+            INSERT INTO order_items (order_id, product_id, quantity, price, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, current_timestamp, current_timestamp)
+            RETURNING order_item_id, order_id, product_id, quantity, price, created_at, updated_at, deleted_at
+            "#,
+                order.order_id,
+                line.product_id,
+                line.quantity,
+                line.price
+            )
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|err| {
+                error!(
+                    "❌ Failed to create order item for order {}: {:?}",
+                    order.order_id, err
+                );
+                RepositoryError::from(err)
+            })?;
+
+            items.push(item);
+        }
+
+        if let Some(claim) = &req.idempotency {
+            sqlx::query!(
+                r#"
+            UPDATE order_idempotency_keys
+            SET order_id = $3
+            WHERE user_id = $1 AND idempotency_key = $2
+            "#,
+                req.user_id,
+                claim.key,
+                order.order_id,
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| {
+                error!(
+                    "❌ Failed to attach order {} to idempotency key: {:?}",
+                    order.order_id, e
+                );
+                RepositoryError::from(e)
+            })?;
+        }
+
+        tx.commit().await.map_err(RepositoryError::from)?;
+
         info!(
-            "✅ Created order ID {} for user {}",
-            result.order_id, result.user_id
+            "✅ Created order {} (ID {}) with {} item(s) for user {}",
+            order.order_number,
+            order.order_id,
+            items.len(),
+            order.user_id
         );
-        Ok(result)
+        Ok(Some(OrderWithItems { order, items }))
     }
 
     async fn update_order(
@@ -65,13 +170,16 @@ impl OrderCommandRepositoryTrait for OrderCommandRepository {
         UPDATE orders
         SET user_id     = $2,
             total_price = $3,
+            status      = $4,
             updated_at  = current_timestamp
         WHERE order_id = $1
-        RETURNING order_id, user_id, total_price, created_at, updated_at, deleted_at
+        RETURNING order_id, external_id, order_number, user_id, total_price, created_at, updated_at, deleted_at,
+            status AS "status: OrderStatus"
         "#,
             req.order_id,
             req.user_id,
             req.total_price,
+            req.status.as_str(),
         )
         .fetch_one(&mut *conn)
         .await
@@ -87,7 +195,7 @@ impl OrderCommandRepositoryTrait for OrderCommandRepository {
     async fn trash_order(&self, id: i32) -> Result<OrderModel, RepositoryError> {
         info!("🗑️ Trashing order: {}", id);
 
-        let mut conn = self.db.acquire().await.map_err(RepositoryError::from)?;
+        let mut tx = self.db.begin().await.map_err(RepositoryError::from)?;
 
         let order = sqlx::query_as!(
             OrderModel,
@@ -95,24 +203,72 @@ impl OrderCommandRepositoryTrait for OrderCommandRepository {
             UPDATE orders
             SET deleted_at = CURRENT_TIMESTAMP
             WHERE order_id = $1 AND deleted_at IS NULL
-            RETURNING *
+            RETURNING order_id, external_id, order_number, user_id, total_price, created_at, updated_at, deleted_at,
+                status AS "status: OrderStatus"
             "#,
             id
         )
-        .fetch_one(&mut *conn)
+        .fetch_optional(&mut *tx)
         .await
         .map_err(|e| {
             error!("❌ Failed to trash order {}: {:?}", id, e);
             RepositoryError::from(e)
+        })?
+        .ok_or(RepositoryError::NotFound)?;
+
+        // Items share the order's deleted_at so restore can tell them apart
+        // from items that were trashed on their own.
+        let items = sqlx::query!(
+            r#"
+            UPDATE order_items
+            SET deleted_at = (SELECT deleted_at FROM orders WHERE order_id = $1),
+                updated_at = current_timestamp
+            WHERE order_id = $1 AND deleted_at IS NULL
+            "#,
+            id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| {
+            error!("❌ Failed to trash items of order {}: {:?}", id, e);
+            RepositoryError::from(e)
         })?;
 
+        tx.commit().await.map_err(RepositoryError::from)?;
+
+        info!(
+            "🗑️ Trashed order {} with {} item(s)",
+            id,
+            items.rows_affected()
+        );
+
         Ok(order)
     }
 
     async fn restore_order(&self, id: i32) -> Result<OrderModel, RepositoryError> {
         info!("🔄 Restoring order: {}", id);
 
-        let mut conn = self.db.acquire().await.map_err(RepositoryError::from)?;
+        let mut tx = self.db.begin().await.map_err(RepositoryError::from)?;
+
+        let items = sqlx::query!(
+            r#"
+            UPDATE order_items
+            SET deleted_at = NULL,
+                updated_at = current_timestamp
+            WHERE order_id = $1
+              AND deleted_at = (
+                  SELECT deleted_at FROM orders
+                  WHERE order_id = $1 AND deleted_at IS NOT NULL
+              )
+            "#,
+            id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| {
+            error!("❌ Failed to restore items of order {}: {:?}", id, e);
+            RepositoryError::from(e)
+        })?;
 
         let order = sqlx::query_as!(
             OrderModel,
@@ -120,17 +276,43 @@ impl OrderCommandRepositoryTrait for OrderCommandRepository {
             UPDATE orders
             SET deleted_at = NULL
             WHERE order_id = $1 AND deleted_at IS NOT NULL
-            RETURNING *
+            RETURNING order_id, external_id, order_number, user_id, total_price, created_at, updated_at, deleted_at,
+                status AS "status: OrderStatus"
             "#,
             id
         )
-        .fetch_one(&mut *conn)
+        .fetch_optional(&mut *tx)
         .await
         .map_err(|e| {
             error!("❌ Failed to restore order {}: {:?}", id, e);
             RepositoryError::from(e)
         })?;
 
+        let Some(order) = order else {
+            let exists = sqlx::query_scalar!(
+                r#"
+            SELECT order_id FROM orders WHERE order_id = $1
+            "#,
+                id
+            )
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(RepositoryError::from)?;
+
+            return Err(match exists {
+                Some(_) => RepositoryError::NotTrashed(format!("Order {id} is not trashed")),
+                None => RepositoryError::NotFound,
+            });
+        };
+
+        tx.commit().await.map_err(RepositoryError::from)?;
+
+        info!(
+            "🔄 Restored order {} with {} item(s)",
+            id,
+            items.rows_affected()
+        );
+
         Ok(order)
     }
 
@@ -139,7 +321,7 @@ impl OrderCommandRepositoryTrait for OrderCommandRepository {
 
         let mut conn = self.db.acquire().await.map_err(RepositoryError::from)?;
 
-        sqlx::query!(
+        let result = sqlx::query!(
             r#"
             DELETE FROM orders
             WHERE order_id = $1 AND deleted_at IS NOT NULL
@@ -153,6 +335,10 @@ impl OrderCommandRepositoryTrait for OrderCommandRepository {
             RepositoryError::from(e)
         })?;
 
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
         Ok(())
     }
 
@@ -196,3 +382,124 @@ impl OrderCommandRepositoryTrait for OrderCommandRepository {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::OrderCommandRepository;
+    use crate::{
+        abstract_trait::order::repository::OrderCommandRepositoryTrait,
+        config::myconfig::OrderNumberFormat, domain::requests::order::CreateOrderRecordRequest,
+    };
+    use shared::errors::RepositoryError;
+    use sqlx::PgPool;
+    use std::{collections::HashSet, sync::Arc};
+    use tokio::task::JoinSet;
+
+    /// Product ids of the live items of order 1.
+    async fn live_items(pool: &PgPool) -> Vec<i32> {
+        sqlx::query_scalar(
+            "SELECT product_id FROM order_items
+             WHERE order_id = 1 AND deleted_at IS NULL
+             ORDER BY product_id",
+        )
+        .fetch_all(pool)
+        .await
+        .unwrap()
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "needs a Postgres server at DATABASE_URL"]
+    async fn trash_and_restore_cascade_to_the_items_trashed_with_the_order(pool: PgPool) {
+        // Product 12's line was trashed on its own before the order was.
+        sqlx::raw_sql(
+            r#"
+            INSERT INTO orders (order_id, order_number, user_id, total_price)
+            VALUES (1, 'ORD-000001', 3, 7000);
+            INSERT INTO order_items (order_id, product_id, quantity, price, deleted_at)
+            VALUES (1, 10, 2, 1500, NULL),
+                   (1, 11, 1, 4000, NULL),
+                   (1, 12, 1, 900, '2025-01-01 00:00:00');
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let repo = OrderCommandRepository::new(
+            pool.clone(),
+            OrderNumberFormat {
+                prefix: "ORD-".to_string(),
+                padding: 6,
+            },
+        );
+
+        assert!(matches!(
+            repo.restore_order(1).await,
+            Err(RepositoryError::NotTrashed(_))
+        ));
+
+        let trashed = repo.trash_order(1).await.unwrap();
+        assert!(trashed.deleted_at.is_some());
+        assert!(live_items(&pool).await.is_empty());
+
+        let restored = repo.restore_order(1).await.unwrap();
+        assert!(restored.deleted_at.is_none());
+        assert_eq!(live_items(&pool).await, [10, 11]);
+
+        assert!(matches!(
+            repo.trash_order(2).await,
+            Err(RepositoryError::NotFound)
+        ));
+    }
+
+    async fn create(repo: &OrderCommandRepository, user_id: i32) -> String {
+        let request = CreateOrderRecordRequest {
+            user_id,
+            total_price: 1000,
+            items: Vec::new(),
+            idempotency: None,
+        };
+
+        repo.create_order(&request)
+            .await
+            .unwrap()
+            .unwrap()
+            .order
+            .order_number
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "needs a Postgres server at DATABASE_URL"]
+    async fn concurrent_creates_get_unique_order_numbers(pool: PgPool) {
+        let repo = Arc::new(OrderCommandRepository::new(
+            pool.clone(),
+            OrderNumberFormat {
+                prefix: "ORD-".to_string(),
+                padding: 6,
+            },
+        ));
+
+        let mut creates = JoinSet::new();
+        for user_id in 1..=20 {
+            let repo = repo.clone();
+            creates.spawn(async move { create(&repo, user_id).await });
+        }
+        let numbers: Vec<String> = creates.join_all().await;
+
+        assert_eq!(numbers.iter().collect::<HashSet<_>>().len(), 20);
+        for number in &numbers {
+            let digits = number.strip_prefix("ORD-").unwrap();
+            assert!(
+                digits.len() == 6 && digits.chars().all(|c| c.is_ascii_digit()),
+                "{number}"
+            );
+        }
+
+        // Past the padding the number grows instead of being cut off.
+        sqlx::query("SELECT setval('order_number_seq', 1234567)")
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert_eq!(create(&repo, 21).await, "ORD-1234568");
+    }
+}
@@ -0,0 +1,76 @@
+use crate::{
+    abstract_trait::order::repository::OrderIdempotencyRepositoryTrait,
+    model::idempotency::IdempotencyKey,
+};
+use shared::{config::ConnectionPool, errors::RepositoryError};
+
+use async_trait::async_trait;
+use tracing::error;
+
+pub struct OrderIdempotencyRepository {
+    db: ConnectionPool,
+}
+
+impl OrderIdempotencyRepository {
+    pub fn new(db: ConnectionPool) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl OrderIdempotencyRepositoryTrait for OrderIdempotencyRepository {
+    async fn find(
+        &self,
+        user_id: i32,
+        key: &str,
+    ) -> Result<Option<IdempotencyKey>, RepositoryError> {
+        let mut conn = self.db.acquire().await.map_err(RepositoryError::from)?;
+
+        let result = sqlx::query_as!(
+            IdempotencyKey,
+            r#"
+            SELECT request_hash, order_id
+            FROM order_idempotency_keys
+            WHERE user_id = $1 AND idempotency_key = $2
+              AND created_at >= current_timestamp - INTERVAL '24 hours'
+            "#,
+            user_id,
+            key,
+        )
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| {
+            error!(
+                "❌ Failed to fetch idempotency key for user {}: {:?}",
+                user_id, e
+            );
+            RepositoryError::from(e)
+        })?;
+
+        Ok(result)
+    }
+
+    async fn purge_expired(&self, limit: i64) -> Result<u64, RepositoryError> {
+        let mut conn = self.db.acquire().await.map_err(RepositoryError::from)?;
+
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM order_idempotency_keys
+            WHERE (user_id, idempotency_key) IN (
+                SELECT user_id, idempotency_key FROM order_idempotency_keys
+                WHERE created_at < current_timestamp - INTERVAL '24 hours'
+                LIMIT $1
+            )
+            "#,
+            limit,
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| {
+            error!("❌ Failed to purge expired idempotency keys: {:?}", e);
+            RepositoryError::from(e)
+        })?;
+
+        Ok(result.rows_affected())
+    }
+}
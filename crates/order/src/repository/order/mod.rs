@@ -1,5 +1,7 @@
 mod command;
+mod idempotency;
 mod query;
 
 pub use self::command::OrderCommandRepository;
+pub use self::idempotency::OrderIdempotencyRepository;
 pub use self::query::OrderQueryRepository;
@@ -1,19 +1,49 @@
 use crate::{
     abstract_trait::order::repository::OrderQueryRepositoryTrait,
-    domain::requests::order::FindAllOrder, model::order::Order as OrderModel,
+    domain::{requests::order::FindAllOrder, status::OrderStatus},
+    model::{
+        order::{Order as OrderModel, OrderWithItems},
+        order_item::OrderItem as OrderItemModel,
+    },
 };
 use async_trait::async_trait;
-use shared::{config::ConnectionPool, errors::RepositoryError};
+use shared::{
+    config::ConnectionPool,
+    errors::RepositoryError,
+    utils::{Cursor, QueryPlanLogger, order_by_clause, parse_sort},
+};
+use sqlx::FromRow;
+use std::{collections::HashMap, time::Instant};
 use tracing::{error, info};
+use uuid::Uuid;
+
+/// Fields `find_all` may be sorted by, mapped to their SQL columns.
+const SORT_COLUMNS: &[(&str, &str)] = &[
+    ("id", "o.order_id"),
+    ("user_id", "o.user_id"),
+    ("total_price", "o.total_price"),
+    ("created_at", "o.created_at"),
+];
+
+#[derive(FromRow)]
+struct OrderRow {
+    #[sqlx(flatten)]
+    order: OrderModel,
+    total_count: Option<i64>,
+}
 
 #[derive(Clone)]
 pub struct OrderQueryRepository {
     db: ConnectionPool,
+    query_plans: QueryPlanLogger,
 }
 
 impl OrderQueryRepository {
     pub fn new(db: ConnectionPool) -> Self {
-        Self { db }
+        Self {
+            db,
+            query_plans: QueryPlanLogger::from_env(),
+        }
     }
 }
 
@@ -22,8 +52,12 @@ impl OrderQueryRepositoryTrait for OrderQueryRepository {
     async fn find_all(
         &self,
         req: &FindAllOrder,
-    ) -> Result<(Vec<OrderModel>, i64), RepositoryError> {
-        info!("🔍 Fetching all orders with search: {:?}", req.search);
+        after: Option<Cursor>,
+    ) -> Result<(Vec<OrderWithItems>, i64), RepositoryError> {
+        info!(
+            "🔍 Fetching all orders with search: {:?}, sort: {:?}, after: {:?}",
+            req.search, req.sort, after
+        );
 
         let mut conn = self.db.acquire().await.map_err(|e| {
             error!("❌ Failed to acquire DB connection: {:?}", e);
@@ -31,7 +65,11 @@ impl OrderQueryRepositoryTrait for OrderQueryRepository {
         })?;
 
         let limit = req.page_size as i64;
-        let offset = ((req.page - 1).max(0) * req.page_size) as i64;
+        let offset = if after.is_some() {
+            0
+        } else {
+            ((req.page - 1).max(0) * req.page_size) as i64
+        };
 
         let search_pattern = if req.search.trim().is_empty() {
             None
@@ -39,46 +77,128 @@ impl OrderQueryRepositoryTrait for OrderQueryRepository {
             Some(req.search.as_str())
         };
 
-        let rows = sqlx::query!(
+        let order_by = if req.sort.trim().is_empty() {
+            "o.created_at DESC, o.order_id DESC".to_string()
+        } else {
+            order_by_clause(&parse_sort(&req.sort)?, SORT_COLUMNS, "o.order_id")?
+        };
+
+        // ORDER BY can't be bound as a parameter; `order_by` only ever holds
+        // column names from SORT_COLUMNS.
+        let sql = format!(
             r#"
             SELECT
                 o.order_id,
+                o.external_id,
+                o.order_number,
                 o.user_id,
                 o.total_price,
                 o.created_at,
                 o.updated_at,
                 o.deleted_at,
+                o.status,
                 COUNT(*) OVER() AS total_count
             FROM orders o
             WHERE ($1::TEXT IS NULL OR o.user_id::TEXT ILIKE '%' || $1 || '%')
-            ORDER BY o.created_at DESC
+              AND ($4::TIMESTAMP IS NULL OR (o.created_at, o.order_id) < ($4, $5::INT))
+              AND ($6::INT IS NULL OR o.user_id = $6)
+              AND ($7::TIMESTAMP IS NULL OR o.created_at >= $7)
+              AND ($8::TIMESTAMP IS NULL OR o.created_at <= $8)
+            ORDER BY {order_by}
             LIMIT $2 OFFSET $3
+            "#
+        );
+
+        let started = Instant::now();
+
+        let rows = sqlx::query_as::<_, OrderRow>(&sql)
+            .bind(search_pattern)
+            .bind(limit)
+            .bind(offset)
+            .bind(after.map(|c| c.created_at))
+            .bind(after.map(|c| c.id))
+            .bind(req.user_id)
+            .bind(req.created_from.map(|d| d.naive_utc()))
+            .bind(req.created_to.map(|d| d.naive_utc()))
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(|e| {
+                error!("❌ Failed to fetch orders: {:?}", e);
+                RepositoryError::from(e)
+            })?;
+
+        self.query_plans
+            .observe(&self.db, "order.find_all", &sql, started.elapsed());
+
+        let total = rows
+            .first()
+            .map(|r| r.total_count.unwrap_or(0))
+            .unwrap_or(0);
+
+        let orders: Vec<OrderModel> = rows.into_iter().map(|r| r.order).collect();
+
+        if !req.include_items || orders.is_empty() {
+            let orders = orders
+                .into_iter()
+                .map(|order| OrderWithItems {
+                    order,
+                    items: vec![],
+                })
+                .collect();
+
+            return Ok((orders, total));
+        }
+
+        let order_ids: Vec<i32> = orders.iter().map(|o| o.order_id).collect();
+
+        let item_rows = sqlx::query!(
+            r#"
+            SELECT
+                order_item_id,
+                order_id,
+                product_id,
+                quantity,
+                price,
+                created_at,
+                updated_at,
+                deleted_at
+            FROM order_items
+            WHERE order_id = ANY($1)
+              AND deleted_at IS NULL
+            ORDER BY order_item_id
             "#,
-            search_pattern,
-            limit,
-            offset
+            &order_ids
         )
         .fetch_all(&mut *conn)
         .await
         .map_err(|e| {
-            error!("❌ Failed to fetch orders: {:?}", e);
+            error!("❌ Failed to fetch order items for orders: {:?}", e);
             RepositoryError::from(e)
         })?;
 
-        let total = rows
-            .first()
-            .map(|r| r.total_count.unwrap_or(0))
-            .unwrap_or(0);
+        let mut items_by_order: HashMap<i32, Vec<OrderItemModel>> = HashMap::new();
 
-        let orders = rows
+        for r in item_rows {
+            items_by_order
+                .entry(r.order_id)
+                .or_default()
+                .push(OrderItemModel {
+                    order_item_id: r.order_item_id,
+                    order_id: r.order_id,
+                    product_id: r.product_id,
+                    quantity: r.quantity,
+                    price: r.price,
+                    created_at: r.created_at,
+                    updated_at: r.updated_at,
+                    deleted_at: r.deleted_at,
+                });
+        }
+
+        let orders = orders
             .into_iter()
-            .map(|r| OrderModel {
-                order_id: r.order_id,
-                user_id: r.user_id,
-                total_price: r.total_price,
-                created_at: r.created_at,
-                updated_at: r.updated_at,
-                deleted_at: r.deleted_at,
+            .map(|order| {
+                let items = items_by_order.remove(&order.order_id).unwrap_or_default();
+                OrderWithItems { order, items }
             })
             .collect();
 
@@ -109,11 +229,14 @@ impl OrderQueryRepositoryTrait for OrderQueryRepository {
             r#"
             SELECT
                 o.order_id,
+                o.external_id,
+                o.order_number,
                 o.user_id,
                 o.total_price,
                 o.created_at,
                 o.updated_at,
                 o.deleted_at,
+                o.status AS "status: OrderStatus",
                 COUNT(*) OVER() AS total_count
             FROM orders o
             WHERE o.deleted_at IS NULL
@@ -141,11 +264,14 @@ impl OrderQueryRepositoryTrait for OrderQueryRepository {
             .into_iter()
             .map(|r| OrderModel {
                 order_id: r.order_id,
+                external_id: r.external_id,
+                order_number: r.order_number,
                 user_id: r.user_id,
                 total_price: r.total_price,
                 created_at: r.created_at,
                 updated_at: r.updated_at,
                 deleted_at: r.deleted_at,
+                status: r.status,
             })
             .collect();
 
@@ -176,11 +302,14 @@ impl OrderQueryRepositoryTrait for OrderQueryRepository {
             r#"
             SELECT
                 o.order_id,
+                o.external_id,
+                o.order_number,
                 o.user_id,
                 o.total_price,
                 o.created_at,
                 o.updated_at,
                 o.deleted_at,
+                o.status AS "status: OrderStatus",
                 COUNT(*) OVER() AS total_count
             FROM orders o
             WHERE o.deleted_at IS NOT NULL
@@ -208,11 +337,14 @@ impl OrderQueryRepositoryTrait for OrderQueryRepository {
             .into_iter()
             .map(|r| OrderModel {
                 order_id: r.order_id,
+                external_id: r.external_id,
+                order_number: r.order_number,
                 user_id: r.user_id,
                 total_price: r.total_price,
                 created_at: r.created_at,
                 updated_at: r.updated_at,
                 deleted_at: r.deleted_at,
+                status: r.status,
             })
             .collect();
 
@@ -229,11 +361,14 @@ impl OrderQueryRepositoryTrait for OrderQueryRepository {
             r#"
             SELECT
                 order_id,
+                external_id,
+                order_number,
                 user_id,
                 total_price,
                 created_at,
                 updated_at,
-                deleted_at
+                deleted_at,
+                status AS "status: OrderStatus"
             FROM orders
             WHERE order_id = $1
             "#,
@@ -245,4 +380,100 @@ impl OrderQueryRepositoryTrait for OrderQueryRepository {
 
         Ok(result)
     }
+
+    async fn find_by_external_id(
+        &self,
+        external_id: Uuid,
+    ) -> Result<Option<OrderModel>, RepositoryError> {
+        info!("🆔 Fetching order by external ID: {}", external_id);
+
+        let mut conn = self.db.acquire().await.map_err(RepositoryError::from)?;
+
+        let result = sqlx::query_as!(
+            OrderModel,
+            r#"
+            SELECT
+                order_id,
+                external_id,
+                order_number,
+                user_id,
+                total_price,
+                created_at,
+                updated_at,
+                deleted_at,
+                status AS "status: OrderStatus"
+            FROM orders
+            WHERE external_id = $1
+            "#,
+            external_id
+        )
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(result)
+    }
+
+    async fn find_by_user_id(
+        &self,
+        user_id: i32,
+        req: &FindAllOrder,
+    ) -> Result<(Vec<OrderWithItems>, i64), RepositoryError> {
+        info!("🔍 Fetching orders of user {user_id}");
+
+        let req = FindAllOrder {
+            search: String::new(),
+            after: String::new(),
+            sort: String::new(),
+            user_id: Some(user_id),
+            ..req.clone()
+        };
+
+        self.find_all(&req, None).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OrderQueryRepository;
+    use crate::{
+        abstract_trait::order::repository::OrderQueryRepositoryTrait,
+        domain::requests::order::FindAllOrder,
+    };
+    use serde_json::json;
+    use sqlx::PgPool;
+
+    /// One order of user 3 holding two lines.
+    async fn seed_order(pool: &PgPool) {
+        sqlx::raw_sql(
+            r#"
+            INSERT INTO orders (order_id, order_number, user_id, total_price)
+            VALUES (1, 'ORD-000001', 3, 7000);
+            INSERT INTO order_items (order_id, product_id, quantity, price)
+            VALUES (1, 10, 2, 1500), (1, 11, 1, 4000);
+            "#,
+        )
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    fn find_all(include_items: bool) -> FindAllOrder {
+        serde_json::from_value(json!({ "include_items": include_items })).unwrap()
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "needs a Postgres server at DATABASE_URL"]
+    async fn items_are_loaded_only_when_asked_for(pool: PgPool) {
+        seed_order(&pool).await;
+        let repo = OrderQueryRepository::new(pool);
+
+        let (orders, total) = repo.find_all(&find_all(false), None).await.unwrap();
+        assert_eq!(total, 1);
+        assert!(orders[0].items.is_empty());
+
+        let (orders, _) = repo.find_all(&find_all(true), None).await.unwrap();
+        let products: Vec<i32> = orders[0].items.iter().map(|i| i.product_id).collect();
+        assert_eq!(products, [10, 11]);
+    }
 }
@@ -132,18 +132,37 @@ impl OrderItemCommandRepositoryTrait for OrderItemCommandRepository {
             UPDATE order_items
             SET deleted_at = NULL,
                 updated_at = current_timestamp
-            WHERE order_item_id = $1
+            WHERE order_item_id = $1 AND deleted_at IS NOT NULL
             RETURNING order_item_id, order_id, product_id, quantity, price, created_at, updated_at, deleted_at
             "#,
             order_item_id
         )
-        .fetch_one(&mut *conn)
+        .fetch_optional(&mut *conn)
         .await
         .map_err(|err| {
             error!("❌ Failed to restore order item {}: {:?}", order_item_id, err);
             RepositoryError::from(err)
         })?;
 
+        let Some(result) = result else {
+            let exists = sqlx::query_scalar!(
+                r#"
+            SELECT order_item_id FROM order_items WHERE order_item_id = $1
+            "#,
+                order_item_id
+            )
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(RepositoryError::from)?;
+
+            return Err(match exists {
+                Some(_) => RepositoryError::NotTrashed(format!(
+                    "Order item {order_item_id} is not trashed"
+                )),
+                None => RepositoryError::NotFound,
+            });
+        };
+
         info!("♻️ Restored order item {}", order_item_id);
         Ok(result)
     }
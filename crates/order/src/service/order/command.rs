@@ -2,30 +2,40 @@ use crate::{
     abstract_trait::{
         grpc_client::DynProductGrpcClient,
         order::{
-            repository::{DynOrderCommandRepository, DynOrderQueryRepository},
+            repository::{
+                DynOrderCommandRepository, DynOrderIdempotencyRepository, DynOrderQueryRepository,
+            },
             service::OrderCommandServiceTrait,
         },
         order_item::repository::{DynOrderItemCommandRepository, DynOrderItemQueryRepository},
     },
+    config::myconfig::{DuplicateItemPolicy, OrderTotalLimits},
     domain::{
         event::{OrderEvent, OrderItemEvent, OrderItemUpdateEvent},
         requests::{
             order::{
-                CreateOrderRecordRequest, CreateOrderRequest, UpdateOrderRecordRequest,
-                UpdateOrderRequest,
+                CreateOrderItemRequest, CreateOrderLineRecord, CreateOrderRecordRequest,
+                CreateOrderRequest, IdempotencyClaim, UpdateOrderRecordRequest, UpdateOrderRequest,
             },
-            order_item::{CreateOrderItemRecordRequest, UpdateOrderItemRecordRequest},
+            order_item::UpdateOrderItemRecordRequest,
         },
         response::{
             api::ApiResponse,
             order::{OrderResponse, OrderResponseDeleteAt},
         },
+        status::OrderStatus,
     },
+    model::order::OrderWithItems,
+    service::order::OrderCreationGate,
 };
 use shared::{
     abstract_trait::DynKafka,
-    errors::ServiceError,
-    utils::{MetadataInjector, Method, Metrics, Status as StatusUtils, TracingContext},
+    cache::CacheStore,
+    errors::{FieldViolation, RepositoryError, ServiceError},
+    utils::{
+        LifecycleMetrics, LifecycleScope, MetadataInjector, Method, Metrics, Status as StatusUtils,
+        TracingContext,
+    },
 };
 
 use anyhow::Result;
@@ -35,6 +45,8 @@ use opentelemetry::{
     global::{self, BoxedTracer},
     trace::{Span, SpanKind, TraceContextExt, Tracer},
 };
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
 use tokio::time::Instant;
 use tonic::Request;
 use tracing::{error, info};
@@ -46,8 +58,14 @@ pub struct OrderCommandService {
     order_item_query: DynOrderItemQueryRepository,
     order_item_command: DynOrderItemCommandRepository,
     query: DynOrderQueryRepository,
+    idempotency: DynOrderIdempotencyRepository,
     kafka: DynKafka,
+    duplicate_item_policy: DuplicateItemPolicy,
+    creation_gate: OrderCreationGate,
+    order_total_limits: OrderTotalLimits,
+    cache_store: Arc<CacheStore>,
     metrics: Metrics,
+    lifecycle: LifecycleMetrics,
 }
 
 pub struct OrderCommandServiceDeps {
@@ -56,12 +74,18 @@ pub struct OrderCommandServiceDeps {
     pub order_item_command: DynOrderItemCommandRepository,
     pub command: DynOrderCommandRepository,
     pub query: DynOrderQueryRepository,
+    pub idempotency: DynOrderIdempotencyRepository,
     pub kafka: DynKafka,
+    pub duplicate_item_policy: DuplicateItemPolicy,
+    pub creation_gate: OrderCreationGate,
+    pub order_total_limits: OrderTotalLimits,
+    pub cache_store: Arc<CacheStore>,
 }
 
 impl OrderCommandService {
     pub fn new(deps: OrderCommandServiceDeps) -> Result<Self> {
         let metrics = Metrics::new(global::meter("order-command-service"));
+        let lifecycle = LifecycleMetrics::new(global::meter("order-command-service"), "order");
 
         let OrderCommandServiceDeps {
             order_item_command,
@@ -69,7 +93,12 @@ impl OrderCommandService {
             product_client,
             command,
             query,
+            idempotency,
             kafka,
+            duplicate_item_policy,
+            creation_gate,
+            order_total_limits,
+            cache_store,
         } = deps;
 
         Ok(Self {
@@ -78,10 +107,204 @@ impl OrderCommandService {
             product_client,
             command,
             query,
+            idempotency,
             kafka,
+            duplicate_item_policy,
+            creation_gate,
+            order_total_limits,
+            cache_store,
             metrics,
+            lifecycle,
         })
     }
+
+    /// Returns the order `key` already produced, or `None` when the key is
+    /// unused. Fails when the key was used with a different request body.
+    async fn replay_idempotent_order(
+        &self,
+        req: &CreateOrderRequest,
+        key: &str,
+        fingerprint: &str,
+    ) -> Result<Option<ApiResponse<OrderResponse>>, ServiceError> {
+        let Some(existing) = self
+            .idempotency
+            .find(req.user_id, key)
+            .await
+            .map_err(ServiceError::Repo)?
+        else {
+            return Ok(None);
+        };
+
+        if existing.request_hash != fingerprint {
+            return Err(ServiceError::Repo(RepositoryError::Conflict(
+                "Idempotency key was already used with a different request body".into(),
+            )));
+        }
+
+        let Some(order_id) = existing.order_id else {
+            return Err(ServiceError::Repo(RepositoryError::Conflict(
+                "A request with this idempotency key is still being processed".into(),
+            )));
+        };
+
+        info!("🔁 Replaying order {order_id} for idempotency key");
+
+        let order = self
+            .query
+            .find_by_id(order_id)
+            .await
+            .map_err(ServiceError::Repo)?
+            .ok_or(ServiceError::Repo(RepositoryError::NotFound))?;
+
+        let items = self
+            .order_item_query
+            .find_order_item_by_order(order_id)
+            .await
+            .map_err(ServiceError::Repo)?;
+
+        Ok(Some(ApiResponse {
+            status: "success".into(),
+            message: "Order created successfully".into(),
+            data: OrderResponse::from(OrderWithItems { order, items }),
+        }))
+    }
+
+    /// Returns `None` when `claim` was taken by a concurrent request before
+    /// this one could commit.
+    async fn place_order(
+        &self,
+        req: &CreateOrderRequest,
+        claim: Option<IdempotencyClaim>,
+        tracing_ctx: &TracingContext,
+        method: Method,
+    ) -> Result<Option<ApiResponse<OrderResponse>>, ServiceError> {
+        let mut request = Request::new(req.clone());
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        if req.items.is_empty() {
+            return Err(ServiceError::Custom("Items cannot be empty".into()));
+        }
+
+        let items = match merge_duplicate_items(&req.items, self.duplicate_item_policy) {
+            Ok(items) => items,
+            Err(e) => {
+                self.complete_tracing_error(tracing_ctx, method, &e.to_string())
+                    .await;
+                return Err(e);
+            }
+        };
+
+        let mut total_price: i32 = 0;
+        let mut prepared_items = Vec::new();
+
+        for item in &items {
+            let product_check = self.product_client.find_by_id(item.product_id).await;
+
+            let product = match product_check {
+                Ok(api_response) => {
+                    let product_data = api_response.data;
+                    info!("✅ Product found: id={}", product_data.id);
+                    product_data
+                }
+                Err(e) => {
+                    error!("❌ gRPC error fetching product: {:?}", e);
+                    self.complete_tracing_error(tracing_ctx, method, "Product query failed")
+                        .await;
+                    return Err(ServiceError::Internal("Product service unavailable".into()));
+                }
+            };
+
+            if item.quantity > product.stock {
+                return Err(ServiceError::InsufficientStock {
+                    product_id: item.product_id,
+                    requested: item.quantity,
+                    available: product.stock,
+                });
+            }
+
+            let item_price: i32 = (product.price as i32) * item.quantity;
+            total_price += item_price as i32;
+
+            prepared_items.push((item.product_id, item.quantity, product.price, item_price));
+        }
+
+        if let Some(reason) = self.order_total_limits.check(total_price) {
+            self.complete_tracing_error(tracing_ctx, method, &reason)
+                .await;
+            return Err(ServiceError::Validation(vec![reason]));
+        }
+
+        let lines = prepared_items
+            .iter()
+            .map(
+                |(product_id, quantity, unit_price, _)| CreateOrderLineRecord {
+                    product_id: *product_id,
+                    quantity: *quantity,
+                    price: *unit_price as i32,
+                },
+            )
+            .collect();
+
+        let Some(created) = self
+            .command
+            .create_order(&CreateOrderRecordRequest {
+                user_id: req.user_id,
+                total_price,
+                items: lines,
+                idempotency: claim,
+            })
+            .await
+            .map_err(ServiceError::Repo)?
+        else {
+            return Ok(None);
+        };
+
+        let mut response = OrderResponse::from(created);
+        response.total_price = total_price;
+
+        self.invalidate_order(response.id, &response.external_id, true)
+            .await;
+
+        let event_items: Vec<OrderItemEvent> =
+            items.into_iter().map(OrderItemEvent::from).collect();
+
+        let event = OrderEvent::Created {
+            order_id: response.id,
+            user_id: response.user_id,
+            items: event_items,
+        };
+
+        let payload = serde_json::to_vec(&event)
+            .map_err(|e| ServiceError::Custom(format!("Kafka error: {e}")))?;
+
+        if let Err(e) = self
+            .kafka
+            .publish("order.created", &response.id.to_string(), &payload)
+            .await
+        {
+            error!("❌ Failed to publish event: {e:?}");
+        }
+
+        Ok(Some(ApiResponse {
+            status: "success".into(),
+            message: "Order created successfully".into(),
+            data: response,
+        }))
+    }
+
+    /// Runs after a write commits. Order item reads are cached under their
+    /// own entity with differently named list queries, so when the items
+    /// changed too that whole entity is dropped.
+    async fn invalidate_order(&self, id: i32, external_id: &str, items_changed: bool) {
+        self.cache_store
+            .invalidate_record("order", id, Some(external_id))
+            .await;
+
+        if items_changed {
+            self.cache_store.invalidate_entity("order_item").await;
+        }
+    }
+
     fn get_tracer(&self) -> BoxedTracer {
         global::tracer("order-command-service")
     }
@@ -190,99 +413,58 @@ impl OrderCommandServiceTrait for OrderCommandService {
             ],
         );
 
-        let mut request = Request::new(req.clone());
-        self.inject_trace_context(&tracing_ctx.cx, &mut request);
-
-        if req.items.is_empty() {
-            return Err(ServiceError::Custom("Items cannot be empty".into()));
+        if self.creation_gate.is_paused().await {
+            self.complete_tracing_error(&tracing_ctx, method, "Order creation is paused")
+                .await;
+            return Err(ServiceError::Unavailable(
+                "Order creation is temporarily paused, please try again later".into(),
+            ));
         }
 
-        let mut total_price: i32 = 0;
-        let mut prepared_items = Vec::new();
-
-        for item in &req.items {
-            let product_check = self.product_client.find_by_id(item.product_id).await;
+        let Some(key) = req.idempotency_key.as_deref() else {
+            return self
+                .place_order(req, None, &tracing_ctx, method)
+                .await?
+                .ok_or_else(|| ServiceError::Internal("Order was not created".into()));
+        };
 
-            let product = match product_check {
-                Ok(api_response) => {
-                    let product_data = api_response.data;
-                    info!("✅ Product found: id={}", product_data.id);
-                    product_data
-                }
-                Err(e) => {
-                    error!("❌ gRPC error fetching product: {:?}", e);
-                    self.complete_tracing_error(&tracing_ctx, method, "Product query failed")
-                        .await;
-                    return Err(ServiceError::Internal("Product service unavailable".into()));
-                }
-            };
+        let fingerprint = request_fingerprint(req);
 
-            if item.quantity > product.stock {
-                return Err(ServiceError::Custom(format!(
-                    "Insufficient stock for product {}: requested={}, available={}",
-                    item.product_id, item.quantity, product.stock
-                )));
+        match self.replay_idempotent_order(req, key, &fingerprint).await {
+            Ok(Some(replay)) => {
+                self.complete_tracing_success(&tracing_ctx, method, "Replayed idempotent order")
+                    .await;
+                return Ok(replay);
+            }
+            Ok(None) => {}
+            Err(e) => {
+                self.complete_tracing_error(&tracing_ctx, method, &e.to_string())
+                    .await;
+                return Err(e);
             }
-
-            let item_price: i32 = (product.price as i32) * item.quantity;
-            total_price += item_price as i32;
-
-            prepared_items.push((item.product_id, item.quantity, product.price, item_price));
-        }
-
-        let order_record = self
-            .command
-            .create_order(&CreateOrderRecordRequest {
-                user_id: req.user_id,
-                total_price,
-            })
-            .await
-            .map_err(ServiceError::Repo)?;
-
-        for (product_id, quantity, unit_price, _) in prepared_items.iter() {
-            self.order_item_command
-                .create_order_item(&CreateOrderItemRecordRequest {
-                    order_id: order_record.order_id,
-                    product_id: *product_id,
-                    quantity: *quantity,
-                    price: *unit_price as i32,
-                })
-                .await
-                .map_err(ServiceError::Repo)?;
         }
 
-        let mut response = OrderResponse::from(order_record);
-        response.total_price = total_price;
-
-        let event_items: Vec<OrderItemEvent> = req
-            .items
-            .iter()
-            .cloned()
-            .map(OrderItemEvent::from)
-            .collect();
-
-        let event = OrderEvent::Created {
-            order_id: response.id,
-            user_id: response.user_id,
-            items: event_items,
+        let claim = IdempotencyClaim {
+            key: key.to_string(),
+            request_hash: fingerprint.clone(),
         };
 
-        let payload = serde_json::to_vec(&event)
-            .map_err(|e| ServiceError::Custom(format!("Kafka error: {e}")))?;
-
-        if let Err(e) = self
-            .kafka
-            .publish("order.created", &response.id.to_string(), &payload)
-            .await
+        if let Some(response) = self
+            .place_order(req, Some(claim), &tracing_ctx, method)
+            .await?
         {
-            error!("❌ Failed to publish event: {e:?}");
+            return Ok(response);
         }
 
-        Ok(ApiResponse {
-            status: "success".into(),
-            message: "Order created successfully".into(),
-            data: response,
-        })
+        // Another request with the same key committed first; answer with
+        // its order.
+        self.replay_idempotent_order(req, key, &fingerprint)
+            .await?
+            .ok_or_else(|| {
+                ServiceError::Repo(RepositoryError::Conflict(
+                    "Idempotency key was released concurrently, please retry".into(),
+                ))
+            })
     }
 
     async fn update_order(
@@ -301,13 +483,23 @@ impl OrderCommandServiceTrait for OrderCommandService {
             ],
         );
 
-        let _old_order = self
+        let old_order = self
             .query
             .find_by_id(req.order_id)
             .await
             .map_err(ServiceError::Repo)?
             .ok_or(ServiceError::Custom("Order not found".into()))?;
 
+        let next_status =
+            match next_status(old_order.status, req.status.as_deref(), req.caller_is_staff) {
+                Ok(status) => status,
+                Err(e) => {
+                    self.complete_tracing_error(&tracing_ctx, method, "Invalid status transition")
+                        .await;
+                    return Err(e);
+                }
+            };
+
         let old_items = self
             .order_item_query
             .find_order_item_by_order(req.order_id)
@@ -339,10 +531,11 @@ impl OrderCommandServiceTrait for OrderCommandService {
             };
 
             if item_req.quantity > product.stock {
-                return Err(ServiceError::Custom(format!(
-                    "Not enough stock for product {}",
-                    item_req.product_id
-                )));
+                return Err(ServiceError::InsufficientStock {
+                    product_id: item_req.product_id,
+                    requested: item_req.quantity,
+                    available: product.stock,
+                });
             }
 
             let old_quantity = old_map.get(&item_req.order_item_id).copied().unwrap_or(0);
@@ -382,6 +575,7 @@ impl OrderCommandServiceTrait for OrderCommandService {
             order_id: req.order_id,
             user_id: req.user_id,
             total_price,
+            status: next_status,
         };
 
         let updated_order = self
@@ -427,9 +621,33 @@ impl OrderCommandServiceTrait for OrderCommandService {
             info!("🔁 No quantity changed, skipping event publish.");
         }
 
+        if next_status != old_order.status {
+            let event = OrderEvent::StatusChanged {
+                order_id: req.order_id,
+                from: old_order.status,
+                to: next_status,
+            };
+
+            let payload = serde_json::to_vec(&event)
+                .map_err(|e| ServiceError::Custom(format!("Failed to serialize event: {e}")))?;
+
+            self.kafka
+                .publish("order.status_changed", &req.order_id.to_string(), &payload)
+                .await
+                .map_err(|e| ServiceError::Kafka(e.to_string()))?;
+
+            info!(
+                "📤 Published order.status_changed event for order_id={}: {} -> {}",
+                req.order_id, old_order.status, next_status
+            );
+        }
+
         let mut response = OrderResponse::from(updated_order);
         response.total_price = total_price;
 
+        self.invalidate_order(response.id, &response.external_id, true)
+            .await;
+
         Ok(ApiResponse {
             status: "success".into(),
             message: "Order updated successfully".into(),
@@ -459,6 +677,7 @@ impl OrderCommandServiceTrait for OrderCommandService {
 
         let order_model = match self.command.trash_order(order_id).await {
             Ok(order) => {
+                self.lifecycle.soft_deleted(LifecycleScope::Single);
                 info!("✅ Order found with ID={order_id}");
 
                 self.complete_tracing_success(
@@ -481,6 +700,8 @@ impl OrderCommandServiceTrait for OrderCommandService {
         };
 
         let response = OrderResponseDeleteAt::from(order_model);
+        self.invalidate_order(response.id, &response.external_id, false)
+            .await;
 
         Ok(ApiResponse {
             status: "success".to_string(),
@@ -511,6 +732,7 @@ impl OrderCommandServiceTrait for OrderCommandService {
 
         let order_model = match self.command.restore_order(order_id).await {
             Ok(order) => {
+                self.lifecycle.restored(LifecycleScope::Single);
                 info!("✅ Order found with ID={order_id}");
 
                 self.complete_tracing_success(
@@ -533,6 +755,8 @@ impl OrderCommandServiceTrait for OrderCommandService {
         };
 
         let response = OrderResponseDeleteAt::from(order_model);
+        self.invalidate_order(response.id, &response.external_id, false)
+            .await;
 
         info!("✅ Order restored: {} (ID: {order_id})", response.user_id,);
 
@@ -625,6 +849,10 @@ impl OrderCommandServiceTrait for OrderCommandService {
 
         info!("📤 Published event: order.deleted | order_id={order_id}");
 
+        self.lifecycle.purged(LifecycleScope::Single);
+        self.cache_store.invalidate_entity("order").await;
+        self.cache_store.invalidate_entity("order_item").await;
+
         self.complete_tracing_success(&tracing_ctx, method, "Order permanently deleted")
             .await;
 
@@ -665,6 +893,8 @@ impl OrderCommandServiceTrait for OrderCommandService {
         };
 
         info!("✅ All Orders restored successfully");
+        self.lifecycle.restored(LifecycleScope::All);
+        self.cache_store.invalidate_entity("order").await;
 
         Ok(ApiResponse {
             status: "success".to_string(),
@@ -703,6 +933,9 @@ impl OrderCommandServiceTrait for OrderCommandService {
         };
 
         info!("✅ All Orders deleted permanently");
+        self.lifecycle.purged(LifecycleScope::All);
+        self.cache_store.invalidate_entity("order").await;
+        self.cache_store.invalidate_entity("order_item").await;
 
         Ok(ApiResponse {
             status: "success".to_string(),
@@ -711,3 +944,424 @@ impl OrderCommandServiceTrait for OrderCommandService {
         })
     }
 }
+
+/// Folds lines for the same product into one. Under `Reject` a repeated
+/// product fails validation on `items` instead.
+fn merge_duplicate_items(
+    items: &[CreateOrderItemRequest],
+    policy: DuplicateItemPolicy,
+) -> Result<Vec<CreateOrderItemRequest>, ServiceError> {
+    let mut merged: Vec<CreateOrderItemRequest> = Vec::with_capacity(items.len());
+
+    for item in items {
+        match merged.iter_mut().find(|m| m.product_id == item.product_id) {
+            Some(existing) => match policy {
+                DuplicateItemPolicy::Reject => {
+                    return Err(ServiceError::FieldValidation(vec![FieldViolation::new(
+                        "items",
+                        format!(
+                            "Duplicate product {} in order items, combine them into a single line",
+                            item.product_id
+                        ),
+                    )]));
+                }
+                DuplicateItemPolicy::Aggregate => {
+                    existing.quantity += item.quantity;
+                }
+            },
+            None => merged.push(item.clone()),
+        }
+    }
+
+    Ok(merged)
+}
+
+/// Resolves the status an update moves the order to. Staying put is always
+/// allowed; anything else must be a legal step from `current`, and only
+/// staff may take a step other than cancelling.
+fn next_status(
+    current: OrderStatus,
+    requested: Option<&str>,
+    caller_is_staff: bool,
+) -> Result<OrderStatus, ServiceError> {
+    let Some(raw) = requested else {
+        return Ok(current);
+    };
+
+    let next: OrderStatus = raw
+        .trim()
+        .to_ascii_lowercase()
+        .parse()
+        .map_err(|e: String| {
+            ServiceError::FieldValidation(vec![FieldViolation::new("status", e)])
+        })?;
+
+    if next != current && !current.can_transition_to(next) {
+        return Err(ServiceError::FieldValidation(vec![FieldViolation::new(
+            "status",
+            format!("Cannot move an order from {current} to {next}"),
+        )]));
+    }
+
+    if next != current && next.is_staff_only() && !caller_is_staff {
+        return Err(ServiceError::Forbidden(format!(
+            "Only ADMIN or MODERATOR can move an order to {next}"
+        )));
+    }
+
+    Ok(next)
+}
+
+/// Hash of the request body an idempotency key was first used with.
+fn request_fingerprint(req: &CreateOrderRequest) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(req.user_id.to_be_bytes());
+
+    for item in &req.items {
+        hasher.update(item.product_id.to_be_bytes());
+        hasher.update(item.quantity.to_be_bytes());
+        hasher.update(item.price.to_be_bytes());
+    }
+
+    format!("{:x}", hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{OrderCommandService, OrderCommandServiceDeps, merge_duplicate_items, next_status};
+    use crate::{
+        abstract_trait::{
+            grpc_client::ProductGrpcClientTrait,
+            order::service::{OrderCommandServiceTrait, OrderQueryServiceTrait},
+        },
+        config::myconfig::{DuplicateItemPolicy, OrderNumberFormat, OrderTotalLimits},
+        domain::{
+            requests::order::{CreateOrderItemRequest, CreateOrderRequest, FindAllOrder},
+            response::{api::ApiResponse, product::ProductResponse},
+            status::OrderStatus::*,
+        },
+        repository::{
+            order::{OrderCommandRepository, OrderIdempotencyRepository, OrderQueryRepository},
+            order_item::{command::OrderItemCommandRepository, query::OrderItemQueryRepository},
+        },
+        service::order::{OrderCreationGate, OrderQueryService},
+    };
+    use async_trait::async_trait;
+    use shared::{
+        abstract_trait::KafkaTrait,
+        cache::CacheStore,
+        config::{RedisConfig, RedisPool},
+        errors::{AppErrorGrpc, ServiceError},
+        utils::BatchResponse,
+    };
+    use sqlx::PgPool;
+    use std::sync::{Arc, Mutex};
+    use tonic::{Code, Status};
+
+    fn line(product_id: i32, quantity: i32) -> CreateOrderItemRequest {
+        CreateOrderItemRequest {
+            product_id,
+            quantity,
+            price: 100,
+        }
+    }
+
+    #[test]
+    fn duplicate_lines_are_rejected_on_items() {
+        let items = [line(1, 2), line(2, 1), line(1, 3)];
+
+        let Err(ServiceError::FieldValidation(violations)) =
+            merge_duplicate_items(&items, DuplicateItemPolicy::Reject)
+        else {
+            panic!("duplicate product should fail validation");
+        };
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].field, "items");
+        assert!(violations[0].description.contains("Duplicate product 1"));
+
+        let distinct = [line(1, 2), line(2, 1)];
+        assert_eq!(
+            merge_duplicate_items(&distinct, DuplicateItemPolicy::Reject)
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn duplicate_lines_are_summed_when_aggregating() {
+        let items = [line(1, 2), line(2, 1), line(1, 3)];
+
+        let merged = merge_duplicate_items(&items, DuplicateItemPolicy::Aggregate).unwrap();
+        let quantities: Vec<_> = merged.iter().map(|m| (m.product_id, m.quantity)).collect();
+        assert_eq!(quantities, [(1, 5), (2, 1)]);
+    }
+
+    #[test]
+    fn customers_may_only_cancel() {
+        assert_eq!(
+            next_status(Pending, Some("cancelled"), false).unwrap(),
+            Cancelled
+        );
+        assert_eq!(next_status(Paid, None, false).unwrap(), Paid);
+        assert_eq!(next_status(Paid, Some("paid"), false).unwrap(), Paid);
+
+        assert!(matches!(
+            next_status(Paid, Some("shipped"), false),
+            Err(ServiceError::Forbidden(message)) if message.ends_with("to shipped")
+        ));
+        assert!(matches!(
+            next_status(Pending, Some("paid"), false),
+            Err(ServiceError::Forbidden(_))
+        ));
+    }
+
+    #[test]
+    fn staff_may_take_any_legal_step() {
+        assert_eq!(next_status(Paid, Some("shipped"), true).unwrap(), Shipped);
+        assert_eq!(
+            next_status(Shipped, Some("Delivered"), true).unwrap(),
+            Delivered
+        );
+
+        assert!(matches!(
+            next_status(Shipped, Some("cancelled"), true),
+            Err(ServiceError::FieldValidation(_))
+        ));
+    }
+
+    /// Every product is in stock and costs `100 * id`.
+    struct Catalog;
+
+    #[async_trait]
+    impl ProductGrpcClientTrait for Catalog {
+        async fn find_by_id(&self, id: i32) -> Result<ApiResponse<ProductResponse>, AppErrorGrpc> {
+            Ok(ApiResponse {
+                status: "success".into(),
+                message: "Product found".into(),
+                data: ProductResponse {
+                    id,
+                    external_id: String::new(),
+                    name: format!("product {id}"),
+                    price: 100 * i64::from(id),
+                    stock: 50,
+                    images: Vec::new(),
+                    reorder_threshold: None,
+                    created_at: None,
+                    updated_at: None,
+                },
+            })
+        }
+
+        async fn find_by_ids(
+            &self,
+            _ids: Vec<i32>,
+        ) -> Result<ApiResponse<BatchResponse<ProductResponse>>, AppErrorGrpc> {
+            unimplemented!()
+        }
+    }
+
+    #[derive(Default)]
+    struct Topics(Mutex<Vec<String>>);
+
+    #[async_trait]
+    impl KafkaTrait for Topics {
+        async fn publish(
+            &self,
+            topic: &str,
+            _key: &str,
+            _value: &[u8],
+        ) -> Result<(), ServiceError> {
+            self.0.lock().unwrap().push(topic.to_string());
+            Ok(())
+        }
+
+        async fn subscribe(&self, _topics: Vec<&str>, _group_id: &str) -> Result<(), ServiceError> {
+            unimplemented!()
+        }
+    }
+
+    /// Never connected: there is no runtime creation flag, so the gate
+    /// follows its config value, and cache invalidation is a logged no-op.
+    fn cache() -> Arc<CacheStore> {
+        Arc::new(CacheStore::new(
+            RedisPool::new(&RedisConfig::new()).unwrap().pool,
+        ))
+    }
+
+    fn service(
+        pool: PgPool,
+        kafka: Arc<Topics>,
+        creation_paused: bool,
+        order_total_limits: OrderTotalLimits,
+    ) -> OrderCommandService {
+        let cache = cache();
+
+        OrderCommandService::new(OrderCommandServiceDeps {
+            product_client: Arc::new(Catalog),
+            order_item_query: Arc::new(OrderItemQueryRepository::new(pool.clone())),
+            order_item_command: Arc::new(OrderItemCommandRepository::new(pool.clone())),
+            command: Arc::new(OrderCommandRepository::new(
+                pool.clone(),
+                OrderNumberFormat {
+                    prefix: "ORD-".into(),
+                    padding: 6,
+                },
+            )),
+            query: Arc::new(OrderQueryRepository::new(pool.clone())),
+            idempotency: Arc::new(OrderIdempotencyRepository::new(pool)),
+            kafka,
+            duplicate_item_policy: DuplicateItemPolicy::Reject,
+            creation_gate: OrderCreationGate::new(creation_paused, cache.clone()),
+            order_total_limits,
+            cache_store: cache,
+        })
+        .unwrap()
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "needs a Postgres server at DATABASE_URL"]
+    async fn created_order_comes_back_with_its_persisted_items(pool: PgPool) {
+        let kafka = Arc::new(Topics::default());
+        let service = service(pool, kafka.clone(), false, OrderTotalLimits::default());
+
+        let created = service
+            .create_order(&CreateOrderRequest {
+                user_id: 3,
+                items: vec![line(1, 2), line(4, 3)],
+                idempotency_key: None,
+            })
+            .await
+            .unwrap()
+            .data;
+
+        assert!(created.id > 0);
+        assert_eq!(created.total_price, 2 * 100 + 3 * 400);
+        assert_eq!(created.items.len(), 2);
+
+        for item in &created.items {
+            assert!(item.id > 0);
+            assert_eq!(item.order_id, created.id);
+            assert_eq!(item.price, 100 * item.product_id);
+            assert_eq!(item.subtotal, item.quantity * item.price);
+        }
+        let mut lines: Vec<_> = created
+            .items
+            .iter()
+            .map(|i| (i.product_id, i.quantity, i.subtotal))
+            .collect();
+        lines.sort();
+        assert_eq!(lines, [(1, 2, 200), (4, 3, 1200)]);
+
+        assert_eq!(*kafka.0.lock().unwrap(), ["order.created"]);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "needs a Postgres server at DATABASE_URL"]
+    async fn paused_creation_is_refused_while_orders_stay_readable(pool: PgPool) {
+        sqlx::raw_sql(
+            r#"
+            INSERT INTO orders (order_id, order_number, user_id, total_price)
+            VALUES (1, 'ORD-000001', 3, 200);
+            INSERT INTO order_items (order_id, product_id, quantity, price)
+            VALUES (1, 1, 2, 100);
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let kafka = Arc::new(Topics::default());
+        let command = service(
+            pool.clone(),
+            kafka.clone(),
+            true,
+            OrderTotalLimits::default(),
+        );
+
+        let refused = command
+            .create_order(&CreateOrderRequest {
+                user_id: 3,
+                items: vec![line(1, 1)],
+                idempotency_key: None,
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(refused, ServiceError::Unavailable(_)));
+        assert_eq!(
+            Status::from(AppErrorGrpc::Service(refused)).code(),
+            Code::Unavailable
+        );
+        assert!(kafka.0.lock().unwrap().is_empty());
+
+        let query = OrderQueryService::new(
+            Arc::new(OrderQueryRepository::new(pool.clone())),
+            Arc::new(OrderItemQueryRepository::new(pool)),
+            Arc::new(Catalog),
+            cache(),
+        )
+        .unwrap();
+
+        assert_eq!(query.find_by_id(1).await.unwrap().data.user_id, 3);
+        let listed = query
+            .find_all(&serde_json::from_value::<FindAllOrder>(serde_json::json!({})).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(listed.pagination.total_items, 1);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "needs a Postgres server at DATABASE_URL"]
+    async fn totals_outside_the_limits_are_rejected_before_anything_is_stored(pool: PgPool) {
+        let kafka = Arc::new(Topics::default());
+        let service = service(
+            pool.clone(),
+            kafka.clone(),
+            false,
+            OrderTotalLimits {
+                min: Some(300),
+                max: Some(1000),
+            },
+        );
+        let order = |items| CreateOrderRequest {
+            user_id: 3,
+            items,
+            idempotency_key: None,
+        };
+
+        for (items, reason) in [
+            (
+                vec![line(1, 2)],
+                "Order total 200 is below the minimum of 300",
+            ),
+            (
+                vec![line(4, 3)],
+                "Order total 1200 exceeds the maximum of 1000",
+            ),
+        ] {
+            let Err(ServiceError::Validation(reasons)) = service.create_order(&order(items)).await
+            else {
+                panic!("{reason}: order was not rejected");
+            };
+            assert_eq!(reasons, [reason]);
+        }
+
+        let stored: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM orders")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(stored, 0);
+        assert!(kafka.0.lock().unwrap().is_empty());
+
+        let at_min = service
+            .create_order(&order(vec![line(3, 1)]))
+            .await
+            .unwrap();
+        assert_eq!(at_min.data.total_price, 300);
+        let at_max = service
+            .create_order(&order(vec![line(5, 2)]))
+            .await
+            .unwrap();
+        assert_eq!(at_max.data.total_price, 1000);
+    }
+}
@@ -0,0 +1,137 @@
+use crate::{
+    abstract_trait::order::repository::DynOrderIdempotencyRepository,
+    config::myconfig::PurgeJobConfig,
+};
+use shared::errors::RepositoryError;
+use std::time::Duration;
+use tracing::{error, info};
+
+/// Deletes idempotency keys past their 24 hour replay window, one bounded
+/// batch per statement so the purge never holds long row locks.
+pub struct IdempotencyKeySweeper {
+    repository: DynOrderIdempotencyRepository,
+    config: PurgeJobConfig,
+}
+
+impl IdempotencyKeySweeper {
+    pub fn new(repository: DynOrderIdempotencyRepository, config: PurgeJobConfig) -> Self {
+        Self { repository, config }
+    }
+
+    pub async fn run(&self) {
+        info!("🧹 Starting idempotency key sweeper...");
+
+        let mut interval = tokio::time::interval(Duration::from_secs(self.config.interval_seconds));
+
+        loop {
+            interval.tick().await;
+
+            if let Err(e) = self.drain().await {
+                error!("Idempotency key sweep failed: {e:?}");
+            }
+        }
+    }
+
+    /// Purges batch after batch until one comes back short, clearing the
+    /// whole backlog within a tick.
+    pub async fn drain(&self) -> Result<u64, RepositoryError> {
+        let mut total = 0;
+
+        loop {
+            let purged = self
+                .repository
+                .purge_expired(self.config.batch_size)
+                .await?;
+            total += purged;
+
+            if (purged as i64) < self.config.batch_size {
+                break;
+            }
+        }
+
+        if total > 0 {
+            info!("🧹 Purged {total} expired idempotency keys");
+        }
+
+        Ok(total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IdempotencyKeySweeper;
+    use crate::{
+        abstract_trait::order::repository::OrderIdempotencyRepositoryTrait,
+        config::myconfig::PurgeJobConfig, model::idempotency::IdempotencyKey,
+        repository::order::OrderIdempotencyRepository,
+    };
+    use async_trait::async_trait;
+    use shared::errors::RepositoryError;
+    use sqlx::PgPool;
+    use std::sync::{Arc, Mutex};
+
+    /// Passes purges through to Postgres and records how many rows each
+    /// statement deleted.
+    struct Recording {
+        inner: OrderIdempotencyRepository,
+        batches: Mutex<Vec<u64>>,
+    }
+
+    #[async_trait]
+    impl OrderIdempotencyRepositoryTrait for Recording {
+        async fn find(
+            &self,
+            _user_id: i32,
+            _key: &str,
+        ) -> Result<Option<IdempotencyKey>, RepositoryError> {
+            unimplemented!()
+        }
+
+        async fn purge_expired(&self, limit: i64) -> Result<u64, RepositoryError> {
+            let purged = self.inner.purge_expired(limit).await?;
+            self.batches.lock().unwrap().push(purged);
+            Ok(purged)
+        }
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "needs a Postgres server at DATABASE_URL"]
+    async fn a_backlog_is_drained_one_bounded_batch_at_a_time(pool: PgPool) {
+        sqlx::raw_sql(
+            r#"
+            INSERT INTO order_idempotency_keys (user_id, idempotency_key, request_hash, created_at)
+            SELECT 1, 'expired-' || n, repeat('0', 64), current_timestamp - INTERVAL '25 hours'
+            FROM generate_series(1, 25) AS n;
+            INSERT INTO order_idempotency_keys (user_id, idempotency_key, request_hash)
+            SELECT 1, 'fresh-' || n, repeat('0', 64)
+            FROM generate_series(1, 3) AS n;
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let repository = Arc::new(Recording {
+            inner: OrderIdempotencyRepository::new(pool.clone()),
+            batches: Mutex::default(),
+        });
+        let sweeper = IdempotencyKeySweeper::new(
+            repository.clone(),
+            PurgeJobConfig {
+                batch_size: 10,
+                interval_seconds: 3600,
+            },
+        );
+
+        assert_eq!(sweeper.drain().await.unwrap(), 25);
+        assert_eq!(*repository.batches.lock().unwrap(), [10, 10, 5]);
+
+        let left: Vec<String> = sqlx::query_scalar(
+            "SELECT idempotency_key FROM order_idempotency_keys ORDER BY idempotency_key",
+        )
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(left, ["fresh-1", "fresh-2", "fresh-3"]);
+    }
+}
@@ -1,5 +1,9 @@
 mod command;
+mod creation_gate;
+mod idempotency_sweeper;
 mod query;
 
 pub use self::command::{OrderCommandService, OrderCommandServiceDeps};
+pub use self::creation_gate::OrderCreationGate;
+pub use self::idempotency_sweeper::IdempotencyKeySweeper;
 pub use self::query::OrderQueryService;
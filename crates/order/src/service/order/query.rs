@@ -1,10 +1,15 @@
 use crate::{
-    abstract_trait::order::{repository::DynOrderQueryRepository, service::OrderQueryServiceTrait},
+    abstract_trait::{
+        grpc_client::DynProductGrpcClient,
+        order::{repository::DynOrderQueryRepository, service::OrderQueryServiceTrait},
+        order_item::repository::DynOrderItemQueryRepository,
+    },
     domain::{
         requests::order::FindAllOrder,
         response::{
             api::{ApiResponse, ApiResponsePagination},
-            order::{OrderResponse, OrderResponseDeleteAt},
+            order::{OrderDetailResponse, OrderResponse, OrderResponseDeleteAt},
+            order_item::OrderItemDetailResponse,
             pagination::Pagination,
         },
     },
@@ -18,30 +23,45 @@ use opentelemetry::{
     trace::{Span, SpanKind, TraceContextExt, Tracer},
 };
 use shared::{
-    cache::CacheStore,
-    errors::ServiceError,
-    utils::{MetadataInjector, Method, Metrics, Status as StatusUtils, TracingContext},
+    cache::{CacheStore, keys},
+    errors::{FieldViolation, RepositoryError, ServiceError},
+    utils::{
+        Cursor, CursorCodec, MetadataInjector, Method, Metrics, Status as StatusUtils,
+        TracingContext, page_count,
+    },
 };
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 use tokio::time::Instant;
 use tonic::Request;
 use tracing::{error, info};
+use uuid::Uuid;
 
 #[derive(Clone)]
 pub struct OrderQueryService {
     pub query: DynOrderQueryRepository,
+    pub order_item_query: DynOrderItemQueryRepository,
+    pub product_client: DynProductGrpcClient,
     pub metrics: Metrics,
     pub cache_store: Arc<CacheStore>,
+    pub cursor_codec: CursorCodec,
 }
 
 impl OrderQueryService {
-    pub fn new(query: DynOrderQueryRepository, cache_store: Arc<CacheStore>) -> Result<Self> {
+    pub fn new(
+        query: DynOrderQueryRepository,
+        order_item_query: DynOrderItemQueryRepository,
+        product_client: DynProductGrpcClient,
+        cache_store: Arc<CacheStore>,
+    ) -> Result<Self> {
         let metrics = Metrics::new(global::meter("order-query-service"));
 
         Ok(Self {
             query,
+            order_item_query,
+            product_client,
             metrics,
             cache_store,
+            cursor_codec: CursorCodec::from_env(),
         })
     }
 
@@ -145,6 +165,21 @@ impl OrderQueryServiceTrait for OrderQueryService {
             req.page, req.page_size, req.search
         );
 
+        // Cursors encode the default (created_at, id) position, so they only
+        // make sense when paging in that order.
+        if !req.after.is_empty() && !req.sort.trim().is_empty() {
+            return Err(ServiceError::FieldValidation(vec![FieldViolation::new(
+                "sort",
+                "Cannot be combined with after",
+            )]));
+        }
+
+        let after = if req.after.is_empty() {
+            None
+        } else {
+            Some(self.cursor_codec.decode(&req.after)?)
+        };
+
         let page = if req.page > 0 { req.page } else { 1 };
         let page_size = if req.page_size > 0 { req.page_size } else { 10 };
         let search = if req.search.is_empty() {
@@ -168,8 +203,14 @@ impl OrderQueryServiceTrait for OrderQueryService {
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
         let cache_key = format!(
-            "order:find_all:page:{page}:size:{page_size}:search:{}",
-            search.unwrap_or_default()
+            "order:find_all:page:{page}:size:{page_size}:search:{}:items:{}:sort:{}:after:{}:user:{}:from:{}:to:{}",
+            search.unwrap_or_default(),
+            req.include_items,
+            req.sort,
+            req.after,
+            req.user_id.map(|id| id.to_string()).unwrap_or_default(),
+            req.created_from.map(|d| d.to_rfc3339()).unwrap_or_default(),
+            req.created_to.map(|d| d.to_rfc3339()).unwrap_or_default()
         );
 
         if let Some(cache) = self
@@ -184,7 +225,7 @@ impl OrderQueryServiceTrait for OrderQueryService {
             return Ok(cache);
         }
 
-        let (orders, total) = match self.query.find_all(req).await {
+        let (orders, total) = match self.query.find_all(req, after).await {
             Ok(res) => {
                 let log_message = format!("Found {} orders", res.0.len());
                 info!("{}", log_message);
@@ -202,16 +243,27 @@ impl OrderQueryServiceTrait for OrderQueryService {
             }
         };
 
+        let next_cursor = orders
+            .last()
+            .filter(|_| req.sort.trim().is_empty() && orders.len() as i32 == req.page_size)
+            .and_then(|last| {
+                last.order.created_at.map(|created_at| {
+                    self.cursor_codec.encode(&Cursor {
+                        created_at,
+                        id: last.order.order_id,
+                    })
+                })
+            });
+
         let order_response: Vec<OrderResponse> =
             orders.into_iter().map(OrderResponse::from).collect();
 
-        let total_pages = ((total - 1) / req.page_size as i64) + 1;
-
         let pagination = Pagination {
             page: req.page,
             page_size: req.page_size,
             total_items: total as i32,
-            total_pages: total_pages as i32,
+            total_pages: page_count(total, req.page_size),
+            next_cursor,
         };
 
         let response = ApiResponsePagination {
@@ -307,13 +359,12 @@ impl OrderQueryServiceTrait for OrderQueryService {
             .map(OrderResponseDeleteAt::from)
             .collect();
 
-        let total_pages = ((total - 1) / req.page_size as i64) + 1;
-
         let pagination = Pagination {
             page: req.page,
             page_size: req.page_size,
             total_items: total as i32,
-            total_pages: total_pages as i32,
+            total_pages: page_count(total, req.page_size),
+            next_cursor: None,
         };
 
         let response = ApiResponsePagination {
@@ -412,13 +463,12 @@ impl OrderQueryServiceTrait for OrderQueryService {
             .map(OrderResponseDeleteAt::from)
             .collect();
 
-        let total_pages = ((total - 1) / req.page_size as i64) + 1;
-
         let pagination = Pagination {
             page: req.page,
             page_size: req.page_size,
             total_items: total as i32,
-            total_pages: total_pages as i32,
+            total_pages: page_count(total, req.page_size),
+            next_cursor: None,
         };
 
         let response = ApiResponsePagination {
@@ -455,7 +505,7 @@ impl OrderQueryServiceTrait for OrderQueryService {
         let mut request = Request::new(id);
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        let cache_key = format!("order:find_by_id:id:{id}");
+        let cache_key = keys::by_id_key("order", id);
 
         if let Some(cache) = self
             .cache_store
@@ -510,4 +560,266 @@ impl OrderQueryServiceTrait for OrderQueryService {
 
         Ok(response)
     }
+
+    async fn find_detail_by_id(
+        &self,
+        id: i32,
+    ) -> Result<ApiResponse<OrderDetailResponse>, ServiceError> {
+        info!("🧾 Finding order detail by ID: {id}");
+
+        let method = Method::Get;
+        let tracing_ctx = self.start_tracing(
+            "find_detail_by_id",
+            vec![
+                KeyValue::new("component", "order"),
+                KeyValue::new("operation", "find_detail_by_id"),
+                KeyValue::new("order.id", id.to_string()),
+            ],
+        );
+
+        let mut request = Request::new(id);
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        // Not cached: product names and prices change without the order
+        // service hearing about it.
+        let order = match self.query.find_by_id(id).await {
+            Ok(Some(order)) => order,
+            Ok(None) => {
+                error!("❌ Order not found with ID: {id}");
+                self.complete_tracing_error(&tracing_ctx, method, "Order not found")
+                    .await;
+                return Err(ServiceError::Repo(RepositoryError::NotFound));
+            }
+            Err(e) => {
+                error!("❌ Database error while finding order ID {id}: {e:?}");
+                self.complete_tracing_error(&tracing_ctx, method, "Database error")
+                    .await;
+                return Err(ServiceError::Repo(e));
+            }
+        };
+
+        let items = match self.order_item_query.find_order_item_by_order(id).await {
+            Ok(items) => items,
+            Err(e) => {
+                error!("❌ Failed to fetch items of order {id}: {e:?}");
+                self.complete_tracing_error(&tracing_ctx, method, "Database error")
+                    .await;
+                return Err(ServiceError::Repo(e));
+            }
+        };
+
+        let mut product_ids: Vec<i32> = items.iter().map(|item| item.product_id).collect();
+        product_ids.sort_unstable();
+        product_ids.dedup();
+
+        let products: HashMap<i32, _> = if product_ids.is_empty() {
+            HashMap::new()
+        } else {
+            match self.product_client.find_by_ids(product_ids).await {
+                Ok(response) => response
+                    .data
+                    .succeeded
+                    .into_iter()
+                    .map(|p| (p.id, p))
+                    .collect(),
+                Err(e) => {
+                    error!("❌ gRPC error fetching products of order {id}: {e:?}");
+                    self.complete_tracing_error(&tracing_ctx, method, "Product query failed")
+                        .await;
+                    return Err(ServiceError::Unavailable(
+                        "Product service unavailable".into(),
+                    ));
+                }
+            }
+        };
+
+        let items: Vec<OrderItemDetailResponse> = items
+            .into_iter()
+            .map(|item| {
+                let product = products.get(&item.product_id);
+                OrderItemDetailResponse::new(item, product)
+            })
+            .collect();
+
+        let response = ApiResponse {
+            status: "success".to_string(),
+            message: "Order detail retrieved successfully".to_string(),
+            data: OrderDetailResponse::new(order, items),
+        };
+
+        self.complete_tracing_success(&tracing_ctx, method, "Order detail retrieved")
+            .await;
+
+        info!(
+            "✅ Found order detail: ID={id}, items={}",
+            response.data.items.len()
+        );
+
+        Ok(response)
+    }
+
+    async fn find_by_external_id(
+        &self,
+        external_id: Uuid,
+    ) -> Result<ApiResponse<OrderResponse>, ServiceError> {
+        info!("🆔 Finding order by external ID: {external_id}");
+
+        let method = Method::Get;
+        let tracing_ctx = self.start_tracing(
+            "order_find_by_external_id",
+            vec![
+                KeyValue::new("component", "order"),
+                KeyValue::new("operation", "find_by_external_id"),
+                KeyValue::new("order.external_id", external_id.to_string()),
+            ],
+        );
+
+        let mut request = Request::new(external_id);
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let cache_key = keys::by_external_id_key("order", &external_id.to_string());
+
+        if let Some(cached) = self
+            .cache_store
+            .get_from_cache::<ApiResponse<OrderResponse>>(&cache_key)
+            .await
+        {
+            info!("✅ Found order {external_id} in cache");
+            self.complete_tracing_success(&tracing_ctx, method, "Order retrieved from cache")
+                .await;
+            return Ok(cached);
+        }
+
+        let order = match self.query.find_by_external_id(external_id).await {
+            Ok(Some(order)) => {
+                self.complete_tracing_success(
+                    &tracing_ctx,
+                    method.clone(),
+                    "Order retrieved from DB",
+                )
+                .await;
+                order
+            }
+            Ok(None) => {
+                error!("❌ Order not found with external ID: {external_id}");
+                self.complete_tracing_error(&tracing_ctx, method.clone(), "Order not found")
+                    .await;
+                return Err(ServiceError::Custom("Order not found".to_string()));
+            }
+            Err(e) => {
+                error!("❌ Database error while finding order {external_id}: {e:?}");
+                self.complete_tracing_error(&tracing_ctx, method.clone(), "Database error")
+                    .await;
+                return Err(ServiceError::Repo(e));
+            }
+        };
+
+        let response = ApiResponse {
+            status: "success".to_string(),
+            message: "Order retrieved successfully".to_string(),
+            data: OrderResponse::from(order),
+        };
+
+        self.cache_store
+            .set_to_cache(&cache_key, &response, Duration::minutes(5))
+            .await;
+
+        Ok(response)
+    }
+
+    async fn find_by_user_id(
+        &self,
+        user_id: i32,
+        req: &FindAllOrder,
+    ) -> Result<ApiResponsePagination<Vec<OrderResponse>>, ServiceError> {
+        info!(
+            "👤 Finding orders of user {user_id} | Page: {}, Size: {}",
+            req.page, req.page_size
+        );
+
+        let page = if req.page > 0 { req.page } else { 1 };
+        let page_size = if req.page_size > 0 { req.page_size } else { 10 };
+
+        let method = Method::Get;
+
+        let tracing_ctx = self.start_tracing(
+            "find_by_user_id",
+            vec![
+                KeyValue::new("user.id", user_id.to_string()),
+                KeyValue::new("page", page.to_string()),
+                KeyValue::new("page_size", page_size.to_string()),
+            ],
+        );
+
+        let mut request = Request::new(req.clone());
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let cache_key = format!(
+            "order:find_by_user_id:user:{user_id}:page:{page}:size:{page_size}:items:{}",
+            req.include_items
+        );
+
+        if let Some(cache) = self
+            .cache_store
+            .get_from_cache::<ApiResponsePagination<Vec<OrderResponse>>>(&cache_key)
+            .await
+        {
+            let log_message = format!(
+                "✅ Found cached orders of user {user_id} (total: {})",
+                cache.data.len()
+            );
+            info!("{log_message}");
+            self.complete_tracing_success(&tracing_ctx, method, &log_message)
+                .await;
+            return Ok(cache);
+        }
+
+        let req = FindAllOrder {
+            page,
+            page_size,
+            ..req.clone()
+        };
+
+        let (orders, total) = match self.query.find_by_user_id(user_id, &req).await {
+            Ok(res) => {
+                let log_message = format!("Found {} orders of user {user_id}", res.0.len());
+                info!("{log_message}");
+                self.complete_tracing_success(&tracing_ctx, method.clone(), &log_message)
+                    .await;
+                res
+            }
+            Err(e) => {
+                let log_message = format!("❌ Failed to find orders of user {user_id}: {e:?}");
+                error!("{log_message}");
+                self.complete_tracing_error(&tracing_ctx, method.clone(), &log_message)
+                    .await;
+
+                return Err(ServiceError::Repo(e));
+            }
+        };
+
+        let response: ApiResponsePagination<Vec<OrderResponse>> = ApiResponsePagination {
+            status: "success".to_string(),
+            message: "Orders retrieved successfully".to_string(),
+            data: orders.into_iter().map(OrderResponse::from).collect(),
+            pagination: Pagination {
+                page,
+                page_size,
+                total_items: total as i32,
+                total_pages: page_count(total, page_size),
+                next_cursor: None,
+            },
+        };
+
+        self.cache_store
+            .set_to_cache(&cache_key, &response.clone(), Duration::minutes(5))
+            .await;
+
+        info!(
+            "✅ Found {} orders of user {user_id} (total: {total})",
+            response.data.len()
+        );
+
+        Ok(response)
+    }
 }
@@ -22,7 +22,7 @@ use opentelemetry::{
 use shared::{
     cache::CacheStore,
     errors::ServiceError,
-    utils::{MetadataInjector, Method, Metrics, Status as StatusUtils, TracingContext},
+    utils::{MetadataInjector, Method, Metrics, Status as StatusUtils, TracingContext, page_count},
 };
 use std::sync::Arc;
 use tokio::time::Instant;
@@ -206,12 +206,12 @@ impl OrderItemQueryServiceTrait for OrderItemQueryService {
             .into_iter()
             .map(OrderItemResponse::from)
             .collect();
-        let total_pages = ((total - 1) / req.page_size as i64) + 1;
         let pagination = Pagination {
             page: req.page,
             page_size: req.page_size,
             total_items: total as i32,
-            total_pages: total_pages as i32,
+            total_pages: page_count(total, req.page_size),
+            next_cursor: None,
         };
 
         let response = ApiResponsePagination {
@@ -302,12 +302,12 @@ impl OrderItemQueryServiceTrait for OrderItemQueryService {
             .into_iter()
             .map(OrderItemResponseDeleteAt::from)
             .collect();
-        let total_pages = ((total - 1) / req.page_size as i64) + 1;
         let pagination = Pagination {
             page: req.page,
             page_size: req.page_size,
             total_items: total as i32,
-            total_pages: total_pages as i32,
+            total_pages: page_count(total, req.page_size),
+            next_cursor: None,
         };
 
         let response = ApiResponsePagination {
@@ -400,12 +400,12 @@ impl OrderItemQueryServiceTrait for OrderItemQueryService {
             .into_iter()
             .map(OrderItemResponseDeleteAt::from)
             .collect();
-        let total_pages = ((total - 1) / req.page_size as i64) + 1;
         let pagination = Pagination {
             page: req.page,
             page_size: req.page_size,
             total_items: total as i32,
-            total_pages: total_pages as i32,
+            total_pages: page_count(total, req.page_size),
+            next_cursor: None,
         };
 
         let response = ApiResponsePagination {
@@ -6,7 +6,7 @@ use crate::{
 use anyhow::{Context, Result};
 use shared::{
     abstract_trait::DynKafka,
-    config::{ConnectionPool, Kafka, RedisConfig, RedisPool},
+    config::{DatabasePools, Kafka, RedisConfig, RedisPool},
     utils::{SystemMetrics, run_metrics_collector},
 };
 use std::{fmt, sync::Arc};
@@ -16,6 +16,7 @@ pub struct AppState {
     pub di_container: DependenciesInject,
     pub kafka_config: DynKafka,
     pub system_metrics: Arc<SystemMetrics>,
+    pub db_pools: DatabasePools,
 }
 
 impl fmt::Debug for AppState {
@@ -28,9 +29,14 @@ impl fmt::Debug for AppState {
 }
 
 impl AppState {
-    pub async fn new(pool: ConnectionPool, config: Config) -> Result<Self> {
+    pub async fn new(pools: DatabasePools, config: Config) -> Result<Self> {
         let kafka_config = Arc::new(Kafka::new(&config.kafka_broker)) as DynKafka;
         let system_metrics = Arc::new(SystemMetrics::new());
+        let duplicate_item_policy = config.duplicate_item_policy;
+        let order_creation_paused = config.order_creation_paused;
+        let order_total_limits = config.order_total_limits;
+        let idempotency_purge = config.idempotency_purge;
+        let order_number_format = config.order_number_format.clone();
 
         let config = RedisConfig::new();
 
@@ -39,9 +45,14 @@ impl AppState {
         redis.ping().await.context("Failed to ping Redis server")?;
 
         let deps = DependenciesInjectDeps {
-            pool: pool.clone(),
+            pools: pools.clone(),
             kafka: kafka_config.clone(),
             redis: redis.clone(),
+            duplicate_item_policy,
+            order_creation_paused,
+            order_total_limits,
+            idempotency_purge,
+            order_number_format,
         };
 
         let grpc_config = GrpcClientConfig::init().context("failed config grpc")?;
@@ -62,6 +73,7 @@ impl AppState {
             di_container,
             kafka_config,
             system_metrics,
+            db_pools: pools,
         })
     }
 }
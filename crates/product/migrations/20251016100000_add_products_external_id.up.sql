@@ -0,0 +1,5 @@
+-- Add up migration script here
+ALTER TABLE products
+ADD COLUMN IF NOT EXISTS external_id UUID NOT NULL DEFAULT gen_random_uuid();
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_products_external_id ON products (external_id);
@@ -0,0 +1,3 @@
+-- Add up migration script here
+ALTER TABLE products
+ADD COLUMN IF NOT EXISTS images JSONB NOT NULL DEFAULT '[]'::jsonb;
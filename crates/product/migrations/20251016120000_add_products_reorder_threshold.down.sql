@@ -0,0 +1,4 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS idx_products_low_stock;
+
+ALTER TABLE products DROP COLUMN IF EXISTS reorder_threshold;
@@ -0,0 +1,6 @@
+-- Add up migration script here
+ALTER TABLE products
+ADD COLUMN IF NOT EXISTS reorder_threshold INTEGER CHECK (reorder_threshold >= 0);
+
+CREATE INDEX IF NOT EXISTS idx_products_low_stock ON products (stock)
+WHERE reorder_threshold IS NOT NULL AND deleted_at IS NULL;
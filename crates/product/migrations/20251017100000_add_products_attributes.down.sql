@@ -0,0 +1,6 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS idx_products_attributes;
+
+ALTER TABLE products
+DROP COLUMN IF EXISTS attributes,
+DROP COLUMN IF EXISTS category;
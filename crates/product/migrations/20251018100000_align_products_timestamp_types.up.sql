@@ -0,0 +1,8 @@
+-- Add up migration script here
+-- The service reads products with UTC TIMESTAMPs, which is what its
+-- compile-time checked queries were prepared against. Align the table so a
+-- freshly migrated database decodes the same way.
+ALTER TABLE products
+    ALTER COLUMN created_at TYPE TIMESTAMP USING created_at AT TIME ZONE 'UTC',
+    ALTER COLUMN updated_at TYPE TIMESTAMP USING updated_at AT TIME ZONE 'UTC',
+    ALTER COLUMN deleted_at TYPE TIMESTAMP USING deleted_at AT TIME ZONE 'UTC';
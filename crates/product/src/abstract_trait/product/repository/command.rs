@@ -28,10 +28,17 @@ pub trait ProductCommandRepositoryTrait {
         &self,
         product_id: i32,
         qty: i32,
-    ) -> Result<ProductModel, RepositoryError>;
+    ) -> Result<Option<ProductModel>, RepositoryError>;
+    async fn find_stock(&self, product_id: i32) -> Result<Option<i32>, RepositoryError>;
     async fn trash_product(&self, id: i32) -> Result<ProductModel, RepositoryError>;
     async fn restore_product(&self, id: i32) -> Result<ProductModel, RepositoryError>;
     async fn delete_product(&self, id: i32) -> Result<(), RepositoryError>;
     async fn restore_all_products(&self) -> Result<(), RepositoryError>;
     async fn delete_all_products(&self) -> Result<(), RepositoryError>;
+    /// Inserts all of `products` in one transaction, returning how many
+    /// rows were written.
+    async fn create_products(
+        &self,
+        products: &[CreateProductRequest],
+    ) -> Result<u64, RepositoryError>;
 }
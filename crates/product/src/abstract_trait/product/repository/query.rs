@@ -1,16 +1,19 @@
 use crate::{domain::requests::product::FindAllProducts, model::product::Product as ProductModel};
 use anyhow::Result;
 use async_trait::async_trait;
-use shared::errors::RepositoryError;
+use shared::{errors::RepositoryError, utils::Cursor};
 use std::sync::Arc;
+use uuid::Uuid;
 
 pub type DynProductQueryRepository = Arc<dyn ProductQueryRepositoryTrait + Send + Sync>;
 
 #[async_trait]
 pub trait ProductQueryRepositoryTrait {
+    /// Offset pagination, or keyset pagination after `after` when given.
     async fn find_all(
         &self,
         req: &FindAllProducts,
+        after: Option<Cursor>,
     ) -> Result<(Vec<ProductModel>, i64), RepositoryError>;
     async fn find_active(
         &self,
@@ -20,5 +23,16 @@ pub trait ProductQueryRepositoryTrait {
         &self,
         req: &FindAllProducts,
     ) -> Result<(Vec<ProductModel>, i64), RepositoryError>;
+    async fn find_low_stock(
+        &self,
+        req: &FindAllProducts,
+    ) -> Result<(Vec<ProductModel>, i64), RepositoryError>;
     async fn find_by_id(&self, id: i32) -> Result<Option<ProductModel>, RepositoryError>;
+    /// Active products among `ids`, ordered by id; ids of missing or trashed
+    /// products are left out rather than failing the lookup.
+    async fn find_by_ids(&self, ids: &[i32]) -> Result<Vec<ProductModel>, RepositoryError>;
+    async fn find_by_external_id(
+        &self,
+        external_id: Uuid,
+    ) -> Result<Option<ProductModel>, RepositoryError>;
 }
@@ -7,7 +7,7 @@ use crate::domain::{
 };
 use anyhow::Result;
 use async_trait::async_trait;
-use shared::errors::ServiceError;
+use shared::{errors::ServiceError, utils::BatchResponse};
 use std::sync::Arc;
 
 pub type DynProductCommandService = Arc<dyn ProductCommandServiceTrait + Send + Sync>;
@@ -43,4 +43,12 @@ pub trait ProductCommandServiceTrait {
     async fn delete_product(&self, id: i32) -> Result<ApiResponse<()>, ServiceError>;
     async fn restore_all_product(&self) -> Result<ApiResponse<()>, ServiceError>;
     async fn delete_all_product(&self) -> Result<ApiResponse<()>, ServiceError>;
+    /// Imports products from a `name,price,stock[,description]` CSV. Bad
+    /// rows are reported in the summary, or fail the whole import when
+    /// `strict` is set.
+    async fn import_products(
+        &self,
+        csv: &str,
+        strict: bool,
+    ) -> Result<ApiResponse<BatchResponse<i32>>, ServiceError>;
 }
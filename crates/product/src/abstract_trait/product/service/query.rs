@@ -7,8 +7,9 @@ use crate::domain::{
 };
 use anyhow::Result;
 use async_trait::async_trait;
-use shared::errors::ServiceError;
+use shared::{errors::ServiceError, utils::BatchResponse};
 use std::sync::Arc;
+use uuid::Uuid;
 
 pub type DynProductQueryService = Arc<dyn ProductQueryServiceTrait + Send + Sync>;
 
@@ -26,5 +27,17 @@ pub trait ProductQueryServiceTrait {
         &self,
         req: &FindAllProducts,
     ) -> Result<ApiResponsePagination<Vec<ProductResponseDeleteAt>>, ServiceError>;
+    async fn find_low_stock(
+        &self,
+        req: &FindAllProducts,
+    ) -> Result<ApiResponsePagination<Vec<ProductResponse>>, ServiceError>;
     async fn find_by_id(&self, id: i32) -> Result<ApiResponse<ProductResponse>, ServiceError>;
+    async fn find_by_ids(
+        &self,
+        ids: &[i32],
+    ) -> Result<ApiResponse<BatchResponse<ProductResponse>>, ServiceError>;
+    async fn find_by_external_id(
+        &self,
+        external_id: Uuid,
+    ) -> Result<ApiResponse<ProductResponse>, ServiceError>;
 }
@@ -1,4 +1,6 @@
+use crate::domain::requests::product_attributes::AttributeSchemas;
 use anyhow::{Context, Result, anyhow};
+use std::collections::HashSet;
 
 #[derive(Debug, Clone)]
 pub struct ServiceConfig {
@@ -6,9 +8,24 @@ pub struct ServiceConfig {
     pub metric_port: u16,
 }
 
+/// How often a failing order event is retried before it is parked on its
+/// dead-letter topic, `<topic><topic_suffix>`.
+#[derive(Debug, Clone)]
+pub struct DeadLetterConfig {
+    pub max_retries: u32,
+    pub topic_suffix: String,
+}
+
+impl DeadLetterConfig {
+    pub fn topic_for(&self, source_topic: &str) -> String {
+        format!("{source_topic}{}", self.topic_suffix)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Config {
     pub database_url: String,
+    pub database_read_url: Option<String>,
     pub jwt_secret: String,
     pub run_migrations: bool,
     pub port: u16,
@@ -16,15 +33,27 @@ pub struct Config {
     pub kafka_broker: String,
     pub db_max_conn: u32,
     pub db_min_conn: u32,
+    pub kafka_max_concurrent_handlers: usize,
+    pub kafka_accepted_event_types: HashSet<String>,
+    pub kafka_dead_letter: DeadLetterConfig,
+    pub max_product_images: usize,
+    /// Publish `product.out_of_stock` / `product.back_in_stock` when stock
+    /// crosses zero.
+    pub stock_events: bool,
+    pub attribute_schemas: AttributeSchemas,
 }
 impl Config {
     pub fn init() -> Result<Self> {
         let database_url =
             std::env::var("DATABASE_URL").context("Missing environment variable: DATABASE_URL")?;
+        let database_read_url = std::env::var("DATABASE_READ_URL")
+            .ok()
+            .filter(|url| !url.trim().is_empty());
         let jwt_secret =
             std::env::var("JWT_SECRET").context("Missing environment variable: JWT_SECRET")?;
-        let run_migrations_str = std::env::var("RUN_MIGRATIONS")
-            .context("Missing environment variable: RUN_MIGRATIONS")?;
+        let run_migrations_str = std::env::var("AUTO_MIGRATE")
+            .or_else(|_| std::env::var("RUN_MIGRATIONS"))
+            .context("Missing environment variable: AUTO_MIGRATE")?;
         let port_str = std::env::var("PORT").context("Missing environment variable: PORT")?;
 
         let kafka_broker = std::env::var("KAFKA").context("Missing environment variable: KAFKA")?;
@@ -34,7 +63,7 @@ impl Config {
             "false" => false,
             other => {
                 return Err(anyhow!(
-                    "RUN_MIGRATIONS must be 'true' or 'false', got '{}'",
+                    "AUTO_MIGRATE must be 'true' or 'false', got '{}'",
                     other
                 ));
             }
@@ -50,6 +79,52 @@ impl Config {
             .parse::<u32>()
             .context("Unable to parse DB_MIN_CONNECTION as u32")?;
 
+        let kafka_max_concurrent_handlers: usize = std::env::var("KAFKA_MAX_CONCURRENT_HANDLERS")
+            .unwrap_or_else(|_| "4".to_string())
+            .parse::<usize>()
+            .context("Unable to parse KAFKA_MAX_CONCURRENT_HANDLERS as usize")?;
+
+        let kafka_accepted_event_types = std::env::var("KAFKA_ACCEPTED_EVENT_TYPES")
+            .unwrap_or_else(|_| "Created,Updated,Deleted".to_string())
+            .split(',')
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(str::to_string)
+            .collect();
+
+        let kafka_max_retries: u32 = std::env::var("KAFKA_MAX_RETRIES")
+            .unwrap_or_else(|_| "3".to_string())
+            .parse::<u32>()
+            .context("Unable to parse KAFKA_MAX_RETRIES as u32")?;
+
+        let kafka_dlq_topic_suffix = std::env::var("KAFKA_DLQ_TOPIC_SUFFIX")
+            .ok()
+            .filter(|suffix| !suffix.trim().is_empty())
+            .unwrap_or_else(|| ".dlq".to_string());
+
+        let max_product_images: usize = std::env::var("PRODUCT_MAX_IMAGES")
+            .unwrap_or_else(|_| "10".to_string())
+            .parse::<usize>()
+            .context("Unable to parse PRODUCT_MAX_IMAGES as usize")?;
+
+        let stock_events = std::env::var("PRODUCT_STOCK_EVENTS")
+            .map(|v| v == "true" || v == "1")
+            .unwrap_or(true);
+
+        let attribute_schemas = match std::env::var("PRODUCT_ATTRIBUTE_SCHEMAS_FILE")
+            .ok()
+            .filter(|path| !path.trim().is_empty())
+        {
+            Some(path) => {
+                let contents = std::fs::read_to_string(&path).with_context(|| {
+                    format!("Unable to read PRODUCT_ATTRIBUTE_SCHEMAS_FILE '{path}'")
+                })?;
+                AttributeSchemas::from_json(&contents)
+                    .with_context(|| format!("Invalid PRODUCT_ATTRIBUTE_SCHEMAS_FILE '{path}'"))?
+            }
+            None => AttributeSchemas::default(),
+        };
+
         let port = port_str
             .parse::<u16>()
             .context("PORT must be a valid u16 integer")?;
@@ -67,6 +142,7 @@ impl Config {
 
         Ok(Self {
             database_url,
+            database_read_url,
             jwt_secret,
             run_migrations,
             port,
@@ -77,6 +153,15 @@ impl Config {
             kafka_broker,
             db_max_conn,
             db_min_conn,
+            kafka_max_concurrent_handlers,
+            kafka_accepted_event_types,
+            kafka_dead_letter: DeadLetterConfig {
+                max_retries: kafka_max_retries,
+                topic_suffix: kafka_dlq_topic_suffix,
+            },
+            max_product_images,
+            stock_events,
+            attribute_schemas,
         })
     }
 }
@@ -1,11 +1,16 @@
 use crate::{
+    domain::requests::product_attributes::AttributeSchemas,
     repository::{command::ProductCommandRepository, query::ProductQueryRepository},
-    service::{command::ProductCommandService, query::ProductQueryService},
+    service::{
+        command::{ProductCommandService, ProductCommandServiceDeps},
+        query::ProductQueryService,
+    },
 };
 use anyhow::{Context, Result};
 use shared::{
+    abstract_trait::DynKafka,
     cache::CacheStore,
-    config::{ConnectionPool, RedisPool},
+    config::{DatabasePools, RedisPool},
 };
 use std::{fmt, sync::Arc};
 
@@ -26,24 +31,42 @@ impl fmt::Debug for DependenciesInject {
 
 #[derive(Clone)]
 pub struct DependenciesInjectDeps {
-    pub pool: ConnectionPool,
+    pub pools: DatabasePools,
     pub redis: RedisPool,
+    pub kafka: DynKafka,
+    pub max_product_images: usize,
+    pub stock_events: bool,
+    pub attribute_schemas: AttributeSchemas,
 }
 
 impl DependenciesInject {
     pub fn new(deps: DependenciesInjectDeps) -> Result<Self> {
-        let DependenciesInjectDeps { pool, redis } = deps;
+        let DependenciesInjectDeps {
+            pools,
+            redis,
+            kafka,
+            max_product_images,
+            stock_events,
+            attribute_schemas,
+        } = deps;
 
-        let product_query_repo = Arc::new(ProductQueryRepository::new(pool.clone()));
-        let product_command_repo = Arc::new(ProductCommandRepository::new(pool.clone()));
+        let product_query_repo = Arc::new(ProductQueryRepository::new(pools.read.clone()));
+        let product_command_repo = Arc::new(ProductCommandRepository::new(pools.write.clone()));
 
         let cache = Arc::new(CacheStore::new(redis.pool.clone()));
 
         let product_query = ProductQueryService::new(product_query_repo.clone(), cache.clone())
             .context("failed initialize product query")?;
 
-        let product_command = ProductCommandService::new(product_command_repo.clone())
-            .context("failed initialize product command")?;
+        let product_command = ProductCommandService::new(ProductCommandServiceDeps {
+            command: product_command_repo.clone(),
+            kafka,
+            cache_store: cache.clone(),
+            max_images: max_product_images,
+            stock_events,
+            attribute_schemas: Arc::new(attribute_schemas),
+        })
+        .context("failed initialize product command")?;
 
         Ok(Self {
             product_query,
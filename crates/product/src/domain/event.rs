@@ -30,3 +30,55 @@ pub enum OrderEvent {
         deleted_items: Vec<OrderItemEvent>,
     },
 }
+
+/// Only the `type` tag, read before deserializing the full event so unknown
+/// types can be skipped.
+#[derive(Deserialize, Debug)]
+pub struct OrderEventEnvelope {
+    #[serde(rename = "type")]
+    pub event_type: String,
+}
+
+/// Published when a stock change makes a product unavailable or available
+/// again, so other services can react without polling.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "type")]
+pub enum ProductEvent {
+    OutOfStock { product_id: i32 },
+    BackInStock { product_id: i32, stock: i32 },
+}
+
+impl ProductEvent {
+    pub fn topic(&self) -> &'static str {
+        match self {
+            Self::OutOfStock { .. } => "product.out_of_stock",
+            Self::BackInStock { .. } => "product.back_in_stock",
+        }
+    }
+
+    /// The event for a stock change from `before` to `after`, if it crossed zero.
+    pub fn for_stock_change(product_id: i32, before: i32, after: i32) -> Option<Self> {
+        match (before > 0, after > 0) {
+            (true, false) => Some(Self::OutOfStock { product_id }),
+            (false, true) => Some(Self::BackInStock {
+                product_id,
+                stock: after,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// An order event that kept failing, parked on its dead-letter topic together
+/// with where it came from and why it was given up on.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DeadLetterEvent {
+    pub source_topic: String,
+    pub partition: i32,
+    pub offset: i64,
+    pub key: Option<String>,
+    pub payload: String,
+    pub error: String,
+    pub retries: u32,
+    pub failed_at: String,
+}
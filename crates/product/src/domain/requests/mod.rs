@@ -1 +1,3 @@
 pub mod product;
+pub mod product_attributes;
+pub mod product_import;
@@ -1,4 +1,6 @@
 use serde::{Deserialize, Serialize};
+use shared::errors::FieldViolation;
+use std::collections::BTreeMap;
 use utoipa::{IntoParams, ToSchema};
 use validator::Validate;
 
@@ -12,6 +14,77 @@ pub struct FindAllProducts {
 
     #[serde(default)]
     pub search: String,
+
+    #[serde(default)]
+    pub min_price: Option<i64>,
+
+    #[serde(default)]
+    pub max_price: Option<i64>,
+
+    /// `key:value` match against the product attributes, e.g. `color:red`.
+    #[serde(default)]
+    pub attribute: String,
+
+    /// Opaque `next_cursor` from a previous page; switches `find_all` to
+    /// keyset pagination when set.
+    #[serde(default)]
+    pub after: String,
+
+    /// Comma separated `field:direction` pairs, e.g. `price:desc,name:asc`.
+    /// Empty keeps the newest-first default.
+    #[serde(default)]
+    pub sort: String,
+}
+
+impl FindAllProducts {
+    /// Either bound may be left open, but a closed range must not be inverted.
+    pub fn price_range_violations(&self) -> Vec<FieldViolation> {
+        let mut violations = Vec::new();
+
+        if self.min_price.is_some_and(|min| min < 0) {
+            violations.push(FieldViolation::new(
+                "min_price",
+                "Minimum price cannot be negative",
+            ));
+        }
+
+        if self.max_price.is_some_and(|max| max < 0) {
+            violations.push(FieldViolation::new(
+                "max_price",
+                "Maximum price cannot be negative",
+            ));
+        }
+
+        if let (Some(min), Some(max)) = (self.min_price, self.max_price)
+            && min > max
+        {
+            violations.push(FieldViolation::new(
+                "min_price",
+                format!("min_price ({min}) must not be greater than max_price ({max})"),
+            ));
+        }
+
+        violations
+    }
+
+    /// The `attribute` filter split into its key and value.
+    pub fn attribute_filter(&self) -> Option<(&str, &str)> {
+        let (key, value) = self.attribute.split_once(':')?;
+        let (key, value) = (key.trim(), value.trim());
+
+        (!key.is_empty()).then_some((key, value))
+    }
+
+    pub fn attribute_violations(&self) -> Vec<FieldViolation> {
+        if self.attribute.trim().is_empty() || self.attribute_filter().is_some() {
+            return Vec::new();
+        }
+
+        vec![FieldViolation::new(
+            "attribute",
+            "Attribute filter must look like key:value",
+        )]
+    }
 }
 
 fn default_page() -> i32 {
@@ -35,6 +108,25 @@ pub struct CreateProductRequest {
     #[validate(range(min = 0, message = "Stock cannot be negative"))]
     #[schema(example = 100)]
     pub stock: i32,
+
+    #[serde(default)]
+    #[schema(example = json!(["https://cdn.example.com/products/smartphone.jpg"]))]
+    pub images: Vec<String>,
+
+    #[serde(default)]
+    #[validate(range(min = 0, message = "Reorder threshold cannot be negative"))]
+    #[schema(example = 10)]
+    pub reorder_threshold: Option<i32>,
+
+    #[serde(default)]
+    #[validate(length(min = 1, max = 100, message = "Category must be 1 to 100 characters"))]
+    #[schema(example = "apparel")]
+    pub category: Option<String>,
+
+    /// Checked against the category's attribute schema when one is configured.
+    #[serde(default)]
+    #[schema(example = json!({"size": "M", "color": "red"}))]
+    pub attributes: BTreeMap<String, String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Validate, ToSchema)]
@@ -52,4 +144,70 @@ pub struct UpdateProductRequest {
     #[validate(range(min = 0, message = "Stock cannot be negative"))]
     #[schema(example = 100)]
     pub stock: i32,
+
+    #[serde(default)]
+    #[schema(example = json!(["https://cdn.example.com/products/smartphone.jpg"]))]
+    pub images: Vec<String>,
+
+    #[serde(default)]
+    #[validate(range(min = 0, message = "Reorder threshold cannot be negative"))]
+    #[schema(example = 10)]
+    pub reorder_threshold: Option<i32>,
+
+    #[serde(default)]
+    #[validate(length(min = 1, max = 100, message = "Category must be 1 to 100 characters"))]
+    #[schema(example = "apparel")]
+    pub category: Option<String>,
+
+    /// Checked against the category's attribute schema when one is configured.
+    #[serde(default)]
+    #[schema(example = json!({"size": "M", "color": "red"}))]
+    pub attributes: BTreeMap<String, String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FindAllProducts;
+
+    fn range(min_price: Option<i64>, max_price: Option<i64>) -> FindAllProducts {
+        FindAllProducts {
+            min_price,
+            max_price,
+            ..serde_json::from_value(serde_json::json!({})).unwrap()
+        }
+    }
+
+    fn violated_fields(req: &FindAllProducts) -> Vec<String> {
+        req.price_range_violations()
+            .into_iter()
+            .map(|v| v.field)
+            .collect()
+    }
+
+    #[test]
+    fn bounded_and_open_ended_ranges_are_accepted() {
+        for (min, max) in [
+            (Some(100), Some(500)),
+            (Some(250), Some(250)),
+            (Some(100), None),
+            (None, Some(500)),
+            (None, None),
+        ] {
+            assert!(
+                violated_fields(&range(min, max)).is_empty(),
+                "{min:?}..{max:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn inverted_or_negative_ranges_are_rejected() {
+        let inverted = range(Some(500), Some(100)).price_range_violations();
+        assert_eq!(inverted.len(), 1);
+        assert_eq!(inverted[0].field, "min_price");
+        assert!(inverted[0].description.contains("(500)"));
+
+        assert_eq!(violated_fields(&range(Some(-1), None)), ["min_price"]);
+        assert_eq!(violated_fields(&range(None, Some(-1))), ["max_price"]);
+    }
 }
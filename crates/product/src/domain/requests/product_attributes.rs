@@ -0,0 +1,249 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use shared::errors::FieldViolation;
+use std::collections::{BTreeMap, HashMap};
+
+/// Kind of value an attribute holds. Values are always stored as strings;
+/// the type only decides what they must parse as.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AttributeType {
+    #[default]
+    String,
+    Integer,
+    Number,
+    Boolean,
+}
+
+impl AttributeType {
+    fn name(self) -> &'static str {
+        match self {
+            Self::String => "string",
+            Self::Integer => "integer",
+            Self::Number => "number",
+            Self::Boolean => "boolean",
+        }
+    }
+}
+
+/// One attribute of a category schema.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AttributeRule {
+    #[serde(default, rename = "type")]
+    pub kind: AttributeType,
+    #[serde(default)]
+    pub required: bool,
+    /// Allowed values; empty allows any value of `kind`.
+    #[serde(default)]
+    pub values: Vec<String>,
+}
+
+/// Attribute schemas by product category, loaded from a JSON file such as
+///
+/// ```json
+/// { "apparel": { "size": { "type": "string", "values": ["S", "M", "L"], "required": true },
+///                "color": {} } }
+/// ```
+///
+/// Products in a category with a schema may only carry the attributes it
+/// lists. Categories without one, and products without a category, accept
+/// any attributes.
+#[derive(Debug, Clone, Default)]
+pub struct AttributeSchemas {
+    by_category: HashMap<String, BTreeMap<String, AttributeRule>>,
+}
+
+impl AttributeSchemas {
+    pub fn from_json(raw: &str) -> Result<Self> {
+        let by_category: HashMap<String, BTreeMap<String, AttributeRule>> =
+            serde_json::from_str(raw).context("Invalid product attribute schema")?;
+
+        Ok(Self {
+            by_category: by_category
+                .into_iter()
+                .map(|(category, rules)| (category.trim().to_lowercase(), rules))
+                .collect(),
+        })
+    }
+
+    /// Every problem with `attributes`, so a client can fix them in one go.
+    pub fn validate(
+        &self,
+        category: Option<&str>,
+        attributes: &BTreeMap<String, String>,
+    ) -> Vec<FieldViolation> {
+        let mut violations = Vec::new();
+
+        for key in attributes.keys() {
+            if key.trim().is_empty() || key.contains(':') {
+                violations.push(FieldViolation::new(
+                    "attributes",
+                    format!("Attribute name '{key}' must be non-empty and must not contain ':'"),
+                ));
+            }
+        }
+
+        let Some(rules) = category.and_then(|c| self.by_category.get(&c.trim().to_lowercase()))
+        else {
+            return violations;
+        };
+
+        for (key, value) in attributes {
+            let field = format!("attributes.{key}");
+
+            let Some(rule) = rules.get(key) else {
+                violations.push(FieldViolation::new(
+                    field,
+                    "Not an attribute of this category",
+                ));
+                continue;
+            };
+
+            if let Some(message) = rule.check(value) {
+                violations.push(FieldViolation::new(field, message));
+            }
+        }
+
+        for (key, rule) in rules {
+            if rule.required && !attributes.contains_key(key) {
+                violations.push(FieldViolation::new(
+                    format!("attributes.{key}"),
+                    "Required for this category",
+                ));
+            }
+        }
+
+        violations
+    }
+}
+
+impl AttributeRule {
+    fn check(&self, value: &str) -> Option<String> {
+        let parses = match self.kind {
+            AttributeType::String => true,
+            AttributeType::Integer => value.parse::<i64>().is_ok(),
+            AttributeType::Number => value.parse::<f64>().is_ok_and(f64::is_finite),
+            AttributeType::Boolean => matches!(value, "true" | "false"),
+        };
+
+        if !parses {
+            return Some(format!("'{value}' is not a valid {}", self.kind.name()));
+        }
+
+        if !self.values.is_empty() && !self.values.iter().any(|allowed| allowed == value) {
+            return Some(format!(
+                "'{value}' is not one of: {}",
+                self.values.join(", ")
+            ));
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AttributeSchemas;
+    use std::collections::BTreeMap;
+
+    fn schemas() -> AttributeSchemas {
+        AttributeSchemas::from_json(
+            r#"{
+                "Apparel": {
+                    "size": { "type": "string", "values": ["S", "M", "L"], "required": true },
+                    "waist": { "type": "integer" },
+                    "organic": { "type": "boolean" },
+                    "color": {}
+                }
+            }"#,
+        )
+        .unwrap()
+    }
+
+    fn attributes(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    /// `(field, description)` of every violation.
+    fn violations(category: Option<&str>, pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        schemas()
+            .validate(category, &attributes(pairs))
+            .into_iter()
+            .map(|v| (v.field, v.description))
+            .collect()
+    }
+
+    #[test]
+    fn attributes_matching_the_category_schema_pass() {
+        let valid = [
+            ("size", "M"),
+            ("waist", "32"),
+            ("organic", "true"),
+            ("color", "navy"),
+        ];
+        assert!(violations(Some("apparel"), &valid).is_empty());
+        assert!(violations(Some(" APPAREL "), &[("size", "S")]).is_empty());
+    }
+
+    #[test]
+    fn every_bad_value_is_reported_at_once() {
+        let found = violations(
+            Some("apparel"),
+            &[("waist", "thirty"), ("organic", "yes"), ("fabric", "wool")],
+        );
+
+        assert_eq!(
+            found,
+            [
+                (
+                    "attributes.fabric".to_string(),
+                    "Not an attribute of this category".to_string()
+                ),
+                (
+                    "attributes.organic".to_string(),
+                    "'yes' is not a valid boolean".to_string()
+                ),
+                (
+                    "attributes.waist".to_string(),
+                    "'thirty' is not a valid integer".to_string()
+                ),
+                (
+                    "attributes.size".to_string(),
+                    "Required for this category".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn values_outside_the_allowed_list_are_rejected() {
+        assert_eq!(
+            violations(Some("apparel"), &[("size", "XL")]),
+            [(
+                "attributes.size".to_string(),
+                "'XL' is not one of: S, M, L".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn categories_without_a_schema_accept_any_well_named_attribute() {
+        let free_form = [("voltage", "230"), ("plug", "type F")];
+        assert!(violations(Some("lighting"), &free_form).is_empty());
+        assert!(violations(None, &free_form).is_empty());
+
+        let found = violations(None, &[("plug:type", "F")]);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].0, "attributes");
+    }
+
+    #[test]
+    fn unknown_rule_fields_are_a_schema_error() {
+        let typo = r#"{ "apparel": { "size": { "requird": true } } }"#;
+        assert!(AttributeSchemas::from_json(typo).is_err());
+    }
+}
@@ -0,0 +1,143 @@
+use crate::domain::requests::product::CreateProductRequest;
+use shared::utils::BatchFailure;
+use validator::Validate;
+
+/// Columns a product CSV may carry. `description` is accepted so exports
+/// from other tools import as-is, but products have no description to
+/// store it in.
+const REQUIRED_COLUMNS: [&str; 3] = ["name", "price", "stock"];
+
+/// Rows ready to insert, keyed by their line number in the file (the header
+/// is line 1), and the rows that didn't parse or validate.
+#[derive(Debug, Default)]
+pub struct ParsedProductImport {
+    pub rows: Vec<(i32, CreateProductRequest)>,
+    pub errors: Vec<BatchFailure>,
+}
+
+/// Parses a `name,price,stock[,description]` CSV. Columns are matched by the
+/// header in any order; quoted fields may contain commas and `""` escapes
+/// but not line breaks. Fails only when the header itself is unusable.
+pub fn parse_product_csv(input: &str) -> Result<ParsedProductImport, String> {
+    let mut lines = input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index as i32 + 1, line.trim_end_matches('\r')))
+        .filter(|(_, line)| !line.trim().is_empty());
+
+    let (_, header) = lines.next().ok_or("The CSV file is empty")?;
+    let header: Vec<String> = split_csv_line(header)
+        .map_err(|e| format!("Invalid header: {e}"))?
+        .into_iter()
+        .map(|column| column.trim().to_ascii_lowercase())
+        .collect();
+
+    let position = |name: &str| header.iter().position(|column| column == name);
+
+    let missing: Vec<&str> = REQUIRED_COLUMNS
+        .into_iter()
+        .filter(|name| position(name).is_none())
+        .collect();
+
+    if !missing.is_empty() {
+        return Err(format!("Missing CSV columns: {}", missing.join(", ")));
+    }
+
+    let (name_at, price_at, stock_at) = (
+        position("name").unwrap_or_default(),
+        position("price").unwrap_or_default(),
+        position("stock").unwrap_or_default(),
+    );
+
+    let mut parsed = ParsedProductImport::default();
+
+    for (row, line) in lines {
+        let result = split_csv_line(line).and_then(|fields| {
+            if fields.len() != header.len() {
+                return Err(format!(
+                    "Expected {} fields, found {}",
+                    header.len(),
+                    fields.len()
+                ));
+            }
+
+            parse_row(&fields[name_at], &fields[price_at], &fields[stock_at])
+        });
+
+        match result {
+            Ok(product) => parsed.rows.push((row, product)),
+            Err(message) => parsed.errors.push(BatchFailure::new(row, message)),
+        }
+    }
+
+    Ok(parsed)
+}
+
+fn parse_row(name: &str, price: &str, stock: &str) -> Result<CreateProductRequest, String> {
+    let price = price
+        .trim()
+        .parse::<i64>()
+        .map_err(|_| format!("Price '{}' is not a whole number", price.trim()))?;
+    let stock = stock
+        .trim()
+        .parse::<i32>()
+        .map_err(|_| format!("Stock '{}' is not a whole number", stock.trim()))?;
+
+    let product = CreateProductRequest {
+        name: name.trim().to_string(),
+        price,
+        stock,
+        images: Vec::new(),
+        reorder_threshold: None,
+        category: None,
+        attributes: Default::default(),
+    };
+
+    product.validate().map_err(|errors| {
+        let mut messages: Vec<String> = errors
+            .field_errors()
+            .into_values()
+            .flatten()
+            .map(|e| {
+                e.message
+                    .as_ref()
+                    .map(|m| m.to_string())
+                    .unwrap_or_else(|| e.code.to_string())
+            })
+            .collect();
+        messages.sort();
+        messages.join("; ")
+    })?;
+
+    Ok(product)
+}
+
+fn split_csv_line(line: &str) -> Result<Vec<String>, String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut chars = line.chars().peekable();
+    let mut quoted = false;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' if quoted => quoted = false,
+            '"' if field.trim().is_empty() => {
+                field.clear();
+                quoted = true;
+            }
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+
+    if quoted {
+        return Err("Unterminated quoted field".to_string());
+    }
+
+    fields.push(field);
+    Ok(fields)
+}
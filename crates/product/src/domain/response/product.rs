@@ -2,18 +2,28 @@ use crate::model::product::Product as ProductModel;
 use shared::utils::parse_datetime;
 
 use genproto::product::{
-    ProductResponse as ProductResponseProto,
+    ProductAttribute as ProductAttributeProto, ProductResponse as ProductResponseProto,
     ProductResponseDeleteAt as ProductResponseDeleteAtProto,
 };
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use utoipa::ToSchema;
 
 #[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
 pub struct ProductResponse {
     pub id: i32,
+    pub external_id: String,
     pub name: String,
     pub price: i64,
     pub stock: i32,
+    #[serde(default)]
+    pub images: Vec<String>,
+    #[serde(default)]
+    pub reorder_threshold: Option<i32>,
+    #[serde(default)]
+    pub category: Option<String>,
+    #[serde(default)]
+    pub attributes: BTreeMap<String, String>,
     #[serde(rename = "created_at")]
     pub created_at: Option<String>,
     #[serde(rename = "updated_at")]
@@ -25,9 +35,14 @@ impl From<ProductModel> for ProductResponse {
     fn from(value: ProductModel) -> Self {
         ProductResponse {
             id: value.product_id,
+            external_id: value.external_id.to_string(),
             name: value.name,
             price: value.price,
             stock: value.stock,
+            images: serde_json::from_value(value.images).unwrap_or_default(),
+            reorder_threshold: value.reorder_threshold,
+            category: value.category,
+            attributes: serde_json::from_value(value.attributes).unwrap_or_default(),
             created_at: value.created_at.map(|dt| dt.to_string()),
             updated_at: value.updated_at.map(|dt| dt.to_string()),
         }
@@ -39,9 +54,14 @@ impl From<ProductResponseProto> for ProductResponse {
     fn from(value: ProductResponseProto) -> Self {
         ProductResponse {
             id: value.id,
+            external_id: value.external_id,
             name: value.name,
             price: value.price,
             stock: value.stock,
+            images: value.images,
+            reorder_threshold: value.reorder_threshold,
+            category: value.category,
+            attributes: attributes_from_proto(value.attributes),
             created_at: parse_datetime(&value.created_at),
             updated_at: parse_datetime(&value.updated_at),
         }
@@ -53,9 +73,14 @@ impl From<ProductResponse> for ProductResponseProto {
     fn from(value: ProductResponse) -> Self {
         ProductResponseProto {
             id: value.id,
+            external_id: value.external_id,
             name: value.name,
             price: value.price,
             stock: value.stock,
+            images: value.images,
+            reorder_threshold: value.reorder_threshold,
+            category: value.category,
+            attributes: attributes_to_proto(value.attributes),
             created_at: value.created_at.unwrap_or_default(),
             updated_at: value.updated_at.unwrap_or_default(),
         }
@@ -65,9 +90,18 @@ impl From<ProductResponse> for ProductResponseProto {
 #[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
 pub struct ProductResponseDeleteAt {
     pub id: i32,
+    pub external_id: String,
     pub name: String,
     pub price: i64,
     pub stock: i32,
+    #[serde(default)]
+    pub images: Vec<String>,
+    #[serde(default)]
+    pub reorder_threshold: Option<i32>,
+    #[serde(default)]
+    pub category: Option<String>,
+    #[serde(default)]
+    pub attributes: BTreeMap<String, String>,
     #[serde(rename = "created_at")]
     pub created_at: Option<String>,
     #[serde(rename = "updated_at")]
@@ -81,9 +115,14 @@ impl From<ProductModel> for ProductResponseDeleteAt {
     fn from(value: ProductModel) -> Self {
         ProductResponseDeleteAt {
             id: value.product_id,
+            external_id: value.external_id.to_string(),
             name: value.name,
             price: value.price,
             stock: value.stock,
+            images: serde_json::from_value(value.images).unwrap_or_default(),
+            reorder_threshold: value.reorder_threshold,
+            category: value.category,
+            attributes: serde_json::from_value(value.attributes).unwrap_or_default(),
             created_at: value.created_at.map(|dt| dt.to_string()),
             updated_at: value.updated_at.map(|dt| dt.to_string()),
             deleted_at: value.deleted_at.map(|dt| dt.to_string()),
@@ -96,9 +135,14 @@ impl From<ProductResponseDeleteAtProto> for ProductResponseDeleteAt {
     fn from(value: ProductResponseDeleteAtProto) -> Self {
         ProductResponseDeleteAt {
             id: value.id,
+            external_id: value.external_id,
             name: value.name,
             price: value.price,
             stock: value.stock,
+            images: value.images,
+            reorder_threshold: value.reorder_threshold,
+            category: value.category,
+            attributes: attributes_from_proto(value.attributes),
             created_at: parse_datetime(&value.created_at),
             updated_at: parse_datetime(&value.updated_at),
             deleted_at: value.deleted_at.as_deref().and_then(parse_datetime),
@@ -111,12 +155,28 @@ impl From<ProductResponseDeleteAt> for ProductResponseDeleteAtProto {
     fn from(value: ProductResponseDeleteAt) -> Self {
         ProductResponseDeleteAtProto {
             id: value.id,
+            external_id: value.external_id,
             name: value.name,
             price: value.price,
             stock: value.stock,
+            images: value.images,
+            reorder_threshold: value.reorder_threshold,
+            category: value.category,
+            attributes: attributes_to_proto(value.attributes),
             created_at: value.created_at.unwrap_or_default(),
             updated_at: value.updated_at.unwrap_or_default(),
             deleted_at: Some(value.deleted_at.unwrap_or_default()),
         }
     }
 }
+
+pub fn attributes_from_proto(attributes: Vec<ProductAttributeProto>) -> BTreeMap<String, String> {
+    attributes.into_iter().map(|a| (a.key, a.value)).collect()
+}
+
+pub fn attributes_to_proto(attributes: BTreeMap<String, String>) -> Vec<ProductAttributeProto> {
+    attributes
+        .into_iter()
+        .map(|(key, value)| ProductAttributeProto { key, value })
+        .collect()
+}
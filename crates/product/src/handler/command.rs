@@ -1,19 +1,25 @@
 use crate::{
     abstract_trait::product::service::DynProductCommandService,
-    domain::requests::product::{
-        CreateProductRequest as DomainCreateProductRequest,
-        UpdateProductRequest as DomainUpdateProductRequest,
+    domain::{
+        requests::product::{
+            CreateProductRequest as DomainCreateProductRequest,
+            UpdateProductRequest as DomainUpdateProductRequest,
+        },
+        response::product::attributes_from_proto,
     },
 };
 use genproto::product::{
-    ApiResponseProduct, ApiResponseProductAll, ApiResponseProductDelete,
-    ApiResponseProductDeleteAt, CreateProductRequest, FindByIdProductRequest, UpdateProductRequest,
-    product_command_service_server::ProductCommandService,
+    ApiResponseImportProducts, ApiResponseProduct, ApiResponseProductAll, ApiResponseProductDelete,
+    ApiResponseProductDeleteAt, CreateProductRequest, FindByIdProductRequest, ImportProductsChunk,
+    UpdateProductRequest, product_command_service_server::ProductCommandService,
 };
 use shared::errors::AppErrorGrpc;
-use tonic::{Request, Response, Status};
+use tonic::{Request, Response, Status, Streaming};
 use tracing::info;
 
+/// Upper bound on a streamed CSV import, which is buffered before parsing.
+const MAX_IMPORT_BYTES: usize = 10 * 1024 * 1024;
+
 #[derive(Clone)]
 pub struct ProductCommandServiceImpl {
     pub command: DynProductCommandService,
@@ -39,6 +45,10 @@ impl ProductCommandService for ProductCommandServiceImpl {
             name: req.name,
             price: req.price,
             stock: req.stock,
+            images: req.images,
+            reorder_threshold: req.reorder_threshold,
+            category: req.category.filter(|c| !c.trim().is_empty()),
+            attributes: attributes_from_proto(req.attributes),
         };
 
         let api_response = self
@@ -73,6 +83,10 @@ impl ProductCommandService for ProductCommandServiceImpl {
             name: req.name,
             price: req.price,
             stock: req.stock,
+            images: req.images,
+            reorder_threshold: req.reorder_threshold,
+            category: req.category.filter(|c| !c.trim().is_empty()),
+            attributes: attributes_from_proto(req.attributes),
         };
 
         let api_response = self
@@ -207,4 +221,52 @@ impl ProductCommandService for ProductCommandServiceImpl {
         info!("All Products permanently deleted");
         Ok(Response::new(reply))
     }
+
+    async fn import_products(
+        &self,
+        request: Request<Streaming<ImportProductsChunk>>,
+    ) -> Result<Response<ApiResponseImportProducts>, Status> {
+        info!("Importing Products from a CSV stream");
+
+        let mut stream = request.into_inner();
+        let mut csv = Vec::new();
+        let mut strict = None;
+
+        while let Some(chunk) = stream.message().await? {
+            strict.get_or_insert(chunk.strict);
+
+            if csv.len() + chunk.data.len() > MAX_IMPORT_BYTES {
+                return Err(Status::invalid_argument(format!(
+                    "CSV import is larger than {MAX_IMPORT_BYTES} bytes"
+                )));
+            }
+
+            csv.extend_from_slice(&chunk.data);
+        }
+
+        let csv = String::from_utf8(csv)
+            .map_err(|_| Status::invalid_argument("CSV import must be UTF-8 text"))?;
+
+        let api_response = self
+            .command
+            .import_products(&csv, strict.unwrap_or(false))
+            .await
+            .map_err(AppErrorGrpc::from)?;
+
+        info!("Products imported: {}", api_response.message);
+
+        let reply = ApiResponseImportProducts {
+            status: "success".into(),
+            message: api_response.message,
+            succeeded: api_response.data.succeeded,
+            failed: api_response
+                .data
+                .failed
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        };
+
+        Ok(Response::new(reply))
+    }
 }
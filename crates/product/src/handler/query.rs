@@ -4,12 +4,18 @@ use crate::{
 };
 use genproto::product::{
     ApiResponsePaginationProduct, ApiResponsePaginationProductDeleteAt, ApiResponseProduct,
-    FindAllProductRequest, FindByIdProductRequest,
+    ApiResponsesProduct, FindAllProductRequest, FindByExternalIdProductRequest,
+    FindByIdProductRequest, FindByIdsProductRequest,
     product_query_service_server::ProductQueryService,
 };
 use shared::errors::AppErrorGrpc;
 use tonic::{Request, Response, Status};
 use tracing::info;
+use uuid::Uuid;
+
+/// Upper bound on the ids of one `FindByIds` call, keeping the `ANY($1)`
+/// array and the reply to a sane size.
+const MAX_FIND_BY_IDS: usize = 1000;
 
 #[derive(Clone)]
 pub struct ProductQueryServiceImpl {
@@ -36,6 +42,11 @@ impl ProductQueryService for ProductQueryServiceImpl {
             page: req.page,
             page_size: req.page_size,
             search: req.search,
+            min_price: req.min_price,
+            max_price: req.max_price,
+            attribute: req.attribute,
+            after: req.after,
+            sort: req.sort,
         };
 
         let api_response = self
@@ -88,6 +99,79 @@ impl ProductQueryService for ProductQueryServiceImpl {
         Ok(Response::new(reply))
     }
 
+    async fn find_by_ids(
+        &self,
+        request: Request<FindByIdsProductRequest>,
+    ) -> Result<Response<ApiResponsesProduct>, Status> {
+        info!("Handling gRPC request: Find Products by IDs");
+
+        let mut ids = request.into_inner().ids;
+        ids.sort_unstable();
+        ids.dedup();
+
+        if ids.len() > MAX_FIND_BY_IDS {
+            return Err(Status::invalid_argument(format!(
+                "At most {MAX_FIND_BY_IDS} product ids can be looked up at once"
+            )));
+        }
+
+        let api_response = self
+            .query
+            .find_by_ids(&ids)
+            .await
+            .map_err(AppErrorGrpc::from)?;
+
+        let reply = ApiResponsesProduct {
+            status: "success".into(),
+            message: api_response.message,
+            succeeded: api_response
+                .data
+                .succeeded
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            failed: api_response
+                .data
+                .failed
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        };
+
+        info!(
+            "Successfully fetched {} Products by ID, {} missing",
+            reply.succeeded.len(),
+            reply.failed.len()
+        );
+        Ok(Response::new(reply))
+    }
+
+    async fn find_by_external_id(
+        &self,
+        request: Request<FindByExternalIdProductRequest>,
+    ) -> Result<Response<ApiResponseProduct>, Status> {
+        info!("Handling gRPC request: Find Product by external ID");
+
+        let req = request.into_inner();
+
+        let external_id = Uuid::parse_str(&req.external_id)
+            .map_err(|_| Status::invalid_argument("external_id must be a valid UUID"))?;
+
+        let api_response = self
+            .query
+            .find_by_external_id(external_id)
+            .await
+            .map_err(AppErrorGrpc::from)?;
+
+        let reply = ApiResponseProduct {
+            status: "success".into(),
+            message: api_response.message,
+            data: Some(api_response.data.into()),
+        };
+
+        Ok(Response::new(reply))
+    }
+
     async fn find_by_active(
         &self,
         request: Request<FindAllProductRequest>,
@@ -100,6 +184,11 @@ impl ProductQueryService for ProductQueryServiceImpl {
             page: req.page,
             page_size: req.page_size,
             search: req.search,
+            min_price: req.min_price,
+            max_price: req.max_price,
+            attribute: String::new(),
+            after: String::new(),
+            sort: String::new(),
         };
 
         let api_response = self
@@ -139,6 +228,11 @@ impl ProductQueryService for ProductQueryServiceImpl {
             page: req.page,
             page_size: req.page_size,
             search: req.search,
+            min_price: req.min_price,
+            max_price: req.max_price,
+            attribute: String::new(),
+            after: String::new(),
+            sort: String::new(),
         };
 
         let api_response = self
@@ -165,4 +259,49 @@ impl ProductQueryService for ProductQueryServiceImpl {
         info!("Successfully fetched {} trashed Products", len);
         Ok(Response::new(reply))
     }
+
+    async fn find_low_stock(
+        &self,
+        request: Request<FindAllProductRequest>,
+    ) -> Result<Response<ApiResponsePaginationProduct>, Status> {
+        info!("Handling gRPC request: FindLowStock Products");
+
+        let req = request.into_inner();
+
+        let domain_req = FindAllProducts {
+            page: req.page,
+            page_size: req.page_size,
+            search: req.search,
+            min_price: req.min_price,
+            max_price: req.max_price,
+            attribute: String::new(),
+            after: String::new(),
+            sort: String::new(),
+        };
+
+        let api_response = self
+            .query
+            .find_low_stock(&domain_req)
+            .await
+            .map_err(AppErrorGrpc::from)?;
+
+        let data: Vec<genproto::product::ProductResponse> = api_response
+            .data
+            .into_iter()
+            .map(|item| item.into())
+            .collect();
+
+        let len = data.len();
+
+        let reply = ApiResponsePaginationProduct {
+            status: "success".into(),
+            message: api_response.message,
+            data,
+            pagination: Some(api_response.pagination.into()),
+        };
+
+        info!("Successfully fetched {} low-stock Products", len);
+
+        Ok(Response::new(reply))
+    }
 }
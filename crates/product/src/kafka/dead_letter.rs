@@ -0,0 +1,73 @@
+use crate::{config::myconfig::DeadLetterConfig, domain::event::DeadLetterEvent};
+use chrono::Utc;
+use rdkafka::{Message, message::OwnedMessage};
+use shared::{abstract_trait::DynKafka, errors::ServiceError};
+use tokio::time::Duration;
+use tracing::warn;
+
+/// Publishes order events that exhausted their retries to the dead-letter
+/// topic of the topic they were consumed from.
+pub struct DeadLetterQueue {
+    kafka: DynKafka,
+    config: DeadLetterConfig,
+}
+
+impl DeadLetterQueue {
+    pub fn new(kafka: DynKafka, config: DeadLetterConfig) -> Self {
+        Self { kafka, config }
+    }
+
+    pub fn max_retries(&self) -> u32 {
+        self.config.max_retries
+    }
+
+    /// Grows linearly with the attempt, capped at five seconds.
+    pub fn retry_delay(&self, attempt: u32) -> Duration {
+        Duration::from_millis(500 * u64::from(attempt)).min(Duration::from_secs(5))
+    }
+
+    pub async fn publish(
+        &self,
+        message: &OwnedMessage,
+        error: &str,
+        retries: u32,
+    ) -> Result<(), ServiceError> {
+        let key = message
+            .key()
+            .map(|k| String::from_utf8_lossy(k).into_owned());
+
+        let event = DeadLetterEvent {
+            source_topic: message.topic().to_string(),
+            partition: message.partition(),
+            offset: message.offset(),
+            key: key.clone(),
+            payload: message
+                .payload()
+                .map(|p| String::from_utf8_lossy(p).into_owned())
+                .unwrap_or_default(),
+            error: error.to_string(),
+            retries,
+            failed_at: Utc::now().to_rfc3339(),
+        };
+
+        let payload = serde_json::to_vec(&event)
+            .map_err(|e| ServiceError::Internal(format!("Failed to encode dead letter: {e}")))?;
+
+        let topic = self.config.topic_for(message.topic());
+
+        self.kafka
+            .publish(&topic, key.as_deref().unwrap_or_default(), &payload)
+            .await?;
+
+        warn!(
+            topic,
+            source_topic = event.source_topic,
+            partition = event.partition,
+            offset = event.offset,
+            retries,
+            "☠️ Moved failing event to dead-letter topic"
+        );
+
+        Ok(())
+    }
+}
@@ -3,15 +3,27 @@ use crate::{
     domain::event::{OrderEvent, OrderItemEvent},
 };
 use shared::errors::ServiceError;
+use std::collections::HashSet;
 use tracing::{error, info};
 
 pub struct OrderEventHandler {
     pub product_service: DynProductCommandService,
+    pub accepted_event_types: HashSet<String>,
 }
 
 impl OrderEventHandler {
-    pub fn new(product_service: DynProductCommandService) -> Self {
-        Self { product_service }
+    pub fn new(
+        product_service: DynProductCommandService,
+        accepted_event_types: HashSet<String>,
+    ) -> Self {
+        Self {
+            product_service,
+            accepted_event_types,
+        }
+    }
+
+    pub fn accepts(&self, event_type: &str) -> bool {
+        self.accepted_event_types.contains(event_type)
     }
 
     pub async fn handle_event(&self, event: OrderEvent) -> Result<(), ServiceError> {
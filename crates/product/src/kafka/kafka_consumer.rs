@@ -1,120 +1,104 @@
-use crate::{domain::event::OrderEvent, kafka::event::OrderEventHandler};
-use anyhow::Result;
+use crate::{
+    domain::event::{OrderEvent, OrderEventEnvelope},
+    kafka::{dead_letter::DeadLetterQueue, event::OrderEventHandler},
+};
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
 use rdkafka::{
-    Message,
+    ClientContext, Message, Offset, TopicPartitionList,
     config::ClientConfig,
-    consumer::{Consumer, StreamConsumer},
+    consumer::{BaseConsumer, CommitMode, Consumer, ConsumerContext, Rebalance, StreamConsumer},
+    message::OwnedMessage,
+};
+use shared::errors::ServiceError;
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc, Mutex, MutexGuard, PoisonError,
+        atomic::{AtomicBool, Ordering},
+    },
 };
-use std::sync::Arc;
 use tokio::{
-    sync::oneshot,
+    sync::{Notify, Semaphore, mpsc, oneshot},
     task::{JoinHandle, spawn},
     time::{Duration, sleep},
 };
 use tracing::{debug, error, info, warn};
 
+const PARTITION_QUEUE_SIZE: usize = 100;
+
+type OrderEventConsumer = StreamConsumer<PartitionRevoker>;
+
 pub struct KafkaEventConsumer {
-    consumer: StreamConsumer,
+    consumer: Arc<OrderEventConsumer>,
     handler: Arc<OrderEventHandler>,
+    dead_letter: Arc<DeadLetterQueue>,
+    workers: PartitionWorkers,
+    max_concurrent_handlers: usize,
 }
 
 impl KafkaEventConsumer {
-    pub fn new(brokers: &str, group_id: &str, handler: Arc<OrderEventHandler>) -> Self {
-        let consumer: StreamConsumer = ClientConfig::new()
+    pub fn new(
+        brokers: &str,
+        group_id: &str,
+        handler: Arc<OrderEventHandler>,
+        dead_letter: Arc<DeadLetterQueue>,
+        max_concurrent_handlers: usize,
+    ) -> Self {
+        let workers = PartitionWorkers::default();
+
+        let consumer: OrderEventConsumer = ClientConfig::new()
             .set("bootstrap.servers", brokers)
             .set("group.id", group_id)
             .set("enable.partition.eof", "false")
             .set("session.timeout.ms", "6000")
-            .set("enable.auto.commit", "true")
+            .set("enable.auto.commit", "false")
             .set("auto.offset.reset", "latest")
-            .create()
+            .create_with_context(PartitionRevoker {
+                workers: workers.clone(),
+            })
             .expect("Failed to create Kafka consumer");
 
-        Self { consumer, handler }
+        Self {
+            consumer: Arc::new(consumer),
+            handler,
+            dead_letter,
+            workers,
+            max_concurrent_handlers: max_concurrent_handlers.max(1),
+        }
     }
 
-    pub async fn start(self) -> Result<JoinHandle<()>> {
+    /// Consumes until the task is dropped. The task fails when a partition
+    /// could not be re-read from a failed message, so the caller restarts the
+    /// consumer from the last committed offsets.
+    pub async fn start(self) -> Result<JoinHandle<Result<()>>> {
         self.consumer
             .subscribe(&["order.created", "order.updated", "order.deleted"])?;
 
-        info!("✅ Kafka consumer started, subscribed to order events");
+        info!(
+            "✅ Kafka consumer started, subscribed to order events (max concurrent handlers: {})",
+            self.max_concurrent_handlers
+        );
 
-        let handler = self.handler;
-        let consumer = self.consumer;
+        let consumer = self.consumer.clone();
+        let dispatcher = self.into_dispatcher();
+        let stalled = dispatcher.stalled();
 
         let handle = spawn(async move {
             loop {
-                match consumer.recv().await {
-                    Err(e) => {
-                        error!("Kafka receive error: {e}");
-                        sleep(Duration::from_secs(5)).await;
-                        continue;
+                tokio::select! {
+                    _ = stalled.notified() => {
+                        return Err(anyhow!("A partition could not be re-read from a failed message"));
                     }
-                    Ok(message) => {
-                        let topic = message.topic().to_string();
-
-                        let payload = match message.payload() {
-                            None => {
-                                error!(topic, "Empty message payload");
-                                continue;
-                            }
-                            Some(p) => {
-                                debug!(topic, payload_len = p.len(), "Payload received");
-                                p
-                            }
-                        };
-
-                        let key = message
-                            .key()
-                            .and_then(|k| std::str::from_utf8(k).ok())
-                            .map(|s| s.to_string());
 
-                        if let Some(ref k) = key {
-                            debug!(topic, key = k, "Received message");
-                        } else {
-                            warn!(topic, "Message has no key");
-                        }
-
-                        let event_result: Result<OrderEvent, _> = serde_json::from_slice(payload);
-                        let event = match event_result {
-                            Ok(e) => {
-                                debug!(topic, key = ?key, event_type = ?e, "Event deserialized successfully");
-                                e
-                            }
+                    message_result = consumer.recv() => {
+                        match message_result {
                             Err(e) => {
-                                error!(
-                                    topic,
-                                    key = ?key,
-                                    "Failed to deserialize event: {e}"
-                                );
+                                error!("Kafka receive error: {e}");
+                                sleep(Duration::from_secs(5)).await;
                                 continue;
                             }
-                        };
-
-                        if let Some(key_str) = &key {
-                            let expected_id = match &event {
-                                OrderEvent::Created { order_id, .. } => *order_id,
-                                OrderEvent::Updated { order_id, .. } => *order_id,
-                                OrderEvent::Deleted { order_id, .. } => *order_id,
-                            };
-
-                            if let Ok(key_id) = key_str.parse::<i32>()
-                                && key_id != expected_id
-                            {
-                                warn!(
-                                    topic,
-                                    key,
-                                    event_order_id = expected_id,
-                                    "Key ID does not match event order_id"
-                                );
-                                debug!(topic, key_id, expected_id, "Key mismatch detected");
-                            }
-                        }
-
-                        if let Err(e) = handler.handle_event(event).await {
-                            error!(topic, key = ?key, "Failed to handle event: {e}");
-                        } else {
-                            info!(topic, key = ?key, "✅ Event processed successfully");
+                            Ok(message) => dispatcher.dispatch(message.detach()).await,
                         }
                     }
                 }
@@ -128,10 +112,14 @@ impl KafkaEventConsumer {
         self.consumer
             .subscribe(&["order.created", "order.updated", "order.deleted"])?;
 
-        info!("✅ Kafka consumer started, subscribed to order events");
+        info!(
+            "✅ Kafka consumer started, subscribed to order events (max concurrent handlers: {})",
+            self.max_concurrent_handlers
+        );
 
-        let handler = self.handler;
-        let consumer = self.consumer;
+        let consumer = self.consumer.clone();
+        let dispatcher = self.into_dispatcher();
+        let stalled = dispatcher.stalled();
 
         loop {
             tokio::select! {
@@ -141,6 +129,10 @@ impl KafkaEventConsumer {
                     break;
                 }
 
+                _ = stalled.notified() => {
+                    return Err(anyhow!("A partition could not be re-read from a failed message"));
+                }
+
                 message_result = consumer.recv() => {
                     match message_result {
                         Err(e) => {
@@ -148,73 +140,7 @@ impl KafkaEventConsumer {
                             sleep(Duration::from_secs(5)).await;
                             continue;
                         }
-                        Ok(message) => {
-                            let topic = message.topic().to_string();
-
-                            let payload = match message.payload() {
-                                None => {
-                                    error!(topic, "Empty message payload");
-                                    continue;
-                                }
-                                Some(p) => {
-                                    debug!(topic, payload_len = p.len(), "Payload received");
-                                    p
-                                }
-                            };
-
-                            let key = message
-                                .key()
-                                .and_then(|k| std::str::from_utf8(k).ok())
-                                .map(|s| s.to_string());
-
-                            if let Some(ref k) = key {
-                                debug!(topic, key = k, "Received message");
-                            } else {
-                                warn!(topic, "Message has no key");
-                            }
-
-                            let event_result: Result<OrderEvent, _> = serde_json::from_slice(payload);
-                            let event = match event_result {
-                                Ok(e) => {
-                                    debug!(topic, key = ?key, event_type = ?e, "Event deserialized successfully");
-                                    e
-                                }
-                                Err(e) => {
-                                    error!(
-                                        topic,
-                                        key = ?key,
-                                        "Failed to deserialize event: {e}"
-                                    );
-                                    continue;
-                                }
-                            };
-
-                            if let Some(key_str) = &key {
-                                let expected_id = match &event {
-                                    OrderEvent::Created { order_id, .. } => *order_id,
-                                    OrderEvent::Updated { order_id, .. } => *order_id,
-                                    OrderEvent::Deleted { order_id, .. } => *order_id,
-                                };
-
-                                if let Ok(key_id) = key_str.parse::<i32>()
-                                    && key_id != expected_id
-                                {
-                                    warn!(
-                                        topic,
-                                        key,
-                                        event_order_id = expected_id,
-                                        "Key ID does not match event order_id"
-                                    );
-                                    debug!(topic, key_id, expected_id, "Key mismatch detected");
-                                }
-                            }
-
-                            if let Err(e) = handler.handle_event(event).await {
-                                error!(topic, key = ?key, "Failed to handle event: {e}");
-                            } else {
-                                info!(topic, key = ?key, "✅ Event processed successfully");
-                            }
-                        }
+                        Ok(message) => dispatcher.dispatch(message.detach()).await,
                     }
                 }
             }
@@ -223,4 +149,732 @@ impl KafkaEventConsumer {
         info!("✅ Kafka consumer stopped gracefully");
         Ok(())
     }
+
+    fn into_dispatcher(self) -> PartitionDispatcher<KafkaPartitionProcessor> {
+        PartitionDispatcher::new(
+            Arc::new(KafkaPartitionProcessor {
+                consumer: self.consumer,
+                handler: self.handler,
+                dead_letter: self.dead_letter,
+            }),
+            self.workers,
+            self.max_concurrent_handlers,
+        )
+    }
+}
+
+/// What a partition worker does with its messages, kept apart from the worker
+/// so ordering and the concurrency cap can be checked without a broker.
+#[async_trait]
+trait PartitionProcessor: Send + Sync + 'static {
+    /// One attempt at handling `message`.
+    async fn handle(&self, message: &OwnedMessage) -> Result<(), ServiceError>;
+    fn max_retries(&self) -> u32;
+    fn retry_delay(&self, attempt: u32) -> Duration;
+    async fn dead_letter(
+        &self,
+        message: &OwnedMessage,
+        error: &str,
+        retries: u32,
+    ) -> Result<(), ServiceError>;
+    fn commit(&self, topic: &str, partition: i32, next_offset: i64);
+    /// Makes the consumer deliver the partition again from `offset`.
+    fn rewind(&self, topic: &str, partition: i32, offset: i64) -> bool;
+}
+
+struct KafkaPartitionProcessor {
+    consumer: Arc<OrderEventConsumer>,
+    handler: Arc<OrderEventHandler>,
+    dead_letter: Arc<DeadLetterQueue>,
+}
+
+#[async_trait]
+impl PartitionProcessor for KafkaPartitionProcessor {
+    async fn handle(&self, message: &OwnedMessage) -> Result<(), ServiceError> {
+        process_message(&self.handler, message).await
+    }
+
+    fn max_retries(&self) -> u32 {
+        self.dead_letter.max_retries()
+    }
+
+    fn retry_delay(&self, attempt: u32) -> Duration {
+        self.dead_letter.retry_delay(attempt)
+    }
+
+    async fn dead_letter(
+        &self,
+        message: &OwnedMessage,
+        error: &str,
+        retries: u32,
+    ) -> Result<(), ServiceError> {
+        self.dead_letter.publish(message, error, retries).await
+    }
+
+    fn commit(&self, topic: &str, partition: i32, next_offset: i64) {
+        let mut tpl = TopicPartitionList::new();
+
+        if let Err(e) = tpl.add_partition_offset(topic, partition, Offset::Offset(next_offset)) {
+            error!(topic, partition, "Failed to build commit offset: {e}");
+            return;
+        }
+
+        if let Err(e) = self.consumer.commit(&tpl, CommitMode::Async) {
+            error!(topic, partition, "Failed to commit offset: {e}");
+        }
+    }
+
+    fn rewind(&self, topic: &str, partition: i32, offset: i64) -> bool {
+        match self.consumer.seek(
+            topic,
+            partition,
+            Offset::Offset(offset),
+            Duration::from_secs(5),
+        ) {
+            Ok(()) => true,
+            Err(e) => {
+                error!(topic, partition, offset, "Failed to seek partition: {e}");
+                false
+            }
+        }
+    }
+}
+
+struct PartitionWorker {
+    sender: mpsc::Sender<OwnedMessage>,
+    revoked: Arc<AtomicBool>,
+}
+
+/// Partition workers by topic partition, shared between the dispatcher and
+/// the rebalance callback.
+#[derive(Clone, Default)]
+struct PartitionWorkers {
+    workers: Arc<Mutex<HashMap<(String, i32), PartitionWorker>>>,
+    /// Signalled when a partition can't be re-read from a failed message.
+    stalled: Arc<Notify>,
+}
+
+impl PartitionWorkers {
+    fn lock(&self) -> MutexGuard<'_, HashMap<(String, i32), PartitionWorker>> {
+        self.workers.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Stops the worker of a partition after the message it is on; whatever
+    /// it still has queued is left to the partition's next owner.
+    fn revoke(&self, topic: &str, partition: i32) {
+        if let Some(worker) = self.lock().remove(&(topic.to_string(), partition)) {
+            worker.revoked.store(true, Ordering::Release);
+        }
+    }
+
+    fn revoke_all(&self) {
+        for (_, worker) in self.lock().drain() {
+            worker.revoked.store(true, Ordering::Release);
+        }
+    }
+}
+
+/// Stops the workers of partitions a rebalance takes away, so messages they
+/// still hold aren't handled a second time by the new owner.
+struct PartitionRevoker {
+    workers: PartitionWorkers,
+}
+
+impl ClientContext for PartitionRevoker {}
+
+impl ConsumerContext for PartitionRevoker {
+    fn pre_rebalance(&self, _consumer: &BaseConsumer<Self>, rebalance: &Rebalance<'_>) {
+        if let Rebalance::Revoke(partitions) = rebalance {
+            for elem in partitions.elements() {
+                info!(
+                    topic = elem.topic(),
+                    partition = elem.partition(),
+                    "Partition revoked, stopping its worker"
+                );
+                self.workers.revoke(elem.topic(), elem.partition());
+            }
+        }
+    }
+}
+
+/// Routes each message to a worker owned by its topic partition. Workers run
+/// messages of one partition strictly in order, while the shared semaphore
+/// caps how many handlers run at once across all partitions.
+struct PartitionDispatcher<P> {
+    processor: Arc<P>,
+    semaphore: Arc<Semaphore>,
+    workers: PartitionWorkers,
+}
+
+impl<P: PartitionProcessor> PartitionDispatcher<P> {
+    fn new(processor: Arc<P>, workers: PartitionWorkers, max_concurrent_handlers: usize) -> Self {
+        Self {
+            processor,
+            semaphore: Arc::new(Semaphore::new(max_concurrent_handlers)),
+            workers,
+        }
+    }
+
+    fn stalled(&self) -> Arc<Notify> {
+        self.workers.stalled.clone()
+    }
+
+    async fn dispatch(&self, message: OwnedMessage) {
+        let key = (message.topic().to_string(), message.partition());
+
+        let sender = {
+            let mut workers = self.workers.lock();
+
+            match workers.get(&key) {
+                Some(worker) if !worker.sender.is_closed() => worker.sender.clone(),
+                _ => {
+                    let (tx, rx) = mpsc::channel(PARTITION_QUEUE_SIZE);
+                    let revoked = Arc::new(AtomicBool::new(false));
+
+                    spawn(run_partition_worker(
+                        self.processor.clone(),
+                        self.semaphore.clone(),
+                        self.workers.stalled.clone(),
+                        key.0.clone(),
+                        key.1,
+                        revoked.clone(),
+                        rx,
+                    ));
+
+                    workers.insert(
+                        key.clone(),
+                        PartitionWorker {
+                            sender: tx.clone(),
+                            revoked,
+                        },
+                    );
+                    tx
+                }
+            }
+        };
+
+        if let Err(e) = sender.send(message).await {
+            error!(
+                topic = key.0,
+                partition = key.1,
+                "Failed to dispatch message to partition worker: {e}"
+            );
+        }
+    }
+}
+
+impl<P> Drop for PartitionDispatcher<P> {
+    fn drop(&mut self) {
+        self.workers.revoke_all();
+    }
+}
+
+async fn run_partition_worker<P: PartitionProcessor>(
+    processor: Arc<P>,
+    semaphore: Arc<Semaphore>,
+    stalled: Arc<Notify>,
+    topic: String,
+    partition: i32,
+    revoked: Arc<AtomicBool>,
+    mut rx: mpsc::Receiver<OwnedMessage>,
+) {
+    // A message that could be neither handled nor dead-lettered is read again
+    // from the broker; everything queued behind it is dropped until it comes
+    // back, so no later offset is ever committed past it.
+    let mut rewound_to: Option<i64> = None;
+
+    while let Some(message) = rx.recv().await {
+        if revoked.load(Ordering::Acquire) {
+            break;
+        }
+
+        let offset = message.offset();
+
+        match rewound_to {
+            Some(failed) if offset != failed => continue,
+            Some(_) => rewound_to = None,
+            None => {}
+        }
+
+        let Some(handled) = handle_with_retries(processor.as_ref(), &semaphore, &message).await
+        else {
+            break;
+        };
+
+        if revoked.load(Ordering::Acquire) {
+            break;
+        }
+
+        if handled {
+            processor.commit(&topic, partition, offset + 1);
+            continue;
+        }
+
+        let delay = processor.retry_delay(processor.max_retries() + 1);
+        warn!(
+            topic,
+            partition, offset, "Reading partition again from failed message in {delay:?}"
+        );
+        sleep(delay).await;
+
+        rewound_to = Some(offset);
+
+        if !processor.rewind(&topic, partition, offset) {
+            stalled.notify_one();
+        }
+    }
+}
+
+/// Runs the handler up to `max_retries + 1` times, then hands the message to
+/// the dead-letter queue. Returns whether its offset may be committed, or
+/// `None` once the semaphore is closed.
+async fn handle_with_retries<P: PartitionProcessor>(
+    processor: &P,
+    semaphore: &Arc<Semaphore>,
+    message: &OwnedMessage,
+) -> Option<bool> {
+    let mut retries = 0;
+
+    loop {
+        let permit = semaphore.clone().acquire_owned().await.ok()?;
+        let result = processor.handle(message).await;
+        drop(permit);
+
+        let error = match result {
+            Ok(()) => return Some(true),
+            Err(e) => e,
+        };
+
+        if retries < processor.max_retries() {
+            retries += 1;
+            let delay = processor.retry_delay(retries);
+            warn!(
+                topic = message.topic(),
+                partition = message.partition(),
+                offset = message.offset(),
+                retries,
+                "Retrying failed event in {delay:?}"
+            );
+            sleep(delay).await;
+            continue;
+        }
+
+        return match processor
+            .dead_letter(message, &error.to_string(), retries)
+            .await
+        {
+            Ok(()) => Some(true),
+            Err(e) => {
+                error!(
+                    topic = message.topic(),
+                    partition = message.partition(),
+                    offset = message.offset(),
+                    "Failed to publish to dead-letter topic: {e}"
+                );
+                Some(false)
+            }
+        };
+    }
+}
+
+/// Fails only when the handler failed; malformed messages are logged and
+/// treated as handled so they don't block the partition.
+async fn process_message(
+    handler: &OrderEventHandler,
+    message: &OwnedMessage,
+) -> Result<(), ServiceError> {
+    let topic = message.topic().to_string();
+
+    let payload = match message.payload() {
+        None => {
+            error!(topic, "Empty message payload");
+            return Ok(());
+        }
+        Some(p) => {
+            debug!(topic, payload_len = p.len(), "Payload received");
+            p
+        }
+    };
+
+    let key = message
+        .key()
+        .and_then(|k| std::str::from_utf8(k).ok())
+        .map(|s| s.to_string());
+
+    if let Some(ref k) = key {
+        debug!(topic, key = k, "Received message");
+    } else {
+        warn!(topic, "Message has no key");
+    }
+
+    match serde_json::from_slice::<OrderEventEnvelope>(payload) {
+        Ok(envelope) if !handler.accepts(&envelope.event_type) => {
+            debug!(
+                topic,
+                key = ?key,
+                event_type = envelope.event_type,
+                "Skipping event type not in accepted list"
+            );
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    let event_result: Result<OrderEvent, _> = serde_json::from_slice(payload);
+    let event = match event_result {
+        Ok(e) => {
+            debug!(topic, key = ?key, event_type = ?e, "Event deserialized successfully");
+            e
+        }
+        Err(e) => {
+            error!(
+                topic,
+                key = ?key,
+                "Failed to deserialize event: {e}"
+            );
+            return Ok(());
+        }
+    };
+
+    if let Some(key_str) = &key {
+        let expected_id = match &event {
+            OrderEvent::Created { order_id, .. } => *order_id,
+            OrderEvent::Updated { order_id, .. } => *order_id,
+            OrderEvent::Deleted { order_id, .. } => *order_id,
+        };
+
+        if let Ok(key_id) = key_str.parse::<i32>()
+            && key_id != expected_id
+        {
+            warn!(
+                topic,
+                key,
+                event_order_id = expected_id,
+                "Key ID does not match event order_id"
+            );
+            debug!(topic, key_id, expected_id, "Key mismatch detected");
+        }
+    }
+
+    match handler.handle_event(event).await {
+        Ok(()) => {
+            info!(topic, key = ?key, "✅ Event processed successfully");
+            Ok(())
+        }
+        Err(e) => {
+            error!(topic, key = ?key, "Failed to handle event: {e}");
+            Err(e)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        abstract_trait::product::service::ProductCommandServiceTrait,
+        domain::{
+            requests::product::{CreateProductRequest, UpdateProductRequest},
+            response::{
+                api::ApiResponse,
+                product::{ProductResponse, ProductResponseDeleteAt},
+            },
+        },
+    };
+    use rdkafka::Timestamp;
+    use serde_json::{Value, json};
+    use shared::utils::BatchResponse;
+    use std::{collections::HashSet, sync::atomic::AtomicUsize};
+    use tokio::time::timeout;
+
+    const TOPIC: &str = "order.created";
+
+    /// Records what the workers do; the dead-letter publish of each offset in
+    /// `dead_letter_fails` fails once.
+    #[derive(Default)]
+    struct RecordingProcessor {
+        running: AtomicUsize,
+        peak: AtomicUsize,
+        handled: Mutex<Vec<(i32, i64)>>,
+        committed: Mutex<Vec<(i32, i64)>>,
+        rewound: Mutex<Vec<(i32, i64)>>,
+        dead_letter_fails: Mutex<HashSet<i64>>,
+        handler_fails: HashSet<i64>,
+    }
+
+    #[async_trait]
+    impl PartitionProcessor for RecordingProcessor {
+        async fn handle(&self, message: &OwnedMessage) -> Result<(), ServiceError> {
+            let running = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(running, Ordering::SeqCst);
+
+            sleep(Duration::from_millis(5)).await;
+
+            self.handled
+                .lock()
+                .unwrap()
+                .push((message.partition(), message.offset()));
+            self.running.fetch_sub(1, Ordering::SeqCst);
+
+            if self.handler_fails.contains(&message.offset()) {
+                return Err(ServiceError::Internal("handler failed".into()));
+            }
+            Ok(())
+        }
+
+        fn max_retries(&self) -> u32 {
+            0
+        }
+
+        fn retry_delay(&self, _attempt: u32) -> Duration {
+            Duration::ZERO
+        }
+
+        async fn dead_letter(
+            &self,
+            message: &OwnedMessage,
+            _error: &str,
+            _retries: u32,
+        ) -> Result<(), ServiceError> {
+            if self
+                .dead_letter_fails
+                .lock()
+                .unwrap()
+                .remove(&message.offset())
+            {
+                return Err(ServiceError::Internal("dead-letter topic down".into()));
+            }
+            Ok(())
+        }
+
+        fn commit(&self, _topic: &str, partition: i32, next_offset: i64) {
+            self.committed
+                .lock()
+                .unwrap()
+                .push((partition, next_offset));
+        }
+
+        fn rewind(&self, _topic: &str, partition: i32, offset: i64) -> bool {
+            self.rewound.lock().unwrap().push((partition, offset));
+            true
+        }
+    }
+
+    fn message(partition: i32, offset: i64) -> OwnedMessage {
+        OwnedMessage::new(
+            None,
+            None,
+            TOPIC.to_string(),
+            Timestamp::NotAvailable,
+            partition,
+            offset,
+            None,
+        )
+    }
+
+    async fn wait_for_commits(processor: &RecordingProcessor, count: usize) {
+        timeout(Duration::from_secs(5), async {
+            while processor.committed.lock().unwrap().len() < count {
+                sleep(Duration::from_millis(1)).await;
+            }
+        })
+        .await
+        .expect("workers did not commit in time");
+    }
+
+    fn of_partition(entries: &[(i32, i64)], partition: i32) -> Vec<i64> {
+        entries
+            .iter()
+            .filter(|(p, _)| *p == partition)
+            .map(|(_, offset)| *offset)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn caps_concurrency_and_keeps_partition_order() {
+        let processor = Arc::new(RecordingProcessor::default());
+        let dispatcher =
+            PartitionDispatcher::new(processor.clone(), PartitionWorkers::default(), 2);
+
+        for offset in 0..5 {
+            for partition in 0..4 {
+                dispatcher.dispatch(message(partition, offset)).await;
+            }
+        }
+
+        wait_for_commits(&processor, 20).await;
+
+        assert_eq!(processor.peak.load(Ordering::SeqCst), 2);
+
+        let handled = processor.handled.lock().unwrap();
+        let committed = processor.committed.lock().unwrap();
+        for partition in 0..4 {
+            assert_eq!(of_partition(&handled, partition), vec![0, 1, 2, 3, 4]);
+            assert_eq!(of_partition(&committed, partition), vec![1, 2, 3, 4, 5]);
+        }
+    }
+
+    #[tokio::test]
+    async fn rereads_partition_from_message_that_could_not_be_dead_lettered() {
+        let processor = Arc::new(RecordingProcessor {
+            handler_fails: HashSet::from([1]),
+            dead_letter_fails: Mutex::new(HashSet::from([1])),
+            ..Default::default()
+        });
+        let dispatcher =
+            PartitionDispatcher::new(processor.clone(), PartitionWorkers::default(), 1);
+
+        for offset in 0..3 {
+            dispatcher.dispatch(message(0, offset)).await;
+        }
+
+        // The broker redelivers from the rewound offset.
+        for offset in 1..3 {
+            dispatcher.dispatch(message(0, offset)).await;
+        }
+
+        wait_for_commits(&processor, 3).await;
+
+        assert_eq!(*processor.rewound.lock().unwrap(), vec![(0, 1)]);
+        assert_eq!(
+            of_partition(&processor.handled.lock().unwrap(), 0),
+            vec![0, 1, 1, 2]
+        );
+        assert_eq!(
+            of_partition(&processor.committed.lock().unwrap(), 0),
+            vec![1, 2, 3]
+        );
+    }
+
+    /// Records the stock changes the event handler asks for, as signed
+    /// quantities.
+    #[derive(Default)]
+    struct StockLedger(Mutex<Vec<(i32, i32)>>);
+
+    impl StockLedger {
+        fn record(&self, product_id: i32, change: i32) -> ApiResponse<ProductResponse> {
+            self.0.lock().unwrap().push((product_id, change));
+            ApiResponse {
+                status: "success".to_string(),
+                message: "Stock updated".to_string(),
+                data: ProductResponse {
+                    id: product_id,
+                    external_id: String::new(),
+                    name: String::new(),
+                    price: 0,
+                    stock: 0,
+                    images: Vec::new(),
+                    reorder_threshold: None,
+                    category: None,
+                    attributes: Default::default(),
+                    created_at: None,
+                    updated_at: None,
+                },
+            }
+        }
+    }
+
+    #[async_trait]
+    impl ProductCommandServiceTrait for StockLedger {
+        async fn create_product(
+            &self,
+            _req: &CreateProductRequest,
+        ) -> Result<ApiResponse<ProductResponse>, ServiceError> {
+            unimplemented!()
+        }
+        async fn update_product(
+            &self,
+            _req: &UpdateProductRequest,
+        ) -> Result<ApiResponse<ProductResponse>, ServiceError> {
+            unimplemented!()
+        }
+        async fn increasing_stock(
+            &self,
+            product_id: i32,
+            qty: i32,
+        ) -> Result<ApiResponse<ProductResponse>, ServiceError> {
+            Ok(self.record(product_id, qty))
+        }
+        async fn decreasing_stock(
+            &self,
+            product_id: i32,
+            qty: i32,
+        ) -> Result<ApiResponse<ProductResponse>, ServiceError> {
+            Ok(self.record(product_id, -qty))
+        }
+        async fn trash_product(
+            &self,
+            _id: i32,
+        ) -> Result<ApiResponse<ProductResponseDeleteAt>, ServiceError> {
+            unimplemented!()
+        }
+        async fn restore_product(
+            &self,
+            _id: i32,
+        ) -> Result<ApiResponse<ProductResponseDeleteAt>, ServiceError> {
+            unimplemented!()
+        }
+        async fn delete_product(&self, _id: i32) -> Result<ApiResponse<()>, ServiceError> {
+            unimplemented!()
+        }
+        async fn restore_all_product(&self) -> Result<ApiResponse<()>, ServiceError> {
+            unimplemented!()
+        }
+        async fn delete_all_product(&self) -> Result<ApiResponse<()>, ServiceError> {
+            unimplemented!()
+        }
+        async fn import_products(
+            &self,
+            _csv: &str,
+            _strict: bool,
+        ) -> Result<ApiResponse<BatchResponse<i32>>, ServiceError> {
+            unimplemented!()
+        }
+    }
+
+    fn event_message(event: &Value) -> OwnedMessage {
+        OwnedMessage::new(
+            Some(event.to_string().into_bytes()),
+            Some(event["order_id"].to_string().into_bytes()),
+            TOPIC.to_string(),
+            Timestamp::NotAvailable,
+            0,
+            0,
+            None,
+        )
+    }
+
+    #[tokio::test]
+    async fn event_types_outside_the_allow_list_are_skipped_as_handled() {
+        let ledger = Arc::new(StockLedger::default());
+        let handler = OrderEventHandler::new(ledger.clone(), HashSet::from(["Created".into()]));
+
+        // Handled without touching stock, so the worker commits past them
+        // instead of retrying or dead-lettering.
+        let deleted = json!({
+            "type": "Deleted",
+            "order_id": 1,
+            "deleted_items": [{ "product_id": 4, "quantity": 2 }],
+        });
+        let unknown = json!({ "type": "Refunded", "order_id": 1 });
+        for skipped in [deleted, unknown] {
+            assert!(
+                process_message(&handler, &event_message(&skipped))
+                    .await
+                    .is_ok()
+            );
+        }
+        assert!(ledger.0.lock().unwrap().is_empty());
+
+        let created = json!({
+            "type": "Created",
+            "order_id": 2,
+            "user_id": 9,
+            "items": [{ "product_id": 4, "quantity": 3 }],
+        });
+        process_message(&handler, &event_message(&created))
+            .await
+            .unwrap();
+        assert_eq!(*ledger.0.lock().unwrap(), [(4, -3)]);
+    }
 }
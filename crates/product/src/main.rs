@@ -1,22 +1,37 @@
 use anyhow::{Context, Result};
-use genproto::product::{
-    product_command_service_server::ProductCommandServiceServer,
-    product_query_service_server::ProductQueryServiceServer,
+use genproto::{
+    health::health_service_server::HealthServiceServer,
+    product::{
+        product_command_service_server::ProductCommandServiceServer,
+        product_query_service_server::ProductQueryServiceServer,
+    },
 };
 use product::{
     config::{myconfig::Config, server_config::ServerConfig},
     handler::{command::ProductCommandServiceImpl, query::ProductQueryServiceImpl},
-    kafka::{event::OrderEventHandler, kafka_consumer::KafkaEventConsumer},
+    kafka::{
+        dead_letter::DeadLetterQueue, event::OrderEventHandler, kafka_consumer::KafkaEventConsumer,
+    },
     state::AppState,
 };
+use prometheus_client::registry::Registry;
 use shared::{
-    config::ConnectionManager,
-    utils::{Telemetry, init_logger},
+    abstract_trait::DynKafka,
+    config::{ConnectionManager, Kafka},
+    utils::{
+        ConnectionLimit, HealthGrpcService, InFlightLayer, InFlightRequests, ReadOnlyLayer,
+        RequestIdLayer, RequiredMetadata, RestartBackoff, SlowRequestLayer, Telemetry, init_logger,
+        spawn_metrics_server,
+    },
+    with_grpc_compression,
 };
 use sqlx::{Pool, Postgres};
 use std::sync::Arc;
 use tokio::sync::broadcast;
-use tonic::transport::Server;
+use tonic::{
+    service::InterceptorLayer,
+    transport::{Server, ServerTlsConfig},
+};
 use tracing::{error, info, warn};
 
 #[tokio::main]
@@ -71,20 +86,21 @@ async fn setup() -> Result<(Config, ServerConfig, Arc<AppState>, Telemetry)> {
 
     info!("🚀 Starting Product Service initialization...");
 
-    let db_pool = ConnectionManager::new_pool(
+    let db_pools = ConnectionManager::new_pools(
         &server_config.database_url,
+        server_config.database_read_url.as_deref(),
         config.db_min_conn,
         config.db_max_conn,
     )
     .await
     .context("Failed to initialize database pool")?;
 
-    run_migrations(&db_pool)
+    run_migrations(&db_pools.write, server_config.run_migrations)
         .await
         .context("Failed to run database migrations")?;
 
     let state = Arc::new(
-        AppState::new(db_pool)
+        AppState::new(db_pools.clone(), config.clone())
             .await
             .context("Failed to create AppState")?,
     );
@@ -110,18 +126,38 @@ async fn run_servers(
     let query_service =
         ProductQueryServiceImpl::new(Arc::new(state.di_container.product_query.clone()));
 
-    let handler = Arc::new(OrderEventHandler::new(Arc::new(
-        state.di_container.product_command.clone(),
-    )));
+    let handler = Arc::new(OrderEventHandler::new(
+        Arc::new(state.di_container.product_command.clone()),
+        config.kafka_accepted_event_types.clone(),
+    ));
+
+    let dead_letter = Arc::new(DeadLetterQueue::new(
+        Arc::new(Kafka::new(&config.kafka_broker)) as DynKafka,
+        config.kafka_dead_letter.clone(),
+    ));
 
     let kafka_broker = config.kafka_broker.clone();
-    let kafka_handle = spawn_kafka_consumer(kafka_broker, handler, shutdown_tx.clone());
+    let kafka_handle = spawn_kafka_consumer(
+        kafka_broker,
+        config.kafka_max_concurrent_handlers,
+        handler,
+        dead_letter,
+        shutdown_tx.clone(),
+    );
 
     let grpc_addr = server_config.grpc_addr;
+    let grpc_tls = server_config.tls.clone();
+    let in_flight = InFlightRequests::default();
+    let mut registry = Registry::default();
+    in_flight.register(&mut registry);
+    spawn_metrics_server(server_config.metrics_addr, registry, state.db_pools.clone());
+
     let grpc_handle = run_grpc_server(
         command_service,
         query_service,
         grpc_addr,
+        grpc_tls,
+        in_flight,
         shutdown_tx.clone(),
     );
 
@@ -135,17 +171,27 @@ async fn run_servers(
 
 fn spawn_kafka_consumer(
     kafka_broker: String,
+    max_concurrent_handlers: usize,
     handler: Arc<OrderEventHandler>,
+    dead_letter: Arc<DeadLetterQueue>,
     shutdown_tx: broadcast::Sender<()>,
 ) -> tokio::task::JoinHandle<()> {
     tokio::spawn(async move {
         let shutdown_rx = shutdown_tx.subscribe();
 
+        let mut backoff = RestartBackoff::default();
+
         loop {
             info!("🔄 Starting Kafka consumer...");
-
-            let consumer =
-                KafkaEventConsumer::new(&kafka_broker, "product-service-group", handler.clone());
+            backoff.mark_started();
+
+            let consumer = KafkaEventConsumer::new(
+                &kafka_broker,
+                "product-service-group",
+                handler.clone(),
+                dead_letter.clone(),
+                max_concurrent_handlers,
+            );
             let mut consumer_shutdown_rx = shutdown_rx.resubscribe();
 
             let kafka_task = tokio::spawn(async move {
@@ -156,6 +202,7 @@ fn spawn_kafka_consumer(
                                 handle.await
                                     .map_err(|e| anyhow::anyhow!(e))
                                     .context("Kafka consumer task failed")
+                                    .and_then(|result| result)
                             }
                             Err(e) => {
                                 Err(e).context("Failed to start Kafka consumer")
@@ -175,19 +222,21 @@ fn spawn_kafka_consumer(
                     break;
                 }
                 Ok(Err(e)) => {
-                    error!("💀 Kafka consumer error: {e}. Restarting in 5s...");
+                    error!("💀 Kafka consumer error: {e}");
                 }
                 Err(e) => {
                     if e.is_cancelled() {
                         info!("Kafka consumer task cancelled during shutdown");
                         break;
                     } else {
-                        error!("💀 Kafka task panicked: {e}. Restarting in 5s...");
+                        error!("💀 Kafka task panicked: {e}");
                     }
                 }
             }
 
-            tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+            let delay = backoff.next_delay();
+            info!("🔄 Restarting Kafka consumer in {delay:?}...");
+            tokio::time::sleep(delay).await;
         }
     })
 }
@@ -196,13 +245,18 @@ fn run_grpc_server(
     command_service: ProductCommandServiceImpl,
     query_service: ProductQueryServiceImpl,
     grpc_addr: std::net::SocketAddr,
+    grpc_tls: Option<ServerTlsConfig>,
+    in_flight: InFlightRequests,
     shutdown_tx: broadcast::Sender<()>,
 ) -> tokio::task::JoinHandle<()> {
     tokio::spawn(async move {
         let shutdown_rx = shutdown_tx.subscribe();
 
+        let mut backoff = RestartBackoff::default();
+
         loop {
             info!("📡 Attempting to start gRPC server on {grpc_addr}");
+            backoff.mark_started();
 
             let server_shutdown_rx = shutdown_rx.resubscribe();
 
@@ -210,6 +264,8 @@ fn run_grpc_server(
                 command_service.clone(),
                 query_service.clone(),
                 grpc_addr,
+                grpc_tls.clone(),
+                in_flight.clone(),
                 server_shutdown_rx,
             )
             .await
@@ -219,8 +275,9 @@ fn run_grpc_server(
                     break;
                 }
                 Err(e) => {
-                    error!("❌ gRPC server failed: {e}. Restarting in 5s...");
-                    tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+                    let delay = backoff.next_delay();
+                    error!("❌ gRPC server failed: {e}. Restarting in {delay:?}...");
+                    tokio::time::sleep(delay).await;
                 }
             }
         }
@@ -247,6 +304,8 @@ async fn start_grpc_server(
     command_service: ProductCommandServiceImpl,
     query_service: ProductQueryServiceImpl,
     addr: std::net::SocketAddr,
+    tls: Option<ServerTlsConfig>,
+    in_flight: InFlightRequests,
     mut shutdown_rx: broadcast::Receiver<()>,
 ) -> Result<()> {
     info!("📡 Starting gRPC server on {addr}");
@@ -256,10 +315,35 @@ async fn start_grpc_server(
         info!("gRPC server received shutdown signal");
     };
 
-    Server::builder()
-        .add_service(ProductCommandServiceServer::new(command_service))
-        .add_service(ProductQueryServiceServer::new(query_service))
-        .serve_with_shutdown(addr, shutdown_future)
+    let mut server = Server::builder();
+
+    if let Some(tls) = tls {
+        server = server
+            .tls_config(tls)
+            .context("Invalid gRPC server TLS configuration")?;
+    }
+
+    let incoming = ConnectionLimit::from_env()
+        .bind(addr)
+        .await
+        .with_context(|| format!("Failed to bind gRPC server on {addr}"))?;
+
+    server
+        .layer(InFlightLayer::new(in_flight))
+        .layer(RequestIdLayer)
+        .layer(SlowRequestLayer::from_env())
+        .layer(InterceptorLayer::new(RequiredMetadata::from_env()))
+        .layer(ReadOnlyLayer::from_env())
+        .add_service(with_grpc_compression!(ProductCommandServiceServer::new(
+            command_service
+        )))
+        .add_service(with_grpc_compression!(ProductQueryServiceServer::new(
+            query_service
+        )))
+        .add_service(with_grpc_compression!(HealthServiceServer::new(
+            HealthGrpcService::new("product-service", env!("CARGO_PKG_VERSION"))
+        )))
+        .serve_with_incoming_shutdown(incoming, shutdown_future)
         .await
         .with_context(|| format!("gRPC server failed to start on {addr}"))
 }
@@ -287,7 +371,19 @@ async fn shutdown(telemetry: Telemetry, server_handles: ServerHandles) {
     info!("✅ Product Service shutdown complete.");
 }
 
-pub async fn run_migrations(pool: &Pool<Postgres>) -> anyhow::Result<()> {
-    sqlx::migrate!("./migrations").run(pool).await?;
+/// Does nothing when AUTO_MIGRATE is false, for deployments that migrate
+/// out of band. Replicas booting together are serialized by the migrator's
+/// Postgres advisory lock, so only one of them applies pending migrations.
+pub async fn run_migrations(pool: &Pool<Postgres>, enabled: bool) -> anyhow::Result<()> {
+    if !enabled {
+        info!("⏭️ AUTO_MIGRATE is disabled, skipping database migrations");
+        return Ok(());
+    }
+
+    let mut migrator = sqlx::migrate!("./migrations");
+    migrator.set_locking(true);
+
+    migrator.run(pool).await?;
+
     Ok(())
 }
@@ -1,13 +1,19 @@
 use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use uuid::Uuid;
 
 #[derive(Debug, Serialize, Deserialize, FromRow)]
 pub struct Product {
     pub product_id: i32,
+    pub external_id: Uuid,
     pub name: String,
     pub price: i64,
     pub stock: i32,
+    pub images: serde_json::Value,
+    pub reorder_threshold: Option<i32>,
+    pub category: Option<String>,
+    pub attributes: serde_json::Value,
     pub created_at: Option<NaiveDateTime>,
     pub updated_at: Option<NaiveDateTime>,
     pub deleted_at: Option<NaiveDateTime>,
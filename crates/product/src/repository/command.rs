@@ -7,6 +7,9 @@ use async_trait::async_trait;
 use shared::{config::ConnectionPool, errors::RepositoryError};
 use tracing::{error, info};
 
+/// Rows sent per `INSERT ... SELECT FROM UNNEST` statement.
+const IMPORT_BATCH_SIZE: usize = 500;
+
 pub struct ProductCommandRepository {
     db: ConnectionPool,
 }
@@ -28,13 +31,17 @@ impl ProductCommandRepositoryTrait for ProductCommandRepository {
         let result = sqlx::query_as!(
             ProductModel,
             r#"
-            INSERT INTO products (name, price, stock, created_at, updated_at)
-            VALUES ($1, $2, $3, current_timestamp, current_timestamp)
-            RETURNING product_id, name, price, stock, created_at, updated_at, deleted_at
+            INSERT INTO products (name, price, stock, images, reorder_threshold, category, attributes, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, current_timestamp, current_timestamp)
+            RETURNING product_id, external_id, name, price, stock, images, reorder_threshold, category, attributes, created_at, updated_at, deleted_at
             "#,
             product.name,
             product.price,
-            product.stock
+            product.stock,
+            serde_json::json!(product.images),
+            product.reorder_threshold,
+            product.category,
+            serde_json::json!(product.attributes)
         )
         .fetch_one(&mut *conn)
         .await
@@ -63,14 +70,22 @@ impl ProductCommandRepositoryTrait for ProductCommandRepository {
             SET name = $2,
                 price = $3,
                 stock = $4,
+                images = $5,
+                reorder_threshold = $6,
+                category = $7,
+                attributes = $8,
                 updated_at = current_timestamp
             WHERE product_id = $1
-            RETURNING product_id, name, price, stock, created_at, updated_at, deleted_at
+            RETURNING product_id, external_id, name, price, stock, images, reorder_threshold, category, attributes, created_at, updated_at, deleted_at
             "#,
             product.id,
             product.name,
             product.price,
-            product.stock
+            product.stock,
+            serde_json::json!(product.images),
+            product.reorder_threshold,
+            product.category,
+            serde_json::json!(product.attributes)
         )
         .fetch_one(&mut *conn)
         .await
@@ -97,7 +112,7 @@ impl ProductCommandRepositoryTrait for ProductCommandRepository {
         SET stock = stock + $1,
             updated_at = current_timestamp
         WHERE product_id = $2
-        RETURNING product_id, name, price, stock, created_at, updated_at, deleted_at
+        RETURNING product_id, external_id, name, price, stock, images, reorder_threshold, category, attributes, created_at, updated_at, deleted_at
         "#,
             qty,
             product_id
@@ -123,7 +138,7 @@ impl ProductCommandRepositoryTrait for ProductCommandRepository {
         &self,
         product_id: i32,
         qty: i32,
-    ) -> Result<ProductModel, RepositoryError> {
+    ) -> Result<Option<ProductModel>, RepositoryError> {
         let mut conn = self.db.acquire().await.map_err(RepositoryError::from)?;
 
         let result = sqlx::query_as!(
@@ -133,12 +148,13 @@ impl ProductCommandRepositoryTrait for ProductCommandRepository {
         SET stock = stock - $1,
             updated_at = current_timestamp
         WHERE product_id = $2
-        RETURNING product_id, name, price, stock, created_at, updated_at, deleted_at
+          AND stock >= $1
+        RETURNING product_id, external_id, name, price, stock, images, reorder_threshold, category, attributes, created_at, updated_at, deleted_at
         "#,
             qty,
             product_id
         )
-        .fetch_one(&mut *conn)
+        .fetch_optional(&mut *conn)
         .await
         .map_err(|err| {
             error!(
@@ -148,13 +164,38 @@ impl ProductCommandRepositoryTrait for ProductCommandRepository {
             RepositoryError::from(err)
         })?;
 
-        info!(
-            "✅ Decreased stock product ID {} (new stock: {})",
-            result.product_id, result.stock
-        );
+        match &result {
+            Some(product) => info!(
+                "✅ Decreased stock product ID {} (new stock: {})",
+                product.product_id, product.stock
+            ),
+            None => info!(
+                "⚠️ Stock not decreased for product ID {product_id}: insufficient stock or not found"
+            ),
+        }
+
         Ok(result)
     }
 
+    async fn find_stock(&self, product_id: i32) -> Result<Option<i32>, RepositoryError> {
+        let mut conn = self.db.acquire().await.map_err(RepositoryError::from)?;
+
+        let stock = sqlx::query_scalar!(
+            r#"
+        SELECT stock FROM products WHERE product_id = $1
+        "#,
+            product_id
+        )
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|err| {
+            error!("❌ Failed to fetch stock product {}: {:?}", product_id, err);
+            RepositoryError::from(err)
+        })?;
+
+        Ok(stock)
+    }
+
     async fn trash_product(&self, id: i32) -> Result<ProductModel, RepositoryError> {
         info!("🗑️ Trashing product: {}", id);
 
@@ -170,12 +211,13 @@ impl ProductCommandRepositoryTrait for ProductCommandRepository {
             "#,
             id
         )
-        .fetch_one(&mut *conn)
+        .fetch_optional(&mut *conn)
         .await
         .map_err(|e| {
             error!("❌ Failed to trash product {}: {:?}", id, e);
             RepositoryError::from(e)
-        })?;
+        })?
+        .ok_or(RepositoryError::NotFound)?;
 
         info!("✅ Product ID {} moved to trash", product.product_id);
         Ok(product)
@@ -196,13 +238,30 @@ impl ProductCommandRepositoryTrait for ProductCommandRepository {
             "#,
             id
         )
-        .fetch_one(&mut *conn)
+        .fetch_optional(&mut *conn)
         .await
         .map_err(|e| {
             error!("❌ Failed to restore product {}: {:?}", id, e);
             RepositoryError::from(e)
         })?;
 
+        let Some(product) = product else {
+            let exists = sqlx::query_scalar!(
+                r#"
+            SELECT product_id FROM products WHERE product_id = $1
+            "#,
+                id
+            )
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(RepositoryError::from)?;
+
+            return Err(match exists {
+                Some(_) => RepositoryError::NotTrashed(format!("Product {id} is not trashed")),
+                None => RepositoryError::NotFound,
+            });
+        };
+
         info!("✅ Product ID {} restored", product.product_id);
         Ok(product)
     }
@@ -212,7 +271,7 @@ impl ProductCommandRepositoryTrait for ProductCommandRepository {
 
         let mut conn = self.db.acquire().await.map_err(RepositoryError::from)?;
 
-        sqlx::query!(
+        let result = sqlx::query!(
             r#"
             DELETE FROM products
             WHERE product_id = $1 AND deleted_at IS NOT NULL
@@ -226,6 +285,10 @@ impl ProductCommandRepositoryTrait for ProductCommandRepository {
             RepositoryError::from(e)
         })?;
 
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
         info!("✅ Product ID {} permanently deleted", id);
         Ok(())
     }
@@ -271,4 +334,42 @@ impl ProductCommandRepositoryTrait for ProductCommandRepository {
         info!("✅ All trashed products permanently deleted");
         Ok(())
     }
+
+    async fn create_products(
+        &self,
+        products: &[CreateProductRequest],
+    ) -> Result<u64, RepositoryError> {
+        let mut tx = self.db.begin().await.map_err(RepositoryError::from)?;
+        let mut inserted = 0;
+
+        for batch in products.chunks(IMPORT_BATCH_SIZE) {
+            let names: Vec<String> = batch.iter().map(|p| p.name.clone()).collect();
+            let prices: Vec<i64> = batch.iter().map(|p| p.price).collect();
+            let stocks: Vec<i32> = batch.iter().map(|p| p.stock).collect();
+
+            let result = sqlx::query!(
+                r#"
+                INSERT INTO products (name, price, stock, created_at, updated_at)
+                SELECT name, price, stock, current_timestamp, current_timestamp
+                FROM UNNEST($1::TEXT[], $2::BIGINT[], $3::INT[]) AS t(name, price, stock)
+                "#,
+                &names,
+                &prices,
+                &stocks
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| {
+                error!("❌ Failed to insert product batch: {:?}", e);
+                RepositoryError::from(e)
+            })?;
+
+            inserted += result.rows_affected();
+        }
+
+        tx.commit().await.map_err(RepositoryError::from)?;
+
+        info!("✅ Imported {} products", inserted);
+        Ok(inserted)
+    }
 }
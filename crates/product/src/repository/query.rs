@@ -3,17 +3,44 @@ use crate::{
     domain::requests::product::FindAllProducts, model::product::Product as ProductModel,
 };
 use async_trait::async_trait;
-use shared::{config::ConnectionPool, errors::RepositoryError};
+use shared::{
+    config::ConnectionPool,
+    errors::RepositoryError,
+    utils::{Cursor, QueryPlanLogger, order_by_clause, parse_sort},
+};
+use sqlx::FromRow;
+use std::time::Instant;
 use tracing::{error, info};
+use uuid::Uuid;
+
+/// Fields `find_all` may be sorted by, mapped to their SQL columns.
+const SORT_COLUMNS: &[(&str, &str)] = &[
+    ("id", "p.product_id"),
+    ("name", "p.name"),
+    ("price", "p.price"),
+    ("stock", "p.stock"),
+    ("created_at", "p.created_at"),
+];
+
+#[derive(FromRow)]
+struct ProductRow {
+    #[sqlx(flatten)]
+    product: ProductModel,
+    total_count: Option<i64>,
+}
 
 #[derive(Clone)]
 pub struct ProductQueryRepository {
     db: ConnectionPool,
+    query_plans: QueryPlanLogger,
 }
 
 impl ProductQueryRepository {
     pub fn new(db: ConnectionPool) -> Self {
-        Self { db }
+        Self {
+            db,
+            query_plans: QueryPlanLogger::from_env(),
+        }
     }
 }
 
@@ -22,14 +49,114 @@ impl ProductQueryRepositoryTrait for ProductQueryRepository {
     async fn find_all(
         &self,
         req: &FindAllProducts,
+        after: Option<Cursor>,
     ) -> Result<(Vec<ProductModel>, i64), RepositoryError> {
-        info!("🔍 Fetching all products with search: {:?}", req.search);
+        info!(
+            "🔍 Fetching all products with search: {:?}, price range: {:?}..{:?}, attribute: {:?}, sort: {:?}, after: {:?}",
+            req.search, req.min_price, req.max_price, req.attribute, req.sort, after
+        );
 
         let mut conn = self.db.acquire().await.map_err(|e| {
             error!("❌ Failed to acquire DB connection: {:?}", e);
             RepositoryError::from(e)
         })?;
 
+        let limit = req.page_size as i64;
+        let offset = if after.is_some() {
+            0
+        } else {
+            ((req.page - 1).max(0) * req.page_size) as i64
+        };
+
+        let search_pattern = if req.search.trim().is_empty() {
+            None
+        } else {
+            Some(req.search.as_str())
+        };
+
+        // Attribute values are stored as strings, so containment matches the
+        // same rows as `attributes ->> key = value` while using the GIN index.
+        let attribute_filter = req
+            .attribute_filter()
+            .map(|(key, value)| serde_json::json!({ key: value }));
+
+        let order_by = if req.sort.trim().is_empty() {
+            "p.created_at DESC, p.product_id DESC".to_string()
+        } else {
+            order_by_clause(&parse_sort(&req.sort)?, SORT_COLUMNS, "p.product_id")?
+        };
+
+        // ORDER BY can't be bound as a parameter; `order_by` only ever holds
+        // column names from SORT_COLUMNS.
+        let sql = format!(
+            r#"
+            SELECT
+                p.product_id,
+                p.external_id,
+                p.name,
+                p.price,
+                p.stock,
+                p.images,
+                p.reorder_threshold,
+                p.category,
+                p.attributes,
+                p.created_at,
+                p.updated_at,
+                p.deleted_at,
+                COUNT(*) OVER() AS total_count
+            FROM products p
+            WHERE ($1::TEXT IS NULL OR p.name ILIKE '%' || $1 || '%')
+              AND ($4::BIGINT IS NULL OR p.price >= $4)
+              AND ($5::BIGINT IS NULL OR p.price <= $5)
+              AND ($6::TIMESTAMP IS NULL OR (p.created_at, p.product_id) < ($6, $7::INT))
+              AND ($8::JSONB IS NULL OR p.attributes @> $8)
+            ORDER BY {order_by}
+            LIMIT $2 OFFSET $3
+            "#
+        );
+
+        let started = Instant::now();
+
+        let rows = sqlx::query_as::<_, ProductRow>(&sql)
+            .bind(search_pattern)
+            .bind(limit)
+            .bind(offset)
+            .bind(req.min_price)
+            .bind(req.max_price)
+            .bind(after.map(|c| c.created_at))
+            .bind(after.map(|c| c.id))
+            .bind(attribute_filter)
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(|e| {
+                error!("❌ Failed to fetch products: {:?}", e);
+                RepositoryError::from(e)
+            })?;
+
+        self.query_plans
+            .observe(&self.db, "product.find_all", &sql, started.elapsed());
+
+        let total = rows
+            .first()
+            .map(|r| r.total_count.unwrap_or(0))
+            .unwrap_or(0);
+
+        let products = rows.into_iter().map(|r| r.product).collect();
+
+        Ok((products, total))
+    }
+
+    async fn find_active(
+        &self,
+        req: &FindAllProducts,
+    ) -> Result<(Vec<ProductModel>, i64), RepositoryError> {
+        info!("🟢 Fetching active products with search: {:?}", req.search);
+
+        let mut conn = self.db.acquire().await.map_err(|e| {
+            error!("❌ DB connection failed: {:?}", e);
+            RepositoryError::from(e)
+        })?;
+
         let limit = req.page_size as i64;
         let offset = ((req.page - 1).max(0) * req.page_size) as i64;
 
@@ -43,15 +170,21 @@ impl ProductQueryRepositoryTrait for ProductQueryRepository {
             r#"
             SELECT
                 p.product_id,
+                p.external_id,
                 p.name,
                 p.price,
                 p.stock,
+                p.images,
+                p.reorder_threshold,
+                p.category,
+                p.attributes,
                 p.created_at,
                 p.updated_at,
                 p.deleted_at,
                 COUNT(*) OVER() AS total_count
             FROM products p
-            WHERE ($1::TEXT IS NULL OR p.name ILIKE '%' || $1 || '%')
+            WHERE p.deleted_at IS NULL
+              AND ($1::TEXT IS NULL OR p.name ILIKE '%' || $1 || '%')
             ORDER BY p.created_at DESC
             LIMIT $2 OFFSET $3
             "#,
@@ -62,7 +195,7 @@ impl ProductQueryRepositoryTrait for ProductQueryRepository {
         .fetch_all(&mut *conn)
         .await
         .map_err(|e| {
-            error!("❌ Failed to fetch products: {:?}", e);
+            error!("❌ Error fetching active products: {:?}", e);
             RepositoryError::from(e)
         })?;
 
@@ -75,9 +208,14 @@ impl ProductQueryRepositoryTrait for ProductQueryRepository {
             .into_iter()
             .map(|r| ProductModel {
                 product_id: r.product_id,
+                external_id: r.external_id,
                 name: r.name,
                 price: r.price,
                 stock: r.stock,
+                images: r.images,
+                reorder_threshold: r.reorder_threshold,
+                category: r.category,
+                attributes: r.attributes,
                 created_at: r.created_at,
                 updated_at: r.updated_at,
                 deleted_at: r.deleted_at,
@@ -87,14 +225,14 @@ impl ProductQueryRepositoryTrait for ProductQueryRepository {
         Ok((products, total))
     }
 
-    async fn find_active(
+    async fn find_trashed(
         &self,
         req: &FindAllProducts,
     ) -> Result<(Vec<ProductModel>, i64), RepositoryError> {
-        info!("🟢 Fetching active products with search: {:?}", req.search);
+        info!("🗑️ Fetching trashed products with search: {:?}", req.search);
 
         let mut conn = self.db.acquire().await.map_err(|e| {
-            error!("❌ DB connection failed: {:?}", e);
+            error!("❌ Failed to acquire DB connection: {:?}", e);
             RepositoryError::from(e)
         })?;
 
@@ -111,17 +249,22 @@ impl ProductQueryRepositoryTrait for ProductQueryRepository {
             r#"
             SELECT
                 p.product_id,
+                p.external_id,
                 p.name,
                 p.price,
                 p.stock,
+                p.images,
+                p.reorder_threshold,
+                p.category,
+                p.attributes,
                 p.created_at,
                 p.updated_at,
                 p.deleted_at,
                 COUNT(*) OVER() AS total_count
             FROM products p
-            WHERE p.deleted_at IS NULL
+            WHERE p.deleted_at IS NOT NULL
               AND ($1::TEXT IS NULL OR p.name ILIKE '%' || $1 || '%')
-            ORDER BY p.created_at DESC
+            ORDER BY p.deleted_at DESC
             LIMIT $2 OFFSET $3
             "#,
             search_pattern,
@@ -131,7 +274,7 @@ impl ProductQueryRepositoryTrait for ProductQueryRepository {
         .fetch_all(&mut *conn)
         .await
         .map_err(|e| {
-            error!("❌ Error fetching active products: {:?}", e);
+            error!("❌ Failed to fetch trashed products: {:?}", e);
             RepositoryError::from(e)
         })?;
 
@@ -144,9 +287,14 @@ impl ProductQueryRepositoryTrait for ProductQueryRepository {
             .into_iter()
             .map(|r| ProductModel {
                 product_id: r.product_id,
+                external_id: r.external_id,
                 name: r.name,
                 price: r.price,
                 stock: r.stock,
+                images: r.images,
+                reorder_threshold: r.reorder_threshold,
+                category: r.category,
+                attributes: r.attributes,
                 created_at: r.created_at,
                 updated_at: r.updated_at,
                 deleted_at: r.deleted_at,
@@ -156,14 +304,17 @@ impl ProductQueryRepositoryTrait for ProductQueryRepository {
         Ok((products, total))
     }
 
-    async fn find_trashed(
+    async fn find_low_stock(
         &self,
         req: &FindAllProducts,
     ) -> Result<(Vec<ProductModel>, i64), RepositoryError> {
-        info!("🗑️ Fetching trashed products with search: {:?}", req.search);
+        info!(
+            "📉 Fetching low-stock products with search: {:?}",
+            req.search
+        );
 
         let mut conn = self.db.acquire().await.map_err(|e| {
-            error!("❌ Failed to acquire DB connection: {:?}", e);
+            error!("❌ DB connection failed: {:?}", e);
             RepositoryError::from(e)
         })?;
 
@@ -180,17 +331,24 @@ impl ProductQueryRepositoryTrait for ProductQueryRepository {
             r#"
             SELECT
                 p.product_id,
+                p.external_id,
                 p.name,
                 p.price,
                 p.stock,
+                p.images,
+                p.reorder_threshold,
+                p.category,
+                p.attributes,
                 p.created_at,
                 p.updated_at,
                 p.deleted_at,
                 COUNT(*) OVER() AS total_count
             FROM products p
-            WHERE p.deleted_at IS NOT NULL
+            WHERE p.deleted_at IS NULL
+              AND p.reorder_threshold IS NOT NULL
+              AND p.stock <= p.reorder_threshold
               AND ($1::TEXT IS NULL OR p.name ILIKE '%' || $1 || '%')
-            ORDER BY p.deleted_at DESC
+            ORDER BY p.stock ASC, p.product_id ASC
             LIMIT $2 OFFSET $3
             "#,
             search_pattern,
@@ -200,7 +358,7 @@ impl ProductQueryRepositoryTrait for ProductQueryRepository {
         .fetch_all(&mut *conn)
         .await
         .map_err(|e| {
-            error!("❌ Failed to fetch trashed products: {:?}", e);
+            error!("❌ Error fetching low-stock products: {:?}", e);
             RepositoryError::from(e)
         })?;
 
@@ -213,9 +371,14 @@ impl ProductQueryRepositoryTrait for ProductQueryRepository {
             .into_iter()
             .map(|r| ProductModel {
                 product_id: r.product_id,
+                external_id: r.external_id,
                 name: r.name,
                 price: r.price,
                 stock: r.stock,
+                images: r.images,
+                reorder_threshold: r.reorder_threshold,
+                category: r.category,
+                attributes: r.attributes,
                 created_at: r.created_at,
                 updated_at: r.updated_at,
                 deleted_at: r.deleted_at,
@@ -235,9 +398,14 @@ impl ProductQueryRepositoryTrait for ProductQueryRepository {
             r#"
             SELECT
                 product_id,
+                external_id,
                 name,
                 price,
                 stock,
+                images,
+                reorder_threshold,
+                category,
+                attributes,
                 created_at,
                 updated_at,
                 deleted_at
@@ -252,4 +420,217 @@ impl ProductQueryRepositoryTrait for ProductQueryRepository {
 
         Ok(result)
     }
+
+    async fn find_by_ids(&self, ids: &[i32]) -> Result<Vec<ProductModel>, RepositoryError> {
+        info!("🆔 Fetching {} products by ID", ids.len());
+
+        let mut conn = self.db.acquire().await.map_err(RepositoryError::from)?;
+
+        let result = sqlx::query_as!(
+            ProductModel,
+            r#"
+            SELECT
+                product_id,
+                external_id,
+                name,
+                price,
+                stock,
+                images,
+                reorder_threshold,
+                category,
+                attributes,
+                created_at,
+                updated_at,
+                deleted_at
+            FROM products
+            WHERE product_id = ANY($1)
+              AND deleted_at IS NULL
+            ORDER BY product_id
+            "#,
+            ids
+        )
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| {
+            error!("❌ Error fetching products by ids: {:?}", e);
+            RepositoryError::from(e)
+        })?;
+
+        Ok(result)
+    }
+
+    async fn find_by_external_id(
+        &self,
+        external_id: Uuid,
+    ) -> Result<Option<ProductModel>, RepositoryError> {
+        info!("🆔 Fetching product by external ID: {}", external_id);
+
+        let mut conn = self.db.acquire().await.map_err(RepositoryError::from)?;
+
+        let result = sqlx::query_as!(
+            ProductModel,
+            r#"
+            SELECT
+                product_id,
+                external_id,
+                name,
+                price,
+                stock,
+                images,
+                reorder_threshold,
+                category,
+                attributes,
+                created_at,
+                updated_at,
+                deleted_at
+            FROM products
+            WHERE external_id = $1
+            "#,
+            external_id
+        )
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ProductQueryRepository;
+    use crate::{
+        abstract_trait::product::repository::ProductQueryRepositoryTrait,
+        domain::requests::product::FindAllProducts,
+    };
+    use sqlx::PgPool;
+
+    fn price_range(min_price: Option<i64>, max_price: Option<i64>) -> FindAllProducts {
+        FindAllProducts {
+            min_price,
+            max_price,
+            sort: "price:asc".into(),
+            ..page(1, 10)
+        }
+    }
+
+    fn page(page: i32, page_size: i32) -> FindAllProducts {
+        serde_json::from_value(serde_json::json!({ "page": page, "page_size": page_size })).unwrap()
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "needs a Postgres server at DATABASE_URL"]
+    async fn only_products_at_or_below_their_threshold_are_low_stock(pool: PgPool) {
+        sqlx::raw_sql(
+            r#"
+            INSERT INTO products (name, price, stock, reorder_threshold, deleted_at) VALUES
+                ('empty shelf', 100, 0, 5, NULL),
+                ('at threshold', 100, 5, 5, NULL),
+                ('just below', 100, 4, 5, NULL),
+                ('above threshold', 100, 6, 5, NULL),
+                ('no threshold', 100, 0, NULL, NULL),
+                ('trashed', 100, 0, 5, now());
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let repo = ProductQueryRepository::new(pool);
+
+        let (first, total) = repo.find_low_stock(&page(1, 2)).await.unwrap();
+        assert_eq!(total, 3);
+        let names: Vec<_> = first.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["empty shelf", "just below"]);
+
+        let (second, total) = repo.find_low_stock(&page(2, 2)).await.unwrap();
+        assert_eq!(total, 3);
+        let names: Vec<_> = second.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["at threshold"]);
+
+        let (past_end, _) = repo.find_low_stock(&page(3, 2)).await.unwrap();
+        assert!(past_end.is_empty());
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "needs a Postgres server at DATABASE_URL"]
+    async fn price_bounds_are_inclusive_and_either_may_be_open(pool: PgPool) {
+        sqlx::raw_sql(
+            r#"
+            INSERT INTO products (name, price, stock) VALUES
+                ('pencil', 50, 10),
+                ('notebook', 100, 10),
+                ('backpack', 300, 10),
+                ('desk lamp', 500, 10),
+                ('office chair', 900, 10);
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let repo = ProductQueryRepository::new(pool);
+
+        for ((min, max), expected) in [
+            (
+                (Some(100), Some(500)),
+                &["notebook", "backpack", "desk lamp"][..],
+            ),
+            ((Some(300), Some(300)), &["backpack"]),
+            ((Some(500), None), &["desk lamp", "office chair"]),
+            ((None, Some(100)), &["pencil", "notebook"]),
+            (
+                (None, None),
+                &[
+                    "pencil",
+                    "notebook",
+                    "backpack",
+                    "desk lamp",
+                    "office chair",
+                ],
+            ),
+        ] {
+            let (products, total) = repo.find_all(&price_range(min, max), None).await.unwrap();
+            let names: Vec<_> = products.iter().map(|p| p.name.as_str()).collect();
+            assert_eq!(names, expected, "{min:?}..{max:?}");
+            assert_eq!(total, expected.len() as i64);
+        }
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "needs a Postgres server at DATABASE_URL"]
+    async fn the_attribute_filter_matches_one_key_exactly(pool: PgPool) {
+        sqlx::raw_sql(
+            r#"
+            INSERT INTO products (name, price, stock, category, attributes) VALUES
+                ('red shirt', 100, 10, 'apparel', '{"color": "red", "size": "M"}'),
+                ('red scarf', 200, 10, 'apparel', '{"color": "red"}'),
+                ('dark red hat', 300, 10, 'apparel', '{"color": "dark red"}'),
+                ('blue shirt', 400, 10, 'apparel', '{"color": "blue", "size": "M"}'),
+                ('red lamp', 500, 10, 'lighting', '{"shade": "red"}'),
+                ('plain mug', 600, 10, NULL, '{}');
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let repo = ProductQueryRepository::new(pool);
+
+        for (attribute, expected) in [
+            ("color:red", &["red shirt", "red scarf"][..]),
+            ("color:dark red", &["dark red hat"]),
+            ("size:M", &["red shirt", "blue shirt"]),
+            ("color:green", &[]),
+        ] {
+            let req = FindAllProducts {
+                attribute: attribute.into(),
+                ..price_range(None, None)
+            };
+            let (products, total) = repo.find_all(&req, None).await.unwrap();
+            let names: Vec<_> = products.iter().map(|p| p.name.as_str()).collect();
+            assert_eq!(names, expected, "{attribute}");
+            assert_eq!(total, expected.len() as i64);
+        }
+    }
 }
@@ -3,7 +3,12 @@ use crate::{
         repository::DynProductCommandRepository, service::ProductCommandServiceTrait,
     },
     domain::{
-        requests::product::{CreateProductRequest, UpdateProductRequest},
+        event::ProductEvent,
+        requests::{
+            product::{CreateProductRequest, UpdateProductRequest},
+            product_attributes::AttributeSchemas,
+            product_import::parse_product_csv,
+        },
         response::{
             api::ApiResponse,
             product::{ProductResponse, ProductResponseDeleteAt},
@@ -12,6 +17,7 @@ use crate::{
 };
 use anyhow::Result;
 use async_trait::async_trait;
+use chrono::Duration;
 use genproto::product::FindByIdProductRequest;
 use opentelemetry::{
     Context, KeyValue,
@@ -19,25 +25,167 @@ use opentelemetry::{
     trace::{Span, SpanKind, TraceContextExt, Tracer},
 };
 use shared::{
-    errors::ServiceError,
-    utils::{MetadataInjector, Method, Metrics, Status as StatusUtils, TracingContext},
+    abstract_trait::DynKafka,
+    cache::{CacheStore, keys},
+    errors::{FieldViolation, RepositoryError, ServiceError},
+    utils::{
+        BatchResponse, LifecycleMetrics, LifecycleScope, MetadataInjector, Method, Metrics,
+        Status as StatusUtils, TracingContext,
+    },
 };
+use std::{collections::BTreeMap, sync::Arc};
 use tokio::time::Instant;
 use tonic::Request;
 use tracing::{error, info};
 
+/// Rows committed per transaction by a non-strict import.
+const IMPORT_BATCH_SIZE: usize = 500;
+
 #[derive(Clone)]
 pub struct ProductCommandService {
     pub command: DynProductCommandRepository,
+    pub kafka: DynKafka,
+    pub cache_store: Arc<CacheStore>,
     pub metrics: Metrics,
+    pub lifecycle: LifecycleMetrics,
+    pub max_images: usize,
+    pub stock_events: bool,
+    pub attribute_schemas: Arc<AttributeSchemas>,
+}
+
+pub struct ProductCommandServiceDeps {
+    pub command: DynProductCommandRepository,
+    pub kafka: DynKafka,
+    pub cache_store: Arc<CacheStore>,
+    pub max_images: usize,
+    pub stock_events: bool,
+    pub attribute_schemas: Arc<AttributeSchemas>,
 }
 
 impl ProductCommandService {
-    pub fn new(command: DynProductCommandRepository) -> Result<Self> {
+    pub fn new(deps: ProductCommandServiceDeps) -> Result<Self> {
+        let ProductCommandServiceDeps {
+            command,
+            kafka,
+            cache_store,
+            max_images,
+            stock_events,
+            attribute_schemas,
+        } = deps;
+
         let metrics = Metrics::new(global::meter("product-command-service"));
+        let lifecycle = LifecycleMetrics::new(global::meter("product-command-service"), "product");
+
+        Ok(Self {
+            command,
+            kafka,
+            cache_store,
+            metrics,
+            lifecycle,
+            max_images,
+            stock_events,
+            attribute_schemas,
+        })
+    }
+
+    fn validate_images(&self, images: &[String]) -> Result<(), ServiceError> {
+        let mut violations = Vec::new();
+
+        if images.len() > self.max_images {
+            violations.push(FieldViolation::new(
+                "images",
+                format!("At most {} images are allowed", self.max_images),
+            ));
+        }
+
+        for (index, url) in images.iter().enumerate() {
+            if !is_https_url(url) {
+                violations.push(FieldViolation::new(
+                    format!("images[{index}]"),
+                    "Image URL must be an absolute https URL",
+                ));
+            }
+        }
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(ServiceError::FieldValidation(violations))
+        }
+    }
+
+    fn validate_attributes(
+        &self,
+        category: Option<&str>,
+        attributes: &BTreeMap<String, String>,
+    ) -> Result<(), ServiceError> {
+        let violations = self.attribute_schemas.validate(category, attributes);
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(ServiceError::FieldValidation(violations))
+        }
+    }
+
+    /// The stock change is already committed, so a failed publish is logged
+    /// rather than failing the request.
+    async fn publish_stock_event(&self, product_id: i32, before: i32, after: i32) {
+        if !self.stock_events {
+            return;
+        }
+
+        let Some(event) = ProductEvent::for_stock_change(product_id, before, after) else {
+            return;
+        };
 
-        Ok(Self { command, metrics })
+        let payload = match serde_json::to_vec(&event) {
+            Ok(payload) => payload,
+            Err(e) => {
+                error!("❌ Failed to encode product event: {e:?}");
+                return;
+            }
+        };
+
+        if let Err(e) = self
+            .kafka
+            .publish(event.topic(), &product_id.to_string(), &payload)
+            .await
+        {
+            error!("❌ Failed to publish event: {e:?}");
+        }
     }
+
+    /// Runs after a write commits so readers don't keep serving the old
+    /// record or list pages until their TTL runs out.
+    async fn invalidate_product(&self, id: i32, external_id: &str) {
+        self.cache_store
+            .invalidate_record("product", id, Some(external_id))
+            .await;
+    }
+
+    /// Stock changes arrive in bursts from order events, and the next
+    /// lookup after a plain invalidation reads the replica, which can still
+    /// hold the old count and would cache it for the full TTL. Writing the
+    /// row the primary just returned keeps lookups on the new stock.
+    async fn refresh_product(&self, product: &ProductResponse) {
+        let response = ApiResponse {
+            status: "success".to_string(),
+            message: "Product retrieved successfully".to_string(),
+            data: product.clone(),
+        };
+
+        for key in [
+            keys::by_id_key("product", product.id),
+            keys::by_external_id_key("product", &product.external_id),
+        ] {
+            self.cache_store
+                .set_to_cache(&key, &response, Duration::minutes(5))
+                .await;
+        }
+        self.cache_store.invalidate_lists("product").await;
+    }
+
     fn get_tracer(&self) -> BoxedTracer {
         global::tracer("product-command-service")
     }
@@ -184,12 +332,16 @@ impl ProductCommandServiceTrait for ProductCommandService {
         };
 
         let response = ProductResponse::from(product_model);
+        self.refresh_product(&response).await;
 
         info!(
             "✅ Stock increased: {} (ID: {}), new stock: {}",
             response.name, response.id, response.stock
         );
 
+        self.publish_stock_event(response.id, response.stock - qty, response.stock)
+            .await;
+
         Ok(ApiResponse {
             status: "success".to_string(),
             message: "Product stock increased successfully".to_string(),
@@ -228,7 +380,7 @@ impl ProductCommandServiceTrait for ProductCommandService {
         }
 
         let product_model = match self.command.decreasing_stock(product_id, qty).await {
-            Ok(product) => {
+            Ok(Some(product)) => {
                 self.complete_tracing_success(
                     &tracing_ctx,
                     Method::Post,
@@ -237,6 +389,38 @@ impl ProductCommandServiceTrait for ProductCommandService {
                 .await;
                 product
             }
+            Ok(None) => {
+                let available = match self.command.find_stock(product_id).await {
+                    Ok(available) => available,
+                    Err(err) => {
+                        error!("❌ Failed to fetch stock: {err:?}");
+                        self.complete_tracing_error(
+                            &tracing_ctx,
+                            Method::Post,
+                            "Failed to decrease stock",
+                        )
+                        .await;
+                        return Err(ServiceError::Repo(err));
+                    }
+                };
+
+                self.complete_tracing_error(&tracing_ctx, Method::Post, "Insufficient stock")
+                    .await;
+
+                return match available {
+                    Some(available) => {
+                        error!(
+                            "❌ Insufficient stock for product ID={product_id}: requested={qty}, available={available}"
+                        );
+                        Err(ServiceError::InsufficientStock {
+                            product_id,
+                            requested: qty,
+                            available,
+                        })
+                    }
+                    None => Err(ServiceError::Repo(RepositoryError::NotFound)),
+                };
+            }
             Err(err) => {
                 error!("❌ Failed to decrease stock: {err:?}");
                 self.complete_tracing_error(&tracing_ctx, Method::Post, "Failed to decrease stock")
@@ -246,12 +430,16 @@ impl ProductCommandServiceTrait for ProductCommandService {
         };
 
         let response = ProductResponse::from(product_model);
+        self.refresh_product(&response).await;
 
         info!(
             "✅ Stock decreased: {} (ID: {}), new stock: {}",
             response.name, response.id, response.stock
         );
 
+        self.publish_stock_event(response.id, response.stock + qty, response.stock)
+            .await;
+
         Ok(ApiResponse {
             status: "success".to_string(),
             message: "Product stock decreased successfully".to_string(),
@@ -276,6 +464,20 @@ impl ProductCommandServiceTrait for ProductCommandService {
             ],
         );
 
+        if let Err(err) = self.validate_images(&req.images) {
+            error!("❌ Invalid product images: {err}");
+            self.complete_tracing_error(&tracing_ctx, method.clone(), "Invalid product images")
+                .await;
+            return Err(err);
+        }
+
+        if let Err(err) = self.validate_attributes(req.category.as_deref(), &req.attributes) {
+            error!("❌ Invalid product attributes: {err}");
+            self.complete_tracing_error(&tracing_ctx, method.clone(), "Invalid product attributes")
+                .await;
+            return Err(err);
+        }
+
         let mut request = Request::new(req.clone());
 
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
@@ -303,6 +505,8 @@ impl ProductCommandServiceTrait for ProductCommandService {
         };
 
         let response = ProductResponse::from(product_model);
+        self.invalidate_product(response.id, &response.external_id)
+            .await;
 
         info!(
             "✅ Product created successfully: {} (ID: {})",
@@ -334,9 +538,34 @@ impl ProductCommandServiceTrait for ProductCommandService {
             ],
         );
 
+        if let Err(err) = self.validate_images(&req.images) {
+            error!("❌ Invalid product images: {err}");
+            self.complete_tracing_error(&tracing_ctx, Method::Put, "Invalid product images")
+                .await;
+            return Err(err);
+        }
+
+        if let Err(err) = self.validate_attributes(req.category.as_deref(), &req.attributes) {
+            error!("❌ Invalid product attributes: {err}");
+            self.complete_tracing_error(&tracing_ctx, Method::Put, "Invalid product attributes")
+                .await;
+            return Err(err);
+        }
+
         let mut request = Request::new(req.clone());
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
+        // An edit can set stock straight from 0 (a restock) or to 0, which
+        // should publish the same events as order-driven changes.
+        let stock_before = if self.stock_events {
+            self.command.find_stock(req.id).await.unwrap_or_else(|err| {
+                error!("❌ Failed to read stock before update: {err:?}");
+                None
+            })
+        } else {
+            None
+        };
+
         let product_model = match self.command.update_product(req).await {
             Ok(product) => {
                 self.complete_tracing_success(
@@ -356,12 +585,19 @@ impl ProductCommandServiceTrait for ProductCommandService {
         };
 
         let response = ProductResponse::from(product_model);
+        self.invalidate_product(response.id, &response.external_id)
+            .await;
 
         info!(
             "✅ Product updated successfully: {} (ID: {})",
             response.name, response.id,
         );
 
+        if let Some(before) = stock_before {
+            self.publish_stock_event(response.id, before, response.stock)
+                .await;
+        }
+
         Ok(ApiResponse {
             status: "success".to_string(),
             message: "Product updated successfully".to_string(),
@@ -391,6 +627,7 @@ impl ProductCommandServiceTrait for ProductCommandService {
 
         let product_model = match self.command.trash_product(product_id).await {
             Ok(product) => {
+                self.lifecycle.soft_deleted(LifecycleScope::Single);
                 self.complete_tracing_success(
                     &tracing_ctx,
                     method.clone(),
@@ -415,6 +652,8 @@ impl ProductCommandServiceTrait for ProductCommandService {
         };
 
         let response = ProductResponseDeleteAt::from(product_model);
+        self.invalidate_product(response.id, &response.external_id)
+            .await;
 
         info!("✅ Product moved to trash: ID {}", response.id);
 
@@ -446,6 +685,7 @@ impl ProductCommandServiceTrait for ProductCommandService {
 
         let product_model = match self.command.restore_product(product_id).await {
             Ok(product) => {
+                self.lifecycle.restored(LifecycleScope::Single);
                 self.complete_tracing_success(
                     &tracing_ctx,
                     method.clone(),
@@ -467,6 +707,8 @@ impl ProductCommandServiceTrait for ProductCommandService {
         };
 
         let response = ProductResponseDeleteAt::from(product_model);
+        self.invalidate_product(response.id, &response.external_id)
+            .await;
 
         info!(
             "✅ Product restored: {} (ID: {})",
@@ -499,6 +741,9 @@ impl ProductCommandServiceTrait for ProductCommandService {
 
         match self.command.delete_product(product_id).await {
             Ok(()) => {
+                self.lifecycle.purged(LifecycleScope::Single);
+                // The external id isn't known here, so drop the whole entity.
+                self.cache_store.invalidate_entity("product").await;
                 self.complete_tracing_success(
                     &tracing_ctx,
                     method.clone(),
@@ -548,6 +793,8 @@ impl ProductCommandServiceTrait for ProductCommandService {
 
         match self.command.restore_all_products().await {
             Ok(()) => {
+                self.lifecycle.restored(LifecycleScope::All);
+                self.cache_store.invalidate_entity("product").await;
                 self.complete_tracing_success(
                     &tracing_ctx,
                     method.clone(),
@@ -594,6 +841,8 @@ impl ProductCommandServiceTrait for ProductCommandService {
 
         match self.command.delete_all_products().await {
             Ok(()) => {
+                self.lifecycle.purged(LifecycleScope::All);
+                self.cache_store.invalidate_entity("product").await;
                 self.complete_tracing_success(
                     &tracing_ctx,
                     method.clone(),
@@ -621,4 +870,273 @@ impl ProductCommandServiceTrait for ProductCommandService {
             data: (),
         })
     }
+
+    async fn import_products(
+        &self,
+        csv: &str,
+        strict: bool,
+    ) -> Result<ApiResponse<BatchResponse<i32>>, ServiceError> {
+        info!("📦 Importing products from CSV (strict: {strict})");
+
+        let method = Method::Post;
+        let tracing_ctx = self.start_tracing(
+            "ImportProducts",
+            vec![
+                KeyValue::new("component", "product"),
+                KeyValue::new("operation", "import"),
+                KeyValue::new("import.strict", strict),
+            ],
+        );
+
+        let parsed = match parse_product_csv(csv) {
+            Ok(parsed) => parsed,
+            Err(reason) => {
+                self.complete_tracing_error(&tracing_ctx, method.clone(), &reason)
+                    .await;
+                return Err(ServiceError::Validation(vec![reason]));
+            }
+        };
+
+        if strict && !parsed.errors.is_empty() {
+            self.complete_tracing_error(&tracing_ctx, method.clone(), "Import has invalid rows")
+                .await;
+            return Err(ServiceError::FieldValidation(
+                parsed
+                    .errors
+                    .into_iter()
+                    .map(|e| FieldViolation::new(format!("row {}", e.id), e.error))
+                    .collect(),
+            ));
+        }
+
+        let mut summary = BatchResponse {
+            succeeded: Vec::new(),
+            failed: parsed.errors,
+        };
+
+        // Strict imports are all-or-nothing; otherwise each batch commits on
+        // its own and a failed batch only costs its own rows.
+        let batch_size = if strict {
+            parsed.rows.len().max(1)
+        } else {
+            IMPORT_BATCH_SIZE
+        };
+
+        for rows in parsed.rows.chunks(batch_size) {
+            let batch: Vec<CreateProductRequest> = rows.iter().map(|(_, p)| p.clone()).collect();
+
+            match self.command.create_products(&batch).await {
+                Ok(_) => summary.succeeded.extend(rows.iter().map(|(row, _)| *row)),
+                Err(err) if strict => {
+                    error!("❌ Strict product import failed: {err:?}");
+                    self.complete_tracing_error(&tracing_ctx, method.clone(), "Import failed")
+                        .await;
+                    return Err(ServiceError::Repo(err));
+                }
+                Err(err) => {
+                    error!("❌ Product import batch failed: {err:?}");
+                    for (row, _) in rows {
+                        summary.fail(*row, format!("Insert failed: {err}"));
+                    }
+                }
+            }
+        }
+
+        summary.failed.sort_by_key(|e| e.id);
+
+        if !summary.succeeded.is_empty() {
+            self.cache_store.invalidate_lists("product").await;
+        }
+
+        self.complete_tracing_success(&tracing_ctx, method, "Products imported")
+            .await;
+
+        info!(
+            "✅ Product import finished: {} inserted, {} failed",
+            summary.succeeded.len(),
+            summary.failed.len()
+        );
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: format!(
+                "Imported {} products, {} rows failed",
+                summary.succeeded.len(),
+                summary.failed.len()
+            ),
+            data: summary,
+        })
+    }
+}
+
+fn is_https_url(url: &str) -> bool {
+    let host = url
+        .strip_prefix("https://")
+        .and_then(|rest| rest.split(['/', '?', '#']).next());
+
+    matches!(host, Some(host) if !host.is_empty()) && !url.chars().any(char::is_whitespace)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ProductCommandService, ProductCommandServiceDeps};
+    use crate::{
+        abstract_trait::product::service::ProductCommandServiceTrait,
+        domain::requests::{product::CreateProductRequest, product_attributes::AttributeSchemas},
+        repository::command::ProductCommandRepository,
+    };
+    use async_trait::async_trait;
+    use axum::{http::StatusCode, response::IntoResponse};
+    use shared::{
+        abstract_trait::KafkaTrait,
+        cache::CacheStore,
+        config::{RedisConfig, RedisPool},
+        errors::{AppErrorGrpc, HttpError, RepositoryError, ServiceError},
+    };
+    use sqlx::PgPool;
+    use std::sync::{Arc, Mutex};
+    use tonic::Status;
+
+    /// Topic and payload of every published event.
+    #[derive(Default)]
+    struct Published(Mutex<Vec<(String, serde_json::Value)>>);
+
+    #[async_trait]
+    impl KafkaTrait for Published {
+        async fn publish(&self, topic: &str, _key: &str, value: &[u8]) -> Result<(), ServiceError> {
+            self.0
+                .lock()
+                .unwrap()
+                .push((topic.to_string(), serde_json::from_slice(value).unwrap()));
+            Ok(())
+        }
+
+        async fn subscribe(&self, _topics: Vec<&str>, _group_id: &str) -> Result<(), ServiceError> {
+            unimplemented!()
+        }
+    }
+
+    fn service(pool: PgPool, kafka: Arc<Published>) -> ProductCommandService {
+        // Never connected: cache writes and invalidations are logged no-ops.
+        let cache_store = Arc::new(CacheStore::new(
+            RedisPool::new(&RedisConfig::new()).unwrap().pool,
+        ));
+
+        ProductCommandService::new(ProductCommandServiceDeps {
+            command: Arc::new(ProductCommandRepository::new(pool)),
+            kafka,
+            cache_store,
+            max_images: 3,
+            stock_events: true,
+            attribute_schemas: Arc::new(AttributeSchemas::default()),
+        })
+        .unwrap()
+    }
+
+    fn product(images: &[&str]) -> CreateProductRequest {
+        CreateProductRequest {
+            name: "Lamp".into(),
+            price: 2500,
+            stock: 10,
+            images: images.iter().map(|url| url.to_string()).collect(),
+            reorder_threshold: None,
+            category: None,
+            attributes: Default::default(),
+        }
+    }
+
+    /// Fields named by the violations of a rejected request.
+    fn rejected_fields(result: Result<impl Sized, ServiceError>) -> Vec<String> {
+        match result {
+            Err(ServiceError::FieldValidation(violations)) => {
+                violations.into_iter().map(|v| v.field).collect()
+            }
+            Err(other) => panic!("expected a field validation error, got {other:?}"),
+            Ok(_) => panic!("expected the request to be rejected"),
+        }
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "needs a Postgres server at DATABASE_URL"]
+    async fn image_urls_must_be_https_and_within_the_cap(pool: PgPool) {
+        let service = service(pool.clone(), Arc::default());
+
+        let urls = [
+            "https://cdn.example.com/lamp/front.jpg",
+            "https://cdn.example.com/lamp/side.jpg?w=800",
+        ];
+        let created = service.create_product(&product(&urls)).await.unwrap().data;
+        assert_eq!(created.images, urls);
+
+        let stored: serde_json::Value =
+            sqlx::query_scalar("SELECT images FROM products WHERE product_id = $1")
+                .bind(created.id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(stored, serde_json::json!(urls));
+
+        let insecure = product(&[urls[0], "http://cdn.example.com/lamp/back.jpg"]);
+        assert_eq!(
+            rejected_fields(service.create_product(&insecure).await),
+            ["images[1]"]
+        );
+
+        let too_many = product(&[urls[0]; 4]);
+        assert_eq!(
+            rejected_fields(service.create_product(&too_many).await),
+            ["images"]
+        );
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "needs a Postgres server at DATABASE_URL"]
+    async fn only_trashed_products_can_be_restored(pool: PgPool) {
+        let service = service(pool, Arc::default());
+        let id = service.create_product(&product(&[])).await.unwrap().data.id;
+
+        let refused = service.restore_product(id).await.unwrap_err();
+        assert!(matches!(
+            &refused,
+            ServiceError::Repo(RepositoryError::NotTrashed(msg)) if msg.contains("not trashed")
+        ));
+
+        // What the gateway answers once the error has crossed gRPC.
+        let status = Status::from(AppErrorGrpc::Service(refused));
+        let response = HttpError::from(AppErrorGrpc::from(status)).into_response();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let trashed = service.trash_product(id).await.unwrap().data;
+        assert!(trashed.deleted_at.is_some());
+
+        let restored = service.restore_product(id).await.unwrap().data;
+        assert_eq!(restored.id, id);
+        assert!(restored.deleted_at.is_none());
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "needs a Postgres server at DATABASE_URL"]
+    async fn only_a_restock_from_zero_is_back_in_stock(pool: PgPool) {
+        let published = Arc::new(Published::default());
+        let service = service(pool, published.clone());
+        let id = service.create_product(&product(&[])).await.unwrap().data.id;
+
+        service.decreasing_stock(id, 10).await.unwrap();
+        service.increasing_stock(id, 5).await.unwrap();
+        service.increasing_stock(id, 3).await.unwrap();
+
+        assert_eq!(
+            *published.0.lock().unwrap(),
+            [
+                (
+                    "product.out_of_stock".to_string(),
+                    serde_json::json!({ "type": "OutOfStock", "product_id": id }),
+                ),
+                (
+                    "product.back_in_stock".to_string(),
+                    serde_json::json!({ "type": "BackInStock", "product_id": id, "stock": 5 }),
+                ),
+            ]
+        );
+    }
 }
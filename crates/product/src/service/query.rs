@@ -20,20 +20,25 @@ use opentelemetry::{
     trace::{Span, SpanKind, TraceContextExt, Tracer},
 };
 use shared::{
-    cache::CacheStore,
-    errors::ServiceError,
-    utils::{MetadataInjector, Method, Metrics, Status as StatusUtils, TracingContext},
+    cache::{CacheStore, keys},
+    errors::{FieldViolation, ServiceError},
+    utils::{
+        BatchResponse, Cursor, CursorCodec, MetadataInjector, Method, Metrics,
+        Status as StatusUtils, TracingContext, page_count,
+    },
 };
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 use tokio::time::Instant;
 use tonic::Request;
 use tracing::{error, info};
+use uuid::Uuid;
 
 #[derive(Clone)]
 pub struct ProductQueryService {
     pub query: DynProductQueryRepository,
     pub metrics: Metrics,
     pub cache_store: Arc<CacheStore>,
+    pub cursor_codec: CursorCodec,
 }
 
 impl ProductQueryService {
@@ -44,6 +49,7 @@ impl ProductQueryService {
             query,
             metrics,
             cache_store,
+            cursor_codec: CursorCodec::from_env(),
         })
     }
 
@@ -147,6 +153,27 @@ impl ProductQueryServiceTrait for ProductQueryService {
             req.page, req.page_size, req.search
         );
 
+        let mut violations = req.price_range_violations();
+        violations.extend(req.attribute_violations());
+        if !violations.is_empty() {
+            return Err(ServiceError::FieldValidation(violations));
+        }
+
+        // Cursors encode the default (created_at, id) position, so they only
+        // make sense when paging in that order.
+        if !req.after.is_empty() && !req.sort.trim().is_empty() {
+            return Err(ServiceError::FieldValidation(vec![FieldViolation::new(
+                "sort",
+                "Cannot be combined with after",
+            )]));
+        }
+
+        let after = if req.after.is_empty() {
+            None
+        } else {
+            Some(self.cursor_codec.decode(&req.after)?)
+        };
+
         let page = if req.page > 0 { req.page } else { 1 };
         let page_size = if req.page_size > 0 { req.page_size } else { 10 };
         let search = if req.search.is_empty() {
@@ -172,8 +199,13 @@ impl ProductQueryServiceTrait for ProductQueryService {
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
         let cache_key = format!(
-            "product:find_all:page:{page}:size:{page_size}:search:{}",
-            search.unwrap_or_default()
+            "product:find_all:page:{page}:size:{page_size}:search:{}:price:{}-{}:attribute:{}:sort:{}:after:{}",
+            search.unwrap_or_default(),
+            req.min_price.map(|v| v.to_string()).unwrap_or_default(),
+            req.max_price.map(|v| v.to_string()).unwrap_or_default(),
+            req.attribute,
+            req.sort,
+            req.after,
         );
 
         if let Some(cached) = self
@@ -187,7 +219,7 @@ impl ProductQueryServiceTrait for ProductQueryService {
             return Ok(cached);
         }
 
-        let (products, total) = match self.query.find_all(req).await {
+        let (products, total) = match self.query.find_all(req, after).await {
             Ok(res) => {
                 info!("✅ Retrieved {} products from DB", res.0.len());
                 self.complete_tracing_success(
@@ -207,14 +239,25 @@ impl ProductQueryServiceTrait for ProductQueryService {
             }
         };
 
-        let data: Vec<ProductResponse> = products.into_iter().map(ProductResponse::from).collect();
-        let total_pages = ((total - 1) / page_size as i64) + 1;
+        let next_cursor = products
+            .last()
+            .filter(|_| req.sort.trim().is_empty() && products.len() as i32 == req.page_size)
+            .and_then(|product| {
+                product.created_at.map(|created_at| {
+                    self.cursor_codec.encode(&Cursor {
+                        created_at,
+                        id: product.product_id,
+                    })
+                })
+            });
 
+        let data: Vec<ProductResponse> = products.into_iter().map(ProductResponse::from).collect();
         let pagination = Pagination {
             page,
             page_size,
             total_items: total as i32,
-            total_pages: total_pages as i32,
+            total_pages: page_count(total, page_size),
+            next_cursor,
         };
 
         let response = ApiResponsePagination {
@@ -310,13 +353,12 @@ impl ProductQueryServiceTrait for ProductQueryService {
             .into_iter()
             .map(ProductResponseDeleteAt::from)
             .collect();
-        let total_pages = ((total - 1) / page_size as i64) + 1;
-
         let pagination = Pagination {
             page,
             page_size,
             total_items: total as i32,
-            total_pages: total_pages as i32,
+            total_pages: page_count(total, page_size),
+            next_cursor: None,
         };
 
         let response = ApiResponsePagination {
@@ -415,13 +457,12 @@ impl ProductQueryServiceTrait for ProductQueryService {
             .into_iter()
             .map(ProductResponseDeleteAt::from)
             .collect();
-        let total_pages = ((total - 1) / page_size as i64) + 1;
-
         let pagination = Pagination {
             page,
             page_size,
             total_items: total as i32,
-            total_pages: total_pages as i32,
+            total_pages: page_count(total, page_size),
+            next_cursor: None,
         };
 
         let response = ApiResponsePagination {
@@ -443,6 +484,84 @@ impl ProductQueryServiceTrait for ProductQueryService {
         Ok(response)
     }
 
+    async fn find_low_stock(
+        &self,
+        req: &FindAllProducts,
+    ) -> Result<ApiResponsePagination<Vec<ProductResponse>>, ServiceError> {
+        info!(
+            "📉 Finding low-stock products | Page: {}, Size: {}, Search: '{}'",
+            req.page, req.page_size, req.search
+        );
+
+        let page = if req.page > 0 { req.page } else { 1 };
+        let page_size = if req.page_size > 0 { req.page_size } else { 10 };
+        let search = if req.search.is_empty() {
+            None
+        } else {
+            Some(req.search.clone())
+        };
+
+        let method = Method::Get;
+
+        let tracing_ctx = self.start_tracing(
+            "product_find_low_stock",
+            vec![
+                KeyValue::new("component", "product"),
+                KeyValue::new("operation", "find_low_stock"),
+                KeyValue::new("page", page.to_string()),
+                KeyValue::new("page_size", page_size.to_string()),
+                KeyValue::new("search", search.clone().unwrap_or_default()),
+            ],
+        );
+
+        let mut request = Request::new(req.clone());
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        // Not cached: stock moves with every order and the report should reflect it.
+        let (products, total) = match self.query.find_low_stock(req).await {
+            Ok(res) => {
+                info!("✅ Retrieved {} low-stock products from DB", res.0.len());
+                self.complete_tracing_success(
+                    &tracing_ctx,
+                    method.clone(),
+                    "Low-stock products retrieved from DB",
+                )
+                .await;
+                res
+            }
+            Err(e) => {
+                let msg = format!("❌ Failed to fetch low-stock products: {e:?}");
+                error!("{}", msg);
+                self.complete_tracing_error(&tracing_ctx, method.clone(), &msg)
+                    .await;
+                return Err(ServiceError::Repo(e));
+            }
+        };
+
+        let data: Vec<ProductResponse> = products.into_iter().map(ProductResponse::from).collect();
+        let pagination = Pagination {
+            page,
+            page_size,
+            total_items: total as i32,
+            total_pages: page_count(total, page_size),
+            next_cursor: None,
+        };
+
+        let response = ApiResponsePagination {
+            status: "success".to_string(),
+            message: "Low-stock products retrieved successfully".to_string(),
+            data,
+            pagination,
+        };
+
+        info!(
+            "✅ Found {} low-stock products (total: {total})",
+            response.data.len()
+        );
+
+        Ok(response)
+    }
+
     async fn find_by_id(&self, id: i32) -> Result<ApiResponse<ProductResponse>, ServiceError> {
         info!("🆔 Finding product by ID: {id}");
 
@@ -459,7 +578,7 @@ impl ProductQueryServiceTrait for ProductQueryService {
         let mut request = Request::new(id);
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        let cache_key = format!("product:find_by_id:id:{id}");
+        let cache_key = keys::by_id_key("product", id);
 
         if let Some(cached) = self
             .cache_store
@@ -513,4 +632,182 @@ impl ProductQueryServiceTrait for ProductQueryService {
 
         Ok(response)
     }
+
+    async fn find_by_ids(
+        &self,
+        ids: &[i32],
+    ) -> Result<ApiResponse<BatchResponse<ProductResponse>>, ServiceError> {
+        info!("🆔 Finding {} products by ID", ids.len());
+
+        if ids.is_empty() {
+            return Ok(ApiResponse {
+                status: "success".to_string(),
+                message: "Products retrieved successfully".to_string(),
+                data: BatchResponse::default(),
+            });
+        }
+
+        let method = Method::Get;
+        let tracing_ctx = self.start_tracing(
+            "product_find_by_ids",
+            vec![
+                KeyValue::new("component", "product"),
+                KeyValue::new("operation", "find_by_ids"),
+                KeyValue::new("product.count", ids.len().to_string()),
+            ],
+        );
+
+        let mut request = Request::new(ids.to_vec());
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let products = match self.query.find_by_ids(ids).await {
+            Ok(products) => {
+                self.complete_tracing_success(&tracing_ctx, method, "Products retrieved")
+                    .await;
+                products
+            }
+            Err(e) => {
+                error!("❌ Failed to fetch products by ids: {e:?}");
+                self.complete_tracing_error(&tracing_ctx, method, "Database error")
+                    .await;
+                return Err(ServiceError::Repo(e));
+            }
+        };
+
+        let data = split_found(
+            ids,
+            products.into_iter().map(ProductResponse::from).collect(),
+        );
+
+        info!(
+            "✅ Found {} of {} requested products",
+            data.succeeded.len(),
+            ids.len()
+        );
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Products retrieved successfully".to_string(),
+            data,
+        })
+    }
+
+    async fn find_by_external_id(
+        &self,
+        external_id: Uuid,
+    ) -> Result<ApiResponse<ProductResponse>, ServiceError> {
+        info!("🆔 Finding product by external ID: {external_id}");
+
+        let method = Method::Get;
+        let tracing_ctx = self.start_tracing(
+            "product_find_by_external_id",
+            vec![
+                KeyValue::new("component", "product"),
+                KeyValue::new("operation", "find_by_external_id"),
+                KeyValue::new("product.external_id", external_id.to_string()),
+            ],
+        );
+
+        let mut request = Request::new(external_id);
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let cache_key = keys::by_external_id_key("product", &external_id.to_string());
+
+        if let Some(cached) = self
+            .cache_store
+            .get_from_cache::<ApiResponse<ProductResponse>>(&cache_key)
+            .await
+        {
+            info!("✅ Found product {external_id} in cache");
+            self.complete_tracing_success(&tracing_ctx, method, "Product retrieved from cache")
+                .await;
+            return Ok(cached);
+        }
+
+        let product = match self.query.find_by_external_id(external_id).await {
+            Ok(Some(product)) => {
+                self.complete_tracing_success(
+                    &tracing_ctx,
+                    method.clone(),
+                    "Product retrieved from DB",
+                )
+                .await;
+                product
+            }
+            Ok(None) => {
+                error!("❌ Product not found with external ID: {external_id}");
+                self.complete_tracing_error(&tracing_ctx, method.clone(), "Product not found")
+                    .await;
+                return Err(ServiceError::Custom("Product not found".to_string()));
+            }
+            Err(e) => {
+                error!("❌ Database error while finding product {external_id}: {e:?}");
+                self.complete_tracing_error(&tracing_ctx, method.clone(), "Database error")
+                    .await;
+                return Err(ServiceError::Repo(e));
+            }
+        };
+
+        let response = ApiResponse {
+            status: "success".to_string(),
+            message: "Product retrieved successfully".to_string(),
+            data: ProductResponse::from(product),
+        };
+
+        self.cache_store
+            .set_to_cache(&cache_key, &response, Duration::minutes(5))
+            .await;
+
+        Ok(response)
+    }
+}
+
+/// Lines the found products up with the requested ids, in request order,
+/// and reports every id that matched nothing.
+fn split_found(ids: &[i32], products: Vec<ProductResponse>) -> BatchResponse<ProductResponse> {
+    let mut products: HashMap<i32, ProductResponse> =
+        products.into_iter().map(|p| (p.id, p)).collect();
+    let mut batch = BatchResponse::default();
+
+    for &id in ids {
+        match products.remove(&id) {
+            Some(product) => batch.succeed(product),
+            None => batch.fail(id, "Product not found"),
+        }
+    }
+
+    batch
+}
+
+#[cfg(test)]
+mod tests {
+    use super::split_found;
+    use crate::domain::response::product::ProductResponse;
+
+    fn product(id: i32) -> ProductResponse {
+        ProductResponse {
+            id,
+            external_id: String::new(),
+            name: format!("Product {id}"),
+            price: 1000,
+            stock: 1,
+            images: Vec::new(),
+            reorder_threshold: None,
+            category: None,
+            attributes: Default::default(),
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn missing_ids_land_in_failed() {
+        let batch = split_found(&[3, 8, 5], vec![product(5), product(3)]);
+
+        let found: Vec<i32> = batch.succeeded.iter().map(|p| p.id).collect();
+        assert_eq!(found, [3, 5]);
+        assert_eq!(batch.failed.len(), 1);
+        assert_eq!(batch.failed[0].id, 8);
+        assert_eq!(batch.failed[0].error, "Product not found");
+    }
 }
@@ -1,7 +1,11 @@
-use crate::di::{DependenciesInject, DependenciesInjectDeps};
+use crate::{
+    config::myconfig::Config,
+    di::{DependenciesInject, DependenciesInjectDeps},
+};
 use anyhow::{Context, Result};
 use shared::{
-    config::{ConnectionPool, RedisConfig, RedisPool},
+    abstract_trait::DynKafka,
+    config::{DatabasePools, Kafka, RedisConfig, RedisPool},
     utils::{SystemMetrics, run_metrics_collector},
 };
 use std::{fmt, sync::Arc};
@@ -10,6 +14,7 @@ use std::{fmt, sync::Arc};
 pub struct AppState {
     pub di_container: DependenciesInject,
     pub system_metrics: Arc<SystemMetrics>,
+    pub db_pools: DatabasePools,
 }
 
 impl fmt::Debug for AppState {
@@ -21,8 +26,12 @@ impl fmt::Debug for AppState {
 }
 
 impl AppState {
-    pub async fn new(pool: ConnectionPool) -> Result<Self> {
+    pub async fn new(pools: DatabasePools, config: Config) -> Result<Self> {
+        let kafka = Arc::new(Kafka::new(&config.kafka_broker)) as DynKafka;
         let system_metrics = Arc::new(SystemMetrics::new());
+        let max_product_images = config.max_product_images;
+        let stock_events = config.stock_events;
+        let attribute_schemas = config.attribute_schemas.clone();
 
         let config = RedisConfig::new();
 
@@ -31,8 +40,12 @@ impl AppState {
         redis.ping().await.context("Failed to ping Redis server")?;
 
         let deps = DependenciesInjectDeps {
-            pool: pool.clone(),
+            pools: pools.clone(),
             redis: redis.clone(),
+            kafka,
+            max_product_images,
+            stock_events,
+            attribute_schemas,
         };
 
         let di_container = DependenciesInject::new(deps)
@@ -43,6 +56,7 @@ impl AppState {
         Ok(Self {
             di_container,
             system_metrics,
+            db_pools: pools,
         })
     }
 }
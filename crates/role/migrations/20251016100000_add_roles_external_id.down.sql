@@ -0,0 +1,4 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS idx_roles_external_id;
+
+ALTER TABLE roles DROP COLUMN IF EXISTS external_id;
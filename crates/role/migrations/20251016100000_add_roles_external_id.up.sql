@@ -0,0 +1,5 @@
+-- Add up migration script here
+ALTER TABLE roles
+ADD COLUMN IF NOT EXISTS external_id UUID NOT NULL DEFAULT gen_random_uuid();
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_roles_external_id ON roles (external_id);
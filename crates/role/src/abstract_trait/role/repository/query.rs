@@ -1,8 +1,12 @@
-use crate::{domain::requests::role::FindAllRole, model::role::Role as RoleModel};
+use crate::{
+    domain::requests::role::FindAllRole,
+    model::{role::Role as RoleModel, user_role::UserRoleNames as UserRoleNamesModel},
+};
 use anyhow::Result;
 use async_trait::async_trait;
 use shared::errors::RepositoryError;
 use std::sync::Arc;
+use uuid::Uuid;
 
 pub type DynRoleQueryRepository = Arc<dyn RoleQueryRepositoryTrait + Send + Sync>;
 
@@ -18,6 +22,14 @@ pub trait RoleQueryRepositoryTrait {
         req: &FindAllRole,
     ) -> Result<(Vec<RoleModel>, i64), RepositoryError>;
     async fn find_by_id(&self, id: i32) -> Result<Option<RoleModel>, RepositoryError>;
+    async fn find_by_external_id(
+        &self,
+        external_id: Uuid,
+    ) -> Result<Option<RoleModel>, RepositoryError>;
     async fn find_by_user_id(&self, user_id: i32) -> Result<Vec<RoleModel>, RepositoryError>;
+    async fn find_role_names_by_user_ids(
+        &self,
+        user_ids: &[i32],
+    ) -> Result<Vec<UserRoleNamesModel>, RepositoryError>;
     async fn find_by_name(&self, name: &str) -> Result<Option<RoleModel>, RepositoryError>;
 }
@@ -3,12 +3,14 @@ use crate::domain::{
     response::{
         api::{ApiResponse, ApiResponsePagination},
         role::{RoleResponse, RoleResponseDeleteAt},
+        user_role::UserRoleNamesResponse,
     },
 };
 use anyhow::Result;
 use async_trait::async_trait;
 use shared::errors::ServiceError;
 use std::sync::Arc;
+use uuid::Uuid;
 
 pub type DynRoleQueryService = Arc<dyn RoleQueryServiceTrait + Send + Sync>;
 
@@ -27,9 +29,17 @@ pub trait RoleQueryServiceTrait {
         req: &FindAllRole,
     ) -> Result<ApiResponsePagination<Vec<RoleResponseDeleteAt>>, ServiceError>;
     async fn find_by_id(&self, id: i32) -> Result<ApiResponse<RoleResponse>, ServiceError>;
+    async fn find_by_external_id(
+        &self,
+        external_id: Uuid,
+    ) -> Result<ApiResponse<RoleResponse>, ServiceError>;
     async fn find_by_name(&self, name: String) -> Result<ApiResponse<RoleResponse>, ServiceError>;
     async fn find_by_user_id(
         &self,
         user_id: i32,
     ) -> Result<ApiResponse<Vec<RoleResponse>>, ServiceError>;
+    async fn find_role_names_by_user_ids(
+        &self,
+        user_ids: &[i32],
+    ) -> Result<ApiResponse<Vec<UserRoleNamesResponse>>, ServiceError>;
 }
@@ -9,6 +9,7 @@ pub struct ServiceConfig {
 #[derive(Debug, Clone)]
 pub struct Config {
     pub database_url: String,
+    pub database_read_url: Option<String>,
     pub jwt_secret: String,
     pub run_migrations: bool,
     pub port: u16,
@@ -21,10 +22,14 @@ impl Config {
     pub fn init() -> Result<Self> {
         let database_url =
             std::env::var("DATABASE_URL").context("Missing environment variable: DATABASE_URL")?;
+        let database_read_url = std::env::var("DATABASE_READ_URL")
+            .ok()
+            .filter(|url| !url.trim().is_empty());
         let jwt_secret =
             std::env::var("JWT_SECRET").context("Missing environment variable: JWT_SECRET")?;
-        let run_migrations_str = std::env::var("RUN_MIGRATIONS")
-            .context("Missing environment variable: RUN_MIGRATIONS")?;
+        let run_migrations_str = std::env::var("AUTO_MIGRATE")
+            .or_else(|_| std::env::var("RUN_MIGRATIONS"))
+            .context("Missing environment variable: AUTO_MIGRATE")?;
         let port_str = std::env::var("PORT").context("Missing environment variable: PORT")?;
 
         let kafka_broker = std::env::var("KAFKA").context("Missing environment variable: KAFKA")?;
@@ -34,7 +39,7 @@ impl Config {
             "false" => false,
             other => {
                 return Err(anyhow!(
-                    "RUN_MIGRATIONS must be 'true' or 'false', got '{}'",
+                    "AUTO_MIGRATE must be 'true' or 'false', got '{}'",
                     other
                 ));
             }
@@ -67,6 +72,7 @@ impl Config {
 
         Ok(Self {
             database_url,
+            database_read_url,
             jwt_secret,
             run_migrations,
             port,
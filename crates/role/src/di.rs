@@ -11,7 +11,7 @@ use crate::{
 use anyhow::{Context, Result};
 use shared::{
     cache::CacheStore,
-    config::{ConnectionPool, RedisPool},
+    config::{DatabasePools, RedisPool},
 };
 use std::{fmt, sync::Arc};
 
@@ -34,17 +34,17 @@ impl fmt::Debug for DependenciesInject {
 
 #[derive(Clone)]
 pub struct DependenciesInjectDeps {
-    pub pool: ConnectionPool,
+    pub pools: DatabasePools,
     pub redis: RedisPool,
 }
 
 impl DependenciesInject {
     pub fn new(deps: DependenciesInjectDeps) -> Result<Self> {
-        let DependenciesInjectDeps { pool, redis } = deps;
+        let DependenciesInjectDeps { pools, redis } = deps;
 
-        let role_query_repo = Arc::new(RoleQueryRepository::new(pool.clone()));
-        let role_command_repo = Arc::new(RoleCommandRepository::new(pool.clone()));
-        let user_role_repo = Arc::new(UserRoleRepository::new(pool.clone()));
+        let role_query_repo = Arc::new(RoleQueryRepository::new(pools.read.clone()));
+        let role_command_repo = Arc::new(RoleCommandRepository::new(pools.write.clone()));
+        let user_role_repo = Arc::new(UserRoleRepository::new(pools.write.clone()));
 
         let cache = Arc::new(CacheStore::new(redis.pool.clone()));
 
@@ -64,3 +64,92 @@ impl DependenciesInject {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{DependenciesInject, DependenciesInjectDeps};
+    use crate::{
+        abstract_trait::role::service::{RoleCommandServiceTrait, RoleQueryServiceTrait},
+        domain::requests::role::CreateRoleRequest,
+    };
+    use shared::config::{DatabasePools, RedisConfig, RedisPool};
+    use sqlx::{Executor, PgPool, postgres::PgPoolOptions};
+
+    async fn role_names(pool: &PgPool) -> Vec<String> {
+        sqlx::query_scalar("SELECT role_name FROM roles ORDER BY role_name")
+            .fetch_all(pool)
+            .await
+            .unwrap()
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "needs a Postgres server at DATABASE_URL"]
+    async fn queries_read_the_replica_and_commands_write_the_primary(primary: PgPool) {
+        // A second database with the same schema stands in for the replica,
+        // so it is visible which pool each repository acquired from.
+        let database: String = sqlx::query_scalar("SELECT current_database()")
+            .fetch_one(&primary)
+            .await
+            .unwrap();
+        // Trimmed so the suffix survives Postgres' 63 byte identifier limit.
+        let replica_name = format!("{}_replica", &database[..database.len().min(48)]);
+        primary
+            .execute(format!(r#"CREATE DATABASE "{replica_name}""#).as_str())
+            .await
+            .unwrap();
+
+        let replica = PgPoolOptions::new()
+            .connect_with(
+                primary
+                    .connect_options()
+                    .as_ref()
+                    .clone()
+                    .database(&replica_name),
+            )
+            .await
+            .unwrap();
+        sqlx::migrate!("./migrations").run(&replica).await.unwrap();
+        replica
+            .execute("INSERT INTO roles (role_name) VALUES ('ROLE_ON_REPLICA')")
+            .await
+            .unwrap();
+
+        let di = DependenciesInject::new(DependenciesInjectDeps {
+            pools: DatabasePools {
+                write: primary.clone(),
+                read: replica.clone(),
+            },
+            redis: RedisPool::new(&RedisConfig::new()).unwrap(),
+        })
+        .unwrap();
+
+        di.role_command
+            .create_role(&CreateRoleRequest {
+                name: "ROLE_ON_PRIMARY".to_string(),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(role_names(&primary).await, ["ROLE_ON_PRIMARY"]);
+        assert_eq!(role_names(&replica).await, ["ROLE_ON_REPLICA"]);
+
+        let found = di
+            .role_query
+            .find_by_name("ROLE_ON_REPLICA".to_string())
+            .await
+            .unwrap();
+        assert_eq!(found.data.role_name, "ROLE_ON_REPLICA");
+        assert!(
+            di.role_query
+                .find_by_name("ROLE_ON_PRIMARY".to_string())
+                .await
+                .is_err()
+        );
+
+        replica.close().await;
+        primary
+            .execute(format!(r#"DROP DATABASE "{replica_name}""#).as_str())
+            .await
+            .unwrap();
+    }
+}
@@ -8,6 +8,9 @@ pub struct Pagination {
     pub page_size: i32,
     pub total_items: i32,
     pub total_pages: i32,
+    /// Opaque token for the next page in cursor mode; absent on the last page.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
 }
 
 impl From<ProtoPagination> for Pagination {
@@ -17,6 +20,7 @@ impl From<ProtoPagination> for Pagination {
             page_size: value.page_size,
             total_items: value.total_records,
             total_pages: value.total_pages,
+            next_cursor: Some(value.next_cursor).filter(|cursor| !cursor.is_empty()),
         }
     }
 }
@@ -28,6 +32,7 @@ impl From<Pagination> for ProtoPagination {
             page_size: value.page_size,
             total_records: value.total_items,
             total_pages: value.total_pages,
+            next_cursor: value.next_cursor.unwrap_or_default(),
         }
     }
 }
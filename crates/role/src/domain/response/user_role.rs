@@ -1,5 +1,7 @@
-use crate::model::user_role::UserRole as UserRoleModel;
-use genproto::user_role::UserRoleResponse as UserRoleResponseProto;
+use crate::model::user_role::{UserRole as UserRoleModel, UserRoleNames as UserRoleNamesModel};
+use genproto::{
+    role::UserRoleNames as UserRoleNamesProto, user_role::UserRoleResponse as UserRoleResponseProto,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -37,3 +39,27 @@ impl From<UserRoleResponse> for UserRoleResponseProto {
         }
     }
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UserRoleNamesResponse {
+    pub user_id: i32,
+    pub roles: Vec<String>,
+}
+
+impl From<UserRoleNamesModel> for UserRoleNamesResponse {
+    fn from(value: UserRoleNamesModel) -> Self {
+        UserRoleNamesResponse {
+            user_id: value.user_id,
+            roles: value.roles,
+        }
+    }
+}
+
+impl From<UserRoleNamesResponse> for UserRoleNamesProto {
+    fn from(value: UserRoleNamesResponse) -> Self {
+        UserRoleNamesProto {
+            user_id: value.user_id,
+            roles: value.roles,
+        }
+    }
+}
@@ -3,12 +3,14 @@ use crate::{
 };
 use genproto::role::{
     ApiResponsePaginationRole, ApiResponsePaginationRoleDeleteAt, ApiResponseRole,
-    ApiResponsesRole, FindAllRoleRequest, FindByIdRoleRequest, FindByIdUserRoleRequest,
-    FindByNameRequest, role_query_service_server::RoleQueryService,
+    ApiResponseUserRoleNames, ApiResponsesRole, FindAllRoleRequest, FindByExternalIdRoleRequest,
+    FindByIdRoleRequest, FindByIdUserRoleRequest, FindByNameRequest, FindByUserIdsRequest,
+    role_query_service_server::RoleQueryService,
 };
 use shared::errors::AppErrorGrpc;
 use tonic::{Request, Response, Status};
 use tracing::info;
+use uuid::Uuid;
 
 #[derive(Clone)]
 pub struct RoleQueryServiceImpl {
@@ -87,6 +89,32 @@ impl RoleQueryService for RoleQueryServiceImpl {
         Ok(Response::new(reply))
     }
 
+    async fn find_by_external_id(
+        &self,
+        request: Request<FindByExternalIdRoleRequest>,
+    ) -> Result<Response<ApiResponseRole>, Status> {
+        info!("Handling gRPC request: Find Role by external ID");
+
+        let req = request.into_inner();
+
+        let external_id = Uuid::parse_str(&req.external_id)
+            .map_err(|_| Status::invalid_argument("external_id must be a valid UUID"))?;
+
+        let api_response = self
+            .query
+            .find_by_external_id(external_id)
+            .await
+            .map_err(AppErrorGrpc::from)?;
+
+        let reply = ApiResponseRole {
+            status: "success".into(),
+            message: api_response.message,
+            data: Some(api_response.data.into()),
+        };
+
+        Ok(Response::new(reply))
+    }
+
     async fn find_by_name(
         &self,
         request: Request<FindByNameRequest>,
@@ -141,6 +169,33 @@ impl RoleQueryService for RoleQueryServiceImpl {
         Ok(Response::new(reply))
     }
 
+    async fn find_role_names_by_user_ids(
+        &self,
+        request: Request<FindByUserIdsRequest>,
+    ) -> Result<Response<ApiResponseUserRoleNames>, Status> {
+        info!("Handling gRPC request: Find Role names by user IDs");
+
+        let req = request.into_inner();
+
+        let api_response = self
+            .query
+            .find_role_names_by_user_ids(&req.user_ids)
+            .await
+            .map_err(AppErrorGrpc::from)?;
+
+        let reply = ApiResponseUserRoleNames {
+            status: "success".into(),
+            message: api_response.message,
+            data: api_response.data.into_iter().map(Into::into).collect(),
+        };
+
+        info!(
+            "Successfully fetched role names for {} users",
+            req.user_ids.len()
+        );
+        Ok(Response::new(reply))
+    }
+
     async fn find_by_active(
         &self,
         request: Request<FindAllRoleRequest>,
@@ -1,11 +1,13 @@
 use anyhow::{Context, Result};
 use genproto::{
+    health::health_service_server::HealthServiceServer,
     role::{
         role_command_service_server::RoleCommandServiceServer,
         role_query_service_server::RoleQueryServiceServer,
     },
     user_role::user_role_service_server::UserRoleServiceServer,
 };
+use prometheus_client::registry::Registry;
 use role::{
     config::{myconfig::Config, server_config::ServerConfig},
     handler::{
@@ -16,12 +18,20 @@ use role::{
 };
 use shared::{
     config::ConnectionManager,
-    utils::{Telemetry, init_logger},
+    utils::{
+        ConnectionLimit, HealthGrpcService, InFlightLayer, InFlightRequests, ReadOnlyLayer,
+        RequestIdLayer, RequiredMetadata, RestartBackoff, SlowRequestLayer, Telemetry, init_logger,
+        spawn_metrics_server,
+    },
+    with_grpc_compression,
 };
 use sqlx::{Pool, Postgres};
 use std::sync::Arc;
 use tokio::sync::broadcast;
-use tonic::transport::Server;
+use tonic::{
+    service::InterceptorLayer,
+    transport::{Server, ServerTlsConfig},
+};
 use tracing::{error, info, warn};
 
 #[tokio::main]
@@ -73,20 +83,21 @@ async fn setup() -> Result<(ServerConfig, Arc<AppState>, Telemetry)> {
         is_enable_file,
     );
 
-    let db_pool = ConnectionManager::new_pool(
+    let db_pools = ConnectionManager::new_pools(
         &server_config.database_url,
+        server_config.database_read_url.as_deref(),
         config.db_min_conn,
         config.db_max_conn,
     )
     .await
     .context("Failed to initialize database pool")?;
 
-    run_migrations(&db_pool)
+    run_migrations(&db_pools.write, server_config.run_migrations)
         .await
         .context("failed to migration database")?;
 
     let state = Arc::new(
-        AppState::new(db_pool)
+        AppState::new(db_pools.clone())
             .await
             .context("Failed to create AppState")?,
     );
@@ -101,13 +112,22 @@ async fn run_servers(
     shutdown_tx: broadcast::Sender<()>,
 ) -> Result<tokio::task::JoinHandle<()>> {
     let grpc_addr = server_config.grpc_addr;
+    let grpc_tls = server_config.tls.clone();
+
+    let in_flight = InFlightRequests::default();
+    let mut registry = Registry::default();
+    in_flight.register(&mut registry);
+    spawn_metrics_server(server_config.metrics_addr, registry, state.db_pools.clone());
 
     let shutdown_tx_for_server = shutdown_tx.clone();
     let shutdown_tx_for_ctrlc = shutdown_tx.clone();
 
     let server_handle = tokio::spawn(async move {
+        let mut backoff = RestartBackoff::default();
+
         loop {
             info!("Attempting to start gRPC server on {grpc_addr}");
+            backoff.mark_started();
 
             let shutdown_rx = shutdown_tx_for_server.subscribe();
 
@@ -123,6 +143,8 @@ async fn run_servers(
                 role_query_service,
                 user_role_service,
                 grpc_addr,
+                grpc_tls.clone(),
+                in_flight.clone(),
                 shutdown_rx,
             )
             .await
@@ -132,8 +154,9 @@ async fn run_servers(
                     break;
                 }
                 Err(e) => {
-                    error!("gRPC server failed: {e}. Restarting in 5s...");
-                    tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+                    let delay = backoff.next_delay();
+                    error!("gRPC server failed: {e}. Restarting in {delay:?}...");
+                    tokio::time::sleep(delay).await;
                 }
             }
         }
@@ -183,6 +206,8 @@ async fn start_grpc_server(
     role_query_service: RoleQueryServiceImpl,
     user_role_service: UserRoleServiceImpl,
     addr: std::net::SocketAddr,
+    tls: Option<ServerTlsConfig>,
+    in_flight: InFlightRequests,
     mut shutdown_rx: broadcast::Receiver<()>,
 ) -> Result<()> {
     info!("📡 Starting gRPC server on {addr}");
@@ -192,17 +217,55 @@ async fn start_grpc_server(
         info!("gRPC server received shutdown signal");
     };
 
-    Server::builder()
-        .add_service(RoleCommandServiceServer::new(role_command_service))
-        .add_service(RoleQueryServiceServer::new(role_query_service))
-        .add_service(UserRoleServiceServer::new(user_role_service))
-        .serve_with_shutdown(addr, shutdown_future)
+    let mut server = Server::builder();
+
+    if let Some(tls) = tls {
+        server = server
+            .tls_config(tls)
+            .context("Invalid gRPC server TLS configuration")?;
+    }
+
+    let incoming = ConnectionLimit::from_env()
+        .bind(addr)
+        .await
+        .with_context(|| format!("Failed to bind gRPC server on {addr}"))?;
+
+    server
+        .layer(InFlightLayer::new(in_flight))
+        .layer(RequestIdLayer)
+        .layer(SlowRequestLayer::from_env())
+        .layer(InterceptorLayer::new(RequiredMetadata::from_env()))
+        .layer(ReadOnlyLayer::from_env())
+        .add_service(with_grpc_compression!(RoleCommandServiceServer::new(
+            role_command_service
+        )))
+        .add_service(with_grpc_compression!(RoleQueryServiceServer::new(
+            role_query_service
+        )))
+        .add_service(with_grpc_compression!(UserRoleServiceServer::new(
+            user_role_service
+        )))
+        .add_service(with_grpc_compression!(HealthServiceServer::new(
+            HealthGrpcService::new("role-service", env!("CARGO_PKG_VERSION"))
+        )))
+        .serve_with_incoming_shutdown(incoming, shutdown_future)
         .await
         .with_context(|| format!("gRPC server failed to start on {addr}"))
 }
 
-pub async fn run_migrations(pool: &Pool<Postgres>) -> anyhow::Result<()> {
-    sqlx::migrate!("./migrations").run(pool).await?;
+/// Does nothing when AUTO_MIGRATE is false, for deployments that migrate
+/// out of band. Replicas booting together are serialized by the migrator's
+/// Postgres advisory lock, so only one of them applies pending migrations.
+pub async fn run_migrations(pool: &Pool<Postgres>, enabled: bool) -> anyhow::Result<()> {
+    if !enabled {
+        info!("⏭️ AUTO_MIGRATE is disabled, skipping database migrations");
+        return Ok(());
+    }
+
+    let mut migrator = sqlx::migrate!("./migrations");
+    migrator.set_locking(true);
+
+    migrator.run(pool).await?;
 
     Ok(())
 }
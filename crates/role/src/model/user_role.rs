@@ -11,3 +11,9 @@ pub struct UserRole {
     pub updated_at: Option<NaiveDateTime>,
     pub deleted_at: Option<NaiveDateTime>,
 }
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct UserRoleNames {
+    pub user_id: i32,
+    pub roles: Vec<String>,
+}
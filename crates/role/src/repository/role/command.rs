@@ -27,7 +27,7 @@ impl RoleCommandRepositoryTrait for RoleCommandRepository {
             r#"
             INSERT INTO roles (role_name, created_at, updated_at)
             VALUES ($1, current_timestamp, current_timestamp)
-            RETURNING role_id, role_name, created_at, updated_at, deleted_at
+            RETURNING role_id, external_id, role_name, created_at, updated_at, deleted_at
             "#,
             role.name
         )
@@ -51,7 +51,7 @@ impl RoleCommandRepositoryTrait for RoleCommandRepository {
             UPDATE roles
             SET role_name = $2, updated_at = current_timestamp
             WHERE role_id = $1
-            RETURNING role_id, role_name, created_at, updated_at, deleted_at
+            RETURNING role_id, external_id, role_name, created_at, updated_at, deleted_at
             "#,
             role.id,
             role.name
@@ -76,16 +76,17 @@ impl RoleCommandRepositoryTrait for RoleCommandRepository {
             UPDATE roles
             SET deleted_at = current_timestamp
             WHERE role_id = $1
-            RETURNING role_id, role_name, created_at, updated_at, deleted_at
+            RETURNING role_id, external_id, role_name, created_at, updated_at, deleted_at
             "#,
             role_id
         )
-        .fetch_one(&mut *conn)
+        .fetch_optional(&mut *conn)
         .await
         .map_err(|err| {
             error!("❌ Failed to trash role ID {}: {:?}", role_id, err);
             RepositoryError::from(err)
-        })?;
+        })?
+        .ok_or(RepositoryError::NotFound)?;
 
         info!("🗑️ Trashed role ID {}", role_id);
         Ok(result)
@@ -99,18 +100,35 @@ impl RoleCommandRepositoryTrait for RoleCommandRepository {
             r#"
             UPDATE roles
             SET deleted_at = NULL
-            WHERE role_id = $1
-            RETURNING role_id, role_name, created_at, updated_at, deleted_at
+            WHERE role_id = $1 AND deleted_at IS NOT NULL
+            RETURNING role_id, external_id, role_name, created_at, updated_at, deleted_at
             "#,
             role_id
         )
-        .fetch_one(&mut *conn)
+        .fetch_optional(&mut *conn)
         .await
         .map_err(|err| {
             error!("❌ Failed to restore role ID {}: {:?}", role_id, err);
             RepositoryError::from(err)
         })?;
 
+        let Some(result) = result else {
+            let exists = sqlx::query_scalar!(
+                r#"
+            SELECT role_id FROM roles WHERE role_id = $1
+            "#,
+                role_id
+            )
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(RepositoryError::from)?;
+
+            return Err(match exists {
+                Some(_) => RepositoryError::NotTrashed(format!("Role {role_id} is not trashed")),
+                None => RepositoryError::NotFound,
+            });
+        };
+
         info!("♻️ Restored role ID {}", role_id);
         Ok(result)
     }
@@ -1,10 +1,12 @@
 use crate::{
     abstract_trait::role::repository::RoleQueryRepositoryTrait,
-    domain::requests::role::FindAllRole, model::role::Role as RoleModel,
+    domain::requests::role::FindAllRole,
+    model::{role::Role as RoleModel, user_role::UserRoleNames as UserRoleNamesModel},
 };
 use async_trait::async_trait;
 use shared::{config::ConnectionPool, errors::RepositoryError};
 use tracing::{error, info};
+use uuid::Uuid;
 
 pub struct RoleQueryRepository {
     db: ConnectionPool,
@@ -37,7 +39,7 @@ impl RoleQueryRepositoryTrait for RoleQueryRepository {
 
         let rows = sqlx::query!(
             r#"
-            SELECT role_id, role_name, created_at, updated_at, deleted_at, COUNT(*) OVER() AS total_count
+            SELECT role_id, external_id, role_name, created_at, updated_at, deleted_at, COUNT(*) OVER() AS total_count
             FROM roles
             WHERE deleted_at IS NULL
               AND ($1::TEXT IS NULL OR role_name ILIKE '%' || $1 || '%')
@@ -65,6 +67,7 @@ impl RoleQueryRepositoryTrait for RoleQueryRepository {
             .into_iter()
             .map(|r| RoleModel {
                 role_id: r.role_id,
+                external_id: r.external_id,
                 role_name: r.role_name,
                 created_at: r.created_at,
                 updated_at: r.updated_at,
@@ -97,7 +100,7 @@ impl RoleQueryRepositoryTrait for RoleQueryRepository {
 
         let rows = sqlx::query!(
             r#"
-            SELECT role_id, role_name, created_at, updated_at, deleted_at, COUNT(*) OVER() AS total_count
+            SELECT role_id, external_id, role_name, created_at, updated_at, deleted_at, COUNT(*) OVER() AS total_count
             FROM roles
             WHERE deleted_at IS NULL
               AND ($1::TEXT IS NULL OR role_name ILIKE '%' || $1 || '%')
@@ -125,6 +128,7 @@ impl RoleQueryRepositoryTrait for RoleQueryRepository {
             .into_iter()
             .map(|r| RoleModel {
                 role_id: r.role_id,
+                external_id: r.external_id,
                 role_name: r.role_name,
                 created_at: r.created_at,
                 updated_at: r.updated_at,
@@ -157,7 +161,7 @@ impl RoleQueryRepositoryTrait for RoleQueryRepository {
 
         let rows = sqlx::query!(
             r#"
-            SELECT role_id, role_name, created_at, updated_at, deleted_at, COUNT(*) OVER() AS total_count
+            SELECT role_id, external_id, role_name, created_at, updated_at, deleted_at, COUNT(*) OVER() AS total_count
             FROM roles
             WHERE deleted_at IS NOT NULL
               AND ($1::TEXT IS NULL OR role_name ILIKE '%' || $1 || '%')
@@ -185,6 +189,7 @@ impl RoleQueryRepositoryTrait for RoleQueryRepository {
             .into_iter()
             .map(|r| RoleModel {
                 role_id: r.role_id,
+                external_id: r.external_id,
                 role_name: r.role_name,
                 created_at: r.created_at,
                 updated_at: r.updated_at,
@@ -205,7 +210,7 @@ impl RoleQueryRepositoryTrait for RoleQueryRepository {
 
         let result = sqlx::query_as!(
             RoleModel,
-            r#"SELECT role_id, role_name, created_at, updated_at, deleted_at FROM roles WHERE role_id = $1"#,
+            r#"SELECT role_id, external_id, role_name, created_at, updated_at, deleted_at FROM roles WHERE role_id = $1"#,
             id
         )
         .fetch_optional(&mut *conn)
@@ -224,6 +229,31 @@ impl RoleQueryRepositoryTrait for RoleQueryRepository {
         Ok(result)
     }
 
+    async fn find_by_external_id(
+        &self,
+        external_id: Uuid,
+    ) -> Result<Option<RoleModel>, RepositoryError> {
+        info!("🆔 Fetching role by external ID: {}", external_id);
+
+        let mut conn = self.db.acquire().await.map_err(|e| {
+            error!("❌ DB connection failed: {:?}", e);
+            RepositoryError::from(e)
+        })?;
+
+        let result = sqlx::query_as!(
+            RoleModel,
+            r#"SELECT role_id, external_id, role_name, created_at, updated_at, deleted_at FROM roles WHERE external_id = $1"#,
+            external_id
+        )
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| {
+            error!("❌ Error fetching role by external ID {}: {:?}", external_id, e);
+            RepositoryError::from(e)
+        })?;
+
+        Ok(result)
+    }
     async fn find_by_name(&self, name: &str) -> Result<Option<RoleModel>, RepositoryError> {
         info!("🔍 Looking up role by name: {}", name);
 
@@ -234,7 +264,7 @@ impl RoleQueryRepositoryTrait for RoleQueryRepository {
 
         let result = sqlx::query_as!(
             RoleModel,
-            r#"SELECT role_id, role_name, created_at, updated_at, deleted_at FROM roles WHERE role_name = $1"#,
+            r#"SELECT role_id, external_id, role_name, created_at, updated_at, deleted_at FROM roles WHERE role_name = $1"#,
             name
         )
         .fetch_optional(&mut *conn)
@@ -263,7 +293,7 @@ impl RoleQueryRepositoryTrait for RoleQueryRepository {
 
         let rows = sqlx::query!(
             r#"
-            SELECT r.role_id, r.role_name, r.created_at, r.updated_at, r.deleted_at
+            SELECT r.role_id, r.external_id, r.role_name, r.created_at, r.updated_at, r.deleted_at
             FROM roles r
             JOIN user_roles ur ON ur.role_id = r.role_id
             WHERE ur.user_id = $1
@@ -284,6 +314,7 @@ impl RoleQueryRepositoryTrait for RoleQueryRepository {
             .into_iter()
             .map(|r| RoleModel {
                 role_id: r.role_id,
+                external_id: r.external_id,
                 role_name: r.role_name,
                 created_at: r.created_at,
                 updated_at: r.updated_at,
@@ -293,4 +324,42 @@ impl RoleQueryRepositoryTrait for RoleQueryRepository {
 
         Ok(result)
     }
+
+    async fn find_role_names_by_user_ids(
+        &self,
+        user_ids: &[i32],
+    ) -> Result<Vec<UserRoleNamesModel>, RepositoryError> {
+        info!("🔍 Fetching role names for {} users", user_ids.len());
+
+        let mut conn = self.db.acquire().await.map_err(|e| {
+            error!("❌ DB connection failed: {:?}", e);
+            RepositoryError::from(e)
+        })?;
+
+        let rows = sqlx::query_as!(
+            UserRoleNamesModel,
+            r#"
+            SELECT
+                ur.user_id,
+                array_agg(r.role_name ORDER BY r.role_name) AS "roles!"
+            FROM user_roles ur
+            JOIN roles r ON r.role_id = ur.role_id
+            WHERE ur.user_id = ANY($1)
+              AND ur.deleted_at IS NULL
+              AND r.deleted_at IS NULL
+            GROUP BY ur.user_id
+            "#,
+            user_ids
+        )
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| {
+            error!("❌ Error fetching role names by user ids: {:?}", e);
+            RepositoryError::from(e)
+        })?;
+
+        info!("✅ Retrieved role names for {} users", rows.len());
+
+        Ok(rows)
+    }
 }
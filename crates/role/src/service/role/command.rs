@@ -20,7 +20,10 @@ use opentelemetry::{
 };
 use shared::{
     errors::ServiceError,
-    utils::{MetadataInjector, Method, Metrics, Status as StatusUtils, TracingContext},
+    utils::{
+        LifecycleMetrics, LifecycleScope, MetadataInjector, Method, Metrics, Status as StatusUtils,
+        TracingContext,
+    },
 };
 use tokio::time::Instant;
 use tonic::Request;
@@ -30,13 +33,19 @@ use tracing::{error, info};
 pub struct RoleCommandService {
     pub command: DynRoleCommandRepository,
     pub metrics: Metrics,
+    pub lifecycle: LifecycleMetrics,
 }
 
 impl RoleCommandService {
     pub fn new(command: DynRoleCommandRepository) -> Result<Self> {
         let metrics = Metrics::new(global::meter("role-command-service"));
+        let lifecycle = LifecycleMetrics::new(global::meter("role-command-service"), "role");
 
-        Ok(Self { command, metrics })
+        Ok(Self {
+            command,
+            metrics,
+            lifecycle,
+        })
     }
 
     fn get_tracer(&self) -> BoxedTracer {
@@ -252,6 +261,7 @@ impl RoleCommandServiceTrait for RoleCommandService {
 
         let role_model = match self.command.trash_role(role_id).await {
             Ok(model) => {
+                self.lifecycle.soft_deleted(LifecycleScope::Single);
                 self.complete_tracing_success(
                     &tracing_ctx,
                     method.clone(),
@@ -299,6 +309,7 @@ impl RoleCommandServiceTrait for RoleCommandService {
 
         let role_model = match self.command.restore_role(role_id).await {
             Ok(model) => {
+                self.lifecycle.restored(LifecycleScope::Single);
                 self.complete_tracing_success(
                     &tracing_ctx,
                     method.clone(),
@@ -345,6 +356,7 @@ impl RoleCommandServiceTrait for RoleCommandService {
 
         match self.command.delete_role(role_id).await {
             Ok(()) => {
+                self.lifecycle.purged(LifecycleScope::Single);
                 self.complete_tracing_success(
                     &tracing_ctx,
                     method.clone(),
@@ -387,6 +399,7 @@ impl RoleCommandServiceTrait for RoleCommandService {
 
         match self.command.restore_all_role().await {
             Ok(()) => {
+                self.lifecycle.restored(LifecycleScope::All);
                 self.complete_tracing_success(
                     &tracing_ctx,
                     method.clone(),
@@ -433,6 +446,7 @@ impl RoleCommandServiceTrait for RoleCommandService {
 
         match self.command.delete_all_role().await {
             Ok(()) => {
+                self.lifecycle.purged(LifecycleScope::All);
                 self.complete_tracing_success(
                     &tracing_ctx,
                     method.clone(),
@@ -6,6 +6,7 @@ use crate::{
             api::{ApiResponse, ApiResponsePagination},
             pagination::Pagination,
             role::{RoleResponse, RoleResponseDeleteAt},
+            user_role::UserRoleNamesResponse,
         },
     },
 };
@@ -20,12 +21,13 @@ use opentelemetry::{
 use shared::{
     cache::CacheStore,
     errors::ServiceError,
-    utils::{MetadataInjector, Method, Metrics, Status as StatusUtils, TracingContext},
+    utils::{MetadataInjector, Method, Metrics, Status as StatusUtils, TracingContext, page_count},
 };
 use std::sync::Arc;
 use tokio::time::Instant;
 use tonic::Request;
 use tracing::{error, info};
+use uuid::Uuid;
 
 #[derive(Clone)]
 pub struct RoleQueryService {
@@ -206,13 +208,12 @@ impl RoleQueryServiceTrait for RoleQueryService {
         };
 
         let data: Vec<RoleResponse> = roles.into_iter().map(RoleResponse::from).collect();
-        let total_pages = ((total - 1) / page_size as i64) + 1;
-
         let pagination = Pagination {
             page,
             page_size,
             total_items: total as i32,
-            total_pages: total_pages as i32,
+            total_pages: page_count(total, page_size),
+            next_cursor: None,
         };
 
         let response = ApiResponsePagination {
@@ -309,13 +310,12 @@ impl RoleQueryServiceTrait for RoleQueryService {
 
         let data: Vec<RoleResponseDeleteAt> =
             roles.into_iter().map(RoleResponseDeleteAt::from).collect();
-        let total_pages = ((total - 1) / page_size as i64) + 1;
-
         let pagination = Pagination {
             page,
             page_size,
             total_items: total as i32,
-            total_pages: total_pages as i32,
+            total_pages: page_count(total, page_size),
+            next_cursor: None,
         };
 
         let response = ApiResponsePagination {
@@ -412,13 +412,12 @@ impl RoleQueryServiceTrait for RoleQueryService {
 
         let data: Vec<RoleResponseDeleteAt> =
             roles.into_iter().map(RoleResponseDeleteAt::from).collect();
-        let total_pages = ((total - 1) / page_size as i64) + 1;
-
         let pagination = Pagination {
             page,
             page_size,
             total_items: total as i32,
-            total_pages: total_pages as i32,
+            total_pages: page_count(total, page_size),
+            next_cursor: None,
         };
 
         let response = ApiResponsePagination {
@@ -514,6 +513,74 @@ impl RoleQueryServiceTrait for RoleQueryService {
         Ok(response)
     }
 
+    async fn find_by_external_id(
+        &self,
+        external_id: Uuid,
+    ) -> Result<ApiResponse<RoleResponse>, ServiceError> {
+        info!("🆔 Finding role by external ID: {external_id}");
+
+        let method = Method::Get;
+        let tracing_ctx = self.start_tracing(
+            "role_find_by_external_id",
+            vec![
+                KeyValue::new("component", "role"),
+                KeyValue::new("operation", "find_by_external_id"),
+                KeyValue::new("role.external_id", external_id.to_string()),
+            ],
+        );
+
+        let mut request = Request::new(external_id);
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let cache_key = format!("role:find_by_external_id:{external_id}");
+
+        if let Some(cached) = self
+            .cache_store
+            .get_from_cache::<ApiResponse<RoleResponse>>(&cache_key)
+            .await
+        {
+            info!("✅ Found role {external_id} in cache");
+            self.complete_tracing_success(&tracing_ctx, method, "Role retrieved from cache")
+                .await;
+            return Ok(cached);
+        }
+
+        let role = match self.query.find_by_external_id(external_id).await {
+            Ok(Some(role)) => {
+                self.complete_tracing_success(
+                    &tracing_ctx,
+                    method.clone(),
+                    "Role retrieved from DB",
+                )
+                .await;
+                role
+            }
+            Ok(None) => {
+                error!("❌ Role not found with external ID: {external_id}");
+                self.complete_tracing_error(&tracing_ctx, method.clone(), "Role not found")
+                    .await;
+                return Err(ServiceError::Custom("Role not found".to_string()));
+            }
+            Err(e) => {
+                error!("❌ Database error while finding role {external_id}: {e:?}");
+                self.complete_tracing_error(&tracing_ctx, method.clone(), "Database error")
+                    .await;
+                return Err(ServiceError::Repo(e));
+            }
+        };
+
+        let response = ApiResponse {
+            status: "success".to_string(),
+            message: "Role retrieved successfully".to_string(),
+            data: RoleResponse::from(role),
+        };
+
+        self.cache_store
+            .set_to_cache(&cache_key, &response, Duration::minutes(5))
+            .await;
+
+        Ok(response)
+    }
     async fn find_by_name(&self, name: String) -> Result<ApiResponse<RoleResponse>, ServiceError> {
         info!("🔤 Finding role by name: {name}");
 
@@ -654,4 +721,122 @@ impl RoleQueryServiceTrait for RoleQueryService {
 
         Ok(response)
     }
+
+    async fn find_role_names_by_user_ids(
+        &self,
+        user_ids: &[i32],
+    ) -> Result<ApiResponse<Vec<UserRoleNamesResponse>>, ServiceError> {
+        info!("👥 Finding role names for {} users", user_ids.len());
+
+        let method = Method::Get;
+        let tracing_ctx = self.start_tracing(
+            "find_role_names_by_user_ids",
+            vec![
+                KeyValue::new("component", "role"),
+                KeyValue::new("operation", "find_role_names_by_user_ids"),
+                KeyValue::new("user.count", user_ids.len().to_string()),
+            ],
+        );
+
+        let mut request = Request::new(user_ids.to_vec());
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let rows = match self.query.find_role_names_by_user_ids(user_ids).await {
+            Ok(rows) => {
+                self.complete_tracing_success(&tracing_ctx, method, "User role names retrieved")
+                    .await;
+                rows
+            }
+            Err(e) => {
+                error!("❌ Failed to fetch role names by user ids: {e:?}");
+                self.complete_tracing_error(&tracing_ctx, method, "Database error")
+                    .await;
+                return Err(ServiceError::Repo(e));
+            }
+        };
+
+        let data: Vec<UserRoleNamesResponse> =
+            rows.into_iter().map(UserRoleNamesResponse::from).collect();
+
+        info!("✅ Found role names for {} users", data.len());
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "User role names retrieved successfully".to_string(),
+            data,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RoleQueryService;
+    use crate::{
+        abstract_trait::role::service::RoleQueryServiceTrait, domain::requests::role::FindAllRole,
+        repository::role::RoleQueryRepository,
+    };
+    use shared::{
+        cache::CacheStore,
+        config::{RedisConfig, RedisPool},
+    };
+    use sqlx::PgPool;
+    use std::sync::Arc;
+
+    fn service(pool: PgPool) -> RoleQueryService {
+        // Never connected: cache reads miss and writes are dropped, so every
+        // call goes to the database.
+        let redis = RedisPool::new(&RedisConfig::new()).unwrap();
+        RoleQueryService::new(
+            Arc::new(RoleQueryRepository::new(pool)),
+            Arc::new(CacheStore::new(redis.pool)),
+        )
+        .unwrap()
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "needs a Postgres server at DATABASE_URL"]
+    async fn role_names_are_grouped_per_user_and_pages_are_counted(pool: PgPool) {
+        sqlx::raw_sql(
+            "INSERT INTO roles (role_id, role_name, deleted_at) VALUES
+                 (1, 'ROLE_ADMIN', NULL),
+                 (2, 'ROLE_USER', NULL),
+                 (3, 'ROLE_SELLER', NULL),
+                 (4, 'ROLE_RETIRED', NOW());
+             INSERT INTO user_roles (user_id, role_id, deleted_at) VALUES
+                 (10, 2, NULL),
+                 (10, 1, NULL),
+                 (10, 4, NULL),
+                 (20, 3, NOW());",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let service = service(pool);
+
+        // User 10 has every live role; 20 only a revoked one and 30 none, so
+        // both are left out and the caller fills in an empty list.
+        let names = service
+            .find_role_names_by_user_ids(&[10, 20, 30])
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(names.len(), 1);
+        assert_eq!(names[0].user_id, 10);
+        assert_eq!(names[0].roles, ["ROLE_ADMIN", "ROLE_USER"]);
+
+        let page = |page| FindAllRole {
+            page,
+            page_size: 2,
+            search: String::new(),
+        };
+        let first = service.find_all(&page(1)).await.unwrap();
+        assert_eq!(first.data.len(), 2);
+        assert_eq!(first.pagination.total_items, 3);
+        assert_eq!(first.pagination.total_pages, 2);
+
+        let second = service.find_all(&page(2)).await.unwrap();
+        assert_eq!(second.data.len(), 1);
+        assert_eq!(second.pagination.total_items, 3);
+    }
 }
@@ -1,7 +1,7 @@
 use crate::di::{DependenciesInject, DependenciesInjectDeps};
 use anyhow::{Context, Result};
 use shared::{
-    config::{ConnectionPool, RedisConfig, RedisPool},
+    config::{DatabasePools, RedisConfig, RedisPool},
     utils::{SystemMetrics, run_metrics_collector},
 };
 use std::{fmt, sync::Arc};
@@ -10,6 +10,7 @@ use std::{fmt, sync::Arc};
 pub struct AppState {
     pub di_container: DependenciesInject,
     pub system_metrics: Arc<SystemMetrics>,
+    pub db_pools: DatabasePools,
 }
 
 impl fmt::Debug for AppState {
@@ -21,7 +22,7 @@ impl fmt::Debug for AppState {
 }
 
 impl AppState {
-    pub async fn new(pool: ConnectionPool) -> Result<Self> {
+    pub async fn new(pools: DatabasePools) -> Result<Self> {
         let system_metrics = Arc::new(SystemMetrics::new());
 
         let config = RedisConfig::new();
@@ -30,7 +31,7 @@ impl AppState {
         redis.ping().await.context("Failed to ping Redis server")?;
 
         let deps = DependenciesInjectDeps {
-            pool: pool.clone(),
+            pools: pools.clone(),
             redis: redis.clone(),
         };
 
@@ -42,6 +43,7 @@ impl AppState {
         Ok(Self {
             di_container,
             system_metrics,
+            db_pools: pools,
         })
     }
 }
@@ -1,6 +1,7 @@
-use crate::errors::ServiceError;
+use crate::{config::Claims, errors::ServiceError};
 use anyhow::Result;
 use async_trait::async_trait;
+use chrono::Duration;
 use std::sync::Arc;
 
 pub type DynJwtService = Arc<dyn JwtServiceTrait + Send + Sync>;
@@ -8,5 +9,18 @@ pub type DynJwtService = Arc<dyn JwtServiceTrait + Send + Sync>;
 #[async_trait]
 pub trait JwtServiceTrait: Send + Sync + std::fmt::Debug {
     fn generate_token(&self, user_id: i64, token_type: &str) -> Result<String, ServiceError>;
+    /// Like `generate_token`, with the user's role names embedded in the
+    /// claims.
+    fn generate_token_with_roles(
+        &self,
+        user_id: i64,
+        token_type: &str,
+        roles: &[String],
+    ) -> Result<String, ServiceError>;
+    /// How long a newly signed refresh token stays valid.
+    fn refresh_token_ttl(&self) -> Duration;
     fn verify_token(&self, token: &str, expected_type: &str) -> Result<i64, ServiceError>;
+    /// Like `verify_token`, returning all of the token's claims.
+    fn verify_token_claims(&self, token: &str, expected_type: &str)
+    -> Result<Claims, ServiceError>;
 }
@@ -2,8 +2,10 @@ mod email;
 mod hashing;
 mod jwt;
 mod kafka;
+mod password_breach;
 
 pub use self::email::{DynEmailService, EmailServiceTrait};
 pub use self::hashing::{DynHashing, HashingTrait};
 pub use self::jwt::{DynJwtService, JwtServiceTrait};
 pub use self::kafka::{DynKafka, KafkaTrait};
+pub use self::password_breach::{DynPasswordBreachChecker, PasswordBreachCheckerTrait};
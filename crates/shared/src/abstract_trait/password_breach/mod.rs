@@ -0,0 +1,9 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+
+pub type DynPasswordBreachChecker = Arc<dyn PasswordBreachCheckerTrait + Send + Sync>;
+
+#[async_trait]
+pub trait PasswordBreachCheckerTrait {
+    async fn is_breached(&self, password: &str) -> bool;
+}
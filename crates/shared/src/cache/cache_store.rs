@@ -1,18 +1,106 @@
+use super::keys;
+use crate::config::{CacheKeyVersions, CacheTtlConfig};
 use chrono::Duration;
 use deadpool_redis::{Connection, Pool};
+use opentelemetry::{KeyValue, global, metrics::Counter};
 use serde::{Serialize, de::DeserializeOwned};
 use std::sync::Arc;
 use tracing::{debug, error, warn};
 
+/// Hit/miss/error counts for cache reads and failed writes, labelled by the
+/// key's leading segment (e.g. `product` for `product:find_by_id:1`) so the
+/// label set stays small.
+#[derive(Clone)]
+struct CacheMetrics {
+    lookups: Counter<u64>,
+    write_errors: Counter<u64>,
+}
+
+impl CacheMetrics {
+    fn new() -> Self {
+        let meter = global::meter("cache-store");
+
+        let lookups = meter
+            .u64_counter("cache.lookups.total")
+            .with_description("Cache reads by result: hit, miss or error")
+            .build();
+
+        let write_errors = meter
+            .u64_counter("cache.write_errors.total")
+            .with_description("Cache writes and deletes that failed")
+            .build();
+
+        Self {
+            lookups,
+            write_errors,
+        }
+    }
+
+    fn prefix(key: &str) -> String {
+        key.split(':').next().unwrap_or(key).to_string()
+    }
+
+    fn lookup(&self, key: &str, result: &'static str) {
+        self.lookups.add(
+            1,
+            &[
+                KeyValue::new("prefix", Self::prefix(key)),
+                KeyValue::new("result", result),
+            ],
+        );
+    }
+
+    fn write_error(&self, key: &str, operation: &'static str) {
+        self.write_errors.add(
+            1,
+            &[
+                KeyValue::new("prefix", Self::prefix(key)),
+                KeyValue::new("operation", operation),
+            ],
+        );
+    }
+}
+
+/// Redis-backed cache shared by every replica. Values are stored as JSON
+/// with a per-key TTL; Redis failures are logged, counted and treated as a
+/// miss so a cache outage never fails the request.
 #[derive(Clone)]
 pub struct CacheStore {
     redis_pool: Arc<Pool>,
+    ttl: CacheTtlConfig,
+    key_versions: CacheKeyVersions,
+    metrics: CacheMetrics,
 }
 
 impl CacheStore {
     pub fn new(redis_pool: Pool) -> Self {
+        Self::with_ttl_config(redis_pool, CacheTtlConfig::new())
+    }
+
+    pub fn with_ttl_config(redis_pool: Pool, ttl: CacheTtlConfig) -> Self {
         Self {
             redis_pool: Arc::new(redis_pool),
+            ttl,
+            key_versions: CacheKeyVersions::new(),
+            metrics: CacheMetrics::new(),
+        }
+    }
+
+    /// Falls back to the default TTL when none (or a non-positive one) is
+    /// given and clamps anything above the configured maximum.
+    fn effective_ttl(&self, key: &str, requested: Option<Duration>) -> Duration {
+        match requested {
+            Some(ttl) if ttl > self.ttl.max_ttl => {
+                warn!(
+                    "Clamping TTL for cache key '{}' from {}s to max {}s",
+                    key,
+                    ttl.num_seconds(),
+                    self.ttl.max_ttl.num_seconds()
+                );
+                self.ttl.max_ttl
+            }
+            Some(ttl) if ttl > Duration::zero() => ttl,
+            _ => self.ttl.default_ttl,
         }
     }
 
@@ -30,25 +118,55 @@ impl CacheStore {
     where
         T: DeserializeOwned,
     {
-        let mut conn = self.get_conn().await?;
+        let raw_key = key;
+        let key = &self.key_versions.apply(key);
+        let Some(mut conn) = self.get_conn().await else {
+            self.metrics.lookup(raw_key, "error");
+            return None;
+        };
         let result: redis::RedisResult<Option<String>> =
             redis::cmd("GET").arg(key).query_async(&mut conn).await;
 
         match result {
             Ok(Some(data)) => match serde_json::from_str::<T>(&data) {
-                Ok(parsed) => Some(parsed),
+                Ok(parsed) => {
+                    self.metrics.lookup(raw_key, "hit");
+                    Some(parsed)
+                }
                 Err(e) => {
                     error!(
                         "Failed to deserialize cached value for key '{}': {:?}",
                         key, e
                     );
+                    self.metrics.lookup(raw_key, "error");
                     None
                 }
             },
             Ok(None) => {
                 warn!("Cache miss for key: {key}");
+                self.metrics.lookup(raw_key, "miss");
                 None
             }
+            Err(e) => {
+                error!("Redis get error for key '{}': {:?}", key, e);
+                self.metrics.lookup(raw_key, "error");
+                None
+            }
+        }
+    }
+
+    /// Like `get_from_cache`, but quiet on a miss; for flags that are usually unset.
+    pub async fn peek<T>(&self, key: &str) -> Option<T>
+    where
+        T: DeserializeOwned,
+    {
+        let key = &self.key_versions.apply(key);
+        let mut conn = self.get_conn().await?;
+        let result: redis::RedisResult<Option<String>> =
+            redis::cmd("GET").arg(key).query_async(&mut conn).await;
+
+        match result {
+            Ok(data) => data.and_then(|data| serde_json::from_str::<T>(&data).ok()),
             Err(e) => {
                 error!("Redis get error for key '{}': {:?}", key, e);
                 None
@@ -60,41 +178,230 @@ impl CacheStore {
     where
         T: Serialize,
     {
+        self.write_to_cache(key, data, Some(expiration)).await;
+    }
+
+    /// Writes with the configured default TTL.
+    pub async fn set_to_cache_default_ttl<T>(&self, key: &str, data: &T)
+    where
+        T: Serialize,
+    {
+        self.write_to_cache(key, data, None).await;
+    }
+
+    async fn write_to_cache<T>(&self, key: &str, data: &T, expiration: Option<Duration>)
+    where
+        T: Serialize,
+    {
+        let raw_key = key;
+        let key = &self.key_versions.apply(key);
+        let expiration = self.effective_ttl(key, expiration);
+
         let json_data = match serde_json::to_string(data) {
             Ok(json) => json,
             Err(e) => {
                 error!("Failed to serialize data for key '{}': {:?}", key, e);
+                self.metrics.write_error(raw_key, "set");
                 return;
             }
         };
 
-        if let Some(mut conn) = self.get_conn().await {
-            let result: redis::RedisResult<()> = redis::pipe()
-                .cmd("SET")
-                .arg(key)
-                .arg(&json_data)
-                .ignore()
-                .cmd("EXPIRE")
-                .arg(key)
-                .arg(expiration.num_seconds() as usize)
-                .query_async(&mut conn)
-                .await;
+        let Some(mut conn) = self.get_conn().await else {
+            self.metrics.write_error(raw_key, "set");
+            return;
+        };
+
+        let result: redis::RedisResult<()> = redis::pipe()
+            .cmd("SET")
+            .arg(key)
+            .arg(&json_data)
+            .ignore()
+            .cmd("EXPIRE")
+            .arg(key)
+            .arg(expiration.num_seconds() as usize)
+            .query_async(&mut conn)
+            .await;
 
-            match result {
-                Ok(_) => debug!("Cached key '{}' with TTL {:?}", key, expiration),
-                Err(e) => error!("Failed to set cache key '{}': {:?}", key, e),
+        match result {
+            Ok(_) => debug!("Cached key '{}' with TTL {:?}", key, expiration),
+            Err(e) => {
+                error!("Failed to set cache key '{}': {:?}", key, e);
+                self.metrics.write_error(raw_key, "set");
             }
         }
     }
 
     pub async fn delete_from_cache(&self, key: &str) {
-        if let Some(mut conn) = self.get_conn().await
-            && let Err(e) = redis::cmd("DEL")
-                .arg(key)
-                .query_async::<()>(&mut conn)
-                .await
+        let raw_key = key;
+        let key = &self.key_versions.apply(key);
+        let Some(mut conn) = self.get_conn().await else {
+            self.metrics.write_error(raw_key, "delete");
+            return;
+        };
+
+        if let Err(e) = redis::cmd("DEL")
+            .arg(key)
+            .query_async::<()>(&mut conn)
+            .await
         {
             error!("Failed to delete key '{}': {:?}", key, e);
+            self.metrics.write_error(raw_key, "delete");
+        }
+    }
+
+    /// Deletes every key starting with `prefix`, walking the keyspace with
+    /// `SCAN` so large caches don't block Redis the way `KEYS` would.
+    pub async fn delete_by_prefix(&self, prefix: &str) {
+        let pattern = format!("{}*", self.key_versions.apply(prefix));
+        let Some(mut conn) = self.get_conn().await else {
+            self.metrics.write_error(prefix, "delete");
+            return;
+        };
+
+        let mut cursor: u64 = 0;
+        loop {
+            let result: redis::RedisResult<(u64, Vec<String>)> = redis::cmd("SCAN")
+                .arg(cursor)
+                .arg("MATCH")
+                .arg(&pattern)
+                .arg("COUNT")
+                .arg(500)
+                .query_async(&mut conn)
+                .await;
+
+            let (next, keys) = match result {
+                Ok(page) => page,
+                Err(e) => {
+                    error!("Failed to scan keys matching '{}': {:?}", pattern, e);
+                    self.metrics.write_error(prefix, "delete");
+                    return;
+                }
+            };
+
+            if !keys.is_empty()
+                && let Err(e) = redis::cmd("DEL")
+                    .arg(&keys)
+                    .query_async::<()>(&mut conn)
+                    .await
+            {
+                error!("Failed to delete keys matching '{}': {:?}", pattern, e);
+                self.metrics.write_error(prefix, "delete");
+                return;
+            }
+
+            if next == 0 {
+                break;
+            }
+            cursor = next;
+        }
+
+        debug!("Invalidated cache keys matching '{}'", pattern);
+    }
+
+    /// Drops one record's by-id and by-external-id entries plus every cached
+    /// list page of its entity, after a write that changed that record.
+    pub async fn invalidate_record(&self, entity: &str, id: i32, external_id: Option<&str>) {
+        self.delete_from_cache(&keys::by_id_key(entity, id)).await;
+
+        if let Some(external_id) = external_id {
+            self.delete_from_cache(&keys::by_external_id_key(entity, external_id))
+                .await;
+        }
+
+        self.invalidate_lists(entity).await;
+    }
+
+    /// Drops every cached list page of `entity`, e.g. after a create.
+    pub async fn invalidate_lists(&self, entity: &str) {
+        for query in keys::LIST_QUERIES {
+            self.delete_by_prefix(&keys::list_prefix(entity, query))
+                .await;
+        }
+    }
+
+    /// Drops everything cached for `entity`, for writes that touch records
+    /// whose keys aren't known, such as bulk restores and deletes.
+    pub async fn invalidate_entity(&self, entity: &str) {
+        self.delete_by_prefix(&format!("{entity}:")).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CacheStore;
+    use crate::config::{CacheKeyVersions, CacheTtlConfig, RedisConfig, RedisPool};
+    use chrono::{Duration, Utc};
+
+    #[test]
+    fn writes_fall_back_to_the_default_ttl_and_clamp_to_the_maximum() {
+        // Never connected; only the TTL arithmetic is exercised.
+        let pool = RedisPool::new(&RedisConfig::new()).unwrap().pool;
+        let store = CacheStore::with_ttl_config(
+            pool,
+            CacheTtlConfig {
+                default_ttl: Duration::minutes(5),
+                max_ttl: Duration::hours(1),
+            },
+        );
+
+        for requested in [None, Some(Duration::zero()), Some(Duration::seconds(-1))] {
+            assert_eq!(store.effective_ttl("k", requested), Duration::minutes(5));
         }
+        assert_eq!(
+            store.effective_ttl("k", Some(Duration::seconds(90))),
+            Duration::seconds(90)
+        );
+        assert_eq!(
+            store.effective_ttl("k", Some(Duration::days(7))),
+            Duration::hours(1)
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a Redis server at REDIS_HOST"]
+    async fn bumping_an_entity_version_misses_its_old_entries() {
+        let pool = RedisPool::new(&RedisConfig::new()).unwrap().pool;
+        let store = |versions: &str| {
+            let mut store = CacheStore::new(pool.clone());
+            store.key_versions = CacheKeyVersions::parse(versions);
+            store
+        };
+        // Unique per run, so entries left over in Redis don't leak in.
+        let id = Utc::now().timestamp_nanos_opt().unwrap();
+        let product_key = format!("product:find_by_id:id:{id}");
+        let order_key = format!("order:find_by_id:id:{id}");
+
+        let before = store("product=1");
+        before
+            .set_to_cache(&product_key, &"old shape", Duration::minutes(1))
+            .await;
+        before
+            .set_to_cache(&order_key, &"order", Duration::minutes(1))
+            .await;
+        assert_eq!(
+            before
+                .get_from_cache::<String>(&product_key)
+                .await
+                .as_deref(),
+            Some("old shape")
+        );
+
+        let after = store("product=2");
+        assert_eq!(after.get_from_cache::<String>(&product_key).await, None);
+        assert_eq!(
+            after.get_from_cache::<String>(&order_key).await.as_deref(),
+            Some("order")
+        );
+
+        after
+            .set_to_cache(&product_key, &"new shape", Duration::minutes(1))
+            .await;
+        assert_eq!(
+            after
+                .get_from_cache::<String>(&product_key)
+                .await
+                .as_deref(),
+            Some("new shape")
+        );
     }
 }
@@ -0,0 +1,22 @@
+//! Cache keys follow `<entity>:<query>:<params>`, e.g.
+//! `product:find_by_id:id:1` or `order:find_active:page:1:size:10:search:`.
+//! The entity segment is what `CacheKeyVersions` bumps and what metrics are
+//! labelled by; the query segment is the read method that filled the entry.
+//! Command services invalidate through these helpers so writes and reads
+//! agree on the names.
+
+/// Paginated list reads; their keys embed page, size and filters, so a
+/// write drops every page by prefix.
+pub const LIST_QUERIES: [&str; 4] = ["find_all", "find_active", "find_trashed", "find_by_user_id"];
+
+pub fn by_id_key(entity: &str, id: i32) -> String {
+    format!("{entity}:find_by_id:id:{id}")
+}
+
+pub fn by_external_id_key(entity: &str, external_id: &str) -> String {
+    format!("{entity}:find_by_external_id:{external_id}")
+}
+
+pub fn list_prefix(entity: &str, query: &str) -> String {
+    format!("{entity}:{query}:")
+}
@@ -1,4 +1,5 @@
 mod cache_store;
+pub mod keys;
 mod rate_limiter;
 mod session;
 
@@ -0,0 +1,135 @@
+use chrono::Duration;
+use std::{collections::HashMap, env};
+
+/// TTL guardrails applied by `CacheStore` on every write.
+#[derive(Debug, Clone, Copy)]
+pub struct CacheTtlConfig {
+    pub default_ttl: Duration,
+    pub max_ttl: Duration,
+}
+
+impl Default for CacheTtlConfig {
+    fn default() -> Self {
+        Self {
+            default_ttl: Duration::minutes(5),
+            max_ttl: Duration::hours(24),
+        }
+    }
+}
+
+impl CacheTtlConfig {
+    pub fn new() -> Self {
+        Self::parse(
+            env::var("CACHE_DEFAULT_TTL_SECONDS").ok().as_deref(),
+            env::var("CACHE_MAX_TTL_SECONDS").ok().as_deref(),
+        )
+    }
+
+    /// Missing, unparsable or non-positive values keep the built-in
+    /// defaults, and the default TTL never exceeds the maximum.
+    pub fn parse(default_ttl_seconds: Option<&str>, max_ttl_seconds: Option<&str>) -> Self {
+        let defaults = Self::default();
+        let seconds = |raw: Option<&str>| {
+            raw.and_then(|v| v.parse::<i64>().ok())
+                .filter(|v| *v > 0)
+                .map(Duration::seconds)
+        };
+
+        let default_ttl = seconds(default_ttl_seconds).unwrap_or(defaults.default_ttl);
+        let max_ttl = seconds(max_ttl_seconds).unwrap_or(defaults.max_ttl);
+
+        Self {
+            default_ttl: default_ttl.min(max_ttl),
+            max_ttl,
+        }
+    }
+}
+
+/// Per-entity version folded into cache keys, from `CACHE_KEY_VERSIONS`
+/// (`product=2,order=3`). Bumping an entity's version after its cached shape
+/// changes makes every older entry unreachable, so no manual flush is needed.
+#[derive(Debug, Clone, Default)]
+pub struct CacheKeyVersions {
+    versions: HashMap<String, u32>,
+}
+
+impl CacheKeyVersions {
+    pub fn new() -> Self {
+        Self::parse(&env::var("CACHE_KEY_VERSIONS").unwrap_or_default())
+    }
+
+    pub fn parse(raw: &str) -> Self {
+        let versions = raw
+            .split(',')
+            .filter_map(|entry| {
+                let (entity, version) = entry.split_once('=')?;
+                let entity = entity.trim();
+                let version = version.trim().parse::<u32>().ok()?;
+                (!entity.is_empty()).then(|| (entity.to_string(), version))
+            })
+            .collect();
+
+        Self { versions }
+    }
+
+    /// Keys look like `product:find_by_id:id:1`; the entity is the segment
+    /// before the first `:`. Entities without a configured version keep their
+    /// key unchanged, so existing entries survive enabling this.
+    pub fn apply(&self, key: &str) -> String {
+        let (entity, rest) = key.split_once(':').unwrap_or((key, ""));
+
+        match self.versions.get(entity) {
+            Some(version) => format!("{entity}:v{version}:{rest}"),
+            None => key.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CacheKeyVersions, CacheTtlConfig};
+    use chrono::Duration;
+
+    #[test]
+    fn unset_or_invalid_values_keep_the_defaults() {
+        for raw in [None, Some(""), Some("soon"), Some("0"), Some("-30")] {
+            let config = CacheTtlConfig::parse(raw, raw);
+            assert_eq!(config.default_ttl, Duration::minutes(5));
+            assert_eq!(config.max_ttl, Duration::hours(24));
+        }
+    }
+
+    #[test]
+    fn default_ttl_is_clamped_to_the_maximum() {
+        let config = CacheTtlConfig::parse(Some("120"), Some("3600"));
+        assert_eq!(config.default_ttl, Duration::seconds(120));
+        assert_eq!(config.max_ttl, Duration::hours(1));
+
+        let config = CacheTtlConfig::parse(Some("7200"), Some("3600"));
+        assert_eq!(config.default_ttl, Duration::hours(1));
+
+        // A lowered maximum also pulls the built-in default down with it.
+        let config = CacheTtlConfig::parse(None, Some("60"));
+        assert_eq!(config.default_ttl, Duration::seconds(60));
+    }
+
+    #[test]
+    fn versions_are_folded_in_per_entity() {
+        let v2 = CacheKeyVersions::parse("product=2, order=3, bogus, user=x");
+        let v3 = CacheKeyVersions::parse("product=3");
+
+        assert_eq!(
+            v2.apply("product:find_by_id:id:1"),
+            "product:v2:find_by_id:id:1"
+        );
+        assert_eq!(
+            v3.apply("product:find_by_id:id:1"),
+            "product:v3:find_by_id:id:1"
+        );
+        assert_eq!(v2.apply("order:find_all"), "order:v3:find_all");
+
+        // Unversioned or malformed entities keep their key as is.
+        assert_eq!(v2.apply("user:find_by_id:id:1"), "user:find_by_id:id:1");
+        assert_eq!(v3.apply("order:find_all"), "order:find_all");
+    }
+}
@@ -1,9 +1,27 @@
 use std::time::Duration;
 
 use sqlx::{Pool, Postgres, postgres::PgPoolOptions};
+use tracing::info;
 
 pub type ConnectionPool = Pool<Postgres>;
 
+/// Primary pool for writes and a pool for reads, which is the primary itself
+/// when no read replica is configured.
+#[derive(Clone, Debug)]
+pub struct DatabasePools {
+    pub write: ConnectionPool,
+    pub read: ConnectionPool,
+}
+
+impl DatabasePools {
+    pub fn single(pool: ConnectionPool) -> Self {
+        Self {
+            read: pool.clone(),
+            write: pool,
+        }
+    }
+}
+
 pub struct ConnectionManager;
 
 impl ConnectionManager {
@@ -25,4 +43,22 @@ impl ConnectionManager {
 
         Ok(pool)
     }
+
+    pub async fn new_pools(
+        primary_url: &str,
+        read_url: Option<&str>,
+        min_conn: u32,
+        max_conn: u32,
+    ) -> anyhow::Result<DatabasePools> {
+        let write = Self::new_pool(primary_url, min_conn, max_conn).await?;
+
+        match read_url {
+            Some(url) if url != primary_url => {
+                info!("📖 Using separate read replica pool for queries");
+                let read = Self::new_pool(url, min_conn, max_conn).await?;
+                Ok(DatabasePools { write, read })
+            }
+            _ => Ok(DatabasePools::single(write)),
+        }
+    }
 }
@@ -1,38 +1,203 @@
 use crate::{abstract_trait::JwtServiceTrait, errors::ServiceError};
-use anyhow::Result;
+use anyhow::{Context, Result, bail};
 use async_trait::async_trait;
-use chrono::{Duration, Utc};
-use jsonwebtoken::{DecodingKey, EncodingKey, Header, Validation, decode, encode};
+use chrono::{DateTime, Duration, Utc};
+use jsonwebtoken::{
+    DecodingKey, EncodingKey, Header, TokenData, Validation, decode, encode, errors::ErrorKind,
+};
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
+/// JWT payload shared by access and refresh tokens:
+///
+/// - `user_id`: the subject.
+/// - `exp` / `iat`: expiry and issue time, in Unix seconds.
+/// - `token_type`: `"access"` or `"refresh"`; verification rejects the other.
+/// - `roles`: role names only (e.g. `["ADMIN"]`), never full role objects,
+///   so the token stays small. Only access tokens carry them.
+/// - `jti`: a random id. Only refresh tokens carry one, so two issued to the
+///   same user in the same second are still distinct rows.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Claims {
     pub user_id: i64,
     pub exp: usize,
     pub iat: usize,
     pub token_type: String,
+    /// Role names of the user when the token was issued; tokens signed
+    /// before roles were added carry none.
+    #[serde(default)]
+    pub roles: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jti: Option<String>,
 }
 
 impl Claims {
-    pub fn new(user_id: i64, exp: usize, iat: usize, token_type: String) -> Self {
+    pub fn new(
+        user_id: i64,
+        exp: usize,
+        iat: usize,
+        token_type: String,
+        roles: Vec<String>,
+    ) -> Self {
         Claims {
             user_id,
             exp,
             iat,
             token_type,
+            roles,
+            jti: None,
         }
     }
 }
 
+/// Secret being rotated out. Tokens it signed keep verifying until
+/// `valid_until`; nothing new is signed with it.
+#[derive(Debug, Clone)]
+pub struct PreviousJwtKey {
+    pub secret: String,
+    pub valid_until: DateTime<Utc>,
+}
+
+impl PreviousJwtKey {
+    /// Reads `JWT_PREVIOUS_SECRET` and `JWT_PREVIOUS_SECRET_VALID_UNTIL`
+    /// (RFC 3339). Set both while rotating, and neither otherwise.
+    pub fn from_env() -> Result<Option<Self>> {
+        let secret = std::env::var("JWT_PREVIOUS_SECRET")
+            .ok()
+            .filter(|s| !s.is_empty());
+        let valid_until = std::env::var("JWT_PREVIOUS_SECRET_VALID_UNTIL")
+            .ok()
+            .filter(|s| !s.trim().is_empty());
+
+        match (secret, valid_until) {
+            (None, None) => Ok(None),
+            (Some(secret), Some(valid_until)) => {
+                let valid_until = DateTime::parse_from_rfc3339(valid_until.trim())
+                    .context("JWT_PREVIOUS_SECRET_VALID_UNTIL must be an RFC 3339 timestamp")?
+                    .with_timezone(&Utc);
+
+                Ok(Some(Self {
+                    secret,
+                    valid_until,
+                }))
+            }
+            _ => bail!(
+                "JWT_PREVIOUS_SECRET and JWT_PREVIOUS_SECRET_VALID_UNTIL must be set together"
+            ),
+        }
+    }
+}
+
+/// How long access and refresh tokens stay valid after they are signed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TokenTtl {
+    pub access: Duration,
+    pub refresh: Duration,
+}
+
+impl Default for TokenTtl {
+    fn default() -> Self {
+        Self {
+            access: Duration::minutes(60),
+            refresh: Duration::hours(24),
+        }
+    }
+}
+
+impl TokenTtl {
+    /// Fails unless both are positive and access tokens expire first, since
+    /// a refresh token that dies before its access token is useless.
+    pub fn new(access: Duration, refresh: Duration) -> Result<Self> {
+        if access <= Duration::zero() || refresh <= Duration::zero() {
+            bail!("Token lifetimes must be positive");
+        }
+
+        if access >= refresh {
+            bail!(
+                "Access token lifetime ({}s) must be shorter than the refresh token lifetime ({}s)",
+                access.num_seconds(),
+                refresh.num_seconds()
+            );
+        }
+
+        Ok(Self { access, refresh })
+    }
+
+    /// Parses a lifetime such as `90s`, `15m`, `12h` or `7d`; a bare number
+    /// is seconds.
+    pub fn parse(raw: &str) -> Result<Duration> {
+        let raw = raw.trim();
+        let (amount, unit) = match raw.find(|c: char| !c.is_ascii_digit()) {
+            Some(i) => raw.split_at(i),
+            None => (raw, "s"),
+        };
+
+        let amount = amount
+            .parse::<i64>()
+            .with_context(|| format!("Invalid duration '{raw}'"))?;
+
+        let duration = match unit.trim() {
+            "s" => Duration::try_seconds(amount),
+            "m" => Duration::try_minutes(amount),
+            "h" => Duration::try_hours(amount),
+            "d" => Duration::try_days(amount),
+            other => bail!("Invalid duration unit '{other}' in '{raw}', expected s, m, h or d"),
+        };
+
+        duration.with_context(|| format!("Duration '{raw}' is too large"))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct JwtConfig {
     pub jwt_secret: String,
+    pub previous_key: Option<PreviousJwtKey>,
+    pub ttl: TokenTtl,
 }
 
 impl JwtConfig {
     pub fn new(jwt_secret: &str) -> Self {
         JwtConfig {
             jwt_secret: jwt_secret.to_string(),
+            previous_key: None,
+            ttl: TokenTtl::default(),
+        }
+    }
+
+    pub fn with_ttl(mut self, ttl: TokenTtl) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Also accepts tokens signed with `previous` until its window closes.
+    pub fn with_previous_key(mut self, previous: Option<PreviousJwtKey>) -> Self {
+        self.previous_key = previous;
+        self
+    }
+
+    /// Checks the signature against the primary secret and, while the
+    /// rotation window is open, against the previous one.
+    fn decode_claims(&self, token: &str) -> Result<TokenData<Claims>, ServiceError> {
+        let validation = Validation::default();
+        let primary = decode::<Claims>(
+            token,
+            &DecodingKey::from_secret(self.jwt_secret.as_ref()),
+            &validation,
+        );
+
+        match (primary, &self.previous_key) {
+            (Err(e), Some(previous))
+                if matches!(e.kind(), ErrorKind::InvalidSignature)
+                    && Utc::now() < previous.valid_until =>
+            {
+                decode::<Claims>(
+                    token,
+                    &DecodingKey::from_secret(previous.secret.as_ref()),
+                    &validation,
+                )
+                .map_err(ServiceError::Jwt)
+            }
+            (result, _) => result.map_err(ServiceError::Jwt),
         }
     }
 }
@@ -40,15 +205,27 @@ impl JwtConfig {
 #[async_trait]
 impl JwtServiceTrait for JwtConfig {
     fn generate_token(&self, user_id: i64, token_type: &str) -> Result<String, ServiceError> {
+        self.generate_token_with_roles(user_id, token_type, &[])
+    }
+
+    fn generate_token_with_roles(
+        &self,
+        user_id: i64,
+        token_type: &str,
+        roles: &[String],
+    ) -> Result<String, ServiceError> {
         let now = Utc::now();
         let iat = now.timestamp() as usize;
         let exp = match token_type {
-            "access" => (now + Duration::minutes(60)).timestamp() as usize,
-            "refresh" => (now + Duration::days(7)).timestamp() as usize,
+            "access" => (now + self.ttl.access).timestamp() as usize,
+            "refresh" => (now + self.ttl.refresh).timestamp() as usize,
             _ => return Err(ServiceError::InvalidTokenType),
         };
 
-        let claims = Claims::new(user_id, exp, iat, token_type.to_string());
+        let mut claims = Claims::new(user_id, exp, iat, token_type.to_string(), roles.to_vec());
+        if token_type == "refresh" {
+            claims.jti = Some(Uuid::new_v4().to_string());
+        }
 
         encode(
             &Header::default(),
@@ -58,10 +235,21 @@ impl JwtServiceTrait for JwtConfig {
         .map_err(ServiceError::Jwt)
     }
 
+    fn refresh_token_ttl(&self) -> Duration {
+        self.ttl.refresh
+    }
+
     fn verify_token(&self, token: &str, expected_type: &str) -> Result<i64, ServiceError> {
-        let decoding_key = DecodingKey::from_secret(self.jwt_secret.as_ref());
-        let token_data = decode::<Claims>(token, &decoding_key, &Validation::default())
-            .map_err(ServiceError::Jwt)?;
+        self.verify_token_claims(token, expected_type)
+            .map(|claims| claims.user_id)
+    }
+
+    fn verify_token_claims(
+        &self,
+        token: &str,
+        expected_type: &str,
+    ) -> Result<Claims, ServiceError> {
+        let token_data = self.decode_claims(token)?;
 
         let current_time = Utc::now().timestamp() as usize;
 
@@ -73,6 +261,57 @@ impl JwtServiceTrait for JwtConfig {
             return Err(ServiceError::InvalidTokenType);
         }
 
-        Ok(token_data.claims.user_id)
+        Ok(token_data.claims)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rotated(valid_until: DateTime<Utc>) -> JwtConfig {
+        JwtConfig::new("new-secret").with_previous_key(Some(PreviousJwtKey {
+            secret: "old-secret".to_string(),
+            valid_until,
+        }))
+    }
+
+    #[test]
+    fn previous_key_tokens_verify_only_inside_the_window() {
+        let old_token = JwtConfig::new("old-secret")
+            .generate_token(7, "access")
+            .unwrap();
+
+        let open = rotated(Utc::now() + Duration::hours(1));
+        assert_eq!(open.verify_token(&old_token, "access").unwrap(), 7);
+
+        let closed = rotated(Utc::now() - Duration::seconds(1));
+        assert!(matches!(
+            closed.verify_token(&old_token, "access"),
+            Err(ServiceError::Jwt(e)) if matches!(e.kind(), ErrorKind::InvalidSignature)
+        ));
+    }
+
+    #[test]
+    fn only_the_primary_key_signs_during_rotation() {
+        let config = rotated(Utc::now() + Duration::hours(1));
+        let token = config.generate_token(7, "refresh").unwrap();
+
+        assert_eq!(config.verify_token(&token, "refresh").unwrap(), 7);
+        assert!(
+            JwtConfig::new("old-secret")
+                .verify_token(&token, "refresh")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn unknown_keys_are_rejected_even_inside_the_window() {
+        let foreign = JwtConfig::new("someone-else")
+            .generate_token(7, "access")
+            .unwrap();
+
+        let open = rotated(Utc::now() + Duration::hours(1));
+        assert!(open.verify_token(&foreign, "access").is_err());
     }
 }
@@ -3,6 +3,7 @@ use rdkafka::Message;
 use rdkafka::config::ClientConfig;
 use rdkafka::consumer::{Consumer, StreamConsumer};
 use rdkafka::error::KafkaResult;
+use rdkafka::message::{Header, OwnedHeaders};
 use rdkafka::producer::{BaseProducer, BaseRecord, Producer};
 use tokio::{
     task,
@@ -12,6 +13,7 @@ use tracing::{error, info};
 
 use crate::abstract_trait::KafkaTrait;
 use crate::errors::ServiceError;
+use crate::utils::{REQUEST_ID_HEADER, current_request_id};
 
 pub struct Kafka {
     producer: BaseProducer,
@@ -34,11 +36,19 @@ impl Kafka {
         }
     }
 
+    /// Messages sent while handling a request carry its ID as a header.
     pub fn send_message(&self, topic: &str, key: &str, value: &[u8]) -> KafkaResult<()> {
-        if let Err((kafka_error, _record)) = self
-            .producer
-            .send(BaseRecord::to(topic).key(key).payload(value))
-        {
+        let request_id = current_request_id();
+        let mut record = BaseRecord::to(topic).key(key).payload(value);
+
+        if let Some(id) = request_id.as_deref() {
+            record = record.headers(OwnedHeaders::new().insert(Header {
+                key: REQUEST_ID_HEADER,
+                value: Some(id),
+            }));
+        }
+
+        if let Err((kafka_error, _record)) = self.producer.send(record) {
             return Err(kafka_error);
         }
 
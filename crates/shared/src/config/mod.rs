@@ -1,11 +1,15 @@
+mod cache;
 mod database;
 mod hashing;
 mod jwt;
 mod kafka;
 mod redis;
+mod tls;
 
-pub use self::database::{ConnectionManager, ConnectionPool};
+pub use self::cache::{CacheKeyVersions, CacheTtlConfig};
+pub use self::database::{ConnectionManager, ConnectionPool, DatabasePools};
 pub use self::hashing::Hashing;
-pub use self::jwt::JwtConfig;
+pub use self::jwt::{Claims, JwtConfig, PreviousJwtKey, TokenTtl};
 pub use self::kafka::Kafka;
 pub use self::redis::{RedisConfig, RedisPool};
+pub use self::tls::{client_tls_from_env, grpc_endpoint, server_tls_from_env};
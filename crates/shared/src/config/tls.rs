@@ -0,0 +1,105 @@
+use anyhow::{Context, Result, bail};
+use std::{env, fs};
+use tonic::transport::{Certificate, ClientTlsConfig, Endpoint, Identity, Server, ServerTlsConfig};
+
+/// Reads the PEM file named by `var`; unset or empty means not configured.
+fn read_pem(var: &str) -> Result<Option<Vec<u8>>> {
+    let Some(path) = env::var(var).ok().filter(|p| !p.trim().is_empty()) else {
+        return Ok(None);
+    };
+
+    let pem = fs::read(&path).with_context(|| format!("{var}: cannot read {path}"))?;
+
+    if !pem.windows(11).any(|w| w == b"-----BEGIN ") {
+        bail!("{var}: {path} is not a PEM file");
+    }
+
+    Ok(Some(pem))
+}
+
+/// Server TLS from `GRPC_TLS_CERT` and `GRPC_TLS_KEY`; with
+/// `GRPC_TLS_CLIENT_CA` set as well, clients must present a certificate
+/// signed by it (mTLS). With none of them set the server stays plaintext.
+///
+/// The config is built once here, so a wrong path or a cert that doesn't
+/// match its key stops startup instead of failing the first handshake.
+pub fn server_tls_from_env() -> Result<Option<ServerTlsConfig>> {
+    let cert = read_pem("GRPC_TLS_CERT")?;
+    let key = read_pem("GRPC_TLS_KEY")?;
+    let client_ca = read_pem("GRPC_TLS_CLIENT_CA")?;
+
+    let (cert, key) = match (cert, key) {
+        (Some(cert), Some(key)) => (cert, key),
+        (None, None) if client_ca.is_none() => return Ok(None),
+        _ => bail!("GRPC_TLS_CERT and GRPC_TLS_KEY must be set together to serve gRPC over TLS"),
+    };
+
+    let mut tls = ServerTlsConfig::new().identity(Identity::from_pem(cert, key));
+
+    if let Some(ca) = client_ca {
+        tls = tls.client_ca_root(Certificate::from_pem(ca));
+    }
+
+    Server::builder()
+        .tls_config(tls.clone())
+        .context("Invalid gRPC server TLS configuration")?;
+
+    Ok(Some(tls))
+}
+
+/// Client TLS from `GRPC_TLS_CA`, the CA the servers' certificates are
+/// checked against. `GRPC_TLS_CLIENT_CERT` and `GRPC_TLS_CLIENT_KEY` add a
+/// client certificate for mTLS and `GRPC_TLS_DOMAIN` overrides the expected
+/// server name. Without `GRPC_TLS_CA` calls stay plaintext.
+pub fn client_tls_from_env() -> Result<Option<ClientTlsConfig>> {
+    let ca = read_pem("GRPC_TLS_CA")?;
+    let cert = read_pem("GRPC_TLS_CLIENT_CERT")?;
+    let key = read_pem("GRPC_TLS_CLIENT_KEY")?;
+
+    let Some(ca) = ca else {
+        if cert.is_some() || key.is_some() {
+            bail!("GRPC_TLS_CLIENT_CERT and GRPC_TLS_CLIENT_KEY need GRPC_TLS_CA");
+        }
+        return Ok(None);
+    };
+
+    let mut tls = ClientTlsConfig::new().ca_certificate(Certificate::from_pem(ca));
+
+    match (cert, key) {
+        (Some(cert), Some(key)) => tls = tls.identity(Identity::from_pem(cert, key)),
+        (None, None) => {}
+        _ => bail!("GRPC_TLS_CLIENT_CERT and GRPC_TLS_CLIENT_KEY must be set together"),
+    }
+
+    if let Some(domain) = env::var("GRPC_TLS_DOMAIN")
+        .ok()
+        .filter(|d| !d.trim().is_empty())
+    {
+        tls = tls.domain_name(domain.trim());
+    }
+
+    Endpoint::from_static("https://localhost")
+        .tls_config(tls.clone())
+        .context("Invalid gRPC client TLS configuration")?;
+
+    Ok(Some(tls))
+}
+
+/// Endpoint for `addr`, switched to `https://` and given `tls` when client
+/// TLS is configured.
+pub fn grpc_endpoint(addr: &str, service: &str, tls: Option<&ClientTlsConfig>) -> Result<Endpoint> {
+    let Some(tls) = tls else {
+        return Endpoint::from_shared(addr.to_string())
+            .with_context(|| format!("Invalid gRPC address for {service}: {addr}"));
+    };
+
+    let addr = match addr.strip_prefix("http://") {
+        Some(rest) => format!("https://{rest}"),
+        None => addr.to_string(),
+    };
+
+    Endpoint::from_shared(addr.clone())
+        .with_context(|| format!("Invalid gRPC address for {service}: {addr}"))?
+        .tls_config(tls.clone())
+        .with_context(|| format!("Invalid TLS configuration for {service}"))
+}
@@ -0,0 +1,161 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    env,
+    sync::OnceLock,
+};
+use tonic::{Code, Status, codegen::Bytes};
+use tonic_types::{ErrorDetails, StatusExt};
+use utoipa::ToSchema;
+
+const ERROR_DOMAIN: &str = "simple-ecommerce";
+
+/// Encoding used for structured details attached to a gRPC status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorDetailFormat {
+    /// Plain JSON bytes, easy to inspect with any gRPC client.
+    Json,
+    /// `google.rpc.Status` with `ErrorInfo` and `BadRequest` messages.
+    Protobuf,
+}
+
+impl ErrorDetailFormat {
+    pub fn from_env() -> Self {
+        match env::var("GRPC_ERROR_DETAIL_FORMAT")
+            .unwrap_or_default()
+            .to_ascii_lowercase()
+            .as_str()
+        {
+            "protobuf" | "proto" => Self::Protobuf,
+            _ => Self::Json,
+        }
+    }
+
+    /// Read once per process, since status conversions have no config at hand.
+    pub fn current() -> Self {
+        static FORMAT: OnceLock<ErrorDetailFormat> = OnceLock::new();
+        *FORMAT.get_or_init(Self::from_env)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct FieldViolation {
+    pub field: String,
+    pub description: String,
+}
+
+impl FieldViolation {
+    pub fn new(field: impl Into<String>, description: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            description: description.into(),
+        }
+    }
+}
+
+/// Machine-readable error code, per-field violations and extra key/value
+/// metadata carried in the status details, so the gateway can rebuild a
+/// precise error body.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusDetails {
+    pub code: String,
+    #[serde(default)]
+    pub field_violations: Vec<FieldViolation>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub metadata: BTreeMap<String, String>,
+}
+
+impl StatusDetails {
+    pub fn new(code: impl Into<String>) -> Self {
+        Self {
+            code: code.into(),
+            field_violations: Vec::new(),
+            metadata: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_field_violations(mut self, field_violations: Vec<FieldViolation>) -> Self {
+        self.field_violations = field_violations;
+        self
+    }
+
+    pub fn with_metadata(mut self, key: impl Into<String>, value: impl ToString) -> Self {
+        self.metadata.insert(key.into(), value.to_string());
+        self
+    }
+
+    /// Parses one metadata value, e.g. a numeric id.
+    pub fn metadata_value<T: std::str::FromStr>(&self, key: &str) -> Option<T> {
+        self.metadata.get(key)?.parse().ok()
+    }
+
+    pub fn into_status(self, code: Code, message: impl Into<String>) -> Status {
+        self.into_status_with_format(code, message, ErrorDetailFormat::current())
+    }
+
+    pub fn into_status_with_format(
+        self,
+        code: Code,
+        message: impl Into<String>,
+        format: ErrorDetailFormat,
+    ) -> Status {
+        match format {
+            ErrorDetailFormat::Json => {
+                let details = serde_json::to_vec(&self).unwrap_or_default();
+                Status::with_details(code, message, Bytes::from(details))
+            }
+            ErrorDetailFormat::Protobuf => {
+                let mut details = ErrorDetails::with_error_info(
+                    self.code.to_ascii_uppercase(),
+                    ERROR_DOMAIN,
+                    self.metadata.into_iter().collect::<HashMap<_, _>>(),
+                );
+
+                if !self.field_violations.is_empty() {
+                    details.set_bad_request(
+                        self.field_violations
+                            .into_iter()
+                            .map(|v| tonic_types::FieldViolation::new(v.field, v.description))
+                            .collect::<Vec<_>>(),
+                    );
+                }
+
+                Status::with_error_details(code, message, details)
+            }
+        }
+    }
+
+    /// Decodes either encoding, so a caller does not have to share the
+    /// sender's `GRPC_ERROR_DETAIL_FORMAT`.
+    pub fn from_status(status: &Status) -> Option<Self> {
+        if status.details().is_empty() {
+            return None;
+        }
+
+        if let Ok(details) = serde_json::from_slice::<Self>(status.details()) {
+            return Some(details);
+        }
+
+        let details = status.check_error_details().ok()?;
+        let error_info = details.error_info()?;
+        let code = error_info.reason.to_ascii_lowercase();
+        let metadata = error_info.metadata.clone().into_iter().collect();
+
+        let field_violations = details
+            .bad_request()
+            .map(|bad_request| {
+                bad_request
+                    .field_violations
+                    .iter()
+                    .map(|v| FieldViolation::new(v.field.clone(), v.description.clone()))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Some(Self {
+            code,
+            field_violations,
+            metadata,
+        })
+    }
+}
@@ -1,3 +1,4 @@
+use crate::errors::detail::FieldViolation;
 use serde::Serialize;
 use utoipa::ToSchema;
 
@@ -6,3 +7,21 @@ pub struct ErrorResponse {
     pub status: String,
     pub message: String,
 }
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct InsufficientStockResponse {
+    pub status: String,
+    pub code: String,
+    pub message: String,
+    pub product_id: i32,
+    pub requested: i32,
+    pub available: i32,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ValidationErrorResponse {
+    pub status: String,
+    pub code: String,
+    pub message: String,
+    pub errors: Vec<FieldViolation>,
+}
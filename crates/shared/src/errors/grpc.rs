@@ -1,6 +1,10 @@
-use crate::errors::{repository::RepositoryError, service::ServiceError};
+use crate::errors::{
+    detail::{ErrorDetailFormat, FieldViolation, StatusDetails},
+    repository::RepositoryError,
+    service::ServiceError,
+};
 use thiserror::Error;
-use tonic::Status;
+use tonic::{Code, Status};
 
 #[derive(Debug, Error)]
 pub enum AppErrorGrpc {
@@ -12,24 +16,56 @@ pub enum AppErrorGrpc {
 
 impl From<AppErrorGrpc> for Status {
     fn from(err: AppErrorGrpc) -> Self {
-        match err {
+        err.into_status_with_format(ErrorDetailFormat::current())
+    }
+}
+
+impl AppErrorGrpc {
+    /// Builds the status with details in `format`; `From` uses the
+    /// process-wide `GRPC_ERROR_DETAIL_FORMAT`.
+    pub fn into_status_with_format(self, format: ErrorDetailFormat) -> Status {
+        match self {
             AppErrorGrpc::Service(service_err) => match service_err {
                 ServiceError::Kafka(err) => Status::unavailable(format!("Kafka error: {err}")),
                 ServiceError::InvalidCredentials => Status::unauthenticated("Invalid credentials"),
 
-                ServiceError::Validation(errors) => {
-                    Status::invalid_argument(format!("Validation failed: {errors:#?}"))
+                ServiceError::Validation(errors) => StatusDetails::new("validation_failed")
+                    .into_status_with_format(
+                        Code::InvalidArgument,
+                        format!("Validation failed: {errors:#?}"),
+                        format,
+                    ),
+
+                ServiceError::FieldValidation(violations) => {
+                    StatusDetails::new("validation_failed")
+                        .with_field_violations(violations)
+                        .into_status_with_format(Code::InvalidArgument, "Validation failed", format)
                 }
 
                 ServiceError::Forbidden(msg) => Status::permission_denied(msg),
 
+                ServiceError::EmailNotVerified => StatusDetails::new("email_not_verified")
+                    .into_status_with_format(
+                        Code::PermissionDenied,
+                        "Email is not verified, please verify your email before logging in",
+                        format,
+                    ),
+
                 ServiceError::Repo(repo_err) => match repo_err {
                     RepositoryError::NotFound => Status::not_found("Not found"),
                     RepositoryError::Conflict(msg) => Status::already_exists(&msg),
+                    RepositoryError::NotTrashed(msg) => Status::already_exists(&msg),
                     RepositoryError::AlreadyExists(msg) => Status::already_exists(&msg),
                     RepositoryError::ForeignKey(msg) => {
                         Status::failed_precondition(format!("Foreign key constraint: {msg}"))
                     }
+                    RepositoryError::InvalidSort(msg) => StatusDetails::new("validation_failed")
+                        .with_field_violations(vec![FieldViolation::new("sort", msg)])
+                        .into_status_with_format(
+                            Code::InvalidArgument,
+                            "Validation failed",
+                            format,
+                        ),
                     RepositoryError::Sqlx(_) => Status::internal("Database error"),
                     RepositoryError::Custom(msg) => Status::internal(&msg),
                 },
@@ -42,6 +78,36 @@ impl From<AppErrorGrpc> for Status {
 
                 ServiceError::InvalidTokenType => Status::unauthenticated("Invalid token type"),
 
+                ServiceError::SessionRevoked => StatusDetails::new("session_revoked")
+                    .into_status_with_format(
+                        Code::Unauthenticated,
+                        "Session revoked, please sign in again",
+                        format,
+                    ),
+
+                ServiceError::InsufficientStock {
+                    product_id,
+                    requested,
+                    available,
+                } => {
+                    let message = format!(
+                        "Insufficient stock for product {product_id}: requested={requested}, available={available}"
+                    );
+
+                    StatusDetails::new("insufficient_stock")
+                        .with_metadata("product_id", product_id)
+                        .with_metadata("requested", requested)
+                        .with_metadata("available", available)
+                        .into_status_with_format(Code::FailedPrecondition, message, format)
+                }
+
+                ServiceError::TooManyRequests(msg) => StatusDetails::new("too_many_requests")
+                    .into_status_with_format(Code::ResourceExhausted, msg, format),
+
+                ServiceError::Unavailable(msg) => Status::unavailable(msg),
+
+                ServiceError::DeadlineExceeded(msg) => Status::deadline_exceeded(msg),
+
                 ServiceError::Internal(msg) => Status::internal(msg),
 
                 ServiceError::Custom(msg) => Status::internal(msg),
@@ -55,30 +121,58 @@ impl From<AppErrorGrpc> for Status {
 impl From<Status> for AppErrorGrpc {
     fn from(status: Status) -> Self {
         match status.code() {
-            tonic::Code::Unauthenticated => AppErrorGrpc::Service(ServiceError::InvalidCredentials),
+            tonic::Code::Unauthenticated => match StatusDetails::from_status(&status) {
+                Some(details) if details.code == "session_revoked" => {
+                    AppErrorGrpc::Service(ServiceError::SessionRevoked)
+                }
+                _ => AppErrorGrpc::Service(ServiceError::InvalidCredentials),
+            },
 
-            tonic::Code::InvalidArgument => {
-                AppErrorGrpc::Service(ServiceError::Validation(vec![status.message().to_string()]))
-            }
+            tonic::Code::InvalidArgument => match StatusDetails::from_status(&status) {
+                Some(details) if !details.field_violations.is_empty() => {
+                    AppErrorGrpc::Service(ServiceError::FieldValidation(details.field_violations))
+                }
+                _ => AppErrorGrpc::Service(ServiceError::Validation(vec![
+                    status.message().to_string(),
+                ])),
+            },
 
             tonic::Code::NotFound => {
                 AppErrorGrpc::Service(ServiceError::Repo(RepositoryError::NotFound))
             }
 
-            tonic::Code::PermissionDenied => {
-                AppErrorGrpc::Service(ServiceError::Forbidden(status.message().to_string()))
-            }
+            tonic::Code::PermissionDenied => match StatusDetails::from_status(&status) {
+                Some(details) if details.code == "email_not_verified" => {
+                    AppErrorGrpc::Service(ServiceError::EmailNotVerified)
+                }
+                _ => AppErrorGrpc::Service(ServiceError::Forbidden(status.message().to_string())),
+            },
 
             tonic::Code::AlreadyExists => AppErrorGrpc::Service(ServiceError::Repo(
                 RepositoryError::AlreadyExists(status.message().to_string()),
             )),
 
-            tonic::Code::FailedPrecondition | tonic::Code::Aborted => AppErrorGrpc::Service(
-                ServiceError::Repo(RepositoryError::ForeignKey(status.message().to_string())),
-            ),
+            tonic::Code::FailedPrecondition => match insufficient_stock(&status) {
+                Some(err) => AppErrorGrpc::Service(err),
+                None => AppErrorGrpc::Service(ServiceError::Repo(RepositoryError::ForeignKey(
+                    status.message().to_string(),
+                ))),
+            },
+
+            tonic::Code::Aborted => AppErrorGrpc::Service(ServiceError::Repo(
+                RepositoryError::ForeignKey(status.message().to_string()),
+            )),
 
             tonic::Code::Unavailable => {
-                AppErrorGrpc::Service(ServiceError::Kafka(status.message().to_string()))
+                AppErrorGrpc::Service(ServiceError::Unavailable(status.message().to_string()))
+            }
+
+            tonic::Code::ResourceExhausted => {
+                AppErrorGrpc::Service(ServiceError::TooManyRequests(status.message().to_string()))
+            }
+
+            tonic::Code::DeadlineExceeded => {
+                AppErrorGrpc::Service(ServiceError::DeadlineExceeded(status.message().to_string()))
             }
 
             tonic::Code::Internal => {
@@ -93,3 +187,142 @@ impl From<Status> for AppErrorGrpc {
         }
     }
 }
+
+/// Rebuilds the typed stock error from the status metadata, or `None` for
+/// any other failed precondition.
+fn insufficient_stock(status: &Status) -> Option<ServiceError> {
+    let details = StatusDetails::from_status(status)?;
+    if details.code != "insufficient_stock" {
+        return None;
+    }
+
+    Some(ServiceError::InsufficientStock {
+        product_id: details.metadata_value("product_id")?,
+        requested: details.metadata_value("requested")?,
+        available: details.metadata_value("available")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AppErrorGrpc;
+    use crate::errors::{
+        ErrorDetailFormat, FieldViolation, HttpError, RepositoryError, ServiceError,
+    };
+    use axum::{
+        body::to_bytes,
+        http::StatusCode,
+        response::{IntoResponse, Response},
+    };
+    use serde_json::Value;
+    use tonic::{Code, Status};
+
+    const FORMATS: [ErrorDetailFormat; 2] = [ErrorDetailFormat::Json, ErrorDetailFormat::Protobuf];
+
+    /// Sends `err` through a status in `format` and back, as the gateway
+    /// sees it.
+    fn round_trip(err: ServiceError, format: ErrorDetailFormat) -> (Code, HttpError) {
+        let status = AppErrorGrpc::Service(err).into_status_with_format(format);
+        (status.code(), HttpError::from(AppErrorGrpc::from(status)))
+    }
+
+    #[test]
+    fn field_violations_survive_both_encodings() {
+        for format in FORMATS {
+            let violations = vec![
+                FieldViolation::new("items", "Duplicate product 3"),
+                FieldViolation::new("email", "Invalid email"),
+            ];
+
+            let (code, err) = round_trip(ServiceError::FieldValidation(violations.clone()), format);
+
+            assert_eq!(code, Code::InvalidArgument);
+            assert!(
+                matches!(&err, HttpError::ValidationFailed(got) if *got == violations),
+                "{format:?}: {err:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn insufficient_stock_keeps_its_numbers_in_both_encodings() {
+        for format in FORMATS {
+            let (code, err) = round_trip(
+                ServiceError::InsufficientStock {
+                    product_id: 12,
+                    requested: 5,
+                    available: 2,
+                },
+                format,
+            );
+
+            assert_eq!(code, Code::FailedPrecondition);
+            assert!(
+                matches!(
+                    err,
+                    HttpError::InsufficientStock {
+                        product_id: 12,
+                        requested: 5,
+                        available: 2,
+                    }
+                ),
+                "{format:?}: {err:?}"
+            );
+        }
+    }
+
+    async fn json_body(response: Response) -> Value {
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn insufficient_stock_reaches_the_client_as_409_with_its_numbers() {
+        for format in FORMATS {
+            let (_, err) = round_trip(
+                ServiceError::InsufficientStock {
+                    product_id: 12,
+                    requested: 5,
+                    available: 2,
+                },
+                format,
+            );
+
+            let response = err.into_response();
+            assert_eq!(response.status(), StatusCode::CONFLICT);
+
+            let body = json_body(response).await;
+            assert_eq!(body["code"], "insufficient_stock", "{format:?}");
+            assert_eq!(body["product_id"], 12);
+            assert_eq!(body["requested"], 5);
+            assert_eq!(body["available"], 2);
+        }
+    }
+
+    #[tokio::test]
+    async fn other_conflicts_stay_plain_conflicts() {
+        for format in FORMATS {
+            let (_, err) = round_trip(
+                ServiceError::Repo(RepositoryError::Conflict(
+                    "Product name already taken".into(),
+                )),
+                format,
+            );
+
+            let response = err.into_response();
+            assert_eq!(response.status(), StatusCode::CONFLICT);
+
+            let body = json_body(response).await;
+            assert_eq!(body["message"], "Product name already taken", "{format:?}");
+            assert!(body.get("code").is_none());
+            assert!(body.get("product_id").is_none());
+        }
+
+        // A failed precondition without the stock details is not a shortfall.
+        let err = AppErrorGrpc::from(Status::failed_precondition("Order is already shipped"));
+        assert!(!matches!(
+            HttpError::from(err),
+            HttpError::InsufficientStock { .. }
+        ));
+    }
+}
@@ -1,5 +1,9 @@
 use crate::errors::{
-    error::ErrorResponse, grpc::AppErrorGrpc, repository::RepositoryError, service::ServiceError,
+    detail::FieldViolation,
+    error::{ErrorResponse, InsufficientStockResponse, ValidationErrorResponse},
+    grpc::AppErrorGrpc,
+    repository::RepositoryError,
+    service::ServiceError,
 };
 use axum::{
     Json,
@@ -14,9 +18,17 @@ pub enum HttpError {
     Unauthorized(String),
     NotFound(String),
     Conflict(String),
+    TooManyRequests(String),
     ServiceUnavailable(String),
+    GatewayTimeout(String),
     Internal(String),
     Forbidden(String),
+    InsufficientStock {
+        product_id: i32,
+        requested: i32,
+        available: i32,
+    },
+    ValidationFailed(Vec<FieldViolation>),
 }
 
 impl From<AppErrorGrpc> for HttpError {
@@ -31,15 +43,27 @@ impl From<AppErrorGrpc> for HttpError {
                     HttpError::BadRequest(format!("Validation failed: {errors:?}"))
                 }
 
+                ServiceError::FieldValidation(violations) => {
+                    HttpError::ValidationFailed(violations)
+                }
+
                 ServiceError::Forbidden(msg) => HttpError::Forbidden(msg),
 
+                ServiceError::EmailNotVerified => HttpError::Forbidden(
+                    "Email is not verified, please verify your email before logging in".into(),
+                ),
+
                 ServiceError::Repo(repo_err) => match repo_err {
                     RepositoryError::NotFound => HttpError::NotFound("Not found".into()),
                     RepositoryError::Conflict(msg) => HttpError::Conflict(msg),
+                    RepositoryError::NotTrashed(msg) => HttpError::Conflict(msg),
                     RepositoryError::AlreadyExists(msg) => HttpError::Conflict(msg),
                     RepositoryError::ForeignKey(msg) => {
                         HttpError::BadRequest(format!("Foreign key violation: {msg}"))
                     }
+                    RepositoryError::InvalidSort(msg) => {
+                        HttpError::ValidationFailed(vec![FieldViolation::new("sort", msg)])
+                    }
                     _ => HttpError::Internal("Repository error".into()),
                 },
 
@@ -49,6 +73,22 @@ impl From<AppErrorGrpc> for HttpError {
                     HttpError::ServiceUnavailable(format!("Kafka error: {err}"))
                 }
 
+                ServiceError::InsufficientStock {
+                    product_id,
+                    requested,
+                    available,
+                } => HttpError::InsufficientStock {
+                    product_id,
+                    requested,
+                    available,
+                },
+
+                ServiceError::TooManyRequests(msg) => HttpError::TooManyRequests(msg),
+
+                ServiceError::Unavailable(msg) => HttpError::ServiceUnavailable(msg),
+
+                ServiceError::DeadlineExceeded(msg) => HttpError::GatewayTimeout(msg),
+
                 ServiceError::Internal(msg) | ServiceError::Custom(msg) => HttpError::Internal(msg),
 
                 ServiceError::Bcrypt(_) => {
@@ -60,6 +100,10 @@ impl From<AppErrorGrpc> for HttpError {
                 ServiceError::InvalidTokenType => {
                     HttpError::Unauthorized("Invalid token type".into())
                 }
+
+                ServiceError::SessionRevoked => {
+                    HttpError::Unauthorized("Session revoked, please sign in again".into())
+                }
             },
 
             AppErrorGrpc::Unhandled(msg) => HttpError::Internal(msg),
@@ -75,8 +119,18 @@ impl IntoResponse for HttpError {
             HttpError::Forbidden(msg) => (StatusCode::FORBIDDEN, msg, "warn"),
             HttpError::NotFound(msg) => (StatusCode::NOT_FOUND, msg, "info"),
             HttpError::Conflict(msg) => (StatusCode::CONFLICT, msg, "warn"),
+            HttpError::TooManyRequests(msg) => (StatusCode::TOO_MANY_REQUESTS, msg, "warn"),
             HttpError::ServiceUnavailable(msg) => (StatusCode::SERVICE_UNAVAILABLE, msg, "error"),
+            HttpError::GatewayTimeout(msg) => (StatusCode::GATEWAY_TIMEOUT, msg, "warn"),
             HttpError::Internal(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg, "error"),
+            HttpError::InsufficientStock {
+                product_id,
+                requested,
+                available,
+            } => return insufficient_stock_response(product_id, requested, available),
+            HttpError::ValidationFailed(violations) => {
+                return validation_failed_response(violations);
+            }
         };
 
         match log_level {
@@ -94,3 +148,38 @@ impl IntoResponse for HttpError {
         (status, body).into_response()
     }
 }
+
+fn insufficient_stock_response(product_id: i32, requested: i32, available: i32) -> Response {
+    let message = format!(
+        "Insufficient stock for product {product_id}: requested={requested}, available={available}"
+    );
+    warn!("HTTP {}: {}", StatusCode::CONFLICT, message);
+
+    let body = Json(InsufficientStockResponse {
+        status: "error".into(),
+        code: "insufficient_stock".into(),
+        message,
+        product_id,
+        requested,
+        available,
+    });
+
+    (StatusCode::CONFLICT, body).into_response()
+}
+
+fn validation_failed_response(errors: Vec<FieldViolation>) -> Response {
+    warn!(
+        "HTTP {}: Validation failed for {} field(s)",
+        StatusCode::BAD_REQUEST,
+        errors.len()
+    );
+
+    let body = Json(ValidationErrorResponse {
+        status: "error".into(),
+        code: "validation_failed".into(),
+        message: "Validation failed".into(),
+        errors,
+    });
+
+    (StatusCode::BAD_REQUEST, body).into_response()
+}
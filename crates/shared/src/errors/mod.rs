@@ -1,10 +1,12 @@
+mod detail;
 mod error;
 mod grpc;
 mod http;
 mod repository;
 mod service;
 
-pub use self::error::ErrorResponse;
+pub use self::detail::{ErrorDetailFormat, FieldViolation, StatusDetails};
+pub use self::error::{ErrorResponse, InsufficientStockResponse, ValidationErrorResponse};
 pub use self::grpc::AppErrorGrpc;
 pub use self::http::HttpError;
 pub use self::repository::RepositoryError;
@@ -12,12 +12,19 @@ pub enum RepositoryError {
     #[error("Conflict: {0}")]
     Conflict(String),
 
+    /// Restore was called on a row that exists but is not soft-deleted.
+    #[error("Not trashed: {0}")]
+    NotTrashed(String),
+
     #[error("Already exists: {0}")]
     AlreadyExists(String),
 
     #[error("Foreign key violation: {0}")]
     ForeignKey(String),
 
+    #[error("Invalid sort: {0}")]
+    InvalidSort(String),
+
     #[error("Custom: {0}")]
     Custom(String),
 }
@@ -1,4 +1,4 @@
-use crate::errors::repository::RepositoryError;
+use crate::errors::{detail::FieldViolation, repository::RepositoryError};
 use bcrypt::BcryptError;
 use jsonwebtoken::errors::Error as JwtError;
 use rdkafka::error::KafkaError;
@@ -15,9 +15,16 @@ pub enum ServiceError {
     #[error("Invalid credentials")]
     InvalidCredentials,
 
+    /// The password was right but the account's email has not been verified.
+    #[error("Email is not verified")]
+    EmailNotVerified,
+
     #[error("Validation failed: {0:?}")]
     Validation(Vec<String>),
 
+    #[error("Validation failed: {0:?}")]
+    FieldValidation(Vec<FieldViolation>),
+
     #[error("Bcrypt error: {0}")]
     Bcrypt(#[from] BcryptError),
 
@@ -33,6 +40,29 @@ pub enum ServiceError {
     #[error("Invalid Token")]
     InvalidTokenType,
 
+    /// A rotated-out refresh token was presented again, so every session of
+    /// the user was revoked.
+    #[error("Session revoked")]
+    SessionRevoked,
+
+    #[error(
+        "Insufficient stock for product {product_id}: requested={requested}, available={available}"
+    )]
+    InsufficientStock {
+        product_id: i32,
+        requested: i32,
+        available: i32,
+    },
+
+    #[error("Too many requests: {0}")]
+    TooManyRequests(String),
+
+    #[error("Service unavailable: {0}")]
+    Unavailable(String),
+
+    #[error("Deadline exceeded: {0}")]
+    DeadlineExceeded(String),
+
     #[error("Internal error: {0}")]
     Internal(String),
 
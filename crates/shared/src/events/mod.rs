@@ -4,6 +4,7 @@ use tracing::{debug, error, info};
 
 use crate::abstract_trait::KafkaTrait;
 use crate::errors::ServiceError;
+use crate::utils::current_request_id;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DomainEvent {
@@ -12,6 +13,9 @@ pub struct DomainEvent {
     pub data: serde_json::Value,
     pub timestamp: String,
     pub version: i32,
+    /// `x-request-id` of the request that caused the event.
+    #[serde(default)]
+    pub correlation_id: Option<String>,
 }
 
 impl DomainEvent {
@@ -26,6 +30,7 @@ impl DomainEvent {
             data,
             timestamp: chrono::Utc::now().to_rfc3339(),
             version: 1,
+            correlation_id: current_request_id(),
         }
     }
 }
@@ -52,8 +57,11 @@ impl EventBus {
             })?;
 
         info!(
-            "Event published: type={}, aggregate_id={}, topic={}",
-            event.event_type, event.aggregate_id, topic
+            "Event published: type={}, aggregate_id={}, topic={}, correlation_id={}",
+            event.event_type,
+            event.aggregate_id,
+            topic,
+            event.correlation_id.as_deref().unwrap_or("-")
         );
 
         Ok(())
@@ -0,0 +1,147 @@
+use rand::Rng;
+use std::time::{Duration, Instant};
+
+/// Exponential restart backoff with jitter. The attempt counter resets once a
+/// run stays up for `healthy_after`, so a long-lived server that crashes again
+/// starts over from `base` instead of the last cap.
+#[derive(Debug, Clone)]
+pub struct RestartBackoff {
+    base: Duration,
+    max: Duration,
+    healthy_after: Duration,
+    attempt: u32,
+    started_at: Option<Instant>,
+}
+
+impl Default for RestartBackoff {
+    fn default() -> Self {
+        Self::new(
+            Duration::from_secs(1),
+            Duration::from_secs(60),
+            Duration::from_secs(60),
+        )
+    }
+}
+
+impl RestartBackoff {
+    pub fn new(base: Duration, max: Duration, healthy_after: Duration) -> Self {
+        Self {
+            base,
+            max: max.max(base),
+            healthy_after,
+            attempt: 0,
+            started_at: None,
+        }
+    }
+
+    /// Call right before (re)starting the supervised task.
+    pub fn mark_started(&mut self) {
+        self.started_at = Some(Instant::now());
+    }
+
+    /// Delay to wait before the next restart after a failure.
+    pub fn next_delay(&mut self) -> Duration {
+        self.next_delay_at(Instant::now(), &mut rand::rng())
+    }
+
+    fn next_delay_at(&mut self, now: Instant, rng: &mut impl Rng) -> Duration {
+        if let Some(started_at) = self.started_at.take()
+            && now.saturating_duration_since(started_at) >= self.healthy_after
+        {
+            self.attempt = 0;
+        }
+
+        let exp = self
+            .base
+            .saturating_mul(2_u32.saturating_pow(self.attempt))
+            .min(self.max);
+        self.attempt = self.attempt.saturating_add(1);
+
+        // Equal jitter: keep half the delay and randomize the rest so replicas
+        // restarting after the same outage spread out.
+        let half = exp / 2;
+        let jitter_ms = rng.random_range(0..=half.as_millis() as u64);
+
+        half + Duration::from_millis(jitter_ms)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{SeedableRng, rngs::StdRng};
+
+    fn backoff() -> RestartBackoff {
+        RestartBackoff::new(
+            Duration::from_secs(1),
+            Duration::from_secs(8),
+            Duration::from_secs(60),
+        )
+    }
+
+    #[test]
+    fn delays_grow_exponentially_up_to_the_cap() {
+        let mut backoff = backoff();
+        let mut rng = StdRng::seed_from_u64(7);
+        let now = Instant::now();
+
+        for ceiling in [1, 2, 4, 8, 8, 8] {
+            let ceiling = Duration::from_secs(ceiling);
+            let delay = backoff.next_delay_at(now, &mut rng);
+
+            assert!(
+                delay >= ceiling / 2 && delay <= ceiling,
+                "{delay:?} outside {:?}..={ceiling:?}",
+                ceiling / 2
+            );
+        }
+    }
+
+    #[test]
+    fn delays_are_jittered() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let now = Instant::now();
+
+        let delays: Vec<_> = (0..20)
+            .map(|_| {
+                let mut backoff = backoff();
+                backoff.attempt = 3;
+                backoff.next_delay_at(now, &mut rng)
+            })
+            .collect();
+
+        assert!(delays.iter().any(|d| *d != delays[0]));
+    }
+
+    #[test]
+    fn healthy_run_resets_the_attempts() {
+        let mut backoff = backoff();
+        let mut rng = StdRng::seed_from_u64(7);
+        let started = Instant::now();
+
+        for _ in 0..5 {
+            backoff.next_delay_at(started, &mut rng);
+        }
+
+        backoff.started_at = Some(started);
+        let delay = backoff.next_delay_at(started + Duration::from_secs(61), &mut rng);
+
+        assert!(delay <= Duration::from_secs(1));
+    }
+
+    #[test]
+    fn short_run_keeps_backing_off() {
+        let mut backoff = backoff();
+        let mut rng = StdRng::seed_from_u64(7);
+        let started = Instant::now();
+
+        for _ in 0..5 {
+            backoff.next_delay_at(started, &mut rng);
+        }
+
+        backoff.started_at = Some(started);
+        let delay = backoff.next_delay_at(started + Duration::from_secs(5), &mut rng);
+
+        assert!(delay >= Duration::from_secs(4));
+    }
+}
@@ -0,0 +1,109 @@
+use axum::http::StatusCode;
+use genproto::api::BatchFailure as BatchFailureProto;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// One item of a batch that could not be processed, keyed by whatever the
+/// caller used to name it: an entity id, or a CSV line number.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct BatchFailure {
+    pub id: i32,
+    pub error: String,
+}
+
+impl BatchFailure {
+    pub fn new(id: i32, error: impl Into<String>) -> Self {
+        Self {
+            id,
+            error: error.into(),
+        }
+    }
+}
+
+impl From<BatchFailure> for BatchFailureProto {
+    fn from(value: BatchFailure) -> Self {
+        BatchFailureProto {
+            id: value.id,
+            error: value.error,
+        }
+    }
+}
+
+impl From<BatchFailureProto> for BatchFailure {
+    fn from(value: BatchFailureProto) -> Self {
+        BatchFailure {
+            id: value.id,
+            error: value.error,
+        }
+    }
+}
+
+/// Outcome of a batch operation that keeps going past bad items, so one
+/// missing id or invalid row doesn't cost the caller the rest.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct BatchResponse<T> {
+    pub succeeded: Vec<T>,
+    pub failed: Vec<BatchFailure>,
+}
+
+impl<T> Default for BatchResponse<T> {
+    fn default() -> Self {
+        Self {
+            succeeded: Vec::new(),
+            failed: Vec::new(),
+        }
+    }
+}
+
+impl<T> BatchResponse<T> {
+    pub fn succeed(&mut self, item: T) {
+        self.succeeded.push(item);
+    }
+
+    pub fn fail(&mut self, id: i32, error: impl Into<String>) {
+        self.failed.push(BatchFailure::new(id, error));
+    }
+
+    /// 200 when every item went through, 207 Multi-Status as soon as any
+    /// failed, so clients know to read `failed` without parsing the body.
+    pub fn status_code(&self) -> StatusCode {
+        if self.failed.is_empty() {
+            StatusCode::OK
+        } else {
+            StatusCode::MULTI_STATUS
+        }
+    }
+
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> BatchResponse<U> {
+        BatchResponse {
+            succeeded: self.succeeded.into_iter().map(f).collect(),
+            failed: self.failed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BatchResponse;
+    use axum::http::StatusCode;
+
+    #[test]
+    fn mixed_outcomes_fill_both_lists_and_answer_207() {
+        let mut batch = BatchResponse::default();
+        assert_eq!(batch.status_code(), StatusCode::OK);
+
+        batch.succeed("first");
+        batch.fail(2, "Product not found");
+        batch.succeed("third");
+
+        assert_eq!(batch.succeeded, ["first", "third"]);
+        assert_eq!(batch.failed.len(), 1);
+        assert_eq!(batch.failed[0].id, 2);
+        assert_eq!(batch.failed[0].error, "Product not found");
+        assert_eq!(batch.status_code(), StatusCode::MULTI_STATUS);
+
+        let lengths = batch.map(str::len);
+        assert_eq!(lengths.succeeded, [5, 5]);
+        assert_eq!(lengths.status_code(), StatusCode::MULTI_STATUS);
+    }
+}
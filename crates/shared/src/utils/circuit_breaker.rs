@@ -0,0 +1,245 @@
+use crate::errors::{AppErrorGrpc, ServiceError, StatusDetails};
+use std::{
+    env,
+    future::Future,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+use tonic::{Code, Status};
+use tracing::{info, warn};
+
+#[derive(Debug, Clone, Copy)]
+enum BreakerState {
+    Closed { failures: u32 },
+    Open { until: Instant },
+    HalfOpen,
+}
+
+/// Fails fast on a downstream gRPC service once it keeps timing out or being
+/// unreachable. After `open_for` a single trial call is let through; its
+/// outcome decides whether the breaker closes again or stays open.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    name: String,
+    failure_threshold: u32,
+    open_for: Duration,
+    state: Mutex<BreakerState>,
+}
+
+impl CircuitBreaker {
+    /// A `failure_threshold` of zero disables the breaker.
+    pub fn new(name: impl Into<String>, failure_threshold: u32, open_for: Duration) -> Self {
+        Self {
+            name: name.into(),
+            failure_threshold,
+            open_for,
+            state: Mutex::new(BreakerState::Closed { failures: 0 }),
+        }
+    }
+
+    pub fn from_env(name: impl Into<String>) -> Self {
+        let failure_threshold = env::var("GRPC_BREAKER_FAILURE_THRESHOLD")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(5);
+
+        let open_secs = env::var("GRPC_BREAKER_OPEN_SECONDS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(30);
+
+        Self::new(name, failure_threshold, Duration::from_secs(open_secs))
+    }
+
+    /// Runs `call` unless the breaker is open. Only transport-level failures
+    /// count towards opening it; business errors such as not-found pass through.
+    pub async fn call<T, F, Fut>(&self, call: F) -> Result<T, AppErrorGrpc>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, Status>>,
+    {
+        if !self.try_acquire() {
+            return Err(AppErrorGrpc::Service(ServiceError::Unavailable(format!(
+                "{} is temporarily unavailable",
+                self.name
+            ))));
+        }
+
+        match call().await {
+            Ok(value) => {
+                self.record_success();
+                Ok(value)
+            }
+            Err(status) => {
+                if is_downstream_failure(&status) {
+                    self.record_failure();
+                } else {
+                    self.record_success();
+                }
+                Err(AppErrorGrpc::from(status))
+            }
+        }
+    }
+
+    fn try_acquire(&self) -> bool {
+        if self.failure_threshold == 0 {
+            return true;
+        }
+
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+
+        match *state {
+            BreakerState::Closed { .. } => true,
+            BreakerState::Open { until } if Instant::now() >= until => {
+                info!("🔌 Circuit breaker for {} half-open, probing", self.name);
+                *state = BreakerState::HalfOpen;
+                true
+            }
+            BreakerState::Open { .. } | BreakerState::HalfOpen => false,
+        }
+    }
+
+    fn record_success(&self) {
+        if self.failure_threshold == 0 {
+            return;
+        }
+
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+
+        if matches!(*state, BreakerState::HalfOpen) {
+            info!("🔌 Circuit breaker for {} closed", self.name);
+        }
+
+        *state = BreakerState::Closed { failures: 0 };
+    }
+
+    fn record_failure(&self) {
+        if self.failure_threshold == 0 {
+            return;
+        }
+
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+
+        let failures = match *state {
+            BreakerState::Closed { failures } => failures + 1,
+            BreakerState::HalfOpen => self.failure_threshold,
+            BreakerState::Open { .. } => return,
+        };
+
+        if failures >= self.failure_threshold {
+            warn!(
+                "🔌 Circuit breaker for {} opened for {:?} after {failures} failures",
+                self.name, self.open_for
+            );
+            *state = BreakerState::Open {
+                until: Instant::now() + self.open_for,
+            };
+        } else {
+            *state = BreakerState::Closed { failures };
+        }
+    }
+}
+
+/// A `ResourceExhausted` carrying error details is a limit aimed at one
+/// caller, such as a login lockout, and says nothing about the service's health.
+fn is_downstream_failure(status: &Status) -> bool {
+    match status.code() {
+        Code::Unavailable | Code::DeadlineExceeded | Code::Unknown => true,
+        Code::ResourceExhausted => StatusDetails::from_status(status).is_none(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Calls through the breaker with a downstream that fails with `status`;
+    /// `calls` counts how often the downstream was actually reached.
+    async fn fail(breaker: &CircuitBreaker, calls: &AtomicU32, status: fn() -> Status) {
+        let result: Result<(), _> = breaker
+            .call(|| async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(status())
+            })
+            .await;
+
+        assert!(result.is_err());
+    }
+
+    fn unavailable() -> Status {
+        Status::unavailable("connection refused")
+    }
+
+    #[tokio::test]
+    async fn opens_after_repeated_downstream_failures() {
+        let breaker = CircuitBreaker::new("role-service", 3, Duration::from_secs(30));
+        let calls = AtomicU32::new(0);
+
+        for _ in 0..3 {
+            fail(&breaker, &calls, unavailable).await;
+        }
+
+        fail(&breaker, &calls, unavailable).await;
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn business_errors_do_not_open_it() {
+        let breaker = CircuitBreaker::new("role-service", 2, Duration::from_secs(30));
+        let calls = AtomicU32::new(0);
+
+        for _ in 0..5 {
+            fail(&breaker, &calls, || Status::not_found("role not found")).await;
+        }
+
+        assert_eq!(calls.load(Ordering::SeqCst), 5);
+    }
+
+    #[tokio::test]
+    async fn closes_after_a_successful_probe() {
+        let breaker = CircuitBreaker::new("user-service", 1, Duration::from_millis(20));
+        let calls = AtomicU32::new(0);
+
+        fail(&breaker, &calls, unavailable).await;
+        fail(&breaker, &calls, unavailable).await;
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        tokio::time::sleep(Duration::from_millis(30)).await;
+
+        let probe: Result<u32, _> = breaker.call(|| async { Ok(7) }).await;
+        assert_eq!(probe.unwrap(), 7);
+
+        let after: Result<u32, _> = breaker.call(|| async { Ok(8) }).await;
+        assert_eq!(after.unwrap(), 8);
+    }
+
+    #[tokio::test]
+    async fn failed_probe_opens_it_again() {
+        let breaker = CircuitBreaker::new("user-service", 2, Duration::from_millis(20));
+        let calls = AtomicU32::new(0);
+
+        for _ in 0..2 {
+            fail(&breaker, &calls, unavailable).await;
+        }
+
+        tokio::time::sleep(Duration::from_millis(30)).await;
+
+        fail(&breaker, &calls, unavailable).await;
+        fail(&breaker, &calls, unavailable).await;
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn zero_threshold_disables_it() {
+        let breaker = CircuitBreaker::new("role-service", 0, Duration::from_secs(30));
+        let calls = AtomicU32::new(0);
+
+        for _ in 0..10 {
+            fail(&breaker, &calls, unavailable).await;
+        }
+
+        assert_eq!(calls.load(Ordering::SeqCst), 10);
+    }
+}
@@ -0,0 +1,112 @@
+use std::env;
+use tonic::codec::CompressionEncoding;
+use tracing::warn;
+
+/// Encoding used for outgoing gRPC messages, from `GRPC_COMPRESSION`
+/// (`gzip` or `none`). Incoming gzip is always accepted, so peers can enable
+/// compression one at a time.
+pub fn grpc_compression_from_env() -> Option<CompressionEncoding> {
+    parse_grpc_compression(&env::var("GRPC_COMPRESSION").unwrap_or_default())
+}
+
+pub fn parse_grpc_compression(raw: &str) -> Option<CompressionEncoding> {
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "gzip" => Some(CompressionEncoding::Gzip),
+        "" | "none" => None,
+        other => {
+            warn!("Unsupported GRPC_COMPRESSION '{other}', sending uncompressed");
+            None
+        }
+    }
+}
+
+/// Applies the `GRPC_COMPRESSION` setting, or an explicit
+/// `Option<CompressionEncoding>`, to a generated tonic client or server,
+/// which share `accept_compressed`/`send_compressed` but no trait.
+#[macro_export]
+macro_rules! with_grpc_compression {
+    ($service:expr) => {
+        $crate::with_grpc_compression!($service, $crate::utils::grpc_compression_from_env())
+    };
+    ($service:expr, $encoding:expr) => {{
+        let service = $service.accept_compressed(::tonic::codec::CompressionEncoding::Gzip);
+        match $encoding {
+            Some(encoding) => service.send_compressed(encoding),
+            None => service,
+        }
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_grpc_compression;
+    use crate::utils::HealthGrpcService;
+    use genproto::health::{
+        HealthCheckRequest, health_service_client::HealthServiceClient,
+        health_service_server::HealthServiceServer,
+    };
+    use tokio::net::TcpListener;
+    use tonic::{
+        codec::CompressionEncoding,
+        transport::{Channel, Server, server::TcpIncoming},
+    };
+
+    /// Response encoding seen by a client that sends with `client_encoding`
+    /// to a server that sends with `server_encoding`.
+    async fn response_encoding(
+        server_encoding: Option<CompressionEncoding>,
+        client_encoding: Option<CompressionEncoding>,
+    ) -> Option<String> {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(
+            Server::builder()
+                .add_service(with_grpc_compression!(
+                    HealthServiceServer::new(HealthGrpcService::new("product-service", "1.0.0")),
+                    server_encoding
+                ))
+                .serve_with_incoming(TcpIncoming::from(listener)),
+        );
+
+        let channel = Channel::from_shared(format!("http://{addr}"))
+            .unwrap()
+            .connect_lazy();
+        let mut client = with_grpc_compression!(HealthServiceClient::new(channel), client_encoding);
+
+        let response = client.check(HealthCheckRequest {}).await.unwrap();
+        assert_eq!(response.get_ref().service, "product-service");
+
+        response
+            .metadata()
+            .get("grpc-encoding")
+            .map(|v| v.to_str().unwrap().to_string())
+    }
+
+    #[tokio::test]
+    async fn responses_are_gzipped_only_when_the_server_enables_it() {
+        assert_eq!(
+            response_encoding(Some(CompressionEncoding::Gzip), None).await,
+            Some("gzip".to_string())
+        );
+        assert_eq!(response_encoding(None, None).await, None);
+    }
+
+    #[tokio::test]
+    async fn compressed_requests_are_accepted_by_an_uncompressed_server() {
+        assert_eq!(
+            response_encoding(None, Some(CompressionEncoding::Gzip)).await,
+            None
+        );
+    }
+
+    #[test]
+    fn unknown_encodings_fall_back_to_uncompressed() {
+        assert_eq!(
+            parse_grpc_compression(" GZIP "),
+            Some(CompressionEncoding::Gzip)
+        );
+        assert_eq!(parse_grpc_compression("none"), None);
+        assert_eq!(parse_grpc_compression(""), None);
+        assert_eq!(parse_grpc_compression("zstd"), None);
+    }
+}
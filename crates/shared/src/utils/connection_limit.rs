@@ -0,0 +1,290 @@
+use std::{
+    env,
+    future::Future,
+    io,
+    net::SocketAddr,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll, ready},
+};
+use tokio::{
+    io::{AsyncRead, AsyncWrite, ReadBuf},
+    net::{TcpListener, TcpStream},
+    sync::{OwnedSemaphorePermit, Semaphore},
+};
+use tonic::{
+    codegen::tokio_stream::Stream,
+    transport::server::{Connected, TcpConnectInfo},
+};
+use tracing::warn;
+
+/// What to do with a connection that arrives while the server is at its
+/// connection limit.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ConnectionLimitAction {
+    /// Accept and immediately close it.
+    #[default]
+    Reject,
+    /// Leave it in the listen backlog until a connection closes.
+    Queue,
+}
+
+/// Cap on simultaneous connections to a gRPC server, from
+/// `GRPC_MAX_CONNECTIONS` (0 or unset = unlimited) and
+/// `GRPC_CONNECTION_LIMIT_ACTION` (`reject` or `queue`).
+///
+/// tonic has no such setting, so the cap is applied to the listener handed
+/// to `serve_with_incoming_shutdown`: every accepted connection holds a
+/// permit until it is closed.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ConnectionLimit {
+    max_connections: usize,
+    action: ConnectionLimitAction,
+}
+
+impl ConnectionLimit {
+    pub fn new(max_connections: usize, action: ConnectionLimitAction) -> Self {
+        Self {
+            max_connections,
+            action,
+        }
+    }
+
+    pub fn from_env() -> Self {
+        let max_connections = env::var("GRPC_MAX_CONNECTIONS")
+            .ok()
+            .and_then(|v| v.trim().parse::<usize>().ok())
+            .unwrap_or(0);
+
+        let action = match env::var("GRPC_CONNECTION_LIMIT_ACTION")
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase()
+            .as_str()
+        {
+            "" | "reject" => ConnectionLimitAction::Reject,
+            "queue" => ConnectionLimitAction::Queue,
+            other => {
+                warn!("Unsupported GRPC_CONNECTION_LIMIT_ACTION '{other}', rejecting instead");
+                ConnectionLimitAction::Reject
+            }
+        };
+
+        Self::new(max_connections, action)
+    }
+
+    /// Binds `addr` and returns its connections with the limit applied.
+    pub async fn bind(self, addr: SocketAddr) -> io::Result<LimitedIncoming> {
+        let listener = TcpListener::bind(addr).await?;
+
+        Ok(LimitedIncoming {
+            listener,
+            permits: (self.max_connections > 0)
+                .then(|| Arc::new(Semaphore::new(self.max_connections))),
+            action: self.action,
+            acquiring: None,
+            permit: None,
+        })
+    }
+}
+
+type AcquireFuture =
+    Pin<Box<dyn Future<Output = Result<OwnedSemaphorePermit, tokio::sync::AcquireError>> + Send>>;
+
+/// Stream of accepted connections for `Server::serve_with_incoming_shutdown`.
+pub struct LimitedIncoming {
+    listener: TcpListener,
+    permits: Option<Arc<Semaphore>>,
+    action: ConnectionLimitAction,
+    acquiring: Option<AcquireFuture>,
+    permit: Option<OwnedSemaphorePermit>,
+}
+
+impl Stream for LimitedIncoming {
+    type Item = io::Result<LimitedConnection>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            // Queueing waits for a free slot before accepting, so the
+            // waiting connections stay in the kernel's backlog.
+            let queue_for = match &this.permits {
+                Some(permits)
+                    if this.action == ConnectionLimitAction::Queue && this.permit.is_none() =>
+                {
+                    Some(permits.clone())
+                }
+                _ => None,
+            };
+
+            if let Some(permits) = queue_for {
+                let acquiring = this
+                    .acquiring
+                    .get_or_insert_with(|| Box::pin(permits.acquire_owned()));
+
+                match ready!(acquiring.as_mut().poll(cx)) {
+                    Ok(permit) => this.permit = Some(permit),
+                    Err(_) => return Poll::Ready(None),
+                }
+                this.acquiring = None;
+            }
+
+            let (stream, remote_addr) = match ready!(this.listener.poll_accept(cx)) {
+                Ok(accepted) => accepted,
+                Err(e) => return Poll::Ready(Some(Err(e))),
+            };
+
+            let permit = match (&this.permits, this.permit.take()) {
+                (None, _) => None,
+                (Some(_), Some(permit)) => Some(permit),
+                (Some(permits), None) => match permits.clone().try_acquire_owned() {
+                    Ok(permit) => Some(permit),
+                    Err(_) => {
+                        warn!("Connection limit reached, refusing connection from {remote_addr}");
+                        drop(stream);
+                        continue;
+                    }
+                },
+            };
+
+            if let Err(e) = stream.set_nodelay(true) {
+                warn!("Failed to set TCP_NODELAY for {remote_addr}: {e}");
+            }
+
+            return Poll::Ready(Some(Ok(LimitedConnection {
+                stream,
+                _permit: permit,
+            })));
+        }
+    }
+}
+
+/// An accepted connection; its slot is freed when it is dropped.
+pub struct LimitedConnection {
+    stream: TcpStream,
+    _permit: Option<OwnedSemaphorePermit>,
+}
+
+impl Connected for LimitedConnection {
+    type ConnectInfo = TcpConnectInfo;
+
+    fn connect_info(&self) -> Self::ConnectInfo {
+        self.stream.connect_info()
+    }
+}
+
+impl AsyncRead for LimitedConnection {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().stream).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for LimitedConnection {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.get_mut().stream).poll_write(cx, buf)
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.get_mut().stream).poll_write_vectored(cx, bufs)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.stream.is_write_vectored()
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().stream).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().stream).poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use tokio::{io::AsyncReadExt, time::timeout};
+    use tonic::codegen::tokio_stream::StreamExt;
+
+    async fn incoming(max_connections: usize, action: ConnectionLimitAction) -> LimitedIncoming {
+        ConnectionLimit::new(max_connections, action)
+            .bind("127.0.0.1:0".parse().unwrap())
+            .await
+            .unwrap()
+    }
+
+    async fn connect(incoming: &LimitedIncoming) -> TcpStream {
+        TcpStream::connect(incoming.listener.local_addr().unwrap())
+            .await
+            .unwrap()
+    }
+
+    /// The next connection handed to the server, or `None` if there is none
+    /// within a short wait.
+    async fn next(incoming: &mut LimitedIncoming) -> Option<LimitedConnection> {
+        timeout(Duration::from_millis(200), incoming.next())
+            .await
+            .ok()
+            .map(|accepted| accepted.unwrap().unwrap())
+    }
+
+    /// Whether the server closed `client` without it having to wait long.
+    async fn closed(client: &mut TcpStream) -> bool {
+        let mut buf = [0u8; 1];
+        matches!(
+            timeout(Duration::from_millis(200), client.read(&mut buf)).await,
+            Ok(Ok(0) | Err(_))
+        )
+    }
+
+    #[tokio::test]
+    async fn connections_over_the_cap_are_closed_when_rejecting() {
+        let mut incoming = incoming(2, ConnectionLimitAction::Reject).await;
+        let _first = connect(&incoming).await;
+        let _second = connect(&incoming).await;
+        let mut third = connect(&incoming).await;
+
+        let first = next(&mut incoming).await.unwrap();
+        let _second = next(&mut incoming).await.unwrap();
+        assert!(next(&mut incoming).await.is_none());
+        assert!(closed(&mut third).await);
+
+        // Closing a connection frees its slot for a new one.
+        drop(first);
+        let _fourth = connect(&incoming).await;
+        assert!(next(&mut incoming).await.is_some());
+    }
+
+    #[tokio::test]
+    async fn connections_over_the_cap_wait_for_a_slot_when_queueing() {
+        let mut incoming = incoming(1, ConnectionLimitAction::Queue).await;
+        let _first = connect(&incoming).await;
+        let mut second = connect(&incoming).await;
+
+        let first = next(&mut incoming).await.unwrap();
+        assert!(next(&mut incoming).await.is_none());
+        assert!(!closed(&mut second).await);
+
+        drop(first);
+        let queued = next(&mut incoming).await.unwrap();
+        assert_eq!(
+            queued.stream.peer_addr().unwrap(),
+            second.local_addr().unwrap()
+        );
+    }
+}
@@ -0,0 +1,153 @@
+use crate::errors::{FieldViolation, ServiceError};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{DateTime, NaiveDateTime};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::env;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Truncated tag length; enough to make guessing a valid cursor impractical
+/// while keeping URLs short.
+const TAG_LEN: usize = 12;
+
+/// Keyset position of the last row of a page, ordered by `(created_at, id)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cursor {
+    pub created_at: NaiveDateTime,
+    pub id: i32,
+}
+
+/// Encodes cursors as URL-safe base64 with an HMAC tag, so clients can pass
+/// them back but can't forge a position of their own.
+#[derive(Clone)]
+pub struct CursorCodec {
+    secret: Vec<u8>,
+}
+
+impl CursorCodec {
+    pub fn new(secret: impl AsRef<[u8]>) -> Self {
+        Self {
+            secret: secret.as_ref().to_vec(),
+        }
+    }
+
+    /// Uses `CURSOR_SECRET`, falling back to `JWT_SECRET` so existing
+    /// deployments need no extra setting.
+    pub fn from_env() -> Self {
+        let secret = env::var("CURSOR_SECRET")
+            .or_else(|_| env::var("JWT_SECRET"))
+            .unwrap_or_default();
+
+        Self::new(secret)
+    }
+
+    pub fn encode(&self, cursor: &Cursor) -> String {
+        let mut bytes = Vec::with_capacity(12 + TAG_LEN);
+        bytes.extend_from_slice(&cursor.created_at.and_utc().timestamp_micros().to_be_bytes());
+        bytes.extend_from_slice(&cursor.id.to_be_bytes());
+
+        let tag = self.mac(&bytes).finalize().into_bytes();
+        bytes.extend_from_slice(&tag[..TAG_LEN]);
+
+        URL_SAFE_NO_PAD.encode(bytes)
+    }
+
+    pub fn decode(&self, encoded: &str) -> Result<Cursor, ServiceError> {
+        let bytes = URL_SAFE_NO_PAD
+            .decode(encoded.trim())
+            .map_err(|_| invalid_cursor())?;
+
+        if bytes.len() != 12 + TAG_LEN {
+            return Err(invalid_cursor());
+        }
+
+        let (payload, tag) = bytes.split_at(12);
+
+        self.mac(payload)
+            .verify_truncated_left(tag)
+            .map_err(|_| invalid_cursor())?;
+
+        let micros = i64::from_be_bytes(payload[..8].try_into().map_err(|_| invalid_cursor())?);
+        let id = i32::from_be_bytes(payload[8..].try_into().map_err(|_| invalid_cursor())?);
+
+        let created_at = DateTime::from_timestamp_micros(micros)
+            .ok_or_else(invalid_cursor)?
+            .naive_utc();
+
+        Ok(Cursor { created_at, id })
+    }
+
+    fn mac(&self, payload: &[u8]) -> HmacSha256 {
+        let mut mac =
+            HmacSha256::new_from_slice(&self.secret).expect("HMAC accepts keys of any length");
+        mac.update(payload);
+        mac
+    }
+}
+
+fn invalid_cursor() -> ServiceError {
+    ServiceError::FieldValidation(vec![FieldViolation::new(
+        "cursor",
+        "Cursor is malformed or has been tampered with",
+    )])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn cursor() -> Cursor {
+        Cursor {
+            created_at: NaiveDate::from_ymd_opt(2025, 10, 16)
+                .unwrap()
+                .and_hms_micro_opt(9, 30, 15, 123_456)
+                .unwrap(),
+            id: 42,
+        }
+    }
+
+    #[test]
+    fn round_trips_through_encode_and_decode() {
+        let codec = CursorCodec::new("secret");
+        let encoded = codec.encode(&cursor());
+
+        assert!(
+            encoded
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        );
+        assert_eq!(codec.decode(&encoded).unwrap(), cursor());
+    }
+
+    #[test]
+    fn rejects_a_tampered_cursor() {
+        let codec = CursorCodec::new("secret");
+        let mut bytes = URL_SAFE_NO_PAD.decode(codec.encode(&cursor())).unwrap();
+        // Point the cursor at another id, keeping the original tag.
+        bytes[11] ^= 0x01;
+
+        let tampered = URL_SAFE_NO_PAD.encode(bytes);
+
+        assert!(matches!(
+            codec.decode(&tampered),
+            Err(ServiceError::FieldValidation(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_a_cursor_signed_with_another_secret() {
+        let encoded = CursorCodec::new("other").encode(&cursor());
+
+        assert!(CursorCodec::new("secret").decode(&encoded).is_err());
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        let codec = CursorCodec::new("secret");
+
+        assert!(codec.decode("not a cursor").is_err());
+        assert!(codec.decode("").is_err());
+    }
+}
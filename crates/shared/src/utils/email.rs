@@ -0,0 +1,65 @@
+use std::env;
+
+/// Normalizes emails before they are stored or looked up, so the same
+/// address typed with different casing or padding resolves to one account.
+#[derive(Debug, Clone, Copy)]
+pub struct EmailNormalizer {
+    trim: bool,
+    lowercase: bool,
+}
+
+impl Default for EmailNormalizer {
+    fn default() -> Self {
+        Self::new(true, true)
+    }
+}
+
+impl EmailNormalizer {
+    pub fn new(trim: bool, lowercase: bool) -> Self {
+        Self { trim, lowercase }
+    }
+
+    pub fn from_env() -> Self {
+        let flag = |name: &str| {
+            env::var(name)
+                .map(|v| v != "false" && v != "0")
+                .unwrap_or(true)
+        };
+
+        Self::new(
+            flag("EMAIL_NORMALIZE_TRIM"),
+            flag("EMAIL_NORMALIZE_LOWERCASE"),
+        )
+    }
+
+    pub fn normalize(&self, email: &str) -> String {
+        let email = if self.trim { email.trim() } else { email };
+
+        if self.lowercase {
+            email.to_lowercase()
+        } else {
+            email.to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EmailNormalizer;
+
+    #[test]
+    fn default_policy_trims_and_lowercases() {
+        assert_eq!(
+            EmailNormalizer::default().normalize(" User@Example.COM "),
+            "user@example.com"
+        );
+    }
+
+    #[test]
+    fn disabled_steps_leave_the_email_alone() {
+        assert_eq!(
+            EmailNormalizer::new(false, false).normalize(" User@Example.COM "),
+            " User@Example.COM "
+        );
+    }
+}
@@ -0,0 +1,37 @@
+use genproto::health::{
+    HealthCheckRequest, HealthCheckResponse, health_service_server::HealthService,
+};
+use tonic::{Request, Response, Status};
+
+pub const HEALTH_SERVING: &str = "SERVING";
+
+/// Liveness RPC every service registers next to its own gRPC services, so the
+/// gateway can report reachability, version, and latency per downstream.
+#[derive(Debug, Clone)]
+pub struct HealthGrpcService {
+    service: String,
+    version: String,
+}
+
+impl HealthGrpcService {
+    pub fn new(service: impl Into<String>, version: impl Into<String>) -> Self {
+        Self {
+            service: service.into(),
+            version: version.into(),
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl HealthService for HealthGrpcService {
+    async fn check(
+        &self,
+        _request: Request<HealthCheckRequest>,
+    ) -> Result<Response<HealthCheckResponse>, Status> {
+        Ok(Response::new(HealthCheckResponse {
+            service: self.service.clone(),
+            status: HEALTH_SERVING.to_string(),
+            version: self.version.clone(),
+        }))
+    }
+}
@@ -0,0 +1,60 @@
+use crate::utils::InFlightRequests;
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tower::{Layer, Service};
+
+#[derive(Clone, Debug)]
+pub struct InFlightLayer {
+    in_flight: InFlightRequests,
+}
+
+impl InFlightLayer {
+    pub fn new(in_flight: InFlightRequests) -> Self {
+        Self { in_flight }
+    }
+}
+
+impl<S> Layer<S> for InFlightLayer {
+    type Service = InFlightService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        InFlightService {
+            inner,
+            in_flight: self.in_flight.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct InFlightService<S> {
+    inner: S,
+    in_flight: InFlightRequests,
+}
+
+impl<S, Req> Service<Req> for InFlightService<S>
+where
+    S: Service<Req>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Req) -> Self::Future {
+        let guard = self.in_flight.start();
+        let future = self.inner.call(req);
+
+        Box::pin(async move {
+            let response = future.await;
+            drop(guard);
+            response
+        })
+    }
+}
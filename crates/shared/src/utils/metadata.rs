@@ -1,5 +1,10 @@
 use opentelemetry::propagation::Injector;
-use tonic::metadata::{MetadataKey, MetadataMap, MetadataValue};
+use std::{env, sync::Arc};
+use tonic::{
+    Request, Status,
+    metadata::{MetadataKey, MetadataMap, MetadataValue},
+    service::Interceptor,
+};
 
 pub struct MetadataInjector<'a>(pub &'a mut MetadataMap);
 
@@ -12,3 +17,96 @@ impl<'a> Injector for MetadataInjector<'a> {
         }
     }
 }
+
+#[derive(Debug, Clone, Default)]
+pub struct RequiredMetadata {
+    keys: Arc<Vec<String>>,
+}
+
+impl RequiredMetadata {
+    pub fn new(keys: Vec<String>) -> Self {
+        Self {
+            keys: Arc::new(keys),
+        }
+    }
+
+    /// Reads `REQUIRED_GRPC_METADATA` as a comma separated list of keys;
+    /// an empty or missing value disables the check.
+    pub fn from_env() -> Self {
+        let keys = env::var("REQUIRED_GRPC_METADATA")
+            .unwrap_or_default()
+            .split(',')
+            .map(|k| k.trim().to_ascii_lowercase())
+            .filter(|k| !k.is_empty())
+            .collect();
+
+        Self::new(keys)
+    }
+}
+
+impl Interceptor for RequiredMetadata {
+    fn call(&mut self, request: Request<()>) -> Result<Request<()>, Status> {
+        let missing: Vec<&str> = self
+            .keys
+            .iter()
+            .filter(|key| request.metadata().get(key.as_str()).is_none())
+            .map(String::as_str)
+            .collect();
+
+        if !missing.is_empty() {
+            return Err(Status::invalid_argument(format!(
+                "Missing required metadata: {}",
+                missing.join(", ")
+            )));
+        }
+
+        Ok(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RequiredMetadata;
+    use tonic::{Code, Request, service::Interceptor};
+
+    fn request_with(headers: &[(&'static str, &'static str)]) -> Request<()> {
+        let mut request = Request::new(());
+        for (key, value) in headers {
+            request.metadata_mut().insert(*key, value.parse().unwrap());
+        }
+        request
+    }
+
+    #[test]
+    fn missing_keys_are_rejected_by_name() {
+        let mut required = RequiredMetadata::new(vec!["x-request-id".into(), "x-tenant".into()]);
+
+        let status = required
+            .call(request_with(&[("x-request-id", "abc")]))
+            .unwrap_err();
+
+        assert_eq!(status.code(), Code::InvalidArgument);
+        assert_eq!(status.message(), "Missing required metadata: x-tenant");
+    }
+
+    #[test]
+    fn requests_carrying_every_key_pass() {
+        let mut required = RequiredMetadata::new(vec!["x-request-id".into(), "x-tenant".into()]);
+
+        let request = required
+            .call(request_with(&[
+                ("x-request-id", "abc"),
+                ("x-tenant", "acme"),
+            ]))
+            .unwrap();
+
+        assert_eq!(request.metadata().get("x-tenant").unwrap(), "acme");
+    }
+
+    #[test]
+    fn no_configured_keys_lets_everything_through() {
+        let mut required = RequiredMetadata::default();
+
+        assert!(required.call(request_with(&[])).is_ok());
+    }
+}
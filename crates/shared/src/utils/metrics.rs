@@ -3,6 +3,7 @@ use opentelemetry::{
     KeyValue, global,
     metrics::{Counter, Gauge, Histogram},
 };
+use prometheus_client::{metrics::gauge::Gauge as PromGauge, registry::Registry};
 use std::fmt::{Display, Formatter};
 use std::{
     fmt, fs,
@@ -201,6 +202,118 @@ impl Default for Metrics {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LifecycleScope {
+    Single,
+    All,
+}
+
+impl Display for LifecycleScope {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            LifecycleScope::Single => "single",
+            LifecycleScope::All => "all",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Counts trash, restore, and purge operations per entity. Bulk operations
+/// count once with `scope="all"` since the repositories don't report rows.
+#[derive(Clone, Debug)]
+pub struct LifecycleMetrics {
+    entity: &'static str,
+    soft_delete_counter: Counter<u64>,
+    restore_counter: Counter<u64>,
+    purge_counter: Counter<u64>,
+}
+
+impl LifecycleMetrics {
+    pub fn new(meter: Meter, entity: &'static str) -> Self {
+        let soft_delete_counter = meter
+            .u64_counter("entity_soft_delete_total")
+            .with_description("Total number of records moved to trash")
+            .build();
+
+        let restore_counter = meter
+            .u64_counter("entity_restore_total")
+            .with_description("Total number of records restored from trash")
+            .build();
+
+        let purge_counter = meter
+            .u64_counter("entity_purge_total")
+            .with_description("Total number of records permanently deleted")
+            .build();
+
+        Self {
+            entity,
+            soft_delete_counter,
+            restore_counter,
+            purge_counter,
+        }
+    }
+
+    fn attributes(&self, scope: LifecycleScope) -> [KeyValue; 2] {
+        [
+            KeyValue::new("entity", self.entity),
+            KeyValue::new("scope", scope.to_string()),
+        ]
+    }
+
+    pub fn soft_deleted(&self, scope: LifecycleScope) {
+        self.soft_delete_counter.add(1, &self.attributes(scope));
+    }
+
+    pub fn restored(&self, scope: LifecycleScope) {
+        self.restore_counter.add(1, &self.attributes(scope));
+    }
+
+    pub fn purged(&self, scope: LifecycleScope) {
+        self.purge_counter.add(1, &self.attributes(scope));
+    }
+}
+
+/// Requests currently being served, exported only through the Prometheus
+/// registry the metrics server scrapes.
+#[derive(Clone, Debug, Default)]
+pub struct InFlightRequests {
+    current: PromGauge,
+}
+
+impl InFlightRequests {
+    pub fn register(&self, registry: &mut Registry) {
+        registry.register(
+            "requests_in_flight",
+            "Number of requests currently being processed",
+            self.current.clone(),
+        );
+    }
+
+    /// Marks a request as in flight until the returned guard is dropped.
+    pub fn start(&self) -> InFlightGuard {
+        self.current.inc();
+
+        InFlightGuard {
+            metrics: self.clone(),
+        }
+    }
+
+    pub fn current(&self) -> i64 {
+        self.current.get()
+    }
+}
+
+#[derive(Debug)]
+pub struct InFlightGuard {
+    metrics: InFlightRequests,
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.metrics.current.dec();
+    }
+}
+
 pub async fn run_metrics_collector(system_metrics: Arc<SystemMetrics>) {
     let mut interval = tokio::time::interval(std::time::Duration::from_secs(15));
     loop {
@@ -208,3 +321,105 @@ pub async fn run_metrics_collector(system_metrics: Arc<SystemMetrics>) {
         system_metrics.update_metrics().await;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{InFlightRequests, LifecycleMetrics, LifecycleScope};
+    use opentelemetry::metrics::MeterProvider;
+    use opentelemetry_sdk::metrics::{
+        InMemoryMetricExporter, PeriodicReader, SdkMeterProvider,
+        data::{AggregatedMetrics, MetricData},
+    };
+    use std::sync::Arc;
+    use tokio::sync::Barrier;
+
+    /// Value of counter `name` for the given entity and scope, as of the
+    /// last flush.
+    fn count(exporter: &InMemoryMetricExporter, name: &str, entity: &str, scope: &str) -> u64 {
+        let exported = exporter.get_finished_metrics().unwrap();
+        let Some(latest) = exported.last() else {
+            return 0;
+        };
+
+        latest
+            .scope_metrics()
+            .flat_map(|scope| scope.metrics())
+            .filter(|metric| metric.name() == name)
+            .filter_map(|metric| match metric.data() {
+                AggregatedMetrics::U64(MetricData::Sum(sum)) => Some(sum),
+                _ => None,
+            })
+            .flat_map(|sum| sum.data_points())
+            .filter(|point| {
+                let labels: Vec<_> = point
+                    .attributes()
+                    .map(|kv| (kv.key.to_string(), kv.value.to_string()))
+                    .collect();
+                labels.contains(&("entity".into(), entity.into()))
+                    && labels.contains(&("scope".into(), scope.into()))
+            })
+            .map(|point| point.value())
+            .sum()
+    }
+
+    #[test]
+    fn lifecycle_counters_count_per_entity_and_scope() {
+        let exporter = InMemoryMetricExporter::default();
+        let provider = SdkMeterProvider::builder()
+            .with_reader(PeriodicReader::builder(exporter.clone()).build())
+            .build();
+        let meter = provider.meter("product-command-service");
+
+        let products = LifecycleMetrics::new(meter.clone(), "product");
+        let orders = LifecycleMetrics::new(meter, "order");
+
+        products.soft_deleted(LifecycleScope::Single);
+        products.soft_deleted(LifecycleScope::Single);
+        products.restored(LifecycleScope::Single);
+        products.purged(LifecycleScope::All);
+        orders.soft_deleted(LifecycleScope::Single);
+        provider.force_flush().unwrap();
+
+        let product = |name, scope| count(&exporter, name, "product", scope);
+        assert_eq!(product("entity_soft_delete_total", "single"), 2);
+        assert_eq!(product("entity_restore_total", "single"), 1);
+        assert_eq!(product("entity_restore_total", "all"), 0);
+        assert_eq!(product("entity_purge_total", "all"), 1);
+        assert_eq!(product("entity_purge_total", "single"), 0);
+        assert_eq!(
+            count(&exporter, "entity_soft_delete_total", "order", "single"),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn in_flight_gauge_follows_held_guards() {
+        const TASKS: usize = 8;
+
+        let in_flight = InFlightRequests::default();
+        let started = Arc::new(Barrier::new(TASKS + 1));
+        let release = Arc::new(Barrier::new(TASKS + 1));
+
+        let tasks: Vec<_> = (0..TASKS)
+            .map(|_| {
+                let in_flight = in_flight.clone();
+                let started = started.clone();
+                let release = release.clone();
+                tokio::spawn(async move {
+                    let _guard = in_flight.start();
+                    started.wait().await;
+                    release.wait().await;
+                })
+            })
+            .collect();
+
+        started.wait().await;
+        assert_eq!(in_flight.current(), TASKS as i64);
+
+        release.wait().await;
+        for task in tasks {
+            task.await.unwrap();
+        }
+        assert_eq!(in_flight.current(), 0);
+    }
+}
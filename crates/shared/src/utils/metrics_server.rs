@@ -0,0 +1,130 @@
+use crate::config::{ConnectionPool, DatabasePools};
+use axum::{
+    Json, Router,
+    body::Body,
+    extract::State,
+    http::{StatusCode, header::CONTENT_TYPE},
+    response::{IntoResponse, Response},
+    routing::get,
+};
+use prometheus_client::{encoding::text::encode, registry::Registry};
+use serde::Serialize;
+use std::{net::SocketAddr, sync::Arc, time::Duration};
+use tokio::{net::TcpListener, task::JoinHandle};
+use tracing::{error, info, warn};
+
+const READINESS_TIMEOUT: Duration = Duration::from_secs(2);
+
+struct MetricsServerState {
+    registry: Registry,
+    pools: DatabasePools,
+}
+
+#[derive(Debug, Serialize)]
+struct DependencyReadiness {
+    name: &'static str,
+    ready: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct ReadinessResponse {
+    status: &'static str,
+    dependencies: Vec<DependencyReadiness>,
+}
+
+/// Serves `registry` as a Prometheus exposition on `addr/metrics`, so the
+/// gateway (or Prometheus itself) can scrape a service directly, and a
+/// `/ready` probe that fails while the database can't be queried.
+pub fn spawn_metrics_server(
+    addr: SocketAddr,
+    registry: Registry,
+    pools: DatabasePools,
+) -> JoinHandle<()> {
+    let app = Router::new()
+        .route("/metrics", get(metrics_handler))
+        .route("/ready", get(readiness_handler))
+        .with_state(Arc::new(MetricsServerState { registry, pools }));
+
+    tokio::spawn(async move {
+        let listener = match TcpListener::bind(addr).await {
+            Ok(listener) => listener,
+            Err(e) => {
+                error!("Failed to bind metrics server on {addr}: {e}");
+                return;
+            }
+        };
+
+        info!("📊 Metrics server running on http://{addr}/metrics");
+
+        if let Err(e) = axum::serve(listener, app).await {
+            error!("Metrics server failed: {e}");
+        }
+    })
+}
+
+async fn metrics_handler(State(state): State<Arc<MetricsServerState>>) -> Response {
+    let mut buffer = String::new();
+
+    if let Err(e) = encode(&mut buffer, &state.registry) {
+        return Response::builder()
+            .status(StatusCode::INTERNAL_SERVER_ERROR)
+            .body(Body::from(format!("Failed to encode metrics: {e}")))
+            .unwrap();
+    }
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(
+            CONTENT_TYPE,
+            "application/openmetrics-text; version=1.0.0; charset=utf-8",
+        )
+        .body(Body::from(buffer))
+        .unwrap()
+}
+
+/// Both pools are checked; without a replica the read pool is the primary
+/// itself, so the second query is just as cheap.
+async fn readiness_handler(State(state): State<Arc<MetricsServerState>>) -> impl IntoResponse {
+    let dependencies = vec![
+        check_database("database_write", &state.pools.write).await,
+        check_database("database_read", &state.pools.read).await,
+    ];
+
+    let ready = dependencies.iter().all(|d| d.ready);
+
+    let status = if ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (
+        status,
+        Json(ReadinessResponse {
+            status: if ready { "ready" } else { "not_ready" },
+            dependencies,
+        }),
+    )
+}
+
+async fn check_database(name: &'static str, pool: &ConnectionPool) -> DependencyReadiness {
+    let query = sqlx::query("SELECT 1").execute(pool);
+
+    let error = match tokio::time::timeout(READINESS_TIMEOUT, query).await {
+        Ok(Ok(_)) => None,
+        Ok(Err(e)) => Some(e.to_string()),
+        Err(_) => Some(format!("No answer within {READINESS_TIMEOUT:?}")),
+    };
+
+    if let Some(error) = &error {
+        warn!("Readiness check for {name} failed: {error}");
+    }
+
+    DependencyReadiness {
+        name,
+        ready: error.is_none(),
+        error,
+    }
+}
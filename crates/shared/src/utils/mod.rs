@@ -1,17 +1,68 @@
+mod backoff;
+mod batch;
+mod circuit_breaker;
+mod compression;
+mod connection_limit;
+mod cursor;
+mod email;
 mod gracefullshutdown;
+mod health;
+mod inflight;
 mod logs;
 mod metadata;
 mod metrics;
+mod metrics_server;
+mod money;
 mod otel;
+mod pagination;
 mod parse_datetime;
+mod password_breach;
+mod query_plan;
 mod random_string;
+mod read_only;
+mod request_id;
+mod slow_request;
+mod sort;
 mod template;
+mod upload;
 
+pub use self::backoff::RestartBackoff;
+pub use self::batch::{BatchFailure, BatchResponse};
+pub use self::circuit_breaker::CircuitBreaker;
+pub use self::compression::grpc_compression_from_env;
+pub use self::connection_limit::{
+    ConnectionLimit, ConnectionLimitAction, LimitedConnection, LimitedIncoming,
+};
+pub use self::cursor::{Cursor, CursorCodec};
+pub use self::email::EmailNormalizer;
 pub use self::gracefullshutdown::shutdown_signal;
+pub use self::health::{HEALTH_SERVING, HealthGrpcService};
+pub use self::inflight::{InFlightLayer, InFlightService};
 pub use self::logs::init_logger;
-pub use self::metadata::MetadataInjector;
-pub use self::metrics::{Method, Metrics, Status, SystemMetrics, run_metrics_collector};
+pub use self::metadata::{MetadataInjector, RequiredMetadata};
+pub use self::metrics::{
+    InFlightGuard, InFlightRequests, LifecycleMetrics, LifecycleScope, Method, Metrics, Status,
+    SystemMetrics, run_metrics_collector,
+};
+pub use self::metrics_server::spawn_metrics_server;
+pub use self::money::{Locale, Money, format_money};
 pub use self::otel::{Telemetry, TracingContext};
-pub use self::parse_datetime::{parse_datetime, parse_expiration_datetime};
+pub use self::pagination::page_count;
+pub use self::parse_datetime::{format_in_timezone, parse_datetime, parse_expiration_datetime};
+pub use self::password_breach::PasswordBreachChecker;
+pub use self::query_plan::QueryPlanLogger;
 pub use self::random_string::generate_random_string;
-pub use self::template::{EmailTemplate, EmailTemplateData, render_email};
+pub use self::read_only::{ReadOnlyLayer, ReadOnlyService};
+pub use self::request_id::{
+    REQUEST_ID_HEADER, RequestIdLayer, RequestIdPropagator, RequestIdService, current_request_id,
+    request_id_or_new, with_request_id,
+};
+pub use self::slow_request::{
+    SlowRequestLayer, SlowRequestService, slow_request_threshold_from_env,
+};
+pub use self::sort::{SortDirection, SortKey, order_by_clause, parse_sort};
+pub use self::template::{
+    EMAIL_TEMPLATES, EmailTemplate, EmailTemplateData, RenderedEmail, preview_email, render_email,
+    render_email_text,
+};
+pub use self::upload::{ImageType, UploadPolicy, UploadedFile};
@@ -0,0 +1,167 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Money {
+    pub amount: String,
+    pub currency: String,
+    pub display: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Locale {
+    pub thousands_separator: char,
+    pub decimal_separator: char,
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Self {
+            thousands_separator: ',',
+            decimal_separator: '.',
+        }
+    }
+}
+
+impl Locale {
+    /// Picks the first language from an `Accept-Language` header, falling back
+    /// to English separators when it is missing or unknown.
+    pub fn from_accept_language(header: Option<&str>) -> Self {
+        let language = header
+            .and_then(|h| h.split(',').next())
+            .and_then(|tag| tag.split(';').next())
+            .and_then(|tag| tag.trim().split(['-', '_']).next())
+            .map(|lang| lang.to_ascii_lowercase())
+            .unwrap_or_default();
+
+        match language.as_str() {
+            "id" | "de" | "es" | "it" | "nl" | "pt" | "tr" => Self {
+                thousands_separator: '.',
+                decimal_separator: ',',
+            },
+            "fr" => Self {
+                thousands_separator: ' ',
+                decimal_separator: ',',
+            },
+            _ => Self::default(),
+        }
+    }
+}
+
+/// Symbol and number of minor-unit digits for a currency code.
+fn currency_spec(currency: &str) -> (&str, u32) {
+    match currency {
+        "IDR" => ("Rp", 0),
+        "USD" => ("$", 2),
+        "EUR" => ("€", 2),
+        "GBP" => ("£", 2),
+        "SGD" => ("S$", 2),
+        "MYR" => ("RM", 2),
+        "JPY" => ("¥", 0),
+        _ => (currency, 2),
+    }
+}
+
+/// Formats an amount stored in the currency's minor unit, so `10000` is
+/// `Rp10.000` in IDR and `$100.00` in USD.
+pub fn format_money(amount: i64, currency: &str, locale: &Locale) -> Money {
+    let currency = currency.to_ascii_uppercase();
+    let (symbol, exponent) = currency_spec(&currency);
+
+    let divisor = 10_i64.pow(exponent);
+    let major = amount.unsigned_abs() / divisor as u64;
+    let minor = amount.unsigned_abs() % divisor as u64;
+    let sign = if amount < 0 { "-" } else { "" };
+
+    let (plain_fraction, display_fraction) = if exponent > 0 {
+        let digits = format!("{minor:0width$}", width = exponent as usize);
+        (
+            format!(".{digits}"),
+            format!("{}{digits}", locale.decimal_separator),
+        )
+    } else {
+        (String::new(), String::new())
+    };
+
+    let grouped = group_thousands(major, locale.thousands_separator);
+    let separator = if symbol.chars().all(|c| c.is_ascii_alphabetic()) && symbol.len() == 3 {
+        " "
+    } else {
+        ""
+    };
+
+    Money {
+        amount: format!("{sign}{major}{plain_fraction}"),
+        display: format!("{sign}{symbol}{separator}{grouped}{display_fraction}"),
+        currency,
+    }
+}
+
+fn group_thousands(value: u64, separator: char) -> String {
+    let digits = value.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(separator);
+        }
+        grouped.push(digit);
+    }
+
+    grouped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_rupiah_for_indonesian_locale() {
+        let locale = Locale::from_accept_language(Some("id-ID,id;q=0.9,en;q=0.8"));
+        let money = format_money(10_000, "idr", &locale);
+
+        assert_eq!(money.display, "Rp10.000");
+        assert_eq!(money.amount, "10000");
+        assert_eq!(money.currency, "IDR");
+    }
+
+    #[test]
+    fn formats_dollars_for_english_locale() {
+        let locale = Locale::from_accept_language(Some("en-US"));
+        let money = format_money(1_000, "USD", &locale);
+
+        assert_eq!(money.display, "$10.00");
+        assert_eq!(money.amount, "10.00");
+    }
+
+    #[test]
+    fn locale_only_changes_the_display_string() {
+        let english = format_money(123_456_789, "USD", &Locale::default());
+        let german = format_money(
+            123_456_789,
+            "USD",
+            &Locale::from_accept_language(Some("de")),
+        );
+
+        assert_eq!(english.display, "$1,234,567.89");
+        assert_eq!(german.display, "$1.234.567,89");
+        assert_eq!(english.amount, german.amount);
+    }
+
+    #[test]
+    fn unknown_or_missing_language_uses_english_separators() {
+        assert_eq!(Locale::from_accept_language(None), Locale::default());
+        assert_eq!(
+            Locale::from_accept_language(Some("xx-YY")),
+            Locale::default()
+        );
+    }
+
+    #[test]
+    fn keeps_the_sign_and_spaces_letter_codes() {
+        let money = format_money(-250, "CHF", &Locale::default());
+
+        assert_eq!(money.display, "-CHF 2.50");
+        assert_eq!(money.amount, "-2.50");
+    }
+}
@@ -1,20 +1,35 @@
-use std::{sync::OnceLock, time::Duration};
+use std::{
+    env,
+    net::{TcpStream, ToSocketAddrs},
+    sync::OnceLock,
+    time::Duration,
+};
 
 use anyhow::Result;
-use opentelemetry::{Context, global};
+use opentelemetry::{Context, global, propagation::TextMapCompositePropagator};
 use opentelemetry_otlp::{LogExporter, MetricExporter, SpanExporter, WithExportConfig};
 use opentelemetry_sdk::{
     Resource,
     logs::SdkLoggerProvider,
     metrics::{PeriodicReader, SdkMeterProvider},
+    propagation::TraceContextPropagator,
     trace::SdkTracerProvider,
 };
 use tokio::time::Instant;
 
+use super::request_id::RequestIdPropagator;
+
+const COLLECTOR_PROBE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Builds the OTLP providers. With `OTEL_FALLBACK_TO_LOCAL` (default on) an
+/// exporter that can't be built is dropped instead of aborting startup, so
+/// the service keeps logging to stdout. OTLP channels connect lazily, so an
+/// unreachable collector is retried on every export until it comes back.
 #[derive(Clone)]
 pub struct Telemetry {
     service_name: String,
     otel_endpoint: String,
+    fallback_to_local: bool,
 }
 
 pub struct TracingContext {
@@ -24,12 +39,59 @@ pub struct TracingContext {
 
 impl Telemetry {
     pub fn new(service_name: impl Into<String>, otel_endpoint: String) -> Self {
+        let fallback_to_local = env::var("OTEL_FALLBACK_TO_LOCAL")
+            .map(|v| v != "false" && v != "0")
+            .unwrap_or(true);
+
         Self {
             service_name: service_name.into(),
             otel_endpoint,
+            fallback_to_local,
         }
     }
 
+    /// Probes the collector once per process. The console subscriber is not
+    /// installed yet at this point, hence `eprintln!`.
+    fn warn_if_collector_unreachable(&self) {
+        static PROBED: OnceLock<()> = OnceLock::new();
+
+        PROBED.get_or_init(|| {
+            if !self.collector_reachable() {
+                eprintln!(
+                    "⚠️ OTEL collector at {} is unreachable; logs stay on stdout and exports are retried in the background",
+                    self.otel_endpoint
+                );
+            }
+        });
+    }
+
+    fn collector_reachable(&self) -> bool {
+        let authority = self
+            .otel_endpoint
+            .split("://")
+            .last()
+            .unwrap_or_default()
+            .split('/')
+            .next()
+            .unwrap_or_default();
+
+        let Ok(addrs) = authority.to_socket_addrs() else {
+            return false;
+        };
+
+        addrs
+            .into_iter()
+            .any(|addr| TcpStream::connect_timeout(&addr, COLLECTOR_PROBE_TIMEOUT).is_ok())
+    }
+
+    fn degrade(&self, signal: &str, err: impl std::fmt::Display) {
+        if !self.fallback_to_local {
+            panic!("Failed to create {signal} exporter: {err}");
+        }
+
+        eprintln!("⚠️ Failed to create {signal} exporter, continuing without it: {err}");
+    }
+
     fn get_resource(&self) -> Resource {
         static RESOURCE: OnceLock<Resource> = OnceLock::new();
         RESOURCE
@@ -42,38 +104,52 @@ impl Telemetry {
     }
 
     pub fn init_tracer(&self) -> SdkTracerProvider {
-        let exporter = SpanExporter::builder()
+        self.warn_if_collector_unreachable();
+
+        let mut builder = SdkTracerProvider::builder().with_resource(self.get_resource());
+
+        match SpanExporter::builder()
             .with_tonic()
             .with_endpoint(self.otel_endpoint.clone())
             .build()
-            .expect("Failed to create span exporter");
+        {
+            Ok(exporter) => builder = builder.with_batch_exporter(exporter),
+            Err(e) => self.degrade("span", e),
+        }
 
-        let provider = SdkTracerProvider::builder()
-            .with_resource(self.get_resource())
-            .with_batch_exporter(exporter)
-            .build();
+        let provider = builder.build();
 
         global::set_tracer_provider(provider.clone());
+        global::set_text_map_propagator(TextMapCompositePropagator::new(vec![
+            Box::new(TraceContextPropagator::new()),
+            Box::new(RequestIdPropagator),
+        ]));
 
         provider
     }
 
     pub fn init_meter(&self) -> SdkMeterProvider {
-        let exporter = MetricExporter::builder()
+        self.warn_if_collector_unreachable();
+
+        let mut builder = SdkMeterProvider::builder().with_resource(self.get_resource());
+
+        match MetricExporter::builder()
             .with_tonic()
             .with_endpoint(self.otel_endpoint.clone())
             .with_timeout(Duration::from_secs(10))
             .build()
-            .expect("failed to build OTLP metric exporter");
+        {
+            Ok(exporter) => {
+                let reader = PeriodicReader::builder(exporter)
+                    .with_interval(Duration::from_secs(5))
+                    .build();
 
-        let reader = PeriodicReader::builder(exporter)
-            .with_interval(Duration::from_secs(5))
-            .build();
+                builder = builder.with_reader(reader);
+            }
+            Err(e) => self.degrade("metric", e),
+        }
 
-        let provider = SdkMeterProvider::builder()
-            .with_resource(self.get_resource())
-            .with_reader(reader)
-            .build();
+        let provider = builder.build();
 
         global::set_meter_provider(provider.clone());
 
@@ -81,16 +157,20 @@ impl Telemetry {
     }
 
     pub fn init_logger(&self) -> SdkLoggerProvider {
-        let exporter = LogExporter::builder()
+        self.warn_if_collector_unreachable();
+
+        let mut builder = SdkLoggerProvider::builder().with_resource(self.get_resource());
+
+        match LogExporter::builder()
             .with_tonic()
             .with_endpoint(self.otel_endpoint.clone())
             .build()
-            .expect("Failed to create log exporter");
+        {
+            Ok(exporter) => builder = builder.with_batch_exporter(exporter),
+            Err(e) => self.degrade("log", e),
+        }
 
-        SdkLoggerProvider::builder()
-            .with_resource(self.get_resource())
-            .with_batch_exporter(exporter)
-            .build()
+        builder.build()
     }
 
     pub async fn shutdown(self) -> Result<()> {
@@ -113,3 +193,91 @@ impl Telemetry {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Telemetry;
+    use opentelemetry_appender_tracing::layer::OpenTelemetryTracingBridge;
+    use std::{
+        io::Write,
+        net::TcpListener,
+        sync::{Arc, Mutex},
+        time::{Duration, Instant},
+    };
+    use tracing_subscriber::{fmt, prelude::*};
+
+    #[derive(Clone, Default)]
+    struct Captured(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn telemetry(otel_endpoint: String) -> Telemetry {
+        Telemetry {
+            service_name: "otel-test".into(),
+            otel_endpoint,
+            fallback_to_local: true,
+        }
+    }
+
+    #[tokio::test]
+    async fn unreachable_collector_still_starts_and_logs_locally() {
+        // Bound and released, so nothing is listening there any more.
+        let port = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let telemetry = telemetry(format!("http://127.0.0.1:{port}"));
+
+        let started = Instant::now();
+        let logger_provider = telemetry.init_logger();
+        let _meter_provider = telemetry.init_meter();
+        let _tracer_provider = telemetry.init_tracer();
+        assert!(started.elapsed() < Duration::from_secs(5));
+
+        let captured = Captured::default();
+        let writer = captured.clone();
+        let subscriber = tracing_subscriber::registry()
+            .with(
+                fmt::layer()
+                    .with_ansi(false)
+                    .with_writer(move || writer.clone()),
+            )
+            .with(OpenTelemetryTracingBridge::new(&logger_provider));
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!("order service ready");
+        });
+
+        let output = String::from_utf8(captured.0.lock().unwrap().clone()).unwrap();
+        assert!(output.contains("order service ready"), "{output}");
+    }
+
+    #[tokio::test]
+    async fn unbuildable_exporters_are_dropped_with_the_fallback_on() {
+        let telemetry = telemetry("not a uri".into());
+
+        let _logger_provider = telemetry.init_logger();
+        let _meter_provider = telemetry.init_meter();
+        let _tracer_provider = telemetry.init_tracer();
+    }
+
+    #[test]
+    #[should_panic(expected = "Failed to create log exporter")]
+    fn unbuildable_exporters_abort_without_the_fallback() {
+        let telemetry = Telemetry {
+            fallback_to_local: false,
+            ..telemetry("not a uri".into())
+        };
+
+        telemetry.init_logger();
+    }
+}
@@ -0,0 +1,32 @@
+/// Number of pages `total_items` fill at `page_size` per page. An empty
+/// result has zero pages, so clients can tell "nothing matched" from "one
+/// page of results".
+pub fn page_count(total_items: i64, page_size: i32) -> i32 {
+    if total_items <= 0 || page_size <= 0 {
+        return 0;
+    }
+
+    ((total_items - 1) / page_size as i64 + 1) as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::page_count;
+
+    #[test]
+    fn an_empty_result_has_no_pages() {
+        assert_eq!(page_count(0, 10), 0);
+    }
+
+    #[test]
+    fn a_partly_filled_last_page_still_counts() {
+        assert_eq!(page_count(1, 10), 1);
+        assert_eq!(page_count(10, 10), 1);
+        assert_eq!(page_count(11, 10), 2);
+    }
+
+    #[test]
+    fn a_page_size_of_zero_has_no_pages() {
+        assert_eq!(page_count(5, 0), 0);
+    }
+}
@@ -1,4 +1,5 @@
 use chrono::{DateTime, NaiveDateTime, Utc};
+use chrono_tz::Tz;
 
 pub fn parse_datetime(value: &str) -> Option<String> {
     if value.is_empty() {
@@ -10,6 +11,14 @@ pub fn parse_datetime(value: &str) -> Option<String> {
     }
 }
 
+/// Rewrites an RFC3339 timestamp with `tz`'s offset; the instant it denotes
+/// is unchanged.
+pub fn format_in_timezone(value: &str, tz: Tz) -> Option<String> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&tz).to_rfc3339())
+        .ok()
+}
+
 pub fn parse_expiration_datetime(input: &str) -> Result<NaiveDateTime, chrono::ParseError> {
     NaiveDateTime::parse_from_str(input, "%Y-%m-%d %H:%M:%S")
 }
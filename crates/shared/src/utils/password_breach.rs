@@ -0,0 +1,164 @@
+use crate::abstract_trait::PasswordBreachCheckerTrait;
+use async_trait::async_trait;
+use sha1::{Digest, Sha1};
+use std::{env, time::Duration};
+use tracing::{info, warn};
+
+const DEFAULT_RANGE_URL: &str = "https://api.pwnedpasswords.com/range";
+
+/// Checks passwords against a breached-password range API using k-anonymity:
+/// only the first five characters of the SHA-1 hash ever leave the service.
+#[derive(Clone)]
+pub struct PasswordBreachChecker {
+    client: reqwest::Client,
+    range_url: String,
+    enabled: bool,
+}
+
+impl PasswordBreachChecker {
+    pub fn new(enabled: bool, range_url: impl Into<String>) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(3))
+            .build()
+            .unwrap_or_default();
+
+        Self {
+            client,
+            range_url: range_url.into().trim_end_matches('/').to_string(),
+            enabled,
+        }
+    }
+
+    pub fn from_env() -> Self {
+        let enabled = env::var("PASSWORD_BREACH_CHECK")
+            .map(|v| v == "true" || v == "1")
+            .unwrap_or(false);
+
+        let range_url =
+            env::var("PASSWORD_BREACH_API_URL").unwrap_or_else(|_| DEFAULT_RANGE_URL.to_string());
+
+        Self::new(enabled, range_url)
+    }
+
+    async fn fetch_range(&self, prefix: &str) -> Result<String, reqwest::Error> {
+        self.client
+            .get(format!("{}/{prefix}", self.range_url))
+            .header("Add-Padding", "true")
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await
+    }
+}
+
+#[async_trait]
+impl PasswordBreachCheckerTrait for PasswordBreachChecker {
+    async fn is_breached(&self, password: &str) -> bool {
+        if !self.enabled {
+            return false;
+        }
+
+        let hash = format!("{:X}", Sha1::digest(password.as_bytes()));
+        let (prefix, suffix) = hash.split_at(5);
+
+        let body = match self.fetch_range(prefix).await {
+            Ok(body) => body,
+            Err(e) => {
+                warn!("⚠️ Password breach check unavailable, allowing password: {e}");
+                return false;
+            }
+        };
+
+        let breached = body.lines().any(|line| {
+            line.split_once(':').is_some_and(|(candidate, count)| {
+                candidate.trim().eq_ignore_ascii_case(suffix)
+                    && count.trim().parse::<u64>().unwrap_or(0) > 0
+            })
+        });
+
+        if breached {
+            info!("🔐 Password rejected, found in breached password range");
+        }
+
+        breached
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PasswordBreachChecker;
+    use crate::abstract_trait::PasswordBreachCheckerTrait;
+    use axum::{Router, extract::Path, routing::get};
+    use std::sync::{Arc, Mutex};
+    use tokio::net::TcpListener;
+
+    /// SHA-1 of "password" is 5BAA61E4C9B93F3F0682250B6CF8331B7EE68FD8.
+    const RANGE_5BAA6: &str = "0018A45C4D1DEF81644B54AB7F969B88D65:0\r\n\
+        1E4C9B93F3F0682250B6CF8331B7EE68FD8:3861493\r\n\
+        1E4F9F8F7E6B4B1B1B1B1B1B1B1B1B1B1B1:2";
+
+    /// Serves `RANGE_5BAA6` for every prefix and records the prefixes asked
+    /// for; returns the range URL and that record.
+    async fn range_api() -> (String, Arc<Mutex<Vec<String>>>) {
+        let asked = Arc::new(Mutex::new(Vec::new()));
+        let record = asked.clone();
+        let app = Router::new().route(
+            "/range/{prefix}",
+            get(move |Path(prefix): Path<String>| async move {
+                record.lock().unwrap().push(prefix);
+                RANGE_5BAA6
+            }),
+        );
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        (format!("http://{addr}/range"), asked)
+    }
+
+    #[tokio::test]
+    async fn only_the_hash_prefix_leaves_the_service() {
+        let (url, asked) = range_api().await;
+        let checker = PasswordBreachChecker::new(true, url);
+
+        checker.is_breached("password").await;
+        checker.is_breached("correct horse battery staple").await;
+
+        let asked = asked.lock().unwrap();
+        assert_eq!(asked[0], "5BAA6");
+        assert!(asked.iter().all(|prefix| prefix.len() == 5));
+    }
+
+    #[tokio::test]
+    async fn a_listed_suffix_is_breached_and_others_are_not() {
+        let (url, _) = range_api().await;
+        let checker = PasswordBreachChecker::new(true, url);
+
+        assert!(checker.is_breached("password").await);
+        // The fake serves the same range for every prefix; this hash's
+        // suffix is not in it.
+        assert!(!checker.is_breached("a password nobody has leaked").await);
+    }
+
+    #[tokio::test]
+    async fn an_unreachable_api_allows_the_password() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+
+        let checker = PasswordBreachChecker::new(true, format!("http://{addr}/range"));
+
+        assert!(!checker.is_breached("password").await);
+    }
+
+    #[tokio::test]
+    async fn a_disabled_checker_never_calls_out() {
+        let (url, asked) = range_api().await;
+        let checker = PasswordBreachChecker::new(false, url);
+
+        assert!(!checker.is_breached("password").await);
+        assert!(asked.lock().unwrap().is_empty());
+    }
+}
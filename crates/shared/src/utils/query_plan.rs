@@ -0,0 +1,158 @@
+use crate::config::ConnectionPool;
+use std::{env, time::Duration};
+use tracing::{info, warn};
+
+/// Debug aid for index tuning: when a query takes longer than the threshold,
+/// its plan is fetched with `EXPLAIN (GENERIC_PLAN)` and logged. The query is
+/// planned, never executed again, and the bound values never reach the log.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct QueryPlanLogger {
+    threshold: Option<Duration>,
+}
+
+impl QueryPlanLogger {
+    pub fn new(threshold: Option<Duration>) -> Self {
+        Self { threshold }
+    }
+
+    /// Off unless `EXPLAIN_SLOW_QUERIES=true`; `SLOW_QUERY_THRESHOLD_MS`
+    /// (default 500) sets what counts as slow.
+    pub fn from_env() -> Self {
+        let enabled = env::var("EXPLAIN_SLOW_QUERIES")
+            .map(|v| v.eq_ignore_ascii_case("true") || v == "1")
+            .unwrap_or(false);
+
+        if !enabled {
+            return Self::default();
+        }
+
+        let threshold_ms = env::var("SLOW_QUERY_THRESHOLD_MS")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+            .unwrap_or(500);
+
+        Self::new(Some(Duration::from_millis(threshold_ms)))
+    }
+
+    /// Logs the plan of `sql` in the background when `elapsed` is over the
+    /// threshold. Does nothing, and costs nothing, while the logger is off.
+    pub fn observe(
+        &self,
+        pool: &ConnectionPool,
+        label: &'static str,
+        sql: &str,
+        elapsed: Duration,
+    ) {
+        let Some(threshold) = self.threshold else {
+            return;
+        };
+
+        if elapsed <= threshold {
+            return;
+        }
+
+        let pool = pool.clone();
+        let explain = format!("EXPLAIN (GENERIC_PLAN, FORMAT TEXT) {sql}");
+
+        tokio::spawn(async move {
+            match sqlx::query_scalar::<_, String>(&explain)
+                .fetch_all(&pool)
+                .await
+            {
+                Ok(lines) => info!(
+                    query = label,
+                    elapsed_ms = elapsed.as_millis() as u64,
+                    threshold_ms = threshold.as_millis() as u64,
+                    "🐢 Slow query plan:\n{}",
+                    lines.join("\n")
+                ),
+                Err(e) => warn!(query = label, "Failed to explain slow query: {e}"),
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::{PgPool, postgres::PgPoolOptions};
+    use std::{
+        io::Write,
+        sync::{Arc, Mutex},
+        time::Instant,
+    };
+    use tracing_subscriber::fmt;
+
+    #[derive(Clone, Default)]
+    struct Captured(Arc<Mutex<Vec<u8>>>);
+
+    impl Captured {
+        fn text(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    impl Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[sqlx::test]
+    #[ignore = "needs a Postgres server at DATABASE_URL"]
+    async fn plans_of_slow_queries_are_logged(pool: PgPool) {
+        let captured = Captured::default();
+        let writer = captured.clone();
+        let subscriber = fmt()
+            .with_ansi(false)
+            .with_writer(move || writer.clone())
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let sql = "SELECT pg_sleep(0.05)";
+        let started = Instant::now();
+        sqlx::query(sql).execute(&pool).await.unwrap();
+
+        QueryPlanLogger::new(Some(Duration::from_millis(10))).observe(
+            &pool,
+            "sleepy",
+            sql,
+            started.elapsed(),
+        );
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !captured.text().contains("Slow query plan") && Instant::now() < deadline {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+
+        let logged = captured.text();
+        assert!(logged.contains("query=\"sleepy\""), "{logged}");
+        assert!(logged.contains("threshold_ms=10"), "{logged}");
+        assert!(logged.contains("Result  (cost="), "{logged}");
+    }
+
+    #[tokio::test]
+    async fn nothing_is_explained_while_off_or_under_the_threshold() {
+        // Never connects: nothing may reach the database here.
+        let pool = PgPoolOptions::new()
+            .connect_lazy("postgres://postgres@127.0.0.1:1/postgres")
+            .unwrap();
+        let metrics = tokio::runtime::Handle::current().metrics();
+        let tasks_before = metrics.num_alive_tasks();
+
+        QueryPlanLogger::default().observe(&pool, "off", "SELECT 1", Duration::from_secs(10));
+        QueryPlanLogger::new(Some(Duration::from_secs(1))).observe(
+            &pool,
+            "fast",
+            "SELECT 1",
+            Duration::from_millis(10),
+        );
+
+        assert_eq!(metrics.num_alive_tasks(), tasks_before);
+        assert_eq!(pool.size(), 0);
+    }
+}
@@ -0,0 +1,78 @@
+use std::{
+    env,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tonic::{Status, codegen::http};
+use tower::{Layer, Service};
+
+/// Rejects every call to a `*CommandService` with `PERMISSION_DENIED` while
+/// `READ_ONLY_MODE` is on; query services keep answering.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ReadOnlyLayer {
+    enabled: bool,
+}
+
+impl ReadOnlyLayer {
+    pub fn new(enabled: bool) -> Self {
+        Self { enabled }
+    }
+
+    pub fn from_env() -> Self {
+        Self::new(
+            env::var("READ_ONLY_MODE")
+                .map(|v| v == "true" || v == "1")
+                .unwrap_or(false),
+        )
+    }
+}
+
+impl<S> Layer<S> for ReadOnlyLayer {
+    type Service = ReadOnlyService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ReadOnlyService {
+            inner,
+            enabled: self.enabled,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ReadOnlyService<S> {
+    inner: S,
+    enabled: bool,
+}
+
+/// gRPC paths look like `/order.OrderCommandService/CreateOrder`.
+fn is_command_call(path: &str) -> bool {
+    path.trim_start_matches('/')
+        .split('/')
+        .next()
+        .is_some_and(|service| service.ends_with("CommandService"))
+}
+
+impl<S, ReqBody, ResBody> Service<http::Request<ReqBody>> for ReadOnlyService<S>
+where
+    S: Service<http::Request<ReqBody>, Response = http::Response<ResBody>>,
+    S::Future: Send + 'static,
+    ResBody: Default + Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: http::Request<ReqBody>) -> Self::Future {
+        if self.enabled && is_command_call(req.uri().path()) {
+            let status = Status::permission_denied("Service is in read-only mode");
+            return Box::pin(async move { Ok(status.into_http()) });
+        }
+
+        Box::pin(self.inner.call(req))
+    }
+}
@@ -0,0 +1,116 @@
+use opentelemetry::{
+    Context as OtelContext,
+    propagation::{Extractor, Injector, TextMapPropagator, text_map_propagator::FieldIter},
+};
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::OnceLock,
+    task::{Context, Poll},
+};
+use tonic::codegen::http;
+use tower::{Layer, Service};
+use tracing::{Instrument, info_span};
+use uuid::Uuid;
+
+/// Header, gRPC metadata key and Kafka header carrying the request ID.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+/// ID of the request being handled, `None` outside a request scope.
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID.try_with(Clone::clone).ok()
+}
+
+/// Runs `future` with `id` as the current request ID.
+pub async fn with_request_id<F: Future>(id: String, future: F) -> F::Output {
+    REQUEST_ID.scope(id, future).await
+}
+
+/// Keeps a caller supplied ID when it is short and made of URL-safe
+/// characters, so it can't inject anything into the logs; otherwise a new
+/// one is generated.
+pub fn request_id_or_new(supplied: Option<&str>) -> String {
+    supplied
+        .map(str::trim)
+        .filter(|id| {
+            !id.is_empty()
+                && id.len() <= 128
+                && id
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        })
+        .map(str::to_string)
+        .unwrap_or_else(|| Uuid::new_v4().to_string())
+}
+
+/// Adds the current request ID to outgoing gRPC metadata alongside the
+/// trace context, so every `MetadataInjector` call site forwards it.
+#[derive(Debug, Default)]
+pub struct RequestIdPropagator;
+
+impl TextMapPropagator for RequestIdPropagator {
+    fn inject_context(&self, _cx: &OtelContext, injector: &mut dyn Injector) {
+        if let Some(id) = current_request_id() {
+            injector.set(REQUEST_ID_HEADER, id);
+        }
+    }
+
+    fn extract_with_context(&self, cx: &OtelContext, _extractor: &dyn Extractor) -> OtelContext {
+        cx.clone()
+    }
+
+    fn fields(&self) -> FieldIter<'_> {
+        static FIELDS: OnceLock<Vec<String>> = OnceLock::new();
+
+        FieldIter::new(FIELDS.get_or_init(|| vec![REQUEST_ID_HEADER.to_string()]))
+    }
+}
+
+/// Picks up the caller's `x-request-id` (or starts a new one) and runs the
+/// gRPC call inside a `request_id` span and request scope, so its logs and
+/// its own downstream calls and events carry the same ID.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RequestIdLayer;
+
+impl<S> Layer<S> for RequestIdLayer {
+    type Service = RequestIdService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequestIdService { inner }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RequestIdService<S> {
+    inner: S,
+}
+
+impl<S, ReqBody> Service<http::Request<ReqBody>> for RequestIdService<S>
+where
+    S: Service<http::Request<ReqBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: http::Request<ReqBody>) -> Self::Future {
+        let request_id = request_id_or_new(
+            req.headers()
+                .get(REQUEST_ID_HEADER)
+                .and_then(|v| v.to_str().ok()),
+        );
+        let span = info_span!("grpc_request", request_id = %request_id, method = req.uri().path());
+        let future = self.inner.call(req);
+
+        Box::pin(with_request_id(request_id, future).instrument(span))
+    }
+}
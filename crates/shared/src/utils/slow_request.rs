@@ -0,0 +1,190 @@
+use std::{
+    env,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+use tonic::codegen::http;
+use tower::{Layer, Service};
+use tracing::warn;
+
+/// Reads `SLOW_REQUEST_THRESHOLD_MS` (default 1000); `0` turns slow-request
+/// logging off.
+pub fn slow_request_threshold_from_env() -> Option<Duration> {
+    let threshold_ms = env::var("SLOW_REQUEST_THRESHOLD_MS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .unwrap_or(1_000);
+
+    (threshold_ms > 0).then(|| Duration::from_millis(threshold_ms))
+}
+
+/// Logs a warning for every gRPC call that takes longer than the threshold,
+/// with the method, the caller's `traceparent` and how long it took.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SlowRequestLayer {
+    threshold: Option<Duration>,
+}
+
+impl SlowRequestLayer {
+    pub fn new(threshold: Option<Duration>) -> Self {
+        Self { threshold }
+    }
+
+    pub fn from_env() -> Self {
+        Self::new(slow_request_threshold_from_env())
+    }
+}
+
+impl<S> Layer<S> for SlowRequestLayer {
+    type Service = SlowRequestService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        SlowRequestService {
+            inner,
+            threshold: self.threshold,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SlowRequestService<S> {
+    inner: S,
+    threshold: Option<Duration>,
+}
+
+impl<S, ReqBody> Service<http::Request<ReqBody>> for SlowRequestService<S>
+where
+    S: Service<http::Request<ReqBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: http::Request<ReqBody>) -> Self::Future {
+        let Some(threshold) = self.threshold else {
+            return Box::pin(self.inner.call(req));
+        };
+
+        let method = req.uri().path().to_string();
+        let traceparent = req
+            .headers()
+            .get("traceparent")
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        let started = Instant::now();
+        let future = self.inner.call(req);
+
+        Box::pin(async move {
+            let response = future.await;
+            let elapsed = started.elapsed();
+
+            if elapsed > threshold {
+                warn!(
+                    method,
+                    traceparent = traceparent.as_deref().unwrap_or("-"),
+                    elapsed_ms = elapsed.as_millis() as u64,
+                    threshold_ms = threshold.as_millis() as u64,
+                    "🐢 Slow gRPC call"
+                );
+            }
+
+            response
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        convert::Infallible,
+        io::Write,
+        sync::{Arc, Mutex},
+    };
+    use tracing_subscriber::fmt;
+
+    #[derive(Clone, Default)]
+    struct Captured(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Answers every call after sleeping for the given time.
+    #[derive(Clone)]
+    struct Delayed(Duration);
+
+    impl Service<http::Request<()>> for Delayed {
+        type Response = ();
+        type Error = Infallible;
+        type Future = Pin<Box<dyn Future<Output = Result<(), Infallible>> + Send>>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _: http::Request<()>) -> Self::Future {
+            let delay = self.0;
+            Box::pin(async move {
+                tokio::time::sleep(delay).await;
+                Ok(())
+            })
+        }
+    }
+
+    async fn logged_for(delay: Duration) -> String {
+        let captured = Captured::default();
+        let writer = captured.clone();
+        let subscriber = fmt()
+            .with_ansi(false)
+            .with_writer(move || writer.clone())
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let mut service =
+            SlowRequestLayer::new(Some(Duration::from_millis(50))).layer(Delayed(delay));
+        let request = http::Request::builder()
+            .uri("/order.OrderService/FindAll")
+            .header(
+                "traceparent",
+                "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            )
+            .body(())
+            .unwrap();
+        service.call(request).await.unwrap();
+
+        String::from_utf8(captured.0.lock().unwrap().clone()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn only_calls_over_the_threshold_are_logged() {
+        let slow = logged_for(Duration::from_millis(120)).await;
+        assert!(slow.contains("WARN"), "{slow}");
+        assert!(slow.contains("Slow gRPC call"), "{slow}");
+        assert!(
+            slow.contains("method=\"/order.OrderService/FindAll\""),
+            "{slow}"
+        );
+        assert!(
+            slow.contains("traceparent=\"00-4bf92f3577b34da6a3ce929d0e0e4736"),
+            "{slow}"
+        );
+        assert!(slow.contains("threshold_ms=50"), "{slow}");
+
+        let fast = logged_for(Duration::ZERO).await;
+        assert!(fast.is_empty(), "{fast}");
+    }
+}
@@ -0,0 +1,170 @@
+use crate::errors::RepositoryError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+impl SortDirection {
+    fn as_sql(&self) -> &'static str {
+        match self {
+            Self::Asc => "ASC",
+            Self::Desc => "DESC",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SortKey {
+    pub field: String,
+    pub direction: SortDirection,
+}
+
+/// Parses `price:desc,name:asc`; a key without a direction sorts ascending.
+pub fn parse_sort(raw: &str) -> Result<Vec<SortKey>, RepositoryError> {
+    let mut keys: Vec<SortKey> = Vec::new();
+
+    for entry in raw.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+        let (field, direction) = entry.split_once(':').unwrap_or((entry, "asc"));
+        let field = field.trim().to_ascii_lowercase();
+
+        let direction = match direction.trim().to_ascii_lowercase().as_str() {
+            "asc" => SortDirection::Asc,
+            "desc" => SortDirection::Desc,
+            other => {
+                return Err(RepositoryError::InvalidSort(format!(
+                    "Unknown sort direction '{other}' for '{field}', expected asc or desc"
+                )));
+            }
+        };
+
+        if keys.iter().any(|k| k.field == field) {
+            return Err(RepositoryError::InvalidSort(format!(
+                "Sort field '{field}' is given more than once"
+            )));
+        }
+
+        keys.push(SortKey { field, direction });
+    }
+
+    Ok(keys)
+}
+
+/// Builds an `ORDER BY` list from `keys`. Only the column expressions in
+/// `allowed` (public name → SQL) ever reach the query text, so user input is
+/// never interpolated. `tie_breaker` is appended unless already sorted on, to
+/// keep page boundaries stable.
+pub fn order_by_clause(
+    keys: &[SortKey],
+    allowed: &[(&str, &str)],
+    tie_breaker: &str,
+) -> Result<String, RepositoryError> {
+    let mut columns = Vec::with_capacity(keys.len() + 1);
+
+    for key in keys {
+        let column = allowed
+            .iter()
+            .find(|(name, _)| *name == key.field)
+            .map(|(_, column)| *column)
+            .ok_or_else(|| {
+                let names: Vec<&str> = allowed.iter().map(|(name, _)| *name).collect();
+                RepositoryError::InvalidSort(format!(
+                    "Cannot sort by '{}', allowed fields: {}",
+                    key.field,
+                    names.join(", ")
+                ))
+            })?;
+
+        columns.push(format!("{column} {}", key.direction.as_sql()));
+    }
+
+    if !keys.iter().any(|key| {
+        allowed
+            .iter()
+            .any(|(name, column)| *name == key.field && *column == tie_breaker)
+    }) {
+        columns.push(format!("{tie_breaker} ASC"));
+    }
+
+    Ok(columns.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALLOWED: &[(&str, &str)] = &[
+        ("id", "p.product_id"),
+        ("price", "p.price"),
+        ("name", "p.name"),
+    ];
+
+    fn key(field: &str, direction: SortDirection) -> SortKey {
+        SortKey {
+            field: field.to_string(),
+            direction,
+        }
+    }
+
+    #[test]
+    fn parses_fields_and_directions() {
+        assert_eq!(
+            parse_sort(" Price:DESC, name ").unwrap(),
+            vec![
+                key("price", SortDirection::Desc),
+                key("name", SortDirection::Asc)
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_bad_directions_and_repeated_fields() {
+        assert!(matches!(
+            parse_sort("price:sideways"),
+            Err(RepositoryError::InvalidSort(_))
+        ));
+        assert!(matches!(
+            parse_sort("price:asc,price:desc"),
+            Err(RepositoryError::InvalidSort(_))
+        ));
+    }
+
+    #[test]
+    fn builds_order_by_with_a_tie_breaker() {
+        let keys = parse_sort("price:desc,name:asc").unwrap();
+
+        assert_eq!(
+            order_by_clause(&keys, ALLOWED, "p.product_id").unwrap(),
+            "p.price DESC, p.name ASC, p.product_id ASC"
+        );
+    }
+
+    #[test]
+    fn defaults_to_the_tie_breaker() {
+        assert_eq!(
+            order_by_clause(&parse_sort("").unwrap(), ALLOWED, "p.product_id").unwrap(),
+            "p.product_id ASC"
+        );
+    }
+
+    #[test]
+    fn does_not_repeat_an_explicit_tie_breaker() {
+        let keys = parse_sort("id:desc").unwrap();
+
+        assert_eq!(
+            order_by_clause(&keys, ALLOWED, "p.product_id").unwrap(),
+            "p.product_id DESC"
+        );
+    }
+
+    #[test]
+    fn rejects_columns_outside_the_whitelist() {
+        let keys = parse_sort("price; DROP TABLE products:asc").unwrap();
+
+        assert!(matches!(
+            order_by_clause(&keys, ALLOWED, "p.product_id"),
+            Err(RepositoryError::InvalidSort(_))
+        ));
+    }
+}
@@ -1,7 +1,11 @@
+use crate::errors::ServiceError;
 use askama::{Error, Template};
 use serde::{Deserialize, Serialize};
 use tracing::{error, info};
 
+/// Template names accepted by [`preview_email`].
+pub const EMAIL_TEMPLATES: &[&str] = &["email"];
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct EmailTemplateData {
     pub title: String,
@@ -45,3 +49,36 @@ pub fn render_email(data: &EmailTemplateData) -> Result<String, Error> {
         }
     }
 }
+
+pub fn render_email_text(data: &EmailTemplateData) -> String {
+    format!(
+        "{}\n\n{}\n\n{}: {}\n",
+        data.title, data.message, data.button, data.link
+    )
+}
+
+#[derive(Debug, Serialize)]
+pub struct RenderedEmail {
+    pub html: String,
+    pub text: String,
+}
+
+pub fn preview_email(
+    template: &str,
+    data: &EmailTemplateData,
+) -> Result<RenderedEmail, ServiceError> {
+    if !EMAIL_TEMPLATES.contains(&template) {
+        return Err(ServiceError::Validation(vec![format!(
+            "Unknown email template '{template}', expected one of: {}",
+            EMAIL_TEMPLATES.join(", ")
+        )]));
+    }
+
+    let html = render_email(data)
+        .map_err(|e| ServiceError::Internal(format!("Failed to render email template: {e}")))?;
+
+    Ok(RenderedEmail {
+        html,
+        text: render_email_text(data),
+    })
+}
@@ -0,0 +1,243 @@
+use crate::errors::{FieldViolation, ServiceError};
+use std::env;
+
+/// Image formats accepted for uploads, recognised by their magic bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageType {
+    Jpeg,
+    Png,
+    Webp,
+}
+
+impl ImageType {
+    pub fn mime(&self) -> &'static str {
+        match self {
+            Self::Jpeg => "image/jpeg",
+            Self::Png => "image/png",
+            Self::Webp => "image/webp",
+        }
+    }
+
+    /// Accepts short names, extensions and MIME types, e.g. `jpg`, `image/png`.
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim().to_ascii_lowercase();
+
+        match value.strip_prefix("image/").unwrap_or(&value) {
+            "jpeg" | "jpg" | "pjpeg" => Some(Self::Jpeg),
+            "png" => Some(Self::Png),
+            "webp" => Some(Self::Webp),
+            _ => None,
+        }
+    }
+
+    pub fn sniff(bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+            Some(Self::Jpeg)
+        } else if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+            Some(Self::Png)
+        } else if bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+            Some(Self::Webp)
+        } else {
+            None
+        }
+    }
+}
+
+/// What a handler knows about an uploaded file, e.g. from a multipart field.
+#[derive(Debug, Clone, Copy)]
+pub struct UploadedFile<'a> {
+    pub file_name: Option<&'a str>,
+    pub content_type: Option<&'a str>,
+    pub bytes: &'a [u8],
+}
+
+/// Allowlist and size cap shared by upload handlers. The detected type comes
+/// from the file's magic bytes; a declared content type or file extension
+/// that disagrees with it is rejected rather than trusted.
+#[derive(Debug, Clone)]
+pub struct UploadPolicy {
+    allowed: Vec<ImageType>,
+    max_bytes: usize,
+}
+
+impl Default for UploadPolicy {
+    fn default() -> Self {
+        Self {
+            allowed: vec![ImageType::Jpeg, ImageType::Png, ImageType::Webp],
+            max_bytes: 5 * 1024 * 1024,
+        }
+    }
+}
+
+impl UploadPolicy {
+    pub fn new(allowed: Vec<ImageType>, max_bytes: usize) -> Self {
+        Self { allowed, max_bytes }
+    }
+
+    /// `UPLOAD_ALLOWED_TYPES` (`jpeg,png,webp`) and `UPLOAD_MAX_BYTES`;
+    /// unknown type names are ignored and an empty list keeps the default.
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+
+        let allowed: Vec<ImageType> = env::var("UPLOAD_ALLOWED_TYPES")
+            .unwrap_or_default()
+            .split(',')
+            .filter_map(ImageType::parse)
+            .collect();
+
+        let max_bytes = env::var("UPLOAD_MAX_BYTES")
+            .ok()
+            .and_then(|v| v.parse::<usize>().ok())
+            .filter(|v| *v > 0)
+            .unwrap_or(defaults.max_bytes);
+
+        Self {
+            allowed: if allowed.is_empty() {
+                defaults.allowed
+            } else {
+                allowed
+            },
+            max_bytes,
+        }
+    }
+
+    pub fn max_bytes(&self) -> usize {
+        self.max_bytes
+    }
+
+    pub fn validate(
+        &self,
+        field: &str,
+        file: &UploadedFile<'_>,
+    ) -> Result<ImageType, ServiceError> {
+        let reject = |description: String| {
+            ServiceError::FieldValidation(vec![FieldViolation::new(field, description)])
+        };
+
+        if file.bytes.is_empty() {
+            return Err(reject("File is empty".to_string()));
+        }
+
+        if file.bytes.len() > self.max_bytes {
+            return Err(reject(format!(
+                "File is {} bytes, larger than the {} byte limit",
+                file.bytes.len(),
+                self.max_bytes
+            )));
+        }
+
+        let detected = ImageType::sniff(file.bytes)
+            .filter(|kind| self.allowed.contains(kind))
+            .ok_or_else(|| {
+                reject(format!(
+                    "File content is not an allowed image type ({})",
+                    self.allowed_list()
+                ))
+            })?;
+
+        if let Some(content_type) = file.content_type
+            && ImageType::parse(content_type) != Some(detected)
+        {
+            return Err(reject(format!(
+                "Declared content type '{content_type}' does not match the file content ({})",
+                detected.mime()
+            )));
+        }
+
+        if let Some(extension) = file
+            .file_name
+            .and_then(|name| name.rsplit_once('.'))
+            .map(|(_, ext)| ext)
+            && ImageType::parse(extension) != Some(detected)
+        {
+            return Err(reject(format!(
+                "File extension '.{extension}' does not match the file content ({})",
+                detected.mime()
+            )));
+        }
+
+        Ok(detected)
+    }
+
+    fn allowed_list(&self) -> String {
+        self.allowed
+            .iter()
+            .map(ImageType::mime)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+    const JPEG: &[u8] = &[0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x10, b'J', b'F', b'I', b'F'];
+
+    fn file<'a>(
+        file_name: Option<&'a str>,
+        content_type: Option<&'a str>,
+        bytes: &'a [u8],
+    ) -> UploadedFile<'a> {
+        UploadedFile {
+            file_name,
+            content_type,
+            bytes,
+        }
+    }
+
+    #[test]
+    fn accepts_a_png() {
+        let detected = UploadPolicy::default()
+            .validate("image", &file(Some("photo.png"), Some("image/png"), PNG))
+            .unwrap();
+
+        assert_eq!(detected, ImageType::Png);
+    }
+
+    #[test]
+    fn rejects_a_spoofed_extension() {
+        let err = UploadPolicy::default()
+            .validate("image", &file(Some("photo.png"), None, JPEG))
+            .unwrap_err();
+
+        assert!(matches!(err, ServiceError::FieldValidation(v) if v[0].field == "image"));
+    }
+
+    #[test]
+    fn rejects_a_mismatched_content_type() {
+        assert!(
+            UploadPolicy::default()
+                .validate("image", &file(None, Some("image/png"), JPEG))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn rejects_content_that_is_not_an_image() {
+        assert!(
+            UploadPolicy::default()
+                .validate("image", &file(Some("photo.png"), None, b"<?php echo 1; ?>"))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn rejects_an_oversize_file() {
+        let mut bytes = PNG.to_vec();
+        bytes.resize(2048, 0);
+
+        let policy = UploadPolicy::new(vec![ImageType::Png], 1024);
+
+        assert!(policy.validate("image", &file(None, None, &bytes)).is_err());
+        assert!(policy.validate("image", &file(None, None, PNG)).is_ok());
+    }
+
+    #[test]
+    fn rejects_a_type_outside_the_allowlist() {
+        let policy = UploadPolicy::new(vec![ImageType::Png], 1024);
+
+        assert!(policy.validate("image", &file(None, None, JPEG)).is_err());
+    }
+}
@@ -0,0 +1,3 @@
+-- Add down migration script here
+-- The original casing and padding are not kept, so there is nothing to restore.
+SELECT 1;
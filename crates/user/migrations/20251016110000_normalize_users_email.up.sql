@@ -0,0 +1,19 @@
+-- Add up migration script here
+DO $$
+DECLARE
+    duplicate TEXT;
+BEGIN
+    SELECT lower(trim(email)) INTO duplicate
+    FROM users
+    GROUP BY lower(trim(email))
+    HAVING count(*) > 1
+    LIMIT 1;
+
+    IF duplicate IS NOT NULL THEN
+        RAISE EXCEPTION 'users.email has accounts that only differ by case or whitespace (e.g. %); merge them before normalizing', duplicate;
+    END IF;
+END $$;
+
+UPDATE users
+SET email = lower(trim(email))
+WHERE email <> lower(trim(email));
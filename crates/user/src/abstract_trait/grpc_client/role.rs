@@ -2,11 +2,15 @@ use crate::domain::response::{api::ApiResponse, role::RoleResponse};
 use anyhow::Result;
 use async_trait::async_trait;
 use shared::errors::AppErrorGrpc;
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 pub type DynRoleGrpcClient = Arc<dyn RoleGrpcClientTrait + Send + Sync>;
 
 #[async_trait]
 pub trait RoleGrpcClientTrait {
     async fn find_by_name(&self, name: &str) -> Result<ApiResponse<RoleResponse>, AppErrorGrpc>;
+    async fn find_role_names_by_user_ids(
+        &self,
+        user_ids: &[i32],
+    ) -> Result<HashMap<i32, Vec<String>>, AppErrorGrpc>;
 }
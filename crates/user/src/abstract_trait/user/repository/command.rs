@@ -1,6 +1,7 @@
 use crate::{
     domain::requests::user::{
-        CreateUserRequest, UpdateUserPasswordRequest, UpdateUserRequest, UpdateUserVerifiedRequest,
+        CreateUserRequest, UpdateUserPasswordRequest, UpdateUserRequest,
+        UpdateUserVerificationCodeRequest, UpdateUserVerifiedRequest,
     },
     model::user::User as UserModel,
 };
@@ -19,10 +20,17 @@ pub trait UserCommandRepositoryTrait {
         &self,
         req: &UpdateUserVerifiedRequest,
     ) -> Result<UserModel, RepositoryError>;
+    async fn update_verification_code(
+        &self,
+        req: &UpdateUserVerificationCodeRequest,
+    ) -> Result<UserModel, RepositoryError>;
     async fn update_password(
         &self,
         req: &UpdateUserPasswordRequest,
     ) -> Result<UserModel, RepositoryError>;
+    /// Scrubs the user's personal data in place, keeping the row so orders
+    /// still reference it.
+    async fn anonymize_user(&self, id: i32) -> Result<UserModel, RepositoryError>;
     async fn trash_user(&self, id: i32) -> Result<UserModel, RepositoryError>;
     async fn restore_user(&self, id: i32) -> Result<UserModel, RepositoryError>;
     async fn delete_user(&self, id: i32) -> Result<(), RepositoryError>;
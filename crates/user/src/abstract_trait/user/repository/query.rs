@@ -1,14 +1,20 @@
 use crate::{domain::requests::user::FindAllUsers, model::user::User as UserModel};
 use anyhow::Result;
 use async_trait::async_trait;
-use shared::errors::RepositoryError;
+use shared::{errors::RepositoryError, utils::Cursor};
 use std::sync::Arc;
+use uuid::Uuid;
 
 pub type DynUserQueryRepository = Arc<dyn UserQueryRepositoryTrait + Send + Sync>;
 
 #[async_trait]
 pub trait UserQueryRepositoryTrait {
-    async fn find_all(&self, req: &FindAllUsers) -> Result<(Vec<UserModel>, i64), RepositoryError>;
+    /// Offset pagination, or keyset pagination after `after` when given.
+    async fn find_all(
+        &self,
+        req: &FindAllUsers,
+        after: Option<Cursor>,
+    ) -> Result<(Vec<UserModel>, i64), RepositoryError>;
     async fn find_active(
         &self,
         req: &FindAllUsers,
@@ -19,9 +25,18 @@ pub trait UserQueryRepositoryTrait {
     ) -> Result<(Vec<UserModel>, i64), RepositoryError>;
     async fn find_by_email(&self, email: String) -> Result<Option<UserModel>, RepositoryError>;
     async fn find_by_id(&self, id: i32) -> Result<Option<UserModel>, RepositoryError>;
+    async fn find_by_external_id(
+        &self,
+        external_id: Uuid,
+    ) -> Result<Option<UserModel>, RepositoryError>;
     async fn find_by_email_and_verify(
         &self,
         email: String,
     ) -> Result<Option<UserModel>, RepositoryError>;
     async fn find_verify_code(&self, code: String) -> Result<Option<UserModel>, RepositoryError>;
+    async fn find_password_history(
+        &self,
+        user_id: i32,
+        limit: i64,
+    ) -> Result<Vec<String>, RepositoryError>;
 }
@@ -1,6 +1,7 @@
 use crate::domain::{
     requests::user::{
-        CreateUserRequest, UpdateUserPasswordRequest, UpdateUserRequest, UpdateUserVerifiedRequest,
+        CreateUserRequest, UpdateUserPasswordRequest, UpdateUserRequest,
+        UpdateUserVerificationCodeRequest, UpdateUserVerifiedRequest,
     },
     response::{
         api::ApiResponse,
@@ -28,10 +29,15 @@ pub trait UserCommandServiceTrait {
         &self,
         req: &UpdateUserVerifiedRequest,
     ) -> Result<ApiResponse<UserResponse>, ServiceError>;
+    async fn update_user_verification_code(
+        &self,
+        req: &UpdateUserVerificationCodeRequest,
+    ) -> Result<ApiResponse<UserResponse>, ServiceError>;
     async fn update_user_password(
         &self,
         req: &UpdateUserPasswordRequest,
     ) -> Result<ApiResponse<UserResponse>, ServiceError>;
+    async fn anonymize_user(&self, id: i32) -> Result<ApiResponse<UserResponse>, ServiceError>;
     async fn trash_user(&self, id: i32) -> Result<ApiResponse<UserResponseDeleteAt>, ServiceError>;
     async fn restore_user(
         &self,
@@ -9,6 +9,7 @@ use anyhow::Result;
 use async_trait::async_trait;
 use shared::errors::ServiceError;
 use std::sync::Arc;
+use uuid::Uuid;
 
 pub type DynUserQueryService = Arc<dyn UserQueryServiceTrait + Send + Sync>;
 
@@ -27,6 +28,10 @@ pub trait UserQueryServiceTrait {
         req: &FindAllUsers,
     ) -> Result<ApiResponsePagination<Vec<UserResponseDeleteAt>>, ServiceError>;
     async fn find_by_id(&self, id: i32) -> Result<ApiResponse<UserResponse>, ServiceError>;
+    async fn find_by_external_id(
+        &self,
+        external_id: Uuid,
+    ) -> Result<ApiResponse<UserResponse>, ServiceError>;
 
     async fn find_by_email(&self, email: String)
     -> Result<ApiResponse<UserResponse>, ServiceError>;
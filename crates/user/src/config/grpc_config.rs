@@ -1,8 +1,12 @@
 use anyhow::{Context, Result};
+use shared::config::client_tls_from_env;
+use tonic::transport::ClientTlsConfig;
 
 #[derive(Clone)]
 pub struct GrpcClientConfig {
     pub role: String,
+    /// `None` calls the services over plaintext.
+    pub tls: Option<ClientTlsConfig>,
 }
 
 impl GrpcClientConfig {
@@ -10,6 +14,9 @@ impl GrpcClientConfig {
         let role = std::env::var("GRPC_ROLE_ADDR")
             .context("Missing environment variable: GRPC_ROLE_ADDR")?;
 
-        Ok(Self { role })
+        Ok(Self {
+            role,
+            tls: client_tls_from_env()?,
+        })
     }
 }
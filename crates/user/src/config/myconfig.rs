@@ -9,6 +9,7 @@ pub struct ServiceConfig {
 #[derive(Debug, Clone)]
 pub struct Config {
     pub database_url: String,
+    pub database_read_url: Option<String>,
     pub jwt_secret: String,
     pub run_migrations: bool,
     pub port: u16,
@@ -16,15 +17,20 @@ pub struct Config {
     pub kafka_broker: String,
     pub db_max_conn: u32,
     pub db_min_conn: u32,
+    pub password_history_size: usize,
 }
 impl Config {
     pub fn init() -> Result<Self> {
         let database_url =
             std::env::var("DATABASE_URL").context("Missing environment variable: DATABASE_URL")?;
+        let database_read_url = std::env::var("DATABASE_READ_URL")
+            .ok()
+            .filter(|url| !url.trim().is_empty());
         let jwt_secret =
             std::env::var("JWT_SECRET").context("Missing environment variable: JWT_SECRET")?;
-        let run_migrations_str = std::env::var("RUN_MIGRATIONS")
-            .context("Missing environment variable: RUN_MIGRATIONS")?;
+        let run_migrations_str = std::env::var("AUTO_MIGRATE")
+            .or_else(|_| std::env::var("RUN_MIGRATIONS"))
+            .context("Missing environment variable: AUTO_MIGRATE")?;
         let port_str = std::env::var("PORT").context("Missing environment variable: PORT")?;
 
         let kafka_broker = std::env::var("KAFKA").context("Missing environment variable: KAFKA")?;
@@ -34,7 +40,7 @@ impl Config {
             "false" => false,
             other => {
                 return Err(anyhow!(
-                    "RUN_MIGRATIONS must be 'true' or 'false', got '{}'",
+                    "AUTO_MIGRATE must be 'true' or 'false', got '{}'",
                     other
                 ));
             }
@@ -50,6 +56,11 @@ impl Config {
             .parse::<u32>()
             .context("Unable to parse DB_MIN_CONNECTION as u32")?;
 
+        let password_history_size: usize = std::env::var("PASSWORD_HISTORY_SIZE")
+            .unwrap_or_else(|_| "0".to_string())
+            .parse::<usize>()
+            .context("Unable to parse PASSWORD_HISTORY_SIZE as usize")?;
+
         let port = port_str
             .parse::<u16>()
             .context("PORT must be a valid u16 integer")?;
@@ -67,6 +78,7 @@ impl Config {
 
         Ok(Self {
             database_url,
+            database_read_url,
             jwt_secret,
             run_migrations,
             port,
@@ -77,6 +89,7 @@ impl Config {
             kafka_broker,
             db_max_conn,
             db_min_conn,
+            password_history_size,
         })
     }
 }
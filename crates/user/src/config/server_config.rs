@@ -1,13 +1,18 @@
 use crate::config::myconfig::Config;
 use anyhow::{Context, Result};
+use shared::config::server_tls_from_env;
+use tonic::transport::ServerTlsConfig;
 
 #[derive(Debug, Clone)]
 pub struct ServerConfig {
     pub grpc_addr: std::net::SocketAddr,
     pub metrics_addr: std::net::SocketAddr,
     pub database_url: String,
+    pub database_read_url: Option<String>,
     pub jwt_secret: String,
     pub run_migrations: bool,
+    /// `None` serves plaintext gRPC.
+    pub tls: Option<ServerTlsConfig>,
 }
 
 impl ServerConfig {
@@ -20,8 +25,10 @@ impl ServerConfig {
                 .parse()
                 .context("Invalid metrics address")?,
             database_url: config.database_url.clone(),
+            database_read_url: config.database_read_url.clone(),
             jwt_secret: config.jwt_secret.clone(),
             run_migrations: config.run_migrations,
+            tls: server_tls_from_env()?,
         })
     }
 }
@@ -10,17 +10,19 @@ use crate::{
 };
 use anyhow::{Context, Result};
 use shared::{
-    abstract_trait::DynHashing,
+    abstract_trait::{DynHashing, DynKafka},
     cache::CacheStore,
-    config::{ConnectionPool, RedisPool},
+    config::{DatabasePools, RedisPool},
 };
 use std::{fmt, sync::Arc};
 
 #[derive(Clone)]
 pub struct DependenciesInjectDeps {
-    pub pool: ConnectionPool,
+    pub pools: DatabasePools,
     pub hash: DynHashing,
     pub redis: RedisPool,
+    pub kafka: DynKafka,
+    pub password_history_size: usize,
 }
 
 #[derive(Clone)]
@@ -40,10 +42,19 @@ impl fmt::Debug for DependenciesInject {
 
 impl DependenciesInject {
     pub fn new(deps: DependenciesInjectDeps, clients: GrpcClients) -> Result<Self> {
-        let DependenciesInjectDeps { hash, pool, redis } = deps;
+        let DependenciesInjectDeps {
+            hash,
+            pools,
+            redis,
+            kafka,
+            password_history_size,
+        } = deps;
 
-        let user_query_repo = Arc::new(UserQueryRepository::new(pool.clone()));
-        let user_command_repo = Arc::new(UserCommandRepository::new(pool.clone()));
+        let user_query_repo = Arc::new(UserQueryRepository::new(pools.read.clone()));
+        let user_command_repo = Arc::new(UserCommandRepository::new(pools.write.clone()));
+        // Existence and email uniqueness checks before an update go to the
+        // primary; the replica can still miss a row that was just written.
+        let user_guard_repo = Arc::new(UserQueryRepository::new(pools.write.clone()));
 
         let role_client: DynRoleGrpcClient =
             Arc::new(RoleGrpcClientService::new(clients.role_client));
@@ -53,7 +64,7 @@ impl DependenciesInject {
         let cache = Arc::new(CacheStore::new(redis.pool.clone()));
 
         let user_query = Arc::new(
-            UserQueryService::new(user_query_repo.clone(), cache.clone())
+            UserQueryService::new(user_query_repo.clone(), role_client.clone(), cache.clone())
                 .context("failed intialize user query")?,
         ) as DynUserQueryService;
 
@@ -61,8 +72,11 @@ impl DependenciesInject {
             hash,
             role_client,
             user_role_client,
-            query: user_query_repo.clone(),
+            query: user_guard_repo,
             command: user_command_repo,
+            kafka,
+            cache_store: cache.clone(),
+            password_history_size,
         };
 
         let user_command = Arc::new(
@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "type")]
+pub enum UserEvent {
+    /// The user's personal data was scrubbed; consumers holding copies of it
+    /// should drop them. The ids stay valid.
+    Anonymized { user_id: i32, external_id: String },
+}
+
+impl UserEvent {
+    pub fn topic(&self) -> &'static str {
+        match self {
+            Self::Anonymized { .. } => "user.anonymized",
+        }
+    }
+}
@@ -12,6 +12,14 @@ pub struct FindAllUsers {
 
     #[serde(default)]
     pub search: String,
+
+    #[serde(default)]
+    pub include_roles: bool,
+
+    /// Opaque `next_cursor` from a previous page; switches `find_all` to
+    /// keyset pagination when set.
+    #[serde(default)]
+    pub after: String,
 }
 
 fn default_page() -> i32 {
@@ -89,6 +97,15 @@ pub struct UpdateUserVerifiedRequest {
     pub is_verified: bool,
 }
 
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct UpdateUserVerificationCodeRequest {
+    #[validate(range(min = 1))]
+    pub user_id: i32,
+
+    #[validate(length(min = 1, max = 100))]
+    pub verification_code: String,
+}
+
 #[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
 pub struct UpdateUserPasswordRequest {
     #[validate(range(min = 1))]
@@ -5,6 +5,7 @@ use shared::utils::parse_datetime;
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct RoleResponse {
     pub id: i32,
+    pub external_id: String,
     pub name: String,
     #[serde(rename = "created_at")]
     pub created_at: Option<String>,
@@ -16,6 +17,7 @@ impl From<RoleResponseProto> for RoleResponse {
     fn from(value: RoleResponseProto) -> Self {
         RoleResponse {
             id: value.id,
+            external_id: value.external_id,
             name: value.name,
             created_at: parse_datetime(&value.created_at),
             updated_at: parse_datetime(&value.updated_at),
@@ -27,6 +29,7 @@ impl From<RoleResponse> for RoleResponseProto {
     fn from(value: RoleResponse) -> Self {
         RoleResponseProto {
             id: value.id,
+            external_id: value.external_id,
             name: value.name,
             created_at: value.created_at.unwrap_or_default(),
             updated_at: value.updated_at.unwrap_or_default(),
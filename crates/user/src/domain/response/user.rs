@@ -10,6 +10,7 @@ use utoipa::ToSchema;
 #[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
 pub struct UserResponse {
     pub id: i32,
+    pub external_id: String,
     pub firstname: String,
     pub lastname: String,
     pub email: String,
@@ -17,17 +18,21 @@ pub struct UserResponse {
     pub created_at: Option<String>,
     #[serde(rename = "updated_at")]
     pub updated_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub roles: Option<Vec<String>>,
 }
 
 impl From<UserModel> for UserResponse {
     fn from(value: UserModel) -> Self {
         UserResponse {
             id: value.user_id,
+            external_id: value.external_id.to_string(),
             firstname: value.firstname,
             lastname: value.lastname,
             email: value.email,
             created_at: value.created_at.map(|dt| dt.to_string()),
             updated_at: value.updated_at.map(|dt| dt.to_string()),
+            roles: None,
         }
     }
 }
@@ -36,11 +41,13 @@ impl From<UserResponseProto> for UserResponse {
     fn from(value: UserResponseProto) -> Self {
         UserResponse {
             id: value.id,
+            external_id: value.external_id,
             firstname: value.firstname,
             lastname: value.lastname,
             email: value.email,
             created_at: parse_datetime(&value.created_at),
             updated_at: parse_datetime(&value.updated_at),
+            roles: None,
         }
     }
 }
@@ -49,11 +56,13 @@ impl From<UserResponse> for UserResponseProto {
     fn from(value: UserResponse) -> Self {
         UserResponseProto {
             id: value.id,
+            external_id: value.external_id,
             firstname: value.firstname,
             lastname: value.lastname,
             email: value.email,
             created_at: value.created_at.unwrap_or_default(),
             updated_at: value.updated_at.unwrap_or_default(),
+            roles: value.roles.unwrap_or_default(),
         }
     }
 }
@@ -61,6 +70,7 @@ impl From<UserResponse> for UserResponseProto {
 #[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
 pub struct UserResponseDeleteAt {
     pub id: i32,
+    pub external_id: String,
     pub firstname: String,
     pub lastname: String,
     pub email: String,
@@ -76,6 +86,7 @@ impl From<UserModel> for UserResponseDeleteAt {
     fn from(value: UserModel) -> Self {
         UserResponseDeleteAt {
             id: value.user_id,
+            external_id: value.external_id.to_string(),
             firstname: value.firstname,
             lastname: value.lastname,
             email: value.email,
@@ -90,6 +101,7 @@ impl From<UserResponseDeleteAtProto> for UserResponseDeleteAt {
     fn from(value: UserResponseDeleteAtProto) -> Self {
         UserResponseDeleteAt {
             id: value.id,
+            external_id: value.external_id,
             firstname: value.firstname,
             lastname: value.lastname,
             email: value.email,
@@ -104,6 +116,7 @@ impl From<UserResponseDeleteAt> for UserResponseDeleteAtProto {
     fn from(value: UserResponseDeleteAt) -> Self {
         UserResponseDeleteAtProto {
             id: value.id,
+            external_id: value.external_id,
             firstname: value.firstname,
             lastname: value.lastname,
             email: value.email,
@@ -117,6 +130,7 @@ impl From<UserResponseDeleteAt> for UserResponseDeleteAtProto {
 #[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
 pub struct UserResponseWithPassword {
     pub id: i32,
+    pub external_id: String,
     pub firstname: String,
     pub lastname: String,
     pub email: String,
@@ -125,18 +139,21 @@ pub struct UserResponseWithPassword {
     pub created_at: Option<String>,
     #[serde(rename = "updated_at")]
     pub updated_at: Option<String>,
+    pub is_verified: bool,
 }
 
 impl From<UserModel> for UserResponseWithPassword {
     fn from(value: UserModel) -> Self {
         UserResponseWithPassword {
             id: value.user_id,
+            external_id: value.external_id.to_string(),
             firstname: value.firstname,
             lastname: value.lastname,
             email: value.email,
             password: value.password,
             created_at: value.created_at.map(|dt| dt.to_string()),
             updated_at: value.updated_at.map(|dt| dt.to_string()),
+            is_verified: value.is_verified,
         }
     }
 }
@@ -145,12 +162,14 @@ impl From<UserResponseWithPasswordProto> for UserResponseWithPassword {
     fn from(value: UserResponseWithPasswordProto) -> Self {
         UserResponseWithPassword {
             id: value.id,
+            external_id: value.external_id,
             firstname: value.firstname,
             lastname: value.lastname,
             email: value.email,
             password: value.password,
             created_at: parse_datetime(&value.created_at),
             updated_at: parse_datetime(&value.updated_at),
+            is_verified: value.is_verified,
         }
     }
 }
@@ -159,12 +178,14 @@ impl From<UserResponseWithPassword> for UserResponseWithPasswordProto {
     fn from(value: UserResponseWithPassword) -> Self {
         UserResponseWithPasswordProto {
             id: value.id,
+            external_id: value.external_id,
             firstname: value.firstname,
             lastname: value.lastname,
             email: value.email,
             password: value.password,
             created_at: value.created_at.unwrap_or_default(),
             updated_at: value.updated_at.unwrap_or_default(),
+            is_verified: value.is_verified,
         }
     }
 }
@@ -4,8 +4,9 @@ use genproto::{
     role::role_query_service_client::RoleQueryServiceClient,
     user_role::user_role_service_client::UserRoleServiceClient,
 };
+use shared::{config::grpc_endpoint, with_grpc_compression};
 use std::time::Duration;
-use tonic::transport::{Channel, Endpoint};
+use tonic::transport::{Channel, ClientTlsConfig};
 
 pub mod role;
 pub mod user_role;
@@ -18,17 +19,22 @@ pub struct GrpcClients {
 
 impl GrpcClients {
     pub async fn init(config: GrpcClientConfig) -> Result<Self> {
-        let role_channel = Self::connect(config.role, "role-service").await?;
+        let role_channel = Self::connect(config.role, "role-service", config.tls.as_ref()).await?;
 
         Ok(Self {
-            role_client: RoleQueryServiceClient::new(role_channel.clone()),
-            user_role_client: UserRoleServiceClient::new(role_channel.clone()),
+            role_client: with_grpc_compression!(RoleQueryServiceClient::new(role_channel.clone())),
+            user_role_client: with_grpc_compression!(UserRoleServiceClient::new(
+                role_channel.clone()
+            )),
         })
     }
 
-    async fn connect(addr: String, service: &str) -> Result<Channel> {
-        let endpoint = Endpoint::from_shared(addr.clone())
-            .with_context(|| format!("Invalid gRPC address for {service}: {addr}"))?;
+    async fn connect(
+        addr: String,
+        service: &str,
+        tls: Option<&ClientTlsConfig>,
+    ) -> Result<Channel> {
+        let endpoint = grpc_endpoint(&addr, service, tls)?;
 
         let configured_endpoint = endpoint
             .connect_timeout(Duration::from_secs(3))
@@ -5,10 +5,11 @@ use crate::{
 use anyhow::Result;
 use async_trait::async_trait;
 use genproto::role::{
-    FindByNameRequest,
+    FindByNameRequest, FindByUserIdsRequest,
     role_query_service_client::RoleQueryServiceClient as RoleQueryServiceGrpcClient,
 };
 use shared::errors::AppErrorGrpc;
+use std::collections::HashMap;
 use tonic::{Request, transport::Channel};
 
 pub struct RoleGrpcClientService {
@@ -46,4 +47,27 @@ impl RoleGrpcClientTrait for RoleGrpcClientService {
             data: domain_user,
         })
     }
+
+    async fn find_role_names_by_user_ids(
+        &self,
+        user_ids: &[i32],
+    ) -> Result<HashMap<i32, Vec<String>>, AppErrorGrpc> {
+        let req = Request::new(FindByUserIdsRequest {
+            user_ids: user_ids.to_vec(),
+        });
+
+        let mut client = self.client.clone();
+
+        let response = client
+            .find_role_names_by_user_ids(req)
+            .await
+            .map_err(AppErrorGrpc::from)?;
+
+        Ok(response
+            .into_inner()
+            .data
+            .into_iter()
+            .map(|entry| (entry.user_id, entry.roles))
+            .collect())
+    }
 }
@@ -4,6 +4,7 @@ use crate::{
         CreateUserRequest as DomainCreateUserRequest,
         UpdateUserPasswordRequest as DomainUpdatePasswordRequest,
         UpdateUserRequest as DomainUpdateUserRequest,
+        UpdateUserVerificationCodeRequest as DomainUpdateVerificationCodeRequest,
         UpdateUserVerifiedRequest as DomainUpdateVerifiedRequest,
     },
 };
@@ -12,10 +13,11 @@ use genproto::{
     user::{
         ApiResponseUser, ApiResponseUserAll, ApiResponseUserDelete, ApiResponseUserDeleteAt,
         FindByIdUserRequest, UpdateUserPasswordRequest, UpdateUserRequest,
-        UpdateUserVerifiedRequest, user_command_service_server::UserCommandService,
+        UpdateUserVerificationCodeRequest, UpdateUserVerifiedRequest,
+        user_command_service_server::UserCommandService,
     },
 };
-use shared::errors::AppErrorGrpc;
+use shared::{errors::AppErrorGrpc, utils::EmailNormalizer};
 use std::fmt;
 use tonic::{Request, Response, Status};
 use tracing::info;
@@ -23,20 +25,23 @@ use tracing::info;
 #[derive(Clone)]
 pub struct UserCommandGrpcServiceImpl {
     pub user_command_service: DynUserCommandService,
+    pub email_normalizer: EmailNormalizer,
 }
 
 impl fmt::Debug for UserCommandGrpcServiceImpl {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("UserCommandGrpcServiceImpl")
             .field("user_Command_service", &"DynUserCommandService")
+            .field("email_normalizer", &self.email_normalizer)
             .finish()
     }
 }
 
 impl UserCommandGrpcServiceImpl {
-    pub fn new(user: DynUserCommandService) -> Self {
+    pub fn new(user: DynUserCommandService, email_normalizer: EmailNormalizer) -> Self {
         Self {
             user_command_service: user,
+            email_normalizer,
         }
     }
 }
@@ -54,7 +59,7 @@ impl UserCommandService for UserCommandGrpcServiceImpl {
         let domain_req = DomainCreateUserRequest {
             first_name: req.firstname,
             last_name: req.lastname,
-            email: req.email,
+            email: self.email_normalizer.normalize(&req.email),
             password: req.password,
             confirm_password: req.confirm_password,
             is_verified: req.is_verified,
@@ -92,7 +97,7 @@ impl UserCommandService for UserCommandGrpcServiceImpl {
             user_id: Some(req.id),
             first_name: req.firstname,
             last_name: req.lastname,
-            email: req.email,
+            email: self.email_normalizer.normalize(&req.email),
             password: req.password,
             confirm_password: req.confirm_password,
         };
@@ -145,6 +150,38 @@ impl UserCommandService for UserCommandGrpcServiceImpl {
         Ok(Response::new(reply))
     }
 
+    async fn update_user_verification_code(
+        &self,
+        request: Request<UpdateUserVerificationCodeRequest>,
+    ) -> Result<Response<ApiResponseUser>, Status> {
+        info!("Updating User Verification Code");
+
+        let req = request.into_inner();
+
+        let domain_req = DomainUpdateVerificationCodeRequest {
+            user_id: req.user_id,
+            verification_code: req.verification_code,
+        };
+
+        let api_response = self
+            .user_command_service
+            .update_user_verification_code(&domain_req)
+            .await
+            .map_err(AppErrorGrpc::from)?;
+
+        let reply = ApiResponseUser {
+            status: "success".into(),
+            message: api_response.message,
+            data: Some(api_response.data.into()),
+        };
+
+        info!(
+            "User verification code updated successfully: ID={}",
+            req.user_id
+        );
+        Ok(Response::new(reply))
+    }
+
     async fn update_user_password(
         &self,
         request: Request<UpdateUserPasswordRequest>,
@@ -174,6 +211,30 @@ impl UserCommandService for UserCommandGrpcServiceImpl {
         Ok(Response::new(reply))
     }
 
+    async fn anonymize_user(
+        &self,
+        request: Request<FindByIdUserRequest>,
+    ) -> Result<Response<ApiResponseUser>, Status> {
+        info!("Anonymizing User");
+
+        let req = request.into_inner();
+
+        let api_response = self
+            .user_command_service
+            .anonymize_user(req.id)
+            .await
+            .map_err(AppErrorGrpc::from)?;
+
+        let reply = ApiResponseUser {
+            status: "success".into(),
+            message: api_response.message,
+            data: Some(api_response.data.into()),
+        };
+
+        info!("User anonymized: ID={}", req.id);
+        Ok(Response::new(reply))
+    }
+
     async fn trashed_user(
         &self,
         request: Request<FindByIdUserRequest>,
@@ -3,32 +3,36 @@ use crate::{
 };
 use genproto::user::{
     ApiResponsePaginationUser, ApiResponsePaginationUserDeleteAt, ApiResponseUser,
-    ApiResponseUserWithPassword, FindAllUserRequest, FindByEmailUserRequest, FindByIdUserRequest,
-    UserResponse, UserResponseDeleteAt, VerifyCodeRequest,
-    user_query_service_server::UserQueryService,
+    ApiResponseUserWithPassword, FindAllUserRequest, FindByEmailUserRequest,
+    FindByExternalIdUserRequest, FindByIdUserRequest, UserResponse, UserResponseDeleteAt,
+    VerifyCodeRequest, user_query_service_server::UserQueryService,
 };
-use shared::errors::AppErrorGrpc;
+use shared::{errors::AppErrorGrpc, utils::EmailNormalizer};
 use std::fmt;
 use tonic::{Request, Response, Status};
 use tracing::info;
+use uuid::Uuid;
 
 #[derive(Clone)]
 pub struct UserQueryGrpcServiceImpl {
     pub user_query_service: DynUserQueryService,
+    pub email_normalizer: EmailNormalizer,
 }
 
 impl fmt::Debug for UserQueryGrpcServiceImpl {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("UserQueryGrpcServiceImpl")
             .field("user_query_service", &"DynUserQueryService")
+            .field("email_normalizer", &self.email_normalizer)
             .finish()
     }
 }
 
 impl UserQueryGrpcServiceImpl {
-    pub fn new(user: DynUserQueryService) -> Self {
+    pub fn new(user: DynUserQueryService, email_normalizer: EmailNormalizer) -> Self {
         Self {
             user_query_service: user,
+            email_normalizer,
         }
     }
 }
@@ -47,6 +51,8 @@ impl UserQueryService for UserQueryGrpcServiceImpl {
             page: req.page,
             page_size: req.page_size,
             search: req.search,
+            include_roles: req.include_roles,
+            after: req.after,
         };
 
         let api_response = self
@@ -99,6 +105,31 @@ impl UserQueryService for UserQueryGrpcServiceImpl {
         Ok(Response::new(reply))
     }
 
+    async fn find_by_external_id(
+        &self,
+        request: Request<FindByExternalIdUserRequest>,
+    ) -> Result<Response<ApiResponseUser>, Status> {
+        info!("Handling gRPC request: Find User by external ID");
+
+        let req = request.into_inner();
+
+        let external_id = Uuid::parse_str(&req.external_id)
+            .map_err(|_| Status::invalid_argument("external_id must be a valid UUID"))?;
+
+        let api_response = self
+            .user_query_service
+            .find_by_external_id(external_id)
+            .await
+            .map_err(AppErrorGrpc::from)?;
+
+        let reply = ApiResponseUser {
+            status: "success".into(),
+            message: api_response.message,
+            data: Some(api_response.data.into()),
+        };
+
+        Ok(Response::new(reply))
+    }
     async fn find_by_email(
         &self,
         request: Request<FindByEmailUserRequest>,
@@ -106,10 +137,11 @@ impl UserQueryService for UserQueryGrpcServiceImpl {
         info!("Handling gRPC request: Find User by Email");
 
         let req = request.into_inner();
+        let email = self.email_normalizer.normalize(&req.email);
 
         let api_response = self
             .user_query_service
-            .find_by_email(req.email.clone())
+            .find_by_email(email.clone())
             .await
             .map_err(AppErrorGrpc::from)?;
 
@@ -119,10 +151,7 @@ impl UserQueryService for UserQueryGrpcServiceImpl {
             data: Some(api_response.data.into()),
         };
 
-        info!(
-            "Successfully fetched User with Email: {}",
-            req.email.clone()
-        );
+        info!("Successfully fetched User with Email: {email}");
         Ok(Response::new(reply))
     }
 
@@ -133,10 +162,11 @@ impl UserQueryService for UserQueryGrpcServiceImpl {
         info!("Handling gRPC request: Find User by Email and Verify");
 
         let req = request.into_inner();
+        let email = self.email_normalizer.normalize(&req.email);
 
         let api_response = self
             .user_query_service
-            .find_by_email_and_verify(req.email.clone())
+            .find_by_email_and_verify(email.clone())
             .await
             .map_err(AppErrorGrpc::from)?;
 
@@ -146,10 +176,7 @@ impl UserQueryService for UserQueryGrpcServiceImpl {
             data: Some(api_response.data.into()),
         };
 
-        info!(
-            "Successfully fetched User with Email for verification: {}",
-            req.email
-        );
+        info!("Successfully fetched User with Email for verification: {email}");
         Ok(Response::new(reply))
     }
     async fn find_verification_code(
@@ -191,6 +218,8 @@ impl UserQueryService for UserQueryGrpcServiceImpl {
             page: req.page,
             page_size: req.page_size,
             search: req.search,
+            include_roles: false,
+            after: String::new(),
         };
 
         let api_response = self
@@ -230,6 +259,8 @@ impl UserQueryService for UserQueryGrpcServiceImpl {
             page: req.page,
             page_size: req.page_size,
             search: req.search,
+            include_roles: false,
+            after: String::new(),
         };
 
         let api_response = self
@@ -1,10 +1,12 @@
 use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct User {
     pub user_id: i32,
+    pub external_id: Uuid,
     pub firstname: String,
     pub lastname: String,
     pub email: String,
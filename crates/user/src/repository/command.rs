@@ -1,7 +1,8 @@
 use crate::{
     abstract_trait::user::repository::UserCommandRepositoryTrait,
     domain::requests::user::{
-        CreateUserRequest, UpdateUserPasswordRequest, UpdateUserRequest, UpdateUserVerifiedRequest,
+        CreateUserRequest, UpdateUserPasswordRequest, UpdateUserRequest,
+        UpdateUserVerificationCodeRequest, UpdateUserVerifiedRequest,
     },
     model::user::User as UserModel,
 };
@@ -41,6 +42,7 @@ impl UserCommandRepositoryTrait for UserCommandRepository {
         )
         RETURNING
             user_id,
+            external_id,
             firstname,
             lastname,
             email,
@@ -66,7 +68,21 @@ impl UserCommandRepositoryTrait for UserCommandRepository {
     }
 
     async fn update_user(&self, req: &UpdateUserRequest) -> Result<UserModel, RepositoryError> {
-        let mut conn = self.db.acquire().await.map_err(RepositoryError::from)?;
+        let mut tx = self.db.begin().await.map_err(RepositoryError::from)?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO password_history (user_id, password)
+            SELECT user_id, password
+            FROM users
+            WHERE user_id = $1 AND deleted_at IS NULL AND password <> $2
+            "#,
+            req.user_id,
+            req.password
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(RepositoryError::from)?;
 
         let user = sqlx::query_as!(
             UserModel,
@@ -86,10 +102,12 @@ impl UserCommandRepositoryTrait for UserCommandRepository {
             req.email,
             req.password
         )
-        .fetch_one(&mut *conn)
+        .fetch_one(&mut *tx)
         .await
         .map_err(RepositoryError::from)?;
 
+        tx.commit().await.map_err(RepositoryError::from)?;
+
         Ok(user)
     }
 
@@ -118,11 +136,49 @@ impl UserCommandRepositoryTrait for UserCommandRepository {
         Ok(user)
     }
 
+    async fn update_verification_code(
+        &self,
+        req: &UpdateUserVerificationCodeRequest,
+    ) -> Result<UserModel, RepositoryError> {
+        let mut conn = self.db.acquire().await.map_err(RepositoryError::from)?;
+
+        let user = sqlx::query_as!(
+            UserModel,
+            r#"
+            UPDATE users
+            SET verification_code = $2,
+                updated_at = current_timestamp
+            WHERE user_id = $1 AND deleted_at IS NULL
+            RETURNING *
+            "#,
+            req.user_id,
+            req.verification_code
+        )
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(user)
+    }
+
     async fn update_password(
         &self,
         req: &UpdateUserPasswordRequest,
     ) -> Result<UserModel, RepositoryError> {
-        let mut conn = self.db.acquire().await.map_err(RepositoryError::from)?;
+        let mut tx = self.db.begin().await.map_err(RepositoryError::from)?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO password_history (user_id, password)
+            SELECT user_id, password
+            FROM users
+            WHERE user_id = $1 AND deleted_at IS NULL
+            "#,
+            req.user_id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(RepositoryError::from)?;
 
         let user = sqlx::query_as!(
             UserModel,
@@ -136,10 +192,54 @@ impl UserCommandRepositoryTrait for UserCommandRepository {
             req.user_id,
             req.password
         )
-        .fetch_one(&mut *conn)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        tx.commit().await.map_err(RepositoryError::from)?;
+
+        Ok(user)
+    }
+
+    /// The email becomes a hash of itself, so it stays unique without being
+    /// recoverable. Old password hashes go too: the account can no longer
+    /// sign in.
+    async fn anonymize_user(&self, id: i32) -> Result<UserModel, RepositoryError> {
+        let mut tx = self.db.begin().await.map_err(RepositoryError::from)?;
+
+        sqlx::query!(
+            r#"
+            DELETE FROM password_history
+            WHERE user_id = $1
+            "#,
+            id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        let user = sqlx::query_as!(
+            UserModel,
+            r#"
+            UPDATE users
+            SET firstname = 'deleted-user-' || user_id,
+                lastname = '',
+                email = 'deleted-' || encode(sha256(email::bytea), 'hex') || '@anonymized.invalid',
+                password = '',
+                verification_code = '',
+                is_verified = FALSE,
+                updated_at = current_timestamp
+            WHERE user_id = $1
+            RETURNING *
+            "#,
+            id
+        )
+        .fetch_one(&mut *tx)
         .await
         .map_err(RepositoryError::from)?;
 
+        tx.commit().await.map_err(RepositoryError::from)?;
+
         Ok(user)
     }
 
@@ -156,9 +256,10 @@ impl UserCommandRepositoryTrait for UserCommandRepository {
             "#,
             id
         )
-        .fetch_one(&mut *conn)
+        .fetch_optional(&mut *conn)
         .await
-        .map_err(RepositoryError::from)?;
+        .map_err(RepositoryError::from)?
+        .ok_or(RepositoryError::NotFound)?;
 
         Ok(user)
     }
@@ -176,17 +277,34 @@ impl UserCommandRepositoryTrait for UserCommandRepository {
             "#,
             id
         )
-        .fetch_one(&mut *conn)
+        .fetch_optional(&mut *conn)
         .await
         .map_err(RepositoryError::from)?;
 
+        let Some(user) = user else {
+            let exists = sqlx::query_scalar!(
+                r#"
+            SELECT user_id FROM users WHERE user_id = $1
+            "#,
+                id
+            )
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(RepositoryError::from)?;
+
+            return Err(match exists {
+                Some(_) => RepositoryError::NotTrashed(format!("User {id} is not trashed")),
+                None => RepositoryError::NotFound,
+            });
+        };
+
         Ok(user)
     }
 
     async fn delete_user(&self, id: i32) -> Result<(), RepositoryError> {
         let mut conn = self.db.acquire().await.map_err(RepositoryError::from)?;
 
-        sqlx::query!(
+        let result = sqlx::query!(
             r#"
             DELETE FROM users WHERE user_id = $1 AND deleted_at IS NOT NULL
             "#,
@@ -196,6 +314,10 @@ impl UserCommandRepositoryTrait for UserCommandRepository {
         .await
         .map_err(RepositoryError::from)?;
 
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
         Ok(())
     }
 
@@ -3,8 +3,9 @@ use crate::{
     domain::requests::user::FindAllUsers, model::user::User as UserModel,
 };
 use async_trait::async_trait;
-use shared::{config::ConnectionPool, errors::RepositoryError};
+use shared::{config::ConnectionPool, errors::RepositoryError, utils::Cursor};
 use tracing::{error, info};
+use uuid::Uuid;
 
 #[derive(Clone)]
 pub struct UserQueryRepository {
@@ -19,8 +20,15 @@ impl UserQueryRepository {
 
 #[async_trait]
 impl UserQueryRepositoryTrait for UserQueryRepository {
-    async fn find_all(&self, req: &FindAllUsers) -> Result<(Vec<UserModel>, i64), RepositoryError> {
-        info!("🔍 Fetching all users with search: {:?}", req.search);
+    async fn find_all(
+        &self,
+        req: &FindAllUsers,
+        after: Option<Cursor>,
+    ) -> Result<(Vec<UserModel>, i64), RepositoryError> {
+        info!(
+            "🔍 Fetching all users with search: {:?}, after: {:?}",
+            req.search, after
+        );
 
         let mut conn = self.db.acquire().await.map_err(|e| {
             error!("❌ Failed to acquire DB connection: {:?}", e);
@@ -28,7 +36,11 @@ impl UserQueryRepositoryTrait for UserQueryRepository {
         })?;
 
         let limit = req.page_size as i64;
-        let offset = ((req.page - 1).max(0) * req.page_size) as i64;
+        let offset = if after.is_some() {
+            0
+        } else {
+            ((req.page - 1).max(0) * req.page_size) as i64
+        };
 
         let search_pattern = if req.search.trim().is_empty() {
             None
@@ -39,7 +51,7 @@ impl UserQueryRepositoryTrait for UserQueryRepository {
         let rows = sqlx::query!(
             r#"
             SELECT
-                user_id, firstname, lastname, email,
+                user_id, external_id, firstname, lastname, email,
                 password, verification_code, is_verified,
                 created_at, updated_at, deleted_at,
                 COUNT(*) OVER() AS total_count
@@ -51,12 +63,15 @@ impl UserQueryRepositoryTrait for UserQueryRepository {
                 lastname ILIKE '%' || $1 || '%' OR
                 email ILIKE '%' || $1 || '%'
             )
-            ORDER BY created_at DESC
+            AND ($4::TIMESTAMP IS NULL OR (created_at, user_id) < ($4, $5::INT))
+            ORDER BY created_at DESC, user_id DESC
             LIMIT $2 OFFSET $3
             "#,
             search_pattern,
             limit,
             offset,
+            after.map(|c| c.created_at),
+            after.map(|c| c.id),
         )
         .fetch_all(&mut *conn)
         .await
@@ -74,6 +89,7 @@ impl UserQueryRepositoryTrait for UserQueryRepository {
             .into_iter()
             .map(|r| UserModel {
                 user_id: r.user_id,
+                external_id: r.external_id,
                 firstname: r.firstname,
                 lastname: r.lastname,
                 email: r.email,
@@ -112,7 +128,7 @@ impl UserQueryRepositoryTrait for UserQueryRepository {
         let rows = sqlx::query!(
             r#"
             SELECT
-                user_id, firstname, lastname, email,
+                user_id, external_id, firstname, lastname, email,
                 password, verification_code, is_verified,
                 created_at, updated_at, deleted_at,
                 COUNT(*) OVER() AS total_count
@@ -147,6 +163,7 @@ impl UserQueryRepositoryTrait for UserQueryRepository {
             .into_iter()
             .map(|r| UserModel {
                 user_id: r.user_id,
+                external_id: r.external_id,
                 firstname: r.firstname,
                 lastname: r.lastname,
                 email: r.email,
@@ -184,7 +201,7 @@ impl UserQueryRepositoryTrait for UserQueryRepository {
         let rows = sqlx::query!(
             r#"
             SELECT
-                user_id, firstname, lastname, email,
+                user_id, external_id, firstname, lastname, email,
                 password, verification_code, is_verified,
                 created_at, updated_at, deleted_at,
                 COUNT(*) OVER() AS total_count
@@ -219,6 +236,7 @@ impl UserQueryRepositoryTrait for UserQueryRepository {
             .into_iter()
             .map(|r| UserModel {
                 user_id: r.user_id,
+                external_id: r.external_id,
                 firstname: r.firstname,
                 lastname: r.lastname,
                 email: r.email,
@@ -278,7 +296,7 @@ impl UserQueryRepositoryTrait for UserQueryRepository {
             UserModel,
             r#"
             SELECT * FROM users
-            WHERE email = $1 AND deleted_at IS NULL AND is_verified = true
+            WHERE email = $1 AND deleted_at IS NULL
             "#,
             email
         )
@@ -305,4 +323,48 @@ impl UserQueryRepositoryTrait for UserQueryRepository {
 
         Ok(result)
     }
+
+    async fn find_by_external_id(
+        &self,
+        external_id: Uuid,
+    ) -> Result<Option<UserModel>, RepositoryError> {
+        let mut conn = self.db.acquire().await.map_err(RepositoryError::from)?;
+
+        let result = sqlx::query_as!(
+            UserModel,
+            r#"
+            SELECT * FROM users WHERE external_id = $1 AND deleted_at IS NULL
+            "#,
+            external_id
+        )
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(result)
+    }
+
+    async fn find_password_history(
+        &self,
+        user_id: i32,
+        limit: i64,
+    ) -> Result<Vec<String>, RepositoryError> {
+        let mut conn = self.db.acquire().await.map_err(RepositoryError::from)?;
+
+        let passwords = sqlx::query_scalar!(
+            r#"
+            SELECT password FROM password_history
+            WHERE user_id = $1
+            ORDER BY created_at DESC, password_history_id DESC
+            LIMIT $2
+            "#,
+            user_id,
+            limit
+        )
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(passwords)
+    }
 }
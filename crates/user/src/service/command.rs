@@ -7,10 +7,11 @@ use crate::{
         },
     },
     domain::{
+        event::UserEvent,
         requests::{
             user::{
                 CreateUserRequest, UpdateUserPasswordRequest, UpdateUserRequest,
-                UpdateUserVerifiedRequest,
+                UpdateUserVerificationCodeRequest, UpdateUserVerifiedRequest,
             },
             user_role::UserRoleRequest,
         },
@@ -28,10 +29,15 @@ use opentelemetry::{
     trace::{Span, SpanKind, TraceContextExt, Tracer},
 };
 use shared::{
-    abstract_trait::DynHashing,
+    abstract_trait::{DynHashing, DynKafka},
+    cache::CacheStore,
     errors::ServiceError,
-    utils::{MetadataInjector, Method, Metrics, Status as StatusUtils, TracingContext},
+    utils::{
+        LifecycleMetrics, LifecycleScope, MetadataInjector, Method, Metrics, Status as StatusUtils,
+        TracingContext,
+    },
 };
+use std::sync::Arc;
 use tokio::time::Instant;
 use tonic::Request;
 use tracing::{error, info};
@@ -43,7 +49,11 @@ pub struct UserCommandService {
     pub user_role_client: DynUserRoleGrpcClient,
     pub query: DynUserQueryRepository,
     pub command: DynUserCommandRepository,
+    pub kafka: DynKafka,
+    pub cache_store: Arc<CacheStore>,
     pub metrics: Metrics,
+    pub lifecycle: LifecycleMetrics,
+    pub password_history_size: usize,
 }
 
 pub struct UserCommandServiceDeps {
@@ -52,11 +62,15 @@ pub struct UserCommandServiceDeps {
     pub hash: DynHashing,
     pub query: DynUserQueryRepository,
     pub command: DynUserCommandRepository,
+    pub kafka: DynKafka,
+    pub cache_store: Arc<CacheStore>,
+    pub password_history_size: usize,
 }
 
 impl UserCommandService {
     pub fn new(deps: UserCommandServiceDeps) -> Result<Self> {
         let metrics = Metrics::new(global::meter("user-command-service"));
+        let lifecycle = LifecycleMetrics::new(global::meter("user-command-service"), "user");
 
         let UserCommandServiceDeps {
             role_client,
@@ -64,6 +78,9 @@ impl UserCommandService {
             query,
             command,
             hash,
+            kafka,
+            cache_store,
+            password_history_size,
         } = deps;
 
         Ok(Self {
@@ -71,11 +88,54 @@ impl UserCommandService {
             user_role_client,
             query,
             command,
+            kafka,
+            cache_store,
             metrics,
+            lifecycle,
             hash,
+            password_history_size,
         })
     }
 
+    /// Rejects `password` when it matches the current hash or one of the
+    /// previous `password_history_size - 1` hashes of the user.
+    async fn ensure_password_not_reused(
+        &self,
+        user_id: i32,
+        current_hash: &str,
+        password: &str,
+    ) -> Result<(), ServiceError> {
+        if self.password_history_size == 0 {
+            return Ok(());
+        }
+
+        let mut recent = vec![current_hash.to_string()];
+
+        if self.password_history_size > 1 {
+            let previous = self
+                .query
+                .find_password_history(user_id, self.password_history_size as i64 - 1)
+                .await
+                .map_err(|e| {
+                    error!("❌ Failed to fetch password history for user {user_id}: {e:?}");
+                    ServiceError::Repo(e)
+                })?;
+
+            recent.extend(previous);
+        }
+
+        for hashed in &recent {
+            if self.hash.compare_password(hashed, password).await.is_ok() {
+                return Err(ServiceError::Custom(format!(
+                    "Password must not match any of your last {} passwords",
+                    self.password_history_size
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
     fn get_tracer(&self) -> BoxedTracer {
         global::tracer("user-command-service")
     }
@@ -376,6 +436,16 @@ impl UserCommandServiceTrait for UserCommandService {
                 ));
             }
 
+            if let Err(e) = self
+                .ensure_password_not_reused(user_id, &existing_user.password, &req.password)
+                .await
+            {
+                self.complete_tracing_error(&tracing_ctx, method.clone(), "Password reused")
+                    .await;
+
+                return Err(e);
+            }
+
             new_password = match self.hash.hash_password(&req.password).await {
                 Ok(hash) => hash,
                 Err(e) => {
@@ -491,6 +561,50 @@ impl UserCommandServiceTrait for UserCommandService {
         Ok(response)
     }
 
+    async fn update_user_verification_code(
+        &self,
+        req: &UpdateUserVerificationCodeRequest,
+    ) -> Result<ApiResponse<UserResponse>, ServiceError> {
+        info!("✉️ Replacing verification code: user_id={}", req.user_id);
+
+        let method = Method::Post;
+        let tracing_ctx = self.start_tracing(
+            "UpdateUserVerificationCode",
+            vec![
+                KeyValue::new("component", "user"),
+                KeyValue::new("operation", "update_verification_code"),
+                KeyValue::new("user.id", req.user_id.to_string()),
+            ],
+        );
+
+        let user_model = match self.command.update_verification_code(req).await {
+            Ok(user) => {
+                self.complete_tracing_success(
+                    &tracing_ctx,
+                    method.clone(),
+                    "Verification code replaced",
+                )
+                .await;
+                user
+            }
+            Err(e) => {
+                error!(
+                    "❌ Failed to replace verification code for user {}: {e:?}",
+                    req.user_id
+                );
+                self.complete_tracing_error(&tracing_ctx, method.clone(), &e.to_string())
+                    .await;
+                return Err(ServiceError::Repo(e));
+            }
+        };
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Verification code updated successfully".to_string(),
+            data: UserResponse::from(user_model),
+        })
+    }
+
     async fn update_user_password(
         &self,
         req: &UpdateUserPasswordRequest,
@@ -507,7 +621,56 @@ impl UserCommandServiceTrait for UserCommandService {
             ],
         );
 
-        let user_model = match self.command.update_password(req).await {
+        let existing_user = match self.query.find_by_id(req.user_id).await {
+            Ok(Some(user)) => user,
+            Ok(None) => {
+                self.complete_tracing_error(&tracing_ctx, method.clone(), "User not found")
+                    .await;
+
+                return Err(ServiceError::Custom("User not found".into()));
+            }
+            Err(e) => {
+                error!("❌ Failed find user ID={} | {:?}", req.user_id, e);
+
+                self.complete_tracing_error(&tracing_ctx, method.clone(), "User query failed")
+                    .await;
+
+                return Err(ServiceError::Repo(e));
+            }
+        };
+
+        if let Err(e) = self
+            .ensure_password_not_reused(req.user_id, &existing_user.password, &req.password)
+            .await
+        {
+            self.complete_tracing_error(&tracing_ctx, method.clone(), "Password reused")
+                .await;
+
+            return Err(e);
+        }
+
+        let hashed_password = match self.hash.hash_password(&req.password).await {
+            Ok(hash) => hash,
+            Err(e) => {
+                error!("❌ Failed hashing password: {:?}", e);
+
+                self.complete_tracing_error(
+                    &tracing_ctx,
+                    method.clone(),
+                    "Failed to hash password",
+                )
+                .await;
+
+                return Err(ServiceError::Internal("Failed to hash password".into()));
+            }
+        };
+
+        let update_req = UpdateUserPasswordRequest {
+            user_id: req.user_id,
+            password: hashed_password,
+        };
+
+        let user_model = match self.command.update_password(&update_req).await {
             Ok(user) => {
                 info!("✅ Password updated for user: {}", user.email);
                 self.complete_tracing_success(
@@ -545,6 +708,76 @@ impl UserCommandServiceTrait for UserCommandService {
         Ok(response)
     }
 
+    async fn anonymize_user(&self, id: i32) -> Result<ApiResponse<UserResponse>, ServiceError> {
+        info!("🕶️ Anonymizing user with ID: {}", id);
+
+        let method = Method::Post;
+        let tracing_ctx = self.start_tracing(
+            "AnonymizeUser",
+            vec![
+                KeyValue::new("component", "user"),
+                KeyValue::new("operation", "anonymize"),
+                KeyValue::new("user.id", id.to_string()),
+            ],
+        );
+
+        let user_model = match self.command.anonymize_user(id).await {
+            Ok(user) => {
+                self.complete_tracing_success(&tracing_ctx, method.clone(), "User anonymized")
+                    .await;
+                user
+            }
+            Err(e) => {
+                error!("❌ Failed to anonymize user ID {id}: {e:?}");
+                self.complete_tracing_error(&tracing_ctx, method.clone(), &e.to_string())
+                    .await;
+                return Err(ServiceError::Repo(e));
+            }
+        };
+
+        let response = UserResponse::from(user_model);
+
+        // Cached reads still hold the old email and names. The email and
+        // verification code keys embed values that are gone now, so those
+        // queries are dropped for every user.
+        self.cache_store
+            .invalidate_record("user", response.id, Some(&response.external_id))
+            .await;
+        for prefix in [
+            "user:find_by_email:",
+            "user:find_by_email_and_verify:",
+            "user:find_by_verification_code:",
+        ] {
+            self.cache_store.delete_by_prefix(prefix).await;
+        }
+
+        let event = UserEvent::Anonymized {
+            user_id: response.id,
+            external_id: response.external_id.clone(),
+        };
+
+        match serde_json::to_vec(&event) {
+            Ok(payload) => {
+                if let Err(e) = self
+                    .kafka
+                    .publish(event.topic(), &response.id.to_string(), &payload)
+                    .await
+                {
+                    error!("❌ Failed to publish event: {e:?}");
+                }
+            }
+            Err(e) => error!("❌ Failed to encode user event: {e:?}"),
+        }
+
+        info!("✅ User anonymized: ID {id}");
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "User anonymized successfully".to_string(),
+            data: response,
+        })
+    }
+
     async fn trash_user(&self, id: i32) -> Result<ApiResponse<UserResponseDeleteAt>, ServiceError> {
         info!("🗑️ Soft deleting user with ID: {}", id);
 
@@ -560,6 +793,7 @@ impl UserCommandServiceTrait for UserCommandService {
 
         let user_model = match self.command.trash_user(id).await {
             Ok(user) => {
+                self.lifecycle.soft_deleted(LifecycleScope::Single);
                 info!("✅ User moved to trash: {}", user.email);
                 self.complete_tracing_success(&tracing_ctx, method.clone(), "User moved to trash")
                     .await;
@@ -604,6 +838,7 @@ impl UserCommandServiceTrait for UserCommandService {
 
         let user_model = match self.command.restore_user(id).await {
             Ok(user) => {
+                self.lifecycle.restored(LifecycleScope::Single);
                 self.complete_tracing_success(&tracing_ctx, method, "User restored")
                     .await;
                 user
@@ -644,6 +879,7 @@ impl UserCommandServiceTrait for UserCommandService {
 
         match self.command.delete_user(id).await {
             Ok(_) => {
+                self.lifecycle.purged(LifecycleScope::Single);
                 info!("✅ User permanently deleted: {id}");
                 self.complete_tracing_success(&tracing_ctx, method, "User deleted permanently")
                     .await;
@@ -680,6 +916,7 @@ impl UserCommandServiceTrait for UserCommandService {
 
         match self.command.restore_all_user().await {
             Ok(_) => {
+                self.lifecycle.restored(LifecycleScope::All);
                 self.complete_tracing_success(&tracing_ctx, method, "All users restored")
                     .await;
             }
@@ -716,6 +953,7 @@ impl UserCommandServiceTrait for UserCommandService {
 
         match self.command.delete_all_user().await {
             Ok(_) => {
+                self.lifecycle.purged(LifecycleScope::All);
                 self.complete_tracing_success(
                     &tracing_ctx,
                     method,
@@ -742,3 +980,133 @@ impl UserCommandServiceTrait for UserCommandService {
         Ok(response)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{UserCommandService, UserCommandServiceDeps};
+    use crate::{
+        abstract_trait::{
+            grpc_client::{role::RoleGrpcClientTrait, user_role::UserRoleGrpcClientTrait},
+            user::service::UserCommandServiceTrait,
+        },
+        domain::{
+            requests::{user::UpdateUserPasswordRequest, user_role::UserRoleRequest},
+            response::{api::ApiResponse, role::RoleResponse, user_role::UserRoleResponse},
+        },
+        repository::{command::UserCommandRepository, query::UserQueryRepository},
+    };
+    use async_trait::async_trait;
+    use shared::{
+        abstract_trait::{HashingTrait, KafkaTrait},
+        cache::CacheStore,
+        config::{Hashing, RedisConfig, RedisPool},
+        errors::{AppErrorGrpc, ServiceError},
+    };
+    use sqlx::PgPool;
+    use std::{collections::HashMap, sync::Arc};
+
+    /// Stands in for the role services and Kafka, none of which a password
+    /// change talks to.
+    struct Unused;
+
+    #[async_trait]
+    impl RoleGrpcClientTrait for Unused {
+        async fn find_by_name(
+            &self,
+            _name: &str,
+        ) -> Result<ApiResponse<RoleResponse>, AppErrorGrpc> {
+            unimplemented!()
+        }
+
+        async fn find_role_names_by_user_ids(
+            &self,
+            _user_ids: &[i32],
+        ) -> Result<HashMap<i32, Vec<String>>, AppErrorGrpc> {
+            unimplemented!()
+        }
+    }
+
+    #[async_trait]
+    impl UserRoleGrpcClientTrait for Unused {
+        async fn assign_role(
+            &self,
+            _req: UserRoleRequest,
+        ) -> Result<ApiResponse<UserRoleResponse>, AppErrorGrpc> {
+            unimplemented!()
+        }
+
+        async fn update_user_role(
+            &self,
+            _req: UserRoleRequest,
+        ) -> Result<ApiResponse<UserRoleResponse>, AppErrorGrpc> {
+            unimplemented!()
+        }
+    }
+
+    #[async_trait]
+    impl KafkaTrait for Unused {
+        async fn publish(
+            &self,
+            _topic: &str,
+            _key: &str,
+            _value: &[u8],
+        ) -> Result<(), ServiceError> {
+            unimplemented!()
+        }
+
+        async fn subscribe(&self, _topics: Vec<&str>, _group_id: &str) -> Result<(), ServiceError> {
+            unimplemented!()
+        }
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "needs a Postgres server at DATABASE_URL"]
+    async fn only_the_last_n_passwords_are_refused(pool: PgPool) {
+        let hashing = Arc::new(Hashing::new());
+        let first_hash = hashing.hash_password("first-pass").await.unwrap();
+        sqlx::query(
+            "INSERT INTO users (user_id, firstname, lastname, email, password, verification_code)
+             VALUES (1, 'Ada', 'Lovelace', 'ada@example.com', $1, '')",
+        )
+        .bind(first_hash)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let service = UserCommandService::new(UserCommandServiceDeps {
+            role_client: Arc::new(Unused),
+            user_role_client: Arc::new(Unused),
+            hash: hashing,
+            query: Arc::new(UserQueryRepository::new(pool.clone())),
+            command: Arc::new(UserCommandRepository::new(pool)),
+            kafka: Arc::new(Unused),
+            cache_store: Arc::new(CacheStore::new(
+                RedisPool::new(&RedisConfig::new()).unwrap().pool,
+            )),
+            password_history_size: 2,
+        })
+        .unwrap();
+
+        let change = async |password: &str| {
+            service
+                .update_user_password(&UpdateUserPasswordRequest {
+                    user_id: 1,
+                    password: password.to_string(),
+                })
+                .await
+        };
+
+        change("second-pass").await.unwrap();
+        assert!(matches!(
+            change("first-pass").await,
+            Err(ServiceError::Custom(message)) if message.contains("last 2 passwords")
+        ));
+
+        // Now the current and the previous password are the last two, so the
+        // first one has aged out.
+        change("third-pass").await.unwrap();
+        assert!(change("third-pass").await.is_err());
+        assert!(change("second-pass").await.is_err());
+        change("first-pass").await.unwrap();
+    }
+}
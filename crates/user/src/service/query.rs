@@ -1,5 +1,8 @@
 use crate::{
-    abstract_trait::user::{repository::DynUserQueryRepository, service::UserQueryServiceTrait},
+    abstract_trait::{
+        grpc_client::role::DynRoleGrpcClient,
+        user::{repository::DynUserQueryRepository, service::UserQueryServiceTrait},
+    },
     domain::{
         requests::user::FindAllUsers,
         response::{
@@ -20,28 +23,40 @@ use opentelemetry::{
 use shared::{
     cache::CacheStore,
     errors::ServiceError,
-    utils::{MetadataInjector, Method, Metrics, Status as StatusUtils, TracingContext},
+    utils::{
+        Cursor, CursorCodec, MetadataInjector, Method, Metrics, Status as StatusUtils,
+        TracingContext, page_count,
+    },
 };
 use std::sync::Arc;
 use tokio::time::Instant;
 use tonic::Request;
 use tracing::{error, info};
+use uuid::Uuid;
 
 #[derive(Clone)]
 pub struct UserQueryService {
     pub query: DynUserQueryRepository,
+    pub role_client: DynRoleGrpcClient,
     pub metrics: Metrics,
     pub cache_store: Arc<CacheStore>,
+    pub cursor_codec: CursorCodec,
 }
 
 impl UserQueryService {
-    pub fn new(query: DynUserQueryRepository, cache_store: Arc<CacheStore>) -> Result<Self> {
+    pub fn new(
+        query: DynUserQueryRepository,
+        role_client: DynRoleGrpcClient,
+        cache_store: Arc<CacheStore>,
+    ) -> Result<Self> {
         let metrics = Metrics::new(global::meter("user-query-service"));
 
         Ok(Self {
             query,
+            role_client,
             metrics,
             cache_store,
+            cursor_codec: CursorCodec::from_env(),
         })
     }
     fn get_tracer(&self) -> BoxedTracer {
@@ -144,6 +159,12 @@ impl UserQueryServiceTrait for UserQueryService {
             req.page, req.page_size, req.search
         );
 
+        let after = if req.after.is_empty() {
+            None
+        } else {
+            Some(self.cursor_codec.decode(&req.after)?)
+        };
+
         let page = if req.page > 0 { req.page } else { 1 };
         let page_size = if req.page_size > 0 { req.page_size } else { 10 };
         let search = if req.search.is_empty() {
@@ -167,8 +188,10 @@ impl UserQueryServiceTrait for UserQueryService {
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
         let cache_key = format!(
-            "user:find_all:page:{page}:size:{page_size}:search:{}",
-            search.unwrap_or_default()
+            "user:find_all:page:{page}:size:{page_size}:search:{}:roles:{}:after:{}",
+            search.unwrap_or_default(),
+            req.include_roles,
+            req.after
         );
 
         if let Some(cache) = self
@@ -183,7 +206,7 @@ impl UserQueryServiceTrait for UserQueryService {
             return Ok(cache);
         }
 
-        let (users, total) = match self.query.find_all(req).await {
+        let (users, total) = match self.query.find_all(req, after).await {
             Ok(res) => {
                 let log_msg = format!("✅ Found {} users", res.0.len());
                 info!("{log_msg}");
@@ -203,15 +226,44 @@ impl UserQueryServiceTrait for UserQueryService {
             }
         };
 
-        let user_response: Vec<UserResponse> = users.into_iter().map(UserResponse::from).collect();
-
-        let total_pages = ((total - 1) / req.page_size as i64) + 1;
+        let next_cursor = users
+            .last()
+            .filter(|_| users.len() as i32 == req.page_size)
+            .and_then(|user| {
+                user.created_at.map(|created_at| {
+                    self.cursor_codec.encode(&Cursor {
+                        created_at,
+                        id: user.user_id,
+                    })
+                })
+            });
+
+        let mut user_response: Vec<UserResponse> =
+            users.into_iter().map(UserResponse::from).collect();
+
+        if req.include_roles && !user_response.is_empty() {
+            let user_ids: Vec<i32> = user_response.iter().map(|u| u.id).collect();
+
+            let mut roles_by_user = self
+                .role_client
+                .find_role_names_by_user_ids(&user_ids)
+                .await
+                .map_err(|e| {
+                    error!("❌ Failed to fetch roles for users: {e:?}");
+                    ServiceError::Internal("Role service unavailable".into())
+                })?;
+
+            for user in user_response.iter_mut() {
+                user.roles = Some(roles_by_user.remove(&user.id).unwrap_or_default());
+            }
+        }
 
         let pagination = Pagination {
             page: req.page,
             page_size: req.page_size,
             total_items: total as i32,
-            total_pages: total_pages as i32,
+            total_pages: page_count(total, req.page_size),
+            next_cursor,
         };
 
         let response = ApiResponsePagination {
@@ -301,13 +353,12 @@ impl UserQueryServiceTrait for UserQueryService {
         let user_response: Vec<UserResponseDeleteAt> =
             users.into_iter().map(UserResponseDeleteAt::from).collect();
 
-        let total_pages = ((total - 1) / req.page_size as i64) + 1;
-
         let pagination = Pagination {
             page: req.page,
             page_size: req.page_size,
             total_items: total as i32,
-            total_pages: total_pages as i32,
+            total_pages: page_count(total, req.page_size),
+            next_cursor: None,
         };
 
         let response = ApiResponsePagination {
@@ -400,13 +451,12 @@ impl UserQueryServiceTrait for UserQueryService {
         let user_response: Vec<UserResponseDeleteAt> =
             users.into_iter().map(UserResponseDeleteAt::from).collect();
 
-        let total_pages = ((total - 1) / req.page_size as i64) + 1;
-
         let pagination = Pagination {
             page: req.page,
             page_size: req.page_size,
             total_items: total as i32,
-            total_pages: total_pages as i32,
+            total_pages: page_count(total, req.page_size),
+            next_cursor: None,
         };
 
         let response = ApiResponsePagination {
@@ -486,6 +536,74 @@ impl UserQueryServiceTrait for UserQueryService {
         Ok(response)
     }
 
+    async fn find_by_external_id(
+        &self,
+        external_id: Uuid,
+    ) -> Result<ApiResponse<UserResponse>, ServiceError> {
+        info!("🆔 Finding user by external ID: {external_id}");
+
+        let method = Method::Get;
+        let tracing_ctx = self.start_tracing(
+            "user_find_by_external_id",
+            vec![
+                KeyValue::new("component", "user"),
+                KeyValue::new("operation", "find_by_external_id"),
+                KeyValue::new("user.external_id", external_id.to_string()),
+            ],
+        );
+
+        let mut request = Request::new(external_id);
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let cache_key = format!("user:find_by_external_id:{external_id}");
+
+        if let Some(cached) = self
+            .cache_store
+            .get_from_cache::<ApiResponse<UserResponse>>(&cache_key)
+            .await
+        {
+            info!("✅ Found user {external_id} in cache");
+            self.complete_tracing_success(&tracing_ctx, method, "User retrieved from cache")
+                .await;
+            return Ok(cached);
+        }
+
+        let user = match self.query.find_by_external_id(external_id).await {
+            Ok(Some(user)) => {
+                self.complete_tracing_success(
+                    &tracing_ctx,
+                    method.clone(),
+                    "User retrieved from DB",
+                )
+                .await;
+                user
+            }
+            Ok(None) => {
+                error!("❌ User not found with external ID: {external_id}");
+                self.complete_tracing_error(&tracing_ctx, method.clone(), "User not found")
+                    .await;
+                return Err(ServiceError::Custom("User not found".to_string()));
+            }
+            Err(e) => {
+                error!("❌ Database error while finding user {external_id}: {e:?}");
+                self.complete_tracing_error(&tracing_ctx, method.clone(), "Database error")
+                    .await;
+                return Err(ServiceError::Repo(e));
+            }
+        };
+
+        let response = ApiResponse {
+            status: "success".to_string(),
+            message: "User retrieved successfully".to_string(),
+            data: UserResponse::from(user),
+        };
+
+        self.cache_store
+            .set_to_cache(&cache_key, &response, Duration::minutes(5))
+            .await;
+
+        Ok(response)
+    }
     async fn find_by_email(
         &self,
         email: String,
@@ -574,7 +692,7 @@ impl UserQueryServiceTrait for UserQueryService {
         let user = match self.query.find_by_email_and_verify(email.clone()).await {
             Ok(Some(user)) => user,
             Ok(None) => {
-                error!("❌ User not found or not verified: {email}");
+                error!("❌ User not found: {email}");
                 self.complete_tracing_error(&tracing_ctx, method.clone(), "User not found")
                     .await;
                 return Err(ServiceError::Custom("User not found or invalid".into()));
@@ -593,11 +711,18 @@ impl UserQueryServiceTrait for UserQueryService {
             data: UserResponseWithPassword::from(user),
         };
 
-        self.cache_store
-            .set_to_cache(&cache_key, &response.clone(), Duration::minutes(5))
-            .await;
+        // Unverified accounts are not cached, so a login right after verifying
+        // the email sees the updated flag.
+        if response.data.is_verified {
+            self.cache_store
+                .set_to_cache(&cache_key, &response.clone(), Duration::minutes(5))
+                .await;
+        }
 
-        info!("🔐 Verified user found: {}", response.data.email);
+        info!(
+            "🔐 User found for credential check: {}",
+            response.data.email
+        );
 
         Ok(response)
     }
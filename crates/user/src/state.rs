@@ -7,7 +7,7 @@ use anyhow::{Context, Result};
 use shared::config::RedisPool;
 use shared::{
     abstract_trait::{DynHashing, DynKafka},
-    config::{ConnectionPool, Hashing, Kafka, RedisConfig},
+    config::{DatabasePools, Hashing, Kafka, RedisConfig},
     utils::{SystemMetrics, run_metrics_collector},
 };
 use std::{fmt, sync::Arc};
@@ -17,6 +17,7 @@ pub struct AppState {
     pub di_container: DependenciesInject,
     pub kafka_config: DynKafka,
     pub system_metrics: Arc<SystemMetrics>,
+    pub db_pools: DatabasePools,
 }
 
 impl fmt::Debug for AppState {
@@ -29,10 +30,11 @@ impl fmt::Debug for AppState {
 }
 
 impl AppState {
-    pub async fn new(pool: ConnectionPool, config: Config) -> Result<Self> {
+    pub async fn new(pools: DatabasePools, config: Config) -> Result<Self> {
         let kafka_config = Arc::new(Kafka::new(&config.kafka_broker)) as DynKafka;
         let system_metrics = Arc::new(SystemMetrics::new());
         let hashing = Arc::new(Hashing::new()) as DynHashing;
+        let password_history_size = config.password_history_size;
 
         let config = RedisConfig::new();
 
@@ -41,9 +43,11 @@ impl AppState {
         redis.ping().await.context("Failed to ping Redis server")?;
 
         let deps = DependenciesInjectDeps {
-            pool: pool.clone(),
+            pools: pools.clone(),
             hash: hashing,
             redis: redis.clone(),
+            kafka: kafka_config.clone(),
+            password_history_size,
         };
 
         let grpc_config = GrpcClientConfig::init().context("failed config grpc")?;
@@ -61,6 +65,7 @@ impl AppState {
             di_container,
             system_metrics,
             kafka_config,
+            db_pools: pools,
         })
     }
 }
@@ -13,6 +13,11 @@ echo "Kafka is ready! Creating topics..."
 /opt/kafka/bin/kafka-topics.sh --create --topic order.created --bootstrap-server kafka:9092 --partitions 3 --replication-factor 1 --if-not-exists
 /opt/kafka/bin/kafka-topics.sh --create --topic order.updated --bootstrap-server kafka:9092 --partitions 3 --replication-factor 1 --if-not-exists
 /opt/kafka/bin/kafka-topics.sh --create --topic order.deleted --bootstrap-server kafka:9092 --partitions 3 --replication-factor 1 --if-not-exists
+/opt/kafka/bin/kafka-topics.sh --create --topic order.status_changed --bootstrap-server kafka:9092 --partitions 3 --replication-factor 1 --if-not-exists
+
+# Create product topics
+/opt/kafka/bin/kafka-topics.sh --create --topic product.out_of_stock --bootstrap-server kafka:9092 --partitions 3 --replication-factor 1 --if-not-exists
+/opt/kafka/bin/kafka-topics.sh --create --topic product.back_in_stock --bootstrap-server kafka:9092 --partitions 3 --replication-factor 1 --if-not-exists
 
 # Create email service topics (existing ones)
 /opt/kafka/bin/kafka-topics.sh --create --topic email-service-topic-auth-forgot-password --bootstrap-server kafka:9092 --partitions 3 --replication-factor 1 --if-not-exists